@@ -120,7 +120,7 @@ export function VectorConfigDetailPage() {
         };
       case 'qdrant':
         return {
-          apiUrl: 'http://localhost:6333',
+          apiUrl: 'http://localhost:6334',
           dimension: 1536,
         };
       case 'milvus':
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::value_objects::LLMSelectionStrategy;
+
 /// Create Agent request DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateAgentDto {
@@ -9,15 +11,25 @@ pub struct CreateAgentDto {
     pub avatar: Option<String>,
     pub greeting: Option<String>,
     pub llm_config_id: Option<Uuid>,
+    #[serde(default)]
+    pub llm_fallback_strategy: Option<LLMSelectionStrategy>,
     pub system_prompt: String,
     pub additional_settings: Option<String>,
     pub preset_questions: Vec<String>,
+    /// Generate preset questions from the system prompt in the background
+    /// when none are given
+    #[serde(default = "default_generate_preset_questions")]
+    pub generate_preset_questions: bool,
     pub knowledge_base_ids: Vec<Uuid>,
     pub mcp_tool_ids: Vec<Uuid>,
     pub flow_ids: Vec<Uuid>,
     pub price: Option<rust_decimal::Decimal>,
 }
 
+fn default_generate_preset_questions() -> bool {
+    true
+}
+
 /// Update Agent request DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateAgentDto {
@@ -25,6 +37,8 @@ pub struct UpdateAgentDto {
     pub avatar: Option<String>,
     pub greeting: Option<String>,
     pub llm_config_id: Option<Uuid>,
+    #[serde(default)]
+    pub llm_fallback_strategy: Option<LLMSelectionStrategy>,
     pub system_prompt: Option<String>,
     pub additional_settings: Option<String>,
     pub preset_questions: Option<Vec<String>>,
@@ -40,6 +54,7 @@ pub struct AgentDto {
     pub avatar: Option<String>,
     pub greeting: Option<String>,
     pub llm_config_id: Option<Uuid>,
+    pub llm_fallback_strategy: LLMSelectionStrategy,
     pub system_prompt: String,
     pub additional_settings: Option<String>,
     pub preset_questions: Vec<String>,
@@ -83,6 +98,7 @@ pub struct AgentDetailDto {
     pub avatar: Option<String>,
     pub greeting: Option<String>,
     pub llm_config_id: Option<Uuid>,
+    pub llm_fallback_strategy: LLMSelectionStrategy,
     pub knowledge_bases: Vec<VectorConfigSummaryDto>,
     pub mcp_tools: Vec<MCPToolSummaryDto>,
     pub flows: Vec<FlowSummaryDto>,
@@ -119,6 +135,19 @@ pub struct UserSummaryDto {
     pub nickname: Option<String>,
 }
 
+/// Copied or employed instance of an agent, as seen by the agent's creator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentCopyDto {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub creator: UserSummaryDto,
+    pub employer: Option<UserSummaryDto>,
+    pub is_fired: bool,
+    pub fired_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Vector config summary DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VectorConfigSummaryDto {
@@ -201,6 +230,13 @@ impl PaginationParams {
     }
 }
 
+/// Allocate agent request DTO
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AllocateAgentDto {
+    /// The allocation lapses at this time; never if omitted
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
 /// Add resource request DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddResourceDto {
@@ -222,6 +258,8 @@ pub struct AgentListQuery {
     pub allocated_only: Option<bool>,
     pub include_fired: Option<bool>,
     pub search: Option<String>,
+    /// Full-text match on agent name and system prompt
+    pub keyword: Option<String>,
 }
 
 impl Default for AgentListQuery {
@@ -233,10 +271,26 @@ impl Default for AgentListQuery {
             allocated_only: None,
             include_fired: None,
             search: None,
+            keyword: None,
         }
     }
 }
 
+/// Unpublish query parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnpublishAgentQuery {
+    /// Also fire every active employed copy of the agent
+    #[serde(default)]
+    pub fire_copies: bool,
+}
+
+/// Export query parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportAgentQuery {
+    /// `openagents` (default) or `zip`
+    pub format: Option<String>,
+}
+
 /// Agent chat request DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentChatRequest {
@@ -245,12 +299,21 @@ pub struct AgentChatRequest {
     pub stream: Option<bool>,
 }
 
+/// Agent preview request DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentPreviewRequest {
+    pub message: String,
+}
+
 /// Agent chat stream chunk DTO (for SSE)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentChatStreamChunk {
+    /// `content`, `reasoning`, `done` or `error`
     #[serde(rename = "type")]
     pub chunk_type: String,
+    /// Part of the answer
     pub content: Option<String>,
+    /// Part of the model's thinking, sent on `reasoning` chunks ahead of the answer
     pub reasoning_content: Option<String>,
     pub session_id: Option<Uuid>,
     pub message_id: Option<Uuid>,
@@ -263,7 +326,9 @@ pub struct AgentChatStreamChunk {
 /// Agent chat response DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentChatResponse {
-    pub session_id: Uuid,
+    /// Absent for previews, which are not kept in a session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<Uuid>,
     pub message_id: Uuid,
     pub reply_id: Uuid,
     pub reply: String,
@@ -346,3 +411,84 @@ pub struct InterviewRecordDto {
     pub created_at: String,
     pub updated_at: String,
 }
+
+/// Generated interview questions DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterviewQuestionsDto {
+    pub agent_id: Uuid,
+    pub questions: Vec<String>,
+}
+
+/// Interview turn request DTO. The answer is ignored on the opening turn,
+/// which only asks the first question.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InterviewTurnRequest {
+    #[serde(default)]
+    pub answer: String,
+}
+
+/// Evaluation of one interview answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerEvaluationDto {
+    /// 0 to 10
+    pub score: i32,
+    pub feedback: String,
+}
+
+/// Interview turn response DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterviewTurnDto {
+    pub record_id: Uuid,
+    /// Absent on the opening turn
+    pub evaluation: Option<AnswerEvaluationDto>,
+    /// Absent once every question is answered
+    pub next_question: Option<String>,
+    pub completed: bool,
+    pub status: String,
+    /// Overall score out of 100, set once completed
+    pub score: Option<i32>,
+    pub feedback: Option<String>,
+}
+
+/// Generate flow from natural language description request DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateFlowRequest {
+    pub description: String,
+}
+
+/// Generated flow response DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedFlowDto {
+    pub flow_id: Uuid,
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub current_version: i32,
+    pub status: String,
+    pub definition: serde_json::Value,
+    /// How cleanly the LLM output parsed, from 0.0 to 1.0
+    pub confidence_score: f32,
+    pub created_by: Uuid,
+    pub created_at: String,
+}
+
+/// Preset questions of an agent, shown as conversation starters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetQuestionsDto {
+    pub agent_id: Uuid,
+    pub questions: Vec<String>,
+}
+
+/// Suggest follow-up questions request DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestQuestionsRequest {
+    pub session_id: Uuid,
+}
+
+/// Follow-up questions generated for a session. Never persisted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestedQuestionsDto {
+    pub agent_id: Uuid,
+    pub session_id: Uuid,
+    pub questions: Vec<String>,
+}
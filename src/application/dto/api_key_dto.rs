@@ -1,8 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::entities::APIKey;
+use crate::domain::entities::{APIKey, APIKeyDailyUsage};
 use crate::domain::value_objects::{APIKeyToken, PermissionScope};
 
 /// DTO for permission scope
@@ -68,6 +68,9 @@ pub struct CreateAPIKeyRequest {
     pub name: String,
     pub permission_scope: PermissionScopeDTO,
     pub expires_at: Option<DateTime<Utc>>,
+    /// Requests allowed per minute; unlimited when absent
+    #[serde(default)]
+    pub rate_limit_rpm: Option<u32>,
 }
 
 /// Response DTO for creating an API key (includes token)
@@ -79,6 +82,7 @@ pub struct CreateAPIKeyResponse {
     pub permission_scope: PermissionScopeDTO,
     pub enabled: bool,
     pub expires_at: Option<DateTime<Utc>>,
+    pub rate_limit_rpm: Option<u32>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -92,11 +96,21 @@ impl CreateAPIKeyResponse {
             permission_scope: (&api_key.permission_scope).into(),
             enabled: api_key.enabled,
             expires_at: api_key.expires_at,
+            rate_limit_rpm: api_key.rate_limit_rpm,
             created_at: api_key.created_at,
         }
     }
 }
 
+/// Response DTO for rotating an API key (includes the new token)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotatedAPIKeyDTO {
+    pub id: Uuid,
+    pub name: String,
+    pub new_token: String,
+    pub rotated_at: DateTime<Utc>,
+}
+
 /// DTO for API key details (without token)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct APIKeyDTO {
@@ -106,6 +120,9 @@ pub struct APIKeyDTO {
     pub enabled: bool,
     pub expires_at: Option<DateTime<Utc>>,
     pub last_used_at: Option<DateTime<Utc>>,
+    pub request_count: u64,
+    pub total_tokens_used: u64,
+    pub rate_limit_rpm: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -119,6 +136,9 @@ impl From<APIKey> for APIKeyDTO {
             enabled: api_key.enabled,
             expires_at: api_key.expires_at,
             last_used_at: api_key.last_used_at,
+            request_count: api_key.request_count,
+            total_tokens_used: api_key.total_tokens_used,
+            rate_limit_rpm: api_key.rate_limit_rpm,
             created_at: api_key.created_at,
             updated_at: api_key.updated_at,
         }
@@ -134,6 +154,9 @@ impl From<&APIKey> for APIKeyDTO {
             enabled: api_key.enabled,
             expires_at: api_key.expires_at,
             last_used_at: api_key.last_used_at,
+            request_count: api_key.request_count,
+            total_tokens_used: api_key.total_tokens_used,
+            rate_limit_rpm: api_key.rate_limit_rpm,
             created_at: api_key.created_at,
             updated_at: api_key.updated_at,
         }
@@ -146,6 +169,9 @@ pub struct UpdateAPIKeyRequest {
     pub name: Option<String>,
     pub enabled: Option<bool>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// New per-minute request limit; 0 removes the limit
+    #[serde(default)]
+    pub rate_limit_rpm: Option<u32>,
 }
 
 /// Auth context for API key authentication
@@ -155,6 +181,7 @@ pub struct APIKeyAuthContext {
     pub tenant_id: Uuid,
     pub user_id: Uuid,
     pub permission_scope: PermissionScopeDTO,
+    pub rate_limit_rpm: Option<u32>,
 }
 
 impl From<APIKey> for APIKeyAuthContext {
@@ -164,6 +191,7 @@ impl From<APIKey> for APIKeyAuthContext {
             tenant_id: api_key.tenant_id.0,
             user_id: api_key.user_id.0,
             permission_scope: api_key.permission_scope.into(),
+            rate_limit_rpm: api_key.rate_limit_rpm,
         }
     }
 }
@@ -175,10 +203,16 @@ impl From<&APIKey> for APIKeyAuthContext {
             tenant_id: api_key.tenant_id.0,
             user_id: api_key.user_id.0,
             permission_scope: (&api_key.permission_scope).into(),
+            rate_limit_rpm: api_key.rate_limit_rpm,
         }
     }
 }
 
+/// Response extension a handler sets with the LLM tokens its request
+/// consumed, counted against the calling API key
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct APIKeyTokenUsage(pub u64);
+
 /// Query result for listing API keys
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct APIKeyListResponse {
@@ -188,6 +222,31 @@ pub struct APIKeyListResponse {
     pub limit: u64,
 }
 
+/// Requests and tokens of an API key on one UTC day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct APIKeyDailyUsageDTO {
+    pub date: NaiveDate,
+    pub request_count: u64,
+    pub tokens_used: u64,
+}
+
+impl From<APIKeyDailyUsage> for APIKeyDailyUsageDTO {
+    fn from(usage: APIKeyDailyUsage) -> Self {
+        Self {
+            date: usage.date,
+            request_count: usage.request_count,
+            tokens_used: usage.tokens_used,
+        }
+    }
+}
+
+/// Daily usage of an API key, one entry per day including days without usage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct APIKeyUsageHistoryResponse {
+    pub api_key_id: Uuid,
+    pub days: Vec<APIKeyDailyUsageDTO>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::domain::entities::{AuditAction, AuditContext, AuditLog, ResourceType};
+
 /// Request to query audit logs
 #[derive(Debug, Clone, Deserialize)]
 pub struct QueryAuditLogsRequest {
@@ -41,6 +43,138 @@ pub struct AuditLogDto {
     pub created_at: DateTime<Utc>,
 }
 
+impl From<&AuditLog> for AuditLogDto {
+    fn from(log: &AuditLog) -> Self {
+        Self {
+            id: log.id,
+            tenant_id: log.tenant_id,
+            user_id: log.user_id,
+            action: log.action.as_str().to_string(),
+            resource_type: log.resource_type.as_str().to_string(),
+            resource_id: log.resource_id,
+            details: log.details.clone(),
+            ip_address: log.ip_address.clone(),
+            user_agent: log.user_agent.clone(),
+            created_at: log.created_at,
+        }
+    }
+}
+
+/// Audit event as returned by `search_events`
+pub type AuditEventDto = AuditLogDto;
+
+/// Structured audit event recorded by mutating operations
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    pub tenant_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub resource_type: ResourceType,
+    pub resource_id: Option<Uuid>,
+    pub details: Option<Value>,
+    pub context: Option<AuditContext>,
+}
+
+impl AuditEvent {
+    pub fn new(tenant_id: Uuid, action: AuditAction, resource_type: ResourceType) -> Self {
+        Self {
+            tenant_id,
+            user_id: None,
+            action,
+            resource_type,
+            resource_id: None,
+            details: None,
+            context: None,
+        }
+    }
+
+    pub fn with_user_id(mut self, user_id: Uuid) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn with_resource_id(mut self, resource_id: Uuid) -> Self {
+        self.resource_id = Some(resource_id);
+        self
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn with_context(mut self, context: Option<AuditContext>) -> Self {
+        self.context = context;
+        self
+    }
+}
+
+/// Build an event from the audit entry of a domain event
+impl From<AuditLog> for AuditEvent {
+    fn from(log: AuditLog) -> Self {
+        let context = if log.ip_address.is_some() || log.user_agent.is_some() {
+            Some(AuditContext {
+                ip_address: log.ip_address,
+                user_agent: log.user_agent,
+            })
+        } else {
+            None
+        };
+
+        Self {
+            tenant_id: log.tenant_id,
+            user_id: log.user_id,
+            action: log.action,
+            resource_type: log.resource_type,
+            resource_id: log.resource_id,
+            details: log.details,
+            context,
+        }
+    }
+}
+
+/// Audit log search criteria. `page` is 1-based.
+#[derive(Debug, Clone)]
+pub struct AuditQuery {
+    pub tenant_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub resource_type: Option<ResourceType>,
+    pub action: Option<AuditAction>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub page: u64,
+    pub limit: u64,
+}
+
+impl AuditQuery {
+    pub fn new(tenant_id: Uuid) -> Self {
+        Self {
+            tenant_id,
+            user_id: None,
+            resource_type: None,
+            action: None,
+            start_time: None,
+            end_time: None,
+            page: 1,
+            limit: 50,
+        }
+    }
+}
+
+/// Query parameters of `GET /audit-logs`
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchAuditLogsRequest {
+    /// Defaults to the caller's tenant
+    pub tenant_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub resource_type: Option<String>,
+    pub action: Option<String>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
 /// Request to get audit statistics
 #[derive(Debug, Clone, Deserialize)]
 pub struct GetAuditStatisticsRequest {
@@ -16,6 +16,9 @@ pub struct LoginResponse {
     pub token: String,
     pub user: UserInfo,
     pub expires_at: DateTime<Utc>,
+    /// Present when refresh tokens are enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<RefreshTokenDto>,
 }
 
 /// User information DTO
@@ -41,6 +44,52 @@ pub struct RefreshTokenResponse {
     pub expires_at: DateTime<Utc>,
 }
 
+/// Refresh token exchange request DTO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshAccessTokenRequest {
+    pub refresh_token: String,
+}
+
+/// Body of `POST /auth/refresh`, which accepts either a refresh token or,
+/// for older clients, a still valid access token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RefreshRequestBody {
+    RefreshToken(RefreshAccessTokenRequest),
+    AccessToken(RefreshTokenRequest),
+}
+
+/// Newly issued refresh token. The plaintext token is only ever returned here.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshTokenDto {
+    pub id: Uuid,
+    pub refresh_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Access token issued in exchange for a refresh token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenPairDto {
+    pub access_token: String,
+    pub expires_at: DateTime<Utc>,
+    /// Replacement refresh token, present when rotation is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<RefreshTokenDto>,
+}
+
+/// Revoke request DTO. Without a token ID all of the user's refresh tokens
+/// are revoked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RevokeTokenRequest {
+    pub token_id: Option<Uuid>,
+}
+
+/// Revoke response DTO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevokeTokenResponse {
+    pub revoked: u64,
+}
+
 /// Logout request DTO
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LogoutRequest {
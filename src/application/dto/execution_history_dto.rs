@@ -3,6 +3,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::domain::entities::{
+    ExecutionMetrics, ExecutionStatus, ExecutionStep, FlowExecutionHistory,
+};
+
 /// Request to query execution history
 #[derive(Debug, Clone, Deserialize)]
 pub struct QueryExecutionsRequest {
@@ -25,6 +29,80 @@ pub struct QueryExecutionsResponse {
     pub total_pages: u64,
 }
 
+/// Execution status values accepted by the list filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionStatusFilter {
+    Success,
+    Failed,
+    Running,
+}
+
+impl ExecutionStatusFilter {
+    pub fn to_status(self) -> ExecutionStatus {
+        match self {
+            ExecutionStatusFilter::Success => ExecutionStatus::Completed,
+            ExecutionStatusFilter::Failed => ExecutionStatus::Failed,
+            ExecutionStatusFilter::Running => ExecutionStatus::Running,
+        }
+    }
+}
+
+/// Filter for listing executions page by page. `cursor` is the `next_cursor`
+/// of the previous page.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExecutionListFilter {
+    pub flow_id: Option<Uuid>,
+    /// Executions of any flow attached to the agent
+    pub agent_id: Option<Uuid>,
+    pub status: Option<ExecutionStatusFilter>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+/// A page of results fetched by cursor rather than by page number
+#[derive(Debug, Clone, Serialize)]
+pub struct CursorPaginatedResponse<T> {
+    pub items: Vec<T>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// Execution row in a list, without input and output payloads
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionSummaryDto {
+    pub id: Uuid,
+    pub flow_id: Uuid,
+    pub flow_version: i32,
+    pub user_id: Uuid,
+    pub session_id: Option<Uuid>,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub execution_time_ms: Option<i32>,
+}
+
+impl From<FlowExecutionHistory> for ExecutionSummaryDto {
+    fn from(execution: FlowExecutionHistory) -> Self {
+        Self {
+            id: execution.id,
+            flow_id: execution.flow_id,
+            flow_version: execution.flow_version,
+            user_id: execution.user_id,
+            session_id: execution.session_id,
+            status: execution.status.as_str().to_string(),
+            error_message: execution.error_message,
+            started_at: execution.started_at,
+            completed_at: execution.completed_at,
+            execution_time_ms: execution.execution_time_ms,
+        }
+    }
+}
+
 /// Execution DTO
 #[derive(Debug, Clone, Serialize)]
 pub struct ExecutionDto {
@@ -43,6 +121,26 @@ pub struct ExecutionDto {
     pub execution_time_ms: Option<i32>,
 }
 
+impl From<FlowExecutionHistory> for ExecutionDto {
+    fn from(execution: FlowExecutionHistory) -> Self {
+        Self {
+            id: execution.id,
+            flow_id: execution.flow_id,
+            flow_version: execution.flow_version,
+            tenant_id: execution.tenant_id,
+            user_id: execution.user_id,
+            session_id: execution.session_id,
+            status: execution.status.as_str().to_string(),
+            input_data: execution.input_data,
+            output_data: execution.output_data,
+            error_message: execution.error_message,
+            started_at: execution.started_at,
+            completed_at: execution.completed_at,
+            execution_time_ms: execution.execution_time_ms,
+        }
+    }
+}
+
 /// Execution step DTO
 #[derive(Debug, Clone, Serialize)]
 pub struct ExecutionStepDto {
@@ -60,6 +158,25 @@ pub struct ExecutionStepDto {
     pub metadata: Option<Value>,
 }
 
+impl From<ExecutionStep> for ExecutionStepDto {
+    fn from(step: ExecutionStep) -> Self {
+        Self {
+            id: step.id,
+            execution_id: step.execution_id,
+            step_name: step.step_name,
+            step_type: step.step_type,
+            status: step.status.as_str().to_string(),
+            input_data: step.input_data,
+            output_data: step.output_data,
+            error_message: step.error_message,
+            started_at: step.started_at,
+            completed_at: step.completed_at,
+            execution_time_ms: step.execution_time_ms,
+            metadata: step.metadata,
+        }
+    }
+}
+
 /// Execution metrics DTO
 #[derive(Debug, Clone, Serialize)]
 pub struct ExecutionMetricsDto {
@@ -74,9 +191,25 @@ pub struct ExecutionMetricsDto {
     pub slowest_step_time_ms: Option<i32>,
 }
 
-/// Response for execution details
+impl From<ExecutionMetrics> for ExecutionMetricsDto {
+    fn from(metrics: ExecutionMetrics) -> Self {
+        Self {
+            execution_id: metrics.execution_id,
+            total_steps: metrics.total_steps,
+            completed_steps: metrics.completed_steps,
+            failed_steps: metrics.failed_steps,
+            skipped_steps: metrics.skipped_steps,
+            total_execution_time_ms: metrics.total_execution_time_ms,
+            average_step_time_ms: metrics.average_step_time_ms,
+            slowest_step: metrics.slowest_step,
+            slowest_step_time_ms: metrics.slowest_step_time_ms,
+        }
+    }
+}
+
+/// Execution with its node trace, in execution order
 #[derive(Debug, Clone, Serialize)]
-pub struct ExecutionDetailsResponse {
+pub struct ExecutionDetailDto {
     pub execution: ExecutionDto,
     pub steps: Vec<ExecutionStepDto>,
     pub metrics: ExecutionMetricsDto,
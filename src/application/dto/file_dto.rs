@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// File received in a multipart upload
+#[derive(Debug, Clone)]
+pub struct FileUpload {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Request for a presigned direct upload
+#[derive(Debug, Clone, Deserialize)]
+pub struct PresignedUploadRequest {
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub max_size_bytes: u64,
+}
+
+/// Form the client posts, with the file as the last field, to `upload_url`
+#[derive(Debug, Clone, Serialize)]
+pub struct PresignedUploadResponse {
+    pub upload_url: String,
+    /// Pass to `POST /files/confirm-upload` once the upload succeeded
+    pub key: String,
+    pub fields: HashMap<String, String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfirmUploadRequest {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfirmUploadResponse {
+    pub key: String,
+    pub url: String,
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::services::llm_service::{ConnectionTestResult, ModelInfo};
+
+/// Outcome of testing a saved LLM configuration against its provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestConnectionResultDto {
+    pub success: bool,
+    pub response_time_ms: u64,
+    pub model_info: Option<ModelInfo>,
+    pub error_message: Option<String>,
+}
+
+impl From<ConnectionTestResult> for TestConnectionResultDto {
+    fn from(result: ConnectionTestResult) -> Self {
+        Self {
+            success: result.success,
+            response_time_ms: result.response_time_ms,
+            model_info: result.model_info,
+            error_message: result.error_message,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::application::dto::agent_dto::AgentDto;
+
+/// Marketplace listing query parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketplaceAgentQuery {
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    pub keyword: Option<String>,
+    pub min_price: Option<Decimal>,
+    pub max_price: Option<Decimal>,
+}
+
+/// Query parameters of the cross-tenant marketplace search
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketplaceSearchQuery {
+    pub q: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+/// Public view of a published agent. Never carries the system prompt or
+/// the agent's knowledge base, tool and flow ids.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketplaceAgentDto {
+    pub id: Uuid,
+    pub name: String,
+    pub avatar: Option<String>,
+    pub greeting: Option<String>,
+    pub preset_questions: Vec<String>,
+    pub creator_name: String,
+    pub knowledge_base_count: usize,
+    pub mcp_tool_count: usize,
+    pub flow_count: usize,
+    pub price: Option<Decimal>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Purchase request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PurchaseAgentRequest {
+    pub payment_method: Option<String>,
+}
+
+/// Payment intent created for a purchase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentIntentDto {
+    pub id: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub status: String,
+}
+
+/// Purchase response with the payment intent and the newly employed agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseAgentResponse {
+    pub payment_intent: PaymentIntentDto,
+    pub agent: AgentDto,
+}
@@ -4,10 +4,20 @@ pub mod audit_dto;
 pub mod execution_history_dto;
 pub mod agent_dto;
 pub mod api_key_dto;
+pub mod marketplace_dto;
+pub mod file_dto;
+pub mod tenant_dto;
+pub mod user_dto;
+pub mod llm_config_dto;
 
 pub use auth_dto::*;
 pub use mcp_dto::*;
 pub use audit_dto::*;
 pub use execution_history_dto::*;
 pub use agent_dto::*;
-pub use api_key_dto::*;
\ No newline at end of file
+pub use api_key_dto::*;
+pub use marketplace_dto::*;
+pub use file_dto::*;
+pub use tenant_dto::*;
+pub use user_dto::*;
+pub use llm_config_dto::*;
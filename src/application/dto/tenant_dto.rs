@@ -0,0 +1,22 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::QuotaConfig;
+
+/// Current resource usage of a tenant next to its quota
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantUsageDto {
+    pub tenant_id: Uuid,
+    pub agents: u64,
+    pub flows: u64,
+    pub mcp_tools: u64,
+    /// Tokens used since `period_start`
+    pub monthly_tokens: u64,
+    /// Records in the tenant's default vector store; absent when the store
+    /// is not configured or cannot be reached
+    pub vector_records: Option<u64>,
+    /// First day of the current token accounting month
+    pub period_start: NaiveDate,
+    pub quota: QuotaConfig,
+}
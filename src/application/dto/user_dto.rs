@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::User;
+
+/// Profile of the signed-in user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserProfileDto {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub username: String,
+    pub nickname: Option<String>,
+    pub email: Option<String>,
+    pub language: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&User> for UserProfileDto {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id.0,
+            tenant_id: user.tenant_id.0,
+            username: user.username.0.clone(),
+            nickname: user.nickname.clone(),
+            email: user.email.clone(),
+            language: user.language.clone(),
+            avatar_url: user.avatar_url.clone(),
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+/// Profile changes; absent fields are left as they are and an empty string
+/// clears the field
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateProfileDto {
+    pub nickname: Option<String>,
+    pub email: Option<String>,
+    pub language: Option<String>,
+}
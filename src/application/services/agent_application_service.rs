@@ -1,19 +1,22 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 use sea_orm::PaginatorTrait;
 use rust_decimal::Decimal;
-use rust_decimal::prelude::FromPrimitive;
 use tokio::sync::Mutex;
 
 use crate::{
-    application::dto::agent_dto::*,
+    application::{dto::{agent_dto::*, AuditEvent}, services::TenantApplicationService},
     domain::{
-        entities::Agent,
+        entities::{Agent, AgentLimitsConfig, QuotaResource, User, MAX_PRESET_QUESTIONS},
+        events::{AgentChange, AgentChanged, DomainEvent, EventStore},
+        services::{ChatStreamBuffer, ChatStreamEvent},
         repositories::{
-            AgentAllocationRepository, AgentRepository, FlowRepository, MCPToolRepository,
-            UserRepository, VectorConfigRepository,
+            APIKeyRepository, AgentAllocationRepository, AgentRepository, FlowRepository,
+            LlmUsageLogRepository, MCPToolRepository, TenantSettingsRepository, UserRepository,
+            VectorConfigRepository,
         },
-        value_objects::{AgentId, ConfigId, FlowId, MCPToolId, TenantId, UserId},
+        value_objects::{APIKeyId, AgentId, ConfigId, FlowId, MCPToolId, TenantId, UserId},
     },
     error::{PlatformError, Result},
 };
@@ -32,6 +35,10 @@ pub trait AgentApplicationService: Send + Sync {
     /// Get agent by ID
     async fn get_agent(&self, id: AgentId, user_id: UserId) -> Result<AgentDetailDto>;
 
+    /// Agent of an API key scoped to exactly one agent, for clients whose
+    /// only credential is the key
+    async fn get_agent_by_api_key(&self, api_key_id: APIKeyId, user_id: UserId) -> Result<AgentDetailDto>;
+
     /// Update agent
     async fn update_agent(
         &self,
@@ -43,13 +50,15 @@ pub trait AgentApplicationService: Send + Sync {
     /// Delete agent
     async fn delete_agent(&self, id: AgentId, user_id: UserId) -> Result<()>;
 
-    /// List agents with pagination
+    /// List agents with pagination, narrowed to those matching `keyword`
+    /// when one is given
     async fn list_agents(
         &self,
         tenant_id: TenantId,
         user_id: UserId,
         params: PaginationParams,
         include_fired: bool,
+        keyword: Option<String>,
     ) -> Result<PaginatedResponse<AgentCardDto>>;
 
     /// List agents created by the user
@@ -73,6 +82,9 @@ pub trait AgentApplicationService: Send + Sync {
     /// Fire an agent (sets fired_at timestamp)
     async fn fire_agent(&self, agent_id: AgentId, user_id: UserId) -> Result<()>;
 
+    /// List the copied and employed instances of an agent (creator only)
+    async fn list_agent_copies(&self, agent_id: AgentId, user_id: UserId) -> Result<Vec<AgentCopyDto>>;
+
     /// List employed agents
     async fn list_employed_agents(
         &self,
@@ -81,8 +93,13 @@ pub trait AgentApplicationService: Send + Sync {
         include_fired: bool,
     ) -> Result<PaginatedResponse<AgentCardDto>>;
 
-    /// Allocate an agent
-    async fn allocate_agent(&self, agent_id: AgentId, user_id: UserId) -> Result<()>;
+    /// Allocate an agent, optionally until `dto.expires_at`
+    async fn allocate_agent(
+        &self,
+        agent_id: AgentId,
+        user_id: UserId,
+        dto: AllocateAgentDto,
+    ) -> Result<()>;
 
     /// Terminate allocation
     async fn terminate_allocation(&self, agent_id: AgentId, user_id: UserId) -> Result<()>;
@@ -142,6 +159,16 @@ pub trait AgentApplicationService: Send + Sync {
         tenant_id: TenantId,
     ) -> Result<crate::application::dto::agent_dto::AgentChatResponse>;
 
+    /// Chat with an agent without a session: nothing is stored and no usage
+    /// statistics are recorded, so creators can try out a configuration
+    async fn preview_chat(
+        &self,
+        agent_id: AgentId,
+        message: String,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<crate::application::dto::agent_dto::AgentChatResponse>;
+
     /// Chat with an agent (streaming)
     async fn chat_stream(
         &self,
@@ -152,6 +179,22 @@ pub trait AgentApplicationService: Send + Sync {
         tenant_id: TenantId,
     ) -> Result<Box<dyn futures::Stream<Item = Result<crate::application::dto::agent_dto::AgentChatStreamChunk>> + Send + Unpin>>;
 
+    /// Keep an event of a session's chat stream so the client can resume it
+    async fn buffer_chat_stream_event(
+        &self,
+        session_id: crate::domain::value_objects::SessionId,
+        event: ChatStreamEvent,
+    ) -> Result<()>;
+
+    /// Buffered events of the session's latest chat stream after `last_event_id`
+    async fn replay_chat_stream(
+        &self,
+        session_id: crate::domain::value_objects::SessionId,
+        last_event_id: u64,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<Vec<ChatStreamEvent>>;
+
     /// Get agent usage statistics
     async fn get_agent_usage_stats(
         &self,
@@ -180,8 +223,95 @@ pub trait AgentApplicationService: Send + Sync {
     /// Publish an agent
     async fn publish_agent(&self, agent_id: AgentId, user_id: UserId) -> Result<()>;
 
-    /// Unpublish an agent
-    async fn unpublish_agent(&self, agent_id: AgentId, user_id: UserId) -> Result<()>;
+    /// Unpublish an agent, optionally firing all of its active employed copies
+    async fn unpublish_agent(&self, agent_id: AgentId, user_id: UserId, fire_copies: bool) -> Result<()>;
+
+    /// Generate a starter flow from a natural language description using the tenant's LLM
+    async fn generate_flow_from_description(
+        &self,
+        description: String,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<GeneratedFlowDto>;
+
+    /// Get the preset questions of an agent the user works with
+    async fn get_preset_questions(
+        &self,
+        agent_id: AgentId,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<PresetQuestionsDto>;
+
+    /// Replace the preset questions of an agent the user can modify with
+    /// ones generated from its system prompt by the agent's LLM
+    async fn regenerate_preset_questions(
+        &self,
+        agent_id: AgentId,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<PresetQuestionsDto>;
+
+    /// Generate follow-up questions for a chat session with the agent's LLM
+    async fn suggest_questions(
+        &self,
+        agent_id: AgentId,
+        session_id: crate::domain::value_objects::SessionId,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<SuggestedQuestionsDto>;
+}
+
+/// Meta-prompt instructing the LLM to answer with a platform flow definition
+const FLOW_GENERATION_PROMPT: &str = r#"You are a workflow designer for an AI agent platform.
+Design a flow for the user's description and answer with a single JSON object and nothing else.
+
+The JSON object must have this shape:
+{
+  "name": "<short flow name>",
+  "workflow": {
+    "graph": {
+      "nodes": [
+        {"id": "<unique id>", "parentId": null, "node_type": "<node type>", "data": {...}, "position": {"x": 0, "y": 0}}
+      ],
+      "edges": [
+        {"id": "<unique id>", "source": "<node id>", "target": "<node id>", "sourceHandle": null, "targetHandle": null}
+      ]
+    }
+  }
+}
+
+Allowed node_type values: start, llm, vector_search, mcp_tool, condition, loop, variable, http_request, code, answer, parameter_extractor, iteration.
+Rules:
+- Include exactly one "start" node; its data.variables lists the flow inputs as {"variable": "<name>", "default": ""}.
+- Include at least one "answer" node; its data.answer references upstream output such as "{{#node_id.text#}}".
+- An "llm" node's data has "title" and "prompt_template": [{"role": "user", "text": "..."}], referencing inputs as "{{#start.<variable>#}}".
+- Every edge must reference existing node ids and every node id must be unique.
+- Lay nodes out left to right, 300 units apart on the x axis."#;
+
+/// Maximum number of follow-up questions returned by `suggest_questions`
+const MAX_SUGGESTED_QUESTIONS: usize = 5;
+
+/// Number of recent session messages given to the LLM when suggesting questions
+const SUGGESTION_HISTORY_MESSAGES: usize = 10;
+
+/// Most session messages replayed to the LLM on each chat turn
+const CHAT_HISTORY_MESSAGES: usize = 50;
+
+/// Prompt instructing the LLM to answer with follow-up questions
+const QUESTION_SUGGESTION_PROMPT: &str = r#"You suggest follow-up questions for a chat between a user and an AI assistant.
+Based on the conversation, propose short questions the user is likely to ask next, written from the user's point of view.
+Answer with a JSON object and nothing else: {"questions": ["<question>", ...]} with at most 5 questions."#;
+
+/// Prompt instructing the LLM to write preset questions for a new agent
+const PRESET_QUESTION_GENERATION_PROMPT: &str = r#"You write starter questions for the AI assistant described by the instructions below.
+Propose short questions a new user could ask to find out what the assistant can do, written from the user's point of view.
+Answer with a JSON object and nothing else: {"questions": ["<question>", ...]} with 3 to 5 questions."#;
+
+/// Flow definition extracted from an LLM response
+struct GeneratedFlowDraft {
+    name: Option<String>,
+    definition: crate::domain::value_objects::FlowDefinition,
+    confidence_score: f32,
 }
 
 /// Agent application service implementation
@@ -196,8 +326,19 @@ pub struct AgentApplicationServiceImpl {
     session_service: Option<Arc<crate::application::services::SessionApplicationService>>,
     llm_service: Option<Arc<dyn crate::domain::services::llm_service::LLMDomainService>>,
     llm_config_repo: Option<Arc<dyn crate::domain::repositories::LLMConfigRepository>>,
+    llm_config_selector: Option<Arc<crate::application::services::LLMConfigSelector>>,
     db: Option<Arc<sea_orm::DatabaseConnection>>,
     stats_service: Option<Arc<crate::domain::services::AgentStatsService>>,
+    flow_service: Option<Arc<dyn crate::application::services::FlowApplicationService>>,
+    event_store: Option<Arc<dyn EventStore>>,
+    usage_log_repo: Option<Arc<dyn LlmUsageLogRepository>>,
+    audit_service: Option<Arc<crate::application::services::AuditApplicationService>>,
+    context_service: Option<Arc<crate::application::services::ContextManagementService>>,
+    agent_limits: AgentLimitsConfig,
+    tenant_settings_repo: Option<Arc<dyn TenantSettingsRepository>>,
+    quota_service: Option<Arc<dyn TenantApplicationService>>,
+    api_key_repo: Option<Arc<dyn APIKeyRepository>>,
+    stream_buffer: Option<Arc<dyn ChatStreamBuffer>>,
 }
 
 impl AgentApplicationServiceImpl {
@@ -221,8 +362,19 @@ impl AgentApplicationServiceImpl {
             session_service: None,
             llm_service: None,
             llm_config_repo: None,
+            llm_config_selector: None,
             db: None,
             stats_service: None,
+            flow_service: None,
+            event_store: None,
+            usage_log_repo: None,
+            audit_service: None,
+            context_service: None,
+            agent_limits: AgentLimitsConfig::default(),
+            tenant_settings_repo: None,
+            quota_service: None,
+            api_key_repo: None,
+            stream_buffer: None,
         }
     }
 
@@ -240,10 +392,17 @@ impl AgentApplicationServiceImpl {
 
     /// Set LLM config repository for chat functionality
     pub fn with_llm_config_repo(mut self, llm_config_repo: Arc<dyn crate::domain::repositories::LLMConfigRepository>) -> Self {
+        self.llm_config_selector = Some(Arc::new(crate::application::services::LLMConfigSelector::new(llm_config_repo.clone())));
         self.llm_config_repo = Some(llm_config_repo);
         self
     }
 
+    /// Share an LLM config selector, e.g. to pool latency measurements
+    pub fn with_llm_config_selector(mut self, llm_config_selector: Arc<crate::application::services::LLMConfigSelector>) -> Self {
+        self.llm_config_selector = Some(llm_config_selector);
+        self
+    }
+
     /// Set database connection for statistics queries
     pub fn with_db(mut self, db: Arc<sea_orm::DatabaseConnection>) -> Self {
         self.db = Some(db);
@@ -256,7 +415,176 @@ impl AgentApplicationServiceImpl {
         self
     }
 
+    /// Set flow service for flow generation
+    pub fn with_flow_service(mut self, flow_service: Arc<dyn crate::application::services::FlowApplicationService>) -> Self {
+        self.flow_service = Some(flow_service);
+        self
+    }
+
+    /// Set event store for recording agent mutations
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Set usage log repository for per-request token accounting
+    pub fn with_usage_log_repository(mut self, usage_log_repo: Arc<dyn LlmUsageLogRepository>) -> Self {
+        self.usage_log_repo = Some(usage_log_repo);
+        self
+    }
+
+    /// Set audit service for recording agent mutations in the audit log
+    pub fn with_audit_service(mut self, audit_service: Arc<crate::application::services::AuditApplicationService>) -> Self {
+        self.audit_service = Some(audit_service);
+        self
+    }
+
+    /// Set context management service so chats include the session history
+    /// within a token budget
+    pub fn with_context_service(mut self, context_service: Arc<crate::application::services::ContextManagementService>) -> Self {
+        self.context_service = Some(context_service);
+        self
+    }
+
+    /// Set the platform-wide agent limits
+    pub fn with_agent_limits(mut self, agent_limits: AgentLimitsConfig) -> Self {
+        self.agent_limits = agent_limits;
+        self
+    }
+
+    /// Set tenant settings repository so tenants can override agent limits
+    pub fn with_tenant_settings_repository(mut self, tenant_settings_repo: Arc<dyn TenantSettingsRepository>) -> Self {
+        self.tenant_settings_repo = Some(tenant_settings_repo);
+        self
+    }
+
+    /// Set quota service so agent creation and chat respect the tenant's quota
+    pub fn with_quota_service(mut self, quota_service: Arc<dyn TenantApplicationService>) -> Self {
+        self.quota_service = Some(quota_service);
+        self
+    }
+
+    /// Set API key repository so agents can be resolved from an API key's scope
+    pub fn with_api_key_repository(mut self, api_key_repo: Arc<dyn APIKeyRepository>) -> Self {
+        self.api_key_repo = Some(api_key_repo);
+        self
+    }
+
+    /// Set chat stream buffer so interrupted streams can be resumed
+    pub fn with_stream_buffer(mut self, stream_buffer: Arc<dyn ChatStreamBuffer>) -> Self {
+        self.stream_buffer = Some(stream_buffer);
+        self
+    }
+
+    async fn ensure_quota(&self, tenant_id: TenantId, resource: QuotaResource) -> Result<()> {
+        match &self.quota_service {
+            Some(quota_service) => quota_service.ensure_quota(tenant_id, resource, 1).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Agent limits for the tenant, with its settings applied over the defaults
+    async fn agent_limits_for(&self, tenant_id: &TenantId) -> Result<AgentLimitsConfig> {
+        let Some(repo) = &self.tenant_settings_repo else {
+            return Ok(self.agent_limits);
+        };
+
+        Ok(repo
+            .find_by_tenant(tenant_id)
+            .await?
+            .map(|settings| settings.agent_limits(&self.agent_limits))
+            .unwrap_or(self.agent_limits))
+    }
+
+    /// Validate the agent against its tenant's limits, logging a warning for
+    /// long but valid system prompts
+    async fn validate_agent(&self, agent: &Agent) -> Result<()> {
+        let limits = self.agent_limits_for(&agent.tenant_id).await?;
+        agent
+            .validate_with_limits(&limits)
+            .map_err(PlatformError::AgentValidationError)?;
+
+        if let Some(warning) = agent.system_prompt_warning() {
+            tracing::warn!("Agent {}: {}", agent.id.0, warning);
+        }
+
+        Ok(())
+    }
+
+    /// Append an agent mutation to the event store and the audit log,
+    /// whichever are configured. Called after the change is saved, so
+    /// failures are only logged.
+    async fn record_agent_change(
+        &self,
+        agent: &Agent,
+        user_id: &UserId,
+        change: AgentChange,
+        details: Option<serde_json::Value>,
+    ) {
+        let event = AgentChanged::new(agent.id.0, agent.tenant_id.0, user_id.0, change, details);
+
+        if let Some(event_store) = &self.event_store {
+            if let Err(e) = event_store.append(&event).await {
+                tracing::warn!(
+                    "Failed to record {} event for agent {}: {}",
+                    event.event_type(),
+                    agent.id.0,
+                    e
+                );
+            }
+        }
+
+        if let (Some(audit_service), Some(entry)) = (&self.audit_service, event.audit_entry()) {
+            if let Err(e) = audit_service.record_event(AuditEvent::from(entry)).await {
+                tracing::warn!("Failed to record audit event for agent {}: {}", agent.id.0, e);
+            }
+        }
+    }
+
+    /// Prompt for a preview: the system prompt, the greeting and the message,
+    /// as the first turn of a new chat
+    fn preview_messages(agent: &Agent, message: String) -> Vec<crate::domain::value_objects::ChatMessage> {
+        use crate::domain::value_objects::ChatMessage;
+
+        let mut messages = vec![ChatMessage::new_system_message(agent.system_prompt.clone())];
+        if let Some(greeting) = &agent.greeting {
+            messages.push(ChatMessage::new_assistant_message(greeting.clone()));
+        }
+        messages.push(ChatMessage::new_user_message(message));
+        messages
+    }
+
     /// Verify that the user can modify the agent (is the creator)
+    /// Append the most recent session messages, which end with the user
+    /// message, to `preamble` within the prompt budget of the model
+    async fn append_recent_history(
+        session_service: &crate::application::services::SessionApplicationService,
+        session_id: &crate::domain::value_objects::SessionId,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        mut preamble: Vec<crate::domain::value_objects::ChatMessage>,
+        model_config: &crate::domain::value_objects::ModelConfig,
+    ) -> Result<Vec<crate::domain::value_objects::ChatMessage>> {
+        let counter = crate::infrastructure::llm::TokenCounter::new(&model_config.model_name);
+        let budget = model_config
+            .prompt_token_budget()
+            .saturating_sub(counter.count_messages(&preamble) as usize);
+
+        let history = session_service
+            .get_recent_messages(
+                session_id,
+                tenant_id,
+                user_id,
+                CHAT_HISTORY_MESSAGES,
+                budget,
+                &model_config.model_name,
+            )
+            .await?;
+        preamble.extend(history);
+
+        Ok(preamble)
+    }
+
     async fn verify_can_modify(&self, agent: &Agent, user_id: &UserId) -> Result<()> {
         if !agent.can_modify(user_id) {
             return Err(PlatformError::AgentUnauthorized(
@@ -266,6 +594,44 @@ impl AgentApplicationServiceImpl {
         Ok(())
     }
 
+    /// Verify that the user works with the agent (creator, employer or allocated)
+    async fn verify_can_use(&self, agent: &Agent, user_id: &UserId) -> Result<()> {
+        if agent.is_creator(user_id) || agent.is_employer(user_id) {
+            return Ok(());
+        }
+        if self.allocation_repo.is_allocated(&agent.id, user_id).await? {
+            return Ok(());
+        }
+        Err(PlatformError::AgentUnauthorized(
+            "Agent is not employed by or allocated to you".to_string(),
+        ))
+    }
+
+    /// Load an agent of the tenant that the user works with
+    async fn find_usable_agent(
+        &self,
+        agent_id: AgentId,
+        user_id: &UserId,
+        tenant_id: TenantId,
+    ) -> Result<Agent> {
+        let agent = self
+            .agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .ok_or_else(|| {
+                PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0))
+            })?;
+
+        if agent.tenant_id != tenant_id {
+            return Err(PlatformError::AgentUnauthorized(
+                "Agent does not belong to your tenant".to_string(),
+            ));
+        }
+
+        self.verify_can_use(&agent, user_id).await?;
+        Ok(agent)
+    }
+
     /// Convert domain Agent to AgentDto
     fn agent_to_dto(&self, agent: &Agent) -> AgentDto {
         AgentDto {
@@ -275,6 +641,7 @@ impl AgentApplicationServiceImpl {
             avatar: agent.avatar.clone(),
             greeting: agent.greeting.clone(),
             llm_config_id: agent.llm_config_id.map(|id| id.0),
+            llm_fallback_strategy: agent.llm_fallback_strategy.clone(),
             system_prompt: agent.system_prompt.clone(),
             additional_settings: agent.additional_settings.clone(),
             preset_questions: agent.preset_questions.clone(),
@@ -290,13 +657,36 @@ impl AgentApplicationServiceImpl {
         }
     }
 
+    /// Fetch the creators of the given agents in a single query
+    async fn find_creators(&self, agents: &[Agent]) -> Result<HashMap<UserId, User>> {
+        let mut creator_ids: Vec<UserId> = agents.iter().map(|agent| agent.creator_id).collect();
+        creator_ids.sort_by_key(|id| id.0);
+        creator_ids.dedup();
+
+        self.user_repo.find_by_ids(&creator_ids).await
+    }
+
+    /// Convert a page of agents to AgentCardDtos, batching creator lookups
+    async fn agents_to_card_dtos(&self, agents: Vec<Agent>, user_id: &UserId) -> Result<Vec<AgentCardDto>> {
+        let creators = self.find_creators(&agents).await?;
+
+        let mut cards = Vec::with_capacity(agents.len());
+        for agent in &agents {
+            cards.push(self.agent_to_card_dto(agent, user_id, &creators).await?);
+        }
+        Ok(cards)
+    }
+
     /// Convert domain Agent to AgentCardDto
-    async fn agent_to_card_dto(&self, agent: &Agent, user_id: &UserId) -> Result<AgentCardDto> {
+    async fn agent_to_card_dto(
+        &self,
+        agent: &Agent,
+        user_id: &UserId,
+        creators: &HashMap<UserId, User>,
+    ) -> Result<AgentCardDto> {
         // Get creator information
-        let creator = self
-            .user_repo
-            .find_by_id(agent.creator_id)
-            .await?
+        let creator = creators
+            .get(&agent.creator_id)
             .ok_or_else(|| PlatformError::NotFound("Creator not found".to_string()))?;
 
         // Check if user is the employer
@@ -339,19 +729,20 @@ impl AgentApplicationServiceImpl {
 
     /// Convert domain Agent to AgentDetailDto
     async fn agent_to_detail_dto(&self, agent: &Agent, user_id: &UserId) -> Result<AgentDetailDto> {
-        // Get creator information
-        let creator = self
-            .user_repo
-            .find_by_id(agent.creator_id)
-            .await?
+        // Get creator and employer information in one query
+        let mut user_ids = vec![agent.creator_id];
+        user_ids.extend(agent.employer_id);
+        let mut users = self.user_repo.find_by_ids(&user_ids).await?;
+
+        let creator = users
+            .get(&agent.creator_id)
+            .cloned()
             .ok_or_else(|| PlatformError::NotFound("Creator not found".to_string()))?;
 
         // Get employer information if exists
         let employer = if let Some(employer_id) = agent.employer_id {
-            let employer_user = self
-                .user_repo
-                .find_by_id(employer_id)
-                .await?
+            let employer_user = users
+                .remove(&employer_id)
                 .ok_or_else(|| PlatformError::NotFound("Employer not found".to_string()))?;
             Some(UserSummaryDto {
                 id: employer_user.id.0,
@@ -428,6 +819,7 @@ impl AgentApplicationServiceImpl {
             avatar: agent.avatar.clone(),
             greeting: agent.greeting.clone(),
             llm_config_id: agent.llm_config_id.map(|id| id.0),
+            llm_fallback_strategy: agent.llm_fallback_strategy.clone(),
             knowledge_bases,
             mcp_tools,
             flows,
@@ -453,6 +845,191 @@ impl AgentApplicationServiceImpl {
             updated_at: agent.updated_at,
         })
     }
+
+    /// Parse an LLM response into a flow definition.
+    ///
+    /// The confidence score starts at 1.0 and drops for every repair needed to
+    /// get at the JSON: stripping markdown fences or surrounding prose, and
+    /// wrapping a bare graph into the expected `workflow` envelope.
+    fn parse_generated_flow(content: &str) -> Result<GeneratedFlowDraft> {
+        let mut confidence_score: f32 = 1.0;
+        let trimmed = content.trim();
+
+        let json_str = if trimmed.starts_with('{') && trimmed.ends_with('}') {
+            trimmed
+        } else {
+            confidence_score -= 0.2;
+            match (trimmed.find('{'), trimmed.rfind('}')) {
+                (Some(start), Some(end)) if start < end => &trimmed[start..=end],
+                _ => {
+                    return Err(PlatformError::ValidationError(
+                        "LLM response does not contain a JSON object".to_string(),
+                    ))
+                }
+            }
+        };
+
+        let mut value: serde_json::Value = serde_json::from_str(json_str).map_err(|e| {
+            PlatformError::ValidationError(format!("Failed to parse generated flow JSON: {}", e))
+        })?;
+
+        let name = value
+            .get("name")
+            .and_then(|n| n.as_str())
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+
+        if value.get("workflow").is_none() {
+            confidence_score -= 0.2;
+            value = if value.get("graph").is_some() {
+                serde_json::json!({ "workflow": { "graph": value["graph"].clone() } })
+            } else {
+                serde_json::json!({ "workflow": { "graph": {
+                    "nodes": value.get("nodes").cloned().unwrap_or_else(|| serde_json::json!([])),
+                    "edges": value.get("edges").cloned().unwrap_or_else(|| serde_json::json!([])),
+                } } })
+            };
+        }
+
+        let definition = crate::domain::value_objects::FlowDefinition::from_json(&value)
+            .map_err(PlatformError::ValidationError)?;
+
+        definition
+            .validate()
+            .map_err(|e| PlatformError::ValidationError(format!("Generated flow is invalid: {}", e)))?;
+
+        Ok(GeneratedFlowDraft {
+            name,
+            definition,
+            confidence_score: confidence_score.max(0.0),
+        })
+    }
+
+    /// Ask the agent's LLM for preset questions based on its system prompt.
+    /// At most `MAX_PRESET_QUESTIONS` are kept.
+    async fn generate_preset_questions(
+        llm_service: &Arc<dyn crate::domain::services::llm_service::LLMDomainService>,
+        llm_config_selector: &Arc<crate::application::services::LLMConfigSelector>,
+        agent: &Agent,
+    ) -> Result<Vec<String>> {
+        use crate::domain::services::llm_service::ResponseFormat;
+        use crate::domain::value_objects::ChatMessage;
+
+        let llm_config = llm_config_selector
+            .select(agent.tenant_id, agent.preferred_llm_config_id(), &agent.llm_fallback_strategy)
+            .await?;
+
+        let messages = vec![
+            ChatMessage::new_system_message(PRESET_QUESTION_GENERATION_PROMPT.to_string()),
+            ChatMessage::new_user_message(format!("Assistant instructions:\n{}", agent.system_prompt)),
+        ];
+
+        let response = llm_service
+            .chat_completion(
+                &llm_config.model_config,
+                messages,
+                agent.tenant_id.0,
+                Some(ResponseFormat {
+                    format_type: "json_object".to_string(),
+                    json_schema: None,
+                }),
+            )
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+
+        let mut questions = Self::parse_suggested_questions(&response.content);
+        questions.truncate(MAX_PRESET_QUESTIONS);
+        if questions.is_empty() {
+            return Err(PlatformError::InternalError(
+                "LLM did not return any preset questions".to_string(),
+            ));
+        }
+        Ok(questions)
+    }
+
+    /// Fill in the preset questions of a new agent in the background. The
+    /// agent is left without preset questions if generation fails or no
+    /// LLM is configured.
+    fn spawn_preset_question_generation(&self, agent: &Agent) {
+        let (Some(llm_service), Some(llm_config_selector)) =
+            (self.llm_service.clone(), self.llm_config_selector.clone())
+        else {
+            return;
+        };
+        let agent_repo = self.agent_repo.clone();
+        let agent = agent.clone();
+
+        tokio::spawn(async move {
+            let result = async {
+                let questions =
+                    Self::generate_preset_questions(&llm_service, &llm_config_selector, &agent).await?;
+
+                // Keep questions the creator set while these were generated
+                let Some(mut current) = agent_repo.find_by_id(&agent.id).await? else {
+                    return Ok(());
+                };
+                if !current.preset_questions.is_empty() {
+                    return Ok(());
+                }
+                current
+                    .set_preset_questions(questions)
+                    .map_err(PlatformError::AgentValidationError)?;
+                agent_repo.save(&current).await
+            }
+            .await;
+
+            if let Err(e) = result {
+                tracing::warn!("Failed to generate preset questions for agent {}: {}", agent.id.0, e);
+            }
+        });
+    }
+
+    /// Parse suggested questions from an LLM response.
+    ///
+    /// Accepts `{"questions": [...]}`, a bare JSON array, or one question per
+    /// line with list markers stripped. Blank and duplicate questions are
+    /// dropped and at most `MAX_SUGGESTED_QUESTIONS` are kept.
+    fn parse_suggested_questions(content: &str) -> Vec<String> {
+        let trimmed = content.trim();
+        let json_slice = |open: char, close: char| match (trimmed.find(open), trimmed.rfind(close)) {
+            (Some(start), Some(end)) if start < end => Some(&trimmed[start..=end]),
+            _ => None,
+        };
+
+        let from_json = json_slice('{', '}')
+            .and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+            .and_then(|value| value.get("questions").cloned())
+            .or_else(|| {
+                json_slice('[', ']').and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+            })
+            .and_then(|value| serde_json::from_value::<Vec<String>>(value).ok());
+
+        let candidates = from_json.unwrap_or_else(|| {
+            trimmed
+                .lines()
+                .map(|line| {
+                    line.trim()
+                        .trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, '-' | '*' | '.' | ')'))
+                        .trim()
+                        .to_string()
+                })
+                .filter(|line| !line.starts_with("```"))
+                .collect()
+        });
+
+        let mut questions: Vec<String> = Vec::new();
+        for question in candidates {
+            let question = question.trim().to_string();
+            if question.is_empty() || questions.contains(&question) {
+                continue;
+            }
+            questions.push(question);
+            if questions.len() == MAX_SUGGESTED_QUESTIONS {
+                break;
+            }
+        }
+        questions
+    }
 }
 
 #[async_trait]
@@ -463,7 +1040,11 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         tenant_id: TenantId,
         creator_id: UserId,
     ) -> Result<AgentDto> {
+        self.ensure_quota(tenant_id, QuotaResource::Agents).await?;
+
         // Create agent entity
+        let generate_preset_questions = dto.generate_preset_questions && dto.preset_questions.is_empty();
+
         let mut agent = Agent::new(tenant_id, dto.name, dto.system_prompt, creator_id)
             .map_err(|e| PlatformError::AgentValidationError(e))?;
 
@@ -471,6 +1052,9 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         agent.update_avatar(dto.avatar);
         agent.update_greeting(dto.greeting);
         agent.update_llm_config(dto.llm_config_id.map(ConfigId::from_uuid));
+        if let Some(strategy) = dto.llm_fallback_strategy {
+            agent.update_llm_fallback_strategy(strategy);
+        }
         agent.update_additional_settings(dto.additional_settings);
         agent.update_price(dto.price)
             .map_err(|e| PlatformError::AgentValidationError(e))?;
@@ -493,12 +1077,20 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         }
 
         // Validate agent
-        agent
-            .validate()
-            .map_err(|e| PlatformError::AgentValidationError(e))?;
+        self.validate_agent(&agent).await?;
 
         // Save agent
         self.agent_repo.save(&agent).await?;
+        self.record_agent_change(
+            &agent,
+            &creator_id,
+            AgentChange::Created,
+            Some(serde_json::json!({ "name": agent.name })),
+        ).await;
+
+        if generate_preset_questions {
+            self.spawn_preset_question_generation(&agent);
+        }
 
         Ok(self.agent_to_dto(&agent))
     }
@@ -513,6 +1105,46 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         self.agent_to_detail_dto(&agent, &user_id).await
     }
 
+    async fn get_agent_by_api_key(&self, api_key_id: APIKeyId, user_id: UserId) -> Result<AgentDetailDto> {
+        let api_key_repo = self.api_key_repo.as_ref().ok_or_else(|| {
+            PlatformError::InternalError("API key repository not configured".to_string())
+        })?;
+        let api_key = api_key_repo
+            .find_by_id(api_key_id)
+            .await?
+            .filter(|api_key| api_key.belongs_to_user(&user_id))
+            .ok_or_else(|| PlatformError::NotFound("API key not found".to_string()))?;
+        if !api_key.is_valid() {
+            return Err(PlatformError::AuthenticationFailed(
+                "API key is disabled or expired".to_string(),
+            ));
+        }
+
+        let agent_id = match api_key.permission_scope.agent_ids.as_slice() {
+            [agent_id] => AgentId::from_uuid(*agent_id),
+            [] => {
+                return Err(PlatformError::ValidationError(
+                    "API key is not scoped to an agent".to_string(),
+                ))
+            }
+            agent_ids => {
+                return Err(PlatformError::ValidationError(format!(
+                    "API key is scoped to {} agents; request one by ID instead",
+                    agent_ids.len()
+                )))
+            }
+        };
+
+        let agent = self
+            .agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .filter(|agent| agent.tenant_id == api_key.tenant_id)
+            .ok_or_else(|| PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0)))?;
+
+        self.agent_to_detail_dto(&agent, &user_id).await
+    }
+
     async fn update_agent(
         &self,
         id: AgentId,
@@ -547,6 +1179,10 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             agent.update_llm_config(Some(ConfigId::from_uuid(llm_config_id)));
         }
 
+        if let Some(strategy) = dto.llm_fallback_strategy {
+            agent.update_llm_fallback_strategy(strategy);
+        }
+
         if let Some(system_prompt) = dto.system_prompt {
             agent
                 .update_system_prompt(system_prompt)
@@ -570,12 +1206,11 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         }
 
         // Validate agent
-        agent
-            .validate()
-            .map_err(|e| PlatformError::AgentValidationError(e))?;
+        self.validate_agent(&agent).await?;
 
         // Save agent
         self.agent_repo.save(&agent).await?;
+        self.record_agent_change(&agent, &user_id, AgentChange::Updated, None).await;
 
         Ok(self.agent_to_dto(&agent))
     }
@@ -592,6 +1227,12 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
 
         // Delete agent (employment and allocation relationships will be cascade deleted by database)
         self.agent_repo.delete(&id).await?;
+        self.record_agent_change(
+            &agent,
+            &user_id,
+            AgentChange::Deleted,
+            Some(serde_json::json!({ "name": agent.name })),
+        ).await;
 
         Ok(())
     }
@@ -602,35 +1243,30 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         user_id: UserId,
         params: PaginationParams,
         include_fired: bool,
+        keyword: Option<String>,
     ) -> Result<PaginatedResponse<AgentCardDto>> {
         let page = params.get_page();
         let limit = params.get_limit();
-        let offset = params.get_offset() as usize;
-
-        // Get published agents by tenant (only show published agents to other users)
-        let agents = self.agent_repo.find_by_tenant_published(&tenant_id).await?;
-
-        // Filter by fired status if needed
-        let visible_agents: Vec<_> = if include_fired {
-            agents
-        } else {
-            agents.into_iter().filter(|agent| !agent.is_fired()).collect()
+        let offset = params.get_offset();
+
+        // Get published agents by tenant (only show published agents to other users).
+        // Fired agents never match a keyword search.
+        let keyword = keyword.filter(|k| !k.trim().is_empty());
+        let (paginated_agents, total) = match keyword {
+            Some(keyword) => {
+                self.agent_repo
+                    .search_by_keyword(&tenant_id, &keyword, offset, limit)
+                    .await?
+            }
+            None => {
+                self.agent_repo
+                    .find_by_tenant_published_paginated(&tenant_id, include_fired, offset, limit)
+                    .await?
+            }
         };
 
-        let total = visible_agents.len() as u64;
-
-        // Apply pagination manually
-        let paginated_agents: Vec<_> = visible_agents
-            .into_iter()
-            .skip(offset)
-            .take(limit as usize)
-            .collect();
-
         // Convert to card DTOs
-        let mut cards = Vec::new();
-        for agent in paginated_agents {
-            cards.push(self.agent_to_card_dto(&agent, &user_id).await?);
-        }
+        let cards = self.agents_to_card_dtos(paginated_agents, &user_id).await?;
 
         Ok(PaginatedResponse::new(cards, total, page, limit))
     }
@@ -640,32 +1276,18 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         user_id: UserId,
         params: PaginationParams,
     ) -> Result<PaginatedResponse<AgentCardDto>> {
-        // Get all agents created by the user
-        let agents = self.agent_repo.find_by_creator(&user_id).await?;
-
-        // Filter out agents that have a source_agent_id (copied or employed agents)
-        let original_agents: Vec<_> = agents
-            .into_iter()
-            .filter(|agent| agent.source_agent_id.is_none())
-            .collect();
-
-        let total = original_agents.len() as u64;
         let page = params.get_page();
         let limit = params.get_limit();
-        let offset = params.get_offset() as usize;
+        let offset = params.get_offset();
 
-        // Apply pagination manually
-        let paginated_agents: Vec<_> = original_agents
-            .into_iter()
-            .skip(offset)
-            .take(limit as usize)
-            .collect();
+        // Get agents created by the user, excluding copied or employed agents
+        let (paginated_agents, total) = self
+            .agent_repo
+            .find_original_by_creator_paginated(&user_id, offset, limit)
+            .await?;
 
         // Convert to card DTOs
-        let mut cards = Vec::new();
-        for agent in paginated_agents {
-            cards.push(self.agent_to_card_dto(&agent, &user_id).await?);
-        }
+        let cards = self.agents_to_card_dtos(paginated_agents, &user_id).await?;
 
         Ok(PaginatedResponse::new(cards, total, page, limit))
     }
@@ -691,16 +1313,22 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             ));
         }
 
+        self.ensure_quota(tenant_id, QuotaResource::Agents).await?;
+
         // Create a copy
         let copied_agent = source_agent.copy_from(user_id);
 
         // Validate the copied agent
-        copied_agent
-            .validate()
-            .map_err(|e| PlatformError::AgentValidationError(e))?;
+        self.validate_agent(&copied_agent).await?;
 
         // Save the copied agent
         self.agent_repo.save(&copied_agent).await?;
+        self.record_agent_change(
+            &copied_agent,
+            &user_id,
+            AgentChange::Copied,
+            Some(serde_json::json!({ "source_agent_id": source_id.0 })),
+        ).await;
 
         Ok(self.agent_to_dto(&copied_agent))
     }
@@ -719,12 +1347,16 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         let employed_agent = source_agent.copy_for_employment(user_id);
 
         // Validate the employed agent
-        employed_agent
-            .validate()
-            .map_err(|e| PlatformError::AgentValidationError(e))?;
+        self.validate_agent(&employed_agent).await?;
 
         // Save the employed agent
         self.agent_repo.save(&employed_agent).await?;
+        self.record_agent_change(
+            &employed_agent,
+            &user_id,
+            AgentChange::Employed,
+            Some(serde_json::json!({ "source_agent_id": agent_id.0 })),
+        ).await;
 
         // Record employment statistics
         if let Some(stats_service) = &self.stats_service {
@@ -758,46 +1390,96 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
 
         // Save the updated agent
         self.agent_repo.save(&agent).await?;
+        self.record_agent_change(&agent, &user_id, AgentChange::Fired, None).await;
 
         Ok(())
     }
 
-    async fn list_employed_agents(
-        &self,
+    async fn list_agent_copies(&self, agent_id: AgentId, user_id: UserId) -> Result<Vec<AgentCopyDto>> {
+        let agent = self
+            .agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .ok_or_else(|| {
+                PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0))
+            })?;
+
+        // Verify permission - only creator can see who copied the agent
+        self.verify_can_modify(&agent, &user_id).await?;
+
+        let copies = self.agent_repo.find_by_source_agent(&agent_id).await?;
+
+        // Get creator and employer information in one query
+        let mut user_ids: Vec<UserId> = copies
+            .iter()
+            .flat_map(|copy| std::iter::once(copy.creator_id).chain(copy.employer_id))
+            .collect();
+        user_ids.sort_by_key(|id| id.0);
+        user_ids.dedup();
+        let users = self.user_repo.find_by_ids(&user_ids).await?;
+
+        let summary = |id: &UserId| {
+            users
+                .get(id)
+                .map(|user| UserSummaryDto {
+                    id: user.id.0,
+                    username: user.username.0.clone(),
+                    nickname: user.nickname.clone(),
+                })
+                .ok_or_else(|| PlatformError::NotFound(format!("User {} not found", id.0)))
+        };
+
+        copies
+            .iter()
+            .map(|copy| {
+                Ok(AgentCopyDto {
+                    id: copy.id.0,
+                    tenant_id: copy.tenant_id.0,
+                    name: copy.name.clone(),
+                    creator: summary(&copy.creator_id)?,
+                    employer: copy.employer_id.as_ref().map(summary).transpose()?,
+                    is_fired: copy.is_fired(),
+                    fired_at: copy.fired_at,
+                    created_at: copy.created_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn list_employed_agents(
+        &self,
         user_id: UserId,
         params: PaginationParams,
         include_fired: bool,
     ) -> Result<PaginatedResponse<AgentCardDto>> {
-        // Get all agents employed by the user
-        let mut agents = self.agent_repo.find_by_employer(&user_id).await?;
-
-        // Filter out fired agents if not including them
-        if !include_fired {
-            agents.retain(|agent| !agent.is_fired());
-        }
-
-        let total = agents.len() as u64;
         let page = params.get_page();
         let limit = params.get_limit();
-        let offset = params.get_offset() as usize;
+        let offset = params.get_offset();
 
-        // Apply pagination manually
-        let paginated_agents: Vec<_> = agents
-            .into_iter()
-            .skip(offset)
-            .take(limit as usize)
-            .collect();
+        // Get agents employed by the user
+        let (paginated_agents, total) = self
+            .agent_repo
+            .find_by_employer_paginated(&user_id, include_fired, offset, limit)
+            .await?;
 
         // Convert to card DTOs
-        let mut cards = Vec::new();
-        for agent in paginated_agents {
-            cards.push(self.agent_to_card_dto(&agent, &user_id).await?);
-        }
+        let cards = self.agents_to_card_dtos(paginated_agents, &user_id).await?;
 
         Ok(PaginatedResponse::new(cards, total, page, limit))
     }
 
-    async fn allocate_agent(&self, agent_id: AgentId, user_id: UserId) -> Result<()> {
+    async fn allocate_agent(
+        &self,
+        agent_id: AgentId,
+        user_id: UserId,
+        dto: AllocateAgentDto,
+    ) -> Result<()> {
+        if dto.expires_at.is_some_and(|expires_at| expires_at <= chrono::Utc::now()) {
+            return Err(PlatformError::ValidationError(
+                "Allocation expiry must be in the future".to_string(),
+            ));
+        }
+
         // Verify agent exists
         let _agent = self
             .agent_repo
@@ -808,7 +1490,9 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             })?;
 
         // Create allocation relationship
-        self.allocation_repo.allocate(&agent_id, &user_id).await?;
+        self.allocation_repo
+            .allocate(&agent_id, &user_id, dto.expires_at)
+            .await?;
 
         Ok(())
     }
@@ -834,26 +1518,18 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         user_id: UserId,
         params: PaginationParams,
     ) -> Result<PaginatedResponse<AgentCardDto>> {
-        // Get all allocated agents (no pagination at repository level for now)
-        let agents = self.agent_repo.find_allocated_to_user(&user_id).await?;
-
-        let total = agents.len() as u64;
         let page = params.get_page();
         let limit = params.get_limit();
-        let offset = params.get_offset() as usize;
+        let offset = params.get_offset();
 
-        // Apply pagination manually
-        let paginated_agents: Vec<_> = agents
-            .into_iter()
-            .skip(offset)
-            .take(limit as usize)
-            .collect();
+        // Get agents allocated to the user
+        let (paginated_agents, total) = self
+            .agent_repo
+            .find_allocated_to_user_paginated(&user_id, offset, limit)
+            .await?;
 
         // Convert to card DTOs
-        let mut cards = Vec::new();
-        for agent in paginated_agents {
-            cards.push(self.agent_to_card_dto(&agent, &user_id).await?);
-        }
+        let cards = self.agents_to_card_dtos(paginated_agents, &user_id).await?;
 
         Ok(PaginatedResponse::new(cards, total, page, limit))
     }
@@ -1027,6 +1703,10 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         Ok(())
     }
 
+    #[tracing::instrument(
+        skip(self, message, session_id),
+        fields(agent_id = %agent_id.0, user_id = %user_id, tenant_id = %tenant_id)
+    )]
     async fn chat(
         &self,
         agent_id: AgentId,
@@ -1054,6 +1734,10 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             ));
         }
 
+        // Tokens are only known after the call, so this stops chats once
+        // the monthly allowance is used up
+        self.ensure_quota(tenant_id, QuotaResource::MonthlyTokens).await?;
+
         // Get or create session
         let session_service = self.session_service.as_ref()
             .ok_or_else(|| PlatformError::InternalError("Session service not configured".to_string()))?;
@@ -1076,6 +1760,16 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             }
         };
 
+        // Summarize long sessions that have no summary yet
+        if !is_new_session {
+            if let Err(e) = session_service
+                .summarize_if_needed(&session_id, &tenant_id, &user_id)
+                .await
+            {
+                tracing::warn!("Failed to summarize session {}: {}", session_id.0, e);
+            }
+        }
+
         // Add user message to session
         let user_metadata = MessageMetadata {
             model_used: None,
@@ -1103,19 +1797,13 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         let llm_service = self.llm_service.as_ref()
             .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?;
 
-        let llm_config_repo = self.llm_config_repo.as_ref()
+        let llm_config_selector = self.llm_config_selector.as_ref()
             .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?;
 
-        // Get LLM config - use agent's specified config or fall back to first available
-        let llm_config = if let Some(config_id) = agent.llm_config_id {
-            llm_config_repo.find_by_id(config_id).await?
-                .ok_or_else(|| PlatformError::NotFound(format!("LLM configuration {} not found", config_id.0)))?
-        } else {
-            let llm_configs = llm_config_repo.find_by_tenant(tenant_id).await?;
-            llm_configs.first()
-                .ok_or_else(|| PlatformError::NotFound("No LLM configuration found for tenant".to_string()))?
-                .clone()
-        };
+        // Get LLM config - the agent's preferred config, else its fallback strategy
+        let llm_config = llm_config_selector
+            .select(tenant_id, agent.preferred_llm_config_id(), &agent.llm_fallback_strategy)
+            .await?;
 
         // Build conversation history
         let mut messages = vec![
@@ -1127,10 +1815,28 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             messages.push(ChatMessage::new_assistant_message(greeting.clone()));
         }
 
-        // Add user message
-        messages.push(ChatMessage::new_user_message(message));
+        // Add the session history, which ends with the user message
+        let messages = match &self.context_service {
+            Some(context_service) => {
+                context_service
+                    .build_chat_context(&session_id, &tenant_id, &user_id, messages, &llm_config.model_config)
+                    .await?
+            }
+            None => {
+                Self::append_recent_history(
+                    &session_service,
+                    &session_id,
+                    &tenant_id,
+                    &user_id,
+                    messages,
+                    &llm_config.model_config,
+                )
+                .await?
+            }
+        };
 
         // Call LLM
+        let started_at = std::time::Instant::now();
         let response = llm_service
             .chat_completion(
                 &llm_config.model_config,
@@ -1140,12 +1846,14 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             )
             .await
             .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+        let latency = started_at.elapsed();
+        llm_config_selector.record_latency(llm_config.id, latency).await;
 
         // Add assistant response to session
         let assistant_metadata = MessageMetadata {
             model_used: Some(response.model_used.clone()),
             tokens_used: Some(response.usage.total_tokens),
-            response_time_ms: None,
+            response_time_ms: Some(latency.as_millis() as u64),
             tool_calls: None,
             custom_data: std::collections::HashMap::from([
                 ("agent_id".to_string(), serde_json::json!(agent_id.0.to_string())),
@@ -1170,10 +1878,18 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             let _ = stats_service.record_messages(agent_id, tenant_id, 2).await;
             // Record tokens used
             let _ = stats_service.record_tokens(agent_id, tenant_id, response.usage.total_tokens as i64).await;
+
+            let revenue = crate::domain::services::AgentStatsService::revenue_for_tokens(
+                agent.price.unwrap_or(Decimal::ZERO),
+                response.usage.total_tokens,
+            );
+            if let Err(e) = stats_service.record_revenue(agent_id, tenant_id, revenue).await {
+                tracing::warn!("Failed to record revenue: {:?}", e);
+            }
         }
 
         Ok(crate::application::dto::agent_dto::AgentChatResponse {
-            session_id: session_id.0,
+            session_id: Some(session_id.0),
             message_id: user_message.id.0,
             reply_id: assistant_message.id.0,
             reply: response.content,
@@ -1185,6 +1901,71 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         })
     }
 
+    #[tracing::instrument(
+        skip(self, message),
+        fields(agent_id = %agent_id.0, user_id = %user_id, tenant_id = %tenant_id)
+    )]
+    async fn preview_chat(
+        &self,
+        agent_id: AgentId,
+        message: String,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<crate::application::dto::agent_dto::AgentChatResponse> {
+        let agent = self
+            .agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .ok_or_else(|| {
+                PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0))
+            })?;
+
+        if agent.tenant_id != tenant_id {
+            return Err(PlatformError::AgentUnauthorized(
+                "Agent does not belong to your tenant".to_string(),
+            ));
+        }
+
+        // Previews still spend the tenant's tokens
+        self.ensure_quota(tenant_id, QuotaResource::MonthlyTokens).await?;
+
+        let llm_service = self.llm_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?;
+
+        let llm_config_selector = self.llm_config_selector.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?;
+
+        let llm_config = llm_config_selector
+            .select(tenant_id, agent.preferred_llm_config_id(), &agent.llm_fallback_strategy)
+            .await?;
+
+        let started_at = std::time::Instant::now();
+        let response = llm_service
+            .chat_completion(
+                &llm_config.model_config,
+                Self::preview_messages(&agent, message),
+                tenant_id.0,
+                None,
+            )
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+        llm_config_selector.record_latency(llm_config.id, started_at.elapsed()).await;
+
+        // The ids name the ephemeral messages; neither is stored
+        Ok(crate::application::dto::agent_dto::AgentChatResponse {
+            session_id: None,
+            message_id: uuid::Uuid::new_v4(),
+            reply_id: uuid::Uuid::new_v4(),
+            reply: response.content,
+            metadata: Some(serde_json::json!({
+                "model": response.model_used,
+                "tokens_used": response.usage.total_tokens,
+                "finish_reason": format!("{:?}", response.finish_reason),
+                "preview": true,
+            })),
+        })
+    }
+
     async fn get_agent_usage_stats(
         &self,
         agent_id: AgentId,
@@ -1442,13 +2223,7 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         let dtos = records
             .into_iter()
             .map(|record| {
-                let status = match record.status {
-                    crate::domain::entities::InterviewStatus::Pending => "pending",
-                    crate::domain::entities::InterviewStatus::InProgress => "in_progress",
-                    crate::domain::entities::InterviewStatus::Passed => "passed",
-                    crate::domain::entities::InterviewStatus::Failed => "failed",
-                    crate::domain::entities::InterviewStatus::Cancelled => "cancelled",
-                };
+                let status = record.status.as_str();
 
                 InterviewRecordDto {
                     id: record.id.to_string(),
@@ -1472,6 +2247,10 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         Ok(dtos)
     }
 
+    #[tracing::instrument(
+        skip(self, message, session_id),
+        fields(agent_id = %agent_id.0, user_id = %user_id, tenant_id = %tenant_id)
+    )]
     async fn chat_stream(
         &self,
         agent_id: AgentId,
@@ -1500,6 +2279,8 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             ));
         }
 
+        self.ensure_quota(tenant_id, QuotaResource::MonthlyTokens).await?;
+
         // Get or create session
         let session_service = self.session_service.as_ref()
             .ok_or_else(|| PlatformError::InternalError("Session service not configured".to_string()))?
@@ -1523,6 +2304,16 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             }
         };
 
+        // Summarize long sessions that have no summary yet
+        if !is_new_session {
+            if let Err(e) = session_service
+                .summarize_if_needed(&session_id, &tenant_id, &user_id)
+                .await
+            {
+                tracing::warn!("Failed to summarize session {}: {}", session_id.0, e);
+            }
+        }
+
         // Add user message to session
         let user_metadata = MessageMetadata {
             model_used: None,
@@ -1551,20 +2342,14 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?
             .clone();
 
-        let llm_config_repo = self.llm_config_repo.as_ref()
+        let llm_config_selector = self.llm_config_selector.as_ref()
             .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?
             .clone();
 
-        // Get LLM config - use agent's specified config or fall back to first available
-        let llm_config = if let Some(config_id) = agent.llm_config_id {
-            llm_config_repo.find_by_id(config_id).await?
-                .ok_or_else(|| PlatformError::NotFound(format!("LLM configuration {} not found", config_id.0)))?
-        } else {
-            let llm_configs = llm_config_repo.find_by_tenant(tenant_id).await?;
-            llm_configs.first()
-                .ok_or_else(|| PlatformError::NotFound("No LLM configuration found for tenant".to_string()))?
-                .clone()
-        };
+        // Get LLM config - the agent's preferred config, else its fallback strategy
+        let llm_config = llm_config_selector
+            .select(tenant_id, agent.preferred_llm_config_id(), &agent.llm_fallback_strategy)
+            .await?;
 
         // Build conversation history
         let mut messages = vec![
@@ -1576,13 +2361,36 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
             messages.push(ChatMessage::new_assistant_message(greeting.clone()));
         }
 
-        // Add user message
-        messages.push(ChatMessage::new_user_message(message));
+        // Add the session history, which ends with the user message
+        let messages = match &self.context_service {
+            Some(context_service) => {
+                context_service
+                    .build_chat_context(&session_id, &tenant_id, &user_id, messages, &llm_config.model_config)
+                    .await?
+            }
+            None => {
+                Self::append_recent_history(
+                    &session_service,
+                    &session_id,
+                    &tenant_id,
+                    &user_id,
+                    messages,
+                    &llm_config.model_config,
+                )
+                .await?
+            }
+        };
 
         // Get model name for metadata
         let model_name = llm_config.model_config.model_name.clone();
 
+        // Counts tokens locally in case the provider never reports usage
+        let usage_collector = Arc::new(Mutex::new(
+            crate::infrastructure::llm::StreamUsageCollector::new(&model_name, &messages),
+        ));
+
         // Call LLM streaming
+        let started_at = std::time::Instant::now();
         let stream = llm_service
             .stream_chat_completion(
                 &llm_config.model_config,
@@ -1601,55 +2409,119 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
         let tenant_id_clone = tenant_id;
         let user_id_clone = user_id;
         let stats_service = self.stats_service.clone();
-        let agent_price = agent.price;
+        let usage_log_repo = self.usage_log_repo.clone();
+        let agent_price = agent.price.unwrap_or(Decimal::ZERO);
+        let llm_config_id = llm_config.id;
 
         // Use Arc<Mutex<>> to allow mutation across async closures
         let accumulated_content = Arc::new(Mutex::new(String::new()));
-        let total_tokens = Arc::new(Mutex::new(0u32));
-        let reply_message_id = Arc::new(Mutex::new(None::<crate::domain::value_objects::MessageId>));
-
-        let transformed_stream = stream.then(move |chunk_result| {
-            let session_service = session_service_clone.clone();
-            let agent_name = agent_name.clone();
-            let stats_service = stats_service.clone();
-            let accumulated_content = accumulated_content.clone();
-            let total_tokens = total_tokens.clone();
-            let reply_message_id = reply_message_id.clone();
-            let model_name = model_name.clone();
-            
-            async move {
-                match chunk_result {
-                    Ok(chunk) => {
-                        // Accumulate content
-                        if let Some(content) = &chunk.content {
-                            let mut acc = accumulated_content.lock().await;
-                            acc.push_str(content);
-                        }
+        let accumulated_reasoning = Arc::new(Mutex::new(String::new()));
+        let failed = Arc::new(Mutex::new(false));
+
+        // A trailing `None` marks the end of the provider stream, so the reply is
+        // saved once whether or not the provider sent usage along the way
+        let transformed_stream = stream
+            .map(Some)
+            .chain(futures::stream::once(async { None }))
+            .then(move |item| {
+                let session_service = session_service_clone.clone();
+                let agent_name = agent_name.clone();
+                let stats_service = stats_service.clone();
+                let usage_log_repo = usage_log_repo.clone();
+                let accumulated_content = accumulated_content.clone();
+                let accumulated_reasoning = accumulated_reasoning.clone();
+                let usage_collector = usage_collector.clone();
+                let failed = failed.clone();
+                let model_name = model_name.clone();
+                let llm_config_selector = llm_config_selector.clone();
+
+                async move {
+                    match item {
+                        Some(Ok(chunk)) => {
+                            usage_collector.lock().await.observe(&chunk);
+
+                            // Accumulate content
+                            if let Some(content) = &chunk.content {
+                                let mut acc = accumulated_content.lock().await;
+                                acc.push_str(content);
+                            }
+                            if let Some(reasoning) = &chunk.reasoning_content {
+                                accumulated_reasoning.lock().await.push_str(reasoning);
+                            }
 
-                        // Update usage info
-                        if let Some(usage) = &chunk.usage {
-                            let mut tokens = total_tokens.lock().await;
-                            *tokens = usage.total_tokens;
+                            // Thinking tokens are tagged so clients can render them apart.
+                            // DeepSeek sends an empty `content` next to its reasoning.
+                            let chunk_type = if chunk.content.as_deref().map_or(true, str::is_empty)
+                                && chunk.reasoning_content.is_some()
+                            {
+                                "reasoning"
+                            } else {
+                                "content"
+                            };
+
+                            // Return content chunk
+                            Some(Ok(crate::application::dto::agent_dto::AgentChatStreamChunk {
+                                chunk_type: chunk_type.to_string(),
+                                content: chunk.content,
+                                reasoning_content: chunk.reasoning_content,
+                                session_id: Some(session_id_clone.0),
+                                message_id: Some(user_message_id.0),
+                                reply_id: None,
+                                metadata: None,
+                                finish_reason: None,
+                                error: None,
+                            }))
+                        }
+                        Some(Err(e)) => {
+                            *failed.lock().await = true;
+
+                            // Return error chunk
+                            Some(Ok(crate::application::dto::agent_dto::AgentChatStreamChunk {
+                                chunk_type: "error".to_string(),
+                                content: None,
+                                reasoning_content: None,
+                                session_id: Some(session_id_clone.0),
+                                message_id: Some(user_message_id.0),
+                                reply_id: None,
+                                metadata: None,
+                                finish_reason: None,
+                                error: Some(format!("{}", e)),
+                            }))
                         }
+                        None => {
+                            // The client already received an error chunk
+                            if *failed.lock().await {
+                                return None;
+                            }
 
-                        // Check if this is the final chunk
-                        // To collect usage so checking finish_reason is not enough.
-                        // if let Some(finish_reason) = &chunk.finish_reason {
-                        if let Some(usage) = &chunk.usage {
-                            // Get final accumulated values
+                            let (usage, estimated) = {
+                                let collector = usage_collector.lock().await;
+                                (collector.usage(), collector.is_estimated())
+                            };
                             let final_content = accumulated_content.lock().await.clone();
-                            let final_tokens = *total_tokens.lock().await;
+                            let final_reasoning = accumulated_reasoning.lock().await.clone();
+
+                            let latency = started_at.elapsed();
+                            llm_config_selector.record_latency(llm_config_id, latency).await;
 
                             // Save the complete assistant message
+                            let mut custom_data = std::collections::HashMap::from([
+                                ("agent_id".to_string(), serde_json::json!(agent_id_clone.0.to_string())),
+                                ("agent_name".to_string(), serde_json::json!(agent_name.clone())),
+                            ]);
+                            if !final_reasoning.is_empty() {
+                                custom_data.insert(
+                                    crate::infrastructure::llm::REASONING_CONTENT_KEY.to_string(),
+                                    serde_json::json!(final_reasoning),
+                                );
+                            }
+
                             let assistant_metadata = MessageMetadata {
                                 model_used: Some(model_name.clone()),
-                                tokens_used: Some(final_tokens),
-                                response_time_ms: None,
+                                tokens_used: Some(usage.total_tokens),
+                                response_time_ms: Some(latency.as_millis() as u64),
                                 tool_calls: None,
-                                custom_data: std::collections::HashMap::from([
-                                    ("agent_id".to_string(), serde_json::json!(agent_id_clone.0.to_string())),
-                                    ("agent_name".to_string(), serde_json::json!(agent_name.clone())),
-                                ]),
+                                custom_data,
                             };
 
                             let assistant_chat_message = ChatMessage {
@@ -1659,83 +2531,105 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
                                 timestamp: chrono::Utc::now(),
                             };
 
-                            match session_service
+                            let reply_id = match session_service
                                 .add_message(&session_id_clone, &tenant_id_clone, &user_id_clone, assistant_chat_message)
                                 .await
                             {
-                                Ok(assistant_message) => {
-                                    let mut reply_id = reply_message_id.lock().await;
-                                    *reply_id = Some(assistant_message.id);
-
-                                    // Record statistics
-                                    if let Some(stats_svc) = &stats_service {
-                                        let _ = stats_svc.record_messages(agent_id_clone, tenant_id_clone, 2).await;
-                                        let _ = stats_svc.record_tokens(agent_id_clone, tenant_id_clone, final_tokens as i64).await;
-                                        let _ = stats_svc.record_revenue(
-                                            agent_id_clone,
-                                            tenant_id_clone,
-                                            agent_price.unwrap_or(Decimal::ZERO) * Decimal::from_u32(final_tokens).unwrap_or(Decimal::ZERO) / Decimal::from_u32(1000).unwrap_or(Decimal::ZERO)
-                                        ).await;
-                                    }
-
-                                    // // Return final chunk with all IDs
-                                    // return Ok(crate::application::dto::agent_dto::AgentChatStreamChunk {
-                                    //     chunk_type: "done".to_string(),
-                                    //     content: None,
-                                    //     reasoning_content: None,
-                                    //     session_id: Some(session_id_clone.0),
-                                    //     message_id: Some(user_message_id.0),
-                                    //     reply_id: Some(assistant_message.id.0),
-                                    //     metadata: Some(serde_json::json!({
-                                    //         "model": model_name,
-                                    //         "tokens_used": final_tokens,
-                                    //         "finish_reason": format!("{:?}", finish_reason),
-                                    //     })),
-                                    //     finish_reason: Some(format!("{:?}", finish_reason)),
-                                    //     error: None,
-                                    // });
-                                },
+                                Ok(assistant_message) => Some(assistant_message.id.0),
                                 Err(e) => {
-                                    log::warn!("add_message failed: {:?}", e);
+                                    tracing::warn!("add_message failed: {:?}", e);
+                                    None
+                                }
+                            };
+
+                            // Record statistics
+                            if let Some(stats_svc) = &stats_service {
+                                let _ = stats_svc.record_messages(agent_id_clone, tenant_id_clone, 2).await;
+                                let _ = stats_svc.record_tokens(agent_id_clone, tenant_id_clone, usage.total_tokens as i64).await;
+                                let revenue = crate::domain::services::AgentStatsService::revenue_for_tokens(
+                                    agent_price,
+                                    usage.total_tokens,
+                                );
+                                if let Err(e) = stats_svc.record_revenue(agent_id_clone, tenant_id_clone, revenue).await {
+                                    tracing::warn!("Failed to record revenue: {:?}", e);
                                 }
                             }
-                        }
 
-                        // Return content chunk
-                        let reply_id = *reply_message_id.lock().await;
-                        Ok(crate::application::dto::agent_dto::AgentChatStreamChunk {
-                            chunk_type: "content".to_string(),
-                            content: chunk.content,
-                            reasoning_content: chunk.reasoning_content,
-                            session_id: Some(session_id_clone.0),
-                            message_id: Some(user_message_id.0),
-                            reply_id: reply_id.map(|id| id.0),
-                            metadata: None,
-                            finish_reason: None,
-                            error: None,
-                        })
-                    }
-                    Err(e) => {
-                        // Return error chunk
-                        Ok(crate::application::dto::agent_dto::AgentChatStreamChunk {
-                            chunk_type: "error".to_string(),
-                            content: None,
-                            reasoning_content: None,
-                            session_id: Some(session_id_clone.0),
-                            message_id: Some(user_message_id.0),
-                            reply_id: None,
-                            metadata: None,
-                            finish_reason: None,
-                            error: Some(format!("{}", e)),
-                        })
+                            // Record per-request usage for cost attribution
+                            if let Some(usage_log_repo) = &usage_log_repo {
+                                let usage_log = crate::domain::entities::LlmUsageLog::new(
+                                    tenant_id_clone,
+                                    agent_id_clone,
+                                    user_id_clone,
+                                    Some(session_id_clone),
+                                    model_name.clone(),
+                                    usage.prompt_tokens as i64,
+                                    usage.completion_tokens as i64,
+                                    estimated,
+                                )
+                                .with_price_per_1k(agent_price);
+
+                                if let Err(e) = usage_log_repo.create(&usage_log).await {
+                                    tracing::warn!("Failed to record LLM usage: {:?}", e);
+                                }
+                            }
+
+                            Some(Ok(crate::application::dto::agent_dto::AgentChatStreamChunk {
+                                chunk_type: "done".to_string(),
+                                content: None,
+                                reasoning_content: None,
+                                session_id: Some(session_id_clone.0),
+                                message_id: Some(user_message_id.0),
+                                reply_id,
+                                metadata: Some(serde_json::json!({
+                                    "model": model_name,
+                                    "tokens_used": usage.total_tokens,
+                                    "prompt_tokens": usage.prompt_tokens,
+                                    "completion_tokens": usage.completion_tokens,
+                                    "usage_estimated": estimated,
+                                })),
+                                finish_reason: None,
+                                error: None,
+                            }))
+                        }
                     }
                 }
-            }
-        });
+            })
+            .filter_map(futures::future::ready);
 
         Ok(Box::new(Box::pin(transformed_stream)))
     }
 
+    async fn buffer_chat_stream_event(
+        &self,
+        session_id: crate::domain::value_objects::SessionId,
+        event: ChatStreamEvent,
+    ) -> Result<()> {
+        match &self.stream_buffer {
+            Some(stream_buffer) => stream_buffer.append(session_id, event).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn replay_chat_stream(
+        &self,
+        session_id: crate::domain::value_objects::SessionId,
+        last_event_id: u64,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<Vec<ChatStreamEvent>> {
+        let stream_buffer = self.stream_buffer.as_ref().ok_or_else(|| {
+            PlatformError::ValidationError("Resuming chat streams is not enabled".to_string())
+        })?;
+        let session_service = self.session_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("Session service not configured".to_string()))?;
+
+        // Only the session's owner may replay its stream
+        session_service.get_session(&session_id, &tenant_id, &user_id).await?;
+
+        stream_buffer.replay(session_id, last_event_id).await
+    }
+
     async fn publish_agent(&self, agent_id: AgentId, user_id: UserId) -> Result<()> {
         let mut agent = self
             .agent_repo
@@ -1755,11 +2649,12 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
 
         // Save the updated agent
         self.agent_repo.save(&agent).await?;
+        self.record_agent_change(&agent, &user_id, AgentChange::Published, None).await;
 
         Ok(())
     }
 
-    async fn unpublish_agent(&self, agent_id: AgentId, user_id: UserId) -> Result<()> {
+    async fn unpublish_agent(&self, agent_id: AgentId, user_id: UserId, fire_copies: bool) -> Result<()> {
         let mut agent = self
             .agent_repo
             .find_by_id(&agent_id)
@@ -1778,7 +2673,901 @@ impl AgentApplicationService for AgentApplicationServiceImpl {
 
         // Save the updated agent
         self.agent_repo.save(&agent).await?;
+        self.record_agent_change(&agent, &user_id, AgentChange::Unpublished, None).await;
+
+        if fire_copies {
+            let copies = self.agent_repo.find_by_source_agent(&agent_id).await?;
+            for mut copy in copies {
+                if !copy.is_employed() || copy.is_fired() {
+                    continue;
+                }
+                copy.fire().map_err(PlatformError::AgentValidationError)?;
+                self.agent_repo.save(&copy).await?;
+                self.record_agent_change(
+                    &copy,
+                    &user_id,
+                    AgentChange::Fired,
+                    Some(serde_json::json!({
+                        "reason": "source_unpublished",
+                        "source_agent_id": agent_id.0,
+                    })),
+                )
+                .await;
+            }
+        }
 
         Ok(())
     }
+
+    async fn generate_flow_from_description(
+        &self,
+        description: String,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<GeneratedFlowDto> {
+        use crate::domain::services::llm_service::ResponseFormat;
+        use crate::domain::value_objects::ChatMessage;
+
+        let description = description.trim().to_string();
+        if description.is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Flow description cannot be empty".to_string(),
+            ));
+        }
+
+        let flow_service = self.flow_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("Flow service not configured".to_string()))?;
+
+        let llm_service = self.llm_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?;
+
+        let llm_config_repo = self.llm_config_repo.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?;
+
+        // Prefer the tenant's default config, fall back to first available
+        let llm_config = match llm_config_repo.find_default_by_tenant(tenant_id).await? {
+            Some(config) => config,
+            None => llm_config_repo.find_by_tenant(tenant_id).await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| PlatformError::NotFound("No LLM configuration found for tenant".to_string()))?,
+        };
+
+        let messages = vec![
+            ChatMessage::new_system_message(FLOW_GENERATION_PROMPT.to_string()),
+            ChatMessage::new_user_message(description.clone()),
+        ];
+
+        let response = llm_service
+            .chat_completion(
+                &llm_config.model_config,
+                messages,
+                tenant_id.0,
+                Some(ResponseFormat {
+                    format_type: "json_object".to_string(),
+                    json_schema: None,
+                }),
+            )
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+
+        let draft = Self::parse_generated_flow(&response.content)?;
+
+        let name = draft.name.unwrap_or_else(|| description.chars().take(50).collect());
+
+        let flow = flow_service
+            .create_flow(tenant_id, name, Some(description), user_id)
+            .await?;
+
+        let version = flow_service
+            .create_version(
+                flow.id,
+                tenant_id,
+                draft.definition,
+                Some("Generated from description".to_string()),
+                user_id,
+            )
+            .await?;
+
+        Ok(GeneratedFlowDto {
+            flow_id: flow.id.0,
+            tenant_id: flow.tenant_id.0,
+            name: flow.name.0.clone(),
+            description: flow.description.clone(),
+            current_version: version.version.0,
+            status: format!("{:?}", flow.status),
+            definition: version.definition.to_json(),
+            confidence_score: draft.confidence_score,
+            created_by: flow.created_by.0,
+            created_at: flow.created_at.to_rfc3339(),
+        })
+    }
+
+    async fn get_preset_questions(
+        &self,
+        agent_id: AgentId,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<PresetQuestionsDto> {
+        let agent = self.find_usable_agent(agent_id, &user_id, tenant_id).await?;
+
+        Ok(PresetQuestionsDto {
+            agent_id: agent.id.0,
+            questions: agent.preset_questions,
+        })
+    }
+
+    async fn regenerate_preset_questions(
+        &self,
+        agent_id: AgentId,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<PresetQuestionsDto> {
+        let mut agent = self
+            .agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .ok_or_else(|| PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0)))?;
+
+        if agent.tenant_id != tenant_id {
+            return Err(PlatformError::AgentUnauthorized(
+                "Agent does not belong to your tenant".to_string(),
+            ));
+        }
+        self.verify_can_modify(&agent, &user_id).await?;
+
+        let llm_service = self.llm_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?;
+
+        let llm_config_selector = self.llm_config_selector.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?;
+
+        let questions = Self::generate_preset_questions(llm_service, llm_config_selector, &agent).await?;
+        agent
+            .set_preset_questions(questions)
+            .map_err(|e| PlatformError::AgentValidationError(e))?;
+
+        self.agent_repo.save(&agent).await?;
+        self.record_agent_change(
+            &agent,
+            &user_id,
+            AgentChange::Updated,
+            Some(serde_json::json!({ "preset_questions": agent.preset_questions })),
+        ).await;
+
+        Ok(PresetQuestionsDto {
+            agent_id: agent.id.0,
+            questions: agent.preset_questions,
+        })
+    }
+
+    async fn suggest_questions(
+        &self,
+        agent_id: AgentId,
+        session_id: crate::domain::value_objects::SessionId,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> Result<SuggestedQuestionsDto> {
+        use crate::domain::services::llm_service::ResponseFormat;
+        use crate::domain::value_objects::{ChatMessage, MessageRole};
+
+        let agent = self.find_usable_agent(agent_id, &user_id, tenant_id).await?;
+
+        let session_service = self.session_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("Session service not configured".to_string()))?;
+
+        let llm_service = self.llm_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?;
+
+        let llm_config_selector = self.llm_config_selector.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?;
+
+        let history = session_service
+            .get_session_messages(&session_id, &tenant_id, &user_id)
+            .await?;
+
+        let transcript = history
+            .iter()
+            .rev()
+            .filter(|m| matches!(m.message.role, MessageRole::User | MessageRole::Assistant))
+            .take(SUGGESTION_HISTORY_MESSAGES)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .map(|m| format!("{:?}: {}", m.message.role, m.message.get_text_content()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut context = format!("Assistant instructions:\n{}\n", agent.system_prompt);
+        if !agent.preset_questions.is_empty() {
+            context.push_str(&format!(
+                "\nExample questions for this assistant:\n{}\n",
+                agent.preset_questions.join("\n")
+            ));
+        }
+        if transcript.is_empty() {
+            context.push_str("\nThe conversation has not started yet.");
+        } else {
+            context.push_str(&format!("\nConversation so far:\n{}", transcript));
+        }
+
+        let llm_config = llm_config_selector
+            .select(tenant_id, agent.preferred_llm_config_id(), &agent.llm_fallback_strategy)
+            .await?;
+
+        let messages = vec![
+            ChatMessage::new_system_message(QUESTION_SUGGESTION_PROMPT.to_string()),
+            ChatMessage::new_user_message(context),
+        ];
+
+        let response = llm_service
+            .chat_completion(
+                &llm_config.model_config,
+                messages,
+                tenant_id.0,
+                Some(ResponseFormat {
+                    format_type: "json_object".to_string(),
+                    json_schema: None,
+                }),
+            )
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+
+        Ok(SuggestedQuestionsDto {
+            agent_id: agent.id.0,
+            session_id: session_id.0,
+            questions: Self::parse_suggested_questions(&response.content),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{
+        MockAgentAllocationRepository, MockAgentRepository, MockFlowRepository,
+        MockInterviewRecordRepository, MockMCPToolRepository, MockTenantSettingsRepository,
+        MockUserRepository, MockVectorConfigRepository,
+    };
+    use crate::domain::entities::TenantSettings;
+    use crate::domain::value_objects::Username;
+
+    const VALID_FLOW: &str = r#"{
+        "name": "Summarizer",
+        "workflow": {"graph": {
+            "nodes": [
+                {"id": "start", "parentId": null, "node_type": "start", "data": {"variables": []}, "position": {"x": 0, "y": 0}},
+                {"id": "answer", "parentId": null, "node_type": "answer", "data": {"answer": ""}, "position": {"x": 300, "y": 0}}
+            ],
+            "edges": [
+                {"id": "start-answer", "source": "start", "target": "answer", "sourceHandle": null, "targetHandle": null}
+            ]
+        }}
+    }"#;
+
+    #[test]
+    fn test_parse_generated_flow_clean_json() {
+        let draft = AgentApplicationServiceImpl::parse_generated_flow(VALID_FLOW).unwrap();
+
+        assert_eq!(draft.name.as_deref(), Some("Summarizer"));
+        assert_eq!(draft.definition.workflow.graph.nodes.len(), 2);
+        assert_eq!(draft.confidence_score, 1.0);
+    }
+
+    #[test]
+    fn test_parse_generated_flow_fenced_json_lowers_confidence() {
+        let content = format!("Here is your flow:\n```json\n{}\n```", VALID_FLOW);
+        let draft = AgentApplicationServiceImpl::parse_generated_flow(&content).unwrap();
+
+        assert!(draft.confidence_score < 1.0);
+    }
+
+    #[test]
+    fn test_parse_generated_flow_rejects_invalid_definition() {
+        let content = r#"{"workflow": {"graph": {"nodes": [], "edges": []}}}"#;
+
+        assert!(AgentApplicationServiceImpl::parse_generated_flow(content).is_err());
+    }
+
+    #[test]
+    fn test_parse_suggested_questions_json_object() {
+        let content = r#"{"questions": ["How do I start?", " ", "How do I start?", "What does it cost?"]}"#;
+        let questions = AgentApplicationServiceImpl::parse_suggested_questions(content);
+
+        assert_eq!(questions, vec!["How do I start?", "What does it cost?"]);
+    }
+
+    #[test]
+    fn test_parse_suggested_questions_caps_list_lines() {
+        let content = "1. One?\n2. Two?\n- Three?\n* Four?\n5) Five?\n6. Six?";
+        let questions = AgentApplicationServiceImpl::parse_suggested_questions(content);
+
+        assert_eq!(questions, vec!["One?", "Two?", "Three?", "Four?", "Five?"]);
+    }
+
+    #[test]
+    fn test_preview_messages_start_a_new_chat() {
+        let mut agent = Agent::new(TenantId::new(), "Helper".to_string(), "You are helpful".to_string(), UserId::new()).unwrap();
+        agent.greeting = Some("Hi there!".to_string());
+
+        let messages = AgentApplicationServiceImpl::preview_messages(&agent, "What can you do?".to_string());
+
+        let roles: Vec<_> = messages.iter().map(|m| m.role.clone()).collect();
+        assert_eq!(
+            roles,
+            vec![
+                crate::domain::value_objects::MessageRole::System,
+                crate::domain::value_objects::MessageRole::Assistant,
+                crate::domain::value_objects::MessageRole::User,
+            ]
+        );
+        assert_eq!(messages[2].get_text_content(), "What can you do?");
+    }
+
+    #[tokio::test]
+    async fn test_preview_chat_rejects_other_tenants_agent() {
+        let agent = Agent::new(TenantId::new(), "Helper".to_string(), "You are helpful".to_string(), UserId::new()).unwrap();
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo.expect_find_by_id().returning(move |_| Ok(Some(agent.clone())));
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        let result = service
+            .preview_chat(agent_id, "Hello".to_string(), UserId::new(), TenantId::new())
+            .await;
+        assert!(matches!(result, Err(PlatformError::AgentUnauthorized(_))));
+    }
+
+    fn api_key_service(
+        api_key: crate::domain::entities::APIKey,
+        agent_repo: MockAgentRepository,
+        user_repo: MockUserRepository,
+    ) -> AgentApplicationServiceImpl {
+        let mut api_key_repo = crate::domain::repositories::MockAPIKeyRepository::new();
+        api_key_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(api_key.clone())));
+        let mut allocation_repo = MockAgentAllocationRepository::new();
+        allocation_repo.expect_is_allocated().returning(|_, _| Ok(false));
+
+        AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(allocation_repo),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(user_repo),
+            Arc::new(MockInterviewRecordRepository::new()),
+        )
+        .with_api_key_repository(Arc::new(api_key_repo))
+    }
+
+    #[tokio::test]
+    async fn test_get_agent_by_api_key_resolves_single_scoped_agent() {
+        use crate::domain::value_objects::PermissionScope;
+
+        let tenant_id = TenantId::new();
+        let creator = User::new(
+            UserId::new(),
+            tenant_id,
+            Username::new("creator".to_string()).unwrap(),
+            "hash".to_string(),
+            None,
+        )
+        .unwrap();
+        let creator_id = creator.id;
+        let agent = Agent::new(tenant_id, "Helper".to_string(), "You are helpful".to_string(), creator_id).unwrap();
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo
+            .expect_find_by_id()
+            .withf(move |id| *id == agent_id)
+            .returning(move |_| Ok(Some(agent.clone())));
+        let mut user_repo = MockUserRepository::new();
+        user_repo
+            .expect_find_by_ids()
+            .returning(move |_| Ok(HashMap::from([(creator_id, creator.clone())])));
+
+        let api_key = crate::domain::entities::APIKey::new(
+            tenant_id,
+            creator_id,
+            "Chatbot".to_string(),
+            "hash".to_string(),
+            PermissionScope::new(vec![agent_id.0], vec![], vec![], vec![]),
+            None,
+        )
+        .unwrap();
+        let api_key_id = api_key.id;
+        let service = api_key_service(api_key, agent_repo, user_repo);
+
+        let detail = service.get_agent_by_api_key(api_key_id, creator_id).await.unwrap();
+        assert_eq!(detail.id, agent_id.0);
+
+        let result = service.get_agent_by_api_key(api_key_id, UserId::new()).await;
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_agent_by_api_key_requires_exactly_one_agent() {
+        use crate::domain::value_objects::PermissionScope;
+
+        let user_id = UserId::new();
+        for agent_ids in [vec![], vec![uuid::Uuid::new_v4(), uuid::Uuid::new_v4()]] {
+            let api_key = crate::domain::entities::APIKey::new(
+                TenantId::new(),
+                user_id,
+                "Chatbot".to_string(),
+                "hash".to_string(),
+                PermissionScope::new(agent_ids, vec![uuid::Uuid::new_v4()], vec![], vec![]),
+                None,
+            )
+            .unwrap();
+            let api_key_id = api_key.id;
+            let mut agent_repo = MockAgentRepository::new();
+            agent_repo.expect_find_by_id().never();
+            let service = api_key_service(api_key, agent_repo, MockUserRepository::new());
+
+            let result = service.get_agent_by_api_key(api_key_id, user_id).await;
+            assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_preset_questions_requires_employment_or_allocation() {
+        let tenant_id = TenantId::new();
+        let creator_id = UserId::new();
+        let allocated_id = UserId::new();
+        let stranger_id = UserId::new();
+
+        let mut agent = Agent::new(tenant_id, "Helper".to_string(), "You are helpful".to_string(), creator_id).unwrap();
+        agent.set_preset_questions(vec!["What can you do?".to_string()]).unwrap();
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo.expect_find_by_id().returning(move |_| Ok(Some(agent.clone())));
+
+        let mut allocation_repo = MockAgentAllocationRepository::new();
+        allocation_repo
+            .expect_is_allocated()
+            .returning(move |_, user_id| Ok(*user_id == allocated_id));
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(allocation_repo),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        let preset = service
+            .get_preset_questions(agent_id, allocated_id, tenant_id)
+            .await
+            .unwrap();
+        assert_eq!(preset.questions, vec!["What can you do?"]);
+
+        let result = service.get_preset_questions(agent_id, stranger_id, tenant_id).await;
+        assert!(matches!(result, Err(PlatformError::AgentUnauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_preset_questions_requires_creator() {
+        let tenant_id = TenantId::new();
+        let creator_id = UserId::new();
+
+        let agent = Agent::new(tenant_id, "Helper".to_string(), "You are helpful".to_string(), creator_id).unwrap();
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo.expect_find_by_id().returning(move |_| Ok(Some(agent.clone())));
+
+        // No LLM configured: the permission check must fail first
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        let result = service
+            .regenerate_preset_questions(agent_id, UserId::new(), tenant_id)
+            .await;
+        assert!(matches!(result, Err(PlatformError::AgentUnauthorized(_))));
+
+        let result = service
+            .regenerate_preset_questions(agent_id, creator_id, TenantId::new())
+            .await;
+        assert!(matches!(result, Err(PlatformError::AgentUnauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_agent_applies_tenant_system_prompt_limit() {
+        let tenant_id = TenantId::new();
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo.expect_save().times(1).returning(|_| Ok(()));
+
+        let mut settings_repo = MockTenantSettingsRepository::new();
+        settings_repo.expect_find_by_tenant().returning(move |_| {
+            let mut settings = TenantSettings::new(tenant_id);
+            settings.update_max_system_prompt_length(Some(10)).unwrap();
+            Ok(Some(settings))
+        });
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        )
+        .with_tenant_settings_repository(Arc::new(settings_repo));
+
+        let dto = |system_prompt: &str| CreateAgentDto {
+            name: "Helper".to_string(),
+            avatar: None,
+            greeting: None,
+            llm_config_id: None,
+            llm_fallback_strategy: None,
+            system_prompt: system_prompt.to_string(),
+            additional_settings: None,
+            preset_questions: Vec::new(),
+            generate_preset_questions: true,
+            knowledge_base_ids: Vec::new(),
+            mcp_tool_ids: Vec::new(),
+            flow_ids: Vec::new(),
+            price: None,
+        };
+
+        let result = service
+            .create_agent(dto("You are a helpful agent"), tenant_id, UserId::new())
+            .await;
+        assert!(matches!(result, Err(PlatformError::AgentValidationError(_))));
+
+        assert!(service.create_agent(dto("Be brief"), tenant_id, UserId::new()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_allocate_agent_rejects_past_expiry() {
+        // No repository expectations: the request must fail before any lookup
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(MockAgentRepository::new()),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        let result = service
+            .allocate_agent(
+                AgentId::new(),
+                UserId::new(),
+                AllocateAgentDto {
+                    expires_at: Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_agents_fetches_creators_in_one_query() {
+        let tenant_id = TenantId::new();
+        let viewer_id = UserId::new();
+        let creator_ids: Vec<UserId> = (0..3).map(|_| UserId::new()).collect();
+
+        let agents: Vec<Agent> = (0..10)
+            .map(|i| {
+                Agent::new(
+                    tenant_id,
+                    format!("Agent {}", i),
+                    "You are helpful".to_string(),
+                    creator_ids[i % creator_ids.len()],
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo
+            .expect_find_by_tenant_published_paginated()
+            .returning(move |_, _, _, _| Ok((agents.clone(), 10)));
+
+        let mut allocation_repo = MockAgentAllocationRepository::new();
+        allocation_repo.expect_is_allocated().returning(|_, _| Ok(false));
+
+        let mut user_repo = MockUserRepository::new();
+        user_repo.expect_find_by_id().never();
+        let expected_ids = creator_ids.clone();
+        user_repo
+            .expect_find_by_ids()
+            .times(1)
+            .withf(move |ids| ids.len() == expected_ids.len())
+            .returning(move |ids| {
+                Ok(ids
+                    .iter()
+                    .map(|id| {
+                        let username = Username::new(format!("user_{}", id.0.simple())).unwrap();
+                        let user = User::new(*id, tenant_id, username, "hash".to_string(), None).unwrap();
+                        (*id, user)
+                    })
+                    .collect())
+            });
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(allocation_repo),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(user_repo),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        let page = service
+            .list_agents(tenant_id, viewer_id, PaginationParams::default(), false, None)
+            .await
+            .unwrap();
+
+        assert_eq!(page.items.len(), 10);
+        assert!(page.items.iter().all(|card| card.creator_name.starts_with("user_")));
+    }
+
+    #[tokio::test]
+    async fn test_list_agents_with_keyword_searches_repository() {
+        let tenant_id = TenantId::new();
+        let viewer_id = UserId::new();
+        let creator_id = UserId::new();
+        let agent = Agent::new(
+            tenant_id,
+            "Sales assistant".to_string(),
+            "You close deals".to_string(),
+            creator_id,
+        )
+        .unwrap();
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo.expect_find_by_tenant_published_paginated().never();
+        agent_repo
+            .expect_search_by_keyword()
+            .times(1)
+            .withf(|_, keyword, _, _| keyword == "sales")
+            .returning(move |_, _, _, _| Ok((vec![agent.clone()], 1)));
+
+        let mut allocation_repo = MockAgentAllocationRepository::new();
+        allocation_repo.expect_is_allocated().returning(|_, _| Ok(false));
+
+        let mut user_repo = MockUserRepository::new();
+        user_repo.expect_find_by_ids().returning(move |_| {
+            let username = Username::new("seller".to_string()).unwrap();
+            let user = User::new(creator_id, tenant_id, username, "hash".to_string(), None).unwrap();
+            Ok(HashMap::from([(creator_id, user)]))
+        });
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(allocation_repo),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(user_repo),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        let page = service
+            .list_agents(
+                tenant_id,
+                viewer_id,
+                PaginationParams::default(),
+                false,
+                Some("sales".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].name, "Sales assistant");
+    }
+
+    #[tokio::test]
+    async fn test_unpublish_agent_fires_active_employed_copies() {
+        let tenant_id = TenantId::new();
+        let creator_id = UserId::new();
+        let mut source = Agent::new(
+            tenant_id,
+            "Support".to_string(),
+            "You answer tickets".to_string(),
+            creator_id,
+        )
+        .unwrap();
+        source.publish().unwrap();
+
+        let employed = source.copy_for_employment(UserId::new());
+        let mut fired = source.copy_for_employment(UserId::new());
+        fired.fire().unwrap();
+        let copied = source.copy_from(UserId::new());
+        let employed_id = employed.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        let found = source.clone();
+        agent_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        agent_repo
+            .expect_find_by_source_agent()
+            .times(1)
+            .returning(move |_| Ok(vec![employed.clone(), fired.clone(), copied.clone()]));
+        agent_repo.expect_save().times(1).withf(|agent| !agent.is_fired()).returning(|_| Ok(()));
+        agent_repo
+            .expect_save()
+            .times(1)
+            .withf(move |agent| agent.id == employed_id && agent.is_fired())
+            .returning(|_| Ok(()));
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        service.unpublish_agent(source.id, creator_id, true).await.unwrap();
+    }
+
+    /// Event store that rejects every append, e.g. on a version conflict
+    struct FailingEventStore;
+
+    #[async_trait]
+    impl EventStore for FailingEventStore {
+        async fn append(&self, _event: &dyn DomainEvent) -> Result<i64> {
+            Err(PlatformError::Conflict("Version conflict".to_string()))
+        }
+
+        async fn load_events(
+            &self,
+            _aggregate_id: uuid::Uuid,
+            _after_version: i64,
+        ) -> Result<Vec<Box<dyn DomainEvent>>> {
+            Ok(Vec::new())
+        }
+
+        async fn load_snapshot(
+            &self,
+            _aggregate_id: uuid::Uuid,
+        ) -> Result<Option<crate::domain::events::AggregateSnapshot>> {
+            Ok(None)
+        }
+
+        async fn save_snapshot(
+            &self,
+            _snapshot: crate::domain::events::AggregateSnapshot,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agent_change_succeeds_when_event_append_fails() {
+        let tenant_id = TenantId::new();
+        let creator_id = UserId::new();
+        let mut agent = Agent::new(
+            tenant_id,
+            "Support".to_string(),
+            "You answer tickets".to_string(),
+            creator_id,
+        )
+        .unwrap();
+        agent.publish().unwrap();
+
+        let mut agent_repo = MockAgentRepository::new();
+        let found = agent.clone();
+        agent_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        agent_repo.expect_save().times(1).withf(|agent| !agent.is_published).returning(|_| Ok(()));
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        )
+        .with_event_store(Arc::new(FailingEventStore));
+
+        service.unpublish_agent(agent.id, creator_id, false).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_created_agents_paginates_in_repository() {
+        let user_id = UserId::new();
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo
+            .expect_find_original_by_creator_paginated()
+            .times(1)
+            .withf(|_, offset, limit| *offset == 5 && *limit == 5)
+            .returning(|_, _, _| Ok((Vec::new(), 12)));
+
+        let mut user_repo = MockUserRepository::new();
+        user_repo.expect_find_by_ids().returning(|_| Ok(HashMap::new()));
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(agent_repo),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(user_repo),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+
+        let params = PaginationParams { page: Some(2), limit: Some(5) };
+        let page = service.list_created_agents(user_id, params).await.unwrap();
+
+        assert_eq!(page.total, 12);
+        assert_eq!(page.total_pages, 3);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_chat_stream_event_is_optional() {
+        let session_id = crate::domain::value_objects::SessionId::new();
+        let event = ChatStreamEvent { id: 1, event: "content".to_string(), data: "{}".to_string() };
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(MockAgentRepository::new()),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        );
+        service.buffer_chat_stream_event(session_id, event.clone()).await.unwrap();
+
+        let result = service
+            .replay_chat_stream(session_id, 0, UserId::new(), TenantId::new())
+            .await;
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+
+        let mut stream_buffer = crate::domain::services::MockChatStreamBuffer::new();
+        let expected = event.clone();
+        stream_buffer
+            .expect_append()
+            .times(1)
+            .withf(move |id, e| *id == session_id && *e == expected)
+            .returning(|_, _| Ok(()));
+
+        let service = AgentApplicationServiceImpl::new(
+            Arc::new(MockAgentRepository::new()),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockInterviewRecordRepository::new()),
+        )
+        .with_stream_buffer(Arc::new(stream_buffer));
+        service.buffer_chat_stream_event(session_id, event).await.unwrap();
+    }
 }
@@ -0,0 +1,504 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read, Write};
+use std::sync::Arc;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+
+use crate::{
+    application::{
+        dto::CreateAgentDto,
+        services::{AgentApplicationService, FileApplicationService},
+    },
+    domain::{
+        entities::Agent,
+        repositories::AgentRepository,
+        value_objects::{AgentId, LLMSelectionStrategy, TenantId, UserId},
+    },
+    error::{PlatformError, Result},
+};
+
+/// Schema identifier written to and expected in OpenAgents documents
+pub const OPEN_AGENTS_SCHEMA: &str = "openagents/v1";
+
+/// Name of the agent document inside a zip export
+const ZIP_DOCUMENT_NAME: &str = "agent.json";
+
+/// Largest avatar downloaded into a zip export
+const MAX_ASSET_SIZE: usize = 10 * 1024 * 1024;
+
+/// Serialization of an exported agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// A single OpenAgents JSON document
+    OpenAgents,
+    /// The OpenAgents document as `agent.json` plus the files it references
+    Zip,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::OpenAgents => "application/json",
+            ExportFormat::Zip => "application/zip",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::OpenAgents => "json",
+            ExportFormat::Zip => "zip",
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "openagents" | "json" => Ok(ExportFormat::OpenAgents),
+            "zip" => Ok(ExportFormat::Zip),
+            _ => Err(PlatformError::ValidationError(format!(
+                "Unsupported agent export format: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// A file bundled with the agent document in a zip export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAgentsAsset {
+    /// Agent field the file belongs to; only `avatar` is used
+    pub field: String,
+    /// Path of the file inside the archive
+    pub path: String,
+    pub content_type: String,
+}
+
+/// Platform-neutral description of an agent. Knowledge bases, MCP tools and
+/// flows are referenced by id and must exist on the importing platform.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAgentsDocument {
+    pub schema: String,
+    pub name: String,
+    pub avatar: Option<String>,
+    pub greeting: Option<String>,
+    pub system_prompt: String,
+    pub additional_settings: Option<String>,
+    #[serde(default)]
+    pub preset_questions: Vec<String>,
+    pub llm_config_id: Option<Uuid>,
+    #[serde(default)]
+    pub llm_fallback_strategy: LLMSelectionStrategy,
+    #[serde(default)]
+    pub knowledge_base_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub mcp_tool_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub flow_ids: Vec<Uuid>,
+    pub price: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<OpenAgentsAsset>,
+}
+
+impl OpenAgentsDocument {
+    pub fn from_agent(agent: &Agent) -> Self {
+        Self {
+            schema: OPEN_AGENTS_SCHEMA.to_string(),
+            name: agent.name.clone(),
+            avatar: agent.avatar.clone(),
+            greeting: agent.greeting.clone(),
+            system_prompt: agent.system_prompt.clone(),
+            additional_settings: agent.additional_settings.clone(),
+            preset_questions: agent.preset_questions.clone(),
+            llm_config_id: agent.llm_config_id.map(|id| id.0),
+            llm_fallback_strategy: agent.llm_fallback_strategy.clone(),
+            knowledge_base_ids: agent.knowledge_base_ids.iter().map(|id| id.0).collect(),
+            mcp_tool_ids: agent.mcp_tool_ids.iter().map(|id| id.0).collect(),
+            flow_ids: agent.flow_ids.iter().map(|id| id.0).collect(),
+            price: agent.price,
+            assets: Vec::new(),
+        }
+    }
+
+    pub fn from_json(data: &[u8]) -> Result<Self> {
+        let document: Self = serde_json::from_slice(data).map_err(|e| {
+            PlatformError::ValidationError(format!("Invalid OpenAgents document: {}", e))
+        })?;
+
+        if document.schema != OPEN_AGENTS_SCHEMA {
+            return Err(PlatformError::ValidationError(format!(
+                "Unsupported OpenAgents schema: {}",
+                document.schema
+            )));
+        }
+        Ok(document)
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec_pretty(self).map_err(|e| {
+            PlatformError::InternalError(format!("Failed to serialize agent: {}", e))
+        })
+    }
+
+    /// Creation request for the agent; preset questions are taken as
+    /// exported and never regenerated
+    pub fn into_create_dto(self) -> CreateAgentDto {
+        CreateAgentDto {
+            name: self.name,
+            avatar: self.avatar,
+            greeting: self.greeting,
+            llm_config_id: self.llm_config_id,
+            llm_fallback_strategy: Some(self.llm_fallback_strategy),
+            system_prompt: self.system_prompt,
+            additional_settings: self.additional_settings,
+            preset_questions: self.preset_questions,
+            generate_preset_questions: false,
+            knowledge_base_ids: self.knowledge_base_ids,
+            mcp_tool_ids: self.mcp_tool_ids,
+            flow_ids: self.flow_ids,
+            price: self.price,
+        }
+    }
+}
+
+/// Zip archive of `document` and its asset files, given as `(path, bytes)`
+pub fn write_zip(document: &OpenAgentsDocument, files: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let zip_error = |e: &dyn std::fmt::Display| {
+        PlatformError::InternalError(format!("Failed to write agent archive: {}", e))
+    };
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+
+    writer.start_file(ZIP_DOCUMENT_NAME, options).map_err(|e| zip_error(&e))?;
+    writer.write_all(&document.to_json()?).map_err(|e| zip_error(&e))?;
+
+    for (path, data) in files {
+        writer.start_file(path.as_str(), options).map_err(|e| zip_error(&e))?;
+        writer.write_all(data).map_err(|e| zip_error(&e))?;
+    }
+
+    Ok(writer.finish().map_err(|e| zip_error(&e))?.into_inner())
+}
+
+/// Agent document of a zip archive along with the asset files it lists
+pub fn read_zip(data: Vec<u8>) -> Result<(OpenAgentsDocument, Vec<(OpenAgentsAsset, Vec<u8>)>)> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        PlatformError::ValidationError(format!("Invalid agent archive: {}", e))
+    };
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).map_err(|e| invalid(&e))?;
+
+    let read_file = |archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, path: &str| {
+        let mut file = archive.by_name(path).map_err(|e| invalid(&format!("{}: {}", path, e)))?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents).map_err(|e| invalid(&e))?;
+        Ok::<_, PlatformError>(contents)
+    };
+
+    let document = OpenAgentsDocument::from_json(&read_file(&mut archive, ZIP_DOCUMENT_NAME)?)?;
+
+    let mut assets = Vec::with_capacity(document.assets.len());
+    for asset in &document.assets {
+        let contents = read_file(&mut archive, &asset.path)?;
+        assets.push((asset.clone(), contents));
+    }
+
+    Ok((document, assets))
+}
+
+/// Exports agents as OpenAgents documents
+#[async_trait]
+pub trait AgentExportService: Send + Sync {
+    /// Export an agent of the tenant created by the user
+    async fn export(
+        &self,
+        agent_id: AgentId,
+        format: ExportFormat,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<u8>>;
+}
+
+/// Creates agents from OpenAgents documents
+#[async_trait]
+pub trait AgentImportService: Send + Sync {
+    /// Create an agent owned by the user from an exported document
+    async fn import(
+        &self,
+        data: Vec<u8>,
+        format: ExportFormat,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<Agent>;
+}
+
+/// Import and export together, for wiring both behind one router
+pub trait AgentImportExportService: AgentImportService + AgentExportService {}
+
+impl<T: AgentImportService + AgentExportService> AgentImportExportService for T {}
+
+pub struct AgentImportExportServiceImpl {
+    agent_repo: Arc<dyn AgentRepository>,
+    agent_service: Arc<dyn AgentApplicationService>,
+    file_service: Option<Arc<dyn FileApplicationService>>,
+    client: reqwest::Client,
+}
+
+impl AgentImportExportServiceImpl {
+    pub fn new(
+        agent_repo: Arc<dyn AgentRepository>,
+        agent_service: Arc<dyn AgentApplicationService>,
+    ) -> Self {
+        Self {
+            agent_repo,
+            agent_service,
+            file_service: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set file service so avatars bundled in zip imports are uploaded;
+    /// without one the exported avatar URL is kept
+    pub fn with_file_service(mut self, file_service: Arc<dyn FileApplicationService>) -> Self {
+        self.file_service = Some(file_service);
+        self
+    }
+
+    async fn find_agent(&self, agent_id: AgentId) -> Result<Agent> {
+        self.agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .ok_or_else(|| PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0)))
+    }
+
+    /// Download an avatar to bundle with a zip export
+    async fn fetch_asset(&self, url: &str) -> Result<(Vec<u8>, String)> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| PlatformError::InternalError(format!("Failed to download {}: {}", url, e)))?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let data = response
+            .bytes()
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("Failed to download {}: {}", url, e)))?;
+
+        if data.len() > MAX_ASSET_SIZE {
+            return Err(PlatformError::ValidationError(format!(
+                "Asset {} exceeds {} bytes",
+                url, MAX_ASSET_SIZE
+            )));
+        }
+        Ok((data.to_vec(), content_type))
+    }
+
+    async fn export_zip(&self, mut document: OpenAgentsDocument) -> Result<Vec<u8>> {
+        let mut files = Vec::new();
+
+        if let Some(avatar) = document.avatar.clone().filter(|url| url.starts_with("http")) {
+            // The avatar URL stays in the document, so a failed download
+            // still yields a usable export
+            match self.fetch_asset(&avatar).await {
+                Ok((data, content_type)) => {
+                    let path = format!("assets/avatar{}", avatar_extension(&avatar));
+                    document.assets.push(OpenAgentsAsset {
+                        field: "avatar".to_string(),
+                        path: path.clone(),
+                        content_type,
+                    });
+                    files.push((path, data));
+                }
+                Err(e) => tracing::warn!("Exporting agent without its avatar file: {}", e),
+            }
+        }
+
+        write_zip(&document, &files)
+    }
+
+    async fn upload_assets(
+        &self,
+        document: &mut OpenAgentsDocument,
+        assets: Vec<(OpenAgentsAsset, Vec<u8>)>,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<()> {
+        let Some(file_service) = &self.file_service else {
+            return Ok(());
+        };
+
+        for (asset, data) in assets {
+            if asset.field != "avatar" {
+                continue;
+            }
+
+            let filename = asset.path.rsplit('/').next().unwrap_or("avatar").to_string();
+            let url = file_service
+                .upload_file(
+                    &tenant_id.to_string(),
+                    &user_id.to_string(),
+                    filename,
+                    asset.content_type,
+                    data,
+                )
+                .await?;
+            document.avatar = Some(url);
+        }
+        Ok(())
+    }
+}
+
+/// Extension of the file an avatar URL points at, with the leading dot
+fn avatar_extension(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext)
+        .filter(|ext| !ext.is_empty() && ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .map(|ext| format!(".{}", ext.to_ascii_lowercase()))
+        .unwrap_or_default()
+}
+
+#[async_trait]
+impl AgentExportService for AgentImportExportServiceImpl {
+    async fn export(
+        &self,
+        agent_id: AgentId,
+        format: ExportFormat,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<u8>> {
+        let agent = self.find_agent(agent_id).await?;
+
+        if agent.tenant_id != tenant_id {
+            return Err(PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0)));
+        }
+        if !agent.can_modify(&user_id) {
+            return Err(PlatformError::AgentUnauthorized(
+                "Only the creator can export this agent".to_string(),
+            ));
+        }
+
+        let document = OpenAgentsDocument::from_agent(&agent);
+        match format {
+            ExportFormat::OpenAgents => document.to_json(),
+            ExportFormat::Zip => self.export_zip(document).await,
+        }
+    }
+}
+
+#[async_trait]
+impl AgentImportService for AgentImportExportServiceImpl {
+    async fn import(
+        &self,
+        data: Vec<u8>,
+        format: ExportFormat,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<Agent> {
+        let document = match format {
+            ExportFormat::OpenAgents => OpenAgentsDocument::from_json(&data)?,
+            ExportFormat::Zip => {
+                let (mut document, assets) = read_zip(data)?;
+                self.upload_assets(&mut document, assets, tenant_id, user_id).await?;
+                document
+            }
+        };
+
+        let created = self
+            .agent_service
+            .create_agent(document.into_create_dto(), tenant_id, user_id)
+            .await?;
+
+        self.find_agent(AgentId::from_uuid(created.id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ConfigId, FlowId, MCPToolId};
+
+    fn sample_agent() -> Agent {
+        let mut agent = Agent::new(
+            TenantId::new(),
+            "Support".to_string(),
+            "You answer support tickets.".to_string(),
+            UserId::new(),
+        )
+        .unwrap();
+        agent.update_avatar(Some("https://cdn.example.com/avatars/support.PNG?v=2".to_string()));
+        agent.update_greeting(Some("Hi!".to_string()));
+        agent.update_llm_config(Some(ConfigId::new()));
+        agent.set_preset_questions(vec!["Where is my order?".to_string()]).unwrap();
+        agent.add_knowledge_base(ConfigId::new());
+        agent.add_mcp_tool(MCPToolId::new());
+        agent.add_flow(FlowId::new());
+        agent
+    }
+
+    #[test]
+    fn test_open_agents_round_trip() {
+        let agent = sample_agent();
+        let document = OpenAgentsDocument::from_agent(&agent);
+
+        let parsed = OpenAgentsDocument::from_json(&document.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, document);
+
+        let dto = parsed.into_create_dto();
+        assert_eq!(dto.system_prompt, agent.system_prompt);
+        assert_eq!(dto.preset_questions, agent.preset_questions);
+        assert_eq!(dto.knowledge_base_ids, vec![agent.knowledge_base_ids[0].0]);
+        assert_eq!(dto.mcp_tool_ids, vec![agent.mcp_tool_ids[0].0]);
+        assert_eq!(dto.flow_ids, vec![agent.flow_ids[0].0]);
+        assert_eq!(dto.llm_config_id, agent.llm_config_id.map(|id| id.0));
+        assert!(!dto.generate_preset_questions);
+    }
+
+    #[test]
+    fn test_zip_round_trip_with_assets() {
+        let mut document = OpenAgentsDocument::from_agent(&sample_agent());
+        document.assets.push(OpenAgentsAsset {
+            field: "avatar".to_string(),
+            path: "assets/avatar.png".to_string(),
+            content_type: "image/png".to_string(),
+        });
+
+        let archive = write_zip(&document, &[("assets/avatar.png".to_string(), vec![1, 2, 3])]).unwrap();
+        let (parsed, assets) = read_zip(archive).unwrap();
+
+        assert_eq!(parsed, document);
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].1, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rejects_unknown_schema() {
+        let mut document = OpenAgentsDocument::from_agent(&sample_agent());
+        document.schema = "openagents/v9".to_string();
+
+        let result = OpenAgentsDocument::from_json(&document.to_json().unwrap());
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_avatar_extension() {
+        assert_eq!(avatar_extension("https://cdn.example.com/a/support.PNG?v=2"), ".png");
+        assert_eq!(avatar_extension("https://cdn.example.com/avatar"), "");
+    }
+}
@@ -1,14 +1,18 @@
+use chrono::Utc;
 use serde_json::json;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::application::dto::{
-    APIKeyAuthContext, APIKeyDTO, APIKeyListResponse, CreateAPIKeyRequest, CreateAPIKeyResponse,
-    PermissionScopeDTO, UpdateAPIKeyRequest,
+    APIKeyAuthContext, APIKeyDTO, APIKeyDailyUsageDTO, APIKeyListResponse, APIKeyUsageHistoryResponse,
+    AuditEvent, CreateAPIKeyRequest, CreateAPIKeyResponse, PermissionScopeDTO, RotatedAPIKeyDTO,
+    UpdateAPIKeyRequest,
 };
 use crate::domain::entities::{AuditAction, AuditContext, ResourceType as AuditResourceType};
 use crate::domain::repositories::{APIKeyRepository, QueryOptions};
-use crate::domain::services::APIKeyService;
+use crate::domain::services::{APIKeyService, APIKeyUsageCounter};
 use crate::domain::value_objects::{
     APIKeyId, PermissionScope, ResourceType, TenantId, UserId,
 };
@@ -16,11 +20,15 @@ use crate::error::{PlatformError, Result};
 
 use super::AuditApplicationService;
 
+/// Days covered by the usage history, today included
+pub const USAGE_HISTORY_DAYS: u64 = 30;
+
 /// Application service for API key management
 pub struct APIKeyApplicationService {
     api_key_service: Arc<dyn APIKeyService>,
     repository: Arc<dyn APIKeyRepository>,
     audit_service: Arc<AuditApplicationService>,
+    usage_counter: Option<Arc<dyn APIKeyUsageCounter>>,
 }
 
 impl APIKeyApplicationService {
@@ -34,9 +42,17 @@ impl APIKeyApplicationService {
             api_key_service,
             repository,
             audit_service,
+            usage_counter: None,
         }
     }
 
+    /// Count usage and enforce per-key rate limits with `usage_counter`.
+    /// Without one, only `last_used_at` is tracked and limits are not enforced.
+    pub fn with_usage_counter(mut self, usage_counter: Arc<dyn APIKeyUsageCounter>) -> Self {
+        self.usage_counter = Some(usage_counter);
+        self
+    }
+
     /// Create a new API key
     pub async fn create_api_key(
         &self,
@@ -62,6 +78,7 @@ impl APIKeyApplicationService {
                 request.name.clone(),
                 permission_scope,
                 request.expires_at,
+                request.rate_limit_rpm,
             )
             .await?;
 
@@ -70,19 +87,20 @@ impl APIKeyApplicationService {
             "api_key_id": api_key.id.0,
             "name": api_key.name,
             "expires_at": api_key.expires_at,
+            "rate_limit_rpm": api_key.rate_limit_rpm,
         });
 
-        self.audit_service
-            .log_event(
-                tenant_id.0,
-                Some(user_id.0),
-                AuditAction::Create,
-                AuditResourceType::Custom("api_key".to_string()),
-                Some(api_key.id.0),
-                Some(details),
-                context,
-            )
-            .await?;
+        let event = AuditEvent::new(
+            tenant_id.0,
+            AuditAction::Create,
+            AuditResourceType::Custom("api_key".to_string()),
+        )
+        .with_user_id(user_id.0)
+        .with_resource_id(api_key.id.0)
+        .with_details(details)
+        .with_context(context);
+
+        self.audit_service.record_event(event).await?;
 
         // Convert to response DTO
         Ok(CreateAPIKeyResponse {
@@ -97,6 +115,7 @@ impl APIKeyApplicationService {
             },
             enabled: api_key.enabled,
             expires_at: api_key.expires_at,
+            rate_limit_rpm: api_key.rate_limit_rpm,
             created_at: api_key.created_at,
         })
     }
@@ -119,25 +138,7 @@ impl APIKeyApplicationService {
 
         let result = self.repository.find_by_user(user_id, options).await?;
 
-        let items = result
-            .items
-            .into_iter()
-            .map(|api_key| APIKeyDTO {
-                id: api_key.id.0,
-                name: api_key.name,
-                permission_scope: PermissionScopeDTO {
-                    agent_ids: api_key.permission_scope.agent_ids,
-                    flow_ids: api_key.permission_scope.flow_ids,
-                    mcp_tool_ids: api_key.permission_scope.mcp_tool_ids,
-                    vector_store_ids: api_key.permission_scope.vector_store_ids,
-                },
-                enabled: api_key.enabled,
-                expires_at: api_key.expires_at,
-                last_used_at: api_key.last_used_at,
-                created_at: api_key.created_at,
-                updated_at: api_key.updated_at,
-            })
-            .collect();
+        let items = result.items.into_iter().map(APIKeyDTO::from).collect();
 
         Ok(APIKeyListResponse {
             items,
@@ -162,21 +163,7 @@ impl APIKeyApplicationService {
             ));
         }
 
-        Ok(APIKeyDTO {
-            id: api_key.id.0,
-            name: api_key.name,
-            permission_scope: PermissionScopeDTO {
-                agent_ids: api_key.permission_scope.agent_ids,
-                flow_ids: api_key.permission_scope.flow_ids,
-                mcp_tool_ids: api_key.permission_scope.mcp_tool_ids,
-                vector_store_ids: api_key.permission_scope.vector_store_ids,
-            },
-            enabled: api_key.enabled,
-            expires_at: api_key.expires_at,
-            last_used_at: api_key.last_used_at,
-            created_at: api_key.created_at,
-            updated_at: api_key.updated_at,
-        })
+        Ok(APIKeyDTO::from(api_key))
     }
 
     /// Update an API key
@@ -225,6 +212,12 @@ impl APIKeyApplicationService {
             changes.push(format!("expires_at: {}", expires_at));
         }
 
+        // Update rate limit if provided; 0 removes it
+        if let Some(rate_limit_rpm) = request.rate_limit_rpm {
+            api_key.update_rate_limit((rate_limit_rpm > 0).then_some(rate_limit_rpm))?;
+            changes.push(format!("rate_limit_rpm: {}", rate_limit_rpm));
+        }
+
         // Save the updated API key
         self.repository.update(&api_key).await?;
 
@@ -234,32 +227,69 @@ impl APIKeyApplicationService {
             "changes": changes,
         });
 
-        self.audit_service
-            .log_event(
-                api_key.tenant_id.0,
-                Some(user_id.0),
-                AuditAction::Update,
-                AuditResourceType::Custom("api_key".to_string()),
-                Some(api_key.id.0),
-                Some(details),
-                context,
-            )
-            .await?;
+        let event = AuditEvent::new(
+            api_key.tenant_id.0,
+            AuditAction::Update,
+            AuditResourceType::Custom("api_key".to_string()),
+        )
+        .with_user_id(user_id.0)
+        .with_resource_id(api_key.id.0)
+        .with_details(details)
+        .with_context(context);
+
+        self.audit_service.record_event(event).await?;
+
+        Ok(APIKeyDTO::from(api_key))
+    }
+
+    /// Rotate an API key's token, keeping its permissions
+    pub async fn rotate_api_key(
+        &self,
+        id: APIKeyId,
+        user_id: UserId,
+        context: Option<AuditContext>,
+    ) -> Result<RotatedAPIKeyDTO> {
+        let api_key = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| PlatformError::NotFound("API key not found".to_string()))?;
+
+        // Verify ownership
+        if !api_key.belongs_to_user(&user_id) {
+            return Err(PlatformError::Forbidden(
+                "You do not have permission to rotate this API key".to_string(),
+            ));
+        }
+
+        let (api_key, token) = self.api_key_service.rotate_api_key(api_key).await?;
+
+        // Log the rotation event
+        let details = json!({
+            "api_key_id": api_key.id.0,
+            "name": api_key.name,
+        });
 
-        Ok(APIKeyDTO {
+        let event = AuditEvent::new(
+            api_key.tenant_id.0,
+            AuditAction::Custom("api_key.rotated".to_string()),
+            AuditResourceType::Custom("api_key".to_string()),
+        )
+        .with_user_id(user_id.0)
+        .with_resource_id(api_key.id.0)
+        .with_details(details)
+        .with_context(context);
+
+        // The old token no longer works, so the new one must reach the caller
+        if let Err(e) = self.audit_service.record_event(event).await {
+            tracing::error!("Failed to record rotation of API key {}: {}", api_key.id.0, e);
+        }
+
+        Ok(RotatedAPIKeyDTO {
             id: api_key.id.0,
             name: api_key.name,
-            permission_scope: PermissionScopeDTO {
-                agent_ids: api_key.permission_scope.agent_ids,
-                flow_ids: api_key.permission_scope.flow_ids,
-                mcp_tool_ids: api_key.permission_scope.mcp_tool_ids,
-                vector_store_ids: api_key.permission_scope.vector_store_ids,
-            },
-            enabled: api_key.enabled,
-            expires_at: api_key.expires_at,
-            last_used_at: api_key.last_used_at,
-            created_at: api_key.created_at,
-            updated_at: api_key.updated_at,
+            new_token: token.into_string(),
+            rotated_at: api_key.updated_at,
         })
     }
 
@@ -292,17 +322,17 @@ impl APIKeyApplicationService {
             "name": api_key.name,
         });
 
-        self.audit_service
-            .log_event(
-                api_key.tenant_id.0,
-                Some(user_id.0),
-                AuditAction::Delete,
-                AuditResourceType::Custom("api_key".to_string()),
-                Some(api_key.id.0),
-                Some(details),
-                context,
-            )
-            .await?;
+        let event = AuditEvent::new(
+            api_key.tenant_id.0,
+            AuditAction::Delete,
+            AuditResourceType::Custom("api_key".to_string()),
+        )
+        .with_user_id(user_id.0)
+        .with_resource_id(api_key.id.0)
+        .with_details(details)
+        .with_context(context);
+
+        self.audit_service.record_event(event).await?;
 
         Ok(())
     }
@@ -372,6 +402,77 @@ impl APIKeyApplicationService {
                 mcp_tool_ids: api_key.permission_scope.mcp_tool_ids,
                 vector_store_ids: api_key.permission_scope.vector_store_ids,
             },
+            rate_limit_rpm: api_key.rate_limit_rpm,
+        })
+    }
+
+    /// Take one request from the key's per-minute allowance. Returns how long
+    /// to wait when it is used up; keys without a limit are always allowed.
+    pub async fn acquire_request(&self, context: &APIKeyAuthContext) -> Result<Option<Duration>> {
+        match (&self.usage_counter, context.rate_limit_rpm) {
+            (Some(counter), Some(rate_limit_rpm)) => {
+                counter
+                    .acquire(APIKeyId(context.api_key_id), rate_limit_rpm)
+                    .await
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Count a request and the LLM tokens it consumed against the key
+    pub async fn record_usage(&self, api_key_id: APIKeyId, tokens_used: u64) -> Result<()> {
+        match &self.usage_counter {
+            Some(counter) => counter.record(api_key_id, tokens_used, Utc::now()).await,
+            None => self.update_last_used(api_key_id).await,
+        }
+    }
+
+    /// Daily requests and tokens of a key over the last `USAGE_HISTORY_DAYS`
+    /// days, oldest first, with zeroes for days without usage
+    pub async fn get_usage_history(
+        &self,
+        id: APIKeyId,
+        user_id: UserId,
+    ) -> Result<APIKeyUsageHistoryResponse> {
+        let api_key = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| PlatformError::NotFound("API key not found".to_string()))?;
+
+        // Verify ownership
+        if !api_key.belongs_to_user(&user_id) {
+            return Err(PlatformError::Forbidden(
+                "You do not have permission to access this API key".to_string(),
+            ));
+        }
+
+        let today = Utc::now().date_naive();
+        let since = today - chrono::Days::new(USAGE_HISTORY_DAYS - 1);
+        let mut usage: HashMap<_, _> = self
+            .repository
+            .find_daily_usage(id, since)
+            .await?
+            .into_iter()
+            .map(|day| (day.date, day))
+            .collect();
+
+        let days = since
+            .iter_days()
+            .take_while(|date| *date <= today)
+            .map(|date| match usage.remove(&date) {
+                Some(day) => APIKeyDailyUsageDTO::from(day),
+                None => APIKeyDailyUsageDTO {
+                    date,
+                    request_count: 0,
+                    tokens_used: 0,
+                },
+            })
+            .collect();
+
+        Ok(APIKeyUsageHistoryResponse {
+            api_key_id: api_key.id.0,
+            days,
         })
     }
 
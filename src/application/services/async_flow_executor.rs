@@ -0,0 +1,234 @@
+use ring::hmac;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{
+    application::services::{FlowApplicationService, FlowExecutionResult},
+    domain::{
+        entities::FlowExecution,
+        repositories::FlowExecutionRepository,
+        services::ensure_public_http_url,
+        value_objects::{FlowExecutionId, FlowId, TenantId, UserId},
+    },
+    error::{PlatformError, Result},
+};
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body>` on execution callbacks
+pub const SIGNATURE_HEADER: &str = "X-Avalon-Signature";
+
+/// Delivery attempts per callback before it is given up
+const CALLBACK_ATTEMPTS: u32 = 3;
+
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Executions still pending or running this long after they started were
+/// left behind by a stopped instance. Younger ones may belong to another
+/// instance that is still running them.
+pub const ORPHANED_EXECUTION_AGE: Duration = Duration::from_secs(3600);
+
+/// Runs flows in the background and reports the outcome to a callback URL,
+/// so long-running flows do not hold the HTTP request open
+pub struct AsyncFlowExecutor {
+    flow_service: Arc<dyn FlowApplicationService>,
+    http_client: reqwest::Client,
+    signing_key: Option<hmac::Key>,
+}
+
+impl AsyncFlowExecutor {
+    pub fn new(flow_service: Arc<dyn FlowApplicationService>) -> Self {
+        Self {
+            flow_service,
+            http_client: reqwest::Client::builder()
+                .timeout(CALLBACK_TIMEOUT)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+            signing_key: None,
+        }
+    }
+
+    /// Set the secret callbacks are signed with; callbacks are refused without one
+    pub fn with_callback_secret(mut self, secret: &str) -> Self {
+        self.signing_key = (!secret.is_empty()).then(|| hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes()));
+        self
+    }
+
+    async fn validate_callback_url(&self, callback_url: &str) -> Result<()> {
+        if self.signing_key.is_none() {
+            return Err(PlatformError::ValidationError(
+                "Flow execution callbacks are not configured".to_string(),
+            ));
+        }
+        ensure_public_http_url(callback_url).await?;
+        Ok(())
+    }
+
+    /// Fail the executions a previous run of the server left pending or
+    /// running; their background tasks died with it
+    pub async fn fail_orphaned_executions(
+        execution_repository: &dyn FlowExecutionRepository,
+    ) -> Result<u64> {
+        let started_before = chrono::Utc::now()
+            - chrono::Duration::from_std(ORPHANED_EXECUTION_AGE).unwrap_or_default();
+        execution_repository
+            .fail_unfinished(started_before, "Execution interrupted by a server restart")
+            .await
+    }
+
+    /// Start the flow and return the execution ID right away. The flow runs
+    /// in a background task; when it ends the `FlowExecutionResult` is posted
+    /// to `callback_url`, if given.
+    #[tracing::instrument(skip(self, input_data), fields(flow_id = %flow_id, tenant_id = %tenant_id))]
+    pub async fn execute_async(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        input_data: Option<Value>,
+        callback_url: Option<String>,
+    ) -> Result<FlowExecutionId> {
+        if let Some(ref callback_url) = callback_url {
+            self.validate_callback_url(callback_url).await?;
+        }
+
+        let execution = self
+            .flow_service
+            .start_execution(flow_id, tenant_id, user_id, input_data)
+            .await?;
+        let execution_id = execution.id;
+
+        let flow_service = self.flow_service.clone();
+        let http_client = self.http_client.clone();
+        let signing_key = self.signing_key.clone();
+        tokio::spawn(async move {
+            let result = Self::run_to_end(flow_service, execution).await;
+
+            if let (Some(callback_url), Some(signing_key)) = (callback_url, signing_key) {
+                Self::deliver_callback(&http_client, &signing_key, &callback_url, &result).await;
+            }
+        });
+
+        Ok(execution_id)
+    }
+
+    async fn run_to_end(
+        flow_service: Arc<dyn FlowApplicationService>,
+        execution: FlowExecution,
+    ) -> FlowExecutionResult {
+        let execution_id = execution.id;
+        let tenant_id = execution.tenant_id;
+
+        match flow_service.finish_execution(execution).await {
+            Ok(execution) => FlowExecutionResult::from_execution(0, &execution),
+            Err(e) => {
+                // Timed out executions are saved before the error is returned
+                match flow_service.get_execution_status(execution_id, tenant_id).await {
+                    Ok(execution) if execution.is_terminal() => {
+                        FlowExecutionResult::from_execution(0, &execution)
+                    }
+                    _ => {
+                        tracing::error!(execution_id = %execution_id.0, "Async flow execution failed: {}", e);
+                        FlowExecutionResult {
+                            execution_id: Some(execution_id.0),
+                            ..FlowExecutionResult::from_error(0, e.to_string())
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn deliver_callback(
+        http_client: &reqwest::Client,
+        signing_key: &hmac::Key,
+        callback_url: &str,
+        result: &FlowExecutionResult,
+    ) {
+        let body = match serde_json::to_vec(result) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to serialize flow execution callback: {}", e);
+                return;
+            }
+        };
+        let signature = sign(signing_key, &body);
+
+        // The host is resolved again, in case its address changed since the
+        // URL was accepted
+        if let Err(e) = ensure_public_http_url(callback_url).await {
+            tracing::warn!(callback_url, "Flow execution callback refused: {}", e);
+            return;
+        }
+
+        for attempt in 1..=CALLBACK_ATTEMPTS {
+            let response = http_client
+                .post(callback_url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.clone())
+                .send()
+                .await;
+
+            match response {
+                Ok(response) if response.status().is_success() => return,
+                // The receiver rejected the payload; resending it will not help
+                Ok(response) if response.status().is_client_error() => {
+                    tracing::warn!(
+                        callback_url,
+                        status = response.status().as_u16(),
+                        "Flow execution callback rejected"
+                    );
+                    return;
+                }
+                Ok(response) => tracing::warn!(
+                    callback_url,
+                    attempt,
+                    status = response.status().as_u16(),
+                    "Flow execution callback failed"
+                ),
+                Err(e) => tracing::warn!(callback_url, attempt, "Flow execution callback failed: {}", e),
+            }
+
+            if attempt < CALLBACK_ATTEMPTS {
+                tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+            }
+        }
+    }
+}
+
+/// Value of the signature header for a callback body
+pub fn sign(key: &hmac::Key, body: &[u8]) -> String {
+    let tag = hmac::sign(key, body);
+    let hex: String = tag.as_ref().iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256={}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_matches_rfc_4231_vector() {
+        // RFC 4231 test case 2
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"Jefe");
+        assert_eq!(
+            sign(&key, b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fail_orphaned_executions_spares_recent_ones() {
+        let mut repo = crate::domain::repositories::MockFlowExecutionRepository::new();
+        repo.expect_fail_unfinished()
+            .withf(|started_before, _| {
+                let age = chrono::Utc::now() - *started_before;
+                age >= chrono::Duration::from_std(ORPHANED_EXECUTION_AGE).unwrap()
+            })
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        assert_eq!(AsyncFlowExecutor::fail_orphaned_executions(&repo).await.unwrap(), 2);
+    }
+}
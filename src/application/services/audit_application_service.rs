@@ -3,19 +3,49 @@ use serde_json::Value;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::application::dto::{AuditEvent, AuditEventDto, AuditQuery, PaginatedResponse};
 use crate::domain::entities::{AuditAction, AuditContext, AuditLog, ResourceType};
+use crate::domain::events::EventStore;
 use crate::domain::repositories::{AuditLogFilter, AuditStatistics};
 use crate::domain::services::AuditService;
-use crate::error::Result;
+use crate::error::{PlatformError, Result};
 
 /// Application service for audit logging
 pub struct AuditApplicationService {
     audit_service: Arc<dyn AuditService>,
+    event_store: Option<Arc<dyn EventStore>>,
 }
 
 impl AuditApplicationService {
     pub fn new(audit_service: Arc<dyn AuditService>) -> Self {
-        Self { audit_service }
+        Self {
+            audit_service,
+            event_store: None,
+        }
+    }
+
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Rebuild the audit trail of a resource by replaying its domain events
+    pub async fn reconstruct_audit_trail(
+        &self,
+        tenant_id: Uuid,
+        resource_id: Uuid,
+    ) -> Result<Vec<AuditLog>> {
+        let event_store = self.event_store.as_ref().ok_or_else(|| {
+            PlatformError::InternalError("Event store is not configured".to_string())
+        })?;
+
+        let events = event_store.load_events(resource_id, 0).await?;
+
+        Ok(events
+            .iter()
+            .filter_map(|event| event.audit_entry())
+            .filter(|entry| entry.tenant_id == tenant_id)
+            .collect())
     }
 
     /// Log an audit event
@@ -42,6 +72,47 @@ impl AuditApplicationService {
             .await
     }
 
+    /// Record a structured audit event
+    pub async fn record_event(&self, event: AuditEvent) -> Result<Uuid> {
+        self.log_event(
+            event.tenant_id,
+            event.user_id,
+            event.action,
+            event.resource_type,
+            event.resource_id,
+            event.details,
+            event.context,
+        )
+        .await
+    }
+
+    /// Search audit events, newest first, one page at a time
+    pub async fn search_events(
+        &self,
+        query: AuditQuery,
+    ) -> Result<PaginatedResponse<AuditEventDto>> {
+        let page = query.page.max(1);
+        let limit = query.limit.max(1);
+
+        let mut filter = AuditLogFilter::new(query.tenant_id)
+            .with_pagination(limit, (page - 1) * limit);
+        filter.user_id = query.user_id;
+        filter.action = query.action;
+        filter.resource_type = query.resource_type;
+        filter.start_date = query.start_time;
+        filter.end_date = query.end_time;
+
+        let logs = self.query_logs(&filter).await?;
+        let total = self.count_logs(&filter).await?;
+
+        Ok(PaginatedResponse::new(
+            logs.iter().map(AuditEventDto::from).collect(),
+            total,
+            page,
+            limit,
+        ))
+    }
+
     /// Query audit logs with filters
     pub async fn query_logs(&self, filter: &AuditLogFilter) -> Result<Vec<AuditLog>> {
         self.audit_service.query_logs(filter).await
@@ -205,6 +276,83 @@ mod tests {
         assert_eq!(total, 100);
     }
 
+    #[tokio::test]
+    async fn test_record_event_forwards_fields() {
+        let mut mock_service = MockAuditServiceImpl::new();
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let agent_id = Uuid::new_v4();
+        let log_id = Uuid::new_v4();
+
+        mock_service
+            .expect_log_event()
+            .times(1)
+            .withf(move |t, u, action, resource_type, r, _, _| {
+                *t == tenant_id
+                    && *u == Some(user_id)
+                    && *action == AuditAction::Delete
+                    && *resource_type == ResourceType::Custom("agent".to_string())
+                    && *r == Some(agent_id)
+            })
+            .returning(move |_, _, _, _, _, _, _| Ok(log_id));
+
+        let service = AuditApplicationService::new(Arc::new(mock_service));
+        let event = AuditEvent::new(
+            tenant_id,
+            AuditAction::Delete,
+            ResourceType::Custom("agent".to_string()),
+        )
+            .with_user_id(user_id)
+            .with_resource_id(agent_id);
+
+        assert_eq!(service.record_event(event).await.unwrap(), log_id);
+    }
+
+    #[tokio::test]
+    async fn test_search_events_pages_from_one() {
+        let mut mock_service = MockAuditServiceImpl::new();
+        let tenant_id = Uuid::new_v4();
+        let start_time = Utc::now() - chrono::Duration::days(1);
+
+        mock_service
+            .expect_query_logs()
+            .times(1)
+            .withf(move |filter: &AuditLogFilter| {
+                filter.limit == Some(20)
+                    && filter.offset == Some(20)
+                    && filter.start_date == Some(start_time)
+                    && filter.end_date.is_none()
+                    && filter.action == Some(AuditAction::Execute)
+            })
+            .returning(move |_| {
+                Ok(vec![AuditLog::new(
+                    tenant_id,
+                    None,
+                    AuditAction::Execute,
+                    ResourceType::FlowExecution,
+                    None,
+                )])
+            });
+
+        mock_service
+            .expect_count_logs()
+            .times(1)
+            .returning(|_| Ok(21));
+
+        let service = AuditApplicationService::new(Arc::new(mock_service));
+        let mut query = AuditQuery::new(tenant_id);
+        query.page = 2;
+        query.limit = 20;
+        query.action = Some(AuditAction::Execute);
+        query.start_time = Some(start_time);
+
+        let page = service.search_events(query).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].action, "execute");
+        assert_eq!(page.total, 21);
+        assert_eq!(page.total_pages, 2);
+    }
+
     #[tokio::test]
     async fn test_query_logs_paginated_total_count_accuracy() {
         let mut mock_service = MockAuditServiceImpl::new();
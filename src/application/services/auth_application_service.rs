@@ -1,14 +1,14 @@
 use async_trait::async_trait;
-use chrono::Duration;
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 use std::sync::Arc;
 
 use crate::{
     domain::{
-        entities::{User, Tenant},
-        repositories::{UserRepository, TenantRepository},
+        entities::{RefreshToken, RefreshTokenConfig, User, Tenant},
+        repositories::{RefreshTokenRepository, UserRepository, TenantRepository},
         services::AuthenticationDomainService,
-        value_objects::{LoginCredentials, Password, JwtToken, TokenClaims},
+        value_objects::{LoginCredentials, Password, JwtToken, TokenClaims, UserId},
         events::{
             UserAuthenticatedEvent, UserAuthenticationFailedEvent, 
             UserLoggedOutEvent, TokenRefreshedEvent, PasswordChangedEvent
@@ -17,7 +17,8 @@ use crate::{
     application::dto::{
         LoginRequest, LoginResponse, UserInfo, RefreshTokenRequest, 
         RefreshTokenResponse, LogoutRequest, LogoutResponse,
-        ChangePasswordRequest, ChangePasswordResponse, AuthContext, TenantContext
+        ChangePasswordRequest, ChangePasswordResponse, AuthContext, TenantContext,
+        RefreshTokenDto, TokenPairDto,
     },
     error::{PlatformError, Result},
 };
@@ -41,6 +42,20 @@ pub trait AuthApplicationService: Send + Sync {
         ip_address: Option<String>,
     ) -> Result<(RefreshTokenResponse, TokenRefreshedEvent)>;
 
+    /// Issue a long-lived refresh token for the user
+    async fn issue_refresh_token(&self, user_id: Uuid) -> Result<RefreshTokenDto>;
+
+    /// Exchange a refresh token for a new short-lived access token, rotating
+    /// the refresh token if configured to
+    async fn refresh_access_token(&self, refresh_token: &str) -> Result<TokenPairDto>;
+
+    /// Revoke one of the user's refresh tokens
+    async fn revoke_refresh_token(&self, token_id: Uuid, user_id: Uuid) -> Result<()>;
+
+    /// Revoke all of the user's refresh tokens, returning how many were revoked.
+    /// Access tokens already issued stay valid until they expire.
+    async fn revoke_all_tokens(&self, user_id: Uuid) -> Result<u64>;
+
     /// Logout user and revoke token
     async fn logout(
         &self,
@@ -76,6 +91,8 @@ pub struct AuthApplicationServiceImpl {
     tenant_repository: Arc<dyn TenantRepository>,
     auth_domain_service: Arc<dyn AuthenticationDomainService>,
     default_token_expiry: Duration,
+    refresh_token_repository: Option<Arc<dyn RefreshTokenRepository>>,
+    refresh_token_config: RefreshTokenConfig,
 }
 
 impl AuthApplicationServiceImpl {
@@ -90,12 +107,72 @@ impl AuthApplicationServiceImpl {
             tenant_repository,
             auth_domain_service,
             default_token_expiry: default_token_expiry.unwrap_or(Duration::hours(24)),
+            refresh_token_repository: None,
+            refresh_token_config: RefreshTokenConfig::default(),
+        }
+    }
+
+    /// Enable refresh tokens; login then returns one alongside the access token
+    pub fn with_refresh_tokens(
+        mut self,
+        repository: Arc<dyn RefreshTokenRepository>,
+        config: RefreshTokenConfig,
+    ) -> Self {
+        self.refresh_token_repository = Some(repository);
+        self.refresh_token_config = config;
+        self
+    }
+
+    fn refresh_token_repository(&self) -> Result<&Arc<dyn RefreshTokenRepository>> {
+        self.refresh_token_repository.as_ref().ok_or_else(|| {
+            PlatformError::ServiceUnavailable("Refresh tokens are not enabled".to_string())
+        })
+    }
+
+    async fn create_refresh_token(&self, user: &User) -> Result<RefreshTokenDto> {
+        let (refresh_token, token) = RefreshToken::issue(
+            user.id,
+            user.tenant_id,
+            Duration::days(self.refresh_token_config.ttl_days as i64),
+        )?;
+
+        self.refresh_token_repository()?.save(&refresh_token).await?;
+
+        Ok(RefreshTokenDto {
+            id: refresh_token.id,
+            refresh_token: token,
+            expires_at: refresh_token.expires_at,
+        })
+    }
+
+    /// With rotation a revoked token should never come back; if it does it
+    /// has probably leaked, so cut off every session of the user
+    async fn reject_reused_refresh_token(&self, user_id: &UserId) -> PlatformError {
+        if self.refresh_token_config.rotate {
+            let revoked = match self.refresh_token_repository() {
+                Ok(repository) => repository.revoke_all_by_user(user_id).await,
+                Err(e) => Err(e),
+            };
+            match revoked {
+                Ok(revoked) => tracing::warn!(
+                    user_id = %user_id,
+                    revoked,
+                    "Revoked refresh token reused, revoking all refresh tokens of the user"
+                ),
+                Err(e) => return e,
+            }
         }
+        PlatformError::AuthenticationFailed("Refresh token has been revoked".to_string())
     }
 }
 
 #[async_trait]
 impl AuthApplicationService for AuthApplicationServiceImpl {
+    // Never record the request itself: it carries the password
+    #[tracing::instrument(
+        skip_all,
+        fields(tenant_id = %request.tenant_id, username = %request.username, ip_address = ?ip_address)
+    )]
     async fn login(
         &self,
         request: LoginRequest,
@@ -136,10 +213,16 @@ impl AuthApplicationService for AuthApplicationServiceImpl {
             created_at: user.created_at,
         };
 
+        let refresh_token = match self.refresh_token_repository {
+            Some(_) => Some(self.create_refresh_token(&user).await?),
+            None => None,
+        };
+
         let response = LoginResponse {
             token: session_info.token.0,
             user: user_info,
             expires_at: session_info.expires_at,
+            refresh_token,
         };
 
         Ok((response, auth_event))
@@ -191,6 +274,84 @@ impl AuthApplicationService for AuthApplicationServiceImpl {
         Ok((response, refresh_event))
     }
 
+    async fn issue_refresh_token(&self, user_id: Uuid) -> Result<RefreshTokenDto> {
+        let user = self.user_repository
+            .find_by_id(user_id.into())
+            .await?
+            .ok_or_else(|| PlatformError::NotFound("User not found".to_string()))?;
+
+        self.create_refresh_token(&user).await
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn refresh_access_token(&self, refresh_token: &str) -> Result<TokenPairDto> {
+        let repository = self.refresh_token_repository()?;
+
+        let stored = repository
+            .find_by_token_hash(&RefreshToken::hash_token(refresh_token))
+            .await?
+            .ok_or_else(|| PlatformError::AuthenticationFailed("Invalid refresh token".to_string()))?;
+
+        if stored.is_revoked() {
+            return Err(self.reject_reused_refresh_token(&stored.user_id).await);
+        }
+
+        if stored.is_expired() {
+            return Err(PlatformError::AuthenticationFailed(
+                "Refresh token has expired".to_string(),
+            ));
+        }
+
+        let user = self.user_repository
+            .find_by_id(stored.user_id)
+            .await?
+            .ok_or_else(|| PlatformError::AuthenticationFailed("User not found".to_string()))?;
+
+        // Two concurrent refreshes with the same token both pass the check
+        // above; only the one whose conditional revoke lands may rotate
+        if self.refresh_token_config.rotate && !repository.revoke(stored.id).await? {
+            return Err(self.reject_reused_refresh_token(&stored.user_id).await);
+        }
+
+        let access_token_ttl =
+            Duration::minutes(self.refresh_token_config.access_token_ttl_minutes as i64);
+        let access_token = self.auth_domain_service
+            .generate_token(&user, access_token_ttl)
+            .await?;
+
+        let rotated = if self.refresh_token_config.rotate {
+            Some(self.create_refresh_token(&user).await?)
+        } else {
+            None
+        };
+
+        Ok(TokenPairDto {
+            access_token: access_token.0,
+            expires_at: Utc::now() + access_token_ttl,
+            refresh_token: rotated,
+        })
+    }
+
+    async fn revoke_refresh_token(&self, token_id: Uuid, user_id: Uuid) -> Result<()> {
+        let repository = self.refresh_token_repository()?;
+
+        let stored = repository
+            .find_by_id(token_id)
+            .await?
+            .filter(|token| token.user_id.0 == user_id)
+            .ok_or_else(|| PlatformError::NotFound("Refresh token not found".to_string()))?;
+
+        repository.revoke(stored.id).await?;
+
+        Ok(())
+    }
+
+    async fn revoke_all_tokens(&self, user_id: Uuid) -> Result<u64> {
+        self.refresh_token_repository()?
+            .revoke_all_by_user(&user_id.into())
+            .await
+    }
+
     async fn logout(
         &self,
         request: LogoutRequest,
@@ -350,7 +511,7 @@ mod tests {
     use super::*;
     use mockall::predicate::*;
     use crate::domain::{
-        repositories::{MockUserRepository, MockTenantRepository},
+        repositories::{MockRefreshTokenRepository, MockUserRepository, MockTenantRepository},
         services::MockAuthenticationDomainService,
         value_objects::{UserId, TenantId, Username, TenantName, SessionInfo},
     };
@@ -522,4 +683,166 @@ mod tests {
         assert_eq!(auth_context.tenant_id, tenant_id.0);
         assert_eq!(auth_context.username, "testuser");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_refresh_access_token_rotates_refresh_token() {
+        let mut user_repo = MockUserRepository::new();
+        let tenant_repo = MockTenantRepository::new();
+        let mut auth_service = MockAuthenticationDomainService::new();
+        let mut refresh_repo = MockRefreshTokenRepository::new();
+
+        let user = create_test_user();
+        let (stored, token) =
+            RefreshToken::issue(user.id, user.tenant_id, Duration::days(30)).unwrap();
+        let stored_id = stored.id;
+
+        refresh_repo
+            .expect_find_by_token_hash()
+            .with(eq(stored.token_hash.clone()))
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+        // The old token is revoked, then the replacement saved
+        refresh_repo
+            .expect_revoke()
+            .with(eq(stored_id))
+            .times(1)
+            .returning(|_| Ok(true));
+        refresh_repo
+            .expect_save()
+            .withf(move |t| t.id != stored_id && t.is_active())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        user_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(user.clone())));
+
+        auth_service
+            .expect_generate_token()
+            .withf(|_, ttl| *ttl == Duration::minutes(15))
+            .times(1)
+            .returning(|_, _| Ok(JwtToken::new("access_token".to_string()).unwrap()));
+
+        let service = AuthApplicationServiceImpl::new(
+            Arc::new(user_repo),
+            Arc::new(tenant_repo),
+            Arc::new(auth_service),
+            None,
+        )
+        .with_refresh_tokens(Arc::new(refresh_repo), RefreshTokenConfig::default());
+
+        let pair = service.refresh_access_token(&token).await.unwrap();
+        assert_eq!(pair.access_token, "access_token");
+        let rotated = pair.refresh_token.expect("refresh token should be rotated");
+        assert_ne!(rotated.refresh_token, token);
+    }
+
+    #[tokio::test]
+    async fn test_reused_refresh_token_revokes_all_tokens() {
+        let user_repo = MockUserRepository::new();
+        let tenant_repo = MockTenantRepository::new();
+        let auth_service = MockAuthenticationDomainService::new();
+        let mut refresh_repo = MockRefreshTokenRepository::new();
+
+        let user = create_test_user();
+        let user_id = user.id;
+        let (mut stored, token) =
+            RefreshToken::issue(user.id, user.tenant_id, Duration::days(30)).unwrap();
+        stored.revoke();
+
+        refresh_repo
+            .expect_find_by_token_hash()
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+        refresh_repo
+            .expect_revoke_all_by_user()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(2));
+
+        let service = AuthApplicationServiceImpl::new(
+            Arc::new(user_repo),
+            Arc::new(tenant_repo),
+            Arc::new(auth_service),
+            None,
+        )
+        .with_refresh_tokens(Arc::new(refresh_repo), RefreshTokenConfig::default());
+
+        let result = service.refresh_access_token(&token).await;
+        assert!(matches!(result, Err(PlatformError::AuthenticationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_refresh_with_same_token_is_treated_as_reuse() {
+        let mut user_repo = MockUserRepository::new();
+        let tenant_repo = MockTenantRepository::new();
+        let mut auth_service = MockAuthenticationDomainService::new();
+        let mut refresh_repo = MockRefreshTokenRepository::new();
+
+        let user = create_test_user();
+        let user_id = user.id;
+        let (stored, token) =
+            RefreshToken::issue(user.id, user.tenant_id, Duration::days(30)).unwrap();
+
+        // The token looked active, but another request revoked it first
+        refresh_repo
+            .expect_find_by_token_hash()
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+        refresh_repo
+            .expect_revoke()
+            .times(1)
+            .returning(|_| Ok(false));
+        refresh_repo
+            .expect_revoke_all_by_user()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(1));
+        refresh_repo.expect_save().never();
+
+        user_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(user.clone())));
+        auth_service.expect_generate_token().never();
+
+        let service = AuthApplicationServiceImpl::new(
+            Arc::new(user_repo),
+            Arc::new(tenant_repo),
+            Arc::new(auth_service),
+            None,
+        )
+        .with_refresh_tokens(Arc::new(refresh_repo), RefreshTokenConfig::default());
+
+        let result = service.refresh_access_token(&token).await;
+        assert!(matches!(result, Err(PlatformError::AuthenticationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_refresh_token_of_another_user_is_not_found() {
+        let mut refresh_repo = MockRefreshTokenRepository::new();
+
+        let user = create_test_user();
+        let (stored, _) =
+            RefreshToken::issue(user.id, user.tenant_id, Duration::days(30)).unwrap();
+        let token_id = stored.id;
+
+        refresh_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+        refresh_repo.expect_revoke().never();
+
+        let service = AuthApplicationServiceImpl::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockTenantRepository::new()),
+            Arc::new(MockAuthenticationDomainService::new()),
+            None,
+        )
+        .with_refresh_tokens(Arc::new(refresh_repo), RefreshTokenConfig::default());
+
+        let result = service.revoke_refresh_token(token_id, Uuid::new_v4()).await;
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+    }
+}
@@ -0,0 +1,208 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use crate::{
+    application::services::FlowApplicationService,
+    domain::{
+        entities::{BatchExecution, FlowExecution},
+        repositories::ExecutionHistoryRepository,
+        value_objects::{FlowId, TenantId, UserId},
+    },
+    error::{PlatformError, Result},
+};
+
+/// Most input sets accepted in one batch
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// Upper bound on the concurrency a caller may ask for
+pub const MAX_BATCH_CONCURRENCY: usize = 20;
+
+/// Outcome of running the flow on one input set
+#[derive(Debug, Clone, Serialize)]
+pub struct FlowExecutionResult {
+    /// Position of the input set in the request
+    pub index: usize,
+    /// Absent if the execution could not be started
+    pub execution_id: Option<Uuid>,
+    pub status: String,
+    pub output: Option<Value>,
+    pub error: Option<String>,
+}
+
+impl FlowExecutionResult {
+    pub(crate) fn from_execution(index: usize, execution: &FlowExecution) -> Self {
+        Self {
+            index,
+            execution_id: Some(execution.id.0),
+            status: format!("{:?}", execution.status).to_lowercase(),
+            output: execution.output_data.clone(),
+            error: execution.error_message.clone(),
+        }
+    }
+
+    pub(crate) fn from_error(index: usize, error: String) -> Self {
+        Self {
+            index,
+            execution_id: None,
+            status: "failed".to_string(),
+            output: None,
+            error: Some(error),
+        }
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.status == "completed"
+    }
+}
+
+/// Runs one flow over many input sets with bounded parallelism
+pub struct BatchFlowExecutor {
+    flow_service: Arc<dyn FlowApplicationService>,
+    execution_history_repository: Arc<dyn ExecutionHistoryRepository>,
+}
+
+impl BatchFlowExecutor {
+    pub fn new(
+        flow_service: Arc<dyn FlowApplicationService>,
+        execution_history_repository: Arc<dyn ExecutionHistoryRepository>,
+    ) -> Self {
+        Self {
+            flow_service,
+            execution_history_repository,
+        }
+    }
+
+    fn validate(inputs: &[HashMap<String, Value>], concurrency: usize) -> Result<()> {
+        if inputs.is_empty() {
+            return Err(PlatformError::ValidationError(
+                "At least one input set is required".to_string(),
+            ));
+        }
+        if inputs.len() > MAX_BATCH_SIZE {
+            return Err(PlatformError::ValidationError(format!(
+                "A batch may contain at most {} input sets",
+                MAX_BATCH_SIZE
+            )));
+        }
+        if concurrency == 0 || concurrency > MAX_BATCH_CONCURRENCY {
+            return Err(PlatformError::ValidationError(format!(
+                "Concurrency must be between 1 and {}",
+                MAX_BATCH_CONCURRENCY
+            )));
+        }
+        Ok(())
+    }
+
+    /// Execute the flow once per input set, at most `concurrency` at a time.
+    /// Results are returned in input order; a failing run does not stop the
+    /// others. Each execution is correlated with the batch record.
+    #[tracing::instrument(
+        skip(self, inputs),
+        fields(flow_id = %flow_id, tenant_id = %tenant_id, batch_size = inputs.len())
+    )]
+    pub async fn execute_batch(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        inputs: Vec<HashMap<String, Value>>,
+        concurrency: usize,
+    ) -> Result<Vec<FlowExecutionResult>> {
+        Self::validate(&inputs, concurrency)?;
+
+        // Fail the whole request up front if the flow is missing
+        self.flow_service.get_flow(flow_id, tenant_id).await?;
+
+        let mut batch = BatchExecution::new(
+            flow_id.0,
+            tenant_id.0,
+            user_id.0,
+            inputs.len() as u32,
+            concurrency as u32,
+        );
+        let batch_id = batch.id;
+
+        let mut results: Vec<Option<FlowExecutionResult>> = vec![None; inputs.len()];
+        let mut task_indexes = HashMap::new();
+        let mut join_set = JoinSet::new();
+        let mut pending = inputs.into_iter().enumerate();
+
+        loop {
+            while join_set.len() < concurrency {
+                let Some((index, input)) = pending.next() else {
+                    break;
+                };
+                let flow_service = self.flow_service.clone();
+                let input_data = Value::Object(input.into_iter().collect());
+                let handle = join_set.spawn(async move {
+                    flow_service
+                        .run_flow_with_audit(flow_id, tenant_id, user_id, Some(input_data), batch_id)
+                        .await
+                });
+                task_indexes.insert(handle.id(), index);
+            }
+
+            let Some(joined) = join_set.join_next_with_id().await else {
+                break;
+            };
+
+            let (index, result) = match joined {
+                Ok((task_id, Ok(execution))) => {
+                    let index = task_indexes[&task_id];
+                    (index, FlowExecutionResult::from_execution(index, &execution))
+                }
+                Ok((task_id, Err(e))) => {
+                    let index = task_indexes[&task_id];
+                    (index, FlowExecutionResult::from_error(index, e.to_string()))
+                }
+                Err(e) => {
+                    let index = task_indexes[&e.id()];
+                    (index, FlowExecutionResult::from_error(index, format!("Execution task failed: {}", e)))
+                }
+            };
+            results[index] = Some(result);
+        }
+
+        let results: Vec<FlowExecutionResult> = results.into_iter().flatten().collect();
+        let succeeded = results.iter().filter(|r| r.is_completed()).count() as u32;
+        batch.complete(succeeded, results.len() as u32 - succeeded);
+
+        if let Err(e) = self.execution_history_repository.create_batch_execution(&batch).await {
+            tracing::error!(batch_id = %batch_id, "Failed to record batch execution: {}", e);
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(count: usize) -> Vec<HashMap<String, Value>> {
+        (0..count)
+            .map(|i| HashMap::from([("i".to_string(), Value::from(i))]))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_batch() {
+        assert!(BatchFlowExecutor::validate(&[], 5).is_err());
+    }
+
+    #[test]
+    fn test_validate_concurrency_bounds() {
+        assert!(BatchFlowExecutor::validate(&inputs(3), 0).is_err());
+        assert!(BatchFlowExecutor::validate(&inputs(3), MAX_BATCH_CONCURRENCY + 1).is_err());
+        assert!(BatchFlowExecutor::validate(&inputs(3), MAX_BATCH_CONCURRENCY).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_oversized_batch() {
+        assert!(BatchFlowExecutor::validate(&inputs(MAX_BATCH_SIZE + 1), 5).is_err());
+    }
+}
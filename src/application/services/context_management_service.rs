@@ -1,18 +1,70 @@
+use std::collections::HashSet;
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use crate::domain::entities::{ChatSession, Message};
 use crate::domain::repositories::{ChatSessionRepository, MessageRepository};
+use crate::domain::services::llm_service::LLMDomainService;
 use crate::domain::services::SessionDomainService;
-use crate::domain::value_objects::{SessionId, TenantId, UserId, ChatMessage, MessageRole};
+use crate::domain::value_objects::{SessionId, TenantId, UserId, ChatMessage, MessageRole, ModelConfig};
 use crate::error::{Result, PlatformError};
+use crate::infrastructure::llm::TokenCounter;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+/// Session variable holding the timestamp of the newest summarized message
+const SUMMARIZED_UNTIL_KEY: &str = "context_summarized_until";
+/// Longest summary `SessionDomainService::update_session_summary` accepts
+const MAX_SUMMARY_CHARS: usize = 5000;
+/// Token limit of `get_context_messages` when no limit is configured
+const DEFAULT_MAX_CONTEXT_TOKENS: usize = 4000;
+
+const SUMMARY_PROMPT: &str = "You maintain a running summary of a conversation. \
+Merge the previous summary with the new messages into one concise summary that keeps \
+facts, decisions, names and open questions. Reply with the summary only.";
+
+/// How messages are dropped once a conversation exceeds its token budget.
+/// System messages and the latest message are never dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextTrimStrategy {
+    /// Drop the oldest messages first
+    #[default]
+    TrimOldest,
+    /// Drop the least important messages first, oldest first among equals
+    TrimByImportance,
+    /// Drop the oldest messages and fold them into a rolling summary
+    Summarize,
+}
+
+/// Summary of the messages trimmed from a session so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingSummary {
+    pub text: String,
+    /// Timestamp of the newest message folded into `text`
+    pub covers_until: DateTime<Utc>,
+}
+
+/// Messages fitted to a token budget
+#[derive(Debug, Clone)]
+pub struct ContextWindow {
+    pub messages: Vec<ChatMessage>,
+    /// Messages dropped to fit the budget, in chronological order
+    pub trimmed: Vec<ChatMessage>,
+    /// Only set by `ContextTrimStrategy::Summarize`
+    pub summary: Option<RollingSummary>,
+    pub token_count: usize,
+}
+
 /// Service for managing conversation context
 pub struct ContextManagementService {
     session_repo: Arc<dyn ChatSessionRepository>,
     message_repo: Arc<dyn MessageRepository>,
     domain_service: Arc<SessionDomainService>,
+    llm_service: Option<Arc<dyn LLMDomainService>>,
+    trim_strategy: ContextTrimStrategy,
     max_context_messages: usize,
-    max_context_tokens: usize,
+    /// Chat prompts default to the prompt budget of the model when unset
+    max_context_tokens: Option<usize>,
 }
 
 impl ContextManagementService {
@@ -25,17 +77,312 @@ impl ContextManagementService {
             session_repo,
             message_repo,
             domain_service,
+            llm_service: None,
+            trim_strategy: ContextTrimStrategy::default(),
             max_context_messages: 50,
-            max_context_tokens: 4000,
+            max_context_tokens: None,
         }
     }
 
     pub fn with_limits(mut self, max_messages: usize, max_tokens: usize) -> Self {
         self.max_context_messages = max_messages;
-        self.max_context_tokens = max_tokens;
+        self.max_context_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn with_trim_strategy(mut self, trim_strategy: ContextTrimStrategy) -> Self {
+        self.trim_strategy = trim_strategy;
         self
     }
 
+    /// Set LLM service used by `ContextTrimStrategy::Summarize`
+    pub fn with_llm_service(mut self, llm_service: Arc<dyn LLMDomainService>) -> Self {
+        self.llm_service = Some(llm_service);
+        self
+    }
+
+    /// Build the prompt for the next reply in a session: `preamble` (system
+    /// prompt, greeting) followed by the recent session history, fitted to
+    /// the token budget. The rolling summary is kept on the session.
+    pub async fn build_chat_context(
+        &self,
+        session_id: &SessionId,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        preamble: Vec<ChatMessage>,
+        model_config: &ModelConfig,
+    ) -> Result<Vec<ChatMessage>> {
+        let mut session = self
+            .session_repo
+            .find_by_id(session_id)
+            .await?
+            .ok_or_else(|| PlatformError::NotFound("Session not found".to_string()))?;
+
+        self.domain_service
+            .validate_session_access(&session, tenant_id, user_id)?;
+
+        let history = self
+            .message_repo
+            .find_recent_by_session(session_id, self.max_context_messages as u64)
+            .await?;
+
+        let mut messages = preamble;
+        messages.extend(history.into_iter().map(|msg| msg.message));
+
+        let previous_summary = match self.trim_strategy {
+            ContextTrimStrategy::Summarize => Self::session_summary(&session),
+            _ => None,
+        };
+
+        let window = self
+            .enforce_token_budget(
+                messages,
+                self.max_context_tokens
+                    .unwrap_or_else(|| model_config.prompt_token_budget()),
+                model_config,
+                tenant_id,
+                previous_summary.clone(),
+            )
+            .await?;
+
+        if window.summary != previous_summary {
+            if let Some(summary) = &window.summary {
+                self.domain_service
+                    .update_session_summary(&mut session, summary.text.clone())?;
+                self.domain_service.set_session_context(
+                    &mut session,
+                    SUMMARIZED_UNTIL_KEY.to_string(),
+                    json!(summary.covers_until.to_rfc3339()),
+                )?;
+                self.session_repo.save(&session).await?;
+            }
+        }
+
+        Ok(window.messages)
+    }
+
+    /// Fit `messages` into `max_context_tokens` as counted by the tokenizer
+    /// of `model_config`, using the configured trim strategy
+    pub async fn enforce_token_budget(
+        &self,
+        messages: Vec<ChatMessage>,
+        max_context_tokens: usize,
+        model_config: &ModelConfig,
+        tenant_id: &TenantId,
+        previous_summary: Option<RollingSummary>,
+    ) -> Result<ContextWindow> {
+        let counter = TokenCounter::new(&model_config.model_name);
+
+        if self.trim_strategy != ContextTrimStrategy::Summarize {
+            let (messages, trimmed) =
+                Self::trim_to_budget(messages, max_context_tokens, &counter, self.trim_strategy);
+            let token_count = counter.count_messages(&messages) as usize;
+            return Ok(ContextWindow {
+                messages,
+                trimmed,
+                summary: None,
+                token_count,
+            });
+        }
+
+        // Leave room for the summary that will be prepended
+        let budget = max_context_tokens.saturating_sub(Self::summary_tokens(&previous_summary, &counter));
+        let (mut messages, mut trimmed) =
+            Self::trim_to_budget(messages, budget, &counter, ContextTrimStrategy::TrimOldest);
+
+        let summary = if trimmed.is_empty() {
+            previous_summary
+        } else {
+            match self
+                .summarize(previous_summary.as_ref(), &trimmed, model_config, tenant_id)
+                .await
+            {
+                Ok(summary) => summary,
+                Err(e) => {
+                    tracing::warn!("Failed to summarize trimmed context: {:?}", e);
+                    previous_summary
+                }
+            }
+        };
+
+        // The new summary may be longer than the one budgeted for; messages
+        // dropped now are summarized on the next turn
+        let budget = max_context_tokens.saturating_sub(Self::summary_tokens(&summary, &counter));
+        let (kept, dropped) =
+            Self::trim_to_budget(messages, budget, &counter, ContextTrimStrategy::TrimOldest);
+        messages = kept;
+        trimmed.extend(dropped);
+        trimmed.sort_by_key(|message| message.timestamp);
+
+        if let Some(summary) = &summary {
+            let position = messages
+                .iter()
+                .take_while(|message| message.role == MessageRole::System)
+                .count();
+            messages.insert(position, Self::summary_message(summary));
+        }
+
+        let token_count = counter.count_messages(&messages) as usize;
+        Ok(ContextWindow {
+            messages,
+            trimmed,
+            summary,
+            token_count,
+        })
+    }
+
+    /// Drop messages until the prompt fits `budget`, returning the kept and
+    /// the dropped messages, both in their original order
+    pub fn trim_to_budget(
+        messages: Vec<ChatMessage>,
+        budget: usize,
+        counter: &TokenCounter,
+        strategy: ContextTrimStrategy,
+    ) -> (Vec<ChatMessage>, Vec<ChatMessage>) {
+        let mut total = counter.count_messages(&messages) as usize;
+        if total <= budget {
+            return (messages, Vec::new());
+        }
+
+        let last = messages.len().saturating_sub(1);
+        let mut candidates: Vec<usize> = (0..messages.len())
+            .filter(|&i| i != last && messages[i].role != MessageRole::System)
+            .collect();
+        if strategy == ContextTrimStrategy::TrimByImportance {
+            candidates.sort_by_key(|&i| (Self::importance(&messages[i]), i));
+        }
+
+        let mut dropped = HashSet::new();
+        for i in candidates {
+            if total <= budget {
+                break;
+            }
+            total -= counter.count_message(&messages[i]) as usize;
+            dropped.insert(i);
+        }
+
+        if total > budget {
+            tracing::warn!(
+                "Context still needs {} tokens after trimming, budget is {}",
+                total,
+                budget
+            );
+        }
+
+        let mut kept = Vec::new();
+        let mut trimmed = Vec::new();
+        for (i, message) in messages.into_iter().enumerate() {
+            if dropped.contains(&i) {
+                trimmed.push(message);
+            } else {
+                kept.push(message);
+            }
+        }
+
+        (kept, trimmed)
+    }
+
+    /// Higher is kept longer. Messages flagged `pinned` in their custom data
+    /// outrank everything else; tool output is the first to go.
+    fn importance(message: &ChatMessage) -> u8 {
+        let pinned = message
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.custom_data.get("pinned"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        if pinned {
+            return 3;
+        }
+
+        match message.role {
+            MessageRole::System | MessageRole::User => 2,
+            MessageRole::Assistant => 1,
+            MessageRole::Tool => 0,
+        }
+    }
+
+    /// Merge the messages not yet covered by `previous` into a new summary
+    async fn summarize(
+        &self,
+        previous: Option<&RollingSummary>,
+        trimmed: &[ChatMessage],
+        model_config: &ModelConfig,
+        tenant_id: &TenantId,
+    ) -> Result<Option<RollingSummary>> {
+        let new_messages: Vec<&ChatMessage> = trimmed
+            .iter()
+            .filter(|message| previous.map_or(true, |p| message.timestamp > p.covers_until))
+            .collect();
+        let covers_until = match new_messages.iter().map(|message| message.timestamp).max() {
+            Some(timestamp) => timestamp,
+            None => return Ok(previous.cloned()),
+        };
+
+        let llm_service = match &self.llm_service {
+            Some(llm_service) => llm_service,
+            None => {
+                tracing::warn!("No LLM service configured, trimmed context is not summarized");
+                return Ok(previous.cloned());
+            }
+        };
+
+        let transcript = new_messages
+            .iter()
+            .map(|message| format!("{:?}: {}", message.role, message.get_text_content()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let request = format!(
+            "Previous summary:\n{}\n\nNew messages:\n{}",
+            previous.map(|p| p.text.as_str()).unwrap_or("(none)"),
+            transcript
+        );
+
+        let response = llm_service
+            .chat_completion(
+                model_config,
+                vec![
+                    ChatMessage::new_system_message(SUMMARY_PROMPT.to_string()),
+                    ChatMessage::new_user_message(request),
+                ],
+                tenant_id.0,
+                None,
+            )
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+
+        Ok(Some(RollingSummary {
+            text: response.content.trim().chars().take(MAX_SUMMARY_CHARS).collect(),
+            covers_until,
+        }))
+    }
+
+    fn session_summary(session: &ChatSession) -> Option<RollingSummary> {
+        let text = session.context.conversation_summary.clone()?;
+        let covers_until = session
+            .get_context_variable(SUMMARIZED_UNTIL_KEY)
+            .and_then(|value| value.as_str())
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&Utc))
+            .unwrap_or(session.created_at);
+
+        Some(RollingSummary { text, covers_until })
+    }
+
+    fn summary_message(summary: &RollingSummary) -> ChatMessage {
+        ChatMessage::new_system_message(format!(
+            "Summary of the earlier conversation:\n{}",
+            summary.text
+        ))
+    }
+
+    fn summary_tokens(summary: &Option<RollingSummary>, counter: &TokenCounter) -> usize {
+        summary
+            .as_ref()
+            .map(|summary| counter.count_message(&Self::summary_message(summary)) as usize)
+            .unwrap_or(0)
+    }
+
     /// Extract conversation context for flow execution
     pub async fn extract_context_for_flow(
         &self,
@@ -167,6 +514,7 @@ impl ContextManagementService {
     /// Apply token limit to messages
     fn apply_token_limit(&self, messages: Vec<Message>) -> Result<Vec<ChatMessage>> {
         let mut result = Vec::new();
+        let max_tokens = self.max_context_tokens.unwrap_or(DEFAULT_MAX_CONTEXT_TOKENS);
         let mut estimated_tokens = 0;
 
         // Process messages in reverse order (most recent first)
@@ -174,7 +522,7 @@ impl ContextManagementService {
             // Rough token estimation: ~4 characters per token
             let msg_tokens = msg.message.get_text_content().len() / 4;
 
-            if estimated_tokens + msg_tokens > self.max_context_tokens {
+            if estimated_tokens + msg_tokens > max_tokens {
                 break;
             }
 
@@ -333,3 +681,217 @@ impl ContextManagementService {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{ChatSessionRepository, MessageRepository};
+    use crate::domain::services::llm_service::{
+        ChatResponse, ChatStreamChunk, ConnectionTestResult, FinishReason, LLMError, ModelInfo,
+        ResponseFormat, TokenUsage, ValidationResult,
+    };
+    use crate::domain::value_objects::{ModelCredentials, ModelParameters, ModelProvider};
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        SessionRepo {}
+
+        #[async_trait]
+        impl ChatSessionRepository for SessionRepo {
+            async fn find_by_id(&self, id: &SessionId) -> Result<Option<ChatSession>>;
+            async fn find_by_user(&self, user_id: &UserId) -> Result<Vec<ChatSession>>;
+            async fn find_by_tenant(&self, tenant_id: &TenantId) -> Result<Vec<ChatSession>>;
+            async fn find_by_tenant_updated_before(&self, tenant_id: &TenantId, before: DateTime<Utc>, limit: u64) -> Result<Vec<ChatSession>>;
+            async fn find_by_tenant_and_user(&self, tenant_id: &TenantId, user_id: &UserId) -> Result<Vec<ChatSession>>;
+            async fn find_active_by_user(&self, user_id: &UserId, timeout_minutes: u64) -> Result<Vec<ChatSession>>;
+            async fn save(&self, session: &ChatSession) -> Result<()>;
+            async fn delete(&self, id: &SessionId) -> Result<()>;
+            async fn delete_expired(&self, before: DateTime<Utc>) -> Result<u64>;
+            async fn count_by_user(&self, user_id: &UserId) -> Result<u64>;
+            async fn find_by_user_paginated(&self, user_id: &UserId, offset: u64, limit: u64) -> Result<Vec<ChatSession>>;
+        }
+    }
+
+    mock! {
+        MessageRepo {}
+
+        #[async_trait]
+        impl MessageRepository for MessageRepo {
+            async fn find_by_id(&self, id: &crate::domain::value_objects::ids::MessageId) -> Result<Option<Message>>;
+            async fn find_by_session(&self, session_id: &SessionId) -> Result<Vec<Message>>;
+            async fn find_recent_by_session(&self, session_id: &SessionId, limit: u64) -> Result<Vec<Message>>;
+            async fn find_by_session_paginated(&self, session_id: &SessionId, offset: u64, limit: u64) -> Result<Vec<Message>>;
+            async fn save(&self, message: &Message) -> Result<()>;
+            async fn delete(&self, id: &crate::domain::value_objects::ids::MessageId) -> Result<()>;
+            async fn delete_by_session(&self, session_id: &SessionId) -> Result<()>;
+            async fn count_by_session(&self, session_id: &SessionId) -> Result<u64>;
+            async fn search_by_content(&self, session_id: &SessionId, query: &str, limit: u64) -> Result<Vec<Message>>;
+            async fn mark_superseded(&self, ids: &[crate::domain::value_objects::ids::MessageId], superseded_at: DateTime<Utc>) -> Result<()>;
+        }
+    }
+
+    mock! {
+        Llm {}
+
+        #[async_trait]
+        impl LLMDomainService for Llm {
+            async fn chat_completion(
+                &self,
+                config: &ModelConfig,
+                messages: Vec<ChatMessage>,
+                tenant_id: uuid::Uuid,
+                response_format: Option<ResponseFormat>,
+            ) -> std::result::Result<ChatResponse, LLMError>;
+            async fn generate_embedding(
+                &self,
+                config: &ModelConfig,
+                text: &str,
+                tenant_id: uuid::Uuid,
+            ) -> std::result::Result<Vec<f32>, LLMError>;
+            async fn stream_chat_completion(
+                &self,
+                config: &ModelConfig,
+                messages: Vec<ChatMessage>,
+                tenant_id: uuid::Uuid,
+            ) -> std::result::Result<Box<dyn futures::Stream<Item = std::result::Result<ChatStreamChunk, LLMError>> + Send + Unpin>, LLMError>;
+            fn validate_config(&self, config: &ModelConfig) -> std::result::Result<ValidationResult, LLMError>;
+            fn supports_streaming(&self, config: &ModelConfig) -> bool;
+            async fn get_available_models(&self, provider: &str) -> std::result::Result<Vec<ModelInfo>, LLMError>;
+            async fn test_connection(&self, config: &ModelConfig) -> std::result::Result<ConnectionTestResult, LLMError>;
+            fn estimate_token_count(&self, messages: &[ChatMessage], model: &str) -> std::result::Result<u32, LLMError>;
+        }
+    }
+
+    fn model_config() -> ModelConfig {
+        ModelConfig {
+            provider: ModelProvider::OpenAI,
+            model_name: "gpt-4".to_string(),
+            parameters: ModelParameters::default(),
+            credentials: ModelCredentials::default(),
+        }
+    }
+
+    /// Roughly 100 tokens tagged with `label`
+    fn long_text(label: &str) -> String {
+        format!("{} {}", label, "word ".repeat(100))
+    }
+
+    fn conversation() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::new_system_message("You are helpful".to_string()),
+            ChatMessage::new_user_message(long_text("first question")),
+            ChatMessage::new_assistant_message(long_text("first answer")),
+            ChatMessage::new_user_message(long_text("second question")),
+        ]
+    }
+
+    fn service(llm: Option<MockLlm>) -> ContextManagementService {
+        let service = ContextManagementService::new(
+            Arc::new(MockSessionRepo::new()),
+            Arc::new(MockMessageRepo::new()),
+            Arc::new(SessionDomainService::new(30)),
+        );
+        match llm {
+            Some(llm) => service.with_llm_service(Arc::new(llm)),
+            None => service,
+        }
+    }
+
+    #[test]
+    fn test_trim_oldest_keeps_system_and_latest_messages() {
+        let counter = TokenCounter::new("gpt-4");
+        let (kept, trimmed) = ContextManagementService::trim_to_budget(
+            conversation(),
+            250,
+            &counter,
+            ContextTrimStrategy::TrimOldest,
+        );
+
+        assert_eq!(kept.len(), 3);
+        assert_eq!(kept[0].role, MessageRole::System);
+        assert!(kept[1].get_text_content().starts_with("first answer"));
+        assert!(kept[2].get_text_content().starts_with("second question"));
+        assert_eq!(trimmed.len(), 1);
+        assert!(trimmed[0].get_text_content().starts_with("first question"));
+        assert!(counter.count_messages(&kept) as usize <= 250);
+    }
+
+    #[test]
+    fn test_trim_by_importance_drops_assistant_before_user() {
+        let counter = TokenCounter::new("gpt-4");
+        let (kept, trimmed) = ContextManagementService::trim_to_budget(
+            conversation(),
+            250,
+            &counter,
+            ContextTrimStrategy::TrimByImportance,
+        );
+
+        assert_eq!(trimmed.len(), 1);
+        assert_eq!(trimmed[0].role, MessageRole::Assistant);
+        assert!(kept[1].get_text_content().starts_with("first question"));
+    }
+
+    #[test]
+    fn test_messages_within_budget_are_untouched() {
+        let counter = TokenCounter::new("gpt-4");
+        let messages = conversation();
+        let (kept, trimmed) = ContextManagementService::trim_to_budget(
+            messages.clone(),
+            10_000,
+            &counter,
+            ContextTrimStrategy::TrimOldest,
+        );
+
+        assert_eq!(kept, messages);
+        assert!(trimmed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_summarize_prepends_rolling_summary() {
+        let mut llm = MockLlm::new();
+        llm.expect_chat_completion()
+            .times(1)
+            .returning(|_, messages, _, _| {
+                assert!(messages[1].get_text_content().contains("first question"));
+                Ok(ChatResponse {
+                    content: "The user asked a first question.".to_string(),
+                    model_used: "gpt-4".to_string(),
+                    usage: TokenUsage {
+                        prompt_tokens: 0,
+                        completion_tokens: 0,
+                        total_tokens: 0,
+                    },
+                    finish_reason: FinishReason::Stop,
+                    metadata: None,
+                })
+            });
+
+        let window = service(Some(llm))
+            .with_trim_strategy(ContextTrimStrategy::Summarize)
+            .enforce_token_budget(conversation(), 300, &model_config(), &TenantId::new(), None)
+            .await
+            .unwrap();
+
+        let summary = window.summary.expect("summary");
+        assert_eq!(summary.text, "The user asked a first question.");
+        assert_eq!(summary.covers_until, window.trimmed.last().unwrap().timestamp);
+        assert_eq!(window.messages[0].get_text_content(), "You are helpful");
+        assert!(window.messages[1].get_text_content().contains("The user asked a first question."));
+        assert!(window.messages.last().unwrap().get_text_content().starts_with("second question"));
+        assert!(window.token_count <= 300);
+    }
+
+    #[tokio::test]
+    async fn test_summarize_without_llm_falls_back_to_trimming() {
+        let window = service(None)
+            .with_trim_strategy(ContextTrimStrategy::Summarize)
+            .enforce_token_budget(conversation(), 250, &model_config(), &TenantId::new(), None)
+            .await
+            .unwrap();
+
+        assert!(window.summary.is_none());
+        assert_eq!(window.trimmed.len(), 1);
+        assert_eq!(window.messages.len(), 3);
+    }
+}
@@ -3,21 +3,131 @@ use serde_json::Value;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::application::dto::{
+    CursorPaginatedResponse, ExecutionDetailDto, ExecutionListFilter, ExecutionSummaryDto,
+};
 use crate::domain::entities::{ExecutionMetrics, ExecutionStep, FlowExecutionHistory};
-use crate::domain::repositories::ExecutionFilter;
+use crate::domain::repositories::{AgentRepository, ExecutionCursor, ExecutionFilter};
 use crate::domain::services::ExecutionHistoryService;
-use crate::error::Result;
+use crate::domain::value_objects::AgentId;
+use crate::error::{PlatformError, Result};
+
+const DEFAULT_LIST_LIMIT: u64 = 20;
+const MAX_LIST_LIMIT: u64 = 100;
 
 /// Application service for execution history
 pub struct ExecutionHistoryApplicationService {
     execution_history_service: Arc<dyn ExecutionHistoryService>,
+    agent_repository: Option<Arc<dyn AgentRepository>>,
 }
 
 impl ExecutionHistoryApplicationService {
     pub fn new(execution_history_service: Arc<dyn ExecutionHistoryService>) -> Self {
         Self {
             execution_history_service,
+            agent_repository: None,
+        }
+    }
+
+    /// Set agent repository so executions can be filtered by agent
+    pub fn with_agent_repository(mut self, agent_repository: Arc<dyn AgentRepository>) -> Self {
+        self.agent_repository = Some(agent_repository);
+        self
+    }
+
+    /// List a tenant's executions newest first, one cursor page at a time
+    pub async fn list_executions(
+        &self,
+        tenant_id: Uuid,
+        filter: ExecutionListFilter,
+    ) -> Result<CursorPaginatedResponse<ExecutionSummaryDto>> {
+        let limit = filter.limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT);
+
+        if let (Some(start), Some(end)) = (filter.start_time, filter.end_time) {
+            if start > end {
+                return Err(PlatformError::ValidationError(
+                    "start_time must not be after end_time".to_string(),
+                ));
+            }
+        }
+
+        let mut query = ExecutionFilter::new(tenant_id);
+        query.flow_id = filter.flow_id;
+        query.status = filter.status.map(|status| status.to_status().as_str().to_string());
+        query.start_date = filter.start_time;
+        query.end_date = filter.end_time;
+        // One extra row tells whether another page follows
+        query.limit = Some(limit + 1);
+
+        if let Some(token) = filter.cursor.as_deref() {
+            query.cursor = Some(ExecutionCursor::decode(token).ok_or_else(|| {
+                PlatformError::ValidationError("Invalid cursor".to_string())
+            })?);
+        }
+
+        if let Some(agent_id) = filter.agent_id {
+            let flow_ids = self.agent_flow_ids(tenant_id, agent_id).await?;
+            if flow_ids.is_empty() {
+                return Ok(CursorPaginatedResponse {
+                    items: Vec::new(),
+                    next_cursor: None,
+                    has_more: false,
+                });
+            }
+            query = query.with_flow_ids(flow_ids);
         }
+
+        let mut executions = self.execution_history_service.query_executions(&query).await?;
+
+        let has_more = executions.len() as u64 > limit;
+        executions.truncate(limit as usize);
+        let next_cursor = has_more
+            .then(|| executions.last())
+            .flatten()
+            .map(|last| ExecutionCursor::new(last.started_at, last.id).encode());
+
+        Ok(CursorPaginatedResponse {
+            items: executions.into_iter().map(ExecutionSummaryDto::from).collect(),
+            next_cursor,
+            has_more,
+        })
+    }
+
+    /// Execution with its full node trace and metrics
+    pub async fn get_execution_detail(
+        &self,
+        tenant_id: Uuid,
+        execution_id: Uuid,
+    ) -> Result<ExecutionDetailDto> {
+        let execution = self
+            .get_execution(execution_id)
+            .await?
+            .filter(|execution| execution.tenant_id == tenant_id)
+            .ok_or_else(|| PlatformError::NotFound("Execution not found".to_string()))?;
+
+        let steps = self.get_execution_steps(execution_id).await?;
+        let metrics = self.get_execution_metrics(execution_id).await?;
+
+        Ok(ExecutionDetailDto {
+            execution: execution.into(),
+            steps: steps.into_iter().map(Into::into).collect(),
+            metrics: metrics.into(),
+        })
+    }
+
+    /// Flows attached to one of the tenant's agents
+    async fn agent_flow_ids(&self, tenant_id: Uuid, agent_id: Uuid) -> Result<Vec<Uuid>> {
+        let agent_repository = self.agent_repository.as_ref().ok_or_else(|| {
+            PlatformError::InternalError("Agent repository not configured".to_string())
+        })?;
+
+        let agent = agent_repository
+            .find_by_id(&AgentId::from_uuid(agent_id))
+            .await?
+            .filter(|agent| agent.tenant_id.0 == tenant_id)
+            .ok_or_else(|| PlatformError::AgentNotFound(agent_id.to_string()))?;
+
+        Ok(agent.flow_ids.iter().map(|flow_id| flow_id.0).collect())
     }
 
     /// Start tracking a new execution
@@ -285,4 +395,94 @@ mod tests {
         let (_, total) = result.unwrap();
         assert_eq!(total, 42);
     }
+
+    #[tokio::test]
+    async fn test_list_executions_returns_next_cursor() {
+        let mut mock_service = MockExecutionHistoryServiceImpl::new();
+        let tenant_id = Uuid::new_v4();
+        let executions: Vec<FlowExecutionHistory> = (0..3)
+            .map(|_| FlowExecutionHistory::new(Uuid::new_v4(), 1, tenant_id, Uuid::new_v4(), None, None))
+            .collect();
+        let second = executions[1].clone();
+
+        mock_service
+            .expect_query_executions()
+            .times(1)
+            .withf(|filter: &ExecutionFilter| {
+                filter.limit == Some(3)
+                    && filter.offset.is_none()
+                    && filter.status.as_deref() == Some("completed")
+            })
+            .returning(move |_| Ok(executions.clone()));
+
+        let service = ExecutionHistoryApplicationService::new(Arc::new(mock_service));
+        let filter = ExecutionListFilter {
+            status: Some(crate::application::dto::ExecutionStatusFilter::Success),
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let page = service.list_executions(tenant_id, filter).await.unwrap();
+
+        assert_eq!(page.items.len(), 2);
+        assert!(page.has_more);
+        assert_eq!(
+            page.next_cursor.as_deref().and_then(ExecutionCursor::decode),
+            Some(ExecutionCursor::new(second.started_at, second.id))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_executions_by_agent_filters_its_flows() {
+        use crate::domain::entities::Agent;
+        use crate::domain::repositories::MockAgentRepository;
+        use crate::domain::value_objects::{FlowId, TenantId, UserId};
+
+        let tenant_id = TenantId::new();
+        let flow_id = FlowId::new();
+        let mut agent = Agent::new(tenant_id, "Agent".to_string(), "Prompt".to_string(), UserId::new()).unwrap();
+        agent.add_flow(flow_id);
+        let agent_id = agent.id.0;
+
+        let mut agent_repository = MockAgentRepository::new();
+        agent_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(agent.clone())));
+
+        let mut mock_service = MockExecutionHistoryServiceImpl::new();
+        mock_service
+            .expect_query_executions()
+            .times(1)
+            .withf(move |filter: &ExecutionFilter| filter.flow_ids == Some(vec![flow_id.0]))
+            .returning(|_| Ok(vec![]));
+
+        let service = ExecutionHistoryApplicationService::new(Arc::new(mock_service))
+            .with_agent_repository(Arc::new(agent_repository));
+
+        let filter = ExecutionListFilter {
+            agent_id: Some(agent_id),
+            ..Default::default()
+        };
+        let page = service.list_executions(tenant_id.0, filter.clone()).await.unwrap();
+        assert!(page.items.is_empty());
+        assert!(!page.has_more);
+
+        // Agents of other tenants are not visible
+        let result = service.list_executions(Uuid::new_v4(), filter).await;
+        assert!(matches!(result, Err(PlatformError::AgentNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_executions_rejects_invalid_cursor() {
+        let service = ExecutionHistoryApplicationService::new(Arc::new(
+            MockExecutionHistoryServiceImpl::new(),
+        ));
+        let filter = ExecutionListFilter {
+            cursor: Some("garbage".to_string()),
+            ..Default::default()
+        };
+
+        let result = service.list_executions(Uuid::new_v4(), filter).await;
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+    }
 }
@@ -1,12 +1,20 @@
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
+    application::dto::{ConfirmUploadResponse, PresignedUploadResponse},
     domain::repositories::FileRepository,
-    error::Result,
+    error::{PlatformError, Result},
 };
 
+/// How long a presigned upload form stays valid
+pub const PRESIGNED_UPLOAD_TTL_MINUTES: i64 = 15;
+
+/// Largest file a presigned upload may carry (1 GiB)
+pub const MAX_PRESIGNED_UPLOAD_BYTES: u64 = 1024 * 1024 * 1024;
+
 #[async_trait]
 pub trait FileApplicationService: Send + Sync {
     async fn upload_file(
@@ -17,6 +25,24 @@ pub trait FileApplicationService: Send + Sync {
         content_type: String,
         data: Vec<u8>,
     ) -> Result<String>;
+
+    /// Sign a form that lets the client upload one file straight to storage
+    async fn generate_presigned_upload_url(
+        &self,
+        tenant_id: &str,
+        user_id: &str,
+        filename: String,
+        content_type: String,
+        max_size_bytes: u64,
+    ) -> Result<PresignedUploadResponse>;
+
+    /// Resolve the download URL once a presigned upload has finished
+    async fn confirm_upload(
+        &self,
+        tenant_id: &str,
+        user_id: &str,
+        key: String,
+    ) -> Result<ConfirmUploadResponse>;
 }
 
 pub struct FileApplicationServiceImpl {
@@ -27,6 +53,19 @@ impl FileApplicationServiceImpl {
     pub fn new(repository: Arc<dyn FileRepository>) -> Self {
         Self { repository }
     }
+
+    /// Keep only the last path component so a client cannot pick the object key
+    fn sanitize_filename(filename: &str) -> Result<String> {
+        let name = filename
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .trim();
+        if name.is_empty() || name == "." || name == ".." {
+            return Err(PlatformError::ValidationError("Invalid filename".to_string()));
+        }
+        Ok(name.to_string())
+    }
 }
 
 #[async_trait]
@@ -49,4 +88,69 @@ impl FileApplicationService for FileApplicationServiceImpl {
         
         Ok(url)
     }
+
+    async fn generate_presigned_upload_url(
+        &self,
+        tenant_id: &str,
+        _user_id: &str,
+        filename: String,
+        content_type: String,
+        max_size_bytes: u64,
+    ) -> Result<PresignedUploadResponse> {
+        if max_size_bytes == 0 || max_size_bytes > MAX_PRESIGNED_UPLOAD_BYTES {
+            return Err(PlatformError::ValidationError(format!(
+                "max_size_bytes must be between 1 and {}",
+                MAX_PRESIGNED_UPLOAD_BYTES
+            )));
+        }
+        let filename = Self::sanitize_filename(&filename)?;
+
+        let file_id = Uuid::new_v4().to_string();
+        let expires_at = Utc::now() + Duration::minutes(PRESIGNED_UPLOAD_TTL_MINUTES);
+
+        let post = self.repository
+            .presign_upload(tenant_id, &file_id, &filename, &content_type, max_size_bytes, expires_at)
+            .await?;
+
+        Ok(PresignedUploadResponse {
+            upload_url: post.url,
+            key: post.key,
+            fields: post.fields,
+            expires_at: post.expires_at,
+        })
+    }
+
+    async fn confirm_upload(
+        &self,
+        tenant_id: &str,
+        _user_id: &str,
+        key: String,
+    ) -> Result<ConfirmUploadResponse> {
+        if key.trim().is_empty() {
+            return Err(PlatformError::ValidationError("key is required".to_string()));
+        }
+
+        let url = self.repository.confirm_upload(tenant_id, &key).await?;
+
+        Ok(ConfirmUploadResponse { key, url })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_filename_keeps_last_component() {
+        assert_eq!(
+            FileApplicationServiceImpl::sanitize_filename("../../etc/passwd").unwrap(),
+            "passwd"
+        );
+        assert_eq!(
+            FileApplicationServiceImpl::sanitize_filename("C:\\docs\\report.pdf").unwrap(),
+            "report.pdf"
+        );
+        assert!(FileApplicationServiceImpl::sanitize_filename("uploads/..").is_err());
+        assert!(FileApplicationServiceImpl::sanitize_filename("dir/").is_err());
+    }
 }
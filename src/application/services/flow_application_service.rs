@@ -1,12 +1,16 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 use serde_json::Value;
+use uuid::Uuid;
 use crate::{
+    application::{dto::{AuditEvent, FlowSummaryDto, PaginatedResponse, PaginationParams}, services::{AuditApplicationService, TenantApplicationService}},
     domain::{
-        entities::{Flow, FlowVersion, FlowExecution, User},
-        repositories::{FlowRepository, FlowVersionRepository, FlowExecutionRepository},
-        services::{FlowDomainService, ExecutionEngine},
-        value_objects::{FlowId, TenantId, UserId, FlowName, FlowDefinition, Version, SessionId, FlowExecutionId},
+        entities::{AuditAction, Flow, QuotaResource, FlowVersion, FlowExecution, FlowNodeAnnotation, ResourceType, User},
+        events::{DomainEvent, EventBus, EventStore, FlowChange, FlowChanged, FlowExecutionStarted, FlowExecutionCompleted, FlowExecutionFailed},
+        repositories::{FlowQuery, FlowRepository, FlowVersionRepository, FlowExecutionRepository, FlowNodeAnnotationRepository},
+        services::{FlowDomainService, ExecutionEngine, ExecutionEngineFactory, FlowExecutionMetrics, LangChainParser, DryRunConfig, DryRunResult},
+        value_objects::{FlowId, TenantId, UserId, FlowName, FlowDefinition, Version, SessionId, FlowExecutionId, FlowNodeAnnotationId},
     },
     error::{Result, PlatformError},
 };
@@ -30,15 +34,27 @@ pub trait FlowApplicationService: Send + Sync {
     async fn get_flow(&self, flow_id: FlowId, tenant_id: TenantId) -> Result<Flow>;
 
     /// List flows for a tenant
-    async fn list_flows(&self, tenant_id: TenantId, page: u64, limit: u64) -> Result<(Vec<Flow>, u64)>;
+    async fn list_tenant_flows(&self, tenant_id: TenantId, page: u64, limit: u64) -> Result<(Vec<Flow>, u64)>;
 
-    /// Update flow
+    /// List the tenant's flows whose current version has nodes matching the query
+    async fn list_flows(
+        &self,
+        query: FlowQuery,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedResponse<FlowSummaryDto>>;
+
+    /// Update flow. A new definition bumps the flow version; earlier
+    /// definitions stay in `flow_versions`. Published flows are only
+    /// changed with `force`.
     async fn update_flow(
         &self,
         flow_id: FlowId,
         tenant_id: TenantId,
         name: Option<String>,
         description: Option<String>,
+        definition: Option<FlowDefinition>,
+        user_id: UserId,
+        force: bool,
     ) -> Result<Flow>;
 
     /// Delete flow
@@ -50,6 +66,29 @@ pub trait FlowApplicationService: Send + Sync {
     /// Archive flow
     async fn archive_flow(&self, flow_id: FlowId, tenant_id: TenantId) -> Result<Flow>;
 
+    /// Lock a flow against changes, e.g. once it is deployed
+    async fn publish_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow>;
+
+    /// Unlock a published flow
+    async fn unpublish_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow>;
+
+    /// Allow new executions of a disabled flow
+    async fn enable_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow>;
+
+    /// Reject new executions of a flow. Executions already running finish.
+    async fn disable_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow>;
+
+    /// Copy a flow and its current definition into a new flow owned by
+    /// `user_id`. Node IDs are regenerated and the copy starts at version 1,
+    /// unpublished; this is how a published flow gets a new version.
+    async fn clone_flow(
+        &self,
+        source_id: FlowId,
+        new_name: String,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<Flow>;
+
     /// Import flow from Dify DSL
     async fn import_from_dsl(
         &self,
@@ -59,6 +98,15 @@ pub trait FlowApplicationService: Send + Sync {
         user_id: UserId,
     ) -> Result<(Flow, ValidationResult)>;
 
+    /// Import flow from a LangChain JSON chain definition
+    async fn import_from_langchain(
+        &self,
+        tenant_id: TenantId,
+        name: String,
+        json: String,
+        user_id: UserId,
+    ) -> Result<(Flow, ValidationResult)>;
+
     /// Validate flow definition
     async fn validate_flow_definition(&self, definition: FlowDefinition) -> Result<ValidationResult>;
 
@@ -72,6 +120,40 @@ pub trait FlowApplicationService: Send + Sync {
         input_data: Option<Value>,
     ) -> Result<FlowExecution>;
 
+    /// Execute flow linked to audit events through a correlation ID
+    async fn run_flow_with_audit(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        input_data: Option<Value>,
+        audit_correlation_id: Uuid,
+    ) -> Result<FlowExecution>;
+
+    /// Create and save a running execution without running the flow, so its
+    /// ID can be handed out before `finish_execution` runs it
+    async fn start_execution(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        input_data: Option<Value>,
+    ) -> Result<FlowExecution>;
+
+    /// Run an execution created by `start_execution` to completion
+    async fn finish_execution(&self, execution: FlowExecution) -> Result<FlowExecution>;
+
+    /// Run the latest version of a flow with LLM and vector nodes answering
+    /// from `config` fixtures. Nothing is persisted, audited or published.
+    async fn dry_run(
+        &self,
+        flow_id: FlowId,
+        input_data: Option<Value>,
+        tenant_id: TenantId,
+        user_id: UserId,
+        config: DryRunConfig,
+    ) -> Result<DryRunResult>;
+
     /// Get flow execution status
     async fn get_execution_status(&self, execution_id: FlowExecutionId, tenant_id: TenantId) -> Result<FlowExecution>;
 
@@ -97,6 +179,9 @@ pub trait FlowApplicationService: Send + Sync {
     /// Get flow versions
     async fn get_versions(&self, flow_id: FlowId, tenant_id: TenantId) -> Result<Vec<FlowVersion>>;
 
+    /// Get the definition a flow had at a given version, e.g. to replay an execution
+    async fn get_flow_at_version(&self, flow_id: FlowId, tenant_id: TenantId, version: i32) -> Result<FlowVersion>;
+
     /// Rollback to specific version
     async fn rollback_to_version(
         &self,
@@ -105,6 +190,44 @@ pub trait FlowApplicationService: Send + Sync {
         target_version: i32,
         user_id: UserId,
     ) -> Result<Flow>;
+
+    /// Attach a review comment to a node of the flow
+    async fn add_annotation(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        node_id: String,
+        content: String,
+        user_id: UserId,
+    ) -> Result<FlowNodeAnnotation>;
+
+    /// Mark an annotation of the flow as resolved
+    async fn resolve_annotation(
+        &self,
+        flow_id: FlowId,
+        annotation_id: FlowNodeAnnotationId,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<()>;
+
+    /// List annotations of a flow, optionally restricted to a single node
+    async fn list_annotations(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        node_id: Option<String>,
+    ) -> Result<Vec<FlowNodeAnnotation>>;
+
+    /// Count annotations of a flow
+    async fn count_annotations(&self, flow_id: FlowId, tenant_id: TenantId) -> Result<u32>;
+
+    /// Count annotations of several of the tenant's flows with one query;
+    /// flows without annotations are missing from the map
+    async fn count_annotations_by_flow(
+        &self,
+        flow_ids: &[FlowId],
+        tenant_id: TenantId,
+    ) -> Result<HashMap<FlowId, u32>>;
 }
 
 /// Flow application service implementation
@@ -114,6 +237,11 @@ pub struct FlowApplicationServiceImpl {
     execution_repo: Arc<dyn FlowExecutionRepository>,
     flow_domain_service: Arc<dyn FlowDomainService>,
     execution_engine: Option<Arc<dyn ExecutionEngine>>,
+    event_bus: Option<Arc<dyn EventBus>>,
+    event_store: Option<Arc<dyn EventStore>>,
+    annotation_repo: Option<Arc<dyn FlowNodeAnnotationRepository>>,
+    audit_service: Option<Arc<AuditApplicationService>>,
+    quota_service: Option<Arc<dyn TenantApplicationService>>,
 }
 
 impl FlowApplicationServiceImpl {
@@ -130,8 +258,311 @@ impl FlowApplicationServiceImpl {
             execution_repo,
             flow_domain_service,
             execution_engine,
+            event_bus: None,
+            event_store: None,
+            annotation_repo: None,
+            audit_service: None,
+            quota_service: None,
         }
     }
+
+    pub fn with_annotation_repository(mut self, annotation_repo: Arc<dyn FlowNodeAnnotationRepository>) -> Self {
+        self.annotation_repo = Some(annotation_repo);
+        self
+    }
+
+    fn annotation_repo(&self) -> Result<&Arc<dyn FlowNodeAnnotationRepository>> {
+        self.annotation_repo.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("Flow annotations are not configured".to_string()))
+    }
+
+    pub fn with_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    pub fn with_audit_service(mut self, audit_service: Arc<AuditApplicationService>) -> Self {
+        self.audit_service = Some(audit_service);
+        self
+    }
+
+    /// Set quota service so flow creation respects the tenant's quota
+    pub fn with_quota_service(mut self, quota_service: Arc<dyn TenantApplicationService>) -> Self {
+        self.quota_service = Some(quota_service);
+        self
+    }
+
+    /// Record a flow execution in the audit log, if an audit service is configured
+    async fn record_execution_audit(&self, execution: &FlowExecution) {
+        let Some(ref audit_service) = self.audit_service else {
+            return;
+        };
+
+        let event = AuditEvent::new(
+            execution.tenant_id.0,
+            AuditAction::Execute,
+            ResourceType::FlowExecution,
+        )
+        .with_user_id(execution.user_id.0)
+        .with_resource_id(execution.id.0)
+        .with_details(serde_json::json!({
+            "flow_id": execution.flow_id.0,
+            "flow_version": execution.flow_version.0,
+            "status": execution.status,
+            "correlation_id": execution.correlation_id,
+        }));
+
+        if let Err(e) = audit_service.record_event(event).await {
+            tracing::warn!("Failed to record audit event for execution {}: {}", execution.id.0, e);
+        }
+    }
+
+    /// Reject changes to a published flow unless they are forced
+    fn ensure_modifiable(flow: &Flow, force: bool) -> Result<()> {
+        if flow.is_published && !force {
+            return Err(PlatformError::Conflict("Cannot modify a published flow".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Append a flow mutation to the event store, if one is configured.
+    /// Called after the change is saved, so a failed append is only logged.
+    async fn record_flow_change(
+        &self,
+        flow: &Flow,
+        user_id: Option<UserId>,
+        change: FlowChange,
+        details: Option<Value>,
+    ) {
+        let event_store = match self.event_store {
+            Some(ref event_store) => event_store,
+            None => return,
+        };
+
+        let event = FlowChanged::new(
+            flow.id.0,
+            flow.tenant_id.0,
+            user_id.map(|id| id.0),
+            change,
+            details,
+        );
+        // The flow change is already committed; failing the request here
+        // would report an error for it and invite a duplicate retry
+        if let Err(e) = event_store.append(&event).await {
+            tracing::error!(
+                "Failed to record {} event for flow {}: {}",
+                event.event_type(),
+                flow.id.0,
+                e
+            );
+        }
+    }
+
+    /// Publish the lifecycle event matching the execution's current status
+    async fn publish_execution_event(&self, execution: &FlowExecution) -> Result<()> {
+        let event_bus = match self.event_bus {
+            Some(ref event_bus) => event_bus,
+            None => return Ok(()),
+        };
+
+        let execution_time_ms = execution.execution_time_ms.unwrap_or(0);
+        let event: Arc<dyn DomainEvent> = if execution.is_failed() || execution.is_cancelled() {
+            let mut event = FlowExecutionFailed::new(
+                execution.id.0,
+                execution.flow_id.0,
+                execution.tenant_id.0,
+                execution.user_id.0,
+                execution.error_message.clone().unwrap_or_default(),
+                execution_time_ms,
+            );
+            if let Some(correlation_id) = execution.correlation_id {
+                event = event.with_correlation(correlation_id);
+            }
+            Arc::new(event)
+        } else if execution.is_completed() {
+            let mut event = FlowExecutionCompleted::new(
+                execution.id.0,
+                execution.flow_id.0,
+                execution.tenant_id.0,
+                execution.user_id.0,
+                execution.output_data.clone(),
+                execution_time_ms,
+            );
+            if let Some(correlation_id) = execution.correlation_id {
+                event = event.with_correlation(correlation_id);
+            }
+            Arc::new(event)
+        } else {
+            let mut event = FlowExecutionStarted::new(
+                execution.id.0,
+                execution.flow_id.0,
+                execution.tenant_id.0,
+                execution.user_id.0,
+                execution.input_data.clone(),
+            );
+            if let Some(correlation_id) = execution.correlation_id {
+                event = event.with_correlation(correlation_id);
+            }
+            Arc::new(event)
+        };
+
+        event_bus.publish(event).await
+    }
+
+    /// Load the flow and check that the user may run it with this input
+    async fn prepare_execution(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        input_data: Option<&Value>,
+    ) -> Result<Flow> {
+        let flow = self.get_flow(flow_id, tenant_id).await?;
+
+        // Only new executions are refused; ones already running are
+        // completed without coming back here
+        if !flow.is_enabled {
+            return Err(PlatformError::FlowExecutionFailed("Flow is disabled".to_string()));
+        }
+
+        // Create minimal user for permission check
+        let user = User {
+            id: user_id,
+            tenant_id,
+            username: crate::domain::value_objects::Username::new("temp".to_string()).unwrap(),
+            nickname: None,
+            email: None,
+            language: None,
+            avatar_url: None,
+            password_hash: String::new(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+
+        if !self.flow_domain_service.can_execute(&flow, &user) {
+            return Err(PlatformError::AuthorizationFailed("Cannot execute this flow".to_string()));
+        }
+
+        // Validate input
+        if let Some(input) = input_data {
+            let validation = self.flow_domain_service.validate_execution_input(&flow, input)?;
+            if !validation.is_valid {
+                return Err(PlatformError::ValidationError(
+                    format!("Invalid execution input: {:?}", validation.errors)
+                ));
+            }
+        }
+
+        Ok(flow)
+    }
+
+    async fn run_execution(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        session_id: Option<SessionId>,
+        input_data: Option<Value>,
+        correlation_id: Option<Uuid>,
+    ) -> Result<FlowExecution> {
+        let flow = self.prepare_execution(flow_id, tenant_id, user_id, input_data.as_ref()).await?;
+        let execution = self
+            .begin_execution(&flow, user_id, session_id, input_data, correlation_id)
+            .await?;
+        self.complete_execution(&flow, execution).await
+    }
+
+    /// Create the execution record and mark it running
+    async fn begin_execution(
+        &self,
+        flow: &Flow,
+        user_id: UserId,
+        session_id: Option<SessionId>,
+        input_data: Option<Value>,
+        correlation_id: Option<Uuid>,
+    ) -> Result<FlowExecution> {
+        let mut execution = FlowExecution::new(
+            flow.id,
+            flow.current_version,
+            flow.tenant_id,
+            user_id,
+            session_id,
+            input_data,
+        );
+        if let Some(correlation_id) = correlation_id {
+            execution = execution.with_correlation_id(correlation_id);
+        }
+
+        execution.start();
+        self.execution_repo.save(&execution).await?;
+        self.publish_execution_event(&execution).await?;
+
+        Ok(execution)
+    }
+
+    /// Run the latest version of the flow for a started execution
+    async fn complete_execution(&self, flow: &Flow, mut execution: FlowExecution) -> Result<FlowExecution> {
+        // Execute flow if engine is available
+        if let Some(ref engine) = self.execution_engine {
+            // Get the latest version definition
+            let version = self.version_repo.find_latest_by_flow(&flow.id).await?
+                .ok_or_else(|| PlatformError::NotFound("Flow version not found".to_string()))?;
+
+            // Prepare initial variables
+            let mut initial_variables = std::collections::HashMap::new();
+            if let Some(Value::Object(map)) = execution.input_data.clone() {
+                for (key, value) in map {
+                    initial_variables.insert(key, value);
+                }
+            }
+            if let Some(correlation_id) = execution.correlation_id {
+                initial_variables.insert(
+                    "audit_correlation_id".to_string(),
+                    Value::String(correlation_id.to_string()),
+                );
+            }
+
+            let mut timeout = None;
+            match engine
+                .execute_with_timeout(&mut execution, &version.definition, initial_variables, flow.timeout_ms)
+                .await
+            {
+                Ok(state) => {
+                    let metrics = FlowExecutionMetrics::from_state(&state, &version.definition);
+                    let output = serde_json::json!({
+                        "status": "completed",
+                        "variables": state.public_variables(),
+                        "metrics": metrics,
+                    });
+                    execution.complete(output);
+                }
+                // The engine already cancelled the execution and kept its partial output
+                Err(PlatformError::Timeout(message)) => {
+                    timeout = Some(message);
+                }
+                Err(e) => {
+                    execution.fail(e.to_string());
+                }
+            }
+            
+            self.execution_repo.save(&execution).await?;
+            self.publish_execution_event(&execution).await?;
+
+            if let Some(message) = timeout {
+                self.record_execution_audit(&execution).await;
+                return Err(PlatformError::Timeout(message));
+            }
+        }
+
+        self.record_execution_audit(&execution).await;
+
+        Ok(execution)
+    }
 }
 
 #[async_trait]
@@ -146,6 +577,10 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         let flow_name = FlowName::new(name)
             .map_err(|e| PlatformError::ValidationError(e))?;
 
+        if let Some(ref quota_service) = self.quota_service {
+            quota_service.ensure_quota(tenant_id, QuotaResource::Flows, 1).await?;
+        }
+
         // Check if name already exists
         if self.flow_repo.name_exists_in_tenant(&tenant_id, &flow_name.0).await? {
             return Err(PlatformError::ValidationError(
@@ -159,6 +594,12 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         self.flow_domain_service.validate_flow(&flow)?;
         
         self.flow_repo.save(&flow).await?;
+        self.record_flow_change(
+            &flow,
+            Some(user_id),
+            FlowChange::Created,
+            Some(serde_json::json!({ "name": flow.name.0 })),
+        ).await;
         Ok(flow)
     }
 
@@ -173,21 +614,49 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         Ok(flow)
     }
 
-    async fn list_flows(&self, tenant_id: TenantId, page: u64, limit: u64) -> Result<(Vec<Flow>, u64)> {
+    async fn list_tenant_flows(&self, tenant_id: TenantId, page: u64, limit: u64) -> Result<(Vec<Flow>, u64)> {
         let offset = page * limit;
         let flows = self.flow_repo.find_by_tenant_paginated(&tenant_id, offset, limit).await?;
         let total = self.flow_repo.count_by_tenant(&tenant_id).await?;
         Ok((flows, total))
     }
 
+    async fn list_flows(
+        &self,
+        query: FlowQuery,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedResponse<FlowSummaryDto>> {
+        let page = pagination.get_page();
+        let limit = pagination.get_limit();
+
+        let (flows, total) = self.flow_repo
+            .find_by_query(&query, pagination.get_offset(), limit)
+            .await?;
+
+        let items = flows
+            .into_iter()
+            .map(|flow| FlowSummaryDto {
+                id: flow.id.0,
+                name: flow.name.0,
+                description: flow.description,
+            })
+            .collect();
+
+        Ok(PaginatedResponse::new(items, total, page, limit))
+    }
+
     async fn update_flow(
         &self,
         flow_id: FlowId,
         tenant_id: TenantId,
         name: Option<String>,
         description: Option<String>,
+        definition: Option<FlowDefinition>,
+        user_id: UserId,
+        force: bool,
     ) -> Result<Flow> {
         let mut flow = self.get_flow(flow_id, tenant_id).await?;
+        Self::ensure_modifiable(&flow, force)?;
 
         if let Some(name_str) = name {
             let flow_name = FlowName::new(name_str)
@@ -200,7 +669,37 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         }
 
         self.flow_domain_service.validate_flow(&flow)?;
+
+        // A new definition is stored as the next version; the previous one is kept for replays
+        let mut details = None;
+        if let Some(definition) = definition {
+            let validation = self.flow_domain_service.validate_flow_definition(&definition)?;
+            if !validation.is_valid {
+                return Err(PlatformError::ValidationError(
+                    format!("Invalid flow definition: {:?}", validation.errors)
+                ));
+            }
+
+            let previous_version = flow.current_version;
+            flow.increment_version();
+
+            let version = FlowVersion::new(
+                flow_id,
+                flow.current_version,
+                definition,
+                Some(format!("Updated from version {}", previous_version.0)),
+                user_id,
+            ).map_err(|e| PlatformError::ValidationError(e))?;
+
+            self.version_repo.save(&version, &tenant_id).await?;
+            details = Some(serde_json::json!({
+                "previous_version": previous_version.0,
+                "version": flow.current_version.0,
+            }));
+        }
+
         self.flow_repo.save(&flow).await?;
+        self.record_flow_change(&flow, Some(user_id), FlowChange::Updated, details).await;
         Ok(flow)
     }
 
@@ -213,6 +712,9 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
             tenant_id,
             username: crate::domain::value_objects::Username::new("temp".to_string()).unwrap(),
             nickname: None,
+            email: None,
+            language: None,
+            avatar_url: None,
             password_hash: String::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
@@ -227,6 +729,12 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         
         // Delete flow
         self.flow_repo.delete(&flow_id).await?;
+        self.record_flow_change(
+            &flow,
+            Some(user_id),
+            FlowChange::Deleted,
+            Some(serde_json::json!({ "name": flow.name.0 })),
+        ).await;
         Ok(())
     }
 
@@ -235,6 +743,7 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         flow.activate()
             .map_err(|e| PlatformError::ValidationError(e))?;
         self.flow_repo.save(&flow).await?;
+        self.record_flow_change(&flow, None, FlowChange::Activated, None).await;
         Ok(flow)
     }
 
@@ -243,6 +752,77 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         flow.archive()
             .map_err(|e| PlatformError::ValidationError(e))?;
         self.flow_repo.save(&flow).await?;
+        self.record_flow_change(&flow, None, FlowChange::Archived, None).await;
+        Ok(flow)
+    }
+
+    async fn publish_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow> {
+        let mut flow = self.get_flow(flow_id, tenant_id).await?;
+        flow.publish().map_err(PlatformError::Conflict)?;
+        self.flow_repo.save(&flow).await?;
+        self.record_flow_change(
+            &flow,
+            Some(user_id),
+            FlowChange::Published,
+            Some(serde_json::json!({ "version": flow.current_version.0 })),
+        ).await;
+        Ok(flow)
+    }
+
+    async fn unpublish_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow> {
+        let mut flow = self.get_flow(flow_id, tenant_id).await?;
+        flow.unpublish().map_err(PlatformError::Conflict)?;
+        self.flow_repo.save(&flow).await?;
+        self.record_flow_change(&flow, Some(user_id), FlowChange::Unpublished, None).await;
+        Ok(flow)
+    }
+
+    async fn enable_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow> {
+        let mut flow = self.get_flow(flow_id, tenant_id).await?;
+        flow.enable().map_err(PlatformError::Conflict)?;
+        self.flow_repo.save(&flow).await?;
+        self.record_flow_change(&flow, Some(user_id), FlowChange::Enabled, None).await;
+        Ok(flow)
+    }
+
+    async fn disable_flow(&self, flow_id: FlowId, tenant_id: TenantId, user_id: UserId) -> Result<Flow> {
+        let mut flow = self.get_flow(flow_id, tenant_id).await?;
+        flow.disable().map_err(PlatformError::Conflict)?;
+        self.flow_repo.save(&flow).await?;
+        self.record_flow_change(&flow, Some(user_id), FlowChange::Disabled, None).await;
+        Ok(flow)
+    }
+
+    async fn clone_flow(
+        &self,
+        source_id: FlowId,
+        new_name: String,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<Flow> {
+        let source = self.get_flow(source_id, tenant_id).await?;
+        let source_version = self.version_repo
+            .find_by_flow_and_version(&source_id, &source.current_version).await?
+            .ok_or_else(|| PlatformError::NotFound(format!(
+                "Version {} of flow {} not found", source.current_version.0, source_id.0
+            )))?;
+
+        let mut flow = self.create_flow(tenant_id, new_name, source.description.clone(), user_id).await?;
+        if let Some(timeout_ms) = source.timeout_ms {
+            flow.update_timeout(Some(timeout_ms));
+            self.flow_repo.save(&flow).await?;
+        }
+
+        let version = FlowVersion::new(
+            flow.id,
+            Version::initial(),
+            source_version.definition.with_regenerated_ids(),
+            Some(format!("Cloned from flow {} version {}", source_id.0, source.current_version.0)),
+            user_id,
+        ).map_err(|e| PlatformError::ValidationError(e))?;
+
+        self.version_repo.save(&version, &tenant_id).await?;
+
         Ok(flow)
     }
 
@@ -287,10 +867,51 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         Ok((flow, validation))
     }
 
+    async fn import_from_langchain(
+        &self,
+        tenant_id: TenantId,
+        name: String,
+        json: String,
+        user_id: UserId,
+    ) -> Result<(Flow, ValidationResult)> {
+        let import = LangChainParser::new().parse(&json)?;
+
+        let mut validation = self.flow_domain_service.validate_flow_definition(&import.definition)?;
+
+        if !validation.is_valid {
+            return Err(PlatformError::ValidationError(
+                format!("Invalid flow definition: {:?}", validation.errors)
+            ));
+        }
+
+        // Surface unmapped steps in the import report
+        for warning in import.warnings {
+            validation.add_warning(warning);
+        }
+
+        let flow = self.create_flow(tenant_id, name, Some("Imported from LangChain".to_string()), user_id).await?;
+
+        let version = FlowVersion::new(
+            flow.id,
+            Version::initial(),
+            import.definition,
+            Some("Initial version from LangChain import".to_string()),
+            user_id,
+        ).map_err(|e| PlatformError::ValidationError(e))?;
+
+        self.version_repo.save(&version, &tenant_id).await?;
+
+        Ok((flow, validation))
+    }
+
     async fn validate_flow_definition(&self, definition: FlowDefinition) -> Result<ValidationResult> {
         self.flow_domain_service.validate_flow_definition(&definition)
     }
 
+    #[tracing::instrument(
+        skip(self, session_id, input_data),
+        fields(flow_id = %flow_id, tenant_id = %tenant_id, user_id = %user_id)
+    )]
     async fn execute_flow(
         &self,
         flow_id: FlowId,
@@ -299,75 +920,98 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         session_id: Option<SessionId>,
         input_data: Option<Value>,
     ) -> Result<FlowExecution> {
-        let flow = self.get_flow(flow_id, tenant_id).await?;
+        self.run_execution(flow_id, tenant_id, user_id, session_id, input_data, None).await
+    }
 
-        // Create minimal user for permission check
-        let user = User {
-            id: user_id,
-            tenant_id,
-            username: crate::domain::value_objects::Username::new("temp".to_string()).unwrap(),
-            nickname: None,
-            password_hash: String::new(),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
+    #[tracing::instrument(
+        skip(self, input_data),
+        fields(flow_id = %flow_id, tenant_id = %tenant_id, user_id = %user_id, %audit_correlation_id)
+    )]
+    async fn run_flow_with_audit(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        input_data: Option<Value>,
+        audit_correlation_id: Uuid,
+    ) -> Result<FlowExecution> {
+        self.run_execution(flow_id, tenant_id, user_id, None, input_data, Some(audit_correlation_id)).await
+    }
 
-        if !self.flow_domain_service.can_execute(&flow, &user) {
-            return Err(PlatformError::AuthorizationFailed("Cannot execute this flow".to_string()));
-        }
+    async fn start_execution(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        input_data: Option<Value>,
+    ) -> Result<FlowExecution> {
+        let flow = self.prepare_execution(flow_id, tenant_id, user_id, input_data.as_ref()).await?;
+        self.begin_execution(&flow, user_id, None, input_data, None).await
+    }
 
-        // Validate input
-        if let Some(ref input) = input_data {
-            let validation = self.flow_domain_service.validate_execution_input(&flow, input)?;
-            if !validation.is_valid {
-                return Err(PlatformError::ValidationError(
-                    format!("Invalid execution input: {:?}", validation.errors)
-                ));
-            }
+    async fn finish_execution(&self, execution: FlowExecution) -> Result<FlowExecution> {
+        let flow = self.get_flow(execution.flow_id, execution.tenant_id).await?;
+        self.complete_execution(&flow, execution).await
+    }
+
+    #[tracing::instrument(
+        skip(self, input_data, config),
+        fields(flow_id = %flow_id, tenant_id = %tenant_id, user_id = %user_id)
+    )]
+    async fn dry_run(
+        &self,
+        flow_id: FlowId,
+        input_data: Option<Value>,
+        tenant_id: TenantId,
+        user_id: UserId,
+        config: DryRunConfig,
+    ) -> Result<DryRunResult> {
+        let flow = self.prepare_execution(flow_id, tenant_id, user_id, input_data.as_ref()).await?;
+
+        let version = self.version_repo.find_latest_by_flow(&flow_id).await?
+            .ok_or_else(|| PlatformError::NotFound("Flow version not found".to_string()))?;
+
+        let validation = self.flow_domain_service.validate_flow_definition(&version.definition)?;
+        if !validation.is_valid {
+            return Err(PlatformError::ValidationError(
+                format!("Invalid flow definition: {:?}", validation.errors)
+            ));
         }
 
-        // Create execution record
+        let initial_variables = match input_data.clone() {
+            Some(Value::Object(map)) => map.into_iter().collect(),
+            _ => std::collections::HashMap::new(),
+        };
+
+        // Ephemeral execution: never saved, so no history or events
         let mut execution = FlowExecution::new(
             flow_id,
-            flow.current_version,
+            version.version,
             tenant_id,
             user_id,
-            session_id,
-            input_data.clone(),
+            None,
+            input_data,
         );
 
-        execution.start();
-        self.execution_repo.save(&execution).await?;
-
-        // Execute flow if engine is available
-        if let Some(ref engine) = self.execution_engine {
-            // Get the latest version definition
-            let version = self.version_repo.find_latest_by_flow(&flow_id).await?
-                .ok_or_else(|| PlatformError::NotFound("Flow version not found".to_string()))?;
-
-            // Prepare initial variables
-            let mut initial_variables = std::collections::HashMap::new();
-            if let Some(Value::Object(map)) = input_data {
-                for (key, value) in map {
-                    initial_variables.insert(key, value);
-                }
-            }
+        let engine = ExecutionEngineFactory::create_dry_run(config);
+        let (state, result) = engine
+            .execute_with_state(&mut execution, &version.definition, initial_variables, flow.timeout_ms)
+            .await;
 
-            match engine.execute(&mut execution, &version.definition, initial_variables).await {
-                Ok(state) => {
-                    // For now, just mark as completed with a simple status
-                    let output = serde_json::json!({"status": "completed", "variables": state.variables});
-                    execution.complete(output);
-                }
-                Err(e) => {
-                    execution.fail(e.to_string());
-                }
-            }
-            
-            self.execution_repo.save(&execution).await?;
-        }
+        let (status, error) = match result {
+            Ok(()) => ("completed", None),
+            Err(PlatformError::Timeout(message)) => ("timed_out", Some(message)),
+            Err(e) => ("failed", Some(e.to_string())),
+        };
 
-        Ok(execution)
+        Ok(DryRunResult::from_state(
+            flow_id,
+            version.version.0,
+            &version.definition,
+            state,
+            status,
+            error,
+        ))
     }
 
     async fn get_execution_status(&self, execution_id: FlowExecutionId, tenant_id: TenantId) -> Result<FlowExecution> {
@@ -417,6 +1061,7 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         user_id: UserId,
     ) -> Result<FlowVersion> {
         let mut flow = self.get_flow(flow_id, tenant_id).await?;
+        Self::ensure_modifiable(&flow, false)?;
 
         // Validate definition
         let validation = self.flow_domain_service.validate_flow_definition(&definition)?;
@@ -439,6 +1084,12 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
 
         self.version_repo.save(&version, &tenant_id).await?;
         self.flow_repo.save(&flow).await?;
+        self.record_flow_change(
+            &flow,
+            Some(user_id),
+            FlowChange::VersionCreated,
+            Some(serde_json::json!({ "version": flow.current_version.0 })),
+        ).await;
 
         Ok(version)
     }
@@ -451,6 +1102,16 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         Ok(versions)
     }
 
+    async fn get_flow_at_version(&self, flow_id: FlowId, tenant_id: TenantId, version: i32) -> Result<FlowVersion> {
+        // Verify access
+        let _ = self.get_flow(flow_id, tenant_id).await?;
+
+        self.version_repo.find_by_flow_and_version(&flow_id, &Version(version)).await?
+            .ok_or_else(|| PlatformError::NotFound(format!(
+                "Version {} of flow {} not found", version, flow_id.0
+            )))
+    }
+
     async fn rollback_to_version(
         &self,
         flow_id: FlowId,
@@ -459,6 +1120,7 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
         user_id: UserId,
     ) -> Result<Flow> {
         let mut flow = self.get_flow(flow_id, tenant_id).await?;
+        Self::ensure_modifiable(&flow, false)?;
 
         // Get target version
         let target_ver = Version(target_version);
@@ -477,7 +1139,103 @@ impl FlowApplicationService for FlowApplicationServiceImpl {
 
         self.version_repo.save(&new_version, &tenant_id).await?;
         self.flow_repo.save(&flow).await?;
+        self.record_flow_change(
+            &flow,
+            Some(user_id),
+            FlowChange::RolledBack,
+            Some(serde_json::json!({
+                "target_version": target_version,
+                "version": flow.current_version.0,
+            })),
+        ).await;
 
         Ok(flow)
     }
+
+    async fn add_annotation(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        node_id: String,
+        content: String,
+        user_id: UserId,
+    ) -> Result<FlowNodeAnnotation> {
+        let annotation_repo = self.annotation_repo()?;
+        let flow = self.get_flow(flow_id, tenant_id).await?;
+
+        // The node must exist in the version being reviewed
+        let version = self.version_repo.find_by_flow_and_version(&flow_id, &flow.current_version).await?
+            .ok_or_else(|| PlatformError::NotFound("Flow version not found".to_string()))?;
+        let node_exists = version.definition.workflow.graph.nodes.iter().any(|n| n.id == node_id);
+        if !node_exists {
+            return Err(PlatformError::NotFound(format!("Node '{}' not found in flow", node_id)));
+        }
+
+        let annotation = FlowNodeAnnotation::new(flow_id, node_id, flow.current_version, user_id, content)
+            .map_err(PlatformError::ValidationError)?;
+
+        annotation_repo.save(&annotation).await?;
+        Ok(annotation)
+    }
+
+    async fn resolve_annotation(
+        &self,
+        flow_id: FlowId,
+        annotation_id: FlowNodeAnnotationId,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<()> {
+        let annotation_repo = self.annotation_repo()?;
+        let mut annotation = annotation_repo.find_by_id(&annotation_id).await?
+            .filter(|annotation| annotation.flow_id == flow_id)
+            .ok_or_else(|| PlatformError::NotFound("Annotation not found".to_string()))?;
+
+        let flow = self.get_flow(annotation.flow_id, tenant_id).await?;
+
+        // Only the author or the flow owner can resolve a comment
+        if annotation.author_id != user_id && flow.created_by != user_id {
+            return Err(PlatformError::AuthorizationFailed(
+                "Only the author or the flow owner can resolve this annotation".to_string()
+            ));
+        }
+
+        annotation.resolve().map_err(PlatformError::ValidationError)?;
+        annotation_repo.save(&annotation).await
+    }
+
+    async fn list_annotations(
+        &self,
+        flow_id: FlowId,
+        tenant_id: TenantId,
+        node_id: Option<String>,
+    ) -> Result<Vec<FlowNodeAnnotation>> {
+        let annotation_repo = self.annotation_repo()?;
+        self.get_flow(flow_id, tenant_id).await?;
+
+        annotation_repo.find_by_flow(&flow_id, node_id.as_deref()).await
+    }
+
+    async fn count_annotations(&self, flow_id: FlowId, tenant_id: TenantId) -> Result<u32> {
+        let annotation_repo = match self.annotation_repo {
+            Some(ref repo) => repo,
+            None => return Ok(0),
+        };
+        self.get_flow(flow_id, tenant_id).await?;
+
+        Ok(annotation_repo.count_by_flow(&flow_id).await? as u32)
+    }
+
+    async fn count_annotations_by_flow(
+        &self,
+        flow_ids: &[FlowId],
+        tenant_id: TenantId,
+    ) -> Result<HashMap<FlowId, u32>> {
+        let annotation_repo = match self.annotation_repo {
+            Some(ref repo) => repo,
+            None => return Ok(HashMap::new()),
+        };
+
+        let counts = annotation_repo.count_by_flows(&tenant_id, flow_ids).await?;
+        Ok(counts.into_iter().map(|(flow_id, count)| (flow_id, count as u32)).collect())
+    }
 }
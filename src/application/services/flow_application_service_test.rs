@@ -0,0 +1,573 @@
+#[cfg(test)]
+mod tests {
+    use super::super::flow_application_service::*;
+    use crate::{
+        domain::{
+            entities::{Flow, FlowExecution, FlowNodeAnnotation, FlowVersion},
+            events::{AggregateSnapshot, DomainEvent, EventStore, InMemoryEventBus, InMemoryEventStore},
+            repositories::{
+                MockFlowExecutionRepository, MockFlowNodeAnnotationRepository, MockFlowRepository,
+                MockFlowVersionRepository,
+            },
+            services::{
+                execution_engine::{ExecutionEngine, ExecutionState, NodeExecutionResult},
+                FlowDomainServiceImpl,
+            },
+            value_objects::{
+                FlowDefinition, FlowEdge, FlowGraph, FlowId, FlowName, FlowNode, FlowWorkflow,
+                NodePosition, NodeType, TenantId, UserId, Version,
+            },
+        },
+        error::{PlatformError, Result},
+    };
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    /// Engine stub that completes immediately and echoes its initial variables
+    struct EchoExecutionEngine;
+
+    #[async_trait]
+    impl ExecutionEngine for EchoExecutionEngine {
+        async fn execute(
+            &self,
+            execution: &mut FlowExecution,
+            _definition: &FlowDefinition,
+            initial_variables: HashMap<String, Value>,
+        ) -> Result<ExecutionState> {
+            Ok(ExecutionState::new(execution.id, initial_variables))
+        }
+
+        async fn execute_node(
+            &self,
+            _node: &FlowNode,
+            _state: &mut ExecutionState,
+        ) -> Result<NodeExecutionResult> {
+            unimplemented!()
+        }
+
+        fn get_next_nodes(
+            &self,
+            _current_node: &FlowNode,
+            _definition: &FlowDefinition,
+            _state: &ExecutionState,
+        ) -> Result<Vec<String>> {
+            Ok(vec![])
+        }
+
+        fn evaluate_condition(&self, _condition: &Value, _state: &ExecutionState) -> Result<bool> {
+            Ok(true)
+        }
+    }
+
+    fn create_definition() -> FlowDefinition {
+        FlowDefinition {
+            workflow: FlowWorkflow {
+                graph: FlowGraph {
+                    nodes: vec![
+                        FlowNode {
+                            id: "start".to_string(),
+                            parent_id: None,
+                            node_type: NodeType::Start,
+                            data: json!({}),
+                            position: NodePosition { x: 0.0, y: 0.0 },
+                        },
+                        FlowNode {
+                            id: "answer".to_string(),
+                            parent_id: None,
+                            node_type: NodeType::Answer,
+                            data: json!({"answer": "done"}),
+                            position: NodePosition { x: 200.0, y: 0.0 },
+                        },
+                    ],
+                    edges: vec![FlowEdge {
+                        id: "start-answer".to_string(),
+                        source: "start".to_string(),
+                        target: "answer".to_string(),
+                        source_handle: None,
+                        target_handle: None,
+                    }],
+                },
+                environment_variables: Vec::new(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_flow_with_audit_links_events_and_history() {
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+        let correlation_id = Uuid::new_v4();
+
+        let mut flow = Flow::new(tenant_id, FlowName::new("Audited".to_string()).unwrap(), None, user_id);
+        flow.activate().unwrap();
+        let flow_id = flow.id;
+
+        let version = FlowVersion::new(flow_id, Version::new(), create_definition(), None, user_id).unwrap();
+
+        let mut flow_repo = MockFlowRepository::new();
+        flow_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(flow.clone())));
+
+        let mut version_repo = MockFlowVersionRepository::new();
+        version_repo
+            .expect_find_latest_by_flow()
+            .returning(move |_| Ok(Some(version.clone())));
+
+        let saved: Arc<Mutex<Vec<FlowExecution>>> = Arc::new(Mutex::new(Vec::new()));
+        let saved_clone = saved.clone();
+        let mut execution_repo = MockFlowExecutionRepository::new();
+        execution_repo.expect_save().returning(move |execution| {
+            saved_clone.lock().unwrap().push(execution.clone());
+            Ok(())
+        });
+
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(flow_repo),
+            Arc::new(version_repo),
+            Arc::new(execution_repo),
+            Arc::new(FlowDomainServiceImpl::new()),
+            Some(Arc::new(EchoExecutionEngine)),
+        )
+        .with_event_bus(event_bus.clone());
+
+        let execution = service
+            .run_flow_with_audit(flow_id, tenant_id, user_id, Some(json!({"q": "hi"})), correlation_id)
+            .await
+            .unwrap();
+
+        assert!(execution.is_completed());
+        assert_eq!(
+            execution.output_data.as_ref().unwrap()["variables"]["audit_correlation_id"],
+            json!(correlation_id.to_string())
+        );
+
+        // The persisted history record carries the correlation ID
+        let history = saved.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(history.correlation_id, Some(correlation_id));
+
+        // Every audit event shares the history record's correlation ID
+        let events = event_bus.published_events().await;
+        let event_types: Vec<&str> = events.iter().map(|e| e.event_type()).collect();
+        assert_eq!(event_types, vec!["flow_execution.started", "flow_execution.completed"]);
+        for event in &events {
+            assert_eq!(event.correlation_id(), history.correlation_id);
+            assert_eq!(event.aggregate_id(), history.id.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flow_mutations_are_appended_to_event_store() {
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+
+        let stored: Arc<Mutex<Option<Flow>>> = Arc::new(Mutex::new(None));
+        let mut flow_repo = MockFlowRepository::new();
+        flow_repo.expect_name_exists_in_tenant().returning(|_, _| Ok(false));
+        let stored_save = stored.clone();
+        flow_repo.expect_save().returning(move |flow| {
+            *stored_save.lock().unwrap() = Some(flow.clone());
+            Ok(())
+        });
+        let stored_find = stored.clone();
+        flow_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(stored_find.lock().unwrap().clone()));
+
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(flow_repo),
+            Arc::new(MockFlowVersionRepository::new()),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        )
+        .with_event_store(event_store.clone());
+
+        let flow = service
+            .create_flow(tenant_id, "Evented".to_string(), None, user_id)
+            .await
+            .unwrap();
+        service
+            .update_flow(flow.id, tenant_id, None, Some("Described".to_string()), None, user_id, false)
+            .await
+            .unwrap();
+
+        let events = event_store.load_events(flow.id.0, 0).await.unwrap();
+        let event_types: Vec<&str> = events.iter().map(|e| e.event_type()).collect();
+        assert_eq!(event_types, vec!["flow.created", "flow.updated"]);
+
+        let created = events[0].audit_entry().unwrap();
+        assert_eq!(created.tenant_id, tenant_id.0);
+        assert_eq!(created.user_id, Some(user_id.0));
+        assert_eq!(created.resource_id, Some(flow.id.0));
+    }
+
+    /// Event store that rejects every append, e.g. on a version conflict
+    struct FailingEventStore;
+
+    #[async_trait]
+    impl EventStore for FailingEventStore {
+        async fn append(&self, _event: &dyn DomainEvent) -> Result<i64> {
+            Err(PlatformError::Conflict("Version conflict".to_string()))
+        }
+
+        async fn load_events(&self, _aggregate_id: Uuid, _after_version: i64) -> Result<Vec<Box<dyn DomainEvent>>> {
+            Ok(Vec::new())
+        }
+
+        async fn load_snapshot(&self, _aggregate_id: Uuid) -> Result<Option<AggregateSnapshot>> {
+            Ok(None)
+        }
+
+        async fn save_snapshot(&self, _snapshot: AggregateSnapshot) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flow_change_succeeds_when_event_append_fails() {
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+
+        let stored: Arc<Mutex<Option<Flow>>> = Arc::new(Mutex::new(None));
+        let mut flow_repo = MockFlowRepository::new();
+        flow_repo.expect_name_exists_in_tenant().returning(|_, _| Ok(false));
+        let stored_save = stored.clone();
+        flow_repo.expect_save().returning(move |flow| {
+            *stored_save.lock().unwrap() = Some(flow.clone());
+            Ok(())
+        });
+        let stored_find = stored.clone();
+        flow_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(stored_find.lock().unwrap().clone()));
+
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(flow_repo),
+            Arc::new(MockFlowVersionRepository::new()),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        )
+        .with_event_store(Arc::new(FailingEventStore));
+
+        let flow = service
+            .create_flow(tenant_id, "Committed".to_string(), None, user_id)
+            .await
+            .unwrap();
+        let updated = service
+            .update_flow(flow.id, tenant_id, None, Some("Saved".to_string()), None, user_id, false)
+            .await
+            .unwrap();
+
+        assert_eq!(updated.description.as_deref(), Some("Saved"));
+        assert_eq!(stored.lock().unwrap().as_ref().unwrap().description.as_deref(), Some("Saved"));
+    }
+
+    #[tokio::test]
+    async fn test_update_flow_definition_keeps_previous_version() {
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+
+        let flow = Flow::new(tenant_id, FlowName::new("Versioned".to_string()).unwrap(), None, user_id);
+        let flow_id = flow.id;
+
+        let stored_flow: Arc<Mutex<Flow>> = Arc::new(Mutex::new(flow));
+        let mut flow_repo = MockFlowRepository::new();
+        let stored_find = stored_flow.clone();
+        flow_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(stored_find.lock().unwrap().clone())));
+        let stored_save = stored_flow.clone();
+        flow_repo.expect_save().returning(move |flow| {
+            *stored_save.lock().unwrap() = flow.clone();
+            Ok(())
+        });
+
+        let versions: Arc<Mutex<Vec<FlowVersion>>> = Arc::new(Mutex::new(vec![
+            FlowVersion::new(flow_id, Version::new(), create_definition(), None, user_id).unwrap(),
+        ]));
+        let mut version_repo = MockFlowVersionRepository::new();
+        let versions_save = versions.clone();
+        version_repo.expect_save().returning(move |version, _| {
+            versions_save.lock().unwrap().push(version.clone());
+            Ok(())
+        });
+        let versions_find = versions.clone();
+        version_repo
+            .expect_find_by_flow_and_version()
+            .returning(move |_, version| {
+                Ok(versions_find.lock().unwrap().iter().find(|v| &v.version == version).cloned())
+            });
+
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(flow_repo),
+            Arc::new(version_repo),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        );
+
+        let mut definition = create_definition();
+        definition.workflow.graph.nodes[1].data = json!({"answer": "changed"});
+
+        let updated = service
+            .update_flow(flow_id, tenant_id, None, None, Some(definition), user_id, false)
+            .await
+            .unwrap();
+        assert_eq!(updated.current_version, Version(2));
+
+        let original = service.get_flow_at_version(flow_id, tenant_id, 1).await.unwrap();
+        assert_eq!(original.definition.workflow.graph.nodes[1].data, json!({"answer": "done"}));
+
+        let latest = service.get_flow_at_version(flow_id, tenant_id, 2).await.unwrap();
+        assert_eq!(latest.definition.workflow.graph.nodes[1].data, json!({"answer": "changed"}));
+
+        assert!(service.get_flow_at_version(flow_id, tenant_id, 3).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_published_flow_is_only_updated_with_force() {
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+
+        let flow = Flow::new(tenant_id, FlowName::new("Deployed".to_string()).unwrap(), None, user_id);
+        let flow_id = flow.id;
+
+        let stored_flow: Arc<Mutex<Flow>> = Arc::new(Mutex::new(flow));
+        let mut flow_repo = MockFlowRepository::new();
+        let stored_find = stored_flow.clone();
+        flow_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(stored_find.lock().unwrap().clone())));
+        let stored_save = stored_flow.clone();
+        flow_repo.expect_save().returning(move |flow| {
+            *stored_save.lock().unwrap() = flow.clone();
+            Ok(())
+        });
+
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(flow_repo),
+            Arc::new(MockFlowVersionRepository::new()),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        );
+
+        let published = service.publish_flow(flow_id, tenant_id, user_id).await.unwrap();
+        assert!(published.is_published);
+        assert!(published.published_at.is_some());
+        assert!(matches!(
+            service.publish_flow(flow_id, tenant_id, user_id).await,
+            Err(PlatformError::Conflict(_))
+        ));
+
+        let result = service
+            .update_flow(flow_id, tenant_id, None, Some("Changed".to_string()), None, user_id, false)
+            .await;
+        assert!(matches!(result, Err(PlatformError::Conflict(_))));
+
+        let forced = service
+            .update_flow(flow_id, tenant_id, None, Some("Changed".to_string()), None, user_id, true)
+            .await
+            .unwrap();
+        assert_eq!(forced.description.as_deref(), Some("Changed"));
+        assert!(forced.is_published);
+
+        let unpublished = service.unpublish_flow(flow_id, tenant_id, user_id).await.unwrap();
+        assert!(!unpublished.is_published);
+        assert!(unpublished.published_at.is_none());
+        service
+            .update_flow(flow_id, tenant_id, None, Some("Editable".to_string()), None, user_id, false)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_disabled_flow_rejects_new_executions() {
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+
+        let mut flow = Flow::new(tenant_id, FlowName::new("Toggled".to_string()).unwrap(), None, user_id);
+        flow.activate().unwrap();
+        let flow_id = flow.id;
+
+        let stored_flow: Arc<Mutex<Flow>> = Arc::new(Mutex::new(flow));
+        let mut flow_repo = MockFlowRepository::new();
+        let stored_find = stored_flow.clone();
+        flow_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(stored_find.lock().unwrap().clone())));
+        let stored_save = stored_flow.clone();
+        flow_repo.expect_save().returning(move |flow| {
+            *stored_save.lock().unwrap() = flow.clone();
+            Ok(())
+        });
+
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(flow_repo),
+            Arc::new(MockFlowVersionRepository::new()),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        );
+
+        let disabled = service.disable_flow(flow_id, tenant_id, user_id).await.unwrap();
+        assert!(!disabled.is_enabled);
+        assert!(matches!(
+            service.disable_flow(flow_id, tenant_id, user_id).await,
+            Err(PlatformError::Conflict(_))
+        ));
+
+        let result = service
+            .execute_flow(flow_id, tenant_id, user_id, None, Some(json!({})))
+            .await;
+        match result {
+            Err(PlatformError::FlowExecutionFailed(message)) => assert_eq!(message, "Flow is disabled"),
+            other => panic!("Expected FlowExecutionFailed, got {:?}", other),
+        }
+
+        let enabled = service.enable_flow(flow_id, tenant_id, user_id).await.unwrap();
+        assert!(enabled.is_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_clone_flow_regenerates_node_ids() {
+        let tenant_id = TenantId::new();
+        let owner_id = UserId::new();
+        let cloner_id = UserId::new();
+
+        let mut source = Flow::new(tenant_id, FlowName::new("Original".to_string()).unwrap(), None, owner_id);
+        source.increment_version();
+        let source_id = source.id;
+
+        let mut definition = create_definition();
+        definition.workflow.graph.nodes[1].data = json!({
+            "answer": "{{#start.query#}}",
+            "value_selector": ["start", "query"],
+        });
+        let source_version = FlowVersion::new(source_id, source.current_version, definition, None, owner_id).unwrap();
+
+        let mut flow_repo = MockFlowRepository::new();
+        flow_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+        flow_repo.expect_name_exists_in_tenant().returning(|_, _| Ok(false));
+        flow_repo.expect_save().returning(|_| Ok(()));
+
+        let saved: Arc<Mutex<Vec<FlowVersion>>> = Arc::new(Mutex::new(Vec::new()));
+        let saved_clone = saved.clone();
+        let mut version_repo = MockFlowVersionRepository::new();
+        version_repo
+            .expect_find_by_flow_and_version()
+            .returning(move |_, _| Ok(Some(source_version.clone())));
+        version_repo.expect_save().returning(move |version, _| {
+            saved_clone.lock().unwrap().push(version.clone());
+            Ok(())
+        });
+
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(flow_repo),
+            Arc::new(version_repo),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        );
+
+        let clone = service
+            .clone_flow(source_id, "Copy".to_string(), tenant_id, cloner_id)
+            .await
+            .unwrap();
+        assert_ne!(clone.id, source_id);
+        assert_eq!(clone.current_version, Version::initial());
+        assert_eq!(clone.created_by, cloner_id);
+
+        let version = saved.lock().unwrap().pop().unwrap();
+        assert_eq!(version.flow_id, clone.id);
+        assert_eq!(version.version, Version::initial());
+
+        let graph = &version.definition.workflow.graph;
+        let start_id = &graph.nodes[0].id;
+        let answer_id = &graph.nodes[1].id;
+        assert_ne!(start_id, "start");
+        assert_ne!(answer_id, "answer");
+        assert_eq!(&graph.edges[0].source, start_id);
+        assert_eq!(&graph.edges[0].target, answer_id);
+        assert_eq!(
+            graph.nodes[1].data,
+            json!({
+                "answer": format!("{{{{#{}.query#}}}}", start_id),
+                "value_selector": [start_id, "query"],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_annotation_of_another_flow_is_not_found() {
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+        let flow = Flow::new(tenant_id, FlowName::new("Reviewed".to_string()).unwrap(), None, user_id);
+        let annotation = FlowNodeAnnotation::new(
+            FlowId::new(),
+            "answer".to_string(),
+            Version::new(),
+            user_id,
+            "Looks off".to_string(),
+        )
+        .unwrap();
+        let annotation_id = annotation.id;
+
+        let mut annotation_repo = MockFlowNodeAnnotationRepository::new();
+        annotation_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(annotation.clone())));
+        annotation_repo.expect_save().never();
+
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockFlowVersionRepository::new()),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        )
+        .with_annotation_repository(Arc::new(annotation_repo));
+
+        let result = service
+            .resolve_annotation(flow.id, annotation_id, tenant_id, user_id)
+            .await;
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_count_annotations_by_flow_uses_one_query() {
+        let tenant_id = TenantId::new();
+        let flow_ids = vec![FlowId::new(), FlowId::new(), FlowId::new()];
+        let annotated = flow_ids[1];
+
+        let mut annotation_repo = MockFlowNodeAnnotationRepository::new();
+        annotation_repo.expect_count_by_flow().never();
+        let expected_ids = flow_ids.clone();
+        annotation_repo
+            .expect_count_by_flows()
+            .withf(move |tenant, ids| *tenant == tenant_id && ids == expected_ids.as_slice())
+            .times(1)
+            .returning(move |_, _| Ok(HashMap::from([(annotated, 4)])));
+
+        let service = FlowApplicationServiceImpl::new(
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockFlowVersionRepository::new()),
+            Arc::new(MockFlowExecutionRepository::new()),
+            Arc::new(FlowDomainServiceImpl::new()),
+            None,
+        )
+        .with_annotation_repository(Arc::new(annotation_repo));
+
+        let counts = service.count_annotations_by_flow(&flow_ids, tenant_id).await.unwrap();
+        assert_eq!(counts, HashMap::from([(annotated, 4)]));
+    }
+}
@@ -11,7 +11,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
-use log::{info, warn, error};
+use tracing::{info, warn, error};
 use uuid::Uuid;
 
 /// Load balancing strategy for multiple providers
@@ -87,6 +87,7 @@ impl IntegratedLLMService {
                 crate::infrastructure::llm::RetryableErrorType::RateLimit,
                 crate::infrastructure::llm::RetryableErrorType::NetworkError,
                 crate::infrastructure::llm::RetryableErrorType::InternalServerError,
+                crate::infrastructure::llm::RetryableErrorType::Overloaded,
             ],
         };
 
@@ -406,6 +407,7 @@ impl LLMDomainService for IntegratedLLMService {
                             ),
                             tenant_id,
                             response_format,
+                            tools: None,
                         };
                         
                         provider.chat_completion(request).await
@@ -475,6 +477,7 @@ impl LLMDomainService for IntegratedLLMService {
                 ),
                 tenant_id,
                 response_format: None,
+                tools: None,
             };
             
             provider.stream_chat_completion(request).await
@@ -0,0 +1,360 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dto::agent_dto::{AnswerEvaluationDto, InterviewTurnDto},
+        services::LLMConfigSelector,
+    },
+    domain::{
+        entities::{Agent, InterviewAnswer, InterviewRecord, InterviewStatus},
+        repositories::{AgentRepository, InterviewRecordRepository},
+        services::{llm_service::ResponseFormat, AgentStatsService, LLMDomainService},
+        value_objects::{AgentId, ChatMessage, TenantId, UserId},
+    },
+    error::{PlatformError, Result},
+};
+
+/// Most questions asked in one interview
+pub const MAX_INTERVIEW_QUESTIONS: usize = 5;
+
+/// Overall score out of 100 needed to pass
+pub const INTERVIEW_PASS_SCORE: i32 = 60;
+
+const QUESTION_GENERATION_PROMPT: &str = r#"You are preparing a job interview for the role described by the assistant instructions below.
+Write questions that test whether a candidate could do this role well. Ask one thing per question and keep each question to a single sentence.
+Answer with a JSON object and nothing else: {"questions": ["<question>", ...]} with 3 to 5 questions."#;
+
+const ANSWER_EVALUATION_PROMPT: &str = r#"You are interviewing a candidate for the role described by the assistant instructions below.
+Evaluate the candidate's answer to the interview question. Score it from 0 (wrong or missing) to 10 (complete and correct) and explain the score in one or two sentences addressed to the candidate.
+Answer with a JSON object and nothing else: {"score": <0-10>, "feedback": "<feedback>"}"#;
+
+#[async_trait]
+pub trait InterviewApplicationService: Send + Sync {
+    /// Ask the agent's LLM for interview questions based on its system prompt
+    async fn generate_questions(&self, agent_id: AgentId, tenant_id: TenantId) -> Result<Vec<String>>;
+
+    /// Evaluate the answer to the current question and ask the next one.
+    /// The first turn of an interview generates the questions and asks the
+    /// first; the last one completes the record with the overall score.
+    async fn conduct_interview_turn(
+        &self,
+        record_id: Uuid,
+        tenant_id: TenantId,
+        user_id: UserId,
+        user_answer: String,
+    ) -> Result<InterviewTurnDto>;
+}
+
+pub struct InterviewApplicationServiceImpl {
+    agent_repo: Arc<dyn AgentRepository>,
+    interview_record_repo: Arc<dyn InterviewRecordRepository>,
+    llm_service: Arc<dyn LLMDomainService>,
+    llm_config_selector: Arc<LLMConfigSelector>,
+    stats_service: Option<Arc<AgentStatsService>>,
+}
+
+impl InterviewApplicationServiceImpl {
+    pub fn new(
+        agent_repo: Arc<dyn AgentRepository>,
+        interview_record_repo: Arc<dyn InterviewRecordRepository>,
+        llm_service: Arc<dyn LLMDomainService>,
+        llm_config_selector: Arc<LLMConfigSelector>,
+    ) -> Self {
+        Self {
+            agent_repo,
+            interview_record_repo,
+            llm_service,
+            llm_config_selector,
+            stats_service: None,
+        }
+    }
+
+    /// Set stats service so passed interviews are counted
+    pub fn with_stats_service(mut self, stats_service: Arc<AgentStatsService>) -> Self {
+        self.stats_service = Some(stats_service);
+        self
+    }
+
+    async fn find_agent(&self, agent_id: AgentId, tenant_id: TenantId) -> Result<Agent> {
+        let agent = self
+            .agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .ok_or_else(|| PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0)))?;
+
+        if agent.tenant_id != tenant_id {
+            return Err(PlatformError::AgentUnauthorized(
+                "Agent does not belong to your tenant".to_string(),
+            ));
+        }
+
+        Ok(agent)
+    }
+
+    /// Send a meta-prompt about the agent to the agent's LLM and return the
+    /// JSON content of the response
+    async fn ask_llm(&self, agent: &Agent, prompt: &str, input: String) -> Result<String> {
+        let llm_config = self
+            .llm_config_selector
+            .select(agent.tenant_id, agent.preferred_llm_config_id(), &agent.llm_fallback_strategy)
+            .await?;
+
+        let messages = vec![
+            ChatMessage::new_system_message(format!(
+                "{}\n\nAssistant instructions:\n{}",
+                prompt, agent.system_prompt
+            )),
+            ChatMessage::new_user_message(input),
+        ];
+
+        let response = self
+            .llm_service
+            .chat_completion(
+                &llm_config.model_config,
+                messages,
+                agent.tenant_id.0,
+                Some(ResponseFormat {
+                    format_type: "json_object".to_string(),
+                    json_schema: None,
+                }),
+            )
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+
+        Ok(response.content)
+    }
+
+    /// The outermost JSON object in an LLM response, tolerating code fences
+    /// and surrounding prose
+    fn extract_json(content: &str) -> Option<serde_json::Value> {
+        let start = content.find('{')?;
+        let end = content.rfind('}')?;
+        if start >= end {
+            return None;
+        }
+        serde_json::from_str(&content[start..=end]).ok()
+    }
+
+    fn parse_questions(content: &str) -> Result<Vec<String>> {
+        let questions: Vec<String> = Self::extract_json(content)
+            .and_then(|value| value.get("questions").cloned())
+            .and_then(|questions| serde_json::from_value::<Vec<String>>(questions).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|question| question.trim().to_string())
+            .filter(|question| !question.is_empty())
+            .take(MAX_INTERVIEW_QUESTIONS)
+            .collect();
+
+        if questions.is_empty() {
+            return Err(PlatformError::InternalError(
+                "LLM did not return any interview questions".to_string(),
+            ));
+        }
+        Ok(questions)
+    }
+
+    fn parse_evaluation(content: &str) -> Result<AnswerEvaluationDto> {
+        let value = Self::extract_json(content).ok_or_else(|| {
+            PlatformError::InternalError("LLM returned an invalid answer evaluation".to_string())
+        })?;
+
+        let score = value
+            .get("score")
+            .and_then(|score| score.as_f64())
+            .ok_or_else(|| {
+                PlatformError::InternalError("LLM answer evaluation has no score".to_string())
+            })?;
+
+        Ok(AnswerEvaluationDto {
+            score: (score.round() as i32).clamp(0, 10),
+            feedback: value
+                .get("feedback")
+                .and_then(|feedback| feedback.as_str())
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        })
+    }
+
+    /// Average answer score scaled to 100
+    fn overall_score(answers: &[InterviewAnswer]) -> i32 {
+        if answers.is_empty() {
+            return 0;
+        }
+        let total: i32 = answers.iter().map(|answer| answer.score).sum();
+        total * 10 / answers.len() as i32
+    }
+
+    async fn find_active_record(
+        &self,
+        record_id: Uuid,
+        tenant_id: TenantId,
+        user_id: UserId,
+    ) -> Result<InterviewRecord> {
+        let record = self
+            .interview_record_repo
+            .find_by_id(record_id)
+            .await?
+            .filter(|record| record.tenant_id == tenant_id)
+            .ok_or_else(|| PlatformError::NotFound(format!("Interview {} not found", record_id)))?;
+
+        if record.user_id != Some(user_id) {
+            return Err(PlatformError::AuthorizationFailed(
+                "Interview belongs to another user".to_string(),
+            ));
+        }
+        if record.status != InterviewStatus::InProgress {
+            return Err(PlatformError::ValidationError(format!(
+                "Interview is {}, not in progress",
+                record.status.as_str()
+            )));
+        }
+
+        Ok(record)
+    }
+
+    fn turn_dto(
+        record: &InterviewRecord,
+        evaluation: Option<AnswerEvaluationDto>,
+    ) -> InterviewTurnDto {
+        InterviewTurnDto {
+            record_id: record.id,
+            evaluation,
+            next_question: record.current_question(),
+            completed: record.is_completed(),
+            status: record.status.as_str().to_string(),
+            score: record.score,
+            feedback: record.feedback.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl InterviewApplicationService for InterviewApplicationServiceImpl {
+    async fn generate_questions(&self, agent_id: AgentId, tenant_id: TenantId) -> Result<Vec<String>> {
+        let agent = self.find_agent(agent_id, tenant_id).await?;
+
+        let content = self
+            .ask_llm(
+                &agent,
+                QUESTION_GENERATION_PROMPT,
+                format!("Write the interview questions for the role of {}.", agent.name),
+            )
+            .await?;
+
+        Self::parse_questions(&content)
+    }
+
+    async fn conduct_interview_turn(
+        &self,
+        record_id: Uuid,
+        tenant_id: TenantId,
+        user_id: UserId,
+        user_answer: String,
+    ) -> Result<InterviewTurnDto> {
+        let mut record = self.find_active_record(record_id, tenant_id, user_id).await?;
+
+        // Opening turn: prepare the questions and ask the first one
+        if record.question_list().is_empty() {
+            let questions = self.generate_questions(record.agent_id, tenant_id).await?;
+            record.set_questions(serde_json::json!(questions));
+            let record = self.interview_record_repo.update(&record).await?;
+            return Ok(Self::turn_dto(&record, None));
+        }
+
+        let user_answer = user_answer.trim().to_string();
+        if user_answer.is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Answer cannot be empty".to_string(),
+            ));
+        }
+
+        let question = record.current_question().ok_or_else(|| {
+            PlatformError::InternalError(format!("Interview {} has no open question", record_id))
+        })?;
+
+        let agent = self.find_agent(record.agent_id, tenant_id).await?;
+        let content = self
+            .ask_llm(
+                &agent,
+                ANSWER_EVALUATION_PROMPT,
+                format!("Question: {}\n\nCandidate's answer: {}", question, user_answer),
+            )
+            .await?;
+        let evaluation = Self::parse_evaluation(&content)?;
+
+        record.record_answer(InterviewAnswer {
+            question,
+            answer: user_answer,
+            score: evaluation.score,
+            feedback: evaluation.feedback.clone(),
+        });
+
+        let finished = record.current_question().is_none();
+        if finished {
+            let answers = record.recorded_answers();
+            let score = Self::overall_score(&answers);
+            let passed = score >= INTERVIEW_PASS_SCORE;
+            let status = if passed { InterviewStatus::Passed } else { InterviewStatus::Failed };
+            record.complete(
+                status,
+                Some(score),
+                Some(format!(
+                    "Answered {} questions with an overall score of {}/100",
+                    answers.len(),
+                    score
+                )),
+            );
+
+            if passed {
+                if let Some(stats_service) = &self.stats_service {
+                    stats_service.record_interview_passed(record.agent_id, tenant_id).await?;
+                }
+            }
+        }
+
+        let record = self.interview_record_repo.update(&record).await?;
+        Ok(Self::turn_dto(&record, Some(evaluation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_questions_from_fenced_json() {
+        let content = "```json\n{\"questions\": [\" What is RAG? \", \"\", \"How do you test prompts?\"]}\n```";
+        let questions = InterviewApplicationServiceImpl::parse_questions(content).unwrap();
+        assert_eq!(questions, vec!["What is RAG?", "How do you test prompts?"]);
+
+        assert!(InterviewApplicationServiceImpl::parse_questions("no questions").is_err());
+    }
+
+    #[test]
+    fn test_parse_evaluation_clamps_score() {
+        let evaluation =
+            InterviewApplicationServiceImpl::parse_evaluation(r#"{"score": 12, "feedback": "Great"}"#)
+                .unwrap();
+        assert_eq!(evaluation.score, 10);
+        assert_eq!(evaluation.feedback, "Great");
+
+        assert!(InterviewApplicationServiceImpl::parse_evaluation(r#"{"feedback": "?"}"#).is_err());
+    }
+
+    #[test]
+    fn test_overall_score() {
+        let answer = |score| InterviewAnswer {
+            question: "Q".to_string(),
+            answer: "A".to_string(),
+            score,
+            feedback: String::new(),
+        };
+
+        assert_eq!(InterviewApplicationServiceImpl::overall_score(&[answer(6), answer(9)]), 75);
+        assert_eq!(InterviewApplicationServiceImpl::overall_score(&[]), 0);
+    }
+}
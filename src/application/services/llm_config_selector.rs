@@ -0,0 +1,299 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, RwLock};
+
+use crate::{
+    domain::{
+        entities::LLMConfig,
+        repositories::LLMConfigRepository,
+        value_objects::{ConfigId, LLMSelectionStrategy, TenantId},
+    },
+    error::{PlatformError, Result},
+};
+
+/// Number of recent responses averaged per config by `LeastLatency`
+const LATENCY_WINDOW: usize = 20;
+
+/// Picks the LLM config used to serve a chat request
+pub struct LLMConfigSelector {
+    llm_config_repo: Arc<dyn LLMConfigRepository>,
+    round_robin: Mutex<HashMap<TenantId, usize>>,
+    latencies: RwLock<HashMap<ConfigId, VecDeque<Duration>>>,
+}
+
+impl LLMConfigSelector {
+    pub fn new(llm_config_repo: Arc<dyn LLMConfigRepository>) -> Self {
+        Self {
+            llm_config_repo,
+            round_robin: Mutex::new(HashMap::new()),
+            latencies: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Return the preferred config if it exists and is active, otherwise
+    /// apply `strategy` over the tenant's active configs
+    pub async fn select(
+        &self,
+        tenant_id: TenantId,
+        preferred: Option<ConfigId>,
+        strategy: &LLMSelectionStrategy,
+    ) -> Result<LLMConfig> {
+        if let Some(config_id) = preferred {
+            match self.llm_config_repo.find_by_id(config_id).await? {
+                Some(config) if config.is_active => return Ok(config),
+                Some(_) => tracing::warn!(
+                    "LLM configuration {} is inactive, falling back to {:?}",
+                    config_id.0,
+                    strategy
+                ),
+                None => tracing::warn!(
+                    "LLM configuration {} not found, falling back to {:?}",
+                    config_id.0,
+                    strategy
+                ),
+            }
+        }
+
+        let candidates = self.llm_config_repo.find_active_by_tenant(tenant_id).await?;
+        if candidates.is_empty() {
+            return Err(PlatformError::NotFound(
+                "No LLM configuration found for tenant".to_string(),
+            ));
+        }
+
+        let selected = match strategy {
+            LLMSelectionStrategy::FirstAvailable => Self::first_available(&candidates),
+            LLMSelectionStrategy::RoundRobin => self.next_round_robin(tenant_id, &candidates).await,
+            LLMSelectionStrategy::LeastLatency => self.least_latency(&candidates).await,
+            LLMSelectionStrategy::ModelPreference(models) => {
+                Self::by_model_preference(&candidates, models)
+                    .unwrap_or_else(|| Self::first_available(&candidates))
+            }
+        };
+
+        Ok(selected.clone())
+    }
+
+    /// Record how long a config took to answer, for `LeastLatency`
+    pub async fn record_latency(&self, config_id: ConfigId, latency: Duration) {
+        let mut latencies = self.latencies.write().await;
+        let samples = latencies.entry(config_id).or_default();
+        if samples.len() == LATENCY_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+
+    /// Rolling average response time, `None` until the config has answered
+    pub async fn average_latency(&self, config_id: ConfigId) -> Option<Duration> {
+        let latencies = self.latencies.read().await;
+        let samples = latencies.get(&config_id).filter(|samples| !samples.is_empty())?;
+        Some(samples.iter().sum::<Duration>() / samples.len() as u32)
+    }
+
+    fn first_available(candidates: &[LLMConfig]) -> &LLMConfig {
+        candidates
+            .iter()
+            .find(|config| config.is_default)
+            .unwrap_or(&candidates[0])
+    }
+
+    async fn next_round_robin<'a>(
+        &self,
+        tenant_id: TenantId,
+        candidates: &'a [LLMConfig],
+    ) -> &'a LLMConfig {
+        let mut counters = self.round_robin.lock().await;
+        let counter = counters.entry(tenant_id).or_insert(0);
+        let selected = &candidates[*counter % candidates.len()];
+        *counter = counter.wrapping_add(1);
+        selected
+    }
+
+    /// Configs without measurements sort first so each one gets sampled
+    async fn least_latency<'a>(&self, candidates: &'a [LLMConfig]) -> &'a LLMConfig {
+        let mut best = &candidates[0];
+        let mut best_latency = Duration::MAX;
+
+        for config in candidates {
+            let latency = self
+                .average_latency(config.id)
+                .await
+                .unwrap_or(Duration::ZERO);
+            if latency < best_latency {
+                best = config;
+                best_latency = latency;
+            }
+        }
+
+        best
+    }
+
+    fn by_model_preference<'a>(
+        candidates: &'a [LLMConfig],
+        models: &[String],
+    ) -> Option<&'a LLMConfig> {
+        models.iter().find_map(|model| {
+            candidates
+                .iter()
+                .find(|config| config.model_config.model_name.eq_ignore_ascii_case(model))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ModelConfig, ModelCredentials, ModelParameters, ModelProvider};
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        ConfigRepo {}
+
+        #[async_trait]
+        impl LLMConfigRepository for ConfigRepo {
+            async fn find_by_id(&self, id: ConfigId) -> Result<Option<LLMConfig>>;
+            async fn find_by_tenant(&self, tenant_id: TenantId) -> Result<Vec<LLMConfig>>;
+            async fn find_active_by_tenant(&self, tenant_id: TenantId) -> Result<Vec<LLMConfig>>;
+            async fn find_default_by_tenant(&self, tenant_id: TenantId) -> Result<Option<LLMConfig>>;
+            async fn find_by_tenant_and_name(&self, tenant_id: TenantId, name: &str) -> Result<Option<LLMConfig>>;
+            async fn save(&self, config: &LLMConfig) -> Result<()>;
+            async fn delete(&self, id: ConfigId) -> Result<()>;
+            async fn name_exists(&self, tenant_id: TenantId, name: &str) -> Result<bool>;
+            async fn count_by_tenant(&self, tenant_id: TenantId) -> Result<u64>;
+            async fn find_by_tenant_and_provider(&self, tenant_id: TenantId, provider: &str) -> Result<Vec<LLMConfig>>;
+            async fn set_as_default(&self, tenant_id: TenantId, config_id: ConfigId) -> Result<()>;
+            async fn find_by_tenant_paginated(&self, tenant_id: TenantId, offset: u64, limit: u64) -> Result<Vec<LLMConfig>>;
+        }
+    }
+
+    fn config(tenant_id: TenantId, model_name: &str) -> LLMConfig {
+        LLMConfig::new(
+            tenant_id,
+            model_name.to_string(),
+            ModelConfig {
+                provider: ModelProvider::OpenAI,
+                model_name: model_name.to_string(),
+                parameters: ModelParameters::default(),
+                credentials: ModelCredentials::default(),
+            },
+        )
+    }
+
+    fn selector_with(configs: Vec<LLMConfig>) -> LLMConfigSelector {
+        let mut repo = MockConfigRepo::new();
+        let by_id = configs.clone();
+        repo.expect_find_by_id()
+            .returning(move |id| Ok(by_id.iter().find(|c| c.id == id).cloned()));
+        repo.expect_find_active_by_tenant()
+            .returning(move |_| Ok(configs.iter().filter(|c| c.is_active).cloned().collect()));
+        LLMConfigSelector::new(Arc::new(repo))
+    }
+
+    #[tokio::test]
+    async fn test_preferred_config_wins_when_active() {
+        let tenant_id = TenantId::new();
+        let first = config(tenant_id, "gpt-4o");
+        let preferred = config(tenant_id, "claude-3-5-sonnet");
+        let selector = selector_with(vec![first, preferred.clone()]);
+
+        let selected = selector
+            .select(tenant_id, Some(preferred.id), &LLMSelectionStrategy::FirstAvailable)
+            .await
+            .unwrap();
+
+        assert_eq!(selected.id, preferred.id);
+    }
+
+    #[tokio::test]
+    async fn test_inactive_preferred_falls_back_to_default() {
+        let tenant_id = TenantId::new();
+        let inactive = config(tenant_id, "gpt-4o").deactivate();
+        let other = config(tenant_id, "gpt-4o-mini");
+        let default = config(tenant_id, "claude-3-5-sonnet").set_as_default();
+        let selector = selector_with(vec![inactive.clone(), other, default.clone()]);
+
+        let selected = selector
+            .select(tenant_id, Some(inactive.id), &LLMSelectionStrategy::FirstAvailable)
+            .await
+            .unwrap();
+
+        assert_eq!(selected.id, default.id);
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_rotates_through_configs() {
+        let tenant_id = TenantId::new();
+        let a = config(tenant_id, "a");
+        let b = config(tenant_id, "b");
+        let selector = selector_with(vec![a.clone(), b.clone()]);
+
+        let mut picked = Vec::new();
+        for _ in 0..3 {
+            picked.push(
+                selector
+                    .select(tenant_id, None, &LLMSelectionStrategy::RoundRobin)
+                    .await
+                    .unwrap()
+                    .id,
+            );
+        }
+
+        assert_eq!(picked, vec![a.id, b.id, a.id]);
+    }
+
+    #[tokio::test]
+    async fn test_least_latency_uses_rolling_average() {
+        let tenant_id = TenantId::new();
+        let slow = config(tenant_id, "slow");
+        let fast = config(tenant_id, "fast");
+        let selector = selector_with(vec![slow.clone(), fast.clone()]);
+
+        selector.record_latency(slow.id, Duration::from_millis(900)).await;
+        selector.record_latency(fast.id, Duration::from_millis(1200)).await;
+        selector.record_latency(fast.id, Duration::from_millis(200)).await;
+
+        let selected = selector
+            .select(tenant_id, None, &LLMSelectionStrategy::LeastLatency)
+            .await
+            .unwrap();
+
+        assert_eq!(selected.id, fast.id);
+        assert_eq!(
+            selector.average_latency(fast.id).await,
+            Some(Duration::from_millis(700))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_model_preference_follows_list_order() {
+        let tenant_id = TenantId::new();
+        let gpt = config(tenant_id, "gpt-4o");
+        let claude = config(tenant_id, "claude-3-5-sonnet");
+        let selector = selector_with(vec![gpt.clone(), claude.clone()]);
+
+        let strategy = LLMSelectionStrategy::ModelPreference(vec![
+            "llama-3".to_string(),
+            "claude-3-5-sonnet".to_string(),
+            "gpt-4o".to_string(),
+        ]);
+        let selected = selector.select(tenant_id, None, &strategy).await.unwrap();
+
+        assert_eq!(selected.id, claude.id);
+    }
+
+    #[tokio::test]
+    async fn test_no_active_configs_is_not_found() {
+        let tenant_id = TenantId::new();
+        let selector = selector_with(vec![config(tenant_id, "gpt-4o").deactivate()]);
+
+        let result = selector
+            .select(tenant_id, None, &LLMSelectionStrategy::FirstAvailable)
+            .await;
+
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+    }
+}
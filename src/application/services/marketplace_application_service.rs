@@ -0,0 +1,522 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dto::{
+            agent_dto::{PaginatedResponse, PaginationParams},
+            marketplace_dto::*,
+        },
+        services::{AgentApplicationService, AuditApplicationService},
+    },
+    domain::{
+        entities::{Agent, AuditAction, ResourceType},
+        repositories::{AgentRepository, MarketplaceAgentFilter, UserRepository},
+        value_objects::{AgentId, TenantId, UserId},
+    },
+    error::{PlatformError, Result},
+};
+
+const DEFAULT_CURRENCY: &str = "USD";
+const PURCHASE_AUDIT_ACTION: &str = "marketplace_purchase";
+
+/// Status of a payment intent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentIntentStatus {
+    RequiresPayment,
+    Succeeded,
+    Failed,
+}
+
+impl PaymentIntentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentIntentStatus::RequiresPayment => "requires_payment",
+            PaymentIntentStatus::Succeeded => "succeeded",
+            PaymentIntentStatus::Failed => "failed",
+        }
+    }
+}
+
+/// A charge for one marketplace purchase
+#[derive(Debug, Clone)]
+pub struct PaymentIntent {
+    pub id: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub status: PaymentIntentStatus,
+}
+
+/// Payment provider used by marketplace purchases
+#[async_trait]
+pub trait PaymentGateway: Send + Sync {
+    async fn create_payment_intent(
+        &self,
+        amount: Decimal,
+        currency: &str,
+        agent_id: AgentId,
+        buyer_id: UserId,
+        payment_method: Option<String>,
+    ) -> Result<PaymentIntent>;
+}
+
+/// Gateway that settles every intent immediately without charging anyone.
+/// Stands in until a real payment provider is integrated.
+pub struct StubPaymentGateway;
+
+#[async_trait]
+impl PaymentGateway for StubPaymentGateway {
+    async fn create_payment_intent(
+        &self,
+        amount: Decimal,
+        currency: &str,
+        _agent_id: AgentId,
+        _buyer_id: UserId,
+        _payment_method: Option<String>,
+    ) -> Result<PaymentIntent> {
+        Ok(PaymentIntent {
+            id: format!("pi_stub_{}", Uuid::new_v4().simple()),
+            amount,
+            currency: currency.to_string(),
+            status: PaymentIntentStatus::Succeeded,
+        })
+    }
+}
+
+#[async_trait]
+pub trait MarketplaceApplicationService: Send + Sync {
+    /// List published agents in the tenant's marketplace
+    async fn list_agents(
+        &self,
+        tenant_id: TenantId,
+        query: MarketplaceAgentQuery,
+    ) -> Result<PaginatedResponse<MarketplaceAgentDto>>;
+
+    /// Search published agents of every tenant by name and greeting
+    async fn search_agents(
+        &self,
+        query: MarketplaceSearchQuery,
+    ) -> Result<PaginatedResponse<MarketplaceAgentDto>>;
+
+    /// Get the public view of a published agent
+    async fn get_agent(&self, agent_id: AgentId, tenant_id: TenantId) -> Result<MarketplaceAgentDto>;
+
+    /// Pay for a published agent and employ it
+    async fn purchase_agent(
+        &self,
+        agent_id: AgentId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        request: PurchaseAgentRequest,
+    ) -> Result<PurchaseAgentResponse>;
+}
+
+pub struct MarketplaceApplicationServiceImpl {
+    agent_repo: Arc<dyn AgentRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    agent_service: Arc<dyn AgentApplicationService>,
+    audit_service: Arc<AuditApplicationService>,
+    payment_gateway: Arc<dyn PaymentGateway>,
+}
+
+impl MarketplaceApplicationServiceImpl {
+    pub fn new(
+        agent_repo: Arc<dyn AgentRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        agent_service: Arc<dyn AgentApplicationService>,
+        audit_service: Arc<AuditApplicationService>,
+    ) -> Self {
+        Self {
+            agent_repo,
+            user_repo,
+            agent_service,
+            audit_service,
+            payment_gateway: Arc::new(StubPaymentGateway),
+        }
+    }
+
+    pub fn with_payment_gateway(mut self, payment_gateway: Arc<dyn PaymentGateway>) -> Self {
+        self.payment_gateway = payment_gateway;
+        self
+    }
+
+    /// Find an agent that is listed in the tenant's marketplace. Unlisted
+    /// agents are reported as missing so their existence is not revealed.
+    async fn find_listed_agent(&self, agent_id: AgentId, tenant_id: TenantId) -> Result<Agent> {
+        let not_found = || PlatformError::AgentNotFound(format!("Agent {} not found", agent_id.0));
+
+        let agent = self
+            .agent_repo
+            .find_by_id(&agent_id)
+            .await?
+            .ok_or_else(not_found)?;
+
+        let listed = agent.tenant_id == tenant_id
+            && agent.is_published
+            && agent.employer_id.is_none()
+            && agent.fired_at.is_none();
+        if !listed {
+            return Err(not_found());
+        }
+
+        Ok(agent)
+    }
+
+    /// Public views of the agents, with creators loaded in one query
+    async fn to_marketplace_dtos(&self, agents: &[Agent]) -> Result<Vec<MarketplaceAgentDto>> {
+        let mut creator_ids: Vec<UserId> = agents.iter().map(|agent| agent.creator_id).collect();
+        creator_ids.sort_by_key(|id| id.0);
+        creator_ids.dedup();
+        let creators = self.user_repo.find_by_ids(&creator_ids).await?;
+
+        Ok(agents
+            .iter()
+            .map(|agent| {
+                let creator_name = creators
+                    .get(&agent.creator_id)
+                    .map(|creator| creator.nickname.clone().unwrap_or(creator.username.0.clone()))
+                    .unwrap_or_default();
+                Self::to_marketplace_dto(agent, creator_name)
+            })
+            .collect())
+    }
+
+    fn to_marketplace_dto(agent: &Agent, creator_name: String) -> MarketplaceAgentDto {
+        MarketplaceAgentDto {
+            id: agent.id.0,
+            name: agent.name.clone(),
+            avatar: agent.avatar.clone(),
+            greeting: agent.greeting.clone(),
+            preset_questions: agent.preset_questions.clone(),
+            creator_name,
+            knowledge_base_count: agent.knowledge_base_ids.len(),
+            mcp_tool_count: agent.mcp_tool_ids.len(),
+            flow_count: agent.flow_ids.len(),
+            price: agent.price,
+            published_at: agent.published_at,
+            created_at: agent.created_at,
+        }
+    }
+}
+
+#[async_trait]
+impl MarketplaceApplicationService for MarketplaceApplicationServiceImpl {
+    async fn list_agents(
+        &self,
+        tenant_id: TenantId,
+        query: MarketplaceAgentQuery,
+    ) -> Result<PaginatedResponse<MarketplaceAgentDto>> {
+        if let (Some(min), Some(max)) = (query.min_price, query.max_price) {
+            if min > max {
+                return Err(PlatformError::ValidationError(
+                    "min_price cannot be greater than max_price".to_string(),
+                ));
+            }
+        }
+
+        let params = PaginationParams {
+            page: query.page,
+            limit: query.limit,
+        };
+        let filter = MarketplaceAgentFilter {
+            keyword: query.keyword,
+            min_price: query.min_price,
+            max_price: query.max_price,
+        };
+
+        let (agents, total) = self
+            .agent_repo
+            .search_marketplace_paginated(&tenant_id, &filter, params.get_offset(), params.get_limit())
+            .await?;
+
+        let items = self.to_marketplace_dtos(&agents).await?;
+        Ok(PaginatedResponse::new(items, total, params.get_page(), params.get_limit()))
+    }
+
+    async fn search_agents(
+        &self,
+        query: MarketplaceSearchQuery,
+    ) -> Result<PaginatedResponse<MarketplaceAgentDto>> {
+        let keyword = query
+            .q
+            .filter(|q| !q.trim().is_empty())
+            .ok_or_else(|| PlatformError::ValidationError("Search query q is required".to_string()))?;
+
+        let params = PaginationParams {
+            page: query.page,
+            limit: query.limit,
+        };
+        let (agents, total) = self
+            .agent_repo
+            .search_published_by_keyword(&keyword, params.get_offset(), params.get_limit())
+            .await?;
+
+        let items = self.to_marketplace_dtos(&agents).await?;
+        Ok(PaginatedResponse::new(items, total, params.get_page(), params.get_limit()))
+    }
+
+    async fn get_agent(&self, agent_id: AgentId, tenant_id: TenantId) -> Result<MarketplaceAgentDto> {
+        let agent = self.find_listed_agent(agent_id, tenant_id).await?;
+
+        let creator_name = self
+            .user_repo
+            .find_by_id(agent.creator_id)
+            .await?
+            .map(|creator| creator.nickname.unwrap_or(creator.username.0))
+            .unwrap_or_default();
+
+        Ok(Self::to_marketplace_dto(&agent, creator_name))
+    }
+
+    async fn purchase_agent(
+        &self,
+        agent_id: AgentId,
+        tenant_id: TenantId,
+        user_id: UserId,
+        request: PurchaseAgentRequest,
+    ) -> Result<PurchaseAgentResponse> {
+        let agent = self.find_listed_agent(agent_id, tenant_id).await?;
+        let amount = agent.price.unwrap_or(Decimal::ZERO);
+
+        let intent = self
+            .payment_gateway
+            .create_payment_intent(amount, DEFAULT_CURRENCY, agent_id, user_id, request.payment_method)
+            .await?;
+
+        if intent.status != PaymentIntentStatus::Succeeded {
+            return Err(PlatformError::ValidationError(format!(
+                "Payment {} was not completed (status: {})",
+                intent.id,
+                intent.status.as_str()
+            )));
+        }
+
+        let employed_agent = self.agent_service.employ_agent(agent_id, user_id).await?;
+
+        // The purchase has already gone through, a missing audit entry must not undo it
+        if let Err(e) = self
+            .audit_service
+            .log_event(
+                tenant_id.0,
+                Some(user_id.0),
+                AuditAction::Custom(PURCHASE_AUDIT_ACTION.to_string()),
+                ResourceType::Custom("agent".to_string()),
+                Some(agent_id.0),
+                Some(json!({
+                    "payment_intent_id": intent.id,
+                    "amount": intent.amount,
+                    "currency": intent.currency,
+                    "employed_agent_id": employed_agent.id,
+                })),
+                None,
+            )
+            .await
+        {
+            tracing::warn!("Failed to audit marketplace purchase of agent {}: {}", agent_id.0, e);
+        }
+
+        Ok(PurchaseAgentResponse {
+            payment_intent: PaymentIntentDto {
+                id: intent.id,
+                amount: intent.amount,
+                currency: intent.currency,
+                status: intent.status.as_str().to_string(),
+            },
+            agent: employed_agent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::AgentApplicationServiceImpl;
+    use crate::domain::entities::{AuditContext, AuditLog};
+    use crate::domain::repositories::{
+        AuditLogFilter, AuditStatistics, MockAgentAllocationRepository, MockAgentRepository,
+        MockFlowRepository, MockInterviewRecordRepository, MockMCPToolRepository,
+        MockUserRepository, MockVectorConfigRepository,
+    };
+    use crate::domain::services::AuditService;
+    use chrono::{DateTime, Utc};
+    use serde_json::Value;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingAuditService {
+        actions: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl AuditService for RecordingAuditService {
+        async fn log_event(
+            &self,
+            _tenant_id: Uuid,
+            _user_id: Option<Uuid>,
+            action: AuditAction,
+            _resource_type: ResourceType,
+            _resource_id: Option<Uuid>,
+            _details: Option<Value>,
+            _context: Option<AuditContext>,
+        ) -> Result<Uuid> {
+            self.actions.lock().unwrap().push(action.as_str().to_string());
+            Ok(Uuid::new_v4())
+        }
+
+        async fn query_logs(&self, _filter: &AuditLogFilter) -> Result<Vec<AuditLog>> {
+            Ok(Vec::new())
+        }
+
+        async fn count_logs(&self, _filter: &AuditLogFilter) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn get_statistics(
+            &self,
+            _tenant_id: Uuid,
+            _start_date: Option<DateTime<Utc>>,
+            _end_date: Option<DateTime<Utc>>,
+        ) -> Result<AuditStatistics> {
+            unimplemented!()
+        }
+    }
+
+    fn published_agent(tenant_id: TenantId) -> Agent {
+        let mut agent = Agent::new(
+            tenant_id,
+            "Analyst".to_string(),
+            "Secret instructions".to_string(),
+            UserId::new(),
+        )
+        .unwrap();
+        agent.publish().unwrap();
+        agent.price = Some(Decimal::new(999, 2));
+        agent
+    }
+
+    fn service(
+        agent_repo: MockAgentRepository,
+        audit: Arc<RecordingAuditService>,
+    ) -> MarketplaceApplicationServiceImpl {
+        let agent_repo = Arc::new(agent_repo);
+        let mut user_repo = MockUserRepository::new();
+        user_repo.expect_find_by_id().returning(|_| Ok(None));
+        let user_repo = Arc::new(user_repo);
+
+        let agent_service = Arc::new(AgentApplicationServiceImpl::new(
+            agent_repo.clone(),
+            Arc::new(MockAgentAllocationRepository::new()),
+            Arc::new(MockVectorConfigRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockFlowRepository::new()),
+            user_repo.clone(),
+            Arc::new(MockInterviewRecordRepository::new()),
+        ));
+
+        MarketplaceApplicationServiceImpl::new(
+            agent_repo,
+            user_repo,
+            agent_service,
+            Arc::new(AuditApplicationService::new(audit)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_unpublished_agent_is_not_listed() {
+        let tenant_id = TenantId::new();
+        let mut agent = published_agent(tenant_id);
+        agent.unpublish().unwrap();
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(agent.clone())));
+
+        let result = service(agent_repo, Arc::default()).get_agent(agent_id, tenant_id).await;
+        assert!(matches!(result, Err(PlatformError::AgentNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_agent_from_other_tenant_is_not_listed() {
+        let agent = published_agent(TenantId::new());
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(agent.clone())));
+
+        let result = service(agent_repo, Arc::default())
+            .get_agent(agent_id, TenantId::new())
+            .await;
+        assert!(matches!(result, Err(PlatformError::AgentNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_requires_query() {
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo.expect_search_published_by_keyword().never();
+
+        let result = service(agent_repo, Arc::default())
+            .search_agents(MarketplaceSearchQuery {
+                q: Some("  ".to_string()),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_public_view_hides_system_prompt() {
+        let tenant_id = TenantId::new();
+        let agent = published_agent(tenant_id);
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(agent.clone())));
+
+        let dto = service(agent_repo, Arc::default())
+            .get_agent(agent_id, tenant_id)
+            .await
+            .unwrap();
+
+        let json = serde_json::to_string(&dto).unwrap();
+        assert!(!json.contains("Secret instructions"));
+        assert_eq!(dto.price, Some(Decimal::new(999, 2)));
+    }
+
+    #[tokio::test]
+    async fn test_purchase_employs_agent_and_audits() {
+        let tenant_id = TenantId::new();
+        let buyer_id = UserId::new();
+        let agent = published_agent(tenant_id);
+        let agent_id = agent.id;
+
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(agent.clone())));
+        agent_repo
+            .expect_save()
+            .times(1)
+            .withf(move |saved| saved.employer_id == Some(buyer_id))
+            .returning(|_| Ok(()));
+
+        let audit = Arc::new(RecordingAuditService::default());
+        let response = service(agent_repo, audit.clone())
+            .purchase_agent(agent_id, tenant_id, buyer_id, PurchaseAgentRequest::default())
+            .await
+            .unwrap();
+
+        assert_eq!(response.payment_intent.status, "succeeded");
+        assert_eq!(response.payment_intent.amount, Decimal::new(999, 2));
+        assert_eq!(response.agent.source_agent_id, Some(agent_id.0));
+        assert_eq!(*audit.actions.lock().unwrap(), vec![PURCHASE_AUDIT_ACTION.to_string()]);
+    }
+}
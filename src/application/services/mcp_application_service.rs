@@ -4,7 +4,7 @@ use uuid::Uuid;
 
 use crate::{
     domain::{
-        entities::{MCPTool, MCPToolVersion},
+        entities::{MCPTool, MCPToolVersion, QuotaResource},
         repositories::{MCPToolRepository, MCPToolVersionRepository},
         services::mcp_tool_service::{
             MCPToolDomainService, ToolCallContext, 
@@ -21,6 +21,8 @@ use crate::{
         TestMCPToolRequest, TestMCPToolResponse, MCPToolVersionResponse,
         MCPToolStatsResponse,
     },
+    application::services::mcp_server_application_service::MCPServerApplicationService,
+    application::services::tenant_application_service::TenantApplicationService,
     error::{PlatformError, Result},
     infrastructure::mcp::{
         MCPProxyService,
@@ -109,7 +111,7 @@ pub trait MCPApplicationService: Send + Sync {
         user_id: UserId,
     ) -> Result<Vec<MCPToolVersionResponse>>;
 
-    /// 回退到指定版本
+    /// 回退到指定版本，返回回退后的工具
     async fn rollback_tool_version(
         &self,
         tool_id: MCPToolId,
@@ -117,6 +119,14 @@ pub trait MCPApplicationService: Send + Sync {
         user_id: UserId,
     ) -> Result<MCPToolResponse>;
 
+    /// 回退到指定版本：以该版本的配置创建一个新版本
+    async fn rollback_to_version(
+        &self,
+        tool_id: MCPToolId,
+        version: i32,
+        user_id: UserId,
+    ) -> Result<()>;
+
     /// 获取工具统计信息
     async fn get_tool_stats(
         &self,
@@ -155,6 +165,10 @@ pub struct MCPApplicationServiceImpl {
     proxy_service: Arc<dyn MCPProxyService>,
     mcp_server_handler: Arc<MCPServerHandler>,
     template_engine: Arc<ResponseTemplateEngine>,
+    mcp_server_service: Option<Arc<dyn MCPServerApplicationService>>,
+    quota_service: Option<Arc<dyn TenantApplicationService>>,
+    /// 每个工具保留的版本数，0表示全部保留
+    version_retention: u64,
 }
 
 impl MCPApplicationServiceImpl {
@@ -177,9 +191,41 @@ impl MCPApplicationServiceImpl {
             proxy_service,
             mcp_server_handler,
             template_engine,
+            mcp_server_service: None,
+            quota_service: None,
+            version_retention: 0,
         }
     }
 
+    /// 设置MCP Server服务，工具变更时同步其实时注册表
+    pub fn with_mcp_server_service(
+        mut self,
+        mcp_server_service: Arc<dyn MCPServerApplicationService>,
+    ) -> Self {
+        self.mcp_server_service = Some(mcp_server_service);
+        self
+    }
+
+    /// 设置配额服务，创建工具时检查租户配额
+    pub fn with_quota_service(mut self, quota_service: Arc<dyn TenantApplicationService>) -> Self {
+        self.quota_service = Some(quota_service);
+        self
+    }
+
+    /// 设置每个工具保留的版本数，配置变更后删除更早的版本
+    pub fn with_version_retention(mut self, version_retention: u64) -> Self {
+        self.version_retention = version_retention;
+        self
+    }
+
+    /// 同步工具到MCP Server实时注册表
+    async fn sync_server_registry(&self, tool: &MCPTool) -> Result<()> {
+        if let Some(ref server_service) = self.mcp_server_service {
+            server_service.register_tool(tool.clone(), tool.tenant_id).await?;
+        }
+        Ok(())
+    }
+
     /// 验证用户对工具的访问权限
     async fn validate_tool_access(
         &self,
@@ -222,6 +268,14 @@ impl MCPApplicationServiceImpl {
         );
 
         self.version_repository.save(&version).await?;
+
+        let pruned = self.version_repository
+            .prune_by_tool_id(tool.id, self.version_retention)
+            .await?;
+        if pruned > 0 {
+            tracing::debug!(tool_id = %tool.id.0, pruned, "Pruned old MCP tool versions");
+        }
+
         Ok(version)
     }
 
@@ -250,6 +304,10 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
         tenant_id: TenantId,
         user_id: UserId,
     ) -> Result<MCPToolResponse> {
+        if let Some(ref quota_service) = self.quota_service {
+            quota_service.ensure_quota(tenant_id, QuotaResource::McpTools, 1).await?;
+        }
+
         // 验证配置（包括路径参数一致性和header命名规范）
         let validation_result = self.domain_service
             .validate_tool_config(&request.config)
@@ -300,6 +358,7 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
 
         // 注册到代理服务
         self.proxy_service.register_tool(tool.clone()).await?;
+        self.sync_server_registry(&tool).await?;
 
         Ok(self.tool_to_response(&tool))
     }
@@ -339,6 +398,7 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
             tool.update_description(Some(description))?;
         }
 
+        let config_changed = request.config.is_some();
         if let Some(config) = request.config {
             // 验证新配置（包括路径参数一致性和header命名规范）
             let validation_result = self.domain_service
@@ -365,17 +425,17 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
 
             // 清除模板缓存（配置更新时）
             self.template_engine.clear_cache(&tool.id.to_string());
-
-            // 注掉: 在tool_repository中处理
-            // // 创建新版本
-            // self.create_tool_version(&tool, request.change_log, user_id).await?;
         }
 
-        // 保存工具
-        self.tool_repository.update(&tool).await?;
+        // 保存工具，配置变更时记录新版本
+        self.tool_repository.update_without_new_version(&tool).await?;
+        if config_changed {
+            self.create_tool_version(&tool, request.change_log, user_id).await?;
+        }
 
         // 更新代理服务
         self.proxy_service.register_tool(tool.clone()).await?;
+        self.sync_server_registry(&tool).await?;
 
         Ok(self.tool_to_response(&tool))
     }
@@ -399,6 +459,9 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
 
         // 从代理服务注销
         self.proxy_service.unregister_tool(tool_id).await?;
+        if let Some(ref server_service) = self.mcp_server_service {
+            server_service.unregister_tool(tool_id, tool.tenant_id).await?;
+        }
 
         // 删除工具（级联删除版本）
         self.tool_repository.delete(tool_id).await?;
@@ -467,7 +530,7 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
 
         // 验证参数
         self.domain_service
-            .validate_call_parameters(&tool, &request.parameters)
+            .validate_call_parameters(&tool, &request.parameters, &context)
             .await?;
 
         // 调用工具
@@ -538,6 +601,7 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
 
         // 更新代理服务
         self.proxy_service.register_tool(tool.clone()).await?;
+        self.sync_server_registry(&tool).await?;
 
         Ok(self.tool_to_response(&tool))
     }
@@ -564,6 +628,7 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
 
         // 更新代理服务
         self.proxy_service.register_tool(tool.clone()).await?;
+        self.sync_server_registry(&tool).await?;
 
         Ok(self.tool_to_response(&tool))
     }
@@ -609,6 +674,22 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
         target_version: i32,
         user_id: UserId,
     ) -> Result<MCPToolResponse> {
+        self.rollback_to_version(tool_id, target_version, user_id).await?;
+
+        let tool = self.tool_repository
+            .find_by_id(tool_id)
+            .await?
+            .ok_or_else(|| PlatformError::NotFound("Tool not found".to_string()))?;
+
+        Ok(self.tool_to_response(&tool))
+    }
+
+    async fn rollback_to_version(
+        &self,
+        tool_id: MCPToolId,
+        version: i32,
+        user_id: UserId,
+    ) -> Result<()> {
         // 获取工具
         let mut tool = self.tool_repository
             .find_by_id(tool_id)
@@ -618,26 +699,25 @@ impl MCPApplicationService for MCPApplicationServiceImpl {
         // 验证访问权限
         self.validate_tool_access(&tool, user_id).await?;
 
-        // 获取目标版本
-        let target_version_entity = self.version_repository
-            .find_by_tool_and_version(tool_id, target_version)
+        // 获取目标版本（可能已被清理）
+        let target_version = self.version_repository
+            .find_by_tool_and_version(tool_id, version)
             .await?
-            .ok_or_else(|| PlatformError::NotFound("Target version not found".to_string()))?;
+            .ok_or_else(|| PlatformError::NotFound(format!("Version {} not found", version)))?;
 
-        // 回退配置
-        tool.update_config(target_version_entity.config);
+        // 以目标版本的配置创建新版本
+        tool.update_config(target_version.config);
+        self.template_engine.clear_cache(&tool.id.to_string());
 
-        // 保存工具
-        self.tool_repository.save(&tool).await?;
-
-        // 创建回退版本记录
-        let change_log = format!("Rolled back to version {}", target_version);
+        self.tool_repository.update_without_new_version(&tool).await?;
+        let change_log = format!("Rolled back to version {}", version);
         self.create_tool_version(&tool, Some(change_log), user_id).await?;
 
         // 更新代理服务
         self.proxy_service.register_tool(tool.clone()).await?;
+        self.sync_server_registry(&tool).await?;
 
-        Ok(self.tool_to_response(&tool))
+        Ok(())
     }
 
     async fn get_tool_stats(
@@ -41,6 +41,7 @@ mod tests {
             async fn find_by_created_by(&self, created_by: UserId) -> Result<Vec<MCPTool>, PlatformError>;
             async fn save(&self, tool: &MCPTool) -> Result<(), PlatformError>;
             async fn update(&self, tool: &MCPTool) -> Result<(), PlatformError>;
+            async fn update_without_new_version(&self, tool: &MCPTool) -> Result<(), PlatformError>;
             async fn delete(&self, id: MCPToolId) -> Result<(), PlatformError>;
             async fn exists_by_tenant_and_name(&self, tenant_id: TenantId, name: &str, exclude_id: Option<MCPToolId>) -> Result<bool, PlatformError>;
             async fn count_by_tenant(&self, tenant_id: TenantId) -> Result<u64, PlatformError>;
@@ -70,6 +71,7 @@ mod tests {
             async fn exists_by_tool_and_version(&self, tool_id: MCPToolId, version: i32) -> Result<bool, PlatformError>;
             async fn get_next_version_number(&self, tool_id: MCPToolId) -> Result<i32, PlatformError>;
             async fn count_by_tool_id(&self, tool_id: MCPToolId) -> Result<u64, PlatformError>;
+            async fn prune_by_tool_id(&self, tool_id: MCPToolId, keep: u64) -> Result<u64, PlatformError>;
             async fn compare_versions(&self, tool_id: MCPToolId, from_version: i32, to_version: i32) -> Result<crate::domain::entities::VersionDiff, PlatformError>;
             async fn get_version_history(&self, tool_id: MCPToolId) -> Result<Vec<crate::domain::entities::MCPToolVersion>, PlatformError>;
             async fn rollback_to_version(&self, tool_id: MCPToolId, target_version: i32, created_by: UserId, change_log: Option<String>) -> Result<crate::domain::entities::MCPToolVersion, PlatformError>;
@@ -84,7 +86,7 @@ mod tests {
             async fn validate_tool_config(&self, config: &ToolConfig) -> Result<ConfigValidationResult, PlatformError>;
             async fn check_tool_permission(&self, tool: &MCPTool, context: &ToolCallContext) -> Result<PermissionCheckResult, PlatformError>;
             async fn check_call_permission(&self, tool: &MCPTool, context: &ToolCallContext, parameters: &serde_json::Value) -> Result<PermissionCheckResult, PlatformError>;
-            async fn validate_call_parameters(&self, tool: &MCPTool, parameters: &serde_json::Value) -> Result<(), PlatformError>;
+            async fn validate_call_parameters(&self, tool: &MCPTool, parameters: &serde_json::Value, context: &ToolCallContext) -> Result<(), PlatformError>;
             async fn test_tool_connection(&self, config: &ToolConfig) -> Result<ToolCallResult, PlatformError>;
             fn create_call_context(&self, tenant_id: TenantId, user_id: UserId, request_id: String) -> ToolCallContext;
             fn can_execute_tool(&self, tool: &MCPTool) -> bool;
@@ -101,6 +103,7 @@ mod tests {
             async fn unregister_tool(&self, tool_id: MCPToolId) -> Result<(), PlatformError>;
             async fn get_tenant_tools(&self, tenant_id: TenantId) -> Result<Vec<MCPTool>, PlatformError>;
             async fn call_tool(&self, tool_id: MCPToolId, parameters: serde_json::Value, context: ToolCallContext) -> Result<ToolCallResult, PlatformError>;
+            async fn forward_tool_call(&self, tool: &MCPTool, parameters: serde_json::Value, context: ToolCallContext) -> Result<ToolCallResult, PlatformError>;
             async fn handle_mcp_request(&self, request: crate::infrastructure::mcp::protocol_handler::MCPRequest, tenant_id: TenantId) -> Result<crate::infrastructure::mcp::protocol_handler::MCPResponse, PlatformError>;
             async fn test_tool_connection(&self, tool_id: MCPToolId) -> Result<ToolCallResult, PlatformError>;
             async fn get_tool_stats(&self, tenant_id: TenantId) -> Result<MCPToolStats, PlatformError>;
@@ -429,7 +432,7 @@ mod tests {
         domain_service
             .expect_validate_call_parameters()
             .times(1)
-            .returning(|_, _| Ok(()));
+            .returning(|_, _, _| Ok(()));
 
         let call_result = ToolCallResult::success(serde_json::json!({"result": "success"}), 100);
 
@@ -720,4 +723,95 @@ mod tests {
         let result = service.delete_tool(tool_id, user_id).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_rollback_to_version_creates_new_version() {
+        let tenant_id = TenantId::new();
+        let creator_id = UserId::new();
+        let editor_id = UserId::new();
+        let mut tool = create_test_tool(tenant_id, creator_id);
+        tool.current_version = 3;
+        let tool_id = tool.id;
+
+        let old_config = ToolConfig::HTTP(HTTPToolConfig::new(
+            "https://api.example.com/v1".to_string(),
+            HttpMethod::POST,
+        ));
+        let target = crate::domain::entities::MCPToolVersion::new(
+            tool_id,
+            1,
+            old_config.clone(),
+            None,
+            creator_id,
+        );
+
+        let mut tool_repo = MockMCPToolRepositoryImpl::new();
+        let mut version_repo = MockMCPToolVersionRepositoryImpl::new();
+        let mut domain_service = MockMCPToolDomainServiceImpl::new();
+        let mut proxy_service = MockMCPProxyServiceImpl::new();
+
+        tool_repo
+            .expect_find_by_id()
+            .with(eq(tool_id))
+            .times(1)
+            .returning(move |_| Ok(Some(tool.clone())));
+
+        domain_service
+            .expect_create_call_context()
+            .times(1)
+            .returning(move |tenant_id, user_id, request_id| {
+                ToolCallContext::new(tenant_id, user_id, request_id)
+            });
+
+        domain_service
+            .expect_check_tool_permission()
+            .times(1)
+            .returning(|_, _| Ok(PermissionCheckResult::allowed()));
+
+        version_repo
+            .expect_find_by_tool_and_version()
+            .with(eq(tool_id), eq(1))
+            .times(1)
+            .returning(move |_, _| Ok(Some(target.clone())));
+
+        let expected_config = old_config.clone();
+        tool_repo
+            .expect_update_without_new_version()
+            .withf(move |tool| tool.current_version == 4 && tool.config == expected_config)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        version_repo
+            .expect_save()
+            .withf(move |version| {
+                version.version == 4
+                    && version.config == old_config
+                    && version.created_by == editor_id
+                    && version.change_log.as_deref() == Some("Rolled back to version 1")
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        version_repo
+            .expect_prune_by_tool_id()
+            .with(eq(tool_id), eq(2))
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        proxy_service
+            .expect_register_tool()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = MCPApplicationServiceImpl::new(
+            Arc::new(tool_repo),
+            Arc::new(version_repo),
+            Arc::new(domain_service),
+            Arc::new(proxy_service),
+        )
+        .with_version_retention(2);
+
+        let result = service.rollback_to_version(tool_id, 1, editor_id).await;
+        assert!(result.is_ok());
+    }
 }
@@ -1,13 +1,16 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 
 use crate::{
     application::dto::{APIKeyAuthContext, PermissionScopeDTO},
     domain::{
         entities::MCPTool,
         repositories::MCPToolRepository,
-        services::mcp_tool_service::ToolCallContext,
-        value_objects::ids::{TenantId, UserId},
+        services::mcp_tool_service::{validate_tool_call_parameters, ToolCallContext},
+        value_objects::ids::{MCPToolId, TenantId, UserId},
     },
     error::{PlatformError, Result},
     infrastructure::mcp::{
@@ -44,12 +47,54 @@ pub trait MCPServerApplicationService: Send + Sync {
         auth_context: &APIKeyAuthContext,
         tool_name: String,
     ) -> Result<MCPToolDescriptor>;
+
+    /// Register (or refresh) a tool in the live registry
+    /// Makes the tool callable through the MCP server without a restart
+    async fn register_tool(&self, tool: MCPTool, tenant_id: TenantId) -> Result<()>;
+
+    /// Remove a tool from the live registry
+    async fn unregister_tool(&self, tool_id: MCPToolId, tenant_id: TenantId) -> Result<()>;
 }
 
+/// How long a tenant's tools stay cached before they are reloaded from the
+/// repository, which also picks up changes made through other instances
+pub const TOOL_REGISTRY_TTL: Duration = Duration::from_secs(300);
+
+/// Tenants whose tools are cached at once; the least recently loaded tenant
+/// is evicted first
+pub const TOOL_REGISTRY_MAX_TENANTS: usize = 1024;
+
+/// A tenant's registered tools and when they were loaded
+#[derive(Debug, Clone)]
+pub struct TenantTools {
+    pub tools: HashMap<MCPToolId, MCPTool>,
+    loaded_at: Instant,
+}
+
+impl TenantTools {
+    fn new(tools: Vec<MCPTool>) -> Self {
+        Self {
+            tools: tools.into_iter().map(|tool| (tool.id, tool)).collect(),
+            loaded_at: Instant::now(),
+        }
+    }
+}
+
+/// Live registry of MCP tools served by the MCP server, keyed by tenant.
+///
+/// A single lock rather than a sharded map: tool calls only take the read
+/// lock, the write lock is held while a tenant is (re)loaded or a tool is
+/// registered, and evicting the least recently loaded tenant needs a
+/// consistent view of all of them.
+pub type MCPToolRegistry = Arc<RwLock<HashMap<TenantId, TenantTools>>>;
+
 /// Implementation of MCP Server Application Service
 pub struct MCPServerApplicationServiceImpl {
     mcp_tool_repository: Arc<dyn MCPToolRepository>,
     mcp_proxy_service: Arc<dyn MCPProxyService>,
+    tool_registry: MCPToolRegistry,
+    registry_ttl: Duration,
+    registry_max_tenants: usize,
 }
 
 impl MCPServerApplicationServiceImpl {
@@ -61,9 +106,74 @@ impl MCPServerApplicationServiceImpl {
         Self {
             mcp_tool_repository,
             mcp_proxy_service,
+            tool_registry: Arc::new(RwLock::new(HashMap::new())),
+            registry_ttl: TOOL_REGISTRY_TTL,
+            registry_max_tenants: TOOL_REGISTRY_MAX_TENANTS,
         }
     }
 
+    /// Set how long tenants stay cached and how many are kept
+    pub fn with_registry_limits(mut self, ttl: Duration, max_tenants: usize) -> Self {
+        self.registry_ttl = ttl;
+        self.registry_max_tenants = max_tenants.max(1);
+        self
+    }
+
+    /// Share an existing tool registry with this service
+    pub fn with_tool_registry(mut self, tool_registry: MCPToolRegistry) -> Self {
+        self.tool_registry = tool_registry;
+        self
+    }
+
+    /// Get the live tool registry
+    pub fn tool_registry(&self) -> MCPToolRegistry {
+        self.tool_registry.clone()
+    }
+
+    /// Load a tenant's active tools from the repository on first access,
+    /// and again once the cached copy is older than the TTL
+    async fn ensure_tenant_loaded(&self, tenant_id: TenantId) -> Result<()> {
+        let fresh = |tenant: &TenantTools| tenant.loaded_at.elapsed() < self.registry_ttl;
+        if self.tool_registry.read().await.get(&tenant_id).is_some_and(fresh) {
+            return Ok(());
+        }
+
+        let tools = self.mcp_tool_repository.find_active_by_tenant(tenant_id).await?;
+
+        let mut registry = self.tool_registry.write().await;
+        // Another request may have loaded the tenant while we were querying
+        if registry.get(&tenant_id).is_some_and(fresh) {
+            return Ok(());
+        }
+
+        if !registry.contains_key(&tenant_id) && registry.len() >= self.registry_max_tenants {
+            registry.retain(|_, tenant| fresh(tenant));
+            if registry.len() >= self.registry_max_tenants {
+                let oldest = registry
+                    .iter()
+                    .min_by_key(|(_, tenant)| tenant.loaded_at)
+                    .map(|(tenant_id, _)| *tenant_id);
+                if let Some(oldest) = oldest {
+                    registry.remove(&oldest);
+                }
+            }
+        }
+        registry.insert(tenant_id, TenantTools::new(tools));
+
+        Ok(())
+    }
+
+    /// Snapshot of the tenant's registered tools
+    async fn registered_tools(&self, tenant_id: TenantId) -> Result<Vec<MCPTool>> {
+        self.ensure_tenant_loaded(tenant_id).await?;
+
+        let registry = self.tool_registry.read().await;
+        Ok(registry
+            .get(&tenant_id)
+            .map(|tenant| tenant.tools.values().cloned().collect())
+            .unwrap_or_default())
+    }
+
     /// Check if a tool is accessible based on the permission scope
     fn is_tool_accessible(&self, tool: &MCPTool, permission_scope: &PermissionScopeDTO) -> bool {
         // If the permission scope is empty for MCP tools, deny access
@@ -94,11 +204,12 @@ impl MCPServerApplicationServiceImpl {
         tool_name: &str,
         permission_scope: &PermissionScopeDTO,
     ) -> Result<MCPTool> {
-        // Find the tool by tenant and name
+        // Find the tool by tenant and name in the live registry
         let tool = self
-            .mcp_tool_repository
-            .find_by_tenant_and_name(tenant_id, tool_name)
+            .registered_tools(tenant_id)
             .await?
+            .into_iter()
+            .find(|tool| tool.name == tool_name)
             .ok_or_else(|| PlatformError::NotFound(format!("Tool '{}' not found", tool_name)))?;
 
         // Check if the tool is accessible
@@ -129,21 +240,17 @@ impl MCPServerApplicationService for MCPServerApplicationServiceImpl {
     ) -> Result<MCPToolListResponse> {
         let tenant_id = TenantId(auth_context.tenant_id);
 
-        // Get all active tools for the tenant using find_by_options
-        use crate::domain::repositories::mcp_tool_repository::MCPToolQueryOptions;
-        
-        let query_options = MCPToolQueryOptions::new()
-            .with_tenant_id(tenant_id)
-            .with_status("active".to_string());
-
-        let query_result = self
-            .mcp_tool_repository
-            .find_by_options(query_options)
-            .await?;
+        // Get all active tools for the tenant from the live registry
+        let active_tools: Vec<MCPTool> = self
+            .registered_tools(tenant_id)
+            .await?
+            .into_iter()
+            .filter(|tool| tool.can_execute())
+            .collect();
 
         // Filter tools based on permission scope
         let accessible_tools =
-            self.filter_tools_by_permission(query_result.tools, &auth_context.permission_scope);
+            self.filter_tools_by_permission(active_tools, &auth_context.permission_scope);
 
         // Convert to MCP format
         let tool_descriptors: Vec<MCPToolDescriptor> = accessible_tools
@@ -177,6 +284,11 @@ impl MCPServerApplicationService for MCPServerApplicationServiceImpl {
             format!("mcp_api_key_{}", auth_context.api_key_id),
         );
 
+        // Validate the arguments against the tool's parameter schema
+        if let Err(e) = validate_tool_call_parameters(&tool, &arguments, &context) {
+            return Ok(MCPToolCallResponse::error(e.to_string()));
+        }
+
         // Call the tool via proxy service
         let result = self
             .mcp_proxy_service
@@ -211,6 +323,34 @@ impl MCPServerApplicationService for MCPServerApplicationServiceImpl {
         // Convert to MCP format
         Ok(tool_to_mcp_format(&tool))
     }
+
+    async fn register_tool(&self, tool: MCPTool, tenant_id: TenantId) -> Result<()> {
+        if tool.tenant_id != tenant_id {
+            return Err(PlatformError::Forbidden(format!(
+                "Tool '{}' does not belong to this tenant",
+                tool.name
+            )));
+        }
+
+        self.ensure_tenant_loaded(tenant_id).await?;
+
+        // A tenant evicted meanwhile is reloaded, tool included, on next use
+        let mut registry = self.tool_registry.write().await;
+        if let Some(tenant) = registry.get_mut(&tenant_id) {
+            tenant.tools.insert(tool.id, tool);
+        }
+
+        Ok(())
+    }
+
+    async fn unregister_tool(&self, tool_id: MCPToolId, tenant_id: TenantId) -> Result<()> {
+        let mut registry = self.tool_registry.write().await;
+        if let Some(tenant) = registry.get_mut(&tenant_id) {
+            tenant.tools.remove(&tool_id);
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
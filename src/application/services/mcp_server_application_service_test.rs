@@ -191,6 +191,18 @@ mod integration_tests {
             ))
         }
 
+        async fn forward_tool_call(
+            &self,
+            _tool: &MCPTool,
+            _parameters: Value,
+            _context: ToolCallContext,
+        ) -> Result<ToolCallResult> {
+            Ok(ToolCallResult::success(
+                serde_json::json!({"result": "success"}),
+                100,
+            ))
+        }
+
         async fn handle_mcp_request(
             &self,
             _request: MCPRequest,
@@ -268,6 +280,7 @@ mod integration_tests {
                 mcp_tool_ids: vec![tool_id_1, tool_id_3], // Only tool-1 and tool-3
                 vector_store_ids: vec![],
             },
+            rate_limit_rpm: None,
         };
 
         let result = service.list_tools(&auth_context).await.unwrap();
@@ -299,6 +312,7 @@ mod integration_tests {
                 mcp_tool_ids: vec![], // Empty permissions
                 vector_store_ids: vec![],
             },
+            rate_limit_rpm: None,
         };
 
         let result = service.list_tools(&auth_context).await.unwrap();
@@ -327,6 +341,7 @@ mod integration_tests {
                 mcp_tool_ids: vec![tool_id],
                 vector_store_ids: vec![],
             },
+            rate_limit_rpm: None,
         };
 
         let result = service
@@ -359,6 +374,7 @@ mod integration_tests {
                 mcp_tool_ids: vec![Uuid::new_v4()], // Different tool ID
                 vector_store_ids: vec![],
             },
+            rate_limit_rpm: None,
         };
 
         let result = service
@@ -393,6 +409,7 @@ mod integration_tests {
                 mcp_tool_ids: vec![tool_id],
                 vector_store_ids: vec![],
             },
+            rate_limit_rpm: None,
         };
 
         let result = service
@@ -403,4 +420,116 @@ mod integration_tests {
         assert_eq!(result.name, "test-tool");
         assert_eq!(result.description, Some("Test tool".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_register_tool_dynamically_is_callable() {
+        let tenant_id = TenantId::new();
+        let tool_id = Uuid::new_v4();
+
+        let repository = Arc::new(MockMCPToolRepository::new(vec![]));
+        let proxy_service = Arc::new(MockMCPProxyService);
+        let service = MCPServerApplicationServiceImpl::new(repository, proxy_service);
+
+        let auth_context = APIKeyAuthContext {
+            api_key_id: Uuid::new_v4(),
+            tenant_id: tenant_id.0,
+            user_id: Uuid::new_v4(),
+            permission_scope: PermissionScopeDTO {
+                agent_ids: vec![],
+                flow_ids: vec![],
+                mcp_tool_ids: vec![tool_id],
+                vector_store_ids: vec![],
+            },
+            rate_limit_rpm: None,
+        };
+
+        // Not known before registration
+        let result = service
+            .call_tool(&auth_context, "runtime-tool".to_string(), serde_json::json!({}))
+            .await;
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+
+        let tool = create_test_tool(tenant_id, tool_id, "runtime-tool", true);
+        service.register_tool(tool, tenant_id).await.unwrap();
+
+        let result = service
+            .call_tool(&auth_context, "runtime-tool".to_string(), serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(result.is_error, None);
+
+        let tools = service.list_tools(&auth_context).await.unwrap();
+        assert_eq!(tools.tools.len(), 1);
+
+        service
+            .unregister_tool(MCPToolId(tool_id), tenant_id)
+            .await
+            .unwrap();
+
+        let result = service
+            .call_tool(&auth_context, "runtime-tool".to_string(), serde_json::json!({}))
+            .await;
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+    }
+
+    fn create_auth_context(tenant_id: TenantId, tool_ids: Vec<Uuid>) -> APIKeyAuthContext {
+        APIKeyAuthContext {
+            api_key_id: Uuid::new_v4(),
+            tenant_id: tenant_id.0,
+            user_id: Uuid::new_v4(),
+            permission_scope: PermissionScopeDTO {
+                agent_ids: vec![],
+                flow_ids: vec![],
+                mcp_tool_ids: tool_ids,
+                vector_store_ids: vec![],
+            },
+            rate_limit_rpm: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_tenant_is_reloaded_from_repository() {
+        let tenant_id = TenantId::new();
+        let tool_id = Uuid::new_v4();
+        let tool = create_test_tool(tenant_id, tool_id, "stored-tool", true);
+
+        let repository = Arc::new(MockMCPToolRepository::new(vec![tool]));
+        let service =
+            MCPServerApplicationServiceImpl::new(repository, Arc::new(MockMCPProxyService))
+                .with_registry_limits(std::time::Duration::ZERO, TOOL_REGISTRY_MAX_TENANTS);
+        let auth_context = create_auth_context(tenant_id, vec![tool_id]);
+
+        assert_eq!(service.list_tools(&auth_context).await.unwrap().tools.len(), 1);
+
+        // Dropped from the cache only; the repository still has it
+        service
+            .unregister_tool(MCPToolId(tool_id), tenant_id)
+            .await
+            .unwrap();
+
+        assert_eq!(service.list_tools(&auth_context).await.unwrap().tools.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_registry_evicts_oldest_tenant_when_full() {
+        let repository = Arc::new(MockMCPToolRepository::new(vec![]));
+        let service =
+            MCPServerApplicationServiceImpl::new(repository, Arc::new(MockMCPProxyService))
+                .with_registry_limits(TOOL_REGISTRY_TTL, 2);
+
+        let tenants: Vec<TenantId> = (0..3).map(|_| TenantId::new()).collect();
+        for tenant_id in &tenants {
+            service
+                .list_tools(&create_auth_context(*tenant_id, vec![]))
+                .await
+                .unwrap();
+        }
+
+        let registry = service.tool_registry();
+        let registry = registry.read().await;
+        assert_eq!(registry.len(), 2);
+        assert!(!registry.contains_key(&tenants[0]));
+        assert!(registry.contains_key(&tenants[1]));
+        assert!(registry.contains_key(&tenants[2]));
+    }
 }
@@ -1,15 +1,53 @@
 use std::sync::Arc;
-use crate::domain::entities::Message;
-use crate::domain::repositories::{ChatSessionRepository, MessageRepository};
-use crate::domain::services::SessionDomainService;
-use crate::domain::value_objects::{SessionId, TenantId, UserId, MessageId};
+use serde::Serialize;
+use crate::application::services::LLMConfigSelector;
+use crate::domain::entities::{Message, MessageBranch};
+use crate::domain::repositories::{
+    AgentRepository, ChatSessionRepository, MessageBranchRepository, MessageRepository,
+};
+use crate::domain::services::{LLMDomainService, SessionDomainService};
+use crate::domain::value_objects::{
+    chat_message::{MessageContent, MessageMetadata},
+    AgentId, ChatMessage, LLMSelectionStrategy, MessageId, MessageRole, SessionId, TenantId,
+    UserId,
+};
 use crate::error::{Result, PlatformError};
 
+/// Most earlier messages sent to the LLM when regenerating a response
+const EDIT_HISTORY_LIMIT: usize = 50;
+
+/// Outcome of editing a user message
+#[derive(Debug, Clone, Serialize)]
+pub struct EditMessageResult {
+    pub branch: MessageBranch,
+    pub edited_message: Message,
+    pub response: Message,
+}
+
+/// One edit of a message, with the response it produced
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageBranchView {
+    pub branch: MessageBranch,
+    pub edited_message: Option<Message>,
+    pub response: Option<Message>,
+}
+
+/// A message and every edit made of it, oldest first
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageBranches {
+    pub original_message: Message,
+    pub branches: Vec<MessageBranchView>,
+}
+
 /// Application service for message storage and retrieval
 pub struct MessageApplicationService {
     session_repo: Arc<dyn ChatSessionRepository>,
     message_repo: Arc<dyn MessageRepository>,
     domain_service: Arc<SessionDomainService>,
+    branch_repo: Option<Arc<dyn MessageBranchRepository>>,
+    agent_repo: Option<Arc<dyn AgentRepository>>,
+    llm_service: Option<Arc<dyn LLMDomainService>>,
+    llm_config_selector: Option<Arc<LLMConfigSelector>>,
 }
 
 impl MessageApplicationService {
@@ -22,9 +60,82 @@ impl MessageApplicationService {
             session_repo,
             message_repo,
             domain_service,
+            branch_repo: None,
+            agent_repo: None,
+            llm_service: None,
+            llm_config_selector: None,
         }
     }
 
+    /// Set branch repository to enable message editing
+    pub fn with_branch_repository(mut self, branch_repo: Arc<dyn MessageBranchRepository>) -> Self {
+        self.branch_repo = Some(branch_repo);
+        self
+    }
+
+    /// Set agent repository so edits of agent chats use the agent's prompt and LLM
+    pub fn with_agent_repository(mut self, agent_repo: Arc<dyn AgentRepository>) -> Self {
+        self.agent_repo = Some(agent_repo);
+        self
+    }
+
+    /// Set LLM service used to regenerate responses to edited messages
+    pub fn with_llm(
+        mut self,
+        llm_service: Arc<dyn LLMDomainService>,
+        llm_config_selector: Arc<LLMConfigSelector>,
+    ) -> Self {
+        self.llm_service = Some(llm_service);
+        self.llm_config_selector = Some(llm_config_selector);
+        self
+    }
+
+    fn branch_repo(&self) -> Result<&Arc<dyn MessageBranchRepository>> {
+        self.branch_repo.as_ref().ok_or_else(|| {
+            PlatformError::InternalError("Message branch repository not configured".to_string())
+        })
+    }
+
+    /// The first version of a message, which all of its edits branch from
+    async fn root_message_id(&self, message_id: &MessageId) -> Result<MessageId> {
+        Ok(self
+            .branch_repo()?
+            .find_by_edited(message_id)
+            .await?
+            .map(|branch| branch.original_message_id)
+            .unwrap_or(*message_id))
+    }
+
+    /// Agent a chat message was sent to, from its metadata
+    fn message_agent_id(message: &Message) -> Option<AgentId> {
+        message
+            .message
+            .metadata
+            .as_ref()?
+            .custom_data
+            .get("agent_id")?
+            .as_str()
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())
+            .map(AgentId)
+    }
+
+    /// Split the active messages of a session into those before `message_id`
+    /// and the message itself with everything after it
+    fn split_at_message(messages: Vec<Message>, message_id: &MessageId) -> Result<(Vec<Message>, Vec<Message>)> {
+        let mut active: Vec<Message> = messages
+            .into_iter()
+            .filter(|message| !message.is_superseded())
+            .collect();
+
+        let position = active
+            .iter()
+            .position(|message| message.id == *message_id)
+            .ok_or_else(|| PlatformError::NotFound("Message not found in session".to_string()))?;
+
+        let later = active.split_off(position);
+        Ok((active, later))
+    }
+
     /// Get message by ID with access validation
     pub async fn get_message(
         &self,
@@ -235,6 +346,194 @@ impl MessageApplicationService {
             .await
     }
 
+    /// Replace a user message with an edited copy and regenerate the response.
+    /// The original message and everything after it are marked superseded,
+    /// and a branch records the edit so the history can still be browsed.
+    pub async fn edit_message(
+        &self,
+        message_id: &MessageId,
+        new_content: String,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+    ) -> Result<EditMessageResult> {
+        let new_content = new_content.trim().to_string();
+        if new_content.is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Message content cannot be empty".to_string(),
+            ));
+        }
+
+        let llm_service = self.llm_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?;
+        let llm_config_selector = self.llm_config_selector.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?;
+        let branch_repo = self.branch_repo()?;
+
+        let original = self.get_message(message_id, tenant_id, user_id).await?;
+        if original.message.role != MessageRole::User {
+            return Err(PlatformError::ValidationError(
+                "Only user messages can be edited".to_string(),
+            ));
+        }
+        if original.is_superseded() {
+            return Err(PlatformError::ValidationError(
+                "Message has already been replaced by an edit".to_string(),
+            ));
+        }
+
+        let root_id = self.root_message_id(&original.id).await?;
+
+        let mut session = self
+            .session_repo
+            .find_by_id(&original.session_id)
+            .await?
+            .ok_or_else(|| PlatformError::NotFound("Session not found".to_string()))?;
+
+        let messages = self.message_repo.find_by_session(&session.id).await?;
+        let (earlier, later) = Self::split_at_message(messages, &original.id)?;
+
+        // Agent chats are regenerated with the agent's prompt and LLM
+        let agent = match (Self::message_agent_id(&original), &self.agent_repo) {
+            (Some(agent_id), Some(agent_repo)) => agent_repo
+                .find_by_id(&agent_id)
+                .await?
+                .filter(|agent| agent.tenant_id == *tenant_id),
+            _ => None,
+        };
+
+        let llm_config = match &agent {
+            Some(agent) => {
+                llm_config_selector
+                    .select(*tenant_id, agent.preferred_llm_config_id(), &agent.llm_fallback_strategy)
+                    .await?
+            }
+            None => {
+                llm_config_selector
+                    .select(*tenant_id, None, &LLMSelectionStrategy::FirstAvailable)
+                    .await?
+            }
+        };
+
+        let mut llm_messages = Vec::new();
+        if let Some(agent) = &agent {
+            llm_messages.push(ChatMessage::new_system_message(agent.system_prompt.clone()));
+            if let Some(greeting) = &agent.greeting {
+                llm_messages.push(ChatMessage::new_assistant_message(greeting.clone()));
+            }
+        }
+        let skip = earlier.len().saturating_sub(EDIT_HISTORY_LIMIT);
+        llm_messages.extend(earlier.into_iter().skip(skip).map(|message| message.message));
+
+        let edited_chat_message = ChatMessage {
+            role: MessageRole::User,
+            content: MessageContent::Text(new_content),
+            metadata: original.message.metadata.clone(),
+            timestamp: chrono::Utc::now(),
+        };
+        llm_messages.push(edited_chat_message.clone());
+
+        let started_at = std::time::Instant::now();
+        let response = llm_service
+            .chat_completion(&llm_config.model_config, llm_messages, tenant_id.0, None)
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+        let latency = started_at.elapsed();
+        llm_config_selector.record_latency(llm_config.id, latency).await;
+
+        let superseded_ids: Vec<MessageId> = later.iter().map(|message| message.id).collect();
+        self.message_repo
+            .mark_superseded(&superseded_ids, chrono::Utc::now())
+            .await?;
+
+        let response_chat_message = ChatMessage {
+            role: MessageRole::Assistant,
+            content: MessageContent::Text(response.content),
+            metadata: Some(MessageMetadata {
+                model_used: Some(response.model_used),
+                tokens_used: Some(response.usage.total_tokens),
+                response_time_ms: Some(latency.as_millis() as u64),
+                tool_calls: None,
+                custom_data: original
+                    .message
+                    .metadata
+                    .as_ref()
+                    .map(|metadata| metadata.custom_data.clone())
+                    .unwrap_or_default(),
+            }),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let edited_message = self
+            .domain_service
+            .add_message_to_session(&mut session, edited_chat_message)?;
+        self.message_repo.save(&edited_message).await?;
+
+        let response_message = self
+            .domain_service
+            .add_message_to_session(&mut session, response_chat_message)?;
+        self.message_repo.save(&response_message).await?;
+
+        self.session_repo.save(&session).await?;
+
+        let branch = MessageBranch::new(
+            session.id,
+            root_id,
+            edited_message.id,
+            Some(response_message.id),
+            *user_id,
+        );
+        branch_repo.save(&branch).await?;
+
+        Ok(EditMessageResult {
+            branch,
+            edited_message,
+            response: response_message,
+        })
+    }
+
+    /// List the edits of a message. Works from the original or any edit of it.
+    pub async fn list_branches(
+        &self,
+        session_id: &SessionId,
+        message_id: &MessageId,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+    ) -> Result<MessageBranches> {
+        let message = self.get_message(message_id, tenant_id, user_id).await?;
+        if message.session_id != *session_id {
+            return Err(PlatformError::NotFound("Message not found".to_string()));
+        }
+
+        let root_id = self.root_message_id(&message.id).await?;
+        let original_message = if root_id == message.id {
+            message
+        } else {
+            self.message_repo
+                .find_by_id(&root_id)
+                .await?
+                .ok_or_else(|| PlatformError::NotFound("Original message not found".to_string()))?
+        };
+
+        let mut branches = Vec::new();
+        for branch in self.branch_repo()?.find_by_original(&root_id).await? {
+            let edited_message = self.message_repo.find_by_id(&branch.edited_message_id).await?;
+            let response = match &branch.response_message_id {
+                Some(response_id) => self.message_repo.find_by_id(response_id).await?,
+                None => None,
+            };
+            branches.push(MessageBranchView {
+                branch,
+                edited_message,
+                response,
+            });
+        }
+
+        Ok(MessageBranches {
+            original_message,
+            branches,
+        })
+    }
+
     /// Filter messages by role
     pub async fn get_messages_by_role(
         &self,
@@ -263,3 +562,55 @@ impl MessageApplicationService {
         Ok(filtered)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(session_id: SessionId, content: &str) -> Message {
+        Message::new(session_id, ChatMessage::new_user_message(content.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_split_at_message_skips_superseded() {
+        let session_id = SessionId::new();
+        let first = message(session_id, "first");
+        let mut replaced = message(session_id, "replaced");
+        replaced.superseded_at = Some(chrono::Utc::now());
+        let second = message(session_id, "second");
+        let third = message(session_id, "third");
+
+        let (earlier, later) = MessageApplicationService::split_at_message(
+            vec![first.clone(), replaced, second.clone(), third.clone()],
+            &second.id,
+        )
+        .unwrap();
+        assert_eq!(earlier, vec![first]);
+        assert_eq!(later, vec![second, third]);
+
+        let missing = MessageApplicationService::split_at_message(Vec::new(), &MessageId::new());
+        assert!(matches!(missing, Err(PlatformError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_message_agent_id_from_metadata() {
+        let agent_id = uuid::Uuid::new_v4();
+        let mut chat_message = ChatMessage::new_user_message("hi".to_string());
+        chat_message.metadata = Some(MessageMetadata {
+            model_used: None,
+            tokens_used: None,
+            response_time_ms: None,
+            tool_calls: None,
+            custom_data: std::collections::HashMap::from([
+                ("agent_id".to_string(), serde_json::json!(agent_id.to_string())),
+            ]),
+        });
+        let with_agent = Message::new(SessionId::new(), chat_message).unwrap();
+
+        assert_eq!(MessageApplicationService::message_agent_id(&with_agent), Some(AgentId(agent_id)));
+        assert_eq!(
+            MessageApplicationService::message_agent_id(&message(SessionId::new(), "hi")),
+            None
+        );
+    }
+}
@@ -3,6 +3,7 @@ pub mod llm_application_service;
 pub mod integrated_llm_service;
 pub mod llm_service_factory;
 pub mod llm_integration_service;
+pub mod llm_config_selector;
 pub mod vector_application_service;
 pub mod vector_storage_application_service;
 pub mod mcp_application_service;
@@ -12,11 +13,20 @@ pub mod context_management_service;
 pub mod audit_application_service;
 pub mod execution_history_application_service;
 pub mod flow_application_service;
+pub mod flow_import_export_service;
+pub mod batch_flow_executor;
+pub mod async_flow_executor;
 pub mod agent_application_service;
+pub mod agent_import_export_service;
 pub mod file_service;
 pub mod api_key_application_service;
 pub mod mcp_server_application_service;
 pub mod dashboard_application_service;
+pub mod marketplace_application_service;
+pub mod interview_application_service;
+pub mod tenant_application_service;
+pub mod user_application_service;
+pub mod vector_ttl_reaper;
 
 #[cfg(test)]
 pub mod integrated_llm_service_test;
@@ -36,11 +46,15 @@ pub mod mcp_application_service_test;
 #[cfg(test)]
 pub mod mcp_server_application_service_test;
 
+#[cfg(test)]
+pub mod flow_application_service_test;
+
 pub use auth_application_service::*;
 pub use llm_application_service::*;
 pub use integrated_llm_service::*;
 pub use llm_service_factory::*;
 pub use llm_integration_service::*;
+pub use llm_config_selector::*;
 pub use vector_application_service::*;
 pub use vector_storage_application_service::*;
 pub use mcp_application_service::*;
@@ -50,8 +64,17 @@ pub use context_management_service::*;
 pub use audit_application_service::*;
 pub use execution_history_application_service::*;
 pub use flow_application_service::*;
+pub use flow_import_export_service::*;
+pub use batch_flow_executor::*;
+pub use async_flow_executor::AsyncFlowExecutor;
 pub use agent_application_service::*;
+pub use agent_import_export_service::*;
 pub use file_service::*;
 pub use api_key_application_service::*;
 pub use mcp_server_application_service::*;
-pub use dashboard_application_service::*;
\ No newline at end of file
+pub use dashboard_application_service::*;
+pub use marketplace_application_service::*;
+pub use interview_application_service::*;
+pub use tenant_application_service::*;
+pub use user_application_service::*;
+pub use vector_ttl_reaper::*;
\ No newline at end of file
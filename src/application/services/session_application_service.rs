@@ -1,17 +1,93 @@
 use std::sync::Arc;
-use crate::domain::entities::{ChatSession, Message};
-use crate::domain::repositories::{ChatSessionRepository, MessageRepository};
+use crate::application::dto::AuditEvent;
+use crate::application::services::AuditApplicationService;
+use crate::domain::entities::{Agent, ChatSession, Message};
+use crate::domain::events::{DomainEvent, EventStore, SessionDeleted};
+use crate::domain::repositories::{
+    AgentRepository, ChatSessionRepository, FileRepository, LLMConfigRepository, MessageRepository,
+};
+use crate::domain::services::llm_service::LLMDomainService;
 use crate::domain::services::SessionDomainService;
-use crate::domain::value_objects::{SessionId, TenantId, UserId, ChatMessage};
+use crate::domain::value_objects::chat_message::{ContentPart, MessageContent};
+use crate::domain::value_objects::{AgentId, MessageRole, SessionId, TenantId, UserId, ChatMessage};
 use crate::error::{Result, PlatformError};
+use crate::infrastructure::llm::TokenCounter;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use tokio::time::{interval, Duration};
 
+/// Message count above which an unsummarized session is summarized lazily
+pub const DEFAULT_SUMMARY_THRESHOLD: usize = 50;
+
+/// Sessions deleted per query while purging old sessions
+const PURGE_BATCH_SIZE: u64 = 100;
+
+/// Characters per line of exported PDF text, about the width of an A4 page
+/// at the body font size
+const PDF_LINE_CHARS: usize = 95;
+
+const SESSION_SUMMARY_PROMPT: &str = "Summarize the following conversation between a user \
+and an AI assistant. Keep the facts, decisions, names and open questions a reader needs to \
+pick the conversation up again. Reply with the summary only.";
+
+/// File format of an exported chat history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatExportFormat {
+    /// User and assistant turns with their timestamps
+    Markdown,
+    /// Session, agent and every message as a JSON document
+    Json,
+    /// Printable transcript headed by the agent's name and avatar URL
+    Pdf,
+}
+
+impl ChatExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ChatExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ChatExportFormat::Json => "application/json",
+            ChatExportFormat::Pdf => "application/pdf",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ChatExportFormat::Markdown => "md",
+            ChatExportFormat::Json => "json",
+            ChatExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+impl std::str::FromStr for ChatExportFormat {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(ChatExportFormat::Markdown),
+            "json" => Ok(ChatExportFormat::Json),
+            "pdf" => Ok(ChatExportFormat::Pdf),
+            _ => Err(PlatformError::ValidationError(format!(
+                "Unsupported chat export format: {}",
+                s
+            ))),
+        }
+    }
+}
+
 /// Application service for session lifecycle management
 pub struct SessionApplicationService {
     session_repo: Arc<dyn ChatSessionRepository>,
     message_repo: Arc<dyn MessageRepository>,
     domain_service: Arc<SessionDomainService>,
+    llm_service: Option<Arc<dyn LLMDomainService>>,
+    llm_config_repo: Option<Arc<dyn LLMConfigRepository>>,
+    summary_threshold: usize,
+    event_store: Option<Arc<dyn EventStore>>,
+    audit_service: Option<Arc<AuditApplicationService>>,
+    agent_repo: Option<Arc<dyn AgentRepository>>,
+    file_repo: Option<Arc<dyn FileRepository>>,
 }
 
 impl SessionApplicationService {
@@ -24,9 +100,53 @@ impl SessionApplicationService {
             session_repo,
             message_repo,
             domain_service,
+            llm_service: None,
+            llm_config_repo: None,
+            summary_threshold: DEFAULT_SUMMARY_THRESHOLD,
+            event_store: None,
+            audit_service: None,
+            agent_repo: None,
+            file_repo: None,
         }
     }
 
+    pub fn with_llm_service(mut self, llm_service: Arc<dyn LLMDomainService>) -> Self {
+        self.llm_service = Some(llm_service);
+        self
+    }
+
+    pub fn with_llm_config_repo(mut self, llm_config_repo: Arc<dyn LLMConfigRepository>) -> Self {
+        self.llm_config_repo = Some(llm_config_repo);
+        self
+    }
+
+    pub fn with_summary_threshold(mut self, summary_threshold: usize) -> Self {
+        self.summary_threshold = summary_threshold;
+        self
+    }
+
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    pub fn with_audit_service(mut self, audit_service: Arc<AuditApplicationService>) -> Self {
+        self.audit_service = Some(audit_service);
+        self
+    }
+
+    /// Name the agent of a session in its exports
+    pub fn with_agent_repository(mut self, agent_repo: Arc<dyn AgentRepository>) -> Self {
+        self.agent_repo = Some(agent_repo);
+        self
+    }
+
+    /// Resolve `oss://` image URLs of added messages to download URLs
+    pub fn with_file_repository(mut self, file_repo: Arc<dyn FileRepository>) -> Self {
+        self.file_repo = Some(file_repo);
+        self
+    }
+
     /// Create a new chat session
     pub async fn create_session(
         &self,
@@ -112,10 +232,12 @@ impl SessionApplicationService {
         session_id: &SessionId,
         tenant_id: &TenantId,
         user_id: &UserId,
-        message: ChatMessage,
+        mut message: ChatMessage,
     ) -> Result<Message> {
         let mut session = self.get_session(session_id, tenant_id, user_id).await?;
 
+        self.resolve_image_urls(tenant_id, &mut message.content).await?;
+
         // Add message using domain service
         let msg = self
             .domain_service
@@ -130,6 +252,29 @@ impl SessionApplicationService {
         Ok(msg)
     }
 
+    /// Replace `oss://<key>` image URLs with the download URL of the tenant's
+    /// uploaded object, so providers can fetch the image
+    async fn resolve_image_urls(&self, tenant_id: &TenantId, content: &mut MessageContent) -> Result<()> {
+        let MessageContent::Multimodal(parts) = content else {
+            return Ok(());
+        };
+
+        for part in parts.iter_mut() {
+            let ContentPart::ImageUrl { image_url } = part else {
+                continue;
+            };
+            let Some(key) = image_url.oss_key() else {
+                continue;
+            };
+            let file_repo = self.file_repo.as_ref().ok_or_else(|| {
+                PlatformError::ValidationError("OSS image URLs are not supported".to_string())
+            })?;
+            image_url.url = file_repo.confirm_upload(&tenant_id.0.to_string(), key).await?;
+        }
+
+        Ok(())
+    }
+
     /// Set context variable in session
     pub async fn set_context_variable(
         &self,
@@ -180,6 +325,99 @@ impl SessionApplicationService {
         self.message_repo.find_by_session(session_id).await
     }
 
+    /// Export the current conversation of a session. Messages replaced by an
+    /// edit are left out; the agent is the one the last agent message names.
+    pub async fn export_chat_history(
+        &self,
+        session_id: &SessionId,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        format: ChatExportFormat,
+    ) -> Result<Vec<u8>> {
+        let session = self.get_session(session_id, tenant_id, user_id).await?;
+        let messages: Vec<Message> = self
+            .message_repo
+            .find_by_session(session_id)
+            .await?
+            .into_iter()
+            .filter(|message| !message.is_superseded())
+            .collect();
+        let agent = self.session_agent(&session, &messages).await;
+
+        match format {
+            ChatExportFormat::Markdown => Ok(render_markdown(&session, agent.as_ref(), &messages).into_bytes()),
+            ChatExportFormat::Json => serde_json::to_vec_pretty(&render_json(&session, agent.as_ref(), &messages))
+                .map_err(|e| PlatformError::InternalError(format!("Failed to serialize chat history: {}", e))),
+            ChatExportFormat::Pdf => {
+                let lines = pdf_lines(&session, agent.as_ref(), &messages);
+                tokio::task::spawn_blocking(move || render_pdf(lines))
+                    .await
+                    .map_err(|e| PlatformError::InternalError(format!("PDF export task failed: {}", e)))?
+            }
+        }
+    }
+
+    /// Agent named in the metadata of the session's messages, if it still
+    /// exists in the session's tenant
+    async fn session_agent(&self, session: &ChatSession, messages: &[Message]) -> Option<Agent> {
+        let agent_repo = self.agent_repo.as_ref()?;
+        let agent_id = messages.iter().rev().find_map(|message| {
+            message
+                .message
+                .metadata
+                .as_ref()?
+                .custom_data
+                .get("agent_id")?
+                .as_str()
+                .and_then(|id| uuid::Uuid::parse_str(id).ok())
+                .map(AgentId)
+        })?;
+
+        match agent_repo.find_by_id(&agent_id).await {
+            Ok(agent) => agent.filter(|agent| agent.tenant_id == session.tenant_id),
+            Err(e) => {
+                tracing::warn!("Failed to load agent {} for chat export: {}", agent_id.0, e);
+                None
+            }
+        }
+    }
+
+    /// The most recent messages of a session, oldest first: at most
+    /// `max_messages`, trimmed from the oldest end to fit `max_tokens` as
+    /// counted by the tokenizer of `model_name`. The newest message is always
+    /// kept so the prompt still ends with it.
+    pub async fn get_recent_messages(
+        &self,
+        session_id: &SessionId,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+        max_messages: usize,
+        max_tokens: usize,
+        model_name: &str,
+    ) -> Result<Vec<ChatMessage>> {
+        let _session = self.get_session(session_id, tenant_id, user_id).await?;
+
+        let messages = self
+            .message_repo
+            .find_recent_by_session(session_id, max_messages as u64)
+            .await?;
+
+        let counter = TokenCounter::new(model_name);
+        let mut used_tokens = 0;
+        let mut recent = Vec::new();
+        for message in messages.into_iter().rev() {
+            let tokens = counter.count_message(&message.message) as usize;
+            if !recent.is_empty() && used_tokens + tokens > max_tokens {
+                break;
+            }
+            used_tokens += tokens;
+            recent.push(message.message);
+        }
+        recent.reverse();
+
+        Ok(recent)
+    }
+
     /// Update session summary for context compression
     pub async fn update_session_summary(
         &self,
@@ -200,7 +438,89 @@ impl SessionApplicationService {
         Ok(())
     }
 
-    /// Delete session
+    /// Summarize all messages of a session with the tenant's default LLM and
+    /// store the result in `session.summary`
+    pub async fn summarize_session(
+        &self,
+        session_id: &SessionId,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+    ) -> Result<String> {
+        let mut session = self.get_session(session_id, tenant_id, user_id).await?;
+
+        let llm_service = self.llm_service.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM service not configured".to_string()))?;
+
+        let llm_config_repo = self.llm_config_repo.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("LLM config repository not configured".to_string()))?;
+
+        let mut messages = self.message_repo.find_by_session(session_id).await?;
+        messages.retain(|message| !message.is_superseded());
+        if messages.is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Cannot summarize a session without messages".to_string(),
+            ));
+        }
+
+        // Prefer the tenant's default config, fall back to first active
+        let llm_config = match llm_config_repo.find_default_by_tenant(*tenant_id).await? {
+            Some(config) => config,
+            None => llm_config_repo.find_active_by_tenant(*tenant_id).await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| PlatformError::NotFound("No LLM configuration found for tenant".to_string()))?,
+        };
+
+        let transcript = messages
+            .iter()
+            .map(|m| format!("{:?}: {}", m.message.role, m.message.get_text_content()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let response = llm_service
+            .chat_completion(
+                &llm_config.model_config,
+                vec![
+                    ChatMessage::new_system_message(SESSION_SUMMARY_PROMPT.to_string()),
+                    ChatMessage::new_user_message(transcript),
+                ],
+                tenant_id.0,
+                None,
+            )
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("LLM error: {}", e)))?;
+
+        let summary = response.content.trim().to_string();
+        session.set_summary(summary.clone());
+        self.session_repo.save(&session).await?;
+
+        Ok(summary)
+    }
+
+    /// Summarize the session if it has outgrown the summary threshold and
+    /// has no summary yet. Returns the new summary, if one was generated.
+    pub async fn summarize_if_needed(
+        &self,
+        session_id: &SessionId,
+        tenant_id: &TenantId,
+        user_id: &UserId,
+    ) -> Result<Option<String>> {
+        let session = self.get_session(session_id, tenant_id, user_id).await?;
+        if session.summary.is_some() {
+            return Ok(None);
+        }
+
+        let message_count = self.message_repo.count_by_session(session_id).await?;
+        if message_count <= self.summary_threshold as u64 {
+            return Ok(None);
+        }
+
+        self.summarize_session(session_id, tenant_id, user_id)
+            .await
+            .map(Some)
+    }
+
+    /// Delete a session of the user together with its messages
     pub async fn delete_session(
         &self,
         session_id: &SessionId,
@@ -210,12 +530,73 @@ impl SessionApplicationService {
         // Validate access first
         let session = self.get_session(session_id, tenant_id, user_id).await?;
 
-        // Delete all messages in session
-        self.message_repo.delete_by_session(&session.id).await?;
+        self.remove_session(&session, Some(user_id)).await
+    }
+
+    /// Delete all sessions of a tenant, with their messages, that have not
+    /// been updated for `older_than_days` days. Returns the number deleted.
+    pub async fn purge_old_sessions(&self, tenant_id: &TenantId, older_than_days: u32) -> Result<u64> {
+        if older_than_days == 0 {
+            return Err(PlatformError::ValidationError(
+                "older_than_days must be at least 1".to_string(),
+            ));
+        }
+
+        let cutoff = Utc::now() - chrono::Duration::days(older_than_days as i64);
+        let mut deleted = 0;
+
+        loop {
+            let sessions = self
+                .session_repo
+                .find_by_tenant_updated_before(tenant_id, cutoff, PURGE_BATCH_SIZE)
+                .await?;
+
+            for session in &sessions {
+                self.remove_session(session, None).await?;
+                deleted += 1;
+            }
 
-        // Delete session
+            if (sessions.len() as u64) < PURGE_BATCH_SIZE {
+                break;
+            }
+        }
+
+        if deleted > 0 {
+            tracing::info!(tenant_id = %tenant_id.0, older_than_days, "Purged {} old sessions", deleted);
+        }
+
+        Ok(deleted)
+    }
+
+    /// Delete the messages of a session, then the session itself, and record
+    /// a `SessionDeleted` event. `deleted_by` is `None` for administrative purges.
+    async fn remove_session(&self, session: &ChatSession, deleted_by: Option<&UserId>) -> Result<()> {
+        let message_count = self.message_repo.count_by_session(&session.id).await?;
+
+        self.message_repo.delete_by_session(&session.id).await?;
         self.session_repo.delete(&session.id).await?;
 
+        let event = SessionDeleted::new(
+            session.id.0,
+            session.tenant_id.0,
+            session.user_id.0,
+            deleted_by.map(|user_id| user_id.0),
+            message_count,
+        );
+
+        // The session is already gone, so failures here are only logged
+        if let Some(event_store) = &self.event_store {
+            if let Err(e) = event_store.append(&event).await {
+                tracing::warn!("Failed to store deletion event for session {}: {}", session.id.0, e);
+            }
+        }
+
+        if let (Some(audit_service), Some(entry)) = (&self.audit_service, event.audit_entry()) {
+            if let Err(e) = audit_service.record_event(AuditEvent::from(entry)).await {
+                tracing::warn!("Failed to record audit event for session {}: {}", session.id.0, e);
+            }
+        }
+
         Ok(())
     }
 
@@ -241,11 +622,11 @@ impl SessionApplicationService {
                 match self.cleanup_expired_sessions().await {
                     Ok(count) => {
                         if count > 0 {
-                            log::info!("Cleaned up {} expired sessions", count);
+                            tracing::info!("Cleaned up {} expired sessions", count);
                         }
                     }
                     Err(e) => {
-                        log::error!("Failed to cleanup expired sessions: {}", e);
+                        tracing::error!("Failed to cleanup expired sessions: {}", e);
                     }
                 }
             }
@@ -253,14 +634,203 @@ impl SessionApplicationService {
     }
 }
 
+fn role_label(role: &MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "User",
+        MessageRole::Assistant => "Assistant",
+        MessageRole::System => "System",
+        MessageRole::Tool => "Tool",
+    }
+}
+
+/// User and assistant turns; system prompts and tool results are internal
+fn conversation_turns(messages: &[Message]) -> impl Iterator<Item = &ChatMessage> {
+    messages
+        .iter()
+        .map(|message| &message.message)
+        .filter(|message| matches!(message.role, MessageRole::User | MessageRole::Assistant))
+}
+
+fn session_title(session: &ChatSession) -> &str {
+    session.title.as_deref().unwrap_or("Chat session")
+}
+
+fn render_markdown(session: &ChatSession, agent: Option<&Agent>, messages: &[Message]) -> String {
+    let mut markdown = format!("# {}\n\n", session_title(session));
+    if let Some(agent) = agent {
+        markdown.push_str(&format!("Agent: {}\n\n", agent.name));
+    }
+    markdown.push_str(&format!("Exported {}\n", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+
+    for message in conversation_turns(messages) {
+        markdown.push_str(&format!(
+            "\n---\n\n**{}** · {}\n\n{}\n",
+            role_label(&message.role),
+            message.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            message.get_text_content().trim()
+        ));
+    }
+
+    markdown
+}
+
+fn render_json(session: &ChatSession, agent: Option<&Agent>, messages: &[Message]) -> serde_json::Value {
+    serde_json::json!({
+        "session": {
+            "id": session.id.0,
+            "title": session.title,
+            "created_at": session.created_at,
+            "updated_at": session.updated_at,
+        },
+        "agent": agent.map(|agent| serde_json::json!({
+            "id": agent.id.0,
+            "name": agent.name,
+            "avatar_url": agent.avatar,
+        })),
+        "exported_at": Utc::now(),
+        "messages": messages.iter().map(|message| serde_json::json!({
+            "id": message.id.0,
+            "role": message.message.role,
+            "content": message.message.get_text_content(),
+            "timestamp": message.message.timestamp,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// One line of text on an exported PDF page
+#[derive(Debug, Clone, PartialEq)]
+struct PdfLine {
+    text: String,
+    size: f32,
+    bold: bool,
+}
+
+impl PdfLine {
+    fn new(text: impl Into<String>, size: f32, bold: bool) -> Self {
+        Self {
+            text: text.into(),
+            size,
+            bold,
+        }
+    }
+}
+
+/// The PDF's built-in fonts only cover Latin text; other characters are
+/// replaced so the document stays readable
+fn pdf_text(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii() && !c.is_ascii_control() { c } else if c == '\t' { ' ' } else { '?' })
+        .collect()
+}
+
+fn wrap_pdf_text(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in pdf_text(text).split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            if !line.is_empty() && line.len() + 1 + word.len() > PDF_LINE_CHARS {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+            while line.len() > PDF_LINE_CHARS {
+                let rest = line.split_off(PDF_LINE_CHARS);
+                lines.push(std::mem::replace(&mut line, rest));
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn pdf_lines(session: &ChatSession, agent: Option<&Agent>, messages: &[Message]) -> Vec<PdfLine> {
+    let mut lines = Vec::new();
+    if let Some(agent) = agent {
+        lines.push(PdfLine::new(pdf_text(&agent.name), 16.0, true));
+        if let Some(avatar) = &agent.avatar {
+            lines.push(PdfLine::new(pdf_text(avatar), 9.0, false));
+        }
+    }
+    lines.push(PdfLine::new(pdf_text(session_title(session)), 13.0, true));
+    lines.push(PdfLine::new(
+        format!("Exported {}", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")),
+        9.0,
+        false,
+    ));
+
+    for message in conversation_turns(messages) {
+        lines.push(PdfLine::new("", 10.0, false));
+        lines.push(PdfLine::new(
+            format!(
+                "{} - {}",
+                role_label(&message.role),
+                message.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+            ),
+            10.0,
+            true,
+        ));
+        lines.extend(
+            wrap_pdf_text(message.get_text_content().trim())
+                .into_iter()
+                .map(|line| PdfLine::new(line, 10.0, false)),
+        );
+    }
+
+    lines
+}
+
+/// Lay the lines out top to bottom on A4 pages
+fn render_pdf(lines: Vec<PdfLine>) -> Result<Vec<u8>> {
+    use printpdf::{BuiltinFont, Mm, PdfDocument};
+
+    const PAGE_WIDTH: f32 = 210.0;
+    const PAGE_HEIGHT: f32 = 297.0;
+    const MARGIN: f32 = 15.0;
+
+    let pdf_error = |e: printpdf::Error| PlatformError::InternalError(format!("Failed to render PDF: {}", e));
+
+    let (doc, page, layer) = PdfDocument::new("Chat history", Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+    let regular = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(pdf_error)?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(pdf_error)?;
+
+    let mut layer = doc.get_page(page).get_layer(layer);
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for line in lines {
+        // Points to millimetres, with some leading
+        let height = line.size * 0.3528 * 1.4;
+        if y - height < MARGIN {
+            let (page, page_layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            layer = doc.get_page(page).get_layer(page_layer);
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= height;
+        if !line.text.is_empty() {
+            let font = if line.bold { &bold } else { &regular };
+            layer.use_text(line.text, line.size, Mm(MARGIN), Mm(y), font);
+        }
+    }
+
+    doc.save_to_bytes().map_err(pdf_error)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::repositories::{ChatSessionRepository, MessageRepository};
+    use crate::domain::entities::LLMConfig;
+    use crate::domain::repositories::{ChatSessionRepository, LLMConfigRepository, MessageRepository};
+    use crate::domain::services::llm_service::{
+        ChatResponse, ChatStreamChunk, ConnectionTestResult, FinishReason, LLMError, ModelInfo,
+        ResponseFormat, TokenUsage, ValidationResult,
+    };
+    use crate::domain::value_objects::{
+        ConfigId, MessageId, ModelConfig, ModelCredentials, ModelParameters, ModelProvider,
+    };
     use async_trait::async_trait;
     use chrono::{DateTime, Utc};
     use mockall::mock;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
 
     mock! {
         ChatSessionRepositoryImpl {}
@@ -270,6 +840,7 @@ mod tests {
             async fn find_by_id(&self, id: &SessionId) -> Result<Option<ChatSession>>;
             async fn find_by_user(&self, user_id: &UserId) -> Result<Vec<ChatSession>>;
             async fn find_by_tenant(&self, tenant_id: &TenantId) -> Result<Vec<ChatSession>>;
+            async fn find_by_tenant_updated_before(&self, tenant_id: &TenantId, before: DateTime<Utc>, limit: u64) -> Result<Vec<ChatSession>>;
             async fn find_by_tenant_and_user(&self, tenant_id: &TenantId, user_id: &UserId) -> Result<Vec<ChatSession>>;
             async fn find_active_by_user(&self, user_id: &UserId, timeout_minutes: u64) -> Result<Vec<ChatSession>>;
             async fn save(&self, session: &ChatSession) -> Result<()>;
@@ -294,10 +865,290 @@ mod tests {
             async fn delete_by_session(&self, session_id: &SessionId) -> Result<()>;
             async fn count_by_session(&self, session_id: &SessionId) -> Result<u64>;
             async fn search_by_content(&self, session_id: &SessionId, query: &str, limit: u64) -> Result<Vec<Message>>;
+            async fn mark_superseded(&self, ids: &[crate::domain::value_objects::ids::MessageId], superseded_at: DateTime<Utc>) -> Result<()>;
+        }
+    }
+
+    mock! {
+        ConfigRepo {}
+
+        #[async_trait]
+        impl LLMConfigRepository for ConfigRepo {
+            async fn find_by_id(&self, id: ConfigId) -> Result<Option<LLMConfig>>;
+            async fn find_by_tenant(&self, tenant_id: TenantId) -> Result<Vec<LLMConfig>>;
+            async fn find_active_by_tenant(&self, tenant_id: TenantId) -> Result<Vec<LLMConfig>>;
+            async fn find_default_by_tenant(&self, tenant_id: TenantId) -> Result<Option<LLMConfig>>;
+            async fn find_by_tenant_and_name(&self, tenant_id: TenantId, name: &str) -> Result<Option<LLMConfig>>;
+            async fn save(&self, config: &LLMConfig) -> Result<()>;
+            async fn delete(&self, id: ConfigId) -> Result<()>;
+            async fn name_exists(&self, tenant_id: TenantId, name: &str) -> Result<bool>;
+            async fn count_by_tenant(&self, tenant_id: TenantId) -> Result<u64>;
+            async fn find_by_tenant_and_provider(&self, tenant_id: TenantId, provider: &str) -> Result<Vec<LLMConfig>>;
+            async fn set_as_default(&self, tenant_id: TenantId, config_id: ConfigId) -> Result<()>;
+            async fn find_by_tenant_paginated(&self, tenant_id: TenantId, offset: u64, limit: u64) -> Result<Vec<LLMConfig>>;
+        }
+    }
+
+    mock! {
+        Llm {}
+
+        #[async_trait]
+        impl LLMDomainService for Llm {
+            async fn chat_completion(
+                &self,
+                config: &ModelConfig,
+                messages: Vec<ChatMessage>,
+                tenant_id: uuid::Uuid,
+                response_format: Option<ResponseFormat>,
+            ) -> std::result::Result<ChatResponse, LLMError>;
+            async fn generate_embedding(
+                &self,
+                config: &ModelConfig,
+                text: &str,
+                tenant_id: uuid::Uuid,
+            ) -> std::result::Result<Vec<f32>, LLMError>;
+            async fn stream_chat_completion(
+                &self,
+                config: &ModelConfig,
+                messages: Vec<ChatMessage>,
+                tenant_id: uuid::Uuid,
+            ) -> std::result::Result<Box<dyn futures::Stream<Item = std::result::Result<ChatStreamChunk, LLMError>> + Send + Unpin>, LLMError>;
+            fn validate_config(&self, config: &ModelConfig) -> std::result::Result<ValidationResult, LLMError>;
+            fn supports_streaming(&self, config: &ModelConfig) -> bool;
+            async fn get_available_models(&self, provider: &str) -> std::result::Result<Vec<ModelInfo>, LLMError>;
+            async fn test_connection(&self, config: &ModelConfig) -> std::result::Result<ConnectionTestResult, LLMError>;
+            fn estimate_token_count(&self, messages: &[ChatMessage], model: &str) -> std::result::Result<u32, LLMError>;
+        }
+    }
+
+    fn default_config(tenant_id: TenantId) -> LLMConfig {
+        LLMConfig::new(
+            tenant_id,
+            "default".to_string(),
+            ModelConfig {
+                provider: ModelProvider::OpenAI,
+                model_name: "gpt-4".to_string(),
+                parameters: ModelParameters::default(),
+                credentials: ModelCredentials::default(),
+            },
+        )
+        .set_as_default()
+    }
+
+    fn text_message(session_id: SessionId, message: ChatMessage) -> Message {
+        Message {
+            id: MessageId::new(),
+            session_id,
+            message,
+            superseded_at: None,
         }
     }
 
+    #[tokio::test]
+    async fn test_summarize_session_stores_summary() {
+        let user_id = UserId::new();
+        let tenant_id = TenantId::new();
+        let session = ChatSession::new(tenant_id, user_id, None);
+        let session_id = session.id;
+
+        let saved: Arc<Mutex<Option<ChatSession>>> = Arc::new(Mutex::new(None));
+        let saved_clone = saved.clone();
+        let mut session_repo = MockChatSessionRepositoryImpl::new();
+        session_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(session.clone())));
+        session_repo.expect_save().times(1).returning(move |session| {
+            *saved_clone.lock().unwrap() = Some(session.clone());
+            Ok(())
+        });
+
+        let mut message_repo = MockMessageRepositoryImpl::new();
+        message_repo.expect_find_by_session().returning(move |_| {
+            Ok(vec![
+                text_message(session_id, ChatMessage::new_user_message("Plan my trip".to_string())),
+                text_message(session_id, ChatMessage::new_assistant_message("Where to?".to_string())),
+            ])
+        });
+
+        let mut config_repo = MockConfigRepo::new();
+        config_repo
+            .expect_find_default_by_tenant()
+            .returning(move |tenant_id| Ok(Some(default_config(tenant_id))));
+
+        let mut llm = MockLlm::new();
+        llm.expect_chat_completion()
+            .times(1)
+            .withf(|_, messages, _, _| messages[1].get_text_content().contains("Plan my trip"))
+            .returning(|_, _, _, _| {
+                Ok(ChatResponse {
+                    content: " The user is planning a trip. ".to_string(),
+                    model_used: "gpt-4".to_string(),
+                    usage: TokenUsage {
+                        prompt_tokens: 0,
+                        completion_tokens: 0,
+                        total_tokens: 0,
+                    },
+                    finish_reason: FinishReason::Stop,
+                    metadata: None,
+                })
+            });
+
+        let service = SessionApplicationService::new(
+            Arc::new(session_repo),
+            Arc::new(message_repo),
+            Arc::new(SessionDomainService::new(30)),
+        )
+        .with_llm_service(Arc::new(llm))
+        .with_llm_config_repo(Arc::new(config_repo));
+
+        let summary = service
+            .summarize_session(&session_id, &tenant_id, &user_id)
+            .await
+            .unwrap();
+
+        assert_eq!(summary, "The user is planning a trip.");
+        let saved = saved.lock().unwrap().clone().unwrap();
+        assert_eq!(saved.summary.as_deref(), Some("The user is planning a trip."));
+    }
+
+    #[tokio::test]
+    async fn test_add_message_resolves_oss_image_urls() {
+        use crate::domain::repositories::MockFileRepository;
+
+        let user_id = UserId::new();
+        let tenant_id = TenantId::new();
+        let session = ChatSession::new(tenant_id, user_id, None);
+        let session_id = session.id;
+
+        let mut session_repo = MockChatSessionRepositoryImpl::new();
+        session_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(session.clone())));
+        session_repo.expect_save().returning(|_| Ok(()));
+
+        let mut message_repo = MockMessageRepositoryImpl::new();
+        message_repo.expect_save().times(1).returning(|_| Ok(()));
+
+        let tenant = tenant_id.0.to_string();
+        let mut file_repo = MockFileRepository::new();
+        file_repo
+            .expect_confirm_upload()
+            .times(1)
+            .withf(move |tenant_id, key| tenant_id == tenant && key.ends_with("/cat.png"))
+            .returning(|_, key| Ok(format!("https://cdn.example.com/{}", key)));
+
+        let service = SessionApplicationService::new(
+            Arc::new(session_repo),
+            Arc::new(message_repo),
+            Arc::new(SessionDomainService::new(30)),
+        )
+        .with_file_repository(Arc::new(file_repo));
+
+        let key = format!("{}/uploads/cat.png", tenant_id.0);
+        let message = ChatMessage::new_user_message_with_images(
+            "What is this?".to_string(),
+            vec![format!("oss://{}", key), "https://example.com/dog.png".to_string()],
+        );
+        let message = service
+            .add_message(&session_id, &tenant_id, &user_id, message)
+            .await
+            .unwrap();
+
+        let MessageContent::Multimodal(parts) = &message.message.content else {
+            panic!("expected multimodal content");
+        };
+        let urls: Vec<&str> = parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::ImageUrl { image_url } => Some(image_url.url.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            urls,
+            vec![format!("https://cdn.example.com/{}", key).as_str(), "https://example.com/dog.png"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_summarize_if_needed_respects_threshold() {
+        let user_id = UserId::new();
+        let tenant_id = TenantId::new();
+        let session = ChatSession::new(tenant_id, user_id, None);
+        let session_id = session.id;
+
+        let mut session_repo = MockChatSessionRepositoryImpl::new();
+        session_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(session.clone())));
+        session_repo.expect_save().never();
+
+        let mut message_repo = MockMessageRepositoryImpl::new();
+        message_repo.expect_count_by_session().returning(|_| Ok(10));
+
+        let mut llm = MockLlm::new();
+        llm.expect_chat_completion().never();
+
+        let service = SessionApplicationService::new(
+            Arc::new(session_repo),
+            Arc::new(message_repo),
+            Arc::new(SessionDomainService::new(30)),
+        )
+        .with_llm_service(Arc::new(llm))
+        .with_summary_threshold(10);
+
+        let summary = service
+            .summarize_if_needed(&session_id, &tenant_id, &user_id)
+            .await
+            .unwrap();
+
+        assert!(summary.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_messages_fits_token_budget() {
+        let user_id = UserId::new();
+        let tenant_id = TenantId::new();
+        let session = ChatSession::new(tenant_id, user_id, None);
+        let session_id = session.id;
 
+        let mut session_repo = MockChatSessionRepositoryImpl::new();
+        session_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(session.clone())));
+
+        let mut message_repo = MockMessageRepositoryImpl::new();
+        message_repo
+            .expect_find_recent_by_session()
+            .withf(|_, limit| *limit == 3)
+            .returning(move |_, _| {
+                Ok(vec![
+                    text_message(session_id, ChatMessage::new_user_message("word ".repeat(200))),
+                    text_message(session_id, ChatMessage::new_assistant_message("Sure.".to_string())),
+                    text_message(session_id, ChatMessage::new_user_message("Thanks!".to_string())),
+                ])
+            });
+
+        let service = SessionApplicationService::new(
+            Arc::new(session_repo),
+            Arc::new(message_repo),
+            Arc::new(SessionDomainService::new(30)),
+        );
+
+        let recent = service
+            .get_recent_messages(&session_id, &tenant_id, &user_id, 3, 50, "gpt-4")
+            .await
+            .unwrap();
+        let texts: Vec<String> = recent.iter().map(|m| m.get_text_content()).collect();
+        assert_eq!(texts, vec!["Sure.".to_string(), "Thanks!".to_string()]);
+
+        let newest_only = service
+            .get_recent_messages(&session_id, &tenant_id, &user_id, 3, 0, "gpt-4")
+            .await
+            .unwrap();
+        assert_eq!(newest_only.len(), 1);
+        assert_eq!(newest_only[0].get_text_content(), "Thanks!");
+    }
 
     #[tokio::test]
     async fn test_list_user_sessions_zero_based() {
@@ -405,4 +1256,194 @@ mod tests {
         let (_, total) = result.unwrap();
         assert_eq!(total, 17);
     }
+
+    #[tokio::test]
+    async fn test_delete_session_removes_messages_and_records_event() {
+        use crate::domain::events::InMemoryEventStore;
+
+        let user_id = UserId::new();
+        let tenant_id = TenantId::new();
+        let session = ChatSession::new(tenant_id, user_id, None);
+        let session_id = session.id;
+
+        let messages = Arc::new(Mutex::new(vec![
+            text_message(session_id, ChatMessage::new_user_message("Hi".to_string())),
+            text_message(session_id, ChatMessage::new_assistant_message("Hello".to_string())),
+        ]));
+
+        let mut session_repo = MockChatSessionRepositoryImpl::new();
+        session_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(session.clone())));
+        session_repo
+            .expect_delete()
+            .times(1)
+            .withf(move |id| *id == session_id)
+            .returning(|_| Ok(()));
+
+        let mut message_repo = MockMessageRepositoryImpl::new();
+        let stored = messages.clone();
+        message_repo
+            .expect_count_by_session()
+            .returning(move |id| Ok(stored.lock().unwrap().iter().filter(|m| m.session_id == *id).count() as u64));
+        let stored = messages.clone();
+        message_repo
+            .expect_delete_by_session()
+            .times(1)
+            .returning(move |id| {
+                stored.lock().unwrap().retain(|m| m.session_id != *id);
+                Ok(())
+            });
+
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let service = SessionApplicationService::new(
+            Arc::new(session_repo),
+            Arc::new(message_repo),
+            Arc::new(SessionDomainService::new(30)),
+        )
+        .with_event_store(event_store.clone());
+
+        service
+            .delete_session(&session_id, &tenant_id, &user_id)
+            .await
+            .unwrap();
+
+        assert!(messages.lock().unwrap().is_empty());
+
+        let events = event_store.load_events(session_id.0, 0).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type(), "session.deleted");
+        let entry = events[0].audit_entry().unwrap();
+        assert_eq!(entry.user_id, Some(user_id.0));
+        assert_eq!(entry.details.unwrap()["message_count"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_purge_old_sessions_deletes_in_batches() {
+        let tenant_id = TenantId::new();
+        let batches = Arc::new(Mutex::new(vec![
+            vec![ChatSession::new(tenant_id, UserId::new(), None)],
+            (0..PURGE_BATCH_SIZE)
+                .map(|_| ChatSession::new(tenant_id, UserId::new(), None))
+                .collect::<Vec<_>>(),
+        ]));
+
+        let mut session_repo = MockChatSessionRepositoryImpl::new();
+        session_repo
+            .expect_find_by_tenant_updated_before()
+            .times(2)
+            .withf(|_, before, limit| *before < Utc::now() - chrono::Duration::days(29) && *limit == PURGE_BATCH_SIZE)
+            .returning(move |_, _, _| Ok(batches.lock().unwrap().pop().unwrap_or_default()));
+        session_repo
+            .expect_delete()
+            .times(PURGE_BATCH_SIZE as usize + 1)
+            .returning(|_| Ok(()));
+
+        let mut message_repo = MockMessageRepositoryImpl::new();
+        message_repo.expect_count_by_session().returning(|_| Ok(0));
+        message_repo
+            .expect_delete_by_session()
+            .times(PURGE_BATCH_SIZE as usize + 1)
+            .returning(|_| Ok(()));
+
+        let service = SessionApplicationService::new(
+            Arc::new(session_repo),
+            Arc::new(message_repo),
+            Arc::new(SessionDomainService::new(30)),
+        );
+
+        assert_eq!(service.purge_old_sessions(&tenant_id, 30).await.unwrap(), PURGE_BATCH_SIZE + 1);
+        assert!(matches!(
+            service.purge_old_sessions(&tenant_id, 0).await,
+            Err(PlatformError::ValidationError(_))
+        ));
+    }
+
+    fn export_service(session: ChatSession, messages: Vec<Message>) -> SessionApplicationService {
+        let mut session_repo = MockChatSessionRepositoryImpl::new();
+        session_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(session.clone())));
+        let mut message_repo = MockMessageRepositoryImpl::new();
+        message_repo
+            .expect_find_by_session()
+            .returning(move |_| Ok(messages.clone()));
+
+        SessionApplicationService::new(
+            Arc::new(session_repo),
+            Arc::new(message_repo),
+            Arc::new(SessionDomainService::new(30)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_export_chat_history_markdown_and_json() {
+        let user_id = UserId::new();
+        let tenant_id = TenantId::new();
+        let session = ChatSession::new(tenant_id, user_id, Some("Trip".to_string()));
+        let session_id = session.id;
+        let mut edited = text_message(session_id, ChatMessage::new_user_message("Old question".to_string()));
+        edited.superseded_at = Some(Utc::now());
+        let messages = vec![
+            text_message(session_id, ChatMessage::new_system_message("Be brief".to_string())),
+            edited,
+            text_message(session_id, ChatMessage::new_user_message("Plan my trip".to_string())),
+            text_message(session_id, ChatMessage::new_assistant_message("Where to?".to_string())),
+        ];
+        let service = export_service(session, messages);
+
+        let markdown = service
+            .export_chat_history(&session_id, &tenant_id, &user_id, ChatExportFormat::Markdown)
+            .await
+            .unwrap();
+        let markdown = String::from_utf8(markdown).unwrap();
+        assert!(markdown.starts_with("# Trip"));
+        assert!(markdown.contains("**User** · "));
+        assert!(markdown.contains("Plan my trip"));
+        assert!(markdown.contains("**Assistant** · "));
+        assert!(!markdown.contains("Old question"));
+        assert!(!markdown.contains("Be brief"));
+
+        let json = service
+            .export_chat_history(&session_id, &tenant_id, &user_id, ChatExportFormat::Json)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["session"]["title"], "Trip");
+        assert_eq!(json["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(json["messages"][1]["content"], "Plan my trip");
+    }
+
+    #[tokio::test]
+    async fn test_export_chat_history_pdf() {
+        let user_id = UserId::new();
+        let tenant_id = TenantId::new();
+        let session = ChatSession::new(tenant_id, user_id, None);
+        let session_id = session.id;
+        let messages = vec![text_message(
+            session_id,
+            ChatMessage::new_user_message("word ".repeat(2000)),
+        )];
+        let service = export_service(session, messages);
+
+        let pdf = service
+            .export_chat_history(&session_id, &tenant_id, &user_id, ChatExportFormat::Pdf)
+            .await
+            .unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_wrap_pdf_text() {
+        let lines = wrap_pdf_text(&format!("{}\nshort 你好", "a".repeat(PDF_LINE_CHARS + 5)));
+        assert_eq!(lines, vec!["a".repeat(PDF_LINE_CHARS), "aaaaa".to_string(), "short ??".to_string()]);
+        assert!(wrap_pdf_text(&"word ".repeat(100)).iter().all(|line| line.len() <= PDF_LINE_CHARS));
+    }
+
+    #[test]
+    fn test_chat_export_format_from_str() {
+        assert_eq!("md".parse::<ChatExportFormat>().unwrap(), ChatExportFormat::Markdown);
+        assert_eq!("PDF".parse::<ChatExportFormat>().unwrap(), ChatExportFormat::Pdf);
+        assert!("docx".parse::<ChatExportFormat>().is_err());
+    }
 }
@@ -0,0 +1,336 @@
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate, Utc};
+use std::sync::Arc;
+
+use crate::{
+    application::{dto::TenantUsageDto, services::VectorApplicationService},
+    domain::{
+        entities::{QuotaConfig, QuotaResource, TenantSettings},
+        repositories::{
+            AgentRepository, FlowRepository, LlmUsageLogRepository, MCPToolRepository,
+            TenantRepository, TenantSettingsRepository,
+        },
+        services::SecretStore,
+        value_objects::TenantId,
+    },
+    error::{PlatformError, Result},
+};
+
+#[async_trait]
+pub trait TenantApplicationService: Send + Sync {
+    /// Replace the quota of a tenant
+    async fn set_quota(&self, tenant_id: TenantId, quota: QuotaConfig) -> Result<QuotaConfig>;
+
+    /// Quota of a tenant; unlimited unless one was set
+    async fn get_quota(&self, tenant_id: TenantId) -> Result<QuotaConfig>;
+
+    /// Current resource usage of a tenant along with its quota
+    async fn get_usage(&self, tenant_id: TenantId) -> Result<TenantUsageDto>;
+
+    /// Fail with `QuotaExceeded` unless `additional` units of `resource` fit
+    /// within the tenant's quota
+    async fn ensure_quota(
+        &self,
+        tenant_id: TenantId,
+        resource: QuotaResource,
+        additional: u64,
+    ) -> Result<()>;
+
+    /// Set the value of a secret flow environment variable
+    async fn set_secret(&self, tenant_id: TenantId, name: String, value: String) -> Result<()>;
+
+    /// Remove a secret; `NotFound` if it was not set
+    async fn delete_secret(&self, tenant_id: TenantId, name: String) -> Result<()>;
+
+    /// Names of the tenant's secrets, sorted. Values are never returned.
+    async fn list_secret_names(&self, tenant_id: TenantId) -> Result<Vec<String>>;
+}
+
+pub struct TenantApplicationServiceImpl {
+    tenant_repo: Arc<dyn TenantRepository>,
+    tenant_settings_repo: Arc<dyn TenantSettingsRepository>,
+    agent_repo: Arc<dyn AgentRepository>,
+    flow_repo: Arc<dyn FlowRepository>,
+    mcp_tool_repo: Arc<dyn MCPToolRepository>,
+    usage_log_repo: Arc<dyn LlmUsageLogRepository>,
+    vector_service: Option<Arc<VectorApplicationService>>,
+    secret_store: Option<Arc<dyn SecretStore>>,
+}
+
+impl TenantApplicationServiceImpl {
+    pub fn new(
+        tenant_repo: Arc<dyn TenantRepository>,
+        tenant_settings_repo: Arc<dyn TenantSettingsRepository>,
+        agent_repo: Arc<dyn AgentRepository>,
+        flow_repo: Arc<dyn FlowRepository>,
+        mcp_tool_repo: Arc<dyn MCPToolRepository>,
+        usage_log_repo: Arc<dyn LlmUsageLogRepository>,
+    ) -> Self {
+        Self {
+            tenant_repo,
+            tenant_settings_repo,
+            agent_repo,
+            flow_repo,
+            mcp_tool_repo,
+            usage_log_repo,
+            vector_service: None,
+            secret_store: None,
+        }
+    }
+
+    /// Set vector service so vector record usage can be read from the
+    /// tenant's default store
+    pub fn with_vector_service(mut self, vector_service: Arc<VectorApplicationService>) -> Self {
+        self.vector_service = Some(vector_service);
+        self
+    }
+
+    /// Set secret store; secret management fails without one
+    pub fn with_secret_store(mut self, secret_store: Arc<dyn SecretStore>) -> Self {
+        self.secret_store = Some(secret_store);
+        self
+    }
+
+    fn secret_store(&self) -> Result<&Arc<dyn SecretStore>> {
+        self.secret_store.as_ref()
+            .ok_or_else(|| PlatformError::InternalError("Secret store is not configured".to_string()))
+    }
+
+    fn month_start(today: NaiveDate) -> NaiveDate {
+        today.with_day(1).unwrap_or(today)
+    }
+
+    async fn ensure_tenant_exists(&self, tenant_id: TenantId) -> Result<()> {
+        self.tenant_repo
+            .find_by_id(tenant_id)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| PlatformError::NotFound(format!("Tenant {} not found", tenant_id.0)))
+    }
+
+    async fn settings_or_default(&self, tenant_id: TenantId) -> Result<TenantSettings> {
+        Ok(self
+            .tenant_settings_repo
+            .find_by_tenant(&tenant_id)
+            .await?
+            .unwrap_or_else(|| TenantSettings::new(tenant_id)))
+    }
+
+    async fn monthly_tokens(&self, tenant_id: TenantId) -> Result<u64> {
+        let today = Utc::now().date_naive();
+        self.usage_log_repo
+            .sum_tokens_by_tenant(&tenant_id, Self::month_start(today), today)
+            .await
+    }
+
+    async fn vector_records(&self, tenant_id: TenantId) -> Option<u64> {
+        let vector_service = self.vector_service.as_ref()?;
+
+        let stats = match vector_service.get_default_vector_store(tenant_id).await {
+            Ok(store) => store.get_stats(None).await,
+            Err(e) => Err(e),
+        };
+
+        match stats {
+            Ok(stats) => Some(stats.total_vectors),
+            Err(e) => {
+                tracing::warn!(tenant_id = %tenant_id.0, "Failed to read vector store stats: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn current_usage(&self, tenant_id: TenantId, resource: QuotaResource) -> Result<u64> {
+        match resource {
+            QuotaResource::Agents => self.agent_repo.count_by_tenant(&tenant_id).await,
+            QuotaResource::Flows => self.flow_repo.count_by_tenant(&tenant_id).await,
+            QuotaResource::McpTools => self.mcp_tool_repo.count_by_tenant(tenant_id).await,
+            QuotaResource::MonthlyTokens => self.monthly_tokens(tenant_id).await,
+            // Writes are not blocked when the store cannot report its size
+            QuotaResource::VectorRecords => Ok(self.vector_records(tenant_id).await.unwrap_or(0)),
+        }
+    }
+}
+
+#[async_trait]
+impl TenantApplicationService for TenantApplicationServiceImpl {
+    async fn set_quota(&self, tenant_id: TenantId, quota: QuotaConfig) -> Result<QuotaConfig> {
+        self.ensure_tenant_exists(tenant_id).await?;
+
+        let mut settings = self.settings_or_default(tenant_id).await?;
+        settings.update_quota(quota);
+        self.tenant_settings_repo.save(&settings).await?;
+
+        Ok(settings.quota)
+    }
+
+    async fn get_quota(&self, tenant_id: TenantId) -> Result<QuotaConfig> {
+        Ok(self
+            .tenant_settings_repo
+            .find_by_tenant(&tenant_id)
+            .await?
+            .map(|settings| settings.quota)
+            .unwrap_or_default())
+    }
+
+    async fn get_usage(&self, tenant_id: TenantId) -> Result<TenantUsageDto> {
+        self.ensure_tenant_exists(tenant_id).await?;
+
+        let (quota, agents, flows, mcp_tools, monthly_tokens, vector_records) = tokio::join!(
+            self.get_quota(tenant_id),
+            self.agent_repo.count_by_tenant(&tenant_id),
+            self.flow_repo.count_by_tenant(&tenant_id),
+            self.mcp_tool_repo.count_by_tenant(tenant_id),
+            self.monthly_tokens(tenant_id),
+            self.vector_records(tenant_id),
+        );
+
+        Ok(TenantUsageDto {
+            tenant_id: tenant_id.0,
+            agents: agents?,
+            flows: flows?,
+            mcp_tools: mcp_tools?,
+            monthly_tokens: monthly_tokens?,
+            vector_records,
+            period_start: Self::month_start(Utc::now().date_naive()),
+            quota: quota?,
+        })
+    }
+
+    async fn ensure_quota(
+        &self,
+        tenant_id: TenantId,
+        resource: QuotaResource,
+        additional: u64,
+    ) -> Result<()> {
+        let quota = self.get_quota(tenant_id).await?;
+        let Some(limit) = quota.limit(resource) else {
+            return Ok(());
+        };
+
+        let current = self.current_usage(tenant_id, resource).await?;
+        if quota.allows(resource, current, additional) {
+            return Ok(());
+        }
+
+        Err(PlatformError::QuotaExceeded(format!(
+            "Tenant {} quota reached: {} of {} used",
+            resource.as_str(),
+            current,
+            limit
+        )))
+    }
+
+    async fn set_secret(&self, tenant_id: TenantId, name: String, value: String) -> Result<()> {
+        let secret_store = self.secret_store()?;
+        self.ensure_tenant_exists(tenant_id).await?;
+        secret_store.set(tenant_id, &name, value).await
+    }
+
+    async fn delete_secret(&self, tenant_id: TenantId, name: String) -> Result<()> {
+        self.secret_store()?.delete(tenant_id, &name).await
+    }
+
+    async fn list_secret_names(&self, tenant_id: TenantId) -> Result<Vec<String>> {
+        let secret_store = self.secret_store()?;
+        self.ensure_tenant_exists(tenant_id).await?;
+        secret_store.list_keys(tenant_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::MockSecretStore;
+    use crate::domain::repositories::{
+        MockAgentRepository, MockFlowRepository, MockLlmUsageLogRepository,
+        MockMCPToolRepository, MockTenantRepository, MockTenantSettingsRepository,
+    };
+
+    fn service_with(
+        quota: Option<QuotaConfig>,
+        agent_repo: MockAgentRepository,
+    ) -> TenantApplicationServiceImpl {
+        let mut settings_repo = MockTenantSettingsRepository::new();
+        settings_repo.expect_find_by_tenant().returning(move |tenant_id| {
+            Ok(quota.clone().map(|quota| {
+                let mut settings = TenantSettings::new(*tenant_id);
+                settings.update_quota(quota);
+                settings
+            }))
+        });
+
+        TenantApplicationServiceImpl::new(
+            Arc::new(MockTenantRepository::new()),
+            Arc::new(settings_repo),
+            Arc::new(agent_repo),
+            Arc::new(MockFlowRepository::new()),
+            Arc::new(MockMCPToolRepository::new()),
+            Arc::new(MockLlmUsageLogRepository::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_ensure_quota_rejects_when_limit_reached() {
+        let mut agent_repo = MockAgentRepository::new();
+        agent_repo.expect_count_by_tenant().returning(|_| Ok(5));
+
+        let service = service_with(
+            Some(QuotaConfig {
+                max_agents: Some(5),
+                ..Default::default()
+            }),
+            agent_repo,
+        );
+
+        let result = service
+            .ensure_quota(TenantId::new(), QuotaResource::Agents, 1)
+            .await;
+        assert!(matches!(result, Err(PlatformError::QuotaExceeded(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_quota_skips_counting_without_limit() {
+        // No count expectation: counting would panic
+        let service = service_with(None, MockAgentRepository::new());
+
+        assert!(service
+            .ensure_quota(TenantId::new(), QuotaResource::Agents, 1)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_missing_secret_is_not_found() {
+        let mut secret_store = MockSecretStore::new();
+        secret_store
+            .expect_delete()
+            .returning(|_, key| Err(PlatformError::NotFound(format!("Secret {} not found", key))));
+        let service = service_with(None, MockAgentRepository::new())
+            .with_secret_store(Arc::new(secret_store));
+
+        let result = service
+            .delete_secret(TenantId::new(), "API_KEY".to_string())
+            .await;
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_secrets_need_a_secret_store() {
+        let service = service_with(None, MockAgentRepository::new());
+
+        let result = service
+            .set_secret(TenantId::new(), "API_KEY".to_string(), "sk-123".to_string())
+            .await;
+        assert!(matches!(result, Err(PlatformError::InternalError(_))));
+    }
+
+    #[test]
+    fn test_month_start() {
+        let today = NaiveDate::from_ymd_opt(2024, 12, 17).unwrap();
+        assert_eq!(
+            TenantApplicationServiceImpl::month_start(today),
+            NaiveDate::from_ymd_opt(2024, 12, 1).unwrap()
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use async_trait::async_trait;
+use image::{imageops::FilterType, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    application::dto::{FileUpload, UpdateProfileDto, UserProfileDto},
+    domain::{
+        entities::User,
+        repositories::{FileRepository, UserRepository},
+        value_objects::UserId,
+    },
+    error::{PlatformError, Result},
+};
+
+/// Width and height of stored avatars, in pixels
+pub const AVATAR_SIZE: u32 = 128;
+
+/// Largest avatar image accepted for upload (2 MiB, the default request body limit)
+pub const MAX_AVATAR_UPLOAD_BYTES: usize = 2 * 1024 * 1024;
+
+/// Largest width or height of an image decoded for an avatar
+const MAX_AVATAR_SOURCE_DIMENSION: u32 = 8192;
+
+/// Profile management for the signed-in user
+#[async_trait]
+pub trait UserApplicationService: Send + Sync {
+    async fn get_profile(&self, user_id: UserId) -> Result<UserProfileDto>;
+
+    /// Change nickname, email and language
+    async fn update_profile(&self, user_id: UserId, dto: UpdateProfileDto) -> Result<UserProfileDto>;
+
+    /// Scale the image to a square avatar, store it and return its URL
+    async fn upload_avatar(&self, user_id: UserId, file: FileUpload) -> Result<String>;
+}
+
+pub struct UserApplicationServiceImpl {
+    user_repository: Arc<dyn UserRepository>,
+    file_repository: Arc<dyn FileRepository>,
+}
+
+impl UserApplicationServiceImpl {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        file_repository: Arc<dyn FileRepository>,
+    ) -> Self {
+        Self {
+            user_repository,
+            file_repository,
+        }
+    }
+
+    async fn find_user(&self, user_id: UserId) -> Result<User> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| PlatformError::NotFound("User not found".to_string()))
+    }
+
+    /// `None` leaves a field unchanged, an empty string clears it
+    fn field_change(value: Option<String>) -> Option<Option<String>> {
+        value.map(|value| {
+            let value = value.trim().to_string();
+            (!value.is_empty()).then_some(value)
+        })
+    }
+
+    /// Decode the upload and scale it to fill an `AVATAR_SIZE` square, as PNG
+    fn resize_avatar(data: &[u8]) -> Result<Vec<u8>> {
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(MAX_AVATAR_SOURCE_DIMENSION);
+        limits.max_image_height = Some(MAX_AVATAR_SOURCE_DIMENSION);
+
+        let mut reader = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .map_err(|e| PlatformError::ValidationError(format!("Failed to read avatar image: {}", e)))?;
+        reader.limits(limits);
+        let image = reader
+            .decode()
+            .map_err(|e| PlatformError::ValidationError(format!("Invalid avatar image: {}", e)))?;
+
+        let avatar = image.resize_to_fill(AVATAR_SIZE, AVATAR_SIZE, FilterType::Lanczos3);
+        let mut png = Vec::new();
+        avatar
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .map_err(|e| PlatformError::InternalError(format!("Failed to encode avatar: {}", e)))?;
+
+        Ok(png)
+    }
+}
+
+#[async_trait]
+impl UserApplicationService for UserApplicationServiceImpl {
+    async fn get_profile(&self, user_id: UserId) -> Result<UserProfileDto> {
+        let user = self.find_user(user_id).await?;
+        Ok(UserProfileDto::from(&user))
+    }
+
+    async fn update_profile(&self, user_id: UserId, dto: UpdateProfileDto) -> Result<UserProfileDto> {
+        let mut user = self.find_user(user_id).await?;
+
+        if let Some(nickname) = Self::field_change(dto.nickname) {
+            user.update_nickname(nickname).map_err(PlatformError::ValidationError)?;
+        }
+        if let Some(email) = Self::field_change(dto.email) {
+            user.update_email(email).map_err(PlatformError::ValidationError)?;
+        }
+        if let Some(language) = Self::field_change(dto.language) {
+            user.update_language(language).map_err(PlatformError::ValidationError)?;
+        }
+
+        self.user_repository.save(&user).await?;
+
+        Ok(UserProfileDto::from(&user))
+    }
+
+    async fn upload_avatar(&self, user_id: UserId, file: FileUpload) -> Result<String> {
+        if file.data.is_empty() {
+            return Err(PlatformError::ValidationError("Avatar file is empty".to_string()));
+        }
+        if file.data.len() > MAX_AVATAR_UPLOAD_BYTES {
+            return Err(PlatformError::ValidationError(format!(
+                "Avatar file cannot exceed {} bytes",
+                MAX_AVATAR_UPLOAD_BYTES
+            )));
+        }
+
+        let mut user = self.find_user(user_id).await?;
+
+        // Decoding and resampling are CPU bound
+        let data = file.data;
+        let png = tokio::task::spawn_blocking(move || Self::resize_avatar(&data))
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("Avatar resize task failed: {}", e)))??;
+
+        let url = self
+            .file_repository
+            .store_file(
+                &user.tenant_id.0.to_string(),
+                &user.id.0.to_string(),
+                &Uuid::new_v4().to_string(),
+                "avatar.png",
+                "image/png",
+                png,
+            )
+            .await?;
+
+        user.update_avatar(Some(url.clone()));
+        self.user_repository.save(&user).await?;
+
+        Ok(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{MockFileRepository, MockUserRepository};
+    use crate::domain::value_objects::{TenantId, Username};
+    use image::{DynamicImage, GenericImageView};
+
+    fn user() -> User {
+        User::new(
+            UserId::new(),
+            TenantId::new(),
+            Username::new("alice".to_string()).unwrap(),
+            "hash".to_string(),
+            Some("Alice".to_string()),
+        )
+        .unwrap()
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        DynamicImage::new_rgb8(width, height)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_changes_and_clears_fields() {
+        let user = user();
+        let user_id = user.id;
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        user_repository
+            .expect_save()
+            .times(1)
+            .withf(|user| user.nickname.is_none() && user.language.as_deref() == Some("zh-CN"))
+            .returning(|_| Ok(()));
+
+        let service = UserApplicationServiceImpl::new(
+            Arc::new(user_repository),
+            Arc::new(MockFileRepository::new()),
+        );
+
+        let profile = service
+            .update_profile(
+                user_id,
+                UpdateProfileDto {
+                    nickname: Some(" ".to_string()),
+                    email: Some("alice@example.com".to_string()),
+                    language: Some("zh-CN".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(profile.nickname, None);
+        assert_eq!(profile.email.as_deref(), Some("alice@example.com"));
+        assert_eq!(profile.language.as_deref(), Some("zh-CN"));
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_rejects_invalid_email() {
+        let user = user();
+        let user_id = user.id;
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        user_repository.expect_save().never();
+
+        let service = UserApplicationServiceImpl::new(
+            Arc::new(user_repository),
+            Arc::new(MockFileRepository::new()),
+        );
+
+        let result = service
+            .update_profile(
+                user_id,
+                UpdateProfileDto {
+                    email: Some("not-an-email".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_avatar_stores_resized_png() {
+        let user = user();
+        let user_id = user.id;
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        user_repository
+            .expect_save()
+            .times(1)
+            .withf(|user| user.avatar_url.as_deref() == Some("https://cdn.example.com/avatar.png"))
+            .returning(|_| Ok(()));
+
+        let mut file_repository = MockFileRepository::new();
+        file_repository
+            .expect_store_file()
+            .times(1)
+            .withf(|_, _, _, filename, content_type, data| {
+                let stored = image::load_from_memory(data).unwrap();
+                filename == "avatar.png"
+                    && content_type == "image/png"
+                    && stored.dimensions() == (AVATAR_SIZE, AVATAR_SIZE)
+            })
+            .returning(|_, _, _, _, _, _| Ok("https://cdn.example.com/avatar.png".to_string()));
+
+        let service = UserApplicationServiceImpl::new(Arc::new(user_repository), Arc::new(file_repository));
+
+        let url = service
+            .upload_avatar(
+                user_id,
+                FileUpload {
+                    filename: "me.png".to_string(),
+                    content_type: "image/png".to_string(),
+                    data: png(300, 200),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(url, "https://cdn.example.com/avatar.png");
+    }
+
+    #[tokio::test]
+    async fn test_upload_avatar_rejects_non_image() {
+        let user = user();
+        let user_id = user.id;
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        let mut file_repository = MockFileRepository::new();
+        file_repository.expect_store_file().never();
+
+        let service = UserApplicationServiceImpl::new(Arc::new(user_repository), Arc::new(file_repository));
+
+        let result = service
+            .upload_avatar(
+                user_id,
+                FileUpload {
+                    filename: "notes.txt".to_string(),
+                    content_type: "text/plain".to_string(),
+                    data: b"hello".to_vec(),
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+    }
+}
@@ -5,7 +5,7 @@ use crate::domain::entities::VectorConfigEntity;
 use crate::domain::repositories::VectorConfigRepository;
 use crate::domain::value_objects::{TenantId, ConfigId};
 use crate::error::PlatformError;
-use crate::infrastructure::vector::{VectorProvider, VectorStoreFactory, VectorStore};
+use crate::infrastructure::vector::{VectorProvider, VectorStoreFactory, VectorStore, UPSERT_PARALLELISM_PARAM};
 
 /// Application service for vector configuration management
 pub struct VectorApplicationService {
@@ -262,12 +262,12 @@ impl VectorApplicationService {
                 "collection_name".to_string(),
             ],
             VectorProvider::Weaviate => vec![
-                "base_url".to_string(),
+                "url".to_string(),
                 "class_name".to_string(),
             ],
             VectorProvider::Qdrant => vec![
-                "base_url".to_string(),
-                "collection_name".to_string(),
+                "url".to_string(),
+                "collection".to_string(),
             ],
             VectorProvider::Milvus => vec![
                 "base_url".to_string(),
@@ -278,13 +278,20 @@ impl VectorApplicationService {
     
     /// Get optional parameters for a provider
     pub fn get_optional_params(provider: VectorProvider) -> Vec<String> {
-        match provider {
-            VectorProvider::Pinecone => vec![],
+        let mut params = match provider {
+            VectorProvider::Pinecone => vec!["host".to_string(), "max_batch_size".to_string()],
             VectorProvider::ChromaDB => vec!["api_key".to_string()],
-            VectorProvider::Weaviate => vec!["api_key".to_string()],
+            VectorProvider::Weaviate => vec!["api_key".to_string(), "scheme".to_string()],
             VectorProvider::Qdrant => vec!["api_key".to_string()],
-            VectorProvider::Milvus => vec!["api_key".to_string(), "username".to_string(), "password".to_string()],
-        }
+            VectorProvider::Milvus => vec![
+                "username".to_string(),
+                "password".to_string(),
+                "metric_type".to_string(),
+                "index_threshold".to_string(),
+            ],
+        };
+        params.push(UPSERT_PARALLELISM_PARAM.to_string());
+        params
     }
 }
 
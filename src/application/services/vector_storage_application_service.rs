@@ -1,17 +1,32 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::application::services::VectorApplicationService;
+use crate::application::services::{TenantApplicationService, VectorApplicationService};
+use crate::domain::entities::QuotaResource;
+use crate::domain::repositories::VectorRecordMetadataRepository;
+use crate::domain::services::{VectorStoreDomainService, VectorStoreDomainServiceImpl};
 use crate::domain::value_objects::{
-    TenantId, VectorRecord, SearchQuery, SearchResult, VectorStats, BatchOperation
+    ConfigId, TenantId, VectorRecord, VectorRecordExpiry, SearchQuery, SearchResult, VectorStats,
+    BatchOperation
 };
 use crate::error::PlatformError;
-use crate::infrastructure::vector::VectorStoreRegistry;
+use crate::infrastructure::vector::{VectorStore, VectorStoreFactory, VectorStoreRegistry};
+
+/// Expiry rows to write after an upsert, and ids per namespace of records
+/// rewritten without an expiry whose rows must go
+#[derive(Debug, Default)]
+struct ExpiryChanges {
+    expiring: Vec<VectorRecordExpiry>,
+    cleared: HashMap<Option<String>, Vec<String>>,
+}
 
 /// Application service for vector storage operations
 pub struct VectorStorageApplicationService {
     vector_config_service: Arc<VectorApplicationService>,
     store_registry: Arc<VectorStoreRegistry>,
+    domain_service: Arc<dyn VectorStoreDomainService>,
+    quota_service: Option<Arc<dyn TenantApplicationService>>,
+    expiry_repository: Option<Arc<dyn VectorRecordMetadataRepository>>,
 }
 
 impl VectorStorageApplicationService {
@@ -22,6 +37,92 @@ impl VectorStorageApplicationService {
         Self {
             vector_config_service,
             store_registry,
+            domain_service: Arc::new(VectorStoreDomainServiceImpl::new()),
+            quota_service: None,
+            expiry_repository: None,
+        }
+    }
+    
+    /// Use a custom domain service for tenant isolation rules
+    pub fn with_domain_service(mut self, domain_service: Arc<dyn VectorStoreDomainService>) -> Self {
+        self.domain_service = domain_service;
+        self
+    }
+    
+    /// Check writes to the default store against the tenant's vector record quota
+    pub fn with_quota_service(mut self, quota_service: Arc<dyn TenantApplicationService>) -> Self {
+        self.quota_service = Some(quota_service);
+        self
+    }
+    
+    /// Track expiring records for stores without native TTL so the reaper
+    /// can delete them; without a repository such records never expire there
+    pub fn with_expiry_repository(
+        mut self,
+        expiry_repository: Arc<dyn VectorRecordMetadataRepository>,
+    ) -> Self {
+        self.expiry_repository = Some(expiry_repository);
+        self
+    }
+    
+    async fn default_store(&self, tenant_id: TenantId) -> Result<(ConfigId, Box<dyn VectorStore>), PlatformError> {
+        let config = self.vector_config_service.get_default_config(tenant_id).await?;
+        let store = VectorStoreFactory::create_store(config.to_store_config()).await?;
+        Ok((config.id, store))
+    }
+    
+    /// Expiry changes for records about to be written to `store`; `None` when
+    /// the store expires records itself or nothing is tracked
+    fn expiry_changes(
+        &self,
+        config_id: ConfigId,
+        store: &dyn VectorStore,
+        records: &[VectorRecord],
+    ) -> Option<ExpiryChanges> {
+        if self.expiry_repository.is_none() || store.provider_info().supports_native_ttl {
+            return None;
+        }
+        
+        let mut changes = ExpiryChanges::default();
+        for record in records {
+            match record.expires_at {
+                Some(expires_at) => changes.expiring.push(VectorRecordExpiry {
+                    config_id,
+                    tenant_id: record.tenant_id,
+                    namespace: record.namespace.clone(),
+                    record_id: record.id.clone(),
+                    expires_at,
+                }),
+                None => changes
+                    .cleared
+                    .entry(record.namespace.clone())
+                    .or_default()
+                    .push(record.id.clone()),
+            }
+        }
+        Some(changes)
+    }
+    
+    async fn track_expiry(&self, config_id: ConfigId, changes: Option<ExpiryChanges>) -> Result<(), PlatformError> {
+        let (Some(repository), Some(changes)) = (&self.expiry_repository, changes) else {
+            return Ok(());
+        };
+        
+        repository.save_all(&changes.expiring).await?;
+        for (namespace, record_ids) in changes.cleared {
+            repository.delete(config_id, namespace, record_ids).await?;
+        }
+        Ok(())
+    }
+    
+    async fn ensure_record_quota(&self, tenant_id: TenantId, records: usize) -> Result<(), PlatformError> {
+        match &self.quota_service {
+            Some(quota_service) if records > 0 => {
+                quota_service
+                    .ensure_quota(tenant_id, QuotaResource::VectorRecords, records as u64)
+                    .await
+            }
+            _ => Ok(()),
         }
     }
     
@@ -29,7 +130,7 @@ impl VectorStorageApplicationService {
     pub async fn upsert_vector(
         &self,
         tenant_id: TenantId,
-        record: VectorRecord,
+        mut record: VectorRecord,
     ) -> Result<(), PlatformError> {
         // Validate tenant ID matches record
         if record.tenant_id != tenant_id {
@@ -38,15 +139,20 @@ impl VectorStorageApplicationService {
             ));
         }
         
-        let store = self.vector_config_service.get_default_vector_store(tenant_id).await?;
-        store.upsert(record).await
+        self.ensure_record_quota(tenant_id, 1).await?;
+        
+        self.domain_service.apply_record_isolation(&mut record);
+        let (config_id, store) = self.default_store(tenant_id).await?;
+        let changes = self.expiry_changes(config_id, store.as_ref(), std::slice::from_ref(&record));
+        store.upsert(record).await?;
+        self.track_expiry(config_id, changes).await
     }
     
     /// Store multiple vector records in batch
     pub async fn upsert_vectors_batch(
         &self,
         tenant_id: TenantId,
-        records: Vec<VectorRecord>,
+        mut records: Vec<VectorRecord>,
     ) -> Result<(), PlatformError> {
         // Validate all records belong to the tenant
         for record in &records {
@@ -57,16 +163,25 @@ impl VectorStorageApplicationService {
             }
         }
         
-        let store = self.vector_config_service.get_default_vector_store(tenant_id).await?;
-        store.upsert_batch(records).await
+        self.ensure_record_quota(tenant_id, records.len()).await?;
+        
+        for record in &mut records {
+            self.domain_service.apply_record_isolation(record);
+        }
+        
+        let (config_id, store) = self.default_store(tenant_id).await?;
+        let changes = self.expiry_changes(config_id, store.as_ref(), &records);
+        store.upsert_batch(records).await?;
+        self.track_expiry(config_id, changes).await
     }
     
     /// Search for similar vectors using the default vector store
     pub async fn search_vectors(
         &self,
         tenant_id: TenantId,
-        query: SearchQuery,
+        mut query: SearchQuery,
     ) -> Result<Vec<SearchResult>, PlatformError> {
+        self.domain_service.apply_query_isolation(&mut query, tenant_id)?;
         let store = self.vector_config_service.get_default_vector_store(tenant_id).await?;
         store.query(query).await
     }
@@ -78,15 +193,20 @@ impl VectorStorageApplicationService {
         ids: Vec<String>,
         namespace: Option<String>,
     ) -> Result<(), PlatformError> {
-        let store = self.vector_config_service.get_default_vector_store(tenant_id).await?;
-        store.delete(ids, namespace).await
+        let (config_id, store) = self.default_store(tenant_id).await?;
+        store.delete(ids.clone(), namespace.clone()).await?;
+        
+        match &self.expiry_repository {
+            Some(repository) => repository.delete(config_id, namespace, ids).await,
+            None => Ok(()),
+        }
     }
     
     /// Execute batch operations (upsert and delete)
     pub async fn execute_batch_operation(
         &self,
         tenant_id: TenantId,
-        operation: BatchOperation,
+        mut operation: BatchOperation,
     ) -> Result<(), PlatformError> {
         // Validate all upsert records belong to the tenant
         for record in &operation.upsert {
@@ -97,8 +217,17 @@ impl VectorStorageApplicationService {
             }
         }
         
-        let store = self.vector_config_service.get_default_vector_store(tenant_id).await?;
-        store.execute_batch(operation).await
+        self.ensure_record_quota(tenant_id, operation.upsert.len()).await?;
+        
+        operation.apply_expiry();
+        for record in &mut operation.upsert {
+            self.domain_service.apply_record_isolation(record);
+        }
+        
+        let (config_id, store) = self.default_store(tenant_id).await?;
+        let changes = self.expiry_changes(config_id, store.as_ref(), &operation.upsert);
+        store.execute_batch(operation).await?;
+        self.track_expiry(config_id, changes).await
     }
     
     /// Get vector storage statistics for the tenant
@@ -115,8 +244,8 @@ impl VectorStorageApplicationService {
     pub async fn upsert_vector_with_config(
         &self,
         tenant_id: TenantId,
-        config_id: crate::domain::value_objects::ConfigId,
-        record: VectorRecord,
+        config_id: ConfigId,
+        mut record: VectorRecord,
     ) -> Result<(), PlatformError> {
         // Validate tenant ID matches record
         if record.tenant_id != tenant_id {
@@ -125,16 +254,19 @@ impl VectorStorageApplicationService {
             ));
         }
         
+        self.domain_service.apply_record_isolation(&mut record);
         let store = self.vector_config_service.get_vector_store(config_id).await?;
-        store.upsert(record).await
+        let changes = self.expiry_changes(config_id, store.as_ref(), std::slice::from_ref(&record));
+        store.upsert(record).await?;
+        self.track_expiry(config_id, changes).await
     }
     
     /// Search vectors using a specific configuration
     pub async fn search_vectors_with_config(
         &self,
         tenant_id: TenantId,
-        config_id: crate::domain::value_objects::ConfigId,
-        query: SearchQuery,
+        config_id: ConfigId,
+        mut query: SearchQuery,
     ) -> Result<Vec<SearchResult>, PlatformError> {
         // Verify the config belongs to the tenant
         let config = self.vector_config_service.get_config(config_id).await?;
@@ -144,6 +276,7 @@ impl VectorStorageApplicationService {
             ));
         }
         
+        self.domain_service.apply_query_isolation(&mut query, tenant_id)?;
         let store = self.vector_config_service.get_vector_store(config_id).await?;
         store.query(query).await
     }
@@ -199,9 +332,10 @@ impl VectorStorageApplicationService {
     pub async fn multi_store_search(
         &self,
         tenant_id: TenantId,
-        query: SearchQuery,
+        mut query: SearchQuery,
         max_results_per_store: usize,
     ) -> Result<HashMap<String, Vec<SearchResult>>, PlatformError> {
+        self.domain_service.apply_query_isolation(&mut query, tenant_id)?;
         let configs = self.vector_config_service.get_configs_by_tenant(tenant_id).await?;
         let mut results = HashMap::new();
         
@@ -217,13 +351,13 @@ impl VectorStorageApplicationService {
                             results.insert(config.name, store_results);
                         }
                         Err(e) => {
-                            log::warn!("Failed to search in store '{}': {}", config.name, e);
+                            tracing::warn!("Failed to search in store '{}': {}", config.name, e);
                             // Continue with other stores
                         }
                     }
                 }
                 Err(e) => {
-                    log::warn!("Failed to get store '{}': {}", config.name, e);
+                    tracing::warn!("Failed to get store '{}': {}", config.name, e);
                     // Continue with other stores
                 }
             }
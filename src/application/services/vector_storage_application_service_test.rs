@@ -136,6 +136,7 @@ impl VectorStore for MockVectorStore {
             dimension: 3, // Mock dimension
             index_fullness: 0.5,
             namespace_stats,
+            index_state: None,
         })
     }
     
@@ -153,6 +154,7 @@ impl VectorStore for MockVectorStore {
             supports_namespaces: true,
             supports_metadata_filtering: true,
             supports_hybrid_search: false,
+            supports_native_ttl: false,
             max_vector_dimension: 1536,
             max_batch_size: 100,
         }
@@ -472,4 +474,48 @@ mod tests {
         let empty_batch = BatchOperation::new();
         assert!(empty_batch.is_empty());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_broadcast_upsert_reports_each_store() {
+        let tenant_id = TenantId::new();
+        let mut registry = VectorStoreRegistry::new();
+        registry.register_store("old".to_string(), Box::new(MockVectorStore::new()));
+        registry.register_store("new".to_string(), Box::new(MockVectorStore::new().with_failure()));
+        
+        let results = registry
+            .broadcast_upsert(
+                create_test_vector_record(tenant_id, "vector_1"),
+                vec!["old".to_string(), "new".to_string(), "missing".to_string(), "old".to_string()],
+            )
+            .await;
+        
+        assert_eq!(results.len(), 3);
+        assert!(results["old"].is_ok());
+        assert!(matches!(results["new"], Err(PlatformError::VectorStoreError(_))));
+        assert!(matches!(results["missing"], Err(PlatformError::NotFound(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_broadcast_query_deduplicates_by_id() {
+        let tenant_id = TenantId::new();
+        let old_store = MockVectorStore::new();
+        let new_store = MockVectorStore::new();
+        for store in [&old_store, &new_store] {
+            store.upsert(create_test_vector_record(tenant_id, "shared")).await.unwrap();
+        }
+        new_store.upsert(create_test_vector_record(tenant_id, "only_new")).await.unwrap();
+        
+        let mut registry = VectorStoreRegistry::new();
+        registry.register_store("old".to_string(), Box::new(old_store));
+        registry.register_store("new".to_string(), Box::new(new_store));
+        registry.register_store("broken".to_string(), Box::new(MockVectorStore::new().with_failure()));
+        
+        let query = SearchQuery::new(vec![1.0, 2.0, 3.0], 10).unwrap();
+        let results = registry.broadcast_query(query).await.unwrap();
+        
+        let mut ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["only_new", "shared"]);
+        assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    }
+}
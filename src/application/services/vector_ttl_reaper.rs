@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::application::services::VectorApplicationService;
+use crate::domain::repositories::VectorRecordMetadataRepository;
+use crate::domain::value_objects::ConfigId;
+use crate::error::{PlatformError, Result};
+
+/// Expired records read per pass
+const REAP_BATCH_SIZE: u64 = 500;
+
+/// Deletes expired records from vector stores that cannot expire them
+/// natively, using the expiries tracked in `vector_record_metadata`
+pub struct VectorTTLReaper {
+    expiry_repository: Arc<dyn VectorRecordMetadataRepository>,
+    vector_config_service: Arc<VectorApplicationService>,
+}
+
+impl VectorTTLReaper {
+    pub fn new(
+        expiry_repository: Arc<dyn VectorRecordMetadataRepository>,
+        vector_config_service: Arc<VectorApplicationService>,
+    ) -> Self {
+        Self {
+            expiry_repository,
+            vector_config_service,
+        }
+    }
+
+    /// Delete every record that expired at or before `now` and return how
+    /// many were removed. Records of a store that fails are kept for the next run.
+    pub async fn reap(&self, now: DateTime<Utc>) -> Result<u64> {
+        let mut reaped = 0;
+
+        loop {
+            let expired = self.expiry_repository.find_expired(now, REAP_BATCH_SIZE).await?;
+            let exhausted = (expired.len() as u64) < REAP_BATCH_SIZE;
+
+            let mut groups: HashMap<(ConfigId, Option<String>), Vec<String>> = HashMap::new();
+            for expiry in expired {
+                groups
+                    .entry((expiry.config_id, expiry.namespace))
+                    .or_default()
+                    .push(expiry.record_id);
+            }
+
+            let mut failed = false;
+            for ((config_id, namespace), record_ids) in groups {
+                let count = record_ids.len() as u64;
+                match self.delete_records(config_id, namespace, record_ids).await {
+                    Ok(()) => reaped += count,
+                    Err(e) => {
+                        tracing::warn!(config_id = %config_id.0, "Failed to delete expired vector records: {}", e);
+                        failed = true;
+                    }
+                }
+            }
+
+            // Failed rows would be read again; leave them to the next run
+            if exhausted || failed {
+                return Ok(reaped);
+            }
+        }
+    }
+
+    async fn delete_records(
+        &self,
+        config_id: ConfigId,
+        namespace: Option<String>,
+        record_ids: Vec<String>,
+    ) -> Result<()> {
+        match self.vector_config_service.get_vector_store(config_id).await {
+            Ok(store) => store.delete(record_ids.clone(), namespace.clone()).await?,
+            // The configuration is gone along with its records
+            Err(PlatformError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        self.expiry_repository.delete(config_id, namespace, record_ids).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{MockVectorConfigRepository, MockVectorRecordMetadataRepository};
+    use crate::domain::value_objects::{TenantId, VectorRecordExpiry};
+
+    #[tokio::test]
+    async fn test_reap_drops_rows_of_deleted_configs() {
+        let config_id = ConfigId::new();
+        let now = Utc::now();
+
+        let mut expiry_repository = MockVectorRecordMetadataRepository::new();
+        expiry_repository.expect_find_expired().times(1).returning(move |_, _| {
+            Ok(vec![VectorRecordExpiry {
+                config_id,
+                tenant_id: TenantId::new(),
+                namespace: None,
+                record_id: "doc-1".to_string(),
+                expires_at: now,
+            }])
+        });
+        expiry_repository
+            .expect_delete()
+            .withf(move |id, namespace, record_ids| {
+                *id == config_id && namespace.is_none() && record_ids == &vec!["doc-1".to_string()]
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let mut config_repository = MockVectorConfigRepository::new();
+        config_repository.expect_find_by_id().returning(|_| Ok(None));
+
+        let reaper = VectorTTLReaper::new(
+            Arc::new(expiry_repository),
+            Arc::new(VectorApplicationService::new(Arc::new(config_repository))),
+        );
+
+        assert_eq!(reaper.reap(now).await.unwrap(), 1);
+    }
+}
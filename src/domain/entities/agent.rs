@@ -1,8 +1,33 @@
-use crate::domain::value_objects::{AgentId, ConfigId, FlowId, MCPToolId, TenantId, UserId};
+use crate::domain::value_objects::{
+    AgentId, ConfigId, FlowId, LLMSelectionStrategy, MCPToolId, TenantId, UserId,
+};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+/// System prompts longer than this still validate but are worth a warning,
+/// since they eat into the context left for the conversation
+pub const SYSTEM_PROMPT_WARNING_LENGTH: usize = 8_000;
+
+/// Most preset questions an agent can have
+pub const MAX_PRESET_QUESTIONS: usize = 3;
+
+/// Size limits applied when validating agents. Tenants may override them
+/// through their settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentLimitsConfig {
+    /// Longest allowed system prompt, in characters
+    pub max_system_prompt_length: usize,
+}
+
+impl Default for AgentLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_system_prompt_length: 32_000,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Agent {
     pub id: AgentId,
@@ -10,7 +35,10 @@ pub struct Agent {
     pub name: String,
     pub avatar: Option<String>,
     pub greeting: Option<String>,
+    /// Preferred LLM config, tried before `llm_fallback_strategy`
     pub llm_config_id: Option<ConfigId>,
+    #[serde(default)]
+    pub llm_fallback_strategy: LLMSelectionStrategy,
     pub knowledge_base_ids: Vec<ConfigId>,
     pub mcp_tool_ids: Vec<MCPToolId>,
     pub flow_ids: Vec<FlowId>,
@@ -57,6 +85,7 @@ impl Agent {
             avatar: None,
             greeting: None,
             llm_config_id: None,
+            llm_fallback_strategy: LLMSelectionStrategy::default(),
             knowledge_base_ids: Vec::new(),
             mcp_tool_ids: Vec::new(),
             flow_ids: Vec::new(),
@@ -103,6 +132,15 @@ impl Agent {
         self.updated_at = Utc::now();
     }
 
+    pub fn preferred_llm_config_id(&self) -> Option<ConfigId> {
+        self.llm_config_id
+    }
+
+    pub fn update_llm_fallback_strategy(&mut self, strategy: LLMSelectionStrategy) {
+        self.llm_fallback_strategy = strategy;
+        self.updated_at = Utc::now();
+    }
+
     pub fn update_system_prompt(&mut self, prompt: String) -> Result<(), String> {
         if prompt.trim().is_empty() {
             return Err("System prompt cannot be empty".to_string());
@@ -135,8 +173,8 @@ impl Agent {
     }
 
     pub fn set_preset_questions(&mut self, questions: Vec<String>) -> Result<(), String> {
-        if questions.len() > 3 {
-            return Err("Preset questions cannot exceed 3 items".to_string());
+        if questions.len() > MAX_PRESET_QUESTIONS {
+            return Err(format!("Preset questions cannot exceed {} items", MAX_PRESET_QUESTIONS));
         }
 
         self.preset_questions = questions;
@@ -208,6 +246,7 @@ impl Agent {
             avatar: self.avatar.clone(),
             greeting: self.greeting.clone(),
             llm_config_id: self.llm_config_id,
+            llm_fallback_strategy: self.llm_fallback_strategy.clone(),
             knowledge_base_ids: self.knowledge_base_ids.clone(),
             mcp_tool_ids: self.mcp_tool_ids.clone(),
             flow_ids: self.flow_ids.clone(),
@@ -236,6 +275,7 @@ impl Agent {
             avatar: self.avatar.clone(),
             greeting: self.greeting.clone(),
             llm_config_id: self.llm_config_id,
+            llm_fallback_strategy: self.llm_fallback_strategy.clone(),
             knowledge_base_ids: self.knowledge_base_ids.clone(),
             mcp_tool_ids: self.mcp_tool_ids.clone(),
             flow_ids: self.flow_ids.clone(),
@@ -317,7 +357,33 @@ impl Agent {
         Ok(())
     }
 
+    pub fn validate_system_prompt_length(&self, max_length: usize) -> Result<(), String> {
+        let length = self.system_prompt.chars().count();
+        if length > max_length {
+            return Err(format!(
+                "System prompt cannot exceed {} characters (got {})",
+                max_length, length
+            ));
+        }
+        Ok(())
+    }
+
+    /// Warning for a system prompt that is valid but unusually long
+    pub fn system_prompt_warning(&self) -> Option<String> {
+        let length = self.system_prompt.chars().count();
+        (length > SYSTEM_PROMPT_WARNING_LENGTH).then(|| {
+            format!(
+                "System prompt is {} characters long; prompts over {} characters leave less room for the conversation",
+                length, SYSTEM_PROMPT_WARNING_LENGTH
+            )
+        })
+    }
+
     pub fn validate(&self) -> Result<(), String> {
+        self.validate_with_limits(&AgentLimitsConfig::default())
+    }
+
+    pub fn validate_with_limits(&self, limits: &AgentLimitsConfig) -> Result<(), String> {
         // Validate name
         if self.name.trim().is_empty() {
             return Err("Agent name cannot be empty".to_string());
@@ -330,12 +396,52 @@ impl Agent {
         if self.system_prompt.trim().is_empty() {
             return Err("System prompt cannot be empty".to_string());
         }
+        self.validate_system_prompt_length(limits.max_system_prompt_length)?;
 
         // Validate preset questions count
-        if self.preset_questions.len() > 3 {
-            return Err("Preset questions cannot exceed 3 items".to_string());
+        if self.preset_questions.len() > MAX_PRESET_QUESTIONS {
+            return Err(format!("Preset questions cannot exceed {} items", MAX_PRESET_QUESTIONS));
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent_with_prompt(len: usize) -> Agent {
+        Agent::new(TenantId::new(), "Agent".to_string(), "a".repeat(len), UserId::new()).unwrap()
+    }
+
+    #[test]
+    fn test_validate_rejects_system_prompt_over_limit() {
+        let limits = AgentLimitsConfig { max_system_prompt_length: 100 };
+
+        assert!(agent_with_prompt(100).validate_with_limits(&limits).is_ok());
+        let err = agent_with_prompt(101).validate_with_limits(&limits).unwrap_err();
+        assert!(err.contains("cannot exceed 100 characters"));
+
+        assert!(agent_with_prompt(32_000).validate().is_ok());
+        assert!(agent_with_prompt(32_001).validate().is_err());
+    }
+
+    #[test]
+    fn test_system_prompt_length_counts_characters() {
+        let mut agent = agent_with_prompt(1);
+        agent.system_prompt = "提示".repeat(50);
+
+        assert!(agent.validate_system_prompt_length(100).is_ok());
+        assert!(agent.validate_system_prompt_length(99).is_err());
+    }
+
+    #[test]
+    fn test_long_system_prompt_warns_without_failing() {
+        assert!(agent_with_prompt(SYSTEM_PROMPT_WARNING_LENGTH).system_prompt_warning().is_none());
+
+        let agent = agent_with_prompt(SYSTEM_PROMPT_WARNING_LENGTH + 1);
+        assert!(agent.system_prompt_warning().is_some());
+        assert!(agent.validate().is_ok());
+    }
+}
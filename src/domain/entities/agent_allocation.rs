@@ -7,6 +7,8 @@ pub struct AgentAllocation {
     pub agent_id: AgentId,
     pub user_id: UserId,
     pub allocated_at: DateTime<Utc>,
+    /// The allocation lapses at this time; never if unset
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl AgentAllocation {
@@ -15,6 +17,33 @@ impl AgentAllocation {
             agent_id,
             user_id,
             allocated_at: Utc::now(),
+            expires_at: None,
         }
     }
+
+    pub fn with_expiry(mut self, expires_at: Option<DateTime<Utc>>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.map_or(true, |expires_at| expires_at > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_allocation_expiry() {
+        let now = Utc::now();
+        let allocation = AgentAllocation::new(AgentId::new(), UserId::new());
+        assert!(allocation.is_active_at(now));
+
+        let allocation = allocation.with_expiry(Some(now + Duration::hours(1)));
+        assert!(allocation.is_active_at(now));
+        assert!(!allocation.is_active_at(now + Duration::hours(1)));
+    }
 }
@@ -75,7 +75,7 @@ impl AgentDailyStats {
     }
 
     pub fn add_revenue(&mut self, amount: Decimal) {
-        self.revenue += amount;
+        self.revenue = self.revenue.saturating_add(amount);
         self.updated_at = Utc::now();
     }
 
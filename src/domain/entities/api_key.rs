@@ -1,6 +1,6 @@
 use crate::domain::value_objects::{APIKeyId, PermissionScope, ResourceType, TenantId, UserId};
 use crate::error::PlatformError;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -16,10 +16,35 @@ pub struct APIKey {
     pub enabled: bool,
     pub expires_at: Option<DateTime<Utc>>,
     pub last_used_at: Option<DateTime<Utc>>,
+    /// Requests made with the key, as of the last usage flush
+    pub request_count: u64,
+    /// LLM tokens consumed through the key, as of the last usage flush
+    pub total_tokens_used: u64,
+    /// Requests allowed per minute; `None` is unlimited
+    pub rate_limit_rpm: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Requests and tokens of one API key on one UTC day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct APIKeyDailyUsage {
+    pub date: NaiveDate,
+    pub request_count: u64,
+    pub tokens_used: u64,
+}
+
+/// Usage of one API key on one UTC day that has not been written to the
+/// database yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct APIKeyUsageDelta {
+    pub api_key_id: APIKeyId,
+    pub date: NaiveDate,
+    pub request_count: u64,
+    pub tokens_used: u64,
+    pub last_used_at: DateTime<Utc>,
+}
+
 impl APIKey {
     /// Create a new API key
     pub fn new(
@@ -73,6 +98,9 @@ impl APIKey {
             enabled: true,
             expires_at,
             last_used_at: None,
+            request_count: 0,
+            total_tokens_used: 0,
+            rate_limit_rpm: None,
             created_at: now,
             updated_at: now,
         })
@@ -123,6 +151,12 @@ impl APIKey {
         self.updated_at = Utc::now();
     }
 
+    /// Replace the stored token hash; the previous token stops validating
+    pub fn rotate(&mut self, key_hash: String) {
+        self.key_hash = key_hash;
+        self.updated_at = Utc::now();
+    }
+
     /// Update the API key name
     pub fn update_name(&mut self, name: String) -> Result<(), PlatformError> {
         if name.trim().is_empty() {
@@ -141,6 +175,19 @@ impl APIKey {
         Ok(())
     }
 
+    /// Set the per-minute request limit; `None` removes it
+    pub fn update_rate_limit(&mut self, rate_limit_rpm: Option<u32>) -> Result<(), PlatformError> {
+        if rate_limit_rpm == Some(0) {
+            return Err(PlatformError::ValidationError(
+                "API key rate limit must be at least 1 request per minute".to_string()
+            ));
+        }
+
+        self.rate_limit_rpm = rate_limit_rpm;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     /// Update the expiration date
     pub fn update_expiration(&mut self, expires_at: Option<DateTime<Utc>>) -> Result<(), PlatformError> {
         if let Some(expires) = expires_at {
@@ -404,4 +451,29 @@ mod tests {
         assert!(!api_key.belongs_to_tenant(&other_tenant_id));
         assert!(!api_key.belongs_to_user(&other_user_id));
     }
+
+    #[test]
+    fn test_api_key_rate_limit() {
+        let token = APIKeyToken::generate().unwrap();
+        let mut api_key = APIKey::new(
+            TenantId::new(),
+            UserId::new(),
+            "Test API Key".to_string(),
+            token.hash(),
+            PermissionScope::empty(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(api_key.rate_limit_rpm, None);
+
+        api_key.update_rate_limit(Some(60)).unwrap();
+        assert_eq!(api_key.rate_limit_rpm, Some(60));
+
+        assert!(api_key.update_rate_limit(Some(0)).is_err());
+        assert_eq!(api_key.rate_limit_rpm, Some(60));
+
+        api_key.update_rate_limit(None).unwrap();
+        assert_eq!(api_key.rate_limit_rpm, None);
+    }
 }
@@ -54,6 +54,8 @@ pub struct FlowExecutionHistory {
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub execution_time_ms: Option<i32>,
+    #[serde(default)]
+    pub correlation_id: Option<Uuid>,
 }
 
 impl FlowExecutionHistory {
@@ -79,9 +81,15 @@ impl FlowExecutionHistory {
             started_at: Utc::now(),
             completed_at: None,
             execution_time_ms: None,
+            correlation_id: None,
         }
     }
 
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
     pub fn start(&mut self) {
         self.status = ExecutionStatus::Running;
         self.started_at = Utc::now();
@@ -219,6 +227,45 @@ impl ExecutionStep {
     }
 }
 
+/// One run of a flow over several input sets. The individual executions
+/// carry the batch ID as their correlation ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchExecution {
+    pub id: Uuid,
+    pub flow_id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub total: u32,
+    pub succeeded: u32,
+    pub failed: u32,
+    pub concurrency: u32,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl BatchExecution {
+    pub fn new(flow_id: Uuid, tenant_id: Uuid, user_id: Uuid, total: u32, concurrency: u32) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            flow_id,
+            tenant_id,
+            user_id,
+            total,
+            succeeded: 0,
+            failed: 0,
+            concurrency,
+            started_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+
+    pub fn complete(&mut self, succeeded: u32, failed: u32) {
+        self.succeeded = succeeded;
+        self.failed = failed;
+        self.completed_at = Some(Utc::now());
+    }
+}
+
 /// Performance metrics for execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionMetrics {
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
 use crate::domain::value_objects::{FlowId, TenantId, UserId, FlowName, FlowDefinition, Version, SessionId, FlowExecutionId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -18,11 +19,26 @@ pub struct Flow {
     pub description: Option<String>,
     pub current_version: Version,
     pub status: FlowStatus,
+    /// Execution time limit; the engine default applies when unset
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Published flows are locked; changes go to a clone instead
+    #[serde(default)]
+    pub is_published: bool,
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    /// Disabled flows keep their definition but reject new executions
+    #[serde(default = "default_enabled")]
+    pub is_enabled: bool,
     pub created_by: UserId,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlowVersion {
     pub id: FlowId,
@@ -49,6 +65,10 @@ impl Flow {
             description,
             current_version: Version::new(),
             status: FlowStatus::Draft,
+            timeout_ms: None,
+            is_published: false,
+            published_at: None,
+            is_enabled: true,
             created_by,
             created_at: now,
             updated_at: now,
@@ -65,6 +85,11 @@ impl Flow {
         self.updated_at = Utc::now();
     }
 
+    pub fn update_timeout(&mut self, timeout_ms: Option<u64>) {
+        self.timeout_ms = timeout_ms;
+        self.updated_at = Utc::now();
+    }
+
     pub fn activate(&mut self) -> Result<(), String> {
         match self.status {
             FlowStatus::Draft => {
@@ -88,6 +113,45 @@ impl Flow {
         }
     }
 
+    pub fn publish(&mut self) -> Result<(), String> {
+        if self.is_published {
+            return Err("Flow is already published".to_string());
+        }
+        let now = Utc::now();
+        self.is_published = true;
+        self.published_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn unpublish(&mut self) -> Result<(), String> {
+        if !self.is_published {
+            return Err("Flow is not published".to_string());
+        }
+        self.is_published = false;
+        self.published_at = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn enable(&mut self) -> Result<(), String> {
+        if self.is_enabled {
+            return Err("Flow is already enabled".to_string());
+        }
+        self.is_enabled = true;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn disable(&mut self) -> Result<(), String> {
+        if !self.is_enabled {
+            return Err("Flow is already disabled".to_string());
+        }
+        self.is_enabled = false;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     pub fn increment_version(&mut self) {
         self.current_version = self.current_version.next();
         self.updated_at = Utc::now();
@@ -182,6 +246,8 @@ pub struct FlowExecution {
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub execution_time_ms: Option<i32>,
+    #[serde(default)]
+    pub correlation_id: Option<Uuid>,
 }
 
 impl FlowExecution {
@@ -207,9 +273,16 @@ impl FlowExecution {
             started_at: Utc::now(),
             completed_at: None,
             execution_time_ms: None,
+            correlation_id: None,
         }
     }
 
+    /// Link this execution to audit events sharing the same correlation ID
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
     pub fn start(&mut self) {
         self.status = FlowExecutionStatus::Running;
         self.started_at = Utc::now();
@@ -235,6 +308,13 @@ impl FlowExecution {
         self.calculate_execution_time();
     }
 
+    /// Cancel an execution that ran out of time, keeping what it produced
+    pub fn time_out(&mut self, error_message: String, partial_output: Value) {
+        self.output_data = Some(partial_output);
+        self.error_message = Some(error_message);
+        self.cancel();
+    }
+
     fn calculate_execution_time(&mut self) {
         if let Some(completed_at) = self.completed_at {
             let duration = completed_at.signed_duration_since(self.started_at);
@@ -254,6 +334,10 @@ impl FlowExecution {
         matches!(self.status, FlowExecutionStatus::Failed)
     }
 
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.status, FlowExecutionStatus::Cancelled)
+    }
+
     pub fn is_terminal(&self) -> bool {
         matches!(
             self.status,
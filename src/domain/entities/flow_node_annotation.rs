@@ -0,0 +1,91 @@
+use crate::domain::value_objects::{FlowId, FlowNodeAnnotationId, UserId, Version};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Review comment attached to a node of a flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowNodeAnnotation {
+    pub id: FlowNodeAnnotationId,
+    pub flow_id: FlowId,
+    pub node_id: String,
+    pub version: Version,
+    pub author_id: UserId,
+    pub content: String,
+    pub resolved: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FlowNodeAnnotation {
+    pub fn new(
+        flow_id: FlowId,
+        node_id: String,
+        version: Version,
+        author_id: UserId,
+        content: String,
+    ) -> Result<Self, String> {
+        if node_id.trim().is_empty() {
+            return Err("Node ID cannot be empty".to_string());
+        }
+        if content.trim().is_empty() {
+            return Err("Annotation content cannot be empty".to_string());
+        }
+        if content.len() > 5000 {
+            return Err("Annotation content cannot exceed 5000 characters".to_string());
+        }
+
+        Ok(Self {
+            id: FlowNodeAnnotationId::new(),
+            flow_id,
+            node_id,
+            version,
+            author_id,
+            content: content.trim().to_string(),
+            resolved: false,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn resolve(&mut self) -> Result<(), String> {
+        if self.resolved {
+            return Err("Annotation is already resolved".to_string());
+        }
+        self.resolved = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_and_resolve_annotation() {
+        let mut annotation = FlowNodeAnnotation::new(
+            FlowId::new(),
+            "llm_1".to_string(),
+            Version::new(),
+            UserId::new(),
+            "  Prompt is missing the user question  ".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(annotation.content, "Prompt is missing the user question");
+        assert!(!annotation.resolved);
+
+        annotation.resolve().unwrap();
+        assert!(annotation.resolved);
+        assert!(annotation.resolve().is_err());
+    }
+
+    #[test]
+    fn test_empty_content_rejected() {
+        let result = FlowNodeAnnotation::new(
+            FlowId::new(),
+            "llm_1".to_string(),
+            Version::new(),
+            UserId::new(),
+            "   ".to_string(),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -12,6 +12,28 @@ pub enum InterviewStatus {
     Cancelled,
 }
 
+impl InterviewStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterviewStatus::Pending => "pending",
+            InterviewStatus::InProgress => "in_progress",
+            InterviewStatus::Passed => "passed",
+            InterviewStatus::Failed => "failed",
+            InterviewStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// A candidate's answer to one interview question and its evaluation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterviewAnswer {
+    pub question: String,
+    pub answer: String,
+    /// 0 to 10
+    pub score: i32,
+    pub feedback: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterviewRecord {
     pub id: Uuid,
@@ -82,6 +104,33 @@ impl InterviewRecord {
         self.updated_at = Utc::now();
     }
 
+    /// Interview questions in the order they are asked
+    pub fn question_list(&self) -> Vec<String> {
+        self.questions
+            .clone()
+            .and_then(|questions| serde_json::from_value(questions).ok())
+            .unwrap_or_default()
+    }
+
+    /// Answers recorded so far, one per asked question
+    pub fn recorded_answers(&self) -> Vec<InterviewAnswer> {
+        self.answers
+            .clone()
+            .and_then(|answers| serde_json::from_value(answers).ok())
+            .unwrap_or_default()
+    }
+
+    /// The next unanswered question, if any
+    pub fn current_question(&self) -> Option<String> {
+        self.question_list().into_iter().nth(self.recorded_answers().len())
+    }
+
+    pub fn record_answer(&mut self, answer: InterviewAnswer) {
+        let mut answers = self.recorded_answers();
+        answers.push(answer);
+        self.set_answers(serde_json::json!(answers));
+    }
+
     pub fn is_completed(&self) -> bool {
         matches!(self.status, InterviewStatus::Passed | InterviewStatus::Failed)
     }
@@ -98,3 +147,26 @@ impl InterviewRecord {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_advance_current_question() {
+        let mut record = InterviewRecord::new(AgentId::new(), TenantId::new(), None);
+        assert_eq!(record.current_question(), None);
+
+        record.set_questions(serde_json::json!(["Q1", "Q2"]));
+        assert_eq!(record.current_question(), Some("Q1".to_string()));
+
+        record.record_answer(InterviewAnswer {
+            question: "Q1".to_string(),
+            answer: "A1".to_string(),
+            score: 7,
+            feedback: "Good".to_string(),
+        });
+        assert_eq!(record.current_question(), Some("Q2".to_string()));
+        assert_eq!(record.recorded_answers()[0].score, 7);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::domain::value_objects::{AgentId, SessionId, TenantId, UserId};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Token usage of a single LLM request, attributed to the agent that made it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmUsageLog {
+    pub id: Uuid,
+    pub tenant_id: TenantId,
+    pub agent_id: AgentId,
+    pub user_id: UserId,
+    pub session_id: Option<SessionId>,
+    pub model: String,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    /// True when the provider reported no usage and tokens were counted locally
+    pub estimated: bool,
+    pub cost: Decimal,
+    pub usage_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LlmUsageLog {
+    pub fn new(
+        tenant_id: TenantId,
+        agent_id: AgentId,
+        user_id: UserId,
+        session_id: Option<SessionId>,
+        model: String,
+        prompt_tokens: i64,
+        completion_tokens: i64,
+        estimated: bool,
+    ) -> Self {
+        let now = Utc::now();
+
+        LlmUsageLog {
+            id: Uuid::new_v4(),
+            tenant_id,
+            agent_id,
+            user_id,
+            session_id,
+            model,
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+            estimated,
+            cost: Decimal::ZERO,
+            usage_date: now.date_naive(),
+            created_at: now,
+        }
+    }
+
+    /// Price the request at `price_per_1k` per thousand total tokens
+    pub fn with_price_per_1k(mut self, price_per_1k: Decimal) -> Self {
+        self.cost = price_per_1k * Decimal::from(self.total_tokens) / Decimal::from(1000);
+        self
+    }
+}
+
+/// Usage of one agent summed over a day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyTokenUsage {
+    pub usage_date: NaiveDate,
+    pub request_count: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    pub cost: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_sums_tokens_and_prices_per_thousand() {
+        let log = LlmUsageLog::new(
+            TenantId::new(),
+            AgentId::new(),
+            UserId::new(),
+            None,
+            "gpt-4".to_string(),
+            500,
+            1500,
+            true,
+        )
+        .with_price_per_1k(Decimal::new(2, 0));
+
+        assert_eq!(log.total_tokens, 2000);
+        assert_eq!(log.cost, Decimal::new(4, 0));
+        assert_eq!(log.usage_date, log.created_at.date_naive());
+    }
+}
@@ -12,6 +12,9 @@ pub mod agent;
 pub mod agent_allocation;
 pub mod agent_daily_stats;
 pub mod interview_record;
+pub mod flow_node_annotation;
+pub mod llm_usage_log;
+pub mod refresh_token;
 mod api_key;
 
 pub use user::*;
@@ -28,4 +31,7 @@ pub use agent::*;
 pub use agent_allocation::*;
 pub use agent_daily_stats::*;
 pub use interview_record::*;
-pub use api_key::APIKey;
\ No newline at end of file
+pub use flow_node_annotation::*;
+pub use llm_usage_log::*;
+pub use refresh_token::*;
+pub use api_key::{APIKey, APIKeyDailyUsage, APIKeyUsageDelta};
\ No newline at end of file
@@ -0,0 +1,131 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::value_objects::{TenantId, UserId};
+use crate::error::PlatformError;
+
+/// Lifetimes of the tokens handed out by the refresh flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RefreshTokenConfig {
+    pub ttl_days: u32,
+    /// Lifetime of access tokens issued in exchange for a refresh token
+    pub access_token_ttl_minutes: u32,
+    /// Replace the refresh token on every use, revoking the old one
+    pub rotate: bool,
+}
+
+impl Default for RefreshTokenConfig {
+    fn default() -> Self {
+        Self {
+            ttl_days: 30,
+            access_token_ttl_minutes: 15,
+            rotate: true,
+        }
+    }
+}
+
+/// Long-lived token that can be exchanged for new access tokens.
+/// Only the SHA-256 hash of the token is stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub tenant_id: TenantId,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RefreshToken {
+    const PREFIX: &'static str = "rt_";
+    const TOKEN_BYTES: usize = 32;
+
+    /// Create a refresh token, returning it along with the plaintext token,
+    /// which is never stored
+    pub fn issue(
+        user_id: UserId,
+        tenant_id: TenantId,
+        ttl: Duration,
+    ) -> Result<(Self, String), PlatformError> {
+        let mut token_bytes = [0u8; Self::TOKEN_BYTES];
+        SystemRandom::new()
+            .fill(&mut token_bytes)
+            .map_err(|e| PlatformError::InternalError(format!("Failed to generate random token: {:?}", e)))?;
+
+        let token = format!("{}{}", Self::PREFIX, URL_SAFE_NO_PAD.encode(token_bytes));
+        let now = Utc::now();
+
+        let refresh_token = RefreshToken {
+            id: Uuid::new_v4(),
+            user_id,
+            tenant_id,
+            token_hash: Self::hash_token(&token),
+            expires_at: now + ttl,
+            revoked_at: None,
+            created_at: now,
+        };
+
+        Ok((refresh_token, token))
+    }
+
+    /// SHA-256 hash of a plaintext token, as stored in `token_hash`
+    pub fn hash_token(token: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(token.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.is_revoked() && !self.is_expired()
+    }
+
+    pub fn revoke(&mut self) {
+        if self.revoked_at.is_none() {
+            self.revoked_at = Some(Utc::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_stores_only_the_hash() {
+        let (refresh_token, token) =
+            RefreshToken::issue(UserId::new(), TenantId::new(), Duration::days(30)).unwrap();
+
+        assert!(token.starts_with("rt_"));
+        assert_ne!(refresh_token.token_hash, token);
+        assert_eq!(refresh_token.token_hash, RefreshToken::hash_token(&token));
+        assert!(refresh_token.is_active());
+    }
+
+    #[test]
+    fn test_revoked_and_expired_tokens_are_inactive() {
+        let (mut revoked, _) =
+            RefreshToken::issue(UserId::new(), TenantId::new(), Duration::days(30)).unwrap();
+        revoked.revoke();
+        assert!(revoked.is_revoked());
+        assert!(!revoked.is_active());
+
+        let (expired, _) =
+            RefreshToken::issue(UserId::new(), TenantId::new(), Duration::seconds(-1)).unwrap();
+        assert!(expired.is_expired());
+        assert!(!expired.is_active());
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
 use crate::domain::value_objects::{SessionId, TenantId, UserId, ChatMessage, SessionContext, MessageId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -9,6 +10,9 @@ pub struct ChatSession {
     pub user_id: UserId,
     pub title: Option<String>,
     pub context: SessionContext,
+    /// Summary of the whole conversation, see `SessionApplicationService::summarize_session`
+    #[serde(default)]
+    pub summary: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -18,6 +22,43 @@ pub struct Message {
     pub id: MessageId,
     pub session_id: SessionId,
     pub message: ChatMessage,
+    /// Set when an edit replaced this message, or an earlier one, with a new branch
+    #[serde(default)]
+    pub superseded_at: Option<DateTime<Utc>>,
+}
+
+/// An edit of a user message. The edited copy and its regenerated response
+/// replace the original and everything after it in the conversation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageBranch {
+    pub id: Uuid,
+    pub session_id: SessionId,
+    /// First version of the message; edits of edits point here too
+    pub original_message_id: MessageId,
+    pub edited_message_id: MessageId,
+    pub response_message_id: Option<MessageId>,
+    pub created_by: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MessageBranch {
+    pub fn new(
+        session_id: SessionId,
+        original_message_id: MessageId,
+        edited_message_id: MessageId,
+        response_message_id: Option<MessageId>,
+        created_by: UserId,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            session_id,
+            original_message_id,
+            edited_message_id,
+            response_message_id,
+            created_by,
+            created_at: Utc::now(),
+        }
+    }
 }
 
 impl ChatSession {
@@ -29,6 +70,7 @@ impl ChatSession {
             user_id,
             title,
             context: SessionContext::new(),
+            summary: None,
             created_at: now,
             updated_at: now,
         }
@@ -69,6 +111,11 @@ impl ChatSession {
         self.updated_at = Utc::now();
     }
 
+    pub fn set_summary(&mut self, summary: String) {
+        self.summary = Some(summary);
+        self.updated_at = Utc::now();
+    }
+
     pub fn is_expired(&self, timeout_minutes: u64) -> bool {
         self.context.is_expired(timeout_minutes)
     }
@@ -103,9 +150,14 @@ impl Message {
             id: MessageId::new(),
             session_id,
             message,
+            superseded_at: None,
         })
     }
 
+    pub fn is_superseded(&self) -> bool {
+        self.superseded_at.is_some()
+    }
+
     pub fn belongs_to_session(&self, session_id: &SessionId) -> bool {
         &self.session_id == session_id
     }
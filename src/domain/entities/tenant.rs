@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::domain::entities::AgentLimitsConfig;
 use crate::domain::value_objects::{TenantId, TenantName};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -30,4 +31,127 @@ impl Tenant {
         // Name validation is handled by TenantName value object
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Resources a tenant quota can limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaResource {
+    Agents,
+    Flows,
+    McpTools,
+    MonthlyTokens,
+    VectorRecords,
+}
+
+impl QuotaResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaResource::Agents => "agents",
+            QuotaResource::Flows => "flows",
+            QuotaResource::McpTools => "mcp_tools",
+            QuotaResource::MonthlyTokens => "monthly_tokens",
+            QuotaResource::VectorRecords => "vector_records",
+        }
+    }
+}
+
+/// Resource limits of a tenant. Unset limits are unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaConfig {
+    pub max_agents: Option<u64>,
+    pub max_flows: Option<u64>,
+    pub max_mcp_tools: Option<u64>,
+    /// LLM tokens per calendar month (UTC)
+    pub max_monthly_tokens: Option<u64>,
+    pub max_vector_records: Option<u64>,
+}
+
+impl QuotaConfig {
+    pub fn limit(&self, resource: QuotaResource) -> Option<u64> {
+        match resource {
+            QuotaResource::Agents => self.max_agents,
+            QuotaResource::Flows => self.max_flows,
+            QuotaResource::McpTools => self.max_mcp_tools,
+            QuotaResource::MonthlyTokens => self.max_monthly_tokens,
+            QuotaResource::VectorRecords => self.max_vector_records,
+        }
+    }
+
+    /// Whether `additional` units fit on top of the `current` usage
+    pub fn allows(&self, resource: QuotaResource, current: u64, additional: u64) -> bool {
+        self.limit(resource)
+            .map_or(true, |limit| current.saturating_add(additional) <= limit)
+    }
+}
+
+/// Per-tenant overrides of platform defaults. Unset fields fall back to the
+/// configured defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantSettings {
+    pub tenant_id: TenantId,
+    pub max_system_prompt_length: Option<usize>,
+    pub quota: QuotaConfig,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TenantSettings {
+    pub fn new(tenant_id: TenantId) -> Self {
+        let now = Utc::now();
+        Self {
+            tenant_id,
+            max_system_prompt_length: None,
+            quota: QuotaConfig::default(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn update_max_system_prompt_length(&mut self, max_length: Option<usize>) -> Result<(), String> {
+        if max_length == Some(0) {
+            return Err("Max system prompt length must be positive".to_string());
+        }
+        self.max_system_prompt_length = max_length;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn update_quota(&mut self, quota: QuotaConfig) {
+        self.quota = quota;
+        self.updated_at = Utc::now();
+    }
+
+    /// Agent limits for this tenant, starting from the platform defaults
+    pub fn agent_limits(&self, defaults: &AgentLimitsConfig) -> AgentLimitsConfig {
+        AgentLimitsConfig {
+            max_system_prompt_length: self
+                .max_system_prompt_length
+                .unwrap_or(defaults.max_system_prompt_length),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_allows_up_to_the_limit() {
+        let quota = QuotaConfig {
+            max_agents: Some(3),
+            ..Default::default()
+        };
+
+        assert!(quota.allows(QuotaResource::Agents, 2, 1));
+        assert!(!quota.allows(QuotaResource::Agents, 3, 1));
+        assert!(!quota.allows(QuotaResource::Agents, 0, 4));
+    }
+
+    #[test]
+    fn test_unset_quota_is_unlimited() {
+        let quota = QuotaConfig::default();
+        assert!(quota.allows(QuotaResource::MonthlyTokens, u64::MAX, 1));
+    }
+}
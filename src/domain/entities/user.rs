@@ -8,11 +8,21 @@ pub struct User {
     pub tenant_id: TenantId,
     pub username: Username,
     pub nickname: Option<String>,
+    pub email: Option<String>,
+    /// Preferred interface language as a BCP 47 tag, e.g. `zh-CN`
+    pub language: Option<String>,
+    pub avatar_url: Option<String>,
     pub password_hash: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Longest accepted email address
+const MAX_EMAIL_LENGTH: usize = 255;
+
+/// Longest accepted language tag
+const MAX_LANGUAGE_LENGTH: usize = 35;
+
 impl User {
     pub fn new(
         user_id: UserId,
@@ -40,6 +50,9 @@ impl User {
             tenant_id,
             username,
             nickname,
+            email: None,
+            language: None,
+            avatar_url: None,
             password_hash,
             created_at: now,
             updated_at: now,
@@ -58,6 +71,69 @@ impl User {
         Ok(())
     }
 
+    pub fn update_email(&mut self, email: Option<String>) -> Result<(), String> {
+        if let Some(ref email) = email {
+            Self::validate_email(email)?;
+        }
+
+        self.email = email;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn update_language(&mut self, language: Option<String>) -> Result<(), String> {
+        if let Some(ref language) = language {
+            Self::validate_language(language)?;
+        }
+
+        self.language = language;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn update_avatar(&mut self, avatar_url: Option<String>) {
+        self.avatar_url = avatar_url;
+        self.updated_at = Utc::now();
+    }
+
+    fn validate_email(email: &str) -> Result<(), String> {
+        if email.len() > MAX_EMAIL_LENGTH {
+            return Err(format!("Email cannot exceed {} characters", MAX_EMAIL_LENGTH));
+        }
+
+        let valid = match email.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+                    && !email.chars().any(char::is_whitespace)
+            }
+            None => false,
+        };
+        if !valid {
+            return Err(format!("Invalid email address: {}", email));
+        }
+        Ok(())
+    }
+
+    /// Accepts tags like `en`, `zh-CN` or `zh-Hant-TW`
+    fn validate_language(language: &str) -> Result<(), String> {
+        let mut subtags = language.split('-');
+        let primary = subtags.next().unwrap_or_default();
+        let valid = language.len() <= MAX_LANGUAGE_LENGTH
+            && (2..=3).contains(&primary.len())
+            && primary.chars().all(|c| c.is_ascii_alphabetic())
+            && subtags.all(|subtag| {
+                (2..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+            });
+        if !valid {
+            return Err(format!("Invalid language tag: {}", language));
+        }
+        Ok(())
+    }
+
     pub fn update_password(&mut self, password_hash: String) -> Result<(), String> {
         if password_hash.trim().is_empty() {
             return Err("Password hash cannot be empty".to_string());
@@ -83,6 +159,58 @@ impl User {
             }
         }
 
+        if let Some(ref email) = self.email {
+            Self::validate_email(email)?;
+        }
+
+        if let Some(ref language) = self.language {
+            Self::validate_language(language)?;
+        }
+
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> User {
+        User::new(
+            UserId::new(),
+            TenantId::new(),
+            Username::new("alice".to_string()).unwrap(),
+            "hash".to_string(),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_update_email_validates_address() {
+        let mut user = user();
+
+        assert!(user.update_email(Some("alice@example.com".to_string())).is_ok());
+        assert_eq!(user.email.as_deref(), Some("alice@example.com"));
+
+        for invalid in ["alice", "alice@", "@example.com", "alice@example", "a b@example.com"] {
+            assert!(user.update_email(Some(invalid.to_string())).is_err(), "{}", invalid);
+        }
+        assert_eq!(user.email.as_deref(), Some("alice@example.com"));
+
+        assert!(user.update_email(None).is_ok());
+        assert!(user.email.is_none());
+    }
+
+    #[test]
+    fn test_update_language_accepts_bcp47_tags() {
+        let mut user = user();
+
+        for valid in ["en", "zh-CN", "zh-Hant-TW"] {
+            assert!(user.update_language(Some(valid.to_string())).is_ok(), "{}", valid);
+        }
+        for invalid in ["", "e", "english", "zh_CN", "zh-"] {
+            assert!(user.update_language(Some(invalid.to_string())).is_err(), "{}", invalid);
+        }
+    }
+}
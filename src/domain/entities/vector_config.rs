@@ -23,8 +23,9 @@ impl VectorConfigEntity {
         tenant_id: TenantId,
         name: String,
         provider: VectorProvider,
-        connection_params: HashMap<String, String>,
+        mut connection_params: HashMap<String, String>,
     ) -> Self {
+        provider.normalize_connection_params(&mut connection_params);
         let now = Utc::now();
         Self {
             id: ConfigId::new(),
@@ -55,7 +56,8 @@ impl VectorConfigEntity {
         self
     }
     
-    pub fn update_connection_params(mut self, params: HashMap<String, String>) -> Self {
+    pub fn update_connection_params(mut self, mut params: HashMap<String, String>) -> Self {
+        self.provider.normalize_connection_params(&mut params);
         self.connection_params = params;
         self.updated_at = Utc::now();
         self
@@ -75,6 +77,7 @@ impl VectorConfigEntity {
             default_namespace: None,
             timeout_seconds: 30, // Default timeout
             max_retries: 3,      // Default retries
+            tenant_id: Some(self.tenant_id),
         }
     }
     
@@ -97,10 +100,10 @@ impl VectorConfigEntity {
                 self.validate_required_params(&["base_url", "collection_name"])?;
             },
             VectorProvider::Weaviate => {
-                self.validate_required_params(&["base_url", "class_name"])?;
+                self.validate_required_params(&["url", "class_name"])?;
             },
             VectorProvider::Qdrant => {
-                self.validate_required_params(&["base_url", "collection_name"])?;
+                self.validate_required_params(&["url", "collection"])?;
             },
             VectorProvider::Milvus => {
                 self.validate_required_params(&["base_url", "collection_name"])?;
@@ -218,6 +221,26 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_legacy_param_names_are_accepted() {
+        let params = HashMap::from([
+            ("base_url".to_string(), "http://localhost:6334".to_string()),
+            ("collection_name".to_string(), "docs".to_string()),
+        ]);
+
+        let config = VectorConfigEntity::new(
+            TenantId::new(),
+            "Qdrant".to_string(),
+            VectorProvider::Qdrant,
+            params,
+        );
+
+        assert!(config.validate().is_ok());
+        assert_eq!(config.connection_params.get("url").unwrap(), "http://localhost:6334");
+        assert_eq!(config.connection_params.get("collection").unwrap(), "docs");
+        assert!(!config.connection_params.contains_key("base_url"));
+    }
+
     #[test]
     fn test_has_sensitive_data() {
         let config = create_test_config();
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{DomainEvent, EventMetadata};
+use crate::domain::entities::{AuditAction, AuditLog, ResourceType};
+
+/// Kind of mutation applied to an agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentChange {
+    Created,
+    Updated,
+    Deleted,
+    Copied,
+    Employed,
+    Fired,
+    Published,
+    Unpublished,
+}
+
+impl AgentChange {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            AgentChange::Created => "agent.created",
+            AgentChange::Updated => "agent.updated",
+            AgentChange::Deleted => "agent.deleted",
+            AgentChange::Copied => "agent.copied",
+            AgentChange::Employed => "agent.employed",
+            AgentChange::Fired => "agent.fired",
+            AgentChange::Published => "agent.published",
+            AgentChange::Unpublished => "agent.unpublished",
+        }
+    }
+
+    fn audit_action(&self) -> AuditAction {
+        match self {
+            AgentChange::Created | AgentChange::Copied => AuditAction::Create,
+            AgentChange::Deleted => AuditAction::Delete,
+            AgentChange::Updated | AgentChange::Published | AgentChange::Unpublished => {
+                AuditAction::Update
+            }
+            AgentChange::Employed => AuditAction::Custom("employ".to_string()),
+            AgentChange::Fired => AuditAction::Custom("fire".to_string()),
+        }
+    }
+}
+
+/// Event emitted when an agent is created, modified or removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentChanged {
+    pub metadata: EventMetadata,
+    pub agent_id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub change: AgentChange,
+    pub details: Option<Value>,
+}
+
+impl AgentChanged {
+    pub fn new(
+        agent_id: Uuid,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        change: AgentChange,
+        details: Option<Value>,
+    ) -> Self {
+        Self {
+            metadata: EventMetadata::new(1),
+            agent_id,
+            tenant_id,
+            user_id,
+            change,
+            details,
+        }
+    }
+}
+
+impl DomainEvent for AgentChanged {
+    fn event_id(&self) -> Uuid {
+        self.metadata.event_id
+    }
+
+    fn event_type(&self) -> &'static str {
+        self.change.event_type()
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.metadata.occurred_at
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        self.agent_id
+    }
+
+    fn version(&self) -> i64 {
+        self.metadata.version
+    }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> Option<Uuid> {
+        self.metadata.correlation_id
+    }
+
+    fn audit_entry(&self) -> Option<AuditLog> {
+        let mut entry = AuditLog::new(
+            self.tenant_id,
+            Some(self.user_id),
+            self.change.audit_action(),
+            ResourceType::Custom("agent".to_string()),
+            Some(self.agent_id),
+        );
+        entry.id = self.metadata.event_id;
+        entry.created_at = self.metadata.occurred_at;
+        entry.details = self.details.clone();
+        Some(entry)
+    }
+}
@@ -4,7 +4,7 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use super::{DomainEvent, EventMetadata};
-use crate::domain::entities::{AuditAction, ResourceType};
+use crate::domain::entities::{AuditAction, AuditLog, ResourceType};
 
 /// Event emitted when an audit log is created
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +62,29 @@ impl DomainEvent for AuditLogCreated {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> Option<Uuid> {
+        self.metadata.correlation_id
+    }
+
+    fn audit_entry(&self) -> Option<AuditLog> {
+        Some(AuditLog {
+            id: self.audit_log_id,
+            tenant_id: self.tenant_id,
+            user_id: self.user_id,
+            action: self.action.clone(),
+            resource_type: self.resource_type.clone(),
+            resource_id: self.resource_id,
+            details: self.details.clone(),
+            ip_address: None,
+            user_agent: None,
+            created_at: self.metadata.occurred_at,
+        })
+    }
 }
 
 /// Event emitted when a flow execution starts
@@ -92,6 +115,11 @@ impl FlowExecutionStarted {
             input_data,
         }
     }
+
+    pub fn with_correlation(mut self, correlation_id: Uuid) -> Self {
+        self.metadata = self.metadata.with_correlation(correlation_id);
+        self
+    }
 }
 
 impl DomainEvent for FlowExecutionStarted {
@@ -114,6 +142,14 @@ impl DomainEvent for FlowExecutionStarted {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> Option<Uuid> {
+        self.metadata.correlation_id
+    }
 }
 
 /// Event emitted when a flow execution completes
@@ -147,6 +183,11 @@ impl FlowExecutionCompleted {
             execution_time_ms,
         }
     }
+
+    pub fn with_correlation(mut self, correlation_id: Uuid) -> Self {
+        self.metadata = self.metadata.with_correlation(correlation_id);
+        self
+    }
 }
 
 impl DomainEvent for FlowExecutionCompleted {
@@ -169,6 +210,14 @@ impl DomainEvent for FlowExecutionCompleted {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> Option<Uuid> {
+        self.metadata.correlation_id
+    }
 }
 
 /// Event emitted when a flow execution fails
@@ -202,6 +251,11 @@ impl FlowExecutionFailed {
             execution_time_ms,
         }
     }
+
+    pub fn with_correlation(mut self, correlation_id: Uuid) -> Self {
+        self.metadata = self.metadata.with_correlation(correlation_id);
+        self
+    }
 }
 
 impl DomainEvent for FlowExecutionFailed {
@@ -224,4 +278,88 @@ impl DomainEvent for FlowExecutionFailed {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> Option<Uuid> {
+        self.metadata.correlation_id
+    }
+}
+
+/// Event emitted when a chat session and its messages are deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDeleted {
+    pub metadata: EventMetadata,
+    pub session_id: Uuid,
+    pub tenant_id: Uuid,
+    /// Owner of the session
+    pub user_id: Uuid,
+    /// User who deleted it; `None` when purged by an administrator
+    pub deleted_by: Option<Uuid>,
+    pub message_count: u64,
+}
+
+impl SessionDeleted {
+    pub fn new(
+        session_id: Uuid,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        deleted_by: Option<Uuid>,
+        message_count: u64,
+    ) -> Self {
+        Self {
+            metadata: EventMetadata::new(1),
+            session_id,
+            tenant_id,
+            user_id,
+            deleted_by,
+            message_count,
+        }
+    }
+}
+
+impl DomainEvent for SessionDeleted {
+    fn event_id(&self) -> Uuid {
+        self.metadata.event_id
+    }
+
+    fn event_type(&self) -> &'static str {
+        "session.deleted"
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.metadata.occurred_at
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        self.session_id
+    }
+
+    fn version(&self) -> i64 {
+        self.metadata.version
+    }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn audit_entry(&self) -> Option<AuditLog> {
+        Some(AuditLog {
+            id: self.metadata.event_id,
+            tenant_id: self.tenant_id,
+            user_id: self.deleted_by,
+            action: AuditAction::Delete,
+            resource_type: ResourceType::Session,
+            resource_id: Some(self.session_id),
+            details: Some(serde_json::json!({
+                "owner_id": self.user_id,
+                "message_count": self.message_count,
+            })),
+            ip_address: None,
+            user_agent: None,
+            created_at: self.metadata.occurred_at,
+        })
+    }
 }
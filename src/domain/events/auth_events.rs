@@ -54,6 +54,10 @@ impl DomainEvent for UserAuthenticatedEvent {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
 }
 
 /// User authentication failed event
@@ -108,6 +112,10 @@ impl DomainEvent for UserAuthenticationFailedEvent {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
 }
 
 /// User logged out event
@@ -161,6 +169,10 @@ impl DomainEvent for UserLoggedOutEvent {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
 }
 
 /// Token refreshed event
@@ -214,6 +226,10 @@ impl DomainEvent for TokenRefreshedEvent {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
 }
 
 /// Password changed event
@@ -264,4 +280,8 @@ impl DomainEvent for PasswordChangedEvent {
     fn version(&self) -> i64 {
         self.metadata.version
     }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use tracing::debug;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use uuid::Uuid;
+
+use super::DomainEvent;
+use crate::error::Result;
+
+/// Publishes domain events to interested consumers
+#[async_trait]
+pub trait EventBus: Send + Sync {
+    /// Publish a domain event
+    async fn publish(&self, event: Arc<dyn DomainEvent>) -> Result<()>;
+}
+
+/// Events a `BroadcastEventBus` subscriber may lag behind before it starts
+/// missing them
+pub const EVENT_BUS_CAPACITY: usize = 1024;
+
+/// In-process event bus that retains published events. Nothing is ever
+/// dropped, so it is meant for tests.
+#[derive(Default)]
+pub struct InMemoryEventBus {
+    events: RwLock<Vec<Arc<dyn DomainEvent>>>,
+}
+
+impl InMemoryEventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All events published so far, in publication order
+    pub async fn published_events(&self) -> Vec<Arc<dyn DomainEvent>> {
+        self.events.read().await.clone()
+    }
+
+    /// Events sharing the given correlation ID
+    pub async fn events_by_correlation(&self, correlation_id: Uuid) -> Vec<Arc<dyn DomainEvent>> {
+        self.events
+            .read()
+            .await
+            .iter()
+            .filter(|event| event.correlation_id() == Some(correlation_id))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventBus for InMemoryEventBus {
+    async fn publish(&self, event: Arc<dyn DomainEvent>) -> Result<()> {
+        debug!("Publishing domain event {} ({})", event.event_type(), event.event_id());
+        self.events.write().await.push(event);
+        Ok(())
+    }
+}
+
+/// Bounded in-process event bus: events go to current subscribers and are
+/// not retained. A subscriber that falls more than `EVENT_BUS_CAPACITY`
+/// events behind skips the oldest ones.
+pub struct BroadcastEventBus {
+    sender: broadcast::Sender<Arc<dyn DomainEvent>>,
+}
+
+impl BroadcastEventBus {
+    pub fn new() -> Self {
+        Self::with_capacity(EVENT_BUS_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Receive events published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<dyn DomainEvent>> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for BroadcastEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventBus for BroadcastEventBus {
+    async fn publish(&self, event: Arc<dyn DomainEvent>) -> Result<()> {
+        debug!("Publishing domain event {} ({})", event.event_type(), event.event_id());
+        // Sending only fails when nobody is subscribed, which is fine
+        let _ = self.sender.send(event);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::{FlowChange, FlowChanged};
+
+    fn flow_event(change: FlowChange) -> Arc<dyn DomainEvent> {
+        Arc::new(FlowChanged::new(Uuid::new_v4(), Uuid::new_v4(), None, change, None))
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_bus_is_bounded() {
+        let bus = BroadcastEventBus::with_capacity(2);
+
+        // No subscribers: the event is dropped, not kept
+        bus.publish(flow_event(FlowChange::Created)).await.unwrap();
+
+        let mut receiver = bus.subscribe();
+        for change in [FlowChange::Updated, FlowChange::Archived, FlowChange::Deleted] {
+            bus.publish(flow_event(change)).await.unwrap();
+        }
+
+        // The slow subscriber skipped the oldest event
+        assert!(matches!(receiver.recv().await, Err(broadcast::error::RecvError::Lagged(1))));
+        assert_eq!(receiver.recv().await.unwrap().event_type(), "flow.archived");
+        assert_eq!(receiver.recv().await.unwrap().event_type(), "flow.deleted");
+    }
+}
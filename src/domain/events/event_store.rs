@@ -0,0 +1,234 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::*;
+use crate::error::{PlatformError, Result};
+
+/// Point-in-time state of an aggregate, used to avoid replaying its full history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateSnapshot {
+    pub aggregate_id: Uuid,
+    pub version: i64,
+    pub state: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AggregateSnapshot {
+    pub fn new(aggregate_id: Uuid, version: i64, state: Value) -> Self {
+        Self {
+            aggregate_id,
+            version,
+            state,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Append-only store of domain events, keyed by aggregate.
+///
+/// The store owns aggregate versioning: an appended event is assigned the
+/// next version for its aggregate, regardless of the version it carries.
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Append an event to its aggregate's stream, returning the assigned version
+    async fn append(&self, event: &dyn DomainEvent) -> Result<i64>;
+
+    /// Load the events of an aggregate with a version greater than `after_version`, oldest first
+    async fn load_events(&self, aggregate_id: Uuid, after_version: i64) -> Result<Vec<Box<dyn DomainEvent>>>;
+
+    /// Latest snapshot of an aggregate, if one has been taken
+    async fn load_snapshot(&self, aggregate_id: Uuid) -> Result<Option<AggregateSnapshot>>;
+
+    /// Replace the snapshot of an aggregate
+    async fn save_snapshot(&self, snapshot: AggregateSnapshot) -> Result<()>;
+}
+
+/// Serialized form of a domain event as kept by an `EventStore`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredEvent {
+    pub event_id: Uuid,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub version: i64,
+    pub payload: Value,
+    pub correlation_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl StoredEvent {
+    pub fn from_event(event: &dyn DomainEvent, version: i64) -> Self {
+        let mut payload = event.payload();
+        if let Some(metadata) = payload.get_mut("metadata").and_then(Value::as_object_mut) {
+            metadata.insert("version".to_string(), Value::from(version));
+        }
+
+        Self {
+            event_id: event.event_id(),
+            aggregate_id: event.aggregate_id(),
+            event_type: event.event_type().to_string(),
+            version,
+            payload,
+            correlation_id: event.correlation_id(),
+            occurred_at: event.occurred_at(),
+        }
+    }
+
+    /// Rebuild the concrete event from its type tag and payload
+    pub fn decode(self) -> Result<Box<dyn DomainEvent>> {
+        fn boxed<E>(payload: Value) -> Result<Box<dyn DomainEvent>>
+        where
+            E: DomainEvent + serde::de::DeserializeOwned + 'static,
+        {
+            Ok(Box::new(serde_json::from_value::<E>(payload)?))
+        }
+
+        match self.event_type.as_str() {
+            "UserAuthenticated" => boxed::<UserAuthenticatedEvent>(self.payload),
+            "UserAuthenticationFailed" => boxed::<UserAuthenticationFailedEvent>(self.payload),
+            "UserLoggedOut" => boxed::<UserLoggedOutEvent>(self.payload),
+            "TokenRefreshed" => boxed::<TokenRefreshedEvent>(self.payload),
+            "PasswordChanged" => boxed::<PasswordChangedEvent>(self.payload),
+            "audit_log.created" => boxed::<AuditLogCreated>(self.payload),
+            "flow_execution.started" => boxed::<FlowExecutionStarted>(self.payload),
+            "flow_execution.completed" => boxed::<FlowExecutionCompleted>(self.payload),
+            "flow_execution.failed" => boxed::<FlowExecutionFailed>(self.payload),
+            "session.deleted" => boxed::<SessionDeleted>(self.payload),
+            t if t.starts_with("flow.") => boxed::<FlowChanged>(self.payload),
+            t if t.starts_with("agent.") => boxed::<AgentChanged>(self.payload),
+            other => Err(PlatformError::InternalError(format!(
+                "Unknown domain event type '{}'", other
+            ))),
+        }
+    }
+}
+
+/// In-process event store, mainly useful for tests
+#[derive(Default)]
+pub struct InMemoryEventStore {
+    streams: RwLock<HashMap<Uuid, Vec<StoredEvent>>>,
+    snapshots: RwLock<HashMap<Uuid, AggregateSnapshot>>,
+}
+
+impl InMemoryEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append(&self, event: &dyn DomainEvent) -> Result<i64> {
+        let mut streams = self.streams.write().await;
+        let stream = streams.entry(event.aggregate_id()).or_default();
+        let version = stream.last().map(|e| e.version).unwrap_or(0) + 1;
+        stream.push(StoredEvent::from_event(event, version));
+        Ok(version)
+    }
+
+    async fn load_events(&self, aggregate_id: Uuid, after_version: i64) -> Result<Vec<Box<dyn DomainEvent>>> {
+        let streams = self.streams.read().await;
+        streams
+            .get(&aggregate_id)
+            .map(|stream| stream.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter(|event| event.version > after_version)
+            .cloned()
+            .map(StoredEvent::decode)
+            .collect()
+    }
+
+    async fn load_snapshot(&self, aggregate_id: Uuid) -> Result<Option<AggregateSnapshot>> {
+        Ok(self.snapshots.read().await.get(&aggregate_id).cloned())
+    }
+
+    async fn save_snapshot(&self, snapshot: AggregateSnapshot) -> Result<()> {
+        self.snapshots.write().await.insert(snapshot.aggregate_id, snapshot);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{AuditAction, ResourceType};
+
+    #[tokio::test]
+    async fn test_append_assigns_versions_and_replays_after_version() {
+        let store = InMemoryEventStore::new();
+        let flow_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+
+        for change in [FlowChange::Created, FlowChange::Updated, FlowChange::Archived] {
+            store
+                .append(&FlowChanged::new(flow_id, tenant_id, None, change, None))
+                .await
+                .unwrap();
+        }
+
+        let events = store.load_events(flow_id, 1).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type(), "flow.updated");
+        assert_eq!(events[0].version(), 2);
+        assert_eq!(events[1].event_type(), "flow.archived");
+        assert_eq!(events[1].version(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_events_rebuild_audit_entries() {
+        let store = InMemoryEventStore::new();
+        let agent_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let event = AgentChanged::new(
+            agent_id,
+            tenant_id,
+            user_id,
+            AgentChange::Deleted,
+            Some(serde_json::json!({ "name": "Support bot" })),
+        );
+        store.append(&event).await.unwrap();
+
+        let events = store.load_events(agent_id, 0).await.unwrap();
+        let entry = events[0].audit_entry().unwrap();
+        assert_eq!(entry.id, event.event_id());
+        assert_eq!(entry.tenant_id, tenant_id);
+        assert_eq!(entry.user_id, Some(user_id));
+        assert_eq!(entry.action, AuditAction::Delete);
+        assert_eq!(entry.resource_type, ResourceType::Custom("agent".to_string()));
+        assert_eq!(entry.resource_id, Some(agent_id));
+    }
+
+    #[test]
+    fn test_decode_unknown_event_type_fails() {
+        let stored = StoredEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id: Uuid::new_v4(),
+            event_type: "unknown.event".to_string(),
+            version: 1,
+            payload: Value::Null,
+            correlation_id: None,
+            occurred_at: Utc::now(),
+        };
+
+        assert!(stored.decode().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let store = InMemoryEventStore::new();
+        let aggregate_id = Uuid::new_v4();
+
+        assert!(store.load_snapshot(aggregate_id).await.unwrap().is_none());
+
+        let snapshot = AggregateSnapshot::new(aggregate_id, 3, serde_json::json!({ "name": "flow" }));
+        store.save_snapshot(snapshot.clone()).await.unwrap();
+        assert_eq!(store.load_snapshot(aggregate_id).await.unwrap(), Some(snapshot));
+    }
+}
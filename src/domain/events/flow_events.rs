@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{DomainEvent, EventMetadata};
+use crate::domain::entities::{AuditAction, AuditLog, ResourceType};
+
+/// Kind of mutation applied to a flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowChange {
+    Created,
+    Updated,
+    Deleted,
+    Activated,
+    Archived,
+    VersionCreated,
+    RolledBack,
+    Published,
+    Unpublished,
+    Enabled,
+    Disabled,
+}
+
+impl FlowChange {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            FlowChange::Created => "flow.created",
+            FlowChange::Updated => "flow.updated",
+            FlowChange::Deleted => "flow.deleted",
+            FlowChange::Activated => "flow.activated",
+            FlowChange::Archived => "flow.archived",
+            FlowChange::VersionCreated => "flow.version_created",
+            FlowChange::RolledBack => "flow.rolled_back",
+            FlowChange::Published => "flow.published",
+            FlowChange::Unpublished => "flow.unpublished",
+            FlowChange::Enabled => "flow.enabled",
+            FlowChange::Disabled => "flow.disabled",
+        }
+    }
+
+    fn audit_action(&self) -> AuditAction {
+        match self {
+            FlowChange::Created | FlowChange::VersionCreated => AuditAction::Create,
+            FlowChange::Deleted => AuditAction::Delete,
+            FlowChange::Updated
+            | FlowChange::Activated
+            | FlowChange::Archived
+            | FlowChange::RolledBack
+            | FlowChange::Published
+            | FlowChange::Unpublished
+            | FlowChange::Enabled
+            | FlowChange::Disabled => AuditAction::Update,
+        }
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        match self {
+            FlowChange::VersionCreated => ResourceType::FlowVersion,
+            _ => ResourceType::Flow,
+        }
+    }
+}
+
+/// Event emitted when a flow is created, modified or removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowChanged {
+    pub metadata: EventMetadata,
+    pub flow_id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub change: FlowChange,
+    pub details: Option<Value>,
+}
+
+impl FlowChanged {
+    pub fn new(
+        flow_id: Uuid,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+        change: FlowChange,
+        details: Option<Value>,
+    ) -> Self {
+        Self {
+            metadata: EventMetadata::new(1),
+            flow_id,
+            tenant_id,
+            user_id,
+            change,
+            details,
+        }
+    }
+}
+
+impl DomainEvent for FlowChanged {
+    fn event_id(&self) -> Uuid {
+        self.metadata.event_id
+    }
+
+    fn event_type(&self) -> &'static str {
+        self.change.event_type()
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.metadata.occurred_at
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        self.flow_id
+    }
+
+    fn version(&self) -> i64 {
+        self.metadata.version
+    }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> Option<Uuid> {
+        self.metadata.correlation_id
+    }
+
+    fn audit_entry(&self) -> Option<AuditLog> {
+        let mut entry = AuditLog::new(
+            self.tenant_id,
+            self.user_id,
+            self.change.audit_action(),
+            self.change.resource_type(),
+            Some(self.flow_id),
+        );
+        entry.id = self.metadata.event_id;
+        entry.created_at = self.metadata.occurred_at;
+        entry.details = self.details.clone();
+        Some(entry)
+    }
+}
@@ -1,13 +1,23 @@
 pub mod auth_events;
 pub mod audit_events;
+pub mod flow_events;
+pub mod agent_events;
+pub mod event_bus;
+pub mod event_store;
 
 pub use auth_events::*;
 pub use audit_events::*;
+pub use flow_events::*;
+pub use agent_events::*;
+pub use event_bus::*;
+pub use event_store::*;
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::domain::entities::AuditLog;
+
 /// Base trait for all domain events
 pub trait DomainEvent: Send + Sync {
     fn event_id(&self) -> Uuid;
@@ -15,6 +25,19 @@ pub trait DomainEvent: Send + Sync {
     fn occurred_at(&self) -> DateTime<Utc>;
     fn aggregate_id(&self) -> Uuid;
     fn version(&self) -> i64;
+
+    /// Correlation ID shared by events belonging to the same operation
+    fn correlation_id(&self) -> Option<Uuid> {
+        None
+    }
+
+    /// Serialized event body, as persisted by an `EventStore`
+    fn payload(&self) -> serde_json::Value;
+
+    /// Audit log entry this event stands for, if it is auditable
+    fn audit_entry(&self) -> Option<AuditLog> {
+        None
+    }
 }
 
 /// Event metadata
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use chrono::NaiveDate;
+use rust_decimal::Decimal;
 use crate::domain::entities::AgentDailyStats;
 use crate::domain::value_objects::{AgentId, TenantId};
 use crate::error::Result;
@@ -42,4 +43,13 @@ pub trait AgentDailyStatsRepository: Send + Sync {
         tenant_id: &TenantId,
         stat_date: NaiveDate,
     ) -> Result<AgentDailyStats>;
+    
+    /// Atomically add revenue to the day's row, creating it if missing
+    async fn add_revenue(
+        &self,
+        agent_id: &AgentId,
+        tenant_id: &TenantId,
+        stat_date: NaiveDate,
+        amount: Decimal,
+    ) -> Result<()>;
 }
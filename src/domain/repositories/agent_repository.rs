@@ -1,9 +1,22 @@
 use async_trait::async_trait;
-use crate::domain::entities::Agent;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use crate::domain::entities::{Agent, AgentAllocation};
 use crate::domain::value_objects::{AgentId, TenantId, UserId};
 use crate::error::Result;
 
+/// Filters for browsing published agents in the marketplace.
+/// Agents without a price are free and count as priced at zero.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketplaceAgentFilter {
+    /// Matched against name and greeting
+    pub keyword: Option<String>,
+    pub min_price: Option<Decimal>,
+    pub max_price: Option<Decimal>,
+}
+
 /// Agent repository interface for managing Agent entities
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait AgentRepository: Send + Sync {
     /// Find an agent by ID
@@ -21,9 +34,15 @@ pub trait AgentRepository: Send + Sync {
     /// Find agents employed by a specific user (employer_id matches user_id)
     async fn find_by_employer(&self, employer_id: &UserId) -> Result<Vec<Agent>>;
     
-    /// Find agents allocated to a specific user
+    /// Find all copied and employed instances of an agent (including fired agents)
+    async fn find_by_source_agent(&self, source_id: &AgentId) -> Result<Vec<Agent>>;
+    
+    /// Find agents allocated to a specific user, including expired allocations
     async fn find_allocated_to_user(&self, user_id: &UserId) -> Result<Vec<Agent>>;
     
+    /// Find agents allocated to a specific user whose allocation has not expired
+    async fn find_allocated_to_user_active(&self, user_id: &UserId) -> Result<Vec<Agent>>;
+    
     /// Save an agent (create or update)
     async fn save(&self, agent: &Agent) -> Result<()>;
     
@@ -54,6 +73,68 @@ pub trait AgentRepository: Send + Sync {
     
     /// Find published agents by tenant
     async fn find_by_tenant_published(&self, tenant_id: &TenantId) -> Result<Vec<Agent>>;
+    
+    /// Find a page of published agents, returning the page and the total count
+    async fn find_by_tenant_published_paginated(
+        &self,
+        tenant_id: &TenantId,
+        include_fired: bool,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)>;
+    
+    /// Find a page of published, active, unemployed agents matching the marketplace filter
+    async fn search_marketplace_paginated(
+        &self,
+        tenant_id: &TenantId,
+        filter: &MarketplaceAgentFilter,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)>;
+    
+    /// Find a page of the tenant's published, active, unemployed agents whose
+    /// name or system prompt matches the keyword
+    async fn search_by_keyword(
+        &self,
+        tenant_id: &TenantId,
+        keyword: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)>;
+
+    /// Same as `search_by_keyword` across the published agents of all
+    /// tenants, but matching name and greeting only: system prompts are not
+    /// searchable outside their tenant
+    async fn search_published_by_keyword(
+        &self,
+        keyword: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)>;
+
+    /// Find a page of agents created by the user that are not copies of another agent
+    async fn find_original_by_creator_paginated(
+        &self,
+        creator_id: &UserId,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)>;
+    
+    async fn find_by_employer_paginated(
+        &self,
+        employer_id: &UserId,
+        include_fired: bool,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)>;
+    
+    /// Find a page of agents whose allocation to the user has not expired
+    async fn find_allocated_to_user_paginated(
+        &self,
+        user_id: &UserId,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)>;
 }
 
 /// Agent employment repository interface for managing employment relationships
@@ -76,20 +157,33 @@ pub trait AgentEmploymentRepository: Send + Sync {
 }
 
 /// Agent allocation repository interface for managing allocation relationships
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait AgentAllocationRepository: Send + Sync {
-    /// Create an allocation relationship between a user and an agent
-    async fn allocate(&self, agent_id: &AgentId, user_id: &UserId) -> Result<()>;
+    /// Create an allocation relationship between a user and an agent,
+    /// lapsing at `expires_at` if set. An expired allocation is replaced.
+    async fn allocate(
+        &self,
+        agent_id: &AgentId,
+        user_id: &UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
     
     /// Terminate an allocation relationship
     async fn terminate(&self, agent_id: &AgentId, user_id: &UserId) -> Result<()>;
     
-    /// Check if a user has been allocated an agent
+    /// Check if a user has an unexpired allocation of an agent
     async fn is_allocated(&self, agent_id: &AgentId, user_id: &UserId) -> Result<bool>;
     
-    /// Find all users who have been allocated a specific agent
+    /// Find all users with an unexpired allocation of a specific agent
     async fn find_by_agent(&self, agent_id: &AgentId) -> Result<Vec<UserId>>;
     
-    /// Find all agents allocated to a specific user
+    /// Find all agents with an unexpired allocation to a specific user
     async fn find_by_user(&self, user_id: &UserId) -> Result<Vec<AgentId>>;
+    
+    /// Find the unexpired allocations of a user, with their expiry
+    async fn find_agents_for_user_with_expiry(&self, user_id: &UserId) -> Result<Vec<AgentAllocation>>;
+    
+    /// Delete allocations that expired before `now`, returning how many were removed
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64>;
 }
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
-use crate::domain::entities::APIKey;
+use crate::domain::entities::{APIKey, APIKeyDailyUsage, APIKeyUsageDelta};
 use crate::domain::value_objects::{APIKeyId, TenantId, UserId};
 use crate::error::Result;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 /// Query options for pagination and filtering
@@ -60,4 +61,12 @@ pub trait APIKeyRepository: Send + Sync {
     
     /// Count API keys belonging to a tenant
     async fn count_by_tenant(&self, tenant_id: TenantId) -> Result<u64>;
+
+    /// Add usage to the key totals and daily breakdowns, and advance
+    /// `last_used_at`
+    async fn record_usage(&self, usage: &[APIKeyUsageDelta]) -> Result<()>;
+
+    /// Daily usage of a key from `since` on, oldest first. Days without
+    /// usage have no entry.
+    async fn find_daily_usage(&self, id: APIKeyId, since: NaiveDate) -> Result<Vec<APIKeyDailyUsage>>;
 }
@@ -72,7 +72,8 @@ pub struct AuditStatistics {
 /// Repository interface for audit logs
 #[async_trait]
 pub trait AuditLogRepository: Send + Sync {
-    /// Create a new audit log entry
+    /// Append an audit log entry. Implementations may write it
+    /// asynchronously; audit logs are never updated.
     async fn create(&self, audit_log: &AuditLog) -> Result<()>;
 
     /// Find audit log by ID
@@ -92,6 +93,7 @@ pub trait AuditLogRepository: Send + Sync {
         end_date: Option<DateTime<Utc>>,
     ) -> Result<AuditStatistics>;
 
-    /// Delete old audit logs (for cleanup)
+    /// Delete audit logs created before `date`. Only the retention job
+    /// calls this; audit logs are not deleted in the normal flow.
     async fn delete_older_than(&self, date: DateTime<Utc>) -> Result<u64>;
 }
@@ -1,22 +1,57 @@
 use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::entities::{ExecutionMetrics, ExecutionStep, FlowExecutionHistory};
+use crate::domain::entities::{BatchExecution, ExecutionMetrics, ExecutionStep, FlowExecutionHistory};
 use crate::error::Result;
 
+/// Position after which the next page of executions starts. Executions are
+/// listed newest first, ordered by `started_at` then `id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionCursor {
+    pub started_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl ExecutionCursor {
+    pub fn new(started_at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { started_at, id }
+    }
+
+    /// Opaque token handed to API clients
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}|{}", self.started_at.timestamp_micros(), self.id))
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(token).ok()?).ok()?;
+        let (micros, id) = decoded.split_once('|')?;
+
+        Some(Self {
+            started_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: Uuid::parse_str(id).ok()?,
+        })
+    }
+}
+
 /// Query filters for flow executions
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionFilter {
     pub tenant_id: Uuid,
     pub flow_id: Option<Uuid>,
+    /// Restrict to executions of any of these flows
+    pub flow_ids: Option<Vec<Uuid>>,
     pub user_id: Option<Uuid>,
     pub session_id: Option<Uuid>,
     pub status: Option<String>,
+    pub correlation_id: Option<Uuid>,
     pub start_date: Option<DateTime<Utc>>,
     pub end_date: Option<DateTime<Utc>>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
+    /// Only executions strictly after this position
+    pub cursor: Option<ExecutionCursor>,
 }
 
 impl ExecutionFilter {
@@ -32,6 +67,11 @@ impl ExecutionFilter {
         self
     }
 
+    pub fn with_flow_ids(mut self, flow_ids: Vec<Uuid>) -> Self {
+        self.flow_ids = Some(flow_ids);
+        self
+    }
+
     pub fn with_user_id(mut self, user_id: Uuid) -> Self {
         self.user_id = Some(user_id);
         self
@@ -47,6 +87,11 @@ impl ExecutionFilter {
         self
     }
 
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
     pub fn with_date_range(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
         self.start_date = Some(start);
         self.end_date = Some(end);
@@ -58,6 +103,12 @@ impl ExecutionFilter {
         self.offset = Some(offset);
         self
     }
+
+    pub fn with_cursor(mut self, cursor: ExecutionCursor, limit: u64) -> Self {
+        self.cursor = Some(cursor);
+        self.limit = Some(limit);
+        self
+    }
 }
 
 /// Repository interface for execution history
@@ -92,4 +143,23 @@ pub trait ExecutionHistoryRepository: Send + Sync {
 
     /// Delete old executions (for cleanup)
     async fn delete_executions_older_than(&self, date: DateTime<Utc>) -> Result<u64>;
+
+    /// Record a finished batch execution
+    async fn create_batch_execution(&self, batch: &BatchExecution) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_cursor_round_trip() {
+        let cursor = ExecutionCursor::new(
+            DateTime::from_timestamp_micros(1_733_000_000_123_456).unwrap(),
+            Uuid::new_v4(),
+        );
+
+        assert_eq!(ExecutionCursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(ExecutionCursor::decode("not-a-cursor"), None);
+    }
 }
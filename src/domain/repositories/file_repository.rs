@@ -1,6 +1,21 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use crate::error::Result;
 
+/// Signed form that lets a client upload one object straight to storage
+#[derive(Debug, Clone)]
+pub struct PresignedPost {
+    /// URL the form is posted to
+    pub url: String,
+    /// Object key the upload is stored under
+    pub key: String,
+    /// Form fields to send along with the file
+    pub fields: HashMap<String, String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait FileRepository: Send + Sync {
     /// Store a file and return its download URL
@@ -19,4 +34,20 @@ pub trait FileRepository: Send + Sync {
     
     /// Get file metadata
     async fn get_file_url(&self, tenant_id: &str, file_id: &str) -> Result<String>;
+
+    /// Sign a form for uploading one file of at most `max_size_bytes`,
+    /// accepted by storage until `expires_at`
+    async fn presign_upload(
+        &self,
+        tenant_id: &str,
+        file_id: &str,
+        filename: &str,
+        content_type: &str,
+        max_size_bytes: u64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<PresignedPost>;
+
+    /// Download URL of an object uploaded through a presigned form.
+    /// Fails with `NotFound` until the upload has finished.
+    async fn confirm_upload(&self, tenant_id: &str, key: &str) -> Result<String>;
 }
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use crate::domain::entities::FlowNodeAnnotation;
+use crate::domain::value_objects::{FlowId, FlowNodeAnnotationId, TenantId};
+use crate::error::Result;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait FlowNodeAnnotationRepository: Send + Sync {
+    /// Find an annotation by ID
+    async fn find_by_id(&self, id: &FlowNodeAnnotationId) -> Result<Option<FlowNodeAnnotation>>;
+
+    /// Find annotations of a flow, optionally restricted to a single node
+    async fn find_by_flow<'a>(&self, flow_id: &FlowId, node_id: Option<&'a str>) -> Result<Vec<FlowNodeAnnotation>>;
+
+    /// Save an annotation (create or update)
+    async fn save(&self, annotation: &FlowNodeAnnotation) -> Result<()>;
+
+    /// Count annotations of a flow
+    async fn count_by_flow(&self, flow_id: &FlowId) -> Result<u64>;
+
+    /// Count annotations of several of the tenant's flows in one query.
+    /// Flows without annotations, or of another tenant, are left out.
+    async fn count_by_flows(&self, tenant_id: &TenantId, flow_ids: &[FlowId]) -> Result<HashMap<FlowId, u64>>;
+}
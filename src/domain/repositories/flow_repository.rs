@@ -1,9 +1,34 @@
 use async_trait::async_trait;
 use crate::domain::entities::{Flow, FlowVersion, FlowExecution, FlowStatus, FlowExecutionStatus};
-use crate::domain::value_objects::{FlowId, TenantId, UserId, SessionId, FlowExecutionId, Version};
+use crate::domain::value_objects::{ConfigId, FlowId, MCPToolId, NodeType, TenantId, UserId, SessionId, FlowExecutionId, Version};
 use crate::error::Result;
 use chrono::{DateTime, Utc};
 
+/// Filters for finding a tenant's flows by the nodes of their current version.
+/// Every given filter must be matched, each by at least one node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowQuery {
+    pub tenant_id: TenantId,
+    /// Flows containing a node of any of these types
+    pub node_types: Option<Vec<NodeType>>,
+    /// Flows with a node calling this LLM config
+    pub llm_config_id: Option<ConfigId>,
+    /// Flows with a node calling this MCP tool
+    pub mcp_tool_id: Option<MCPToolId>,
+}
+
+impl FlowQuery {
+    pub fn new(tenant_id: TenantId) -> Self {
+        Self {
+            tenant_id,
+            node_types: None,
+            llm_config_id: None,
+            mcp_tool_id: None,
+        }
+    }
+}
+
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait FlowRepository: Send + Sync {
     /// Find a flow by ID
@@ -37,8 +62,17 @@ pub trait FlowRepository: Send + Sync {
     
     /// Check if flow name exists within tenant
     async fn name_exists_in_tenant(&self, tenant_id: &TenantId, name: &str) -> Result<bool>;
+    
+    /// Find a page of flows matching the query, with the total match count
+    async fn find_by_query(
+        &self,
+        query: &FlowQuery,
+        offset: u64,
+        limit: u64
+    ) -> Result<(Vec<Flow>, u64)>;
 }
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait FlowVersionRepository: Send + Sync {
     /// Find a flow version by ID
@@ -74,6 +108,7 @@ pub trait FlowVersionRepository: Send + Sync {
     ) -> Result<Vec<FlowVersion>>;
 }
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait FlowExecutionRepository: Send + Sync {
     /// Find a flow execution by ID
@@ -135,4 +170,12 @@ pub trait FlowExecutionRepository: Send + Sync {
         tenant_id: &TenantId,
         limit: u64
     ) -> Result<Vec<FlowExecution>>;
+
+    /// Mark pending and running executions started before `started_before`
+    /// as failed with `error_message`, returning how many were updated
+    async fn fail_unfinished(
+        &self,
+        started_before: chrono::DateTime<chrono::Utc>,
+        error_message: &str,
+    ) -> Result<u64>;
 }
\ No newline at end of file
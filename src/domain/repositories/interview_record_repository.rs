@@ -6,6 +6,7 @@ use crate::domain::entities::InterviewRecord;
 use crate::domain::value_objects::{AgentId, TenantId, UserId};
 use crate::error::Result;
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait InterviewRecordRepository: Send + Sync {
     async fn create(&self, record: &InterviewRecord) -> Result<InterviewRecord>;
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use crate::domain::entities::{DailyTokenUsage, LlmUsageLog};
+use crate::domain::value_objects::{AgentId, TenantId};
+use crate::error::Result;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait LlmUsageLogRepository: Send + Sync {
+    async fn create(&self, log: &LlmUsageLog) -> Result<()>;
+
+    /// Usage of an agent per day within the inclusive date range, oldest first
+    async fn find_daily_usage_by_agent(
+        &self,
+        agent_id: &AgentId,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<DailyTokenUsage>>;
+
+    /// Total tokens used by a tenant within the inclusive date range
+    async fn sum_tokens_by_tenant(
+        &self,
+        tenant_id: &TenantId,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<u64>;
+}
@@ -57,6 +57,7 @@ pub struct MCPToolQueryResult {
 }
 
 /// MCP工具仓储接口
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait MCPToolRepository: Send + Sync {
     /// 根据ID查找工具
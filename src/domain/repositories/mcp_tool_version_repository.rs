@@ -100,6 +100,9 @@ pub trait MCPToolVersionRepository: Send + Sync {
     /// 统计工具的版本数量
     async fn count_by_tool_id(&self, tool_id: MCPToolId) -> Result<u64, PlatformError>;
 
+    /// 只保留工具最新的 `keep` 个版本，返回删除的版本数；`keep` 为0时不删除
+    async fn prune_by_tool_id(&self, tool_id: MCPToolId, keep: u64) -> Result<u64, PlatformError>;
+
     /// 比较两个版本
     async fn compare_versions(
         &self,
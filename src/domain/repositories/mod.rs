@@ -1,5 +1,6 @@
 pub mod user_repository;
 pub mod tenant_repository;
+pub mod tenant_settings_repository;
 pub mod flow_repository;
 pub mod session_repository;
 pub mod mcp_tool_repository;
@@ -11,11 +12,16 @@ pub mod execution_history_repository;
 pub mod agent_repository;
 pub mod agent_daily_stats_repository;
 pub mod interview_record_repository;
+pub mod flow_node_annotation_repository;
 pub mod file_repository;
 pub mod api_key_repository;
+pub mod llm_usage_log_repository;
+pub mod refresh_token_repository;
+pub mod vector_record_metadata_repository;
 
 pub use user_repository::*;
 pub use tenant_repository::*;
+pub use tenant_settings_repository::*;
 pub use flow_repository::*;
 pub use session_repository::*;
 pub use mcp_tool_repository::*;
@@ -27,5 +33,9 @@ pub use execution_history_repository::*;
 pub use agent_repository::*;
 pub use agent_daily_stats_repository::*;
 pub use interview_record_repository::*;
+pub use flow_node_annotation_repository::*;
 pub use file_repository::*;
-pub use api_key_repository::*;
\ No newline at end of file
+pub use api_key_repository::*;
+pub use llm_usage_log_repository::*;
+pub use refresh_token_repository::*;
+pub use vector_record_metadata_repository::*;
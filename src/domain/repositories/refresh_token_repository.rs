@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::domain::entities::RefreshToken;
+use crate::domain::value_objects::UserId;
+use crate::error::Result;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    /// Save a refresh token (create or update)
+    async fn save(&self, token: &RefreshToken) -> Result<()>;
+
+    /// Find a refresh token by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>>;
+
+    /// Find a refresh token by the hash of its plaintext value
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>>;
+
+    /// Revoke the token unless it already is, in a single conditional
+    /// update; returns false when it was revoked before
+    async fn revoke(&self, id: Uuid) -> Result<bool>;
+
+    /// Revoke every active refresh token of the user, returning how many were revoked
+    async fn revoke_all_by_user(&self, user_id: &UserId) -> Result<u64>;
+}
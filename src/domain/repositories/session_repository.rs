@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use crate::domain::entities::{ChatSession, Message};
+use crate::domain::entities::{ChatSession, Message, MessageBranch};
 use crate::domain::value_objects::{SessionId, TenantId, UserId, MessageId};
 use crate::error::Result;
 use chrono::{DateTime, Utc};
@@ -15,6 +15,14 @@ pub trait ChatSessionRepository: Send + Sync {
     /// Find sessions by tenant
     async fn find_by_tenant(&self, tenant_id: &TenantId) -> Result<Vec<ChatSession>>;
     
+    /// Find up to `limit` sessions of a tenant last updated before `before`, oldest first
+    async fn find_by_tenant_updated_before(
+        &self,
+        tenant_id: &TenantId,
+        before: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<ChatSession>>;
+    
     /// Find sessions by tenant and user
     async fn find_by_tenant_and_user(&self, tenant_id: &TenantId, user_id: &UserId) -> Result<Vec<ChatSession>>;
     
@@ -50,7 +58,7 @@ pub trait MessageRepository: Send + Sync {
     /// Find messages by session
     async fn find_by_session(&self, session_id: &SessionId) -> Result<Vec<Message>>;
     
-    /// Find recent messages by session
+    /// Find recent messages by session, leaving out superseded ones
     async fn find_recent_by_session(&self, session_id: &SessionId, limit: u64) -> Result<Vec<Message>>;
     
     /// Find messages by session with pagination
@@ -80,4 +88,19 @@ pub trait MessageRepository: Send + Sync {
         query: &str, 
         limit: u64
     ) -> Result<Vec<Message>>;
+    
+    /// Mark messages as superseded by an edit
+    async fn mark_superseded(&self, ids: &[MessageId], superseded_at: DateTime<Utc>) -> Result<()>;
+}
+
+#[async_trait]
+pub trait MessageBranchRepository: Send + Sync {
+    /// Save a new branch
+    async fn save(&self, branch: &MessageBranch) -> Result<()>;
+    
+    /// Find the branches of an original message, oldest first
+    async fn find_by_original(&self, original_message_id: &MessageId) -> Result<Vec<MessageBranch>>;
+    
+    /// Find the branch that created an edited message
+    async fn find_by_edited(&self, edited_message_id: &MessageId) -> Result<Option<MessageBranch>>;
 }
\ No newline at end of file
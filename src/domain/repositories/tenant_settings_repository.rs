@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use crate::domain::entities::TenantSettings;
+use crate::domain::value_objects::TenantId;
+use crate::error::Result;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TenantSettingsRepository: Send + Sync {
+    /// Find the settings of a tenant, if any were saved
+    async fn find_by_tenant(&self, tenant_id: &TenantId) -> Result<Option<TenantSettings>>;
+
+    /// Save tenant settings (create or update)
+    async fn save(&self, settings: &TenantSettings) -> Result<()>;
+}
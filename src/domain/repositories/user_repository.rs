@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use crate::domain::entities::User;
 use crate::domain::value_objects::{UserId, TenantId, Username};
 use crate::error::Result;
@@ -8,6 +9,9 @@ use crate::error::Result;
 pub trait UserRepository: Send + Sync {
     /// Find a user by their ID
     async fn find_by_id(&self, id: UserId) -> Result<Option<User>>;
+
+    /// Find users by a set of IDs in a single query, keyed by user ID
+    async fn find_by_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, User>>;
     
     /// Find a user by tenant ID and username
     async fn find_by_tenant_and_username(&self, tenant_id: TenantId, username: &str) -> Result<Option<User>>;
@@ -4,6 +4,7 @@ use crate::domain::value_objects::{TenantId, ConfigId};
 use crate::error::PlatformError;
 
 /// Repository interface for vector configuration management
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait VectorConfigRepository: Send + Sync {
     /// Find vector configuration by ID
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::{ConfigId, VectorRecordExpiry};
+use crate::error::Result;
+
+/// Expiry of vector records in stores that cannot expire them natively
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait VectorRecordMetadataRepository: Send + Sync {
+    /// Track the expiry of records, replacing any earlier expiry of the same record
+    async fn save_all(&self, expiries: &[VectorRecordExpiry]) -> Result<()>;
+
+    /// Stop tracking records of a store's namespace
+    async fn delete(
+        &self,
+        config_id: ConfigId,
+        namespace: Option<String>,
+        record_ids: Vec<String>,
+    ) -> Result<()>;
+
+    /// Records that expired at or before `now`, soonest first
+    async fn find_expired(&self, now: DateTime<Utc>, limit: u64) -> Result<Vec<VectorRecordExpiry>>;
+}
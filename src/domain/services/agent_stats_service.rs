@@ -1,10 +1,63 @@
 use std::sync::Arc;
-use chrono::Utc;
+use chrono::{Duration, NaiveDate, Utc};
 use rust_decimal::Decimal;
+use crate::domain::entities::AgentDailyStats;
 use crate::domain::repositories::AgentDailyStatsRepository;
 use crate::domain::value_objects::{AgentId, TenantId};
 use crate::error::Result;
 
+/// Largest value the `DECIMAL(20, 8)` revenue column can hold
+pub const MAX_DAILY_REVENUE: Decimal = Decimal::from_parts(
+    // 99_999_999_999_999_999_999 split into 32-bit words
+    0x630F_FFFF, 0x6BC7_5E2D, 0x0000_0005, false, 8,
+);
+
+/// Days covered by `rolling_summary`, including today
+pub const ROLLING_SUMMARY_DAYS: i64 = 30;
+
+/// Totals of an agent's daily statistics over a date range
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentStatsSummary {
+    pub agent_id: AgentId,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub interview_count: i64,
+    pub interview_passed_count: i64,
+    pub employment_count: i64,
+    pub session_count: i64,
+    pub message_count: i64,
+    pub token_count: i64,
+    pub revenue: Decimal,
+}
+
+impl AgentStatsSummary {
+    pub fn from_daily(
+        agent_id: AgentId,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        daily: &[AgentDailyStats],
+    ) -> Self {
+        let sum = |count: fn(&AgentDailyStats) -> i64| {
+            daily.iter().fold(0i64, |total, stats| total.saturating_add(count(stats)))
+        };
+
+        Self {
+            agent_id,
+            start_date,
+            end_date,
+            interview_count: sum(|s| s.interview_count),
+            interview_passed_count: sum(|s| s.interview_passed_count),
+            employment_count: sum(|s| s.employment_count),
+            session_count: sum(|s| s.session_count),
+            message_count: sum(|s| s.message_count),
+            token_count: sum(|s| s.token_count),
+            revenue: daily
+                .iter()
+                .fold(Decimal::ZERO, |total, stats| total.saturating_add(stats.revenue)),
+        }
+    }
+}
+
 /// Domain service for managing agent statistics
 pub struct AgentStatsService {
     stats_repo: Arc<dyn AgentDailyStatsRepository>,
@@ -81,14 +134,143 @@ impl AgentStatsService {
         Ok(())
     }
 
-    /// Record revenue
+    /// Record revenue. The amount is clamped to the column range and added
+    /// in a single upsert, so concurrent calls do not lose updates.
     pub async fn record_revenue(&self, agent_id: AgentId, tenant_id: TenantId, amount: Decimal) -> Result<()> {
+        let amount = Self::clamp_revenue(amount);
+        if amount.is_zero() {
+            return Ok(());
+        }
+
         let today = Utc::now().date_naive();
-        let mut stats = self.stats_repo.get_or_create(&agent_id, &tenant_id, today).await?;
-        
-        stats.add_revenue(amount);
-        self.stats_repo.update(&stats).await?;
-        
-        Ok(())
+        self.stats_repo.add_revenue(&agent_id, &tenant_id, today, amount).await
+    }
+
+    /// Revenue of a reply priced per 1K tokens, saturating instead of
+    /// overflowing
+    pub fn revenue_for_tokens(price_per_1k: Decimal, total_tokens: u32) -> Decimal {
+        if price_per_1k <= Decimal::ZERO || total_tokens == 0 {
+            return Decimal::ZERO;
+        }
+
+        let revenue = price_per_1k
+            .checked_mul(Decimal::from(total_tokens))
+            .and_then(|total| total.checked_div(Decimal::ONE_THOUSAND))
+            .unwrap_or(MAX_DAILY_REVENUE);
+
+        Self::clamp_revenue(revenue)
+    }
+
+    fn clamp_revenue(amount: Decimal) -> Decimal {
+        amount.round_dp(8).clamp(Decimal::ZERO, MAX_DAILY_REVENUE)
+    }
+
+    /// Totals for a single day
+    pub async fn daily_summary(&self, agent_id: AgentId, date: NaiveDate) -> Result<AgentStatsSummary> {
+        let daily: Vec<AgentDailyStats> = self
+            .stats_repo
+            .find_by_agent_and_date(&agent_id, date)
+            .await?
+            .into_iter()
+            .collect();
+
+        Ok(AgentStatsSummary::from_daily(agent_id, date, date, &daily))
+    }
+
+    /// Totals for the last `ROLLING_SUMMARY_DAYS` days, including today
+    pub async fn rolling_summary(&self, agent_id: AgentId) -> Result<AgentStatsSummary> {
+        let end_date = Utc::now().date_naive();
+        let start_date = end_date - Duration::days(ROLLING_SUMMARY_DAYS - 1);
+        let daily = self
+            .stats_repo
+            .find_by_agent_and_date_range(&agent_id, start_date, end_date)
+            .await?;
+
+        Ok(AgentStatsSummary::from_daily(agent_id, start_date, end_date, &daily))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use mockall::mock;
+    use mockall::predicate::*;
+
+    mock! {
+        StatsRepo {}
+
+        #[async_trait]
+        impl AgentDailyStatsRepository for StatsRepo {
+            async fn create(&self, stats: &AgentDailyStats) -> Result<AgentDailyStats>;
+            async fn update(&self, stats: &AgentDailyStats) -> Result<AgentDailyStats>;
+            async fn find_by_agent_and_date(&self, agent_id: &AgentId, stat_date: NaiveDate) -> Result<Option<AgentDailyStats>>;
+            async fn find_by_agent_and_date_range(&self, agent_id: &AgentId, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<AgentDailyStats>>;
+            async fn find_by_tenant_and_date(&self, tenant_id: &TenantId, stat_date: NaiveDate) -> Result<Vec<AgentDailyStats>>;
+            async fn find_by_tenant_and_date_range(&self, tenant_id: &TenantId, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<AgentDailyStats>>;
+            async fn get_or_create(&self, agent_id: &AgentId, tenant_id: &TenantId, stat_date: NaiveDate) -> Result<AgentDailyStats>;
+            async fn add_revenue(&self, agent_id: &AgentId, tenant_id: &TenantId, stat_date: NaiveDate, amount: Decimal) -> Result<()>;
+        }
+    }
+
+    #[test]
+    fn test_max_daily_revenue_matches_column() {
+        assert_eq!(MAX_DAILY_REVENUE.to_string(), "999999999999.99999999");
+    }
+
+    #[test]
+    fn test_revenue_for_tokens() {
+        assert_eq!(
+            AgentStatsService::revenue_for_tokens(Decimal::new(2, 2), 1500),
+            Decimal::new(3, 2)
+        );
+        assert_eq!(AgentStatsService::revenue_for_tokens(Decimal::ZERO, u32::MAX), Decimal::ZERO);
+        assert_eq!(AgentStatsService::revenue_for_tokens(Decimal::new(-5, 0), 1000), Decimal::ZERO);
+        assert_eq!(
+            AgentStatsService::revenue_for_tokens(Decimal::MAX, u32::MAX),
+            MAX_DAILY_REVENUE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_revenue_upserts_clamped_amount() {
+        let agent_id = AgentId::new();
+        let tenant_id = TenantId::new();
+        let mut repo = MockStatsRepo::new();
+        repo.expect_add_revenue()
+            .with(eq(agent_id), eq(tenant_id), always(), eq(MAX_DAILY_REVENUE))
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+        let service = AgentStatsService::new(Arc::new(repo));
+
+        service.record_revenue(agent_id, tenant_id, Decimal::MAX).await.unwrap();
+        // Nothing to add, so no round trip
+        service.record_revenue(agent_id, tenant_id, Decimal::ZERO).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rolling_summary_totals_the_last_30_days() {
+        let agent_id = AgentId::new();
+        let tenant_id = TenantId::new();
+        let today = Utc::now().date_naive();
+        let mut first = AgentDailyStats::new(agent_id, tenant_id, today - Duration::days(29));
+        first.add_tokens(100);
+        first.add_revenue(Decimal::new(150, 2));
+        let mut second = AgentDailyStats::new(agent_id, tenant_id, today);
+        second.add_tokens(50);
+        second.increment_session();
+        second.add_revenue(Decimal::new(25, 2));
+
+        let mut repo = MockStatsRepo::new();
+        repo.expect_find_by_agent_and_date_range()
+            .with(eq(agent_id), eq(today - Duration::days(29)), eq(today))
+            .returning(move |_, _, _| Ok(vec![first.clone(), second.clone()]));
+        let service = AgentStatsService::new(Arc::new(repo));
+
+        let summary = service.rolling_summary(agent_id).await.unwrap();
+
+        assert_eq!(summary.token_count, 150);
+        assert_eq!(summary.session_count, 1);
+        assert_eq!(summary.revenue, Decimal::new(175, 2));
     }
 }
@@ -19,8 +19,12 @@ pub trait APIKeyService: Send + Sync {
         name: String,
         permission_scope: PermissionScope,
         expires_at: Option<DateTime<Utc>>,
+        rate_limit_rpm: Option<u32>,
     ) -> Result<(APIKey, APIKeyToken)>;
     
+    /// Generate a new token for an existing API key, keeping its permissions
+    async fn rotate_api_key(&self, api_key: APIKey) -> Result<(APIKey, APIKeyToken)>;
+
     /// Validate an API key token and return the API key entity
     async fn validate_and_get_key(&self, token: &str) -> Result<APIKey>;
     
@@ -54,6 +58,7 @@ impl APIKeyService for APIKeyDomainService {
         name: String,
         permission_scope: PermissionScope,
         expires_at: Option<DateTime<Utc>>,
+        rate_limit_rpm: Option<u32>,
     ) -> Result<(APIKey, APIKeyToken)> {
         // Generate a cryptographically secure token
         let token = APIKeyToken::generate()?;
@@ -62,7 +67,7 @@ impl APIKeyService for APIKeyDomainService {
         let key_hash = token.hash();
         
         // Create the API key entity
-        let api_key = APIKey::new(
+        let mut api_key = APIKey::new(
             tenant_id,
             user_id,
             name,
@@ -70,6 +75,7 @@ impl APIKeyService for APIKeyDomainService {
             permission_scope,
             expires_at,
         )?;
+        api_key.update_rate_limit(rate_limit_rpm)?;
         
         // Save to repository
         self.repository.save(&api_key).await?;
@@ -77,6 +83,16 @@ impl APIKeyService for APIKeyDomainService {
         // Return both the entity and the plain token (only time it's returned)
         Ok((api_key, token))
     }
+
+    async fn rotate_api_key(&self, mut api_key: APIKey) -> Result<(APIKey, APIKeyToken)> {
+        let token = APIKeyToken::generate()?;
+
+        // Overwriting the hash invalidates the old token immediately
+        api_key.rotate(token.hash());
+        self.repository.update(&api_key).await?;
+
+        Ok((api_key, token))
+    }
     
     async fn validate_and_get_key(&self, token: &str) -> Result<APIKey> {
         // Validate token format
@@ -127,6 +143,37 @@ mod tests {
     use crate::domain::repositories::MockAPIKeyRepository;
     use crate::domain::value_objects::{AgentId, FlowId};
     
+    #[tokio::test]
+    async fn test_rotate_api_key_replaces_hash() {
+        let old_token = APIKeyToken::generate().unwrap();
+        let agent_id = AgentId::new().0;
+        let api_key = APIKey::new(
+            TenantId::new(),
+            UserId::new(),
+            "Test API Key".to_string(),
+            old_token.hash(),
+            PermissionScope::new(vec![agent_id], vec![], vec![], vec![]),
+            None,
+        ).unwrap();
+        let old_hash = old_token.hash();
+
+        let mut mock_repo = MockAPIKeyRepository::new();
+        mock_repo
+            .expect_update()
+            .times(1)
+            .withf(move |key| key.key_hash != old_hash)
+            .returning(|_| Ok(()));
+
+        let service = APIKeyDomainService::new(Arc::new(mock_repo));
+        let (rotated, new_token) = service.rotate_api_key(api_key.clone()).await.unwrap();
+
+        assert_eq!(rotated.id, api_key.id);
+        assert_eq!(rotated.key_hash, new_token.hash());
+        assert_ne!(rotated.key_hash, old_token.hash());
+        assert_eq!(rotated.permission_scope, api_key.permission_scope);
+        assert!(new_token.as_str().starts_with("pk_"));
+    }
+
     #[tokio::test]
     async fn test_create_api_key() {
         let mut mock_repo = MockAPIKeyRepository::new();
@@ -149,6 +196,7 @@ mod tests {
             "Test API Key".to_string(),
             permission_scope,
             None,
+            None,
         ).await;
         
         assert!(result.is_ok());
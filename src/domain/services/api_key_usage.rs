@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use crate::domain::entities::APIKeyUsageDelta;
+use crate::domain::value_objects::APIKeyId;
+use crate::error::Result;
+
+/// Fast per-request API key usage counters and rate limits. Counts are
+/// kept here and drained into the database periodically.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait APIKeyUsageCounter: Send + Sync {
+    /// Count one request, and the LLM tokens it consumed, against the key
+    async fn record(&self, api_key_id: APIKeyId, tokens_used: u64, at: DateTime<Utc>) -> Result<()>;
+
+    /// Take one request from the key's per-minute allowance, returning how
+    /// long to wait when it is used up
+    async fn acquire(&self, api_key_id: APIKeyId, requests_per_minute: u32) -> Result<Option<Duration>>;
+
+    /// Remove and return the usage recorded since the previous drain
+    async fn drain(&self) -> Result<Vec<APIKeyUsageDelta>>;
+
+    /// Add drained usage back, so the next drain returns it again when it
+    /// could not be stored
+    async fn restore(&self, usage: &[APIKeyUsageDelta]) -> Result<()>;
+}
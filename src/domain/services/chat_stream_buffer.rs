@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::SessionId;
+use crate::error::Result;
+
+/// Number of most recent events kept per chat stream for resumption
+pub const CHAT_STREAM_BUFFER_SIZE: usize = 1000;
+
+/// One event of a chat stream as sent to the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatStreamEvent {
+    /// Position in the stream, starting at 1
+    pub id: u64,
+    /// `content`, `reasoning`, `done` or `error`
+    pub event: String,
+    /// JSON payload
+    pub data: String,
+}
+
+impl ChatStreamEvent {
+    /// Whether the stream ends with this event
+    pub fn is_terminal(&self) -> bool {
+        self.event == "done" || self.event == "error"
+    }
+}
+
+/// Keeps the tail of each session's latest chat stream so a client that
+/// lost its connection can pick up where it left off.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ChatStreamBuffer: Send + Sync {
+    /// Add an event to the session's stream. The first event of a stream
+    /// (id 1) replaces whatever was buffered before.
+    async fn append(&self, session_id: SessionId, event: ChatStreamEvent) -> Result<()>;
+
+    /// Buffered events of the session's stream with an id greater than `after_id`
+    async fn replay(&self, session_id: SessionId, after_id: u64) -> Result<Vec<ChatStreamEvent>>;
+}
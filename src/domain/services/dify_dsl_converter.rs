@@ -0,0 +1,365 @@
+use serde_json::{json, Map, Value};
+
+use crate::domain::services::DifyDSLParser;
+use crate::domain::value_objects::{
+    FlowDefinition, FlowEdge, FlowGraph, FlowNode, FlowWorkflow, NodePosition, NodeType,
+};
+use crate::error::{PlatformError, Result};
+
+/// Dify DSL versions the converter reads and writes
+pub const SUPPORTED_DIFY_DSL_VERSIONS: &[&str] = &["0.1.0"];
+
+/// Version written on export
+pub const DIFY_DSL_VERSION: &str = "0.1.0";
+
+/// Flow decoded from a Dify DSL document
+#[derive(Debug, Clone)]
+pub struct DifyDslImport {
+    pub name: String,
+    pub description: Option<String>,
+    pub definition: FlowDefinition,
+}
+
+/// Converts between flow definitions and Dify app DSL documents
+/// (`kind: app`, `workflow.graph`, `workflow.environment_variables`).
+pub struct DifyDslConverter;
+
+impl DifyDslConverter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Build a Dify DSL document from a flow definition
+    pub fn export(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        definition: &FlowDefinition,
+    ) -> Value {
+        let nodes: Vec<Value> = definition
+            .workflow
+            .graph
+            .nodes
+            .iter()
+            .map(|node| self.export_node(node))
+            .collect();
+
+        let edges: Vec<Value> = definition
+            .workflow
+            .graph
+            .edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "id": edge.id,
+                    "source": edge.source,
+                    "target": edge.target,
+                    "sourceHandle": edge.source_handle,
+                    "targetHandle": edge.target_handle,
+                    "type": "custom",
+                })
+            })
+            .collect();
+
+        json!({
+            "app": {
+                "name": name,
+                "description": description.unwrap_or_default(),
+                "mode": "workflow",
+            },
+            "kind": "app",
+            "version": DIFY_DSL_VERSION,
+            "workflow": {
+                "environment_variables": definition.workflow.environment_variables,
+                "graph": {
+                    "nodes": nodes,
+                    "edges": edges,
+                },
+            },
+        })
+    }
+
+    /// Parse a Dify DSL document into a flow definition
+    pub fn import(&self, dsl: &Value) -> Result<DifyDslImport> {
+        let version = dsl.get("version").and_then(|v| v.as_str()).unwrap_or_default();
+        if !SUPPORTED_DIFY_DSL_VERSIONS.contains(&version) {
+            return Err(PlatformError::ValidationError(format!(
+                "Unsupported Dify DSL version '{}'. Supported versions: {}",
+                version,
+                SUPPORTED_DIFY_DSL_VERSIONS.join(", ")
+            )));
+        }
+
+        let app = dsl.get("app").cloned().unwrap_or(Value::Null);
+        let name = app
+            .get("name")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| {
+                PlatformError::ValidationError("Dify DSL is missing 'app.name'".to_string())
+            })?
+            .to_string();
+        let description = app
+            .get("description")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        let workflow = dsl.get("workflow").ok_or_else(|| {
+            PlatformError::ValidationError("Dify DSL is missing 'workflow'".to_string())
+        })?;
+        let graph = workflow.get("graph").ok_or_else(|| {
+            PlatformError::ValidationError("Dify DSL is missing 'workflow.graph'".to_string())
+        })?;
+
+        let nodes = graph
+            .get("nodes")
+            .and_then(|v| v.as_array())
+            .map(|nodes| nodes.iter().map(|n| self.import_node(n)).collect::<Result<Vec<_>>>())
+            .transpose()?
+            .unwrap_or_default();
+
+        let edges = graph
+            .get("edges")
+            .and_then(|v| v.as_array())
+            .map(|edges| edges.iter().map(|e| self.import_edge(e)).collect::<Result<Vec<_>>>())
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut environment_variables = workflow
+            .get("environment_variables")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let parser = DifyDSLParser::new();
+        parser.parse_environment_variables(&environment_variables)?;
+        parser.redact_secret_values(&mut environment_variables);
+
+        let definition = FlowDefinition {
+            workflow: FlowWorkflow {
+                graph: FlowGraph { nodes, edges },
+                environment_variables,
+            },
+        };
+
+        definition
+            .validate()
+            .map_err(PlatformError::ValidationError)?;
+
+        Ok(DifyDslImport {
+            name,
+            description,
+            definition,
+        })
+    }
+
+    fn export_node(&self, node: &FlowNode) -> Value {
+        let mut data = match &node.data {
+            Value::Object(map) => map.clone(),
+            _ => Map::new(),
+        };
+        data.insert("type".to_string(), json!(Self::dify_node_type(&node.node_type)));
+
+        let mut exported = json!({
+            "id": node.id,
+            "type": "custom",
+            "data": data,
+            "position": { "x": node.position.x, "y": node.position.y },
+        });
+        if let Some(parent_id) = &node.parent_id {
+            exported["parentId"] = json!(parent_id);
+        }
+        exported
+    }
+
+    fn import_node(&self, node: &Value) -> Result<FlowNode> {
+        let id = node
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                PlatformError::ValidationError("Dify DSL node is missing 'id'".to_string())
+            })?
+            .to_string();
+
+        let data = node.get("data").cloned().unwrap_or_else(|| json!({}));
+        let dify_type = data.get("type").and_then(|v| v.as_str()).ok_or_else(|| {
+            PlatformError::ValidationError(format!("Node '{}' is missing 'data.type'", id))
+        })?;
+        let node_type = Self::node_type_from_dify(dify_type).ok_or_else(|| {
+            PlatformError::ValidationError(format!(
+                "Unknown node type '{}' on node '{}'. Supported types: {}",
+                dify_type,
+                id,
+                Self::supported_dify_node_types().join(", ")
+            ))
+        })?;
+
+        let position = node
+            .get("position")
+            .map(|p| NodePosition {
+                x: p.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                y: p.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            })
+            .unwrap_or(NodePosition { x: 0.0, y: 0.0 });
+
+        Ok(FlowNode {
+            id,
+            parent_id: node
+                .get("parentId")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            node_type,
+            data,
+            position,
+        })
+    }
+
+    fn import_edge(&self, edge: &Value) -> Result<FlowEdge> {
+        let field = |name: &str| edge.get(name).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+        let source = field("source").ok_or_else(|| {
+            PlatformError::ValidationError("Dify DSL edge is missing 'source'".to_string())
+        })?;
+        let target = field("target").ok_or_else(|| {
+            PlatformError::ValidationError("Dify DSL edge is missing 'target'".to_string())
+        })?;
+
+        Ok(FlowEdge {
+            id: field("id").unwrap_or_else(|| format!("{}-{}", source, target)),
+            source,
+            target,
+            source_handle: field("sourceHandle"),
+            target_handle: field("targetHandle"),
+        })
+    }
+
+    fn dify_node_type(node_type: &NodeType) -> &'static str {
+        match node_type {
+            NodeType::Start => "start",
+            NodeType::End => "end",
+            NodeType::Llm => "llm",
+            NodeType::VectorSearch => "knowledge-retrieval",
+            NodeType::McpTool => "tool",
+            NodeType::Condition => "if-else",
+            NodeType::Loop => "loop",
+            NodeType::Variable => "variable-assigner",
+            NodeType::HttpRequest => "http-request",
+            NodeType::Code => "code",
+            NodeType::Answer => "answer",
+            NodeType::ParameterExtractor => "parameter-extractor",
+            NodeType::Iteration => "iteration",
+            NodeType::DocumentIngestion => "document-ingestion",
+            NodeType::KnowledgeBaseRetrieval => "knowledge-base-retrieval",
+            NodeType::Embedding => "embedding",
+        }
+    }
+
+    fn node_type_from_dify(dify_type: &str) -> Option<NodeType> {
+        let node_type = match dify_type {
+            "start" | "iteration-start" => NodeType::Start,
+            "end" => NodeType::End,
+            "llm" => NodeType::Llm,
+            "knowledge-retrieval" => NodeType::VectorSearch,
+            "tool" => NodeType::McpTool,
+            "if-else" => NodeType::Condition,
+            "loop" => NodeType::Loop,
+            "variable-assigner" | "variable-aggregator" | "assigner" => NodeType::Variable,
+            "http-request" => NodeType::HttpRequest,
+            "code" => NodeType::Code,
+            "answer" => NodeType::Answer,
+            "parameter-extractor" => NodeType::ParameterExtractor,
+            "iteration" => NodeType::Iteration,
+            "document-ingestion" => NodeType::DocumentIngestion,
+            "knowledge-base-retrieval" => NodeType::KnowledgeBaseRetrieval,
+            "embedding" => NodeType::Embedding,
+            _ => return None,
+        };
+        Some(node_type)
+    }
+
+    fn supported_dify_node_types() -> Vec<&'static str> {
+        vec![
+            "start", "iteration-start", "end", "llm", "knowledge-retrieval", "tool", "if-else",
+            "loop", "variable-assigner", "variable-aggregator", "assigner", "http-request", "code",
+            "answer", "parameter-extractor", "iteration", "document-ingestion",
+            "knowledge-base-retrieval", "embedding",
+        ]
+    }
+}
+
+impl Default for DifyDslConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_dsl() -> Value {
+        json!({
+            "app": { "name": "Support bot", "description": "Answers questions", "mode": "workflow" },
+            "kind": "app",
+            "version": "0.1.0",
+            "workflow": {
+                "environment_variables": [
+                    { "name": "api_base", "value_type": "string", "value": "https://example.com" }
+                ],
+                "graph": {
+                    "nodes": [
+                        { "id": "start", "type": "custom", "data": { "type": "start", "title": "Start" }, "position": { "x": 0.0, "y": 0.0 } },
+                        { "id": "llm", "type": "custom", "data": { "type": "llm", "title": "LLM" }, "position": { "x": 200.0, "y": 0.0 } },
+                        { "id": "answer", "type": "custom", "data": { "type": "answer", "answer": "{{#llm.text#}}" }, "position": { "x": 400.0, "y": 0.0 } }
+                    ],
+                    "edges": [
+                        { "id": "e1", "source": "start", "target": "llm", "sourceHandle": "source", "targetHandle": "target" },
+                        { "id": "e2", "source": "llm", "target": "answer", "sourceHandle": "source", "targetHandle": "target" }
+                    ]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_import_export_round_trip() {
+        let converter = DifyDslConverter::new();
+        let imported = converter.import(&sample_dsl()).unwrap();
+
+        assert_eq!(imported.name, "Support bot");
+        assert_eq!(imported.definition.workflow.graph.nodes.len(), 3);
+        assert_eq!(imported.definition.workflow.graph.nodes[1].node_type, NodeType::Llm);
+        assert_eq!(imported.definition.workflow.environment_variables.len(), 1);
+
+        let exported = converter.export(
+            &imported.name,
+            imported.description.as_deref(),
+            &imported.definition,
+        );
+        assert_eq!(exported["version"], DIFY_DSL_VERSION);
+        assert_eq!(exported["workflow"]["graph"]["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(exported["workflow"]["graph"]["edges"].as_array().unwrap().len(), 2);
+        assert_eq!(exported["workflow"]["environment_variables"][0]["name"], "api_base");
+
+        let reimported = converter.import(&exported).unwrap();
+        assert_eq!(reimported.definition, imported.definition);
+    }
+
+    #[test]
+    fn test_import_rejects_unsupported_version() {
+        let mut dsl = sample_dsl();
+        dsl["version"] = json!("0.2.0");
+
+        let error = DifyDslConverter::new().import(&dsl).unwrap_err().to_string();
+        assert!(error.contains("Unsupported Dify DSL version '0.2.0'"));
+    }
+
+    #[test]
+    fn test_import_reports_unknown_node_type() {
+        let mut dsl = sample_dsl();
+        dsl["workflow"]["graph"]["nodes"][1]["data"]["type"] = json!("question-classifier");
+
+        let error = DifyDslConverter::new().import(&dsl).unwrap_err().to_string();
+        assert!(error.contains("Unknown node type 'question-classifier' on node 'llm'"));
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use crate::domain::value_objects::{FlowDefinition, FlowNode, FlowEdge, FlowVariable, FlowMetadata, NodeType, VariableType, NodePosition};
+use crate::domain::value_objects::{
+    EnvironmentVariable, FlowDefinition, FlowNode, FlowEdge, FlowVariable, FlowMetadata, NodeType,
+    VariableType, NodePosition,
+};
 use crate::domain::{ FlowWorkflow, FlowGraph };
 use crate::error::Result;
 
@@ -13,6 +16,9 @@ pub struct DifyDSL {
     pub edges: Vec<DifyEdge>,
     #[serde(default)]
     pub variables: Vec<DifyVariable>,
+    /// Dify `environment_variables`, entries of `{name, value_type, value}`
+    #[serde(default)]
+    pub environment_variables: Vec<Value>,
     #[serde(default)]
     pub metadata: DifyMetadata,
 }
@@ -108,12 +114,17 @@ impl DifyDSLParser {
         // Convert metadata
         let metadata = self.convert_metadata(dsl.metadata);
 
+        let mut environment_variables = dsl.environment_variables;
+        self.parse_environment_variables(&environment_variables)?;
+        self.redact_secret_values(&mut environment_variables);
+
         let definition = FlowDefinition {
             workflow: FlowWorkflow {
                 graph: FlowGraph {
                     nodes,
                     edges,
                 },
+                environment_variables,
             },
             // variables,
             // metadata,
@@ -123,9 +134,37 @@ impl DifyDSLParser {
         definition.validate()
             .map_err(|e| crate::error::PlatformError::ValidationError(e))?;
 
+        definition.workflow.graph.validate()
+            .map_err(|errors| crate::error::PlatformError::ValidationError(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")
+            ))?;
+
         Ok(definition)
     }
 
+    /// Parse a Dify `environment_variables` block. Secret values are dropped;
+    /// they are read from the tenant's secret store when the flow runs.
+    pub fn parse_environment_variables(&self, variables: &[Value]) -> Result<Vec<EnvironmentVariable>> {
+        let workflow = FlowWorkflow {
+            graph: FlowGraph { nodes: Vec::new(), edges: Vec::new() },
+            environment_variables: variables.to_vec(),
+        };
+
+        workflow.parsed_environment_variables()
+            .map_err(crate::error::PlatformError::ValidationError)
+    }
+
+    /// Blank the value of secret entries so they are not stored with the flow
+    pub fn redact_secret_values(&self, variables: &mut [Value]) {
+        for variable in variables.iter_mut() {
+            if variable.get("value_type").and_then(Value::as_str) == Some("secret") {
+                if let Some(entry) = variable.as_object_mut() {
+                    entry.insert("value".to_string(), Value::String(String::new()));
+                }
+            }
+        }
+    }
+
     fn validate_dsl_version(&self, version: &str) -> Result<()> {
         // Support versions 1.x and 2.x
         if !version.starts_with("1.") && !version.starts_with("2.") {
@@ -186,6 +225,7 @@ impl DifyDSLParser {
         let node_type = match dify_type.to_lowercase().as_str() {
             "start" => NodeType::Start,
             "end" => NodeType::End,
+            "answer" => NodeType::Answer,
             "llm" | "llm-chat" | "llm_chat" => NodeType::Llm,
             "knowledge-retrieval" | "knowledge_retrieval" | "vector-search" | "vector_search" => NodeType::VectorSearch,
             "tool" | "mcp-tool" | "mcp_tool" => NodeType::McpTool,
@@ -194,6 +234,9 @@ impl DifyDSLParser {
             "variable" | "variable-assigner" | "variable_assigner" => NodeType::Variable,
             "http-request" | "http_request" | "http" => NodeType::HttpRequest,
             "code" | "code-executor" | "code_executor" => NodeType::Code,
+            "document-ingestion" | "document_ingestion" => NodeType::DocumentIngestion,
+            "knowledge-base-retrieval" | "knowledge_base_retrieval" => NodeType::KnowledgeBaseRetrieval,
+            "embedding" => NodeType::Embedding,
             _ => {
                 return Err(crate::error::PlatformError::ValidationError(
                     format!("Unknown node type: {}", dify_type)
@@ -280,6 +323,7 @@ impl Default for DifyDSLParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::value_objects::ValidationError;
 
     #[test]
     fn test_parse_simple_dsl() {
@@ -465,4 +509,150 @@ mod tests {
         // assert_eq!(definition.variables.len(), 1);
         // assert_eq!(definition.variables[0].name, "input_text");
     }
+
+    #[test]
+    fn test_parse_environment_variables() {
+        let parser = DifyDSLParser::new();
+        let variables = parser
+            .parse_environment_variables(&[
+                serde_json::json!({ "name": "API_BASE", "value_type": "string", "value": "https://api.example.com" }),
+                serde_json::json!({ "name": "RETRIES", "value_type": "number", "value": "3" }),
+                serde_json::json!({ "name": "API_KEY", "value_type": "secret", "value": "sk-leaked" }),
+            ])
+            .unwrap();
+
+        assert_eq!(variables.len(), 3);
+        assert_eq!(variables[0].state_key(), "env.API_BASE");
+        assert_eq!(variables[1].value, serde_json::json!(3.0));
+        assert!(variables[2].is_secret());
+        assert_eq!(variables[2].value, Value::Null);
+    }
+
+    #[test]
+    fn test_parse_environment_variables_rejects_invalid_entries() {
+        let parser = DifyDSLParser::new();
+
+        let unknown_type = serde_json::json!({ "name": "A", "value_type": "boolean", "value": true });
+        assert!(parser.parse_environment_variables(&[unknown_type]).is_err());
+
+        let bad_number = serde_json::json!({ "name": "A", "value_type": "number", "value": "many" });
+        assert!(parser.parse_environment_variables(&[bad_number]).is_err());
+
+        let bad_name = serde_json::json!({ "name": "MY-KEY", "value_type": "string", "value": "" });
+        assert!(parser.parse_environment_variables(&[bad_name]).is_err());
+
+        let duplicate = serde_json::json!({ "name": "A", "value_type": "string", "value": "" });
+        assert!(parser.parse_environment_variables(&[duplicate.clone(), duplicate]).is_err());
+    }
+
+    #[test]
+    fn test_parse_keeps_environment_variables_without_secret_values() {
+        let dsl_json = r#"{
+            "version": "1.0",
+            "kind": "workflow",
+            "nodes": [
+                { "id": "start", "type": "start", "title": "Start", "data": {} },
+                { "id": "answer", "type": "answer", "title": "Answer", "data": {} }
+            ],
+            "edges": [{ "id": "e1", "source": "start", "target": "answer" }],
+            "environment_variables": [
+                { "name": "REGION", "value_type": "string", "value": "eu" },
+                { "name": "API_KEY", "value_type": "secret", "value": "sk-leaked" }
+            ]
+        }"#;
+
+        let definition = DifyDSLParser::new().parse(dsl_json).unwrap();
+        let stored = &definition.workflow.environment_variables;
+
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0]["value"], "eu");
+        assert_eq!(stored[1]["value"], "");
+    }
+
+    fn node(id: &str, node_type: NodeType) -> FlowNode {
+        FlowNode {
+            id: id.to_string(),
+            parent_id: None,
+            node_type,
+            data: Value::Null,
+            position: NodePosition { x: 0.0, y: 0.0 },
+        }
+    }
+
+    fn edge(source: &str, target: &str, handle: Option<&str>) -> FlowEdge {
+        FlowEdge {
+            id: format!("{}-{}", source, target),
+            source: source.to_string(),
+            target: target.to_string(),
+            source_handle: handle.map(str::to_string),
+            target_handle: None,
+        }
+    }
+
+    #[test]
+    fn test_graph_validate_accepts_condition_and_loop() {
+        let graph = FlowGraph {
+            nodes: vec![
+                node("start", NodeType::Start),
+                node("check", NodeType::Condition),
+                node("loop", NodeType::Loop),
+                node("body", NodeType::Llm),
+                node("answer", NodeType::Answer),
+                node("end", NodeType::End),
+            ],
+            edges: vec![
+                edge("start", "check", None),
+                edge("check", "loop", Some("true")),
+                edge("check", "end", Some("false")),
+                edge("loop", "body", Some("loop")),
+                edge("body", "loop", None),
+                edge("loop", "answer", Some("exit")),
+            ],
+        };
+
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_graph_validate_reports_all_errors() {
+        let graph = FlowGraph {
+            nodes: vec![
+                node("start", NodeType::Start),
+                node("second_start", NodeType::Start),
+                node("a", NodeType::Llm),
+                node("b", NodeType::Code),
+                node("after", NodeType::Answer),
+                node("orphan", NodeType::Variable),
+                node("check", NodeType::Condition),
+            ],
+            edges: vec![
+                edge("start", "a", None),
+                edge("a", "b", None),
+                edge("b", "a", None),
+                edge("b", "after", None),
+                edge("start", "check", None),
+                edge("check", "after", Some("true")),
+                edge("check", "missing", Some("false")),
+            ],
+        };
+
+        let errors = graph.validate().unwrap_err();
+
+        assert!(errors.contains(&ValidationError::MultipleStartNodes {
+            node_ids: vec!["start".to_string(), "second_start".to_string()],
+        }));
+        assert!(errors.contains(&ValidationError::UnknownEdgeNode {
+            edge_id: "check-missing".to_string(),
+            node_id: "missing".to_string(),
+        }));
+        assert!(errors.contains(&ValidationError::OrphanNode { node_id: "orphan".to_string() }));
+        assert!(errors.contains(&ValidationError::InvalidConditionBranches {
+            node_id: "check".to_string(),
+            handles: vec!["true".to_string()],
+        }));
+        assert!(errors.contains(&ValidationError::Cycle {
+            node_ids: vec!["a".to_string(), "b".to_string()],
+        }));
+        assert!(!errors.contains(&ValidationError::MissingEndNode));
+    }
 }
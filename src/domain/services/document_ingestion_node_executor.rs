@@ -0,0 +1,359 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::repositories::llm_config_repository::LLMConfigRepository;
+use crate::domain::services::execution_engine::{
+    ExecutionState, NodeExecutionResult, NodeExecutionStatus, NodeExecutor,
+};
+use crate::domain::services::llm_service::LLMDomainService;
+use crate::domain::services::vector_service::VectorStoreDomainService;
+use crate::domain::value_objects::ids::TenantId;
+use crate::domain::value_objects::{FlowNode, ModelConfig, NodeType, VectorRecord};
+use crate::domain::ConfigId;
+use crate::error::{PlatformError, Result};
+
+const DEFAULT_CHUNK_SIZE: usize = 1000;
+const DEFAULT_CHUNK_OVERLAP: usize = 200;
+/// Number of embedded chunks upserted per vector store call
+const UPSERT_BATCH_SIZE: usize = 32;
+
+/// Document Ingestion node executor - splits a document, embeds every chunk
+/// and upserts the vectors into the tenant's vector store
+pub struct DocumentIngestionNodeExecutor {
+    llm_service: Arc<dyn LLMDomainService>,
+    llm_config_repository: Arc<dyn LLMConfigRepository>,
+    vector_service: Arc<dyn VectorStoreDomainService>,
+    client: reqwest::Client,
+}
+
+impl DocumentIngestionNodeExecutor {
+    pub fn new(
+        llm_service: Arc<dyn LLMDomainService>,
+        llm_config_repository: Arc<dyn LLMConfigRepository>,
+        vector_service: Arc<dyn VectorStoreDomainService>,
+    ) -> Self {
+        Self {
+            llm_service,
+            llm_config_repository,
+            vector_service,
+            // Redirects could lead to internal addresses the source check never saw
+            client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    fn extract_source(&self, node: &FlowNode, state: &ExecutionState) -> Result<String> {
+        let source = node
+            .data
+            .get("source")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                PlatformError::ValidationError(
+                    "Document ingestion node missing 'source' field".to_string(),
+                )
+            })?;
+
+        let source = super::node_executors::resolve_template(source, state);
+        if source.trim().is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Document ingestion source cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(source.trim().to_string())
+    }
+
+    fn extract_chunking(&self, node: &FlowNode) -> Result<(usize, usize)> {
+        let chunk_size = node
+            .data
+            .get("chunk_size")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize)
+            .unwrap_or(DEFAULT_CHUNK_SIZE);
+
+        let chunk_overlap = node
+            .data
+            .get("chunk_overlap")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize)
+            .unwrap_or_else(|| DEFAULT_CHUNK_OVERLAP.min(chunk_size / 2));
+
+        if chunk_size == 0 {
+            return Err(PlatformError::ValidationError(
+                "chunk_size must be greater than 0".to_string(),
+            ));
+        }
+        if chunk_overlap >= chunk_size {
+            return Err(PlatformError::ValidationError(format!(
+                "chunk_overlap ({}) must be smaller than chunk_size ({})",
+                chunk_overlap, chunk_size
+            )));
+        }
+
+        Ok((chunk_size, chunk_overlap))
+    }
+
+    async fn extract_model_config(&self, node: &FlowNode) -> Result<ModelConfig> {
+        let llm_config_id = node
+            .data
+            .get("llm_config_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                PlatformError::ValidationError(
+                    "Document ingestion node missing 'llm_config_id' field".to_string(),
+                )
+            })?;
+
+        let config = self
+            .llm_config_repository
+            .find_by_id(ConfigId::from_string(llm_config_id).map_err(|e| {
+                PlatformError::ValidationError(format!(
+                    "Invalid UUID: {}. Error: {}",
+                    llm_config_id, e
+                ))
+            })?)
+            .await?
+            .ok_or_else(|| {
+                PlatformError::ValidationError(format!("LLM config not found: {}", llm_config_id))
+            })?;
+
+        Ok(config.model_config)
+    }
+
+    fn extract_tenant_id(&self, state: &ExecutionState) -> Result<TenantId> {
+        state
+            .variables
+            .get("tenant_id")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .map(TenantId::from)
+            .ok_or_else(|| {
+                PlatformError::ValidationError(
+                    "Missing or invalid tenant_id in execution context".to_string(),
+                )
+            })
+    }
+
+    /// Fetch the document from an http(s) URL. Local paths and URLs that
+    /// resolve to internal addresses are refused, so a flow cannot pull
+    /// server files or internal endpoints into a vector store.
+    async fn load_source(&self, source: &str) -> Result<String> {
+        let url = super::outbound_url::ensure_public_http_url(source).await?;
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("Failed to fetch {}: {}", source, e)))?
+            .error_for_status()
+            .map_err(|e| PlatformError::InternalError(format!("Failed to fetch {}: {}", source, e)))?;
+
+        response
+            .text()
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("Failed to read {}: {}", source, e)))
+    }
+
+    fn set_progress(node: &FlowNode, state: &mut ExecutionState, processed: usize, total: usize) {
+        state.set_variable(format!("#{}.chunks_processed#", node.id), serde_json::json!(processed));
+        state.set_variable(format!("#{}.total_chunks#", node.id), serde_json::json!(total));
+    }
+
+    fn failed(node: &FlowNode, started_at: DateTime<Utc>, error: String) -> NodeExecutionResult {
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+        NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Failed,
+            output: None,
+            error: Some(error),
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        }
+    }
+}
+
+/// Split text into windows of `chunk_size` characters, each starting
+/// `chunk_size - chunk_overlap` characters after the previous one.
+/// Callers must ensure `chunk_overlap < chunk_size`.
+pub fn split_text(text: &str, chunk_size: usize, chunk_overlap: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let step = chunk_size - chunk_overlap;
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let end = (start + chunk_size).min(chars.len());
+        let chunk: String = chars[start..end].iter().collect();
+        if !chunk.trim().is_empty() {
+            chunks.push(chunk);
+        }
+        if end == chars.len() {
+            break;
+        }
+        start += step;
+    }
+
+    chunks
+}
+
+#[async_trait]
+impl NodeExecutor for DocumentIngestionNodeExecutor {
+    async fn execute(
+        &self,
+        node: &FlowNode,
+        state: &mut ExecutionState,
+    ) -> Result<NodeExecutionResult> {
+        let started_at = Utc::now();
+
+        let source = match self.extract_source(node, state) {
+            Ok(source) => source,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let (chunk_size, chunk_overlap) = match self.extract_chunking(node) {
+            Ok(chunking) => chunking,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let model_config = match self.extract_model_config(node).await {
+            Ok(config) => config,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let tenant_id = match self.extract_tenant_id(state) {
+            Ok(id) => id,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let namespace = node
+            .data
+            .get("namespace")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let text = match self.load_source(&source).await {
+            Ok(text) => text,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let chunks = split_text(&text, chunk_size, chunk_overlap);
+        let total_chunks = chunks.len();
+        Self::set_progress(node, state, 0, total_chunks);
+
+        // Ids derive from the source so re-ingesting a document overwrites its chunks
+        let document_id = Uuid::new_v5(&Uuid::NAMESPACE_URL, source.as_bytes());
+        let mut pending = Vec::with_capacity(UPSERT_BATCH_SIZE);
+        let mut chunks_processed = 0;
+
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            let vector = match self
+                .llm_service
+                .generate_embedding(&model_config, &chunk, tenant_id.0)
+                .await
+            {
+                Ok(vector) => vector,
+                Err(e) => {
+                    return Ok(Self::failed(
+                        node,
+                        started_at,
+                        format!("Embedding chunk {} failed: {}", index, e),
+                    ))
+                }
+            };
+
+            let mut record = match VectorRecord::new(format!("{}-{}", document_id, index), vector, tenant_id) {
+                Ok(record) => record,
+                Err(e) => return Ok(Self::failed(node, started_at, e)),
+            };
+            record.metadata = HashMap::from([
+                ("text".to_string(), Value::String(chunk)),
+                ("source".to_string(), Value::String(source.clone())),
+                ("chunk_index".to_string(), serde_json::json!(index)),
+            ]);
+            record.namespace = namespace.clone();
+            pending.push(record);
+
+            if pending.len() == UPSERT_BATCH_SIZE || index + 1 == total_chunks {
+                let batch = std::mem::take(&mut pending);
+                let batch_len = batch.len();
+                if let Err(e) = self.vector_service.store_vectors_batch(batch).await {
+                    return Ok(Self::failed(
+                        node,
+                        started_at,
+                        format!("Vector upsert failed: {}", e),
+                    ));
+                }
+                chunks_processed += batch_len;
+                Self::set_progress(node, state, chunks_processed, total_chunks);
+            }
+        }
+
+        let output = serde_json::json!({
+            "source": source,
+            "document_id": document_id.to_string(),
+            "chunks_processed": chunks_processed,
+            "total_chunks": total_chunks,
+        });
+
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+
+        Ok(NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Success,
+            output: Some(output),
+            error: None,
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        })
+    }
+
+    fn can_handle(&self, node_type: &NodeType) -> bool {
+        matches!(node_type, NodeType::DocumentIngestion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_text_overlaps_chunks() {
+        let chunks = split_text("abcdefghij", 4, 1);
+        assert_eq!(chunks, vec!["abcd", "defg", "ghij"]);
+    }
+
+    #[test]
+    fn test_split_text_short_and_empty_input() {
+        assert_eq!(split_text("hello", 100, 10), vec!["hello"]);
+        assert!(split_text("", 100, 10).is_empty());
+    }
+
+    #[test]
+    fn test_split_text_counts_characters_not_bytes() {
+        let chunks = split_text("你好世界再见", 4, 2);
+        assert_eq!(chunks, vec!["你好世界", "世界再见"]);
+    }
+}
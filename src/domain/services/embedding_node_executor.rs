@@ -0,0 +1,359 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::repositories::llm_config_repository::LLMConfigRepository;
+use crate::domain::repositories::vector_config_repository::VectorConfigRepository;
+use crate::domain::services::execution_engine::{
+    ExecutionState, NodeExecutionResult, NodeExecutionStatus, NodeExecutor,
+};
+use crate::domain::services::llm_service::LLMDomainService;
+use crate::domain::services::vector_service::VectorStoreDomainService;
+use crate::domain::value_objects::ids::TenantId;
+use crate::domain::value_objects::{FlowNode, ModelConfig, NodeType, VectorRecord};
+use crate::domain::ConfigId;
+use crate::error::{PlatformError, Result};
+use crate::infrastructure::vector::VectorStoreFactory;
+
+/// Embedding node executor - embeds a text variable, stores the vector as
+/// `#node_id.embedding#` and optionally upserts it into a vector store
+pub struct EmbeddingNodeExecutor {
+    llm_service: Arc<dyn LLMDomainService>,
+    llm_config_repository: Arc<dyn LLMConfigRepository>,
+    vector_config_repository: Arc<dyn VectorConfigRepository>,
+    vector_service: Arc<dyn VectorStoreDomainService>,
+}
+
+impl EmbeddingNodeExecutor {
+    pub fn new(
+        llm_service: Arc<dyn LLMDomainService>,
+        llm_config_repository: Arc<dyn LLMConfigRepository>,
+        vector_config_repository: Arc<dyn VectorConfigRepository>,
+        vector_service: Arc<dyn VectorStoreDomainService>,
+    ) -> Self {
+        Self {
+            llm_service,
+            llm_config_repository,
+            vector_config_repository,
+            vector_service,
+        }
+    }
+
+    async fn extract_model_config(&self, node: &FlowNode, tenant_id: TenantId) -> Result<ModelConfig> {
+        let llm_config_id = node
+            .data
+            .get("llm_config_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                PlatformError::ValidationError(
+                    "Embedding node missing 'llm_config_id' field".to_string(),
+                )
+            })?;
+
+        let config = self
+            .llm_config_repository
+            .find_by_id(ConfigId::from_string(llm_config_id).map_err(|e| {
+                PlatformError::ValidationError(format!(
+                    "Invalid UUID: {}. Error: {}",
+                    llm_config_id, e
+                ))
+            })?)
+            .await?
+            .filter(|config| config.tenant_id == tenant_id)
+            .ok_or_else(|| {
+                PlatformError::ValidationError(format!("LLM config not found: {}", llm_config_id))
+            })?;
+
+        if !config.model_config.is_embedding_model() {
+            return Err(PlatformError::ValidationError(format!(
+                "LLM config {} is not an embedding model ({})",
+                llm_config_id, config.model_config.model_name
+            )));
+        }
+
+        Ok(config.model_config)
+    }
+
+    fn extract_tenant_id(&self, state: &ExecutionState) -> Result<TenantId> {
+        state
+            .variables
+            .get("tenant_id")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .map(TenantId::from)
+            .ok_or_else(|| {
+                PlatformError::ValidationError(
+                    "Missing or invalid tenant_id in execution context".to_string(),
+                )
+            })
+    }
+
+    /// Upsert the vector into the store of `upsert_to_vector_config_id`,
+    /// returning the record id
+    async fn upsert_vector(
+        &self,
+        node: &FlowNode,
+        vector_config_id: &str,
+        tenant_id: TenantId,
+        text: &str,
+        vector: Vec<f32>,
+    ) -> Result<String> {
+        let config_id = ConfigId::from_string(vector_config_id).map_err(|e| {
+            PlatformError::ValidationError(format!(
+                "Invalid UUID: {}. Error: {}",
+                vector_config_id, e
+            ))
+        })?;
+        let config = self
+            .vector_config_repository
+            .find_by_id(config_id)
+            .await?
+            .ok_or_else(|| {
+                PlatformError::NotFound(format!("Vector config not found: {}", vector_config_id))
+            })?;
+
+        if config.tenant_id != tenant_id {
+            return Err(PlatformError::AuthorizationFailed(
+                "Configuration does not belong to the specified tenant".to_string(),
+            ));
+        }
+
+        let record_id = embedding_record_id(text);
+        let mut record = VectorRecord::new(record_id.clone(), vector, tenant_id)
+            .map_err(PlatformError::ValidationError)?;
+        record.metadata = HashMap::from([
+            ("text".to_string(), Value::String(text.to_string())),
+            ("node_id".to_string(), Value::String(node.id.clone())),
+        ]);
+        record.namespace = node
+            .data
+            .get("namespace")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        self.vector_service.validate_vector_record(&record)?;
+        self.vector_service.apply_record_isolation(&mut record);
+
+        let store = VectorStoreFactory::create_store(config.to_store_config()).await?;
+        store.upsert(record).await?;
+
+        Ok(record_id)
+    }
+
+    fn failed(node: &FlowNode, started_at: DateTime<Utc>, error: String) -> NodeExecutionResult {
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+        NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Failed,
+            output: None,
+            error: Some(error),
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        }
+    }
+}
+
+/// Text named by `input_variable`, either a variable name such as
+/// `#start.text#` or a template such as `{{title}}: {{body}}`
+pub fn resolve_embedding_input(node: &FlowNode, state: &ExecutionState) -> Result<String> {
+    let reference = node
+        .data
+        .get("input_variable")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            PlatformError::ValidationError(
+                "Embedding node missing 'input_variable' field".to_string(),
+            )
+        })?;
+
+    let text = match state.get_variable(reference) {
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+        None if reference.contains("{{") => super::node_executors::resolve_template(reference, state),
+        None => {
+            return Err(PlatformError::ValidationError(format!(
+                "Variable '{}' not found",
+                reference
+            )))
+        }
+    };
+
+    if text.trim().is_empty() {
+        return Err(PlatformError::ValidationError(
+            "Embedding input cannot be empty".to_string(),
+        ));
+    }
+
+    Ok(text)
+}
+
+/// Record id derived from the text, so embedding the same text again
+/// overwrites its record
+pub fn embedding_record_id(text: &str) -> String {
+    Uuid::new_v5(&Uuid::NAMESPACE_OID, text.as_bytes()).to_string()
+}
+
+#[async_trait]
+impl NodeExecutor for EmbeddingNodeExecutor {
+    async fn execute(
+        &self,
+        node: &FlowNode,
+        state: &mut ExecutionState,
+    ) -> Result<NodeExecutionResult> {
+        let started_at = Utc::now();
+
+        let text = match resolve_embedding_input(node, state) {
+            Ok(text) => text,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let tenant_id = match self.extract_tenant_id(state) {
+            Ok(id) => id,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let model_config = match self.extract_model_config(node, tenant_id).await {
+            Ok(config) => config,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let vector = match self
+            .llm_service
+            .generate_embedding(&model_config, &text, tenant_id.0)
+            .await
+        {
+            Ok(vector) if !vector.is_empty() => vector,
+            Ok(_) => {
+                return Ok(Self::failed(
+                    node,
+                    started_at,
+                    "Embedding provider returned an empty vector".to_string(),
+                ))
+            }
+            Err(e) => return Ok(Self::failed(node, started_at, format!("Embedding failed: {}", e))),
+        };
+
+        let embedding = serde_json::json!(vector);
+        state.set_variable(format!("#{}.embedding#", node.id), embedding.clone());
+
+        let record_id = match node
+            .data
+            .get("upsert_to_vector_config_id")
+            .and_then(|v| v.as_str())
+        {
+            Some(vector_config_id) => {
+                match self
+                    .upsert_vector(node, vector_config_id, tenant_id, &text, vector.clone())
+                    .await
+                {
+                    Ok(record_id) => Some(record_id),
+                    Err(e) => {
+                        return Ok(Self::failed(
+                            node,
+                            started_at,
+                            format!("Vector upsert failed: {}", e),
+                        ))
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let output = serde_json::json!({
+            "embedding": embedding,
+            "dimension": vector.len(),
+            "model_used": model_config.model_name,
+            "vector_record_id": record_id,
+        });
+
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+
+        Ok(NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Success,
+            output: Some(output),
+            error: None,
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        })
+    }
+
+    fn can_handle(&self, node_type: &NodeType) -> bool {
+        matches!(node_type, NodeType::Embedding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{FlowExecutionId, NodePosition};
+
+    fn node(data: Value) -> FlowNode {
+        FlowNode {
+            id: "embed".to_string(),
+            parent_id: None,
+            node_type: NodeType::Embedding,
+            data,
+            position: NodePosition { x: 0.0, y: 0.0 },
+        }
+    }
+
+    fn state() -> ExecutionState {
+        ExecutionState::new(
+            FlowExecutionId::new(),
+            HashMap::from([
+                ("#start.text#".to_string(), Value::String("hello world".to_string())),
+                ("count".to_string(), serde_json::json!(3)),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_resolve_embedding_input_reads_variable_or_template() {
+        let state = state();
+
+        let text = resolve_embedding_input(&node(serde_json::json!({"input_variable": "#start.text#"})), &state);
+        assert_eq!(text.unwrap(), "hello world");
+
+        let text = resolve_embedding_input(&node(serde_json::json!({"input_variable": "count"})), &state);
+        assert_eq!(text.unwrap(), "3");
+
+        let text = resolve_embedding_input(
+            &node(serde_json::json!({"input_variable": "Count: {{count}}"})),
+            &state,
+        );
+        assert_eq!(text.unwrap(), "Count: 3");
+    }
+
+    #[test]
+    fn test_resolve_embedding_input_rejects_missing_input() {
+        let state = state();
+
+        assert!(resolve_embedding_input(&node(serde_json::json!({})), &state).is_err());
+        assert!(resolve_embedding_input(&node(serde_json::json!({"input_variable": "missing"})), &state).is_err());
+    }
+
+    #[test]
+    fn test_embedding_record_id_is_stable() {
+        assert_eq!(embedding_record_id("hello"), embedding_record_id("hello"));
+        assert_ne!(embedding_record_id("hello"), embedding_record_id("world"));
+    }
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::domain::services::llm_service::LLMError;
+
+/// Turns text into embedding vectors for vector search.
+///
+/// Some providers embed documents and queries differently, hence the
+/// separate `embed_query`.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Embed a batch of documents, returning one vector per text in input order
+    async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError>;
+
+    /// Embed a search query
+    async fn embed_query(&self, text: String) -> Result<Vec<f32>, LLMError> {
+        self.embed_texts(vec![text])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| LLMError::ProviderError("No embedding data in response".to_string()))
+    }
+
+    /// Model used to compute embeddings
+    fn model_name(&self) -> &str;
+}
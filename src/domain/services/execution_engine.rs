@@ -1,11 +1,16 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::domain::entities::FlowExecution;
-use crate::domain::value_objects::{FlowDefinition, FlowExecutionId, FlowNode, NodeType};
+use crate::domain::services::llm_service::TokenUsage;
+use crate::domain::services::node_schema::{validate_node_input, validate_node_output};
+use crate::domain::services::SecretStore;
+use crate::domain::value_objects::{FlowDefinition, FlowExecutionId, FlowNode, NodeType, TenantId};
 use crate::error::{PlatformError, Result};
 
 /// Node execution result
@@ -18,6 +23,35 @@ pub struct NodeExecutionResult {
     pub started_at: DateTime<Utc>,
     pub completed_at: DateTime<Utc>,
     pub execution_time_ms: i64,
+    /// Set when the execution timed out before the node finished
+    pub cancelled: bool,
+    /// Tokens reported by the provider, for nodes that call an LLM. For
+    /// cached results, the usage of the call that produced the response
+    pub token_usage: Option<TokenUsage>,
+    /// Attempts made after the first one failed
+    pub retry_count: u32,
+    /// Set when the output was served from a cache instead of executed
+    pub cached: bool,
+}
+
+impl NodeExecutionResult {
+    /// Result for a node the execution timeout prevented from finishing
+    pub fn cancelled(node_id: String, error: String) -> Self {
+        let now = Utc::now();
+        Self {
+            node_id,
+            status: NodeExecutionStatus::Cancelled,
+            output: None,
+            error: Some(error),
+            started_at: now,
+            completed_at: now,
+            execution_time_ms: 0,
+            cancelled: true,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -25,8 +59,68 @@ pub enum NodeExecutionStatus {
     Success,
     Failed,
     Skipped,
+    Cancelled,
+}
+
+/// Totals over the node results of one flow execution
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FlowExecutionMetrics {
+    pub total_execution_time_ms: i64,
+    pub total_tokens: u64,
+    /// LLM and parameter extractor nodes
+    pub llm_node_count: usize,
+    /// MCP tool and HTTP request nodes
+    pub tool_node_count: usize,
+    pub failed_node_count: usize,
+    pub retry_count: u32,
+    pub cached_node_count: usize,
+    /// Tokens cached nodes would have used; not part of `total_tokens`
+    pub saved_tokens: u64,
+}
+
+impl FlowExecutionMetrics {
+    /// Nodes inside loops count once, with their last result
+    pub fn from_state(state: &ExecutionState, definition: &FlowDefinition) -> Self {
+        let node_types: HashMap<&str, &NodeType> = definition
+            .workflow
+            .graph
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), &node.node_type))
+            .collect();
+
+        let mut metrics = Self::default();
+        for result in state.node_results.values() {
+            metrics.total_execution_time_ms += result.execution_time_ms;
+            if let Some(usage) = &result.token_usage {
+                if result.cached {
+                    metrics.saved_tokens += u64::from(usage.total_tokens);
+                } else {
+                    metrics.total_tokens += u64::from(usage.total_tokens);
+                }
+            }
+            metrics.retry_count += result.retry_count;
+            if result.cached {
+                metrics.cached_node_count += 1;
+            }
+            if result.status == NodeExecutionStatus::Failed {
+                metrics.failed_node_count += 1;
+            }
+
+            match node_types.get(result.node_id.as_str()) {
+                Some(NodeType::Llm | NodeType::ParameterExtractor) => metrics.llm_node_count += 1,
+                Some(NodeType::McpTool | NodeType::HttpRequest) => metrics.tool_node_count += 1,
+                _ => {}
+            }
+        }
+
+        metrics
+    }
 }
 
+/// Execution time limit for flows that don't set `timeout_ms`
+pub const DEFAULT_MAX_EXECUTION_TIME_MS: u64 = 300_000;
+
 /// Execution state that tracks the current state of flow execution
 #[derive(Debug, Clone)]
 pub struct ExecutionState {
@@ -36,8 +130,17 @@ pub struct ExecutionState {
     pub node_results: HashMap<String, NodeExecutionResult>,
     pub visited_nodes: Vec<String>,
     pub loop_counters: HashMap<String, usize>,
+    /// Branch chosen by the last condition node, consumed by the engine
+    pub next_node_id: Option<String>,
+    /// Wall-clock budget for the whole execution
+    pub max_execution_time_ms: u64,
+    /// Variables holding secrets, masked wherever variables are exposed
+    pub secret_variables: HashSet<String>,
 }
 
+/// Stands in for secret values in execution output
+pub const REDACTED_SECRET: &str = "******";
+
 impl ExecutionState {
     pub fn new(execution_id: FlowExecutionId, initial_variables: HashMap<String, Value>) -> Self {
         Self {
@@ -47,6 +150,9 @@ impl ExecutionState {
             node_results: HashMap::new(),
             visited_nodes: Vec::new(),
             loop_counters: HashMap::new(),
+            next_node_id: None,
+            max_execution_time_ms: DEFAULT_MAX_EXECUTION_TIME_MS,
+            secret_variables: HashSet::new(),
         }
     }
 
@@ -78,9 +184,17 @@ impl ExecutionState {
             node_results: HashMap::new(),
             visited_nodes: Vec::new(),
             loop_counters: HashMap::new(),
+            next_node_id: None,
+            max_execution_time_ms: DEFAULT_MAX_EXECUTION_TIME_MS,
+            secret_variables: HashSet::new(),
         }
     }
 
+    pub fn with_max_execution_time_ms(mut self, max_execution_time_ms: u64) -> Self {
+        self.max_execution_time_ms = max_execution_time_ms;
+        self
+    }
+
     pub fn set_variable(&mut self, name: String, value: Value) {
         self.variables.insert(name, value);
     }
@@ -89,6 +203,27 @@ impl ExecutionState {
         self.variables.get(name)
     }
 
+    /// Set a variable whose value must not appear in execution output
+    pub fn set_secret_variable(&mut self, name: String, value: Value) {
+        self.secret_variables.insert(name.clone());
+        self.variables.insert(name, value);
+    }
+
+    /// Variables with secret values masked, for output and persistence
+    pub fn public_variables(&self) -> HashMap<String, Value> {
+        self.variables
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.secret_variables.contains(name) {
+                    Value::String(REDACTED_SECRET.to_string())
+                } else {
+                    value.clone()
+                };
+                (name.clone(), value)
+            })
+            .collect()
+    }
+
     pub fn record_node_result(&mut self, result: NodeExecutionResult) {
         self.visited_nodes.push(result.node_id.clone());
         self.node_results.insert(result.node_id.clone(), result);
@@ -109,6 +244,87 @@ impl ExecutionState {
     }
 }
 
+/// Compare a state value against an expected value using a condition operator.
+///
+/// JSON strings holding numbers compare numerically against numbers, so a
+/// `"42"` variable equals `42` and is greater than `"7"`.
+pub fn compare_values(actual: &Value, operator: &str, expected: &Value) -> Result<bool> {
+    let result = match operator {
+        "==" | "eq" => values_equal(actual, expected),
+        "!=" | "ne" => !values_equal(actual, expected),
+        ">" | "gt" => compare_numbers(actual, expected, |a, b| a > b),
+        "<" | "lt" => compare_numbers(actual, expected, |a, b| a < b),
+        ">=" | "gte" => compare_numbers(actual, expected, |a, b| a >= b),
+        "<=" | "lte" => compare_numbers(actual, expected, |a, b| a <= b),
+        "contains" => match actual {
+            Value::String(s) => s.contains(&value_to_string(expected)),
+            Value::Array(items) => items.iter().any(|item| values_equal(item, expected)),
+            Value::Object(map) => map.contains_key(&value_to_string(expected)),
+            _ => false,
+        },
+        "starts_with" => match actual {
+            Value::String(_) | Value::Number(_) => {
+                value_to_string(actual).starts_with(&value_to_string(expected))
+            }
+            _ => false,
+        },
+        "is_empty" => match actual {
+            Value::Null => true,
+            Value::String(s) => s.trim().is_empty(),
+            Value::Array(items) => items.is_empty(),
+            Value::Object(map) => map.is_empty(),
+            _ => false,
+        },
+        _ => {
+            return Err(PlatformError::ValidationError(format!(
+                "Unknown operator: {}",
+                operator
+            )));
+        }
+    };
+
+    Ok(result)
+}
+
+fn value_as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    if actual == expected {
+        return true;
+    }
+    if let (Some(a), Some(b)) = (value_as_number(actual), value_as_number(expected)) {
+        return a == b;
+    }
+    // Compare scalars by their string form, e.g. `true` and `"true"`
+    match (actual, expected) {
+        (Value::String(_), Value::Bool(_) | Value::Number(_))
+        | (Value::Bool(_) | Value::Number(_), Value::String(_)) => {
+            value_to_string(actual) == value_to_string(expected)
+        }
+        _ => false,
+    }
+}
+
+fn compare_numbers(actual: &Value, expected: &Value, cmp: impl Fn(f64, f64) -> bool) -> bool {
+    match (value_as_number(actual), value_as_number(expected)) {
+        (Some(a), Some(b)) => cmp(a, b),
+        _ => false,
+    }
+}
+
 /// Node executor trait for executing different types of nodes
 #[async_trait]
 pub trait NodeExecutor: Send + Sync {
@@ -134,6 +350,20 @@ pub trait ExecutionEngine: Send + Sync {
         initial_variables: HashMap<String, Value>,
     ) -> Result<ExecutionState>;
 
+    /// Execute a flow, cancelling it once `max_execution_time_ms` elapses.
+    /// `None` uses the engine default; engines without a time limit run the
+    /// flow to completion.
+    async fn execute_with_timeout(
+        &self,
+        execution: &mut FlowExecution,
+        definition: &FlowDefinition,
+        initial_variables: HashMap<String, Value>,
+        max_execution_time_ms: Option<u64>,
+    ) -> Result<ExecutionState> {
+        let _ = max_execution_time_ms;
+        self.execute(execution, definition, initial_variables).await
+    }
+
     /// Execute a single node
     async fn execute_node(
         &self,
@@ -157,6 +387,8 @@ pub trait ExecutionEngine: Send + Sync {
 pub struct ExecutionEngineImpl {
     node_executors: Vec<Arc<dyn NodeExecutor>>,
     max_iterations: usize,
+    default_max_execution_time_ms: u64,
+    secret_store: Option<Arc<dyn SecretStore>>,
 }
 
 impl ExecutionEngineImpl {
@@ -164,6 +396,8 @@ impl ExecutionEngineImpl {
         Self {
             node_executors,
             max_iterations: 1000, // Prevent infinite loops
+            default_max_execution_time_ms: DEFAULT_MAX_EXECUTION_TIME_MS,
+            secret_store: None,
         }
     }
 
@@ -172,6 +406,159 @@ impl ExecutionEngineImpl {
         self
     }
 
+    pub fn with_default_max_execution_time_ms(mut self, max_execution_time_ms: u64) -> Self {
+        self.default_max_execution_time_ms = max_execution_time_ms;
+        self
+    }
+
+    /// Resolve `secret` environment variables from this store. Without one,
+    /// secret variables are left unset.
+    pub fn with_secret_store(mut self, secret_store: Arc<dyn SecretStore>) -> Self {
+        self.secret_store = Some(secret_store);
+        self
+    }
+
+    /// Inject the flow's environment variables as `env.{name}`, and as
+    /// `#env.{name}#` for Dify templates (`{{#env.name#}}`)
+    async fn inject_environment_variables(
+        &self,
+        tenant_id: TenantId,
+        definition: &FlowDefinition,
+        state: &mut ExecutionState,
+    ) -> Result<()> {
+        let variables = definition
+            .workflow
+            .parsed_environment_variables()
+            .map_err(PlatformError::ValidationError)?;
+
+        for variable in variables {
+            let key = variable.state_key();
+            let dify_key = format!("#{}#", key);
+
+            if !variable.is_secret() {
+                state.set_variable(dify_key, variable.value.clone());
+                state.set_variable(key, variable.value);
+                continue;
+            }
+
+            let Some(ref secret_store) = self.secret_store else {
+                tracing::warn!("No secret store configured, leaving {} unset", key);
+                continue;
+            };
+            let secret = match secret_store.get(tenant_id, &variable.name).await {
+                Ok(secret) => secret,
+                Err(PlatformError::NotFound(_)) => {
+                    return Err(PlatformError::ValidationError(format!(
+                        "Secret '{}' used by environment variable {} is not set for this tenant",
+                        variable.name, key
+                    )));
+                }
+                Err(e) => return Err(e),
+            };
+
+            state.set_secret_variable(dify_key, Value::String(secret.clone()));
+            state.set_secret_variable(key, Value::String(secret));
+        }
+
+        Ok(())
+    }
+
+    /// Record every node that has no result yet as cancelled, including the
+    /// one that was running when the timeout fired
+    fn cancel_remaining_nodes(&self, state: &mut ExecutionState, definition: &FlowDefinition) {
+        let reason = format!(
+            "Cancelled after flow execution exceeded {} ms",
+            state.max_execution_time_ms
+        );
+
+        for node in &definition.workflow.graph.nodes {
+            if !state.node_results.contains_key(&node.id) {
+                state.node_results.insert(
+                    node.id.clone(),
+                    NodeExecutionResult::cancelled(node.id.clone(), reason.clone()),
+                );
+            }
+        }
+    }
+
+    /// Snapshot of a timed-out execution, saved as its output
+    fn partial_output(state: &ExecutionState, definition: &FlowDefinition) -> Value {
+        let node_results: serde_json::Map<String, Value> = state
+            .node_results
+            .iter()
+            .map(|(node_id, result)| {
+                (
+                    node_id.clone(),
+                    serde_json::json!({
+                        "status": format!("{:?}", result.status),
+                        "cancelled": result.cancelled,
+                        "error": result.error,
+                        "execution_time_ms": result.execution_time_ms,
+                    }),
+                )
+            })
+            .collect();
+
+        serde_json::json!({
+            "variables": state.public_variables(),
+            "visited_nodes": state.visited_nodes,
+            "node_results": node_results,
+            "metrics": FlowExecutionMetrics::from_state(state, definition),
+        })
+    }
+
+    /// Run a flow and hand back its state whether or not it succeeded, so
+    /// callers can inspect the nodes that ran before a failure or timeout
+    pub async fn execute_with_state(
+        &self,
+        execution: &mut FlowExecution,
+        definition: &FlowDefinition,
+        initial_variables: HashMap<String, Value>,
+        max_execution_time_ms: Option<u64>,
+    ) -> (ExecutionState, Result<()>) {
+        // Mark execution as running
+        execution.start();
+
+        // Initialize execution state with tenant and user context for isolation
+        let mut state = ExecutionState::with_context(
+            execution.id,
+            execution.tenant_id.0,
+            execution.user_id.0,
+            execution.session_id.map(|sid| sid.0),
+            initial_variables,
+        )
+        .with_max_execution_time_ms(
+            max_execution_time_ms.unwrap_or(self.default_max_execution_time_ms),
+        );
+
+        if let Err(e) = self
+            .inject_environment_variables(execution.tenant_id, definition, &mut state)
+            .await
+        {
+            execution.fail(e.to_string());
+            return (state, Err(e));
+        }
+
+        let limit = Duration::from_millis(state.max_execution_time_ms);
+        let outcome =
+            tokio::time::timeout(limit, self.run_nodes(execution, definition, &mut state)).await;
+
+        let result = match outcome {
+            Ok(result) => result,
+            Err(_) => {
+                self.cancel_remaining_nodes(&mut state, definition);
+                let error = format!(
+                    "Flow execution exceeded {} ms",
+                    state.max_execution_time_ms
+                );
+                execution.time_out(error.clone(), Self::partial_output(&state, definition));
+                Err(PlatformError::Timeout(error))
+            }
+        };
+
+        (state, result)
+    }
+
     fn find_executor(&self, node_type: &NodeType) -> Option<&Arc<dyn NodeExecutor>> {
         self.node_executors.iter().find(|e| e.can_handle(node_type))
     }
@@ -203,6 +590,121 @@ impl ExecutionEngineImpl {
             .collect()
     }
 
+    /// Walk the graph from the start node until an end node is reached
+    async fn run_nodes(
+        &self,
+        execution: &mut FlowExecution,
+        definition: &FlowDefinition,
+        state: &mut ExecutionState,
+    ) -> Result<()> {
+        // Find start nodes
+        let start_nodes = definition.get_start_nodes();
+        if start_nodes.is_empty() {
+            execution.fail("No start node found in flow definition".to_string());
+            return Err(PlatformError::ValidationError(
+                "No start node found".to_string(),
+            ));
+        }
+
+        // Use the first start node
+        let start_node = start_nodes[0];
+        let mut current_nodes = vec![start_node.id.clone()];
+        let mut iteration_count = 0;
+
+        // Execute nodes until we reach an end node or max iterations
+        while !current_nodes.is_empty() && iteration_count < self.max_iterations {
+            iteration_count += 1;
+            let mut next_nodes = Vec::new();
+
+            for node_id in current_nodes {
+                let node = match self.find_node_by_id(&node_id, definition) {
+                    Some(n) => n,
+                    None => {
+                        let error = format!("Node not found: {}", node_id);
+                        execution.fail(error.clone());
+                        return Err(PlatformError::ValidationError(error));
+                    }
+                };
+
+                // Execute the node
+                state.current_node = Some(node_id.clone());
+                let result = self.execute_node(node, state).await?;
+                state.record_node_result(result.clone());
+
+                // Check if this is an end or answer node
+                if node.node_type == NodeType::End || node.node_type == NodeType::Answer {
+                    // Collect final output from state
+                    let output = serde_json::json!({
+                        "variables": state.public_variables(),
+                        "visited_nodes": state.visited_nodes,
+                    });
+                    execution.complete(output);
+                    return Ok(());
+                }
+
+                // Check if node execution failed
+                if result.status == NodeExecutionStatus::Failed {
+                    let error = result
+                        .error
+                        .unwrap_or_else(|| "Node execution failed".to_string());
+                    execution.fail(error.clone());
+                    return Err(PlatformError::InternalError(error));
+                }
+
+                // Dify-style iteration nodes only prepare a config; fan-out
+                // iteration nodes already ran their sub-nodes in the executor
+                let iteration_config_key = format!("#{}.iteration_config#", node.id);
+                if node.node_type == NodeType::Iteration
+                    && state.get_variable(&iteration_config_key).is_some()
+                {
+                    // Execute the iteration logic
+                    let iteration_result = self.execute_iteration(node, state, definition).await?;
+                    
+                    if iteration_result.status == NodeExecutionStatus::Failed {
+                        let error = iteration_result
+                            .error
+                            .unwrap_or_else(|| "Iteration execution failed".to_string());
+                        execution.fail(error.clone());
+                        return Err(PlatformError::InternalError(error));
+                    }
+                }
+
+                // Get next nodes to execute
+                let next = self.get_next_nodes(node, definition, state)?;
+                state.next_node_id = None;
+
+                if next.is_empty() {
+                    // Collect final output from state
+                    let output = serde_json::json!({
+                        "variables": state.public_variables(),
+                        "visited_nodes": state.visited_nodes,
+                    });
+                    execution.complete(output);
+                    return Ok(());
+                }
+
+                next_nodes.extend(next);
+            }
+
+            current_nodes = next_nodes;
+        }
+
+        // Check if we hit max iterations
+        if iteration_count >= self.max_iterations {
+            let error = format!(
+                "Flow execution exceeded maximum iterations: {}",
+                self.max_iterations
+            );
+            execution.fail(error.clone());
+            return Err(PlatformError::InternalError(error));
+        }
+
+        // If we exit the loop without reaching an end node, it's an error
+        let error = "Flow execution completed without reaching an end node".to_string();
+        execution.fail(error.clone());
+        Err(PlatformError::InternalError(error))
+    }
+
     /// Execute iteration logic for an iteration node
     async fn execute_iteration(
         &self,
@@ -316,6 +818,10 @@ impl ExecutionEngineImpl {
                             execution_time_ms: Utc::now()
                                 .signed_duration_since(started_at)
                                 .num_milliseconds(),
+                            cancelled: false,
+                            token_usage: None,
+                            retry_count: 0,
+                            cached: false,
                         });
                     }
 
@@ -341,6 +847,10 @@ impl ExecutionEngineImpl {
                     execution_time_ms: Utc::now()
                         .signed_duration_since(started_at)
                         .num_milliseconds(),
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         }
@@ -367,6 +877,10 @@ impl ExecutionEngineImpl {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 }
@@ -379,119 +893,21 @@ impl ExecutionEngine for ExecutionEngineImpl {
         definition: &FlowDefinition,
         initial_variables: HashMap<String, Value>,
     ) -> Result<ExecutionState> {
-        // Mark execution as running
-        execution.start();
-
-        // Initialize execution state with tenant and user context for isolation
-        let mut state = ExecutionState::with_context(
-            execution.id,
-            execution.tenant_id.0,
-            execution.user_id.0,
-            execution.session_id.map(|sid| sid.0),
-            initial_variables,
-        );
-
-        // Find start nodes
-        let start_nodes = definition.get_start_nodes();
-        if start_nodes.is_empty() {
-            execution.fail("No start node found in flow definition".to_string());
-            return Err(PlatformError::ValidationError(
-                "No start node found".to_string(),
-            ));
-        }
-
-        // Use the first start node
-        let start_node = start_nodes[0];
-        let mut current_nodes = vec![start_node.id.clone()];
-        let mut iteration_count = 0;
-
-        // Execute nodes until we reach an end node or max iterations
-        while !current_nodes.is_empty() && iteration_count < self.max_iterations {
-            iteration_count += 1;
-            let mut next_nodes = Vec::new();
-
-            for node_id in current_nodes {
-                let node = match self.find_node_by_id(&node_id, definition) {
-                    Some(n) => n,
-                    None => {
-                        let error = format!("Node not found: {}", node_id);
-                        execution.fail(error.clone());
-                        return Err(PlatformError::ValidationError(error));
-                    }
-                };
-
-                // Execute the node
-                state.current_node = Some(node_id.clone());
-                let result = self.execute_node(node, &mut state).await?;
-                state.record_node_result(result.clone());
-
-                // Check if this is an end or answer node
-                if node.node_type == NodeType::End || node.node_type == NodeType::Answer {
-                    // Collect final output from state
-                    let output = serde_json::json!({
-                        "variables": state.variables,
-                        "visited_nodes": state.visited_nodes,
-                    });
-                    execution.complete(output);
-                    return Ok(state);
-                }
-
-                // Check if node execution failed
-                if result.status == NodeExecutionStatus::Failed {
-                    let error = result
-                        .error
-                        .unwrap_or_else(|| "Node execution failed".to_string());
-                    execution.fail(error.clone());
-                    return Err(PlatformError::InternalError(error));
-                }
-
-                // Handle iteration node specially
-                if node.node_type == NodeType::Iteration {
-                    // Execute the iteration logic
-                    let iteration_result = self.execute_iteration(node, &mut state, definition).await?;
-                    
-                    if iteration_result.status == NodeExecutionStatus::Failed {
-                        let error = iteration_result
-                            .error
-                            .unwrap_or_else(|| "Iteration execution failed".to_string());
-                        execution.fail(error.clone());
-                        return Err(PlatformError::InternalError(error));
-                    }
-                }
-
-                // Get next nodes to execute
-                let next = self.get_next_nodes(node, definition, &state)?;
-
-                if next.is_empty() {
-                    // Collect final output from state
-                    let output = serde_json::json!({
-                        "variables": state.variables,
-                        "visited_nodes": state.visited_nodes,
-                    });
-                    execution.complete(output);
-                    return Ok(state);
-                }
-
-                next_nodes.extend(next);
-            }
-
-            current_nodes = next_nodes;
-        }
-
-        // Check if we hit max iterations
-        if iteration_count >= self.max_iterations {
-            let error = format!(
-                "Flow execution exceeded maximum iterations: {}",
-                self.max_iterations
-            );
-            execution.fail(error.clone());
-            return Err(PlatformError::InternalError(error));
-        }
+        self.execute_with_timeout(execution, definition, initial_variables, None)
+            .await
+    }
 
-        // If we exit the loop without reaching an end node, it's an error
-        let error = "Flow execution completed without reaching an end node".to_string();
-        execution.fail(error.clone());
-        Err(PlatformError::InternalError(error))
+    async fn execute_with_timeout(
+        &self,
+        execution: &mut FlowExecution,
+        definition: &FlowDefinition,
+        initial_variables: HashMap<String, Value>,
+        max_execution_time_ms: Option<u64>,
+    ) -> Result<ExecutionState> {
+        let (state, result) = self
+            .execute_with_state(execution, definition, initial_variables, max_execution_time_ms)
+            .await;
+        result.map(|_| state)
     }
 
     async fn execute_node(
@@ -516,12 +932,38 @@ impl ExecutionEngine for ExecutionEngineImpl {
                     started_at,
                     completed_at: Utc::now(),
                     execution_time_ms: 0,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
 
-        // Execute the node
-        let result = executor.execute(node, state).await?;
+        // Check the node's declared contracts around its execution
+        if let Err(e) = validate_node_input(node, state) {
+            return Ok(NodeExecutionResult {
+                node_id: node.id.clone(),
+                status: NodeExecutionStatus::Failed,
+                output: None,
+                error: Some(e.to_string()),
+                started_at,
+                completed_at: Utc::now(),
+                execution_time_ms: 0,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
+            });
+        }
+
+        let mut result = executor.execute(node, state).await?;
+        if result.status == NodeExecutionStatus::Success {
+            if let Err(e) = validate_node_output(node, result.output.as_ref()) {
+                result.status = NodeExecutionStatus::Failed;
+                result.error = Some(e.to_string());
+            }
+        }
         Ok(result)
     }
 
@@ -534,6 +976,10 @@ impl ExecutionEngine for ExecutionEngineImpl {
         let mut next_nodes = Vec::new();
 
         match current_node.node_type {
+            NodeType::Condition if current_node.data.get("conditions").is_some() => {
+                // The condition executor already picked the branch
+                next_nodes.extend(state.next_node_id.clone());
+            }
             NodeType::Condition => {
                 // For condition nodes, evaluate the condition and choose the appropriate branch
                 let edges = self.get_outgoing_edges(&current_node.id, definition);
@@ -653,51 +1099,7 @@ impl ExecutionEngine for ExecutionEngineImpl {
                 PlatformError::ValidationError(format!("Variable not found: {}", variable_name))
             })?;
 
-            let result = match operator {
-                "==" | "eq" => actual_value == expected_value,
-                "!=" | "ne" => actual_value != expected_value,
-                ">" | "gt" => {
-                    if let (Some(a), Some(b)) = (actual_value.as_f64(), expected_value.as_f64()) {
-                        a > b
-                    } else {
-                        false
-                    }
-                }
-                "<" | "lt" => {
-                    if let (Some(a), Some(b)) = (actual_value.as_f64(), expected_value.as_f64()) {
-                        a < b
-                    } else {
-                        false
-                    }
-                }
-                ">=" | "gte" => {
-                    if let (Some(a), Some(b)) = (actual_value.as_f64(), expected_value.as_f64()) {
-                        a >= b
-                    } else {
-                        false
-                    }
-                }
-                "<=" | "lte" => {
-                    if let (Some(a), Some(b)) = (actual_value.as_f64(), expected_value.as_f64()) {
-                        a <= b
-                    } else {
-                        false
-                    }
-                }
-                "contains" => {
-                    if let (Some(a), Some(b)) = (actual_value.as_str(), expected_value.as_str()) {
-                        a.contains(b)
-                    } else {
-                        false
-                    }
-                }
-                _ => {
-                    return Err(PlatformError::ValidationError(format!(
-                        "Unknown operator: {}",
-                        operator
-                    )));
-                }
-            };
+            let result = compare_values(actual_value, operator, expected_value)?;
 
             Ok(result)
         } else {
@@ -707,3 +1109,276 @@ impl ExecutionEngine for ExecutionEngineImpl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::FlowExecutionStatus;
+    use crate::domain::value_objects::{
+        FlowEdge, FlowGraph, FlowId, FlowWorkflow, NodePosition, TenantId, UserId, Version,
+    };
+
+    /// Completes start and end nodes at once and stalls on LLM nodes
+    struct StallingExecutor;
+
+    #[async_trait]
+    impl NodeExecutor for StallingExecutor {
+        async fn execute(
+            &self,
+            node: &FlowNode,
+            _state: &mut ExecutionState,
+        ) -> Result<NodeExecutionResult> {
+            let started_at = Utc::now();
+            if node.node_type == NodeType::Llm {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+            Ok(NodeExecutionResult {
+                node_id: node.id.clone(),
+                status: NodeExecutionStatus::Success,
+                output: None,
+                error: None,
+                started_at,
+                completed_at: Utc::now(),
+                execution_time_ms: 0,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
+            })
+        }
+
+        fn can_handle(&self, _node_type: &NodeType) -> bool {
+            true
+        }
+    }
+
+    fn node(id: &str, node_type: NodeType) -> FlowNode {
+        FlowNode {
+            id: id.to_string(),
+            parent_id: None,
+            node_type,
+            data: serde_json::json!({}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        }
+    }
+
+    fn edge(source: &str, target: &str) -> FlowEdge {
+        FlowEdge {
+            id: format!("{}-{}", source, target),
+            source: source.to_string(),
+            target: target.to_string(),
+            source_handle: None,
+            target_handle: None,
+        }
+    }
+
+    fn definition(middle: NodeType) -> FlowDefinition {
+        FlowDefinition {
+            workflow: FlowWorkflow {
+                graph: FlowGraph {
+                    nodes: vec![
+                        node("start", NodeType::Start),
+                        node("llm", middle),
+                        node("end", NodeType::End),
+                    ],
+                    edges: vec![edge("start", "llm"), edge("llm", "end")],
+                },
+                environment_variables: vec![],
+            },
+        }
+    }
+
+    fn execution() -> FlowExecution {
+        FlowExecution::new(FlowId::new(), Version::new(), TenantId::new(), UserId::new(), None, None)
+    }
+
+    #[tokio::test]
+    async fn test_execution_within_time_limit_completes() {
+        let engine = ExecutionEngineImpl::new(vec![Arc::new(StallingExecutor)]);
+        let mut execution = execution();
+
+        let state = engine
+            .execute_with_timeout(&mut execution, &definition(NodeType::Variable), HashMap::new(), Some(1_000))
+            .await
+            .unwrap();
+
+        assert!(execution.is_completed());
+        assert_eq!(state.max_execution_time_ms, 1_000);
+        assert!(state.node_results.values().all(|r| !r.cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_cancels_remaining_nodes() {
+        let engine = ExecutionEngineImpl::new(vec![Arc::new(StallingExecutor)])
+            .with_default_max_execution_time_ms(50);
+        let mut execution = execution();
+
+        let result = engine
+            .execute(&mut execution, &definition(NodeType::Llm), HashMap::new())
+            .await;
+
+        assert!(matches!(result, Err(PlatformError::Timeout(_))));
+        assert_eq!(execution.status, FlowExecutionStatus::Cancelled);
+
+        let output = execution.output_data.unwrap();
+        assert_eq!(output["visited_nodes"], serde_json::json!(["start"]));
+        assert_eq!(output["node_results"]["start"]["cancelled"], false);
+        assert_eq!(output["node_results"]["llm"]["cancelled"], true);
+        assert_eq!(output["node_results"]["end"]["status"], "Cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_environment_variables_injected_with_secrets_masked() {
+        let mut secret_store = crate::domain::services::MockSecretStore::new();
+        secret_store
+            .expect_get()
+            .withf(|_, key| key == "API_KEY")
+            .returning(|_, _| Ok("sk-123".to_string()));
+        let engine = ExecutionEngineImpl::new(vec![Arc::new(StallingExecutor)])
+            .with_secret_store(Arc::new(secret_store));
+
+        let mut definition = definition(NodeType::Variable);
+        definition.workflow.environment_variables = vec![
+            serde_json::json!({"name": "REGION", "value_type": "string", "value": "eu"}),
+            serde_json::json!({"name": "API_KEY", "value_type": "secret", "value": ""}),
+        ];
+        let mut execution = execution();
+
+        let state = engine
+            .execute(&mut execution, &definition, HashMap::new())
+            .await
+            .unwrap();
+
+        assert_eq!(state.get_variable("env.REGION"), Some(&serde_json::json!("eu")));
+        assert_eq!(state.get_variable("#env.REGION#"), Some(&serde_json::json!("eu")));
+        assert_eq!(state.get_variable("env.API_KEY"), Some(&serde_json::json!("sk-123")));
+        assert_eq!(
+            state.public_variables()["env.API_KEY"],
+            serde_json::json!(REDACTED_SECRET)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_secret_fails_execution() {
+        let mut secret_store = crate::domain::services::MockSecretStore::new();
+        secret_store
+            .expect_get()
+            .returning(|_, key| Err(PlatformError::NotFound(format!("Secret {} not found", key))));
+        let engine = ExecutionEngineImpl::new(vec![Arc::new(StallingExecutor)])
+            .with_secret_store(Arc::new(secret_store));
+
+        let mut definition = definition(NodeType::Variable);
+        definition.workflow.environment_variables =
+            vec![serde_json::json!({"name": "API_KEY", "value_type": "secret"})];
+        let mut execution = execution();
+
+        let result = engine.execute(&mut execution, &definition, HashMap::new()).await;
+
+        assert!(matches!(result, Err(PlatformError::ValidationError(_))));
+        assert_eq!(execution.status, FlowExecutionStatus::Failed);
+    }
+
+    #[test]
+    fn test_metrics_aggregate_node_results() {
+        let mut definition = definition(NodeType::Llm);
+        definition
+            .workflow
+            .graph
+            .nodes
+            .push(node("tool", NodeType::McpTool));
+
+        let mut state = ExecutionState::new(FlowExecutionId::new(), HashMap::new());
+        let now = Utc::now();
+        let result = |node_id: &str, status, execution_time_ms, token_usage| NodeExecutionResult {
+            node_id: node_id.to_string(),
+            status,
+            output: None,
+            error: None,
+            started_at: now,
+            completed_at: now,
+            execution_time_ms,
+            cancelled: false,
+            token_usage,
+            retry_count: 0,
+            cached: false,
+        };
+        state.record_node_result(result("start", NodeExecutionStatus::Success, 1, None));
+        state.record_node_result(result(
+            "llm",
+            NodeExecutionStatus::Success,
+            120,
+            Some(TokenUsage {
+                prompt_tokens: 30,
+                completion_tokens: 12,
+                total_tokens: 42,
+            }),
+        ));
+        state.record_node_result(result("tool", NodeExecutionStatus::Failed, 30, None));
+
+        let metrics = FlowExecutionMetrics::from_state(&state, &definition);
+
+        assert_eq!(metrics.total_execution_time_ms, 151);
+        assert_eq!(metrics.total_tokens, 42);
+        assert_eq!(metrics.llm_node_count, 1);
+        assert_eq!(metrics.tool_node_count, 1);
+        assert_eq!(metrics.failed_node_count, 1);
+    }
+
+    #[test]
+    fn test_metrics_count_cached_tokens_as_saved() {
+        let definition = definition(NodeType::Llm);
+        let mut state = ExecutionState::new(FlowExecutionId::new(), HashMap::new());
+        let now = Utc::now();
+        state.record_node_result(NodeExecutionResult {
+            node_id: "llm".to_string(),
+            status: NodeExecutionStatus::Success,
+            output: None,
+            error: None,
+            started_at: now,
+            completed_at: now,
+            execution_time_ms: 2,
+            cancelled: false,
+            token_usage: Some(TokenUsage {
+                prompt_tokens: 30,
+                completion_tokens: 12,
+                total_tokens: 42,
+            }),
+            retry_count: 0,
+            cached: true,
+        });
+
+        let metrics = FlowExecutionMetrics::from_state(&state, &definition);
+
+        assert_eq!(metrics.total_tokens, 0);
+        assert_eq!(metrics.saved_tokens, 42);
+        assert_eq!(metrics.cached_node_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_node_schemas_fail_the_node() {
+        let engine = ExecutionEngineImpl::new(vec![Arc::new(StallingExecutor)]);
+        let mut state = ExecutionState::new(
+            FlowExecutionId::new(),
+            HashMap::from([("#start.query#".to_string(), serde_json::json!(42))]),
+        );
+
+        let mut input_checked = node("code", NodeType::Code);
+        input_checked.data = serde_json::json!({
+            "input_schema": {"properties": {"#start.query#": {"type": "string"}}}
+        });
+        let result = engine.execute_node(&input_checked, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.unwrap().contains("input_schema"));
+
+        // StallingExecutor produces no output
+        let mut output_checked = node("code", NodeType::Code);
+        output_checked.data = serde_json::json!({"output_schema": {"type": "object"}});
+        let result = engine.execute_node(&output_checked, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.unwrap().contains("output_schema"));
+
+        output_checked.data = serde_json::json!({"output_schema": {"type": "null"}});
+        let result = engine.execute_node(&output_checked, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Success);
+    }
+}
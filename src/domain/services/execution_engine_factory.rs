@@ -4,14 +4,23 @@ use crate::domain::services::{
     execution_engine::{ExecutionEngine, ExecutionEngineImpl, NodeExecutor},
     node_executors::*,
     iteration_node_executor::IterationNodeExecutor,
+    document_ingestion_node_executor::DocumentIngestionNodeExecutor,
+    knowledge_base_retrieval_node_executor::KnowledgeBaseRetrievalNodeExecutor,
+    embedding_node_executor::EmbeddingNodeExecutor,
+    flow_dry_run::{DryRunConfig, DryRunNodeExecutor},
     llm_service::LLMDomainService,
     vector_service::VectorStoreDomainService,
+    embedding_service::EmbeddingProvider,
     mcp_tool_service::MCPToolDomainService,
+    secret_store::SecretStore,
+    prompt_cache::PromptCache,
 };
 use crate::domain::repositories::{
     mcp_tool_repository::MCPToolRepository,
     llm_config_repository::LLMConfigRepository,
+    vector_config_repository::VectorConfigRepository,
 };
+use crate::infrastructure::mcp::MCPProxyService;
 
 /// Factory for creating execution engines with all necessary node executors
 pub struct ExecutionEngineFactory;
@@ -21,9 +30,14 @@ impl ExecutionEngineFactory {
     pub fn create_with_services(
         llm_service: Arc<dyn LLMDomainService>,
         llm_config_repository: Arc<dyn LLMConfigRepository>,
+        vector_config_repository: Arc<dyn VectorConfigRepository>,
         vector_service: Arc<dyn VectorStoreDomainService>,
         mcp_service: Arc<dyn MCPToolDomainService>,
         tool_repository: Arc<dyn MCPToolRepository>,
+        mcp_proxy_service: Arc<dyn MCPProxyService>,
+        embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+        secret_store: Option<Arc<dyn SecretStore>>,
+        prompt_cache: Option<Arc<dyn PromptCache>>,
     ) -> Arc<dyn ExecutionEngine> {
         let mut executors: Vec<Arc<dyn NodeExecutor>> = Vec::new();
 
@@ -38,13 +52,50 @@ impl ExecutionEngineFactory {
         executors.push(Arc::new(AnswerNodeExecutor::new()));
 
         // Add service-integrated node executors
-        executors.push(Arc::new(LLMChatNodeExecutor::new(llm_service.clone(), llm_config_repository.clone())));
-        executors.push(Arc::new(VectorSearchNodeExecutor::new(vector_service)));
-        executors.push(Arc::new(MCPToolNodeExecutor::new(mcp_service, tool_repository)));
+        let tool_call_executor = Arc::new(ToolCallNodeExecutor::new(
+            mcp_service.clone(),
+            tool_repository.clone(),
+            mcp_proxy_service.clone(),
+        ));
+        let mut llm_chat = LLMChatNodeExecutor::new(llm_service.clone(), llm_config_repository.clone())
+            .with_tool_executor(tool_call_executor);
+        if let Some(prompt_cache) = prompt_cache {
+            llm_chat = llm_chat.with_prompt_cache(prompt_cache);
+        }
+        executors.push(Arc::new(llm_chat));
+        let mut vector_search = VectorSearchNodeExecutor::new(vector_service.clone());
+        if let Some(embedding_provider) = embedding_provider {
+            vector_search = vector_search.with_embedding_provider(embedding_provider);
+        }
+        executors.push(Arc::new(vector_search));
+        executors.push(Arc::new(DocumentIngestionNodeExecutor::new(
+            llm_service.clone(),
+            llm_config_repository.clone(),
+            vector_service.clone(),
+        )));
+        executors.push(Arc::new(KnowledgeBaseRetrievalNodeExecutor::new(
+            llm_service.clone(),
+            llm_config_repository.clone(),
+            vector_config_repository.clone(),
+            vector_service.clone(),
+        )));
+        executors.push(Arc::new(EmbeddingNodeExecutor::new(
+            llm_service.clone(),
+            llm_config_repository.clone(),
+            vector_config_repository,
+            vector_service,
+        )));
+        executors.push(Arc::new(MCPToolNodeExecutor::new(mcp_service, tool_repository, mcp_proxy_service)));
         executors.push(Arc::new(ParameterExtractorNodeExecutor::new(llm_service, llm_config_repository)));
-        executors.push(Arc::new(IterationNodeExecutor::new()));
+        // Iteration sub-nodes can use every other executor
+        let iteration = IterationNodeExecutor::new().with_executors(executors.clone());
+        executors.push(Arc::new(iteration));
 
-        Arc::new(ExecutionEngineImpl::new(executors))
+        let mut engine = ExecutionEngineImpl::new(executors);
+        if let Some(secret_store) = secret_store {
+            engine = engine.with_secret_store(secret_store);
+        }
+        Arc::new(engine)
     }
 
     /// Create a basic execution engine without external service integrations
@@ -64,6 +115,26 @@ impl ExecutionEngineFactory {
         Arc::new(ExecutionEngineImpl::new(executors))
     }
 
+    /// Create an engine for dry runs: LLM and vector nodes answer from the
+    /// fixtures in `config`, every other node runs as usual
+    pub fn create_dry_run(config: DryRunConfig) -> ExecutionEngineImpl {
+        let mut executors: Vec<Arc<dyn NodeExecutor>> = Vec::new();
+
+        executors.push(Arc::new(StartNodeExecutor::new()));
+        executors.push(Arc::new(EndNodeExecutor::new()));
+        executors.push(Arc::new(VariableNodeExecutor::new()));
+        executors.push(Arc::new(ConditionNodeExecutor::new()));
+        executors.push(Arc::new(LoopNodeExecutor::new()));
+        executors.push(Arc::new(CodeNodeExecutor::new()));
+        executors.push(Arc::new(HttpRequestNodeExecutor::new()));
+        executors.push(Arc::new(AnswerNodeExecutor::new()));
+        executors.push(Arc::new(DryRunNodeExecutor::new(config)));
+        let iteration = IterationNodeExecutor::new().with_executors(executors.clone());
+        executors.push(Arc::new(iteration));
+
+        ExecutionEngineImpl::new(executors)
+    }
+
     /// Create an execution engine with custom node executors
     pub fn create_with_executors(executors: Vec<Arc<dyn NodeExecutor>>) -> Arc<dyn ExecutionEngine> {
         Arc::new(ExecutionEngineImpl::new(executors))
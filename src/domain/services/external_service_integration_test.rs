@@ -22,6 +22,10 @@ mod tests {
     };
     use crate::domain::entities::{FlowExecution, MCPTool};
     use crate::domain::repositories::mcp_tool_repository::MCPToolRepository;
+    use crate::infrastructure::mcp::{
+        protocol_handler::{MCPRequest, MCPResponse},
+        MCPProxyService, MCPToolStats,
+    };
     use crate::error::{Result, PlatformError};
 
     // Mock LLM Service
@@ -159,6 +163,7 @@ mod tests {
                 dimension: 4,
                 index_fullness: 0.5,
                 namespace_stats: HashMap::new(),
+                index_state: None,
             })
         }
 
@@ -184,6 +189,63 @@ mod tests {
                 None => tenant_id.to_string(),
             }
         }
+
+        fn apply_record_isolation(&self, _record: &mut crate::domain::value_objects::VectorRecord) {}
+
+        fn apply_query_isolation(&self, _query: &mut SearchQuery, _tenant_id: TenantId) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    // Mock MCP proxy that echoes the forwarded arguments
+    struct MockMCPProxyService;
+
+    #[async_trait]
+    impl MCPProxyService for MockMCPProxyService {
+        async fn register_tool(&self, _tool: MCPTool) -> Result<()> {
+            Ok(())
+        }
+
+        async fn unregister_tool(&self, _tool_id: crate::domain::value_objects::ids::MCPToolId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_tenant_tools(&self, _tenant_id: TenantId) -> Result<Vec<MCPTool>> {
+            Ok(vec![])
+        }
+
+        async fn call_tool(
+            &self,
+            _tool_id: crate::domain::value_objects::ids::MCPToolId,
+            _parameters: Value,
+            _context: ToolCallContext,
+        ) -> Result<ToolCallResult> {
+            Err(PlatformError::NotFound("Not implemented".to_string()))
+        }
+
+        async fn forward_tool_call(
+            &self,
+            _tool: &MCPTool,
+            parameters: Value,
+            _context: ToolCallContext,
+        ) -> Result<ToolCallResult> {
+            Ok(ToolCallResult::success(
+                json!({"content": [{"type": "text", "text": parameters.to_string()}]}),
+                10,
+            ))
+        }
+
+        async fn handle_mcp_request(&self, _request: MCPRequest, _tenant_id: TenantId) -> Result<MCPResponse> {
+            Err(PlatformError::NotFound("Not implemented".to_string()))
+        }
+
+        async fn test_tool_connection(&self, _tool_id: crate::domain::value_objects::ids::MCPToolId) -> Result<ToolCallResult> {
+            Err(PlatformError::NotFound("Not implemented".to_string()))
+        }
+
+        async fn get_tool_stats(&self, _tenant_id: TenantId) -> Result<MCPToolStats> {
+            Err(PlatformError::NotFound("Not implemented".to_string()))
+        }
     }
 
     // Mock MCP Service
@@ -226,6 +288,7 @@ mod tests {
             &self,
             _tool: &MCPTool,
             _parameters: &Value,
+            _context: &ToolCallContext,
         ) -> Result<()> {
             Ok(())
         }
@@ -252,6 +315,7 @@ mod tests {
                 session_id: None,
                 request_id,
                 metadata: HashMap::new(),
+                strict_validation: true,
             }
         }
 
@@ -488,7 +552,7 @@ mod tests {
         // Setup
         let mcp_service = Arc::new(MockMCPService);
         let tool_repository = Arc::new(MockToolRepository);
-        let mcp_executor = MCPToolNodeExecutor::new(mcp_service, tool_repository);
+        let mcp_executor = MCPToolNodeExecutor::new(mcp_service, tool_repository, Arc::new(MockMCPProxyService));
 
         let tenant_id = TenantId::new();
         let user_id = UserId::new();
@@ -530,6 +594,7 @@ mod tests {
         // Check that result was stored in state
         let tool_result = state.variables.get("tool_result");
         assert!(tool_result.is_some());
+        assert_eq!(state.variables.get("#mcp1.result#"), tool_result);
     }
 
     #[tokio::test]
@@ -0,0 +1,353 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::domain::services::execution_engine::{
+    ExecutionState, FlowExecutionMetrics, NodeExecutionResult, NodeExecutionStatus, NodeExecutor,
+};
+use crate::domain::value_objects::{FlowDefinition, FlowId, FlowNode, NodeType};
+use crate::error::Result;
+
+const CONTEXT_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Fixture data for a dry run. LLM and vector nodes never reach their
+/// services; they answer from these fixtures instead.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunConfig {
+    /// LLM reply per node id, used by LLM and parameter extractor nodes
+    #[serde(default)]
+    pub llm_responses: HashMap<String, String>,
+    /// Reply for LLM nodes without an entry in `llm_responses`
+    #[serde(default)]
+    pub default_llm_response: Option<String>,
+    /// Search results per node id as `{"id", "score", "metadata"}` objects,
+    /// used by vector search and knowledge base retrieval nodes
+    #[serde(default)]
+    pub vector_results: HashMap<String, Vec<Value>>,
+}
+
+impl DryRunConfig {
+    fn llm_response(&self, node: &FlowNode) -> String {
+        self.llm_responses
+            .get(&node.id)
+            .cloned()
+            .or_else(|| self.default_llm_response.clone())
+            .unwrap_or_else(|| format!("[dry run] response of node {}", node.id))
+    }
+
+    fn vector_results(&self, node: &FlowNode) -> Vec<Value> {
+        self.vector_results.get(&node.id).cloned().unwrap_or_default()
+    }
+}
+
+/// Stands in for every node that calls an LLM or a vector store during a
+/// dry run, writing the same state variables as the real executors
+pub struct DryRunNodeExecutor {
+    config: DryRunConfig,
+}
+
+impl DryRunNodeExecutor {
+    pub fn new(config: DryRunConfig) -> Self {
+        Self { config }
+    }
+
+    fn output_variable<'a>(node: &'a FlowNode, default: &'a str) -> &'a str {
+        node.data
+            .get("output_variable")
+            .and_then(|v| v.as_str())
+            .unwrap_or(default)
+    }
+
+    fn context(results: &[Value]) -> String {
+        results
+            .iter()
+            .filter_map(|result| {
+                result
+                    .pointer("/metadata/text")
+                    .or_else(|| result.pointer("/metadata/content"))
+                    .and_then(|text| text.as_str())
+            })
+            .collect::<Vec<_>>()
+            .join(CONTEXT_SEPARATOR)
+    }
+
+    fn mock_node(&self, node: &FlowNode, state: &mut ExecutionState) -> Value {
+        match node.node_type {
+            NodeType::Llm => {
+                let content = self.config.llm_response(node);
+                state.set_variable(
+                    Self::output_variable(node, "llm_response").to_string(),
+                    Value::String(content.clone()),
+                );
+                state.set_variable(format!("#{}.text#", node.id), Value::String(content.clone()));
+                state.set_variable(
+                    format!("#{}.structured_output#", node.id),
+                    Value::String(content.clone()),
+                );
+                serde_json::json!({ "content": content, "model_used": "dry-run" })
+            }
+            NodeType::ParameterExtractor => {
+                let content = self.config.llm_response(node);
+                let parameter = node
+                    .data
+                    .get("parameters")
+                    .and_then(|v| v.as_array())
+                    .and_then(|arr| arr.first())
+                    .and_then(|p| p.get("name"))
+                    .and_then(|n| n.as_str())
+                    .unwrap_or("extracted_parameters");
+                // Fixtures may be a JSON array of values or plain text
+                let extracted = serde_json::from_str::<Vec<Value>>(&content)
+                    .unwrap_or_else(|_| vec![Value::String(content)]);
+                state.set_variable(
+                    format!("#{}.{}#", node.id, parameter),
+                    Value::Array(extracted.clone()),
+                );
+                serde_json::json!({
+                    "extracted_parameters": extracted,
+                    "parameter_name": parameter,
+                    "model_used": "dry-run",
+                })
+            }
+            NodeType::VectorSearch => {
+                let results = self.config.vector_results(node);
+                state.set_variable(
+                    Self::output_variable(node, "search_results").to_string(),
+                    Value::Array(results.clone()),
+                );
+                serde_json::json!({ "results_count": results.len(), "results": results })
+            }
+            NodeType::KnowledgeBaseRetrieval => {
+                let results = self.config.vector_results(node);
+                let context = Self::context(&results);
+                state.set_variable(format!("#{}.context#", node.id), Value::String(context.clone()));
+                state.set_variable(format!("#{}.results#", node.id), Value::Array(results.clone()));
+                serde_json::json!({
+                    "context": context,
+                    "count": results.len(),
+                    "results": results,
+                })
+            }
+            // Nothing is embedded or written to the vector store
+            NodeType::Embedding => {
+                state.set_variable(format!("#{}.embedding#", node.id), Value::Array(Vec::new()));
+                serde_json::json!({ "dimension": 0, "embedding": [] })
+            }
+            _ => {
+                state.set_variable(format!("#{}.chunks_processed#", node.id), serde_json::json!(0));
+                state.set_variable(format!("#{}.total_chunks#", node.id), serde_json::json!(0));
+                serde_json::json!({ "chunks_processed": 0, "total_chunks": 0 })
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl NodeExecutor for DryRunNodeExecutor {
+    async fn execute(
+        &self,
+        node: &FlowNode,
+        state: &mut ExecutionState,
+    ) -> Result<NodeExecutionResult> {
+        let started_at = Utc::now();
+        let output = self.mock_node(node, state);
+        let completed_at = Utc::now();
+
+        Ok(NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Success,
+            output: Some(output),
+            error: None,
+            started_at,
+            completed_at,
+            execution_time_ms: completed_at
+                .signed_duration_since(started_at)
+                .num_milliseconds(),
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        })
+    }
+
+    fn can_handle(&self, node_type: &NodeType) -> bool {
+        Self::is_mocked(node_type)
+    }
+}
+
+impl DryRunNodeExecutor {
+    /// Node types answered from fixtures during a dry run
+    pub fn is_mocked(node_type: &NodeType) -> bool {
+        matches!(
+            node_type,
+            NodeType::Llm
+                | NodeType::ParameterExtractor
+                | NodeType::VectorSearch
+                | NodeType::KnowledgeBaseRetrieval
+                | NodeType::DocumentIngestion
+                | NodeType::Embedding
+        )
+    }
+}
+
+/// One node of a dry run trace
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunNodeTrace {
+    pub node_id: String,
+    pub node_type: NodeType,
+    pub status: String,
+    /// Whether the node answered from fixtures
+    pub mocked: bool,
+    pub output: Option<Value>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    pub execution_time_ms: i64,
+}
+
+/// Outcome of a dry run; nothing about it is persisted
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunResult {
+    pub flow_id: FlowId,
+    pub flow_version: i32,
+    /// `completed`, `failed` or `timed_out`
+    pub status: String,
+    pub error: Option<String>,
+    pub variables: HashMap<String, Value>,
+    /// Nodes in execution order, followed by any the timeout cancelled
+    pub trace: Vec<DryRunNodeTrace>,
+    pub execution_time_ms: i64,
+    pub metrics: FlowExecutionMetrics,
+}
+
+impl DryRunResult {
+    pub fn from_state(
+        flow_id: FlowId,
+        flow_version: i32,
+        definition: &FlowDefinition,
+        state: ExecutionState,
+        status: &str,
+        error: Option<String>,
+    ) -> Self {
+        let node_types: HashMap<&str, &NodeType> = definition
+            .workflow
+            .graph
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), &node.node_type))
+            .collect();
+
+        // Loops visit nodes more than once, but only the last result is kept
+        let mut order: Vec<&String> = Vec::new();
+        for node_id in &state.visited_nodes {
+            if !order.contains(&node_id) {
+                order.push(node_id);
+            }
+        }
+        for node in &definition.workflow.graph.nodes {
+            if state.node_results.contains_key(&node.id) && !order.contains(&&node.id) {
+                order.push(&node.id);
+            }
+        }
+
+        let trace: Vec<DryRunNodeTrace> = order
+            .into_iter()
+            .filter_map(|node_id| {
+                let result = state.node_results.get(node_id)?;
+                let node_type = (*node_types.get(node_id.as_str())?).clone();
+                Some(DryRunNodeTrace {
+                    node_id: node_id.clone(),
+                    mocked: DryRunNodeExecutor::is_mocked(&node_type),
+                    node_type,
+                    status: format!("{:?}", result.status).to_lowercase(),
+                    output: result.output.clone(),
+                    error: result.error.clone(),
+                    started_at: result.started_at,
+                    completed_at: result.completed_at,
+                    execution_time_ms: result.execution_time_ms,
+                })
+            })
+            .collect();
+
+        let execution_time_ms = trace.iter().map(|node| node.execution_time_ms).sum();
+        let metrics = FlowExecutionMetrics::from_state(&state, definition);
+
+        Self {
+            flow_id,
+            flow_version,
+            status: status.to_string(),
+            error,
+            variables: state.public_variables(),
+            trace,
+            execution_time_ms,
+            metrics,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{FlowExecutionId, NodePosition};
+
+    fn node(id: &str, node_type: NodeType, data: Value) -> FlowNode {
+        FlowNode {
+            id: id.to_string(),
+            parent_id: None,
+            node_type,
+            data,
+            position: NodePosition { x: 0.0, y: 0.0 },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_llm_node_uses_fixture_response() {
+        let config = DryRunConfig {
+            llm_responses: HashMap::from([("llm-1".to_string(), "Hello".to_string())]),
+            ..Default::default()
+        };
+        let executor = DryRunNodeExecutor::new(config);
+        let mut state = ExecutionState::new(FlowExecutionId::new(), HashMap::new());
+
+        let node = node("llm-1", NodeType::Llm, serde_json::json!({ "output_variable": "answer" }));
+        let result = executor.execute(&node, &mut state).await.unwrap();
+
+        assert_eq!(result.status, NodeExecutionStatus::Success);
+        assert_eq!(state.get_variable("answer"), Some(&Value::String("Hello".to_string())));
+        assert_eq!(state.get_variable("#llm-1.text#"), Some(&Value::String("Hello".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_knowledge_base_node_builds_context_from_fixtures() {
+        let config = DryRunConfig {
+            vector_results: HashMap::from([(
+                "kb".to_string(),
+                vec![
+                    serde_json::json!({ "id": "a", "score": 0.9, "metadata": { "text": "first" } }),
+                    serde_json::json!({ "id": "b", "score": 0.8, "metadata": { "content": "second" } }),
+                ],
+            )]),
+            ..Default::default()
+        };
+        let executor = DryRunNodeExecutor::new(config);
+        let mut state = ExecutionState::new(FlowExecutionId::new(), HashMap::new());
+
+        let node = node("kb", NodeType::KnowledgeBaseRetrieval, Value::Null);
+        executor.execute(&node, &mut state).await.unwrap();
+
+        assert_eq!(
+            state.get_variable("#kb.context#"),
+            Some(&Value::String(format!("first{}second", CONTEXT_SEPARATOR)))
+        );
+    }
+
+    #[test]
+    fn test_only_llm_and_vector_nodes_are_mocked() {
+        assert!(DryRunNodeExecutor::is_mocked(&NodeType::Llm));
+        assert!(DryRunNodeExecutor::is_mocked(&NodeType::VectorSearch));
+        assert!(!DryRunNodeExecutor::is_mocked(&NodeType::HttpRequest));
+        assert!(!DryRunNodeExecutor::is_mocked(&NodeType::Code));
+    }
+}
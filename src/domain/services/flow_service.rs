@@ -270,6 +270,9 @@ mod tests {
             tenant_id: TenantId::new(),
             username: Username::new("testuser".to_string()).unwrap(),
             nickname: Some("Test User".to_string()),
+            email: None,
+            language: None,
+            avatar_url: None,
             password_hash: "hash".to_string(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
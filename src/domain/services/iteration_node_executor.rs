@@ -1,19 +1,224 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
 use serde_json::Value;
+use std::sync::Arc;
 
 use crate::domain::services::execution_engine::{
     ExecutionState, NodeExecutionResult, NodeExecutionStatus, NodeExecutor,
 };
-use crate::domain::value_objects::{FlowNode, NodeType};
-use crate::error::Result;
+use crate::domain::value_objects::{FlowNode, NodePosition, NodeType};
+use crate::error::{PlatformError, Result};
 
-/// Iteration node executor - iterates over an array and executes a sub-flow for each item
-pub struct IterationNodeExecutor;
+const DEFAULT_CONCURRENCY: usize = 5;
+
+/// Upper bound for a node's `concurrency`, so one flow cannot run an
+/// unbounded number of items at once
+pub const MAX_ITERATION_CONCURRENCY: usize = 20;
+
+/// A node of an iteration sub-graph as written in `node.data.sub_nodes`
+#[derive(Debug, Deserialize)]
+struct SubNode {
+    id: String,
+    node_type: NodeType,
+    #[serde(default)]
+    data: Value,
+}
+
+/// Iteration node executor - iterates over an array and executes a sub-flow for each item.
+///
+/// Nodes with `iterator_variable` and `sub_nodes` fan out: every element runs
+/// the sub-nodes in order against its own copy of the state, with the element
+/// in `loop_item`, up to `concurrency` elements at a time. Dify-style nodes
+/// with `iterator_selector` are prepared here and run by the execution engine.
+pub struct IterationNodeExecutor {
+    executors: Vec<Arc<dyn NodeExecutor>>,
+}
 
 impl IterationNodeExecutor {
     pub fn new() -> Self {
-        Self
+        Self {
+            executors: Vec::new(),
+        }
+    }
+
+    /// Set the executors used to run sub-nodes
+    pub fn with_executors(mut self, executors: Vec<Arc<dyn NodeExecutor>>) -> Self {
+        self.executors = executors;
+        self
+    }
+
+    fn parse_sub_nodes(node: &FlowNode, sub_nodes: &Value) -> Result<Vec<FlowNode>> {
+        let sub_nodes: Vec<SubNode> = serde_json::from_value(sub_nodes.clone()).map_err(|e| {
+            PlatformError::ValidationError(format!("Invalid sub_nodes: {}", e))
+        })?;
+
+        if sub_nodes.is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Iteration node 'sub_nodes' cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(sub_nodes
+            .into_iter()
+            .map(|sub_node| FlowNode {
+                id: sub_node.id,
+                parent_id: Some(node.id.clone()),
+                node_type: sub_node.node_type,
+                data: sub_node.data,
+                position: NodePosition { x: 0.0, y: 0.0 },
+            })
+            .collect())
+    }
+
+    /// Run the sub-nodes for one element. Returns the element's result: the
+    /// `output_variable` if the node names one, else the last sub-node output.
+    async fn run_item(
+        &self,
+        node: &FlowNode,
+        sub_nodes: &[FlowNode],
+        base_state: &ExecutionState,
+        index: usize,
+        item: Value,
+    ) -> std::result::Result<Value, Value> {
+        let mut state = base_state.clone();
+        state.set_variable("loop_item".to_string(), item);
+        state.set_variable("loop_index".to_string(), serde_json::json!(index));
+
+        let item_error = |node_id: &str, error: String| {
+            serde_json::json!({ "index": index, "node_id": node_id, "error": error })
+        };
+
+        let mut last_output = Value::Null;
+        for sub_node in sub_nodes {
+            let Some(executor) = self.executors.iter().find(|e| e.can_handle(&sub_node.node_type)) else {
+                return Err(item_error(
+                    &sub_node.id,
+                    format!("No executor found for node type: {:?}", sub_node.node_type),
+                ));
+            };
+
+            state.current_node = Some(sub_node.id.clone());
+            let result = match executor.execute(sub_node, &mut state).await {
+                Ok(result) => result,
+                Err(e) => return Err(item_error(&sub_node.id, e.to_string())),
+            };
+
+            if result.status == NodeExecutionStatus::Failed {
+                return Err(item_error(
+                    &sub_node.id,
+                    result.error.clone().unwrap_or_else(|| "Node execution failed".to_string()),
+                ));
+            }
+
+            last_output = result.output.clone().unwrap_or(Value::Null);
+            state.record_node_result(result);
+        }
+
+        match node.data.get("output_variable").and_then(|v| v.as_str()) {
+            Some(var_name) => Ok(state.get_variable(var_name).cloned().unwrap_or(Value::Null)),
+            None => Ok(last_output),
+        }
+    }
+
+    async fn fan_out(
+        &self,
+        node: &FlowNode,
+        state: &mut ExecutionState,
+        iterator_variable: &str,
+        sub_nodes: &Value,
+        started_at: DateTime<Utc>,
+    ) -> Result<NodeExecutionResult> {
+        let failed = |error: String| {
+            let completed_at = Utc::now();
+            NodeExecutionResult {
+                node_id: node.id.clone(),
+                status: NodeExecutionStatus::Failed,
+                output: None,
+                error: Some(error),
+                started_at,
+                completed_at,
+                execution_time_ms: completed_at
+                    .signed_duration_since(started_at)
+                    .num_milliseconds(),
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
+            }
+        };
+
+        let items = match state.get_variable(iterator_variable).and_then(|v| v.as_array()) {
+            Some(items) => items.clone(),
+            None => {
+                return Ok(failed(format!(
+                    "Iterator variable '{}' not found or not an array",
+                    iterator_variable
+                )))
+            }
+        };
+
+        let sub_nodes = match Self::parse_sub_nodes(node, sub_nodes) {
+            Ok(sub_nodes) => sub_nodes,
+            Err(e) => return Ok(failed(e.to_string())),
+        };
+
+        let concurrency = node
+            .data
+            .get("concurrency")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize)
+            .unwrap_or(DEFAULT_CONCURRENCY)
+            .clamp(1, MAX_ITERATION_CONCURRENCY);
+
+        let base_state = state.clone();
+        // `buffered` keeps results in input order while running items concurrently
+        let outcomes: Vec<std::result::Result<Value, Value>> = stream::iter(items.into_iter().enumerate())
+            .map(|(index, item)| self.run_item(node, &sub_nodes, &base_state, index, item))
+            .buffered(concurrency)
+            .collect()
+            .await;
+
+        let mut results = Vec::with_capacity(outcomes.len());
+        let mut errors = Vec::new();
+        for outcome in outcomes {
+            match outcome {
+                Ok(value) => results.push(value),
+                Err(error) => {
+                    results.push(Value::Null);
+                    errors.push(error);
+                }
+            }
+        }
+
+        let output = serde_json::json!({
+            "iterations": results.len(),
+            "error_count": errors.len(),
+            "results": results,
+            "errors": errors,
+        });
+        state.set_variable(format!("#{}.results#", node.id), Value::Array(results));
+        state.set_variable(format!("#{}.errors#", node.id), Value::Array(errors));
+
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+
+        Ok(NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Success,
+            output: Some(output),
+            error: None,
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        })
     }
 }
 
@@ -32,6 +237,15 @@ impl NodeExecutor for IterationNodeExecutor {
     ) -> Result<NodeExecutionResult> {
         let started_at = Utc::now();
 
+        if let (Some(iterator_variable), Some(sub_nodes)) = (
+            node.data.get("iterator_variable").and_then(|v| v.as_str()),
+            node.data.get("sub_nodes"),
+        ) {
+            return self
+                .fan_out(node, state, iterator_variable, sub_nodes, started_at)
+                .await;
+        }
+
         // Extract iterator_selector: [node_id, variable_name]
         let iterator_selector = node
             .data
@@ -56,6 +270,10 @@ impl NodeExecutor for IterationNodeExecutor {
                 started_at,
                 completed_at,
                 execution_time_ms,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
             });
         }
 
@@ -94,6 +312,10 @@ impl NodeExecutor for IterationNodeExecutor {
                 started_at,
                 completed_at,
                 execution_time_ms,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
             });
         }
 
@@ -173,6 +395,10 @@ impl NodeExecutor for IterationNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -180,3 +406,156 @@ impl NodeExecutor for IterationNodeExecutor {
         matches!(node_type, NodeType::Iteration)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::domain::value_objects::FlowExecutionId;
+
+    /// Doubles `loop_item`, failing on negative numbers
+    struct DoubleExecutor {
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl NodeExecutor for DoubleExecutor {
+        async fn execute(
+            &self,
+            node: &FlowNode,
+            state: &mut ExecutionState,
+        ) -> Result<NodeExecutionResult> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            let item = state.get_variable("loop_item").and_then(|v| v.as_i64()).unwrap();
+            let now = Utc::now();
+            let (status, output, error) = if item < 0 {
+                (NodeExecutionStatus::Failed, None, Some("negative item".to_string()))
+            } else {
+                (NodeExecutionStatus::Success, Some(serde_json::json!(item * 2)), None)
+            };
+            state.set_variable("doubled".to_string(), serde_json::json!(item * 2));
+
+            Ok(NodeExecutionResult {
+                node_id: node.id.clone(),
+                status,
+                output,
+                error,
+                started_at: now,
+                completed_at: now,
+                execution_time_ms: 0,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
+            })
+        }
+
+        fn can_handle(&self, node_type: &NodeType) -> bool {
+            matches!(node_type, NodeType::Code)
+        }
+    }
+
+    fn setup(items: Value, data: Value) -> (IterationNodeExecutor, Arc<DoubleExecutor>, FlowNode, ExecutionState) {
+        let double = Arc::new(DoubleExecutor {
+            running: AtomicUsize::new(0),
+            max_running: AtomicUsize::new(0),
+        });
+        let executor = IterationNodeExecutor::new().with_executors(vec![double.clone()]);
+
+        let mut node_data = serde_json::json!({
+            "iterator_variable": "items",
+            "sub_nodes": [{"id": "double", "node_type": "code", "data": {}}],
+        });
+        node_data.as_object_mut().unwrap().extend(data.as_object().unwrap().clone());
+
+        let node = FlowNode {
+            id: "iter".to_string(),
+            parent_id: None,
+            node_type: NodeType::Iteration,
+            data: node_data,
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let state = ExecutionState::new(
+            FlowExecutionId::new(),
+            HashMap::from([("items".to_string(), items)]),
+        );
+
+        (executor, double, node, state)
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_collects_results_in_order() {
+        let (executor, double, node, mut state) =
+            setup(serde_json::json!([1, 2, 3, 4, 5, 6, 7, 8]), serde_json::json!({}));
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+
+        assert_eq!(result.status, NodeExecutionStatus::Success);
+        assert_eq!(
+            state.get_variable("#iter.results#"),
+            Some(&serde_json::json!([2, 4, 6, 8, 10, 12, 14, 16]))
+        );
+        assert_eq!(state.get_variable("#iter.errors#"), Some(&serde_json::json!([])));
+        assert!(double.max_running.load(Ordering::SeqCst) <= DEFAULT_CONCURRENCY);
+        // Item variables stay in the per-item copies
+        assert!(state.get_variable("loop_item").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_respects_concurrency() {
+        let (executor, double, node, mut state) =
+            setup(serde_json::json!([1, 2, 3, 4]), serde_json::json!({"concurrency": 1}));
+
+        executor.execute(&node, &mut state).await.unwrap();
+
+        assert_eq!(double.max_running.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_caps_concurrency() {
+        let items: Vec<i64> = (1..=(MAX_ITERATION_CONCURRENCY as i64 * 2)).collect();
+        let (executor, double, node, mut state) =
+            setup(serde_json::json!(items), serde_json::json!({"concurrency": 10_000}));
+
+        executor.execute(&node, &mut state).await.unwrap();
+
+        assert!(double.max_running.load(Ordering::SeqCst) <= MAX_ITERATION_CONCURRENCY);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_merges_item_errors() {
+        let (executor, _, node, mut state) = setup(
+            serde_json::json!([1, -1, 3]),
+            serde_json::json!({"output_variable": "doubled"}),
+        );
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+
+        assert_eq!(result.status, NodeExecutionStatus::Success);
+        assert_eq!(
+            state.get_variable("#iter.results#"),
+            Some(&serde_json::json!([2, null, 6]))
+        );
+        let errors = state.get_variable("#iter.errors#").unwrap().as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["index"], 1);
+        assert_eq!(errors[0]["node_id"], "double");
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_requires_array() {
+        let (executor, _, node, mut state) = setup(serde_json::json!("nope"), serde_json::json!({}));
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+    }
+}
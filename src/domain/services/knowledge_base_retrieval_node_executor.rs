@@ -0,0 +1,547 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::repositories::llm_config_repository::LLMConfigRepository;
+use crate::domain::repositories::vector_config_repository::VectorConfigRepository;
+use crate::domain::services::execution_engine::{
+    ExecutionState, NodeExecutionResult, NodeExecutionStatus, NodeExecutor,
+};
+use crate::domain::services::llm_service::LLMDomainService;
+use crate::domain::services::vector_service::VectorStoreDomainService;
+use crate::domain::value_objects::ids::TenantId;
+use crate::domain::value_objects::{FlowNode, ModelConfig, NodeType, SearchQuery, SearchResult};
+use crate::domain::ConfigId;
+use crate::error::{PlatformError, Result};
+use crate::infrastructure::vector::VectorStoreFactory;
+
+const DEFAULT_TOP_K: usize = 5;
+const CONTEXT_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Cross-encoder re-ranking endpoint, compatible with the Cohere/Jina
+/// `/rerank` API
+#[derive(Debug, Clone, Deserialize)]
+pub struct RerankConfig {
+    pub url: String,
+    pub model: String,
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResult {
+    index: usize,
+    relevance_score: f32,
+}
+
+/// A retrieved chunk with the knowledge base it came from
+#[derive(Debug, Clone)]
+pub struct RetrievedChunk {
+    pub result: SearchResult,
+    pub vector_config_id: String,
+}
+
+impl RetrievedChunk {
+    /// Chunk text as written by the document ingestion node
+    pub fn text(&self) -> Option<&str> {
+        let metadata = self.result.metadata.as_ref()?;
+        metadata
+            .get("text")
+            .or_else(|| metadata.get("content"))
+            .and_then(|v| v.as_str())
+    }
+}
+
+/// Knowledge Base Retrieval node executor - embeds a text query, searches
+/// every listed knowledge base and stores the best chunks as prompt context
+pub struct KnowledgeBaseRetrievalNodeExecutor {
+    llm_service: Arc<dyn LLMDomainService>,
+    llm_config_repository: Arc<dyn LLMConfigRepository>,
+    vector_config_repository: Arc<dyn VectorConfigRepository>,
+    vector_service: Arc<dyn VectorStoreDomainService>,
+    client: reqwest::Client,
+}
+
+impl KnowledgeBaseRetrievalNodeExecutor {
+    pub fn new(
+        llm_service: Arc<dyn LLMDomainService>,
+        llm_config_repository: Arc<dyn LLMConfigRepository>,
+        vector_config_repository: Arc<dyn VectorConfigRepository>,
+        vector_service: Arc<dyn VectorStoreDomainService>,
+    ) -> Self {
+        Self {
+            llm_service,
+            llm_config_repository,
+            vector_config_repository,
+            vector_service,
+            // Redirects could lead to internal addresses the URL check never saw
+            client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `query_text` may be a template, or `query_variable` may name a variable
+    fn extract_query_text(&self, node: &FlowNode, state: &ExecutionState) -> Result<String> {
+        let query_text = if let Some(template) = node.data.get("query_text").and_then(|v| v.as_str()) {
+            super::node_executors::resolve_template(template, state)
+        } else if let Some(var_name) = node.data.get("query_variable").and_then(|v| v.as_str()) {
+            match state.get_variable(var_name) {
+                Some(Value::String(s)) => s.clone(),
+                Some(v) => v.to_string(),
+                None => {
+                    return Err(PlatformError::ValidationError(format!(
+                        "Variable '{}' not found",
+                        var_name
+                    )))
+                }
+            }
+        } else {
+            return Err(PlatformError::ValidationError(
+                "Knowledge base retrieval node missing 'query_text' field".to_string(),
+            ));
+        };
+
+        if query_text.trim().is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Knowledge base retrieval query cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(query_text)
+    }
+
+    fn extract_vector_config_ids(&self, node: &FlowNode) -> Result<Vec<ConfigId>> {
+        let ids = node
+            .data
+            .get("vector_config_ids")
+            .and_then(|v| v.as_array())
+            .filter(|ids| !ids.is_empty())
+            .ok_or_else(|| {
+                PlatformError::ValidationError(
+                    "Knowledge base retrieval node requires a non-empty 'vector_config_ids' list"
+                        .to_string(),
+                )
+            })?;
+
+        ids.iter()
+            .map(|id| {
+                let id = id.as_str().ok_or_else(|| {
+                    PlatformError::ValidationError("vector_config_ids must be strings".to_string())
+                })?;
+                ConfigId::from_string(id).map_err(|e| {
+                    PlatformError::ValidationError(format!("Invalid UUID: {}. Error: {}", id, e))
+                })
+            })
+            .collect()
+    }
+
+    /// Embedding model from `llm_config_id`, or the tenant's default LLM config
+    async fn extract_model_config(&self, node: &FlowNode, tenant_id: TenantId) -> Result<ModelConfig> {
+        let config = match node.data.get("llm_config_id").and_then(|v| v.as_str()) {
+            Some(llm_config_id) => self
+                .llm_config_repository
+                .find_by_id(ConfigId::from_string(llm_config_id).map_err(|e| {
+                    PlatformError::ValidationError(format!(
+                        "Invalid UUID: {}. Error: {}",
+                        llm_config_id, e
+                    ))
+                })?)
+                .await?
+                .ok_or_else(|| {
+                    PlatformError::ValidationError(format!("LLM config not found: {}", llm_config_id))
+                })?,
+            None => self
+                .llm_config_repository
+                .find_default_by_tenant(tenant_id)
+                .await?
+                .ok_or_else(|| {
+                    PlatformError::ValidationError(
+                        "No llm_config_id given and tenant has no default LLM config".to_string(),
+                    )
+                })?,
+        };
+
+        Ok(config.model_config)
+    }
+
+    fn extract_tenant_id(&self, state: &ExecutionState) -> Result<TenantId> {
+        state
+            .variables
+            .get("tenant_id")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .map(TenantId::from)
+            .ok_or_else(|| {
+                PlatformError::ValidationError(
+                    "Missing or invalid tenant_id in execution context".to_string(),
+                )
+            })
+    }
+
+    async fn search_knowledge_base(
+        &self,
+        config_id: ConfigId,
+        tenant_id: TenantId,
+        mut query: SearchQuery,
+    ) -> Result<Vec<RetrievedChunk>> {
+        let config = self
+            .vector_config_repository
+            .find_by_id(config_id)
+            .await?
+            .ok_or_else(|| {
+                PlatformError::NotFound(format!("Vector config not found: {}", config_id.0))
+            })?;
+
+        if config.tenant_id != tenant_id {
+            return Err(PlatformError::AuthorizationFailed(
+                "Configuration does not belong to the specified tenant".to_string(),
+            ));
+        }
+
+        self.vector_service.apply_query_isolation(&mut query, tenant_id)?;
+
+        let store = VectorStoreFactory::create_store(config.to_store_config()).await?;
+        let results = store.query(query).await?;
+
+        Ok(results
+            .into_iter()
+            .map(|result| RetrievedChunk {
+                result,
+                vector_config_id: config_id.0.to_string(),
+            })
+            .collect())
+    }
+
+    /// Re-score chunks with a cross-encoder, most relevant first. The URL
+    /// comes from node data, so internal addresses are refused.
+    async fn rerank(
+        &self,
+        config: &RerankConfig,
+        query: &str,
+        chunks: Vec<RetrievedChunk>,
+        top_k: usize,
+    ) -> Result<Vec<RetrievedChunk>> {
+        let url = super::outbound_url::ensure_public_http_url(&config.url).await?;
+        let documents: Vec<&str> = chunks.iter().map(|c| c.text().unwrap_or_default()).collect();
+
+        let mut request = self.client.post(url).json(&serde_json::json!({
+            "model": config.model,
+            "query": query,
+            "documents": documents,
+            "top_n": top_k,
+        }));
+        if let Some(api_key) = &config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response: RerankResponse = request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| PlatformError::InternalError(format!("Rerank request failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("Invalid rerank response: {}", e)))?;
+
+        let mut reranked: Vec<RetrievedChunk> = response
+            .results
+            .into_iter()
+            .filter_map(|r| {
+                chunks.get(r.index).cloned().map(|mut chunk| {
+                    chunk.result.score = r.relevance_score;
+                    chunk
+                })
+            })
+            .collect();
+        reranked.sort_by(|a, b| b.result.score.total_cmp(&a.result.score));
+        reranked.truncate(top_k);
+
+        Ok(reranked)
+    }
+
+    fn failed(node: &FlowNode, started_at: DateTime<Utc>, error: String) -> NodeExecutionResult {
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+        NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Failed,
+            output: None,
+            error: Some(error),
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        }
+    }
+}
+
+/// Merge results from several knowledge bases by descending score, keeping
+/// the best-scoring copy of chunks found more than once
+pub fn merge_results(results: Vec<RetrievedChunk>, top_k: usize) -> Vec<RetrievedChunk> {
+    let mut results = results;
+    results.sort_by(|a, b| b.result.score.total_cmp(&a.result.score));
+
+    let mut seen = HashSet::new();
+    results
+        .into_iter()
+        .filter(|chunk| seen.insert((chunk.vector_config_id.clone(), chunk.result.id.clone())))
+        .take(top_k)
+        .collect()
+}
+
+/// Concatenate chunk texts into a single block for an LLM prompt
+pub fn build_context(chunks: &[RetrievedChunk]) -> String {
+    chunks
+        .iter()
+        .filter_map(|chunk| chunk.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(CONTEXT_SEPARATOR)
+}
+
+#[async_trait]
+impl NodeExecutor for KnowledgeBaseRetrievalNodeExecutor {
+    async fn execute(
+        &self,
+        node: &FlowNode,
+        state: &mut ExecutionState,
+    ) -> Result<NodeExecutionResult> {
+        let started_at = Utc::now();
+
+        let query_text = match self.extract_query_text(node, state) {
+            Ok(text) => text,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let vector_config_ids = match self.extract_vector_config_ids(node) {
+            Ok(ids) => ids,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let tenant_id = match self.extract_tenant_id(state) {
+            Ok(id) => id,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let rerank_config = match node.data.get("rerank").filter(|v| !v.is_null()) {
+            Some(value) => match serde_json::from_value::<RerankConfig>(value.clone()) {
+                Ok(config) => Some(config),
+                Err(e) => {
+                    return Ok(Self::failed(
+                        node,
+                        started_at,
+                        format!("Invalid rerank config: {}", e),
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        let top_k = node
+            .data
+            .get("top_k")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize)
+            .unwrap_or(DEFAULT_TOP_K);
+
+        let score_threshold = node
+            .data
+            .get("score_threshold")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32);
+
+        let model_config = match self.extract_model_config(node, tenant_id).await {
+            Ok(config) => config,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let vector = match self
+            .llm_service
+            .generate_embedding(&model_config, &query_text, tenant_id.0)
+            .await
+        {
+            Ok(vector) => vector,
+            Err(e) => {
+                return Ok(Self::failed(
+                    node,
+                    started_at,
+                    format!("Query embedding failed: {}", e),
+                ))
+            }
+        };
+
+        let mut query = match SearchQuery::new(vector, top_k) {
+            Ok(query) => query,
+            Err(e) => return Ok(Self::failed(node, started_at, e)),
+        };
+        query.namespace = node
+            .data
+            .get("namespace")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let searches = vector_config_ids
+            .into_iter()
+            .map(|config_id| self.search_knowledge_base(config_id, tenant_id, query.clone()));
+
+        let mut retrieved = Vec::new();
+        for result in futures::future::join_all(searches).await {
+            match result {
+                Ok(chunks) => retrieved.extend(chunks),
+                Err(e) => {
+                    return Ok(Self::failed(
+                        node,
+                        started_at,
+                        format!("Knowledge base search failed: {}", e),
+                    ))
+                }
+            }
+        }
+
+        if let Some(threshold) = score_threshold {
+            retrieved.retain(|chunk| chunk.result.score >= threshold);
+        }
+
+        let mut chunks = match &rerank_config {
+            // Give the cross-encoder every candidate, it picks the top_k
+            Some(_) => merge_results(retrieved, usize::MAX),
+            None => merge_results(retrieved, top_k),
+        };
+
+        if let Some(config) = &rerank_config {
+            chunks = match self.rerank(config, &query_text, chunks.clone(), top_k).await {
+                Ok(reranked) => reranked,
+                Err(e) => {
+                    tracing::warn!("Falling back to vector scores: {}", e);
+                    chunks.truncate(top_k);
+                    chunks
+                }
+            };
+        }
+
+        let context = build_context(&chunks);
+        let results_json: Vec<Value> = chunks
+            .iter()
+            .map(|chunk| {
+                serde_json::json!({
+                    "id": chunk.result.id,
+                    "score": chunk.result.score,
+                    "vector_config_id": chunk.vector_config_id,
+                    "metadata": chunk.result.metadata,
+                })
+            })
+            .collect();
+
+        state.set_variable(format!("#{}.context#", node.id), Value::String(context.clone()));
+        state.set_variable(format!("#{}.results#", node.id), Value::Array(results_json.clone()));
+
+        let output = serde_json::json!({
+            "query": query_text,
+            "context": context,
+            "results": results_json,
+            "count": results_json.len(),
+        });
+
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+
+        Ok(NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Success,
+            output: Some(output),
+            error: None,
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        })
+    }
+
+    fn can_handle(&self, node_type: &NodeType) -> bool {
+        matches!(node_type, NodeType::KnowledgeBaseRetrieval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn chunk(kb: &str, id: &str, score: f32, text: &str) -> RetrievedChunk {
+        let mut result = SearchResult::new(id.to_string(), score);
+        result.metadata = Some(HashMap::from([(
+            "text".to_string(),
+            Value::String(text.to_string()),
+        )]));
+        RetrievedChunk {
+            result,
+            vector_config_id: kb.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merge_results_orders_by_score_across_knowledge_bases() {
+        let merged = merge_results(
+            vec![
+                chunk("kb1", "a", 0.5, "a"),
+                chunk("kb2", "b", 0.9, "b"),
+                chunk("kb1", "c", 0.7, "c"),
+            ],
+            2,
+        );
+
+        let ids: Vec<&str> = merged.iter().map(|c| c.result.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_merge_results_drops_duplicate_chunks() {
+        let merged = merge_results(
+            vec![
+                chunk("kb1", "a", 0.5, "a"),
+                chunk("kb1", "a", 0.8, "a"),
+                chunk("kb2", "a", 0.6, "a"),
+            ],
+            10,
+        );
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].result.score, 0.8);
+    }
+
+    #[test]
+    fn test_build_context_joins_chunk_texts() {
+        let mut no_text = chunk("kb1", "c", 0.1, "");
+        no_text.result.metadata = None;
+
+        let context = build_context(&[
+            chunk("kb1", "a", 0.9, " first "),
+            chunk("kb2", "b", 0.8, "second"),
+            no_text,
+        ]);
+
+        assert_eq!(context, format!("first{}second", CONTEXT_SEPARATOR));
+    }
+}
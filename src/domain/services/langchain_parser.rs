@@ -0,0 +1,340 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::domain::value_objects::{FlowDefinition, FlowNode, FlowEdge, NodeType, NodePosition};
+use crate::domain::{ FlowWorkflow, FlowGraph };
+use crate::error::Result;
+
+/// Simplified LangChain chain representation (as produced by `chain.dict()` / LCEL exports)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LangChainDefinition {
+    #[serde(alias = "_type")]
+    pub chain_type: String,
+    #[serde(default)]
+    pub steps: Vec<LangChainStep>,
+    #[serde(default)]
+    pub prompt: Option<LangChainPrompt>,
+    #[serde(default)]
+    pub llm: Option<LangChainLLMConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LangChainStep {
+    #[serde(alias = "_type")]
+    pub chain_type: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<LangChainPrompt>,
+    #[serde(default)]
+    pub llm: Option<LangChainLLMConfig>,
+    pub output_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LangChainPrompt {
+    pub template: String,
+    #[serde(default)]
+    pub input_variables: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LangChainLLMConfig {
+    #[serde(alias = "_type")]
+    pub provider: Option<String>,
+    #[serde(alias = "model")]
+    pub model_name: Option<String>,
+    pub temperature: Option<f64>,
+}
+
+/// Result of converting a LangChain definition
+#[derive(Debug, Clone)]
+pub struct LangChainImport {
+    pub definition: FlowDefinition,
+    pub warnings: Vec<String>,
+}
+
+/// LangChain JSON parser
+pub struct LangChainParser;
+
+impl LangChainParser {
+    const START_NODE_ID: &'static str = "start";
+    const ANSWER_NODE_ID: &'static str = "answer";
+    const NODE_SPACING: f64 = 300.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a LangChain JSON string into a FlowDefinition
+    pub fn parse(&self, json_str: &str) -> Result<LangChainImport> {
+        let chain: LangChainDefinition = serde_json::from_str(json_str)
+            .map_err(|e| crate::error::PlatformError::ValidationError(
+                format!("Failed to parse LangChain JSON: {}", e)
+            ))?;
+
+        self.convert_to_flow_definition(chain)
+    }
+
+    fn convert_to_flow_definition(&self, chain: LangChainDefinition) -> Result<LangChainImport> {
+        let mut warnings = Vec::new();
+
+        // A bare LLMChain has no steps; treat the chain itself as the single step
+        let steps = match chain.chain_type.as_str() {
+            "SimpleSequentialChain" | "SequentialChain" => chain.steps.clone(),
+            "LLMChain" if chain.steps.is_empty() => vec![LangChainStep {
+                chain_type: "LLMChain".to_string(),
+                name: None,
+                prompt: chain.prompt.clone(),
+                llm: chain.llm.clone(),
+                output_key: None,
+            }],
+            other => {
+                warnings.push(format!(
+                    "Unsupported chain_type '{}', steps are imported as a linear sequence",
+                    other
+                ));
+                chain.steps.clone()
+            }
+        };
+
+        if steps.is_empty() {
+            return Err(crate::error::PlatformError::ValidationError(
+                "LangChain definition has no steps".to_string()
+            ));
+        }
+
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        // Inputs of the first step become the flow's start variables
+        let start_variables: Vec<Value> = steps[0]
+            .prompt
+            .as_ref()
+            .map(|p| p.input_variables.iter()
+                .map(|v| json!({"variable": v, "default": ""}))
+                .collect())
+            .unwrap_or_default();
+
+        nodes.push(FlowNode {
+            id: Self::START_NODE_ID.to_string(),
+            parent_id: None,
+            node_type: NodeType::Start,
+            data: json!({"variables": start_variables}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        });
+
+        let mut previous_id = Self::START_NODE_ID.to_string();
+        let mut last_llm_id: Option<String> = None;
+
+        for (index, step) in steps.iter().enumerate() {
+            let node_id = format!("step_{}", index + 1);
+            let position = NodePosition { x: Self::NODE_SPACING * (index + 1) as f64, y: 0.0 };
+
+            let node = if step.chain_type == "LLMChain" {
+                let llm = step.llm.as_ref().or(chain.llm.as_ref());
+                let template = step.prompt.as_ref().map(|p| p.template.clone()).unwrap_or_default();
+                let input_variables = step.prompt.as_ref()
+                    .map(|p| p.input_variables.clone())
+                    .unwrap_or_default();
+
+                // First step reads flow inputs, later steps read the previous step's output
+                let source_node = if index == 0 { Self::START_NODE_ID } else { previous_id.as_str() };
+                let text = self.convert_template(&template, &input_variables, source_node, index == 0);
+
+                last_llm_id = Some(node_id.clone());
+
+                FlowNode {
+                    id: node_id.clone(),
+                    parent_id: None,
+                    node_type: NodeType::Llm,
+                    data: json!({
+                        "title": step.name.clone().unwrap_or_else(|| node_id.clone()),
+                        "model": self.convert_llm_config(llm),
+                        "prompt_template": [{"role": "user", "text": text}],
+                    }),
+                    position,
+                }
+            } else {
+                warnings.push(format!(
+                    "Step {} ('{}') has no equivalent node type and was imported as an HTTP request placeholder",
+                    index + 1,
+                    step.chain_type
+                ));
+
+                FlowNode {
+                    id: node_id.clone(),
+                    parent_id: None,
+                    node_type: NodeType::HttpRequest,
+                    data: json!({
+                        "title": step.name.clone().unwrap_or_else(|| step.chain_type.clone()),
+                        "placeholder": true,
+                        "langchain_type": step.chain_type,
+                    }),
+                    position,
+                }
+            };
+
+            edges.push(FlowEdge {
+                id: format!("{}-{}", previous_id, node_id),
+                source: previous_id.clone(),
+                target: node_id.clone(),
+                source_handle: None,
+                target_handle: None,
+            });
+
+            nodes.push(node);
+            previous_id = node_id;
+        }
+
+        let answer = last_llm_id
+            .map(|id| format!("{{{{#{}.text#}}}}", id))
+            .unwrap_or_default();
+
+        nodes.push(FlowNode {
+            id: Self::ANSWER_NODE_ID.to_string(),
+            parent_id: None,
+            node_type: NodeType::Answer,
+            data: json!({"answer": answer}),
+            position: NodePosition { x: Self::NODE_SPACING * (steps.len() + 1) as f64, y: 0.0 },
+        });
+
+        edges.push(FlowEdge {
+            id: format!("{}-{}", previous_id, Self::ANSWER_NODE_ID),
+            source: previous_id,
+            target: Self::ANSWER_NODE_ID.to_string(),
+            source_handle: None,
+            target_handle: None,
+        });
+
+        let definition = FlowDefinition {
+            workflow: FlowWorkflow {
+                graph: FlowGraph {
+                    nodes,
+                    edges,
+                },
+                environment_variables: Vec::new(),
+            },
+        };
+
+        definition.validate()
+            .map_err(|e| crate::error::PlatformError::ValidationError(e))?;
+
+        Ok(LangChainImport { definition, warnings })
+    }
+
+    /// Rewrite LangChain `{var}` placeholders into platform `{{#node_id.var#}}` references
+    fn convert_template(
+        &self,
+        template: &str,
+        input_variables: &[String],
+        source_node: &str,
+        is_first_step: bool,
+    ) -> String {
+        let mut result = template.to_string();
+        for var in input_variables {
+            let placeholder = format!("{{{}}}", var);
+            let reference = if is_first_step {
+                format!("{{{{#{}.{}#}}}}", source_node, var)
+            } else {
+                format!("{{{{#{}.text#}}}}", source_node)
+            };
+            result = result.replace(&placeholder, &reference);
+        }
+        result
+    }
+
+    fn convert_llm_config(&self, llm: Option<&LangChainLLMConfig>) -> Value {
+        let provider = llm
+            .and_then(|l| l.provider.as_deref())
+            .map(|p| match p.to_lowercase().as_str() {
+                "anthropic" | "anthropic-chat" | "claude" => "claude".to_string(),
+                "ollama" | "local" => "local_llm".to_string(),
+                _ => "openai".to_string(),
+            })
+            .unwrap_or_else(|| "openai".to_string());
+
+        let mut model = json!({ "provider": provider });
+        if let Some(name) = llm.and_then(|l| l.model_name.clone()) {
+            model["name"] = json!(name);
+        }
+        if let Some(temperature) = llm.and_then(|l| l.temperature) {
+            model["completion_params"] = json!({ "temperature": temperature });
+        }
+        model
+    }
+}
+
+impl Default for LangChainParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_sequential_chain() {
+        let json_str = r#"{
+            "chain_type": "SimpleSequentialChain",
+            "llm": {"provider": "openai", "model_name": "gpt-4"},
+            "steps": [
+                {
+                    "chain_type": "LLMChain",
+                    "prompt": {"template": "Write a title about {topic}", "input_variables": ["topic"]}
+                },
+                {
+                    "chain_type": "LLMChain",
+                    "prompt": {"template": "Write an article titled {title}", "input_variables": ["title"]}
+                }
+            ]
+        }"#;
+
+        let parser = LangChainParser::new();
+        let result = parser.parse(json_str).unwrap();
+        let graph = &result.definition.workflow.graph;
+
+        assert!(result.warnings.is_empty());
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.nodes[1].node_type, NodeType::Llm);
+        assert_eq!(
+            graph.nodes[1].data["prompt_template"][0]["text"],
+            json!("Write a title about {{#start.topic#}}")
+        );
+        assert_eq!(
+            graph.nodes[2].data["prompt_template"][0]["text"],
+            json!("Write an article titled {{#step_1.text#}}")
+        );
+        assert_eq!(graph.nodes[3].data["answer"], json!("{{#step_2.text#}}"));
+    }
+
+    #[test]
+    fn test_unsupported_step_becomes_placeholder() {
+        let json_str = r#"{
+            "chain_type": "SimpleSequentialChain",
+            "steps": [
+                {"chain_type": "SQLDatabaseChain"},
+                {"chain_type": "LLMChain", "prompt": {"template": "Summarize {rows}", "input_variables": ["rows"]}}
+            ]
+        }"#;
+
+        let parser = LangChainParser::new();
+        let result = parser.parse(json_str).unwrap();
+
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("SQLDatabaseChain"));
+        assert_eq!(
+            result.definition.workflow.graph.nodes[1].node_type,
+            NodeType::HttpRequest
+        );
+    }
+
+    #[test]
+    fn test_empty_steps_rejected() {
+        let json_str = r#"{"chain_type": "SimpleSequentialChain", "steps": []}"#;
+
+        let parser = LangChainParser::new();
+        assert!(parser.parse(json_str).is_err());
+    }
+}
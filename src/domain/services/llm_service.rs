@@ -3,7 +3,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
+use tracing::Instrument;
 use crate::infrastructure::llm::LLMProviderRegistry;
 
 /// LLM domain service interface defining business rules and operations
@@ -18,6 +21,20 @@ pub trait LLMDomainService: Send + Sync {
         response_format: Option<ResponseFormat>,
     ) -> Result<ChatResponse, LLMError>;
 
+    /// Generate a chat completion that may call `tools`. Calls the model
+    /// asks for are returned in the response's `metadata.tool_calls`.
+    /// Services without function calling answer without the tools.
+    async fn chat_completion_with_tools(
+        &self,
+        config: &ModelConfig,
+        messages: Vec<ChatMessage>,
+        tenant_id: Uuid,
+        response_format: Option<ResponseFormat>,
+        _tools: Vec<ToolDefinition>,
+    ) -> Result<ChatResponse, LLMError> {
+        self.chat_completion(config, messages, tenant_id, response_format).await
+    }
+
     /// Generate embeddings for the given text
     async fn generate_embedding(
         &self,
@@ -141,9 +158,26 @@ pub enum LLMError {
     #[error("Token limit exceeded: {0}")]
     TokenLimitExceeded(String),
 
+    /// The prompt does not fit the model's context window; retrying the
+    /// same request cannot succeed
+    #[error("Context length exceeded: {0}")]
+    ContextLengthExceeded(String),
+
+    /// The provider is temporarily over capacity; a later retry may succeed
+    #[error("Provider overloaded: {0}")]
+    ProviderOverloaded(String),
+
+    /// The provider refused the request under its usage policies
+    #[error("Content policy violation: {0}")]
+    ContentPolicyViolation(String),
+
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    /// The provider did not answer within the request timeout
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
@@ -186,6 +220,8 @@ pub struct ChatRequest {
     pub tenant_id: Uuid,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolDefinition>>,
 }
 
 /// 流式响应配置结构体
@@ -208,6 +244,15 @@ pub struct ResponseFormat {
     pub json_schema: Option<JsonSchema>,
 }
 
+/// Function the model may call while answering
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON schema of the arguments
+    pub parameters: serde_json::Value,
+}
+
 /// JSON schema for structured outputs
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct JsonSchema {
@@ -255,10 +300,35 @@ impl LLMDomainServiceImpl {
             ),
             tenant_id,
             response_format: None,
+            tools: None,
         }
     }
 }
 
+fn llm_request_span(
+    operation: &'static str,
+    provider_name: &str,
+    config: &ModelConfig,
+    tenant_id: Uuid,
+) -> tracing::Span {
+    tracing::info_span!(
+        "llm.request",
+        operation,
+        provider = %provider_name,
+        model = %config.model_name,
+        tenant_id = %tenant_id,
+        latency_ms = tracing::field::Empty,
+    )
+}
+
+/// Run a provider call inside `span`, recording its latency on the span
+async fn timed<F: Future>(span: tracing::Span, call: F) -> F::Output {
+    let started = Instant::now();
+    let output = call.instrument(span.clone()).await;
+    span.record("latency_ms", started.elapsed().as_millis() as u64);
+    output
+}
+
 #[async_trait]
 impl LLMDomainService for LLMDomainServiceImpl {
     async fn chat_completion(
@@ -267,6 +337,18 @@ impl LLMDomainService for LLMDomainServiceImpl {
         messages: Vec<ChatMessage>,
         tenant_id: Uuid,
         response_format: Option<ResponseFormat>,
+    ) -> Result<ChatResponse, LLMError> {
+        self.chat_completion_with_tools(config, messages, tenant_id, response_format, Vec::new())
+            .await
+    }
+
+    async fn chat_completion_with_tools(
+        &self,
+        config: &ModelConfig,
+        messages: Vec<ChatMessage>,
+        tenant_id: Uuid,
+        response_format: Option<ResponseFormat>,
+        tools: Vec<ToolDefinition>,
     ) -> Result<ChatResponse, LLMError> {
         // Validate configuration first
         let validation = self.validate_config(config)?;
@@ -285,7 +367,9 @@ impl LLMDomainService for LLMDomainServiceImpl {
         
         let mut request = self.build_chat_request(config, messages, tenant_id, false);
         request.response_format = response_format;
-        provider.chat_completion(request).await
+        request.tools = Some(tools).filter(|tools| !tools.is_empty());
+        let span = llm_request_span("chat_completion", &provider_name, config, tenant_id);
+        timed(span, provider.chat_completion(request)).await
     }
 
     async fn generate_embedding(
@@ -302,7 +386,8 @@ impl LLMDomainService for LLMDomainServiceImpl {
         let provider = self.provider_registry.create_provider(&config)
         .ok_or_else(|| LLMError::ProviderError(format!("Provider '{}' not found", provider_name)))?;
         
-        provider.generate_embedding(text).await
+        let span = llm_request_span("generate_embedding", &provider_name, config, tenant_id);
+        timed(span, provider.generate_embedding(text)).await
     }
 
     async fn stream_chat_completion(
@@ -330,7 +415,9 @@ impl LLMDomainService for LLMDomainServiceImpl {
         .ok_or_else(|| LLMError::ProviderError(format!("Provider '{}' not found", provider_name)))?;
         
         let request = self.build_chat_request(config, messages, tenant_id, true);
-        provider.stream_chat_completion(request).await
+        // Latency covers opening the stream, not consuming it
+        let span = llm_request_span("stream_chat_completion", &provider_name, config, tenant_id);
+        timed(span, provider.stream_chat_completion(request)).await
     }
 
     fn validate_config(&self, config: &ModelConfig) -> Result<ValidationResult, LLMError> {
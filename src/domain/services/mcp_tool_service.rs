@@ -19,6 +19,8 @@ pub struct ToolCallContext {
     pub session_id: Option<String>,
     pub request_id: String,
     pub metadata: HashMap<String, Value>,
+    /// 为true时参数不符合工具的参数模式则拒绝调用；为false时只记录警告并继续调用
+    pub strict_validation: bool,
 }
 
 impl ToolCallContext {
@@ -29,6 +31,7 @@ impl ToolCallContext {
             session_id: None,
             request_id,
             metadata: HashMap::new(),
+            strict_validation: true,
         }
     }
 
@@ -41,6 +44,37 @@ impl ToolCallContext {
         self.metadata.insert(key, value);
         self
     }
+
+    pub fn with_strict_validation(mut self, strict_validation: bool) -> Self {
+        self.strict_validation = strict_validation;
+        self
+    }
+}
+
+/// 在代理调用之前按工具的参数模式验证调用参数。非严格模式下验证失败只记录警告
+pub fn validate_tool_call_parameters(
+    tool: &MCPTool,
+    parameters: &Value,
+    context: &ToolCallContext,
+) -> Result<(), PlatformError> {
+    let Err(error) = tool.config.validate_call_parameters(parameters) else {
+        return Ok(());
+    };
+
+    if context.strict_validation {
+        return Err(PlatformError::ValidationError(format!(
+            "Invalid parameters for tool '{}': {}",
+            tool.name, error
+        )));
+    }
+
+    tracing::warn!(
+        "Calling tool '{}' with parameters that do not match its schema (request {}): {}",
+        tool.name,
+        context.request_id,
+        error
+    );
+    Ok(())
 }
 
 /// MCP工具调用结果
@@ -169,6 +203,7 @@ pub trait MCPToolDomainService: Send + Sync {
         &self,
         tool: &MCPTool,
         parameters: &Value,
+        context: &ToolCallContext,
     ) -> Result<(), PlatformError>;
 
     /// 测试工具连接
@@ -283,10 +318,9 @@ impl MCPToolDomainService for MCPToolDomainServiceImpl {
         &self,
         tool: &MCPTool,
         parameters: &Value,
+        context: &ToolCallContext,
     ) -> Result<(), PlatformError> {
-        tool.config
-            .validate_call_parameters(parameters)
-            .map_err(|e| PlatformError::ValidationError(e))
+        validate_tool_call_parameters(tool, parameters, context)
     }
 
     async fn test_tool_connection(&self, config: &ToolConfig) -> Result<ToolCallResult, PlatformError> {
@@ -345,7 +379,7 @@ impl MCPToolDomainService for MCPToolDomainServiceImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::value_objects::tool_config::{HTTPToolConfig, HttpMethod};
+    use crate::domain::value_objects::tool_config::{HTTPToolConfig, HttpMethod, ParameterSchema, ParameterType};
 
     #[tokio::test]
     async fn test_validate_tool_config() {
@@ -402,6 +436,33 @@ mod tests {
         assert!(!result.allowed);
     }
 
+    #[tokio::test]
+    async fn test_validate_call_parameters_strictness() {
+        let service = MCPToolDomainServiceImpl::new();
+        let tenant_id = TenantId::new();
+        let user_id = UserId::new();
+
+        let config = HTTPToolConfig::new("https://api.example.com/weather".to_string(), HttpMethod::POST)
+            .with_parameter(ParameterSchema::new("city".to_string(), ParameterType::String, true));
+        let tool = MCPTool::new(
+            tenant_id,
+            "weather".to_string(),
+            None,
+            ToolConfig::HTTP(config),
+            user_id,
+        );
+        let parameters = serde_json::json!({"city": 42});
+
+        let context = ToolCallContext::new(tenant_id, user_id, "req-123".to_string());
+        match service.validate_call_parameters(&tool, &parameters, &context).await {
+            Err(PlatformError::ValidationError(message)) => assert!(message.contains("/city"), "{}", message),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let context = context.with_strict_validation(false);
+        assert!(service.validate_call_parameters(&tool, &parameters, &context).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_test_tool_connection() {
         let service = MCPToolDomainServiceImpl::new();
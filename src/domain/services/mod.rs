@@ -1,18 +1,31 @@
 pub mod auth_service;
 pub mod llm_service;
+pub mod embedding_service;
 pub mod vector_service;
 pub mod mcp_tool_service;
 pub mod flow_service;
 pub mod dify_dsl_parser;
+pub mod dify_dsl_converter;
+pub mod langchain_parser;
 pub mod execution_engine;
+pub mod node_schema;
 pub mod node_executors;
 pub mod iteration_node_executor;
+pub mod document_ingestion_node_executor;
+pub mod knowledge_base_retrieval_node_executor;
+pub mod embedding_node_executor;
 pub mod execution_engine_factory;
+pub mod flow_dry_run;
 pub mod session_service;
 pub mod audit_service;
 pub mod execution_history_service;
 pub mod api_key_service;
+pub mod api_key_usage;
 pub mod agent_stats_service;
+pub mod secret_store;
+pub mod prompt_cache;
+pub mod chat_stream_buffer;
+pub mod outbound_url;
 
 #[cfg(test)]
 mod execution_engine_test;
@@ -22,16 +35,29 @@ mod external_service_integration_test;
 
 pub use auth_service::*;
 pub use llm_service::*;
+pub use embedding_service::*;
 pub use vector_service::*;
 pub use mcp_tool_service::*;
 pub use flow_service::*;
 pub use dify_dsl_parser::*;
+pub use dify_dsl_converter::*;
+pub use langchain_parser::*;
 pub use execution_engine::*;
+pub use node_schema::*;
 pub use node_executors::*;
 pub use iteration_node_executor::*;
+pub use document_ingestion_node_executor::*;
+pub use knowledge_base_retrieval_node_executor::*;
+pub use embedding_node_executor::*;
 pub use execution_engine_factory::*;
+pub use flow_dry_run::*;
 pub use session_service::*;
 pub use audit_service::*;
 pub use execution_history_service::*;
 pub use api_key_service::*;
-pub use agent_stats_service::*;
\ No newline at end of file
+pub use api_key_usage::*;
+pub use agent_stats_service::*;
+pub use secret_store::*;
+pub use prompt_cache::*;
+pub use chat_stream_buffer::*;
+pub use outbound_url::*;
\ No newline at end of file
@@ -5,12 +5,49 @@ use serde_json::Value;
 use std::sync::Arc;
 
 use crate::domain::services::execution_engine::{
-    ExecutionState, NodeExecutionResult, NodeExecutionStatus, NodeExecutor,
+    compare_values, ExecutionState, NodeExecutionResult, NodeExecutionStatus, NodeExecutor,
 };
 use crate::domain::value_objects::{FlowNode, NodeType};
 use crate::domain::ConfigId;
 use crate::error::Result;
 
+/// Replace {{variable_name}} with actual values from state
+/// This supports both regular variables and node-prefixed parameters like {{#node_id.param#}}
+pub(crate) fn resolve_template(template: &str, state: &ExecutionState) -> String {
+    let mut result = template.to_string();
+
+    for (key, value) in &state.variables {
+        let placeholder = format!("{{{{{}}}}}", key);
+        if result.contains(&placeholder) {
+            let value_str = match value {
+                Value::String(s) => s.clone(),
+                v => v.to_string(),
+            };
+            result = result.replace(&placeholder, &value_str);
+        }
+    }
+
+    result
+}
+
+/// Resolve `{{...}}` references in every string of a JSON value. Values
+/// are substituted into the strings, not into serialized JSON, so they
+/// cannot change the value's structure.
+pub(crate) fn resolve_template_value(value: &Value, state: &ExecutionState) -> Value {
+    match value {
+        Value::String(s) => Value::String(resolve_template(s, state)),
+        Value::Array(items) => Value::Array(
+            items.iter().map(|item| resolve_template_value(item, state)).collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, item)| (key.clone(), resolve_template_value(item, state)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
 /// Start node executor - saves flow parameters for later access
 pub struct StartNodeExecutor;
 
@@ -76,6 +113,10 @@ impl NodeExecutor for StartNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -159,7 +200,7 @@ impl NodeExecutor for EndNodeExecutor {
 
         let output = serde_json::json!({
             "message": "Flow completed",
-            "final_variables": state.variables,
+            "final_variables": state.public_variables(),
         });
 
         let completed_at = Utc::now();
@@ -175,6 +216,10 @@ impl NodeExecutor for EndNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -236,6 +281,10 @@ impl NodeExecutor for VariableNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -266,13 +315,63 @@ impl VariableNodeExecutor {
     }
 }
 
-/// Condition node executor - evaluates conditions
+/// Condition node executor - picks the branch to follow.
+///
+/// `data.conditions` is an ordered list of
+/// `{"variable", "operator", "value", "next_node_id"}` objects. The first
+/// matching condition selects the next node; when none match,
+/// `data.default_next_node_id` is used if present.
 pub struct ConditionNodeExecutor;
 
 impl ConditionNodeExecutor {
     pub fn new() -> Self {
         Self
     }
+
+    /// Index and target of the first matching condition
+    fn select_branch(conditions: &[Value], state: &ExecutionState) -> Result<Option<(usize, String)>> {
+        for (index, condition) in conditions.iter().enumerate() {
+            let variable = condition
+                .get("variable")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    crate::error::PlatformError::ValidationError(format!(
+                        "Condition {} missing 'variable' field",
+                        index
+                    ))
+                })?;
+
+            let operator = condition
+                .get("operator")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    crate::error::PlatformError::ValidationError(format!(
+                        "Condition {} missing 'operator' field",
+                        index
+                    ))
+                })?;
+
+            let next_node_id = condition
+                .get("next_node_id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    crate::error::PlatformError::ValidationError(format!(
+                        "Condition {} missing 'next_node_id' field",
+                        index
+                    ))
+                })?;
+
+            // Unset variables compare as null, so `is_empty` matches them
+            let actual = state.get_variable(variable).unwrap_or(&Value::Null);
+            let expected = condition.get("value").unwrap_or(&Value::Null);
+
+            if compare_values(actual, operator, expected)? {
+                return Ok(Some((index, next_node_id.to_string())));
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 impl Default for ConditionNodeExecutor {
@@ -286,15 +385,80 @@ impl NodeExecutor for ConditionNodeExecutor {
     async fn execute(
         &self,
         node: &FlowNode,
-        _state: &mut ExecutionState,
+        state: &mut ExecutionState,
     ) -> Result<NodeExecutionResult> {
         let started_at = Utc::now();
+        state.next_node_id = None;
+
+        // Legacy single `condition` nodes are evaluated by the engine
+        let Some(conditions) = node.data.get("conditions") else {
+            let completed_at = Utc::now();
+            return Ok(NodeExecutionResult {
+                node_id: node.id.clone(),
+                status: NodeExecutionStatus::Success,
+                output: Some(serde_json::json!({
+                    "message": "Condition evaluated",
+                    "condition": node.data.get("condition"),
+                })),
+                error: None,
+                started_at,
+                completed_at,
+                execution_time_ms: completed_at
+                    .signed_duration_since(started_at)
+                    .num_milliseconds(),
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
+            });
+        };
+
+        let selected = match conditions
+            .as_array()
+            .ok_or_else(|| {
+                crate::error::PlatformError::ValidationError(
+                    "Condition node 'conditions' must be an array".to_string(),
+                )
+            })
+            .and_then(|conditions| Self::select_branch(conditions, state))
+        {
+            Ok(selected) => selected,
+            Err(e) => {
+                let completed_at = Utc::now();
+                let execution_time_ms = completed_at
+                    .signed_duration_since(started_at)
+                    .num_milliseconds();
+                return Ok(NodeExecutionResult {
+                    node_id: node.id.clone(),
+                    status: NodeExecutionStatus::Failed,
+                    output: None,
+                    error: Some(e.to_string()),
+                    started_at,
+                    completed_at,
+                    execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
+                });
+            }
+        };
+
+        let (matched_condition, next_node_id) = match selected {
+            Some((index, next_node_id)) => (Some(index), Some(next_node_id)),
+            None => (
+                None,
+                node.data
+                    .get("default_next_node_id")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+            ),
+        };
+        state.next_node_id = next_node_id.clone();
 
-        // Condition evaluation is handled by the execution engine
-        // This executor just marks the node as executed
         let output = serde_json::json!({
-            "message": "Condition evaluated",
-            "condition": node.data.get("condition"),
+            "matched_condition": matched_condition,
+            "next_node_id": next_node_id,
         });
 
         let completed_at = Utc::now();
@@ -310,6 +474,10 @@ impl NodeExecutor for ConditionNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -363,6 +531,10 @@ impl NodeExecutor for LoopNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -459,6 +631,10 @@ impl NodeExecutor for AnswerNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -467,12 +643,147 @@ impl NodeExecutor for AnswerNodeExecutor {
     }
 }
 
-/// Code node executor - executes code snippets (placeholder for now)
-pub struct CodeNodeExecutor;
+/// Code node executor - runs JavaScript snippets in an isolated V8 context.
+/// Requires the `js-sandbox` feature; without it code nodes fail.
+///
+/// Expected data format:
+/// {"code": "return inputs.a + inputs.b;", "inputs": {"a": "#start.a#", "b": "#start.b#"}, "timeout_ms": 5000}
+///
+/// The snippet is the body of a synchronous function; its return value must be
+/// JSON-serializable and is stored under `#node_id.output#`.
+pub struct CodeNodeExecutor {
+    max_heap_bytes: usize,
+}
 
 impl CodeNodeExecutor {
+    const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+    /// Upper bound for a node's `timeout_ms`; each run holds a blocking thread
+    const MAX_TIMEOUT_MS: u64 = 60_000;
+    const DEFAULT_MAX_HEAP_BYTES: usize = 64 * 1024 * 1024;
+
     pub fn new() -> Self {
-        Self
+        Self {
+            max_heap_bytes: Self::DEFAULT_MAX_HEAP_BYTES,
+        }
+    }
+
+    /// Set the V8 heap limit for each script run
+    pub fn with_memory_limit(mut self, max_heap_bytes: usize) -> Self {
+        self.max_heap_bytes = max_heap_bytes;
+        self
+    }
+
+    /// Collect the `inputs` object exposed to the script.
+    /// `data.inputs` is either `{"name": "state_key"}` or a list of state keys.
+    fn collect_inputs(node: &FlowNode, state: &ExecutionState) -> Value {
+        let lookup = |key: &str| {
+            let key = key.trim().trim_start_matches("{{").trim_end_matches("}}");
+            state.get_variable(key).cloned().unwrap_or(Value::Null)
+        };
+
+        let mut inputs = serde_json::Map::new();
+        match node.data.get("inputs") {
+            Some(Value::Object(mapping)) => {
+                for (name, source) in mapping {
+                    let value = match source.as_str() {
+                        Some(key) => lookup(key),
+                        None => source.clone(),
+                    };
+                    inputs.insert(name.clone(), value);
+                }
+            }
+            Some(Value::Array(keys)) => {
+                for key in keys.iter().filter_map(|k| k.as_str()) {
+                    inputs.insert(key.to_string(), lookup(key));
+                }
+            }
+            _ => {}
+        }
+
+        Value::Object(inputs)
+    }
+
+    /// Run the script on the current (blocking) thread. V8 isolates are not
+    /// `Send`, so the runtime is created, used and dropped here.
+    #[cfg(feature = "js-sandbox")]
+    fn run_script(
+        code: &str,
+        inputs: &Value,
+        timeout: std::time::Duration,
+        max_heap_bytes: usize,
+    ) -> std::result::Result<Value, String> {
+        use deno_core::{v8, JsRuntime, RuntimeOptions};
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::mpsc;
+
+        let mut runtime = JsRuntime::new(RuntimeOptions {
+            create_params: Some(v8::CreateParams::default().heap_limits(0, max_heap_bytes)),
+            ..Default::default()
+        });
+
+        let isolate_handle = runtime.v8_isolate().thread_safe_handle();
+        let out_of_memory = Arc::new(AtomicBool::new(false));
+        {
+            let handle = isolate_handle.clone();
+            let out_of_memory = out_of_memory.clone();
+            // Terminate instead of letting V8 abort the process, granting headroom to unwind
+            runtime.add_near_heap_limit_callback(move |current_limit, _initial_limit| {
+                out_of_memory.store(true, Ordering::SeqCst);
+                handle.terminate_execution();
+                current_limit * 2
+            });
+        }
+
+        // Watchdog terminates the isolate once the timeout elapses
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let watchdog = {
+            let timed_out = timed_out.clone();
+            std::thread::spawn(move || {
+                if let Err(mpsc::RecvTimeoutError::Timeout) = done_rx.recv_timeout(timeout) {
+                    timed_out.store(true, Ordering::SeqCst);
+                    isolate_handle.terminate_execution();
+                }
+            })
+        };
+
+        let script = format!(
+            "globalThis.inputs = {};\n(function (inputs) {{\n{}\n}})(globalThis.inputs)",
+            inputs, code
+        );
+
+        let result = runtime
+            .execute_script("<code_node>", script)
+            .map_err(|e| e.to_string())
+            .and_then(|global| {
+                let scope = &mut runtime.handle_scope();
+                let local = v8::Local::new(scope, global);
+                deno_core::serde_v8::from_v8::<Value>(scope, local)
+                    .map_err(|e| format!("Script result is not JSON-serializable: {}", e))
+            });
+
+        drop(done_tx);
+        let _ = watchdog.join();
+
+        if timed_out.load(Ordering::SeqCst) {
+            return Err(format!("Script timed out after {} ms", timeout.as_millis()));
+        }
+        if out_of_memory.load(Ordering::SeqCst) {
+            return Err(format!("Script exceeded memory limit of {} bytes", max_heap_bytes));
+        }
+
+        result
+    }
+
+    #[cfg(not(feature = "js-sandbox"))]
+    fn run_script(
+        _code: &str,
+        _inputs: &Value,
+        _timeout: std::time::Duration,
+        _max_heap_bytes: usize,
+    ) -> std::result::Result<Value, String> {
+        Err("Code nodes are not available: the server was built without the js-sandbox feature"
+            .to_string())
     }
 }
 
@@ -491,32 +802,65 @@ impl NodeExecutor for CodeNodeExecutor {
     ) -> Result<NodeExecutionResult> {
         let started_at = Utc::now();
 
-        // For now, this is a placeholder
-        // In a real implementation, this would execute code in a sandboxed environment
-        let code = node.data.get("code").and_then(|v| v.as_str()).unwrap_or("");
+        let code = node
+            .data
+            .get("code")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let inputs = Self::collect_inputs(node, state);
+        let timeout = std::time::Duration::from_millis(
+            node.data
+                .get("timeout_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(Self::DEFAULT_TIMEOUT_MS)
+                .min(Self::MAX_TIMEOUT_MS),
+        );
+        let max_heap_bytes = self.max_heap_bytes;
 
-        // Simple variable extraction for demonstration
-        // In production, you'd use a proper code execution engine
-        let output = serde_json::json!({
-            "message": "Code execution placeholder",
-            "code_length": code.len(),
-            "variables": state.variables,
-        });
+        let result = tokio::task::spawn_blocking(move || {
+            Self::run_script(&code, &inputs, timeout, max_heap_bytes)
+        })
+        .await
+        .unwrap_or_else(|e| Err(format!("Script execution aborted: {}", e)));
 
         let completed_at = Utc::now();
         let execution_time_ms = completed_at
             .signed_duration_since(started_at)
             .num_milliseconds();
 
-        Ok(NodeExecutionResult {
-            node_id: node.id.clone(),
-            status: NodeExecutionStatus::Success,
-            output: Some(output),
-            error: None,
-            started_at,
-            completed_at,
-            execution_time_ms,
-        })
+        match result {
+            Ok(output) => {
+                state.set_variable(format!("#{}.output#", node.id), output.clone());
+
+                Ok(NodeExecutionResult {
+                    node_id: node.id.clone(),
+                    status: NodeExecutionStatus::Success,
+                    output: Some(json!({ "output": output })),
+                    error: None,
+                    started_at,
+                    completed_at,
+                    execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
+                })
+            }
+            Err(e) => Ok(NodeExecutionResult {
+                node_id: node.id.clone(),
+                status: NodeExecutionStatus::Failed,
+                output: None,
+                error: Some(e),
+                started_at,
+                completed_at,
+                execution_time_ms,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
+            }),
+        }
     }
 
     fn can_handle(&self, node_type: &NodeType) -> bool {
@@ -524,12 +868,139 @@ impl NodeExecutor for CodeNodeExecutor {
     }
 }
 
-/// HTTP Request node executor - makes HTTP requests (placeholder for now)
-pub struct HttpRequestNodeExecutor;
+/// HTTP Request node executor - makes HTTP requests
+///
+/// Expected data format:
+/// {"url": "https://...", "method": "POST", "headers": {"X-Key": "..."}, "body": ...,
+///  "timeout_ms": 30000, "auth": {"type": "bearer", "token": "..."}}
+pub struct HttpRequestNodeExecutor {
+    client: reqwest::Client,
+}
 
 impl HttpRequestNodeExecutor {
+    const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+    /// Upper bound for a node's `timeout_ms`
+    const MAX_TIMEOUT_MS: u64 = 300_000;
+
     pub fn new() -> Self {
-        Self
+        Self {
+            // Redirects could lead to internal addresses the URL check never saw
+            client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Build the outgoing request from node data, resolving `{{...}}` references
+    fn build_request(
+        &self,
+        node: &FlowNode,
+        state: &ExecutionState,
+    ) -> std::result::Result<reqwest::Request, String> {
+        let url = node
+            .data
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "HTTP request node missing 'url' field".to_string())?;
+        let url = resolve_template(url, state);
+
+        let method = node
+            .data
+            .get("method")
+            .and_then(|v| v.as_str())
+            .unwrap_or("GET");
+        let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+            .map_err(|_| format!("Invalid HTTP method: {}", method))?;
+
+        let timeout_ms = node
+            .data
+            .get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(Self::DEFAULT_TIMEOUT_MS)
+            .min(Self::MAX_TIMEOUT_MS);
+
+        let mut builder = self
+            .client
+            .request(method, &url)
+            .timeout(std::time::Duration::from_millis(timeout_ms));
+
+        if let Some(headers) = node.data.get("headers").and_then(|v| v.as_object()) {
+            for (name, value) in headers {
+                let value = match value {
+                    Value::String(s) => resolve_template(s, state),
+                    v => v.to_string(),
+                };
+                builder = builder.header(name.as_str(), value);
+            }
+        }
+
+        if let Some(auth) = node.data.get("auth").and_then(|v| v.as_object()) {
+            let field = |key: &str| {
+                auth.get(key)
+                    .and_then(|v| v.as_str())
+                    .map(|v| resolve_template(v, state))
+            };
+
+            builder = match auth.get("type").and_then(|v| v.as_str()).unwrap_or("none") {
+                "none" => builder,
+                "bearer" => builder.bearer_auth(field("token").unwrap_or_default()),
+                "basic" => builder.basic_auth(field("username").unwrap_or_default(), field("password")),
+                "api_key" => builder.header(
+                    field("header").unwrap_or_else(|| "X-API-Key".to_string()),
+                    field("key").unwrap_or_default(),
+                ),
+                other => return Err(format!("Unsupported auth type: {}", other)),
+            };
+        }
+
+        match node.data.get("body") {
+            None | Some(Value::Null) => {}
+            Some(Value::String(body)) => {
+                builder = builder.body(resolve_template(body, state));
+            }
+            Some(body) => {
+                // Structured bodies are sent as JSON unless a content type was given
+                let has_content_type = node
+                    .data
+                    .get("headers")
+                    .and_then(|v| v.as_object())
+                    .map(|h| h.keys().any(|k| k.eq_ignore_ascii_case("content-type")))
+                    .unwrap_or(false);
+                if !has_content_type {
+                    builder = builder.header("Content-Type", "application/json");
+                }
+                builder = builder.body(resolve_template_value(body, state).to_string());
+            }
+        }
+
+        builder.build().map_err(|e| format!("Invalid HTTP request: {}", e))
+    }
+
+    fn failed_result(
+        node: &FlowNode,
+        started_at: chrono::DateTime<Utc>,
+        output: Option<Value>,
+        error: String,
+    ) -> NodeExecutionResult {
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+
+        NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Failed,
+            output,
+            error: Some(error),
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        }
     }
 }
 
@@ -544,25 +1015,76 @@ impl NodeExecutor for HttpRequestNodeExecutor {
     async fn execute(
         &self,
         node: &FlowNode,
-        _state: &mut ExecutionState,
+        state: &mut ExecutionState,
     ) -> Result<NodeExecutionResult> {
         let started_at = Utc::now();
 
-        // Placeholder for HTTP request execution
-        // In production, this would make actual HTTP requests
-        let url = node.data.get("url").and_then(|v| v.as_str()).unwrap_or("");
-        let method = node
-            .data
-            .get("method")
-            .and_then(|v| v.as_str())
-            .unwrap_or("GET");
+        let request = match self.build_request(node, state) {
+            Ok(request) => request,
+            Err(e) => return Ok(Self::failed_result(node, started_at, None, e)),
+        };
 
-        let output = serde_json::json!({
-            "message": "HTTP request placeholder",
-            "url": url,
-            "method": method,
+        // Flow authors choose the URL; keep it off internal services
+        if let Err(e) = super::outbound_url::ensure_public_http_url(request.url().as_str()).await {
+            return Ok(Self::failed_result(node, started_at, None, e.to_string()));
+        }
+
+        let response = match self.client.execute(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Ok(Self::failed_result(
+                    node,
+                    started_at,
+                    None,
+                    format!("HTTP request failed: {}", e),
+                ))
+            }
+        };
+
+        let status = response.status();
+        let headers: serde_json::Map<String, Value> = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|v| (name.as_str().to_string(), json!(v)))
+            })
+            .collect();
+
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(e) => {
+                return Ok(Self::failed_result(
+                    node,
+                    started_at,
+                    None,
+                    format!("Failed to read HTTP response body: {}", e),
+                ))
+            }
+        };
+
+        // Store response in state variables
+        state.set_variable(format!("#{}.status_code#", node.id), json!(status.as_u16()));
+        state.set_variable(format!("#{}.body#", node.id), json!(body));
+        state.set_variable(format!("#{}.headers#", node.id), Value::Object(headers.clone()));
+
+        let output = json!({
+            "status_code": status.as_u16(),
+            "body": body,
+            "headers": headers,
         });
 
+        if !status.is_success() {
+            return Ok(Self::failed_result(
+                node,
+                started_at,
+                Some(output),
+                format!("HTTP request returned status {}", status),
+            ));
+        }
+
         let completed_at = Utc::now();
         let execution_time_ms = completed_at
             .signed_duration_since(started_at)
@@ -576,6 +1098,10 @@ impl NodeExecutor for HttpRequestNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -584,11 +1110,17 @@ impl NodeExecutor for HttpRequestNodeExecutor {
     }
 }
 
+/// Tool call rounds an LLM node takes before the model has to answer,
+/// unless the node sets `max_tool_rounds`
+pub const DEFAULT_MAX_TOOL_ROUNDS: usize = 3;
+
 /// LLM Chat node executor - integrates with LLM services
 pub struct LLMChatNodeExecutor {
     llm_service: Arc<dyn crate::domain::services::llm_service::LLMDomainService>,
     llm_config_repository:
         Arc<dyn crate::domain::repositories::llm_config_repository::LLMConfigRepository>,
+    tool_executor: Option<Arc<ToolCallNodeExecutor>>,
+    prompt_cache: Option<Arc<dyn crate::domain::services::prompt_cache::PromptCache>>,
 }
 
 impl LLMChatNodeExecutor {
@@ -601,9 +1133,35 @@ impl LLMChatNodeExecutor {
         Self {
             llm_service,
             llm_config_repository,
+            tool_executor: None,
+            prompt_cache: None,
         }
     }
 
+    /// Set tool executor so nodes listing `tools` can call MCP tools
+    pub fn with_tool_executor(mut self, tool_executor: Arc<ToolCallNodeExecutor>) -> Self {
+        self.tool_executor = Some(tool_executor);
+        self
+    }
+
+    /// Set prompt cache so nodes with `cache_ttl_seconds` can reuse responses
+    pub fn with_prompt_cache(
+        mut self,
+        prompt_cache: Arc<dyn crate::domain::services::prompt_cache::PromptCache>,
+    ) -> Self {
+        self.prompt_cache = Some(prompt_cache);
+        self
+    }
+
+    /// How long the node's responses are cached, if at all
+    fn extract_cache_ttl(&self, node: &FlowNode) -> Option<std::time::Duration> {
+        node.data
+            .get("cache_ttl_seconds")
+            .and_then(|v| v.as_u64())
+            .filter(|ttl| *ttl > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
     fn extract_messages(
         &self,
         node: &FlowNode,
@@ -729,22 +1287,7 @@ impl LLMChatNodeExecutor {
     }
 
     fn resolve_template(&self, template: &str, state: &ExecutionState) -> String {
-        let mut result = template.to_string();
-
-        // Replace {{variable_name}} with actual values from state
-        // This supports both regular variables and node-prefixed parameters like {{#node_id.param#}}
-        for (key, value) in &state.variables {
-            let placeholder = format!("{{{{{}}}}}", key);
-            if result.contains(&placeholder) {
-                let value_str = match value {
-                    Value::String(s) => s.clone(),
-                    v => v.to_string(),
-                };
-                result = result.replace(&placeholder, &value_str);
-            }
-        }
-
-        result
+        resolve_template(template, state)
     }
 
     async fn extract_model_config(
@@ -824,33 +1367,225 @@ impl LLMChatNodeExecutor {
                 )
             })
     }
-}
 
-#[async_trait]
-impl NodeExecutor for LLMChatNodeExecutor {
-    async fn execute(
+    /// Store the response in state variables and build the node result
+    fn complete(
         &self,
         node: &FlowNode,
         state: &mut ExecutionState,
-    ) -> Result<NodeExecutionResult> {
-        let started_at = Utc::now();
+        started_at: chrono::DateTime<Utc>,
+        response: crate::domain::services::llm_service::ChatResponse,
+        tool_calls: Vec<crate::domain::value_objects::chat_message::ToolCall>,
+    ) -> NodeExecutionResult {
+        // Store response in state variables
+        let output_var = node
+            .data
+            .get("output_variable")
+            .and_then(|v| v.as_str())
+            .unwrap_or("llm_response");
 
-        // Extract configuration and messages
-        let model_config = match self.extract_model_config(node, state).await {
-            Ok(config) => config,
-            Err(e) => {
-                let completed_at = Utc::now();
-                let execution_time_ms = completed_at
-                    .signed_duration_since(started_at)
-                    .num_milliseconds();
-                return Ok(NodeExecutionResult {
-                    node_id: node.id.clone(),
-                    status: NodeExecutionStatus::Failed,
-                    output: None,
-                    error: Some(e.to_string()),
+        state.set_variable(output_var.to_string(), serde_json::json!(response.content));
+
+        // Also store response.content in #node_id.text# and #node_id.structured_output# for easy access in subsequent nodes
+        state.set_variable(format!("#{}.text#", node.id), serde_json::json!(response.content));
+        state.set_variable(format!("#{}.structured_output#", node.id), serde_json::json!(response.content));
+
+        let mut output = serde_json::json!({
+            "content": response.content,
+            "model_used": response.model_used,
+            "usage": {
+                "prompt_tokens": response.usage.prompt_tokens,
+                "completion_tokens": response.usage.completion_tokens,
+                "total_tokens": response.usage.total_tokens,
+            },
+            "finish_reason": format!("{:?}", response.finish_reason),
+        });
+        if !tool_calls.is_empty() {
+            output["tool_calls"] = serde_json::json!(tool_calls);
+        }
+
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+
+        NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Success,
+            output: Some(output),
+            error: None,
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: Some(response.usage),
+            retry_count: 0,
+            cached: false,
+        }
+    }
+
+    /// MCP tools the model may call, from the node's `tools` list
+    fn extract_tool_ids(
+        &self,
+        node: &FlowNode,
+    ) -> Result<Vec<crate::domain::value_objects::ids::MCPToolId>> {
+        let Some(tools) = node.data.get("tools") else {
+            return Ok(Vec::new());
+        };
+
+        let tools = tools.as_array().ok_or_else(|| {
+            crate::error::PlatformError::ValidationError(
+                "LLM node 'tools' must be a list of MCP tool ids".to_string(),
+            )
+        })?;
+
+        tools
+            .iter()
+            .map(|tool_id| {
+                tool_id
+                    .as_str()
+                    .and_then(|s| uuid::Uuid::parse_str(s).ok())
+                    .map(crate::domain::value_objects::ids::MCPToolId::from_uuid)
+                    .ok_or_else(|| {
+                        crate::error::PlatformError::ValidationError(format!(
+                            "Invalid tool id in LLM node 'tools': {}",
+                            tool_id
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    fn extract_max_tool_rounds(&self, node: &FlowNode) -> usize {
+        node.data
+            .get("max_tool_rounds")
+            .and_then(|v| v.as_u64())
+            .map(|rounds| rounds as usize)
+            .unwrap_or(DEFAULT_MAX_TOOL_ROUNDS)
+    }
+
+    /// Chat while letting the model call the node's tools. Each round runs
+    /// the calls the model asked for and sends it the results; once
+    /// `max_tool_rounds` rounds are used the model has to answer without tools.
+    /// Returns the final response, with the usage of all rounds, and the
+    /// calls that were made.
+    async fn chat_with_tools(
+        &self,
+        node: &FlowNode,
+        state: &ExecutionState,
+        model_config: &crate::domain::value_objects::ModelConfig,
+        mut messages: Vec<crate::domain::value_objects::ChatMessage>,
+        tenant_id: uuid::Uuid,
+        response_format: Option<crate::domain::services::llm_service::ResponseFormat>,
+        tool_ids: &[crate::domain::value_objects::ids::MCPToolId],
+    ) -> Result<(
+        crate::domain::services::llm_service::ChatResponse,
+        Vec<crate::domain::value_objects::chat_message::ToolCall>,
+    )> {
+        let tool_executor = self.tool_executor.as_ref().ok_or_else(|| {
+            crate::error::PlatformError::InternalError(
+                "MCP tools are not available to LLM nodes".to_string(),
+            )
+        })?;
+
+        let tools = tool_executor.load_tools(tool_ids, state).await?;
+        let definitions: Vec<_> = tools.iter().map(ToolCallNodeExecutor::tool_definition).collect();
+        let max_rounds = self.extract_max_tool_rounds(node);
+
+        let mut usage = crate::domain::services::llm_service::TokenUsage::new(0, 0);
+        let mut executed = Vec::new();
+        let mut round = 0;
+
+        loop {
+            let offered = if round < max_rounds { definitions.clone() } else { Vec::new() };
+            let mut response = self
+                .llm_service
+                .chat_completion_with_tools(
+                    model_config,
+                    messages.clone(),
+                    tenant_id,
+                    response_format.clone(),
+                    offered,
+                )
+                .await
+                .map_err(|e| {
+                    crate::error::PlatformError::InternalError(format!("LLM call failed: {}", e))
+                })?;
+
+            usage.prompt_tokens += response.usage.prompt_tokens;
+            usage.completion_tokens += response.usage.completion_tokens;
+            usage.total_tokens += response.usage.total_tokens;
+
+            let tool_calls = response
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.tool_calls.clone())
+                .unwrap_or_default();
+
+            if tool_calls.is_empty() || round >= max_rounds {
+                response.usage = usage;
+                return Ok((response, executed));
+            }
+
+            // Results are matched to calls by id, so every call needs one
+            let tool_calls: Vec<_> = tool_calls
+                .into_iter()
+                .enumerate()
+                .map(|(index, mut call)| {
+                    call.call_id
+                        .get_or_insert_with(|| format!("call_{}_{}", round, index));
+                    call
+                })
+                .collect();
+
+            messages.push(crate::domain::value_objects::ChatMessage::new_tool_call_message(
+                response.content.clone(),
+                tool_calls.clone(),
+            ));
+
+            for call in tool_calls {
+                let call = tool_executor.invoke(&tools, call, state).await;
+                messages.push(crate::domain::value_objects::ChatMessage::new_tool_result_message(
+                    call.call_id.clone().unwrap_or_default(),
+                    ToolCallNodeExecutor::result_content(&call),
+                ));
+                executed.push(call);
+            }
+
+            round += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl NodeExecutor for LLMChatNodeExecutor {
+    async fn execute(
+        &self,
+        node: &FlowNode,
+        state: &mut ExecutionState,
+    ) -> Result<NodeExecutionResult> {
+        let started_at = Utc::now();
+
+        // Extract configuration and messages
+        let model_config = match self.extract_model_config(node, state).await {
+            Ok(config) => config,
+            Err(e) => {
+                let completed_at = Utc::now();
+                let execution_time_ms = completed_at
+                    .signed_duration_since(started_at)
+                    .num_milliseconds();
+                return Ok(NodeExecutionResult {
+                    node_id: node.id.clone(),
+                    status: NodeExecutionStatus::Failed,
+                    output: None,
+                    error: Some(e.to_string()),
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -870,6 +1605,10 @@ impl NodeExecutor for LLMChatNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -889,6 +1628,33 @@ impl NodeExecutor for LLMChatNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
+                });
+            }
+        };
+
+        let tool_ids = match self.extract_tool_ids(node) {
+            Ok(ids) => ids,
+            Err(e) => {
+                let completed_at = Utc::now();
+                let execution_time_ms = completed_at
+                    .signed_duration_since(started_at)
+                    .num_milliseconds();
+                return Ok(NodeExecutionResult {
+                    node_id: node.id.clone(),
+                    status: NodeExecutionStatus::Failed,
+                    output: None,
+                    error: Some(e.to_string()),
+                    started_at,
+                    completed_at,
+                    execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -896,6 +1662,70 @@ impl NodeExecutor for LLMChatNodeExecutor {
         // Extract structured output configuration if present
         let response_format = self.extract_structured_output(node);
 
+        // Let the model call the node's tools before it answers
+        if !tool_ids.is_empty() {
+            let (response, tool_calls) = match self
+                .chat_with_tools(
+                    node,
+                    state,
+                    &model_config,
+                    messages,
+                    tenant_id,
+                    response_format,
+                    &tool_ids,
+                )
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    let completed_at = Utc::now();
+                    let execution_time_ms = completed_at
+                        .signed_duration_since(started_at)
+                        .num_milliseconds();
+                    return Ok(NodeExecutionResult {
+                        node_id: node.id.clone(),
+                        status: NodeExecutionStatus::Failed,
+                        output: None,
+                        error: Some(e.to_string()),
+                        started_at,
+                        completed_at,
+                        execution_time_ms,
+                        cancelled: false,
+                        token_usage: None,
+                        retry_count: 0,
+                        cached: false,
+                    });
+                }
+            };
+            return Ok(self.complete(node, state, started_at, response, tool_calls));
+        }
+
+        // Nodes with tools are never cached, since a cached answer would skip the tool calls
+        let cache = match (&self.prompt_cache, self.extract_cache_ttl(node)) {
+            (Some(prompt_cache), Some(ttl)) => {
+                let prompt_hash = crate::domain::services::prompt_cache::prompt_hash(
+                    tenant_id,
+                    &model_config,
+                    &messages,
+                    response_format.as_ref(),
+                );
+                Some((prompt_cache, prompt_hash, ttl))
+            }
+            _ => None,
+        };
+
+        if let Some((prompt_cache, prompt_hash, _)) = &cache {
+            match prompt_cache.get(prompt_hash).await {
+                Ok(Some(response)) => {
+                    let mut result = self.complete(node, state, started_at, response, Vec::new());
+                    result.cached = true;
+                    return Ok(result);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(node_id = %node.id, "Failed to read LLM prompt cache: {}", e),
+            }
+        }
+
         // Call LLM service
         let response = match self
             .llm_service
@@ -916,48 +1746,21 @@ impl NodeExecutor for LLMChatNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
 
-        // Store response in state variables
-        let output_var = node
-            .data
-            .get("output_variable")
-            .and_then(|v| v.as_str())
-            .unwrap_or("llm_response");
-
-        state.set_variable(output_var.to_string(), serde_json::json!(response.content));
-
-        // Also store response.content in #node_id.text# and #node_id.structured_output# for easy access in subsequent nodes
-        state.set_variable(format!("#{}.text#", node.id), serde_json::json!(response.content));
-        state.set_variable(format!("#{}.structured_output#", node.id), serde_json::json!(response.content));
-
-        let output = serde_json::json!({
-            "content": response.content,
-            "model_used": response.model_used,
-            "usage": {
-                "prompt_tokens": response.usage.prompt_tokens,
-                "completion_tokens": response.usage.completion_tokens,
-                "total_tokens": response.usage.total_tokens,
-            },
-            "finish_reason": format!("{:?}", response.finish_reason),
-        });
-
-        let completed_at = Utc::now();
-        let execution_time_ms = completed_at
-            .signed_duration_since(started_at)
-            .num_milliseconds();
+        if let Some((prompt_cache, prompt_hash, ttl)) = &cache {
+            if let Err(e) = prompt_cache.set(prompt_hash, &response, *ttl).await {
+                tracing::warn!(node_id = %node.id, "Failed to write LLM prompt cache: {}", e);
+            }
+        }
 
-        Ok(NodeExecutionResult {
-            node_id: node.id.clone(),
-            status: NodeExecutionStatus::Success,
-            output: Some(output),
-            error: None,
-            started_at,
-            completed_at,
-            execution_time_ms,
-        })
+        Ok(self.complete(node, state, started_at, response, Vec::new()))
     }
 
     fn can_handle(&self, node_type: &NodeType) -> bool {
@@ -968,22 +1771,69 @@ impl NodeExecutor for LLMChatNodeExecutor {
 /// Vector Search node executor - integrates with vector database services
 pub struct VectorSearchNodeExecutor {
     vector_service: Arc<dyn crate::domain::services::vector_service::VectorStoreDomainService>,
+    embedding_provider: Option<Arc<dyn crate::domain::services::embedding_service::EmbeddingProvider>>,
 }
 
 impl VectorSearchNodeExecutor {
     pub fn new(
         vector_service: Arc<dyn crate::domain::services::vector_service::VectorStoreDomainService>,
     ) -> Self {
-        Self { vector_service }
+        Self {
+            vector_service,
+            embedding_provider: None,
+        }
+    }
+
+    /// Set embedding provider so nodes can search with a `text_query`
+    pub fn with_embedding_provider(
+        mut self,
+        embedding_provider: Arc<dyn crate::domain::services::embedding_service::EmbeddingProvider>,
+    ) -> Self {
+        self.embedding_provider = Some(embedding_provider);
+        self
+    }
+
+    /// Embed the node's `text_query` when no pre-computed vector is given
+    async fn embed_text_query(&self, node: &FlowNode, state: &ExecutionState) -> Result<Option<Vec<f32>>> {
+        if node.data.get("query_vector_variable").is_some() || node.data.get("query_vector").is_some() {
+            return Ok(None);
+        }
+
+        let Some(text_query) = node.data.get("text_query").and_then(|v| v.as_str()) else {
+            return Ok(None);
+        };
+
+        let text_query = resolve_template(text_query, state);
+        if text_query.trim().is_empty() {
+            return Err(crate::error::PlatformError::ValidationError(
+                "Vector search text_query cannot be empty".to_string(),
+            ));
+        }
+
+        let embedding_provider = self.embedding_provider.as_ref().ok_or_else(|| {
+            crate::error::PlatformError::ValidationError(
+                "Vector search text_query requires an embedding provider".to_string(),
+            )
+        })?;
+
+        let vector = embedding_provider
+            .embed_query(text_query)
+            .await
+            .map_err(|e| crate::error::PlatformError::InternalError(format!("Embedding failed: {}", e)))?;
+
+        Ok(Some(vector))
     }
 
     fn extract_search_query(
         &self,
         node: &FlowNode,
         state: &ExecutionState,
+        embedded_query: Option<Vec<f32>>,
     ) -> Result<crate::domain::value_objects::SearchQuery> {
-        // Get query vector - can be from a variable or directly specified
-        let vector = if let Some(var_name) = node
+        // Get query vector - can be from a variable, directly specified or embedded from text
+        let vector = if let Some(vector) = embedded_query {
+            vector
+        } else if let Some(var_name) = node
             .data
             .get("query_vector_variable")
             .and_then(|v| v.as_str())
@@ -1010,7 +1860,7 @@ impl VectorSearchNodeExecutor {
                 .collect::<Vec<f32>>()
         } else {
             return Err(crate::error::PlatformError::ValidationError(
-                "Vector search node missing query vector".to_string(),
+                "Vector search node missing query vector or text_query".to_string(),
             ));
         };
 
@@ -1070,8 +1920,12 @@ impl NodeExecutor for VectorSearchNodeExecutor {
     ) -> Result<NodeExecutionResult> {
         let started_at = Utc::now();
 
-        // Extract search query
-        let query = match self.extract_search_query(node, state) {
+        // Extract search query, embedding the text query first if needed
+        let query = match self
+            .embed_text_query(node, state)
+            .await
+            .and_then(|embedded| self.extract_search_query(node, state, embedded))
+        {
             Ok(q) => q,
             Err(e) => {
                 let completed_at = Utc::now();
@@ -1086,6 +1940,10 @@ impl NodeExecutor for VectorSearchNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -1105,6 +1963,10 @@ impl NodeExecutor for VectorSearchNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -1125,6 +1987,10 @@ impl NodeExecutor for VectorSearchNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -1167,6 +2033,10 @@ impl NodeExecutor for VectorSearchNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -1179,6 +2049,8 @@ impl NodeExecutor for VectorSearchNodeExecutor {
 pub struct MCPToolNodeExecutor {
     mcp_service: Arc<dyn crate::domain::services::mcp_tool_service::MCPToolDomainService>,
     tool_repository: Arc<dyn crate::domain::repositories::mcp_tool_repository::MCPToolRepository>,
+    proxy_service: Arc<dyn crate::infrastructure::mcp::MCPProxyService>,
+    template_engine: crate::infrastructure::mcp::ParameterTemplateEngine,
 }
 
 impl MCPToolNodeExecutor {
@@ -1187,10 +2059,13 @@ impl MCPToolNodeExecutor {
         tool_repository: Arc<
             dyn crate::domain::repositories::mcp_tool_repository::MCPToolRepository,
         >,
+        proxy_service: Arc<dyn crate::infrastructure::mcp::MCPProxyService>,
     ) -> Self {
         Self {
             mcp_service,
             tool_repository,
+            proxy_service,
+            template_engine: crate::infrastructure::mcp::ParameterTemplateEngine::new(),
         }
     }
 
@@ -1201,42 +2076,8 @@ impl MCPToolNodeExecutor {
             )
         })?;
 
-        // Resolve variable references in parameters
-        let resolved_params = self.resolve_parameters(params_data, state);
-        Ok(resolved_params)
-    }
-
-    fn resolve_parameters(&self, params: &Value, state: &ExecutionState) -> Value {
-        match params {
-            Value::Object(map) => {
-                let mut resolved = serde_json::Map::new();
-                for (key, value) in map {
-                    resolved.insert(key.clone(), self.resolve_parameters(value, state));
-                }
-                Value::Object(resolved)
-            }
-            Value::Array(arr) => {
-                let resolved: Vec<Value> = arr
-                    .iter()
-                    .map(|v| self.resolve_parameters(v, state))
-                    .collect();
-                Value::Array(resolved)
-            }
-            Value::String(s) => {
-                // Check if it's a variable reference with {{...}} syntax
-                if let Some(var_name) = s.strip_prefix("{{").and_then(|s| s.strip_suffix("}}")) {
-                    let trimmed = var_name.trim();
-                    state
-                        .variables
-                        .get(trimmed)
-                        .cloned()
-                        .unwrap_or_else(|| Value::String(s.clone()))
-                } else {
-                    Value::String(s.clone())
-                }
-            }
-            other => other.clone(),
-        }
+        // Resolve variable references and templates in parameters
+        self.template_engine.resolve(params_data, &state.variables)
     }
 
     fn extract_context(
@@ -1330,6 +2171,10 @@ impl NodeExecutor for MCPToolNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -1350,6 +2195,10 @@ impl NodeExecutor for MCPToolNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
             Err(e) => {
@@ -1365,13 +2214,23 @@ impl NodeExecutor for MCPToolNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
 
-        // Extract call context with tenant isolation
+        // Extract call context with tenant isolation. Parameters are checked
+        // strictly unless the node sets `strict_validation: false`
+        let strict_validation = node
+            .data
+            .get("strict_validation")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
         let context = match self.extract_context(state) {
-            Ok(ctx) => ctx,
+            Ok(ctx) => ctx.with_strict_validation(strict_validation),
             Err(e) => {
                 let completed_at = Utc::now();
                 let execution_time_ms = completed_at
@@ -1385,6 +2244,10 @@ impl NodeExecutor for MCPToolNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -1409,6 +2272,10 @@ impl NodeExecutor for MCPToolNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -1430,6 +2297,10 @@ impl NodeExecutor for MCPToolNodeExecutor {
                 started_at,
                 completed_at,
                 execution_time_ms,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
             });
         }
 
@@ -1449,6 +2320,10 @@ impl NodeExecutor for MCPToolNodeExecutor {
                     started_at,
                     completed_at,
                     execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
                 });
             }
         };
@@ -1456,7 +2331,7 @@ impl NodeExecutor for MCPToolNodeExecutor {
         // Validate parameters
         if let Err(e) = self
             .mcp_service
-            .validate_call_parameters(&tool, &parameters)
+            .validate_call_parameters(&tool, &parameters, &context)
             .await
         {
             let completed_at = Utc::now();
@@ -1471,21 +2346,40 @@ impl NodeExecutor for MCPToolNodeExecutor {
                 started_at,
                 completed_at,
                 execution_time_ms,
+                cancelled: false,
+                token_usage: None,
+                retry_count: 0,
+                cached: false,
             });
         }
 
-        // Call the tool (this would be implemented in the infrastructure layer)
-        // For now, we'll create a placeholder result
-        let tool_result = crate::domain::services::mcp_tool_service::ToolCallResult::success(
-            serde_json::json!({
-                "message": "Tool executed successfully",
-                "tool_id": tool_id.to_string(),
-                "parameters": parameters,
-            }),
-            Utc::now()
-                .signed_duration_since(started_at)
-                .num_milliseconds() as u64,
-        );
+        // Forward the call to the tool's endpoint as a JSON-RPC request
+        let tool_result = match self
+            .proxy_service
+            .forward_tool_call(&tool, parameters, context)
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                let completed_at = Utc::now();
+                let execution_time_ms = completed_at
+                    .signed_duration_since(started_at)
+                    .num_milliseconds();
+                return Ok(NodeExecutionResult {
+                    node_id: node.id.clone(),
+                    status: NodeExecutionStatus::Failed,
+                    output: None,
+                    error: Some(format!("Tool call failed: {}", e)),
+                    started_at,
+                    completed_at,
+                    execution_time_ms,
+                    cancelled: false,
+                    token_usage: None,
+                    retry_count: 0,
+                    cached: false,
+                });
+            }
+        };
 
         // Store result in state variables
         let output_var = node
@@ -1496,6 +2390,7 @@ impl NodeExecutor for MCPToolNodeExecutor {
 
         if let Some(result_data) = &tool_result.result {
             state.set_variable(output_var.to_string(), result_data.clone());
+            state.set_variable(format!("#{}.result#", node.id), result_data.clone());
         }
 
         let output = serde_json::json!({
@@ -1524,6 +2419,10 @@ impl NodeExecutor for MCPToolNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -1532,51 +2431,182 @@ impl NodeExecutor for MCPToolNodeExecutor {
     }
 }
 
-/// Parameter Extractor node executor - uses LLM to extract structured parameters
-pub struct ParameterExtractorNodeExecutor {
-    llm_service: Arc<dyn crate::domain::services::llm_service::LLMDomainService>,
-    llm_config_repository:
-        Arc<dyn crate::domain::repositories::llm_config_repository::LLMConfigRepository>,
+/// Tool call executor - runs the MCP tools an LLM node's model asks for
+/// while answering, with the permission and parameter checks of MCP tool nodes
+pub struct ToolCallNodeExecutor {
+    mcp_tool: MCPToolNodeExecutor,
 }
 
-impl ParameterExtractorNodeExecutor {
+impl ToolCallNodeExecutor {
     pub fn new(
-        llm_service: Arc<dyn crate::domain::services::llm_service::LLMDomainService>,
-        llm_config_repository: Arc<
-            dyn crate::domain::repositories::llm_config_repository::LLMConfigRepository,
+        mcp_service: Arc<dyn crate::domain::services::mcp_tool_service::MCPToolDomainService>,
+        tool_repository: Arc<
+            dyn crate::domain::repositories::mcp_tool_repository::MCPToolRepository,
         >,
+        proxy_service: Arc<dyn crate::infrastructure::mcp::MCPProxyService>,
     ) -> Self {
         Self {
-            llm_service,
-            llm_config_repository,
+            mcp_tool: MCPToolNodeExecutor::new(mcp_service, tool_repository, proxy_service),
         }
     }
 
-    async fn extract_model_config(
+    /// Load tools by id. Tools of other tenants are reported as not found.
+    pub async fn load_tools(
         &self,
-        node: &FlowNode,
-        _state: &ExecutionState,
-    ) -> Result<crate::domain::value_objects::ModelConfig> {
-        let config_data = node.data.get("model").ok_or_else(|| {
-            crate::error::PlatformError::ValidationError(
-                "Parameter extractor node missing 'model' field".to_string(),
-            )
-        })?;
+        tool_ids: &[crate::domain::value_objects::ids::MCPToolId],
+        state: &ExecutionState,
+    ) -> Result<Vec<crate::domain::entities::MCPTool>> {
+        let context = self.mcp_tool.extract_context(state)?;
+
+        let mut tools = Vec::with_capacity(tool_ids.len());
+        for tool_id in tool_ids {
+            let tool = self
+                .mcp_tool
+                .tool_repository
+                .find_by_id(*tool_id)
+                .await?
+                .filter(|tool| tool.can_access(&context.tenant_id))
+                .ok_or_else(|| {
+                    crate::error::PlatformError::ValidationError(format!(
+                        "Tool not found: {}",
+                        tool_id
+                    ))
+                })?;
+            tools.push(tool);
+        }
 
-        let llm_config_id = config_data
-            .get("llm_config_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| {
-                crate::error::PlatformError::ValidationError(
-                    "Parameter extractor node missing 'model.llm_config_id' field".to_string(),
-                )
-            })?;
+        Ok(tools)
+    }
 
-        let config = self
-            .llm_config_repository
-            .find_by_id(ConfigId::from_string(llm_config_id).map_err(|e| {
-                crate::error::PlatformError::ValidationError(format!(
-                    "Invalid UUID: {}. Error: {}",
+    /// Function definition the model sees for a tool
+    pub fn tool_definition(
+        tool: &crate::domain::entities::MCPTool,
+    ) -> crate::domain::services::llm_service::ToolDefinition {
+        let descriptor = crate::infrastructure::mcp::mcp_protocol::tool_to_mcp_format(tool);
+        crate::domain::services::llm_service::ToolDefinition {
+            name: descriptor.name,
+            description: descriptor.description,
+            parameters: descriptor.input_schema,
+        }
+    }
+
+    /// Run a call the model asked for. Failures are recorded on the returned
+    /// call instead of failing the node, so the model can react to them.
+    pub async fn invoke(
+        &self,
+        tools: &[crate::domain::entities::MCPTool],
+        mut call: crate::domain::value_objects::chat_message::ToolCall,
+        state: &ExecutionState,
+    ) -> crate::domain::value_objects::chat_message::ToolCall {
+        let Some(tool) = tools.iter().find(|tool| tool.name == call.tool_name) else {
+            call.error = Some(format!("Unknown tool: {}", call.tool_name));
+            return call;
+        };
+        call.tool_id = tool.id.to_string();
+
+        match self.call_tool(tool, call.parameters.clone(), state).await {
+            Ok(result) => {
+                call.result = result.result;
+                call.error = result.error;
+                call.execution_time_ms = Some(result.execution_time_ms);
+            }
+            Err(e) => call.error = Some(e.to_string()),
+        }
+
+        call
+    }
+
+    async fn call_tool(
+        &self,
+        tool: &crate::domain::entities::MCPTool,
+        parameters: Value,
+        state: &ExecutionState,
+    ) -> Result<crate::domain::services::mcp_tool_service::ToolCallResult> {
+        let context = self.mcp_tool.extract_context(state)?;
+
+        let permission = self
+            .mcp_tool
+            .mcp_service
+            .check_tool_permission(tool, &context)
+            .await?;
+        if !permission.allowed {
+            return Err(crate::error::PlatformError::AuthorizationFailed(
+                permission
+                    .reason
+                    .unwrap_or_else(|| "Permission denied".to_string()),
+            ));
+        }
+
+        self.mcp_tool
+            .mcp_service
+            .validate_call_parameters(tool, &parameters, &context)
+            .await?;
+
+        self.mcp_tool
+            .proxy_service
+            .forward_tool_call(tool, parameters, context)
+            .await
+    }
+
+    /// Content of the tool message that sends a call's outcome to the model
+    pub fn result_content(call: &crate::domain::value_objects::chat_message::ToolCall) -> String {
+        if let Some(error) = &call.error {
+            return json!({ "error": error }).to_string();
+        }
+
+        match &call.result {
+            Some(Value::String(s)) if !s.trim().is_empty() => s.clone(),
+            Some(result) => result.to_string(),
+            None => Value::Null.to_string(),
+        }
+    }
+}
+
+/// Parameter Extractor node executor - uses LLM to extract structured parameters
+pub struct ParameterExtractorNodeExecutor {
+    llm_service: Arc<dyn crate::domain::services::llm_service::LLMDomainService>,
+    llm_config_repository:
+        Arc<dyn crate::domain::repositories::llm_config_repository::LLMConfigRepository>,
+}
+
+impl ParameterExtractorNodeExecutor {
+    pub fn new(
+        llm_service: Arc<dyn crate::domain::services::llm_service::LLMDomainService>,
+        llm_config_repository: Arc<
+            dyn crate::domain::repositories::llm_config_repository::LLMConfigRepository,
+        >,
+    ) -> Self {
+        Self {
+            llm_service,
+            llm_config_repository,
+        }
+    }
+
+    async fn extract_model_config(
+        &self,
+        node: &FlowNode,
+        _state: &ExecutionState,
+    ) -> Result<crate::domain::value_objects::ModelConfig> {
+        let config_data = node.data.get("model").ok_or_else(|| {
+            crate::error::PlatformError::ValidationError(
+                "Parameter extractor node missing 'model' field".to_string(),
+            )
+        })?;
+
+        let llm_config_id = config_data
+            .get("llm_config_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                crate::error::PlatformError::ValidationError(
+                    "Parameter extractor node missing 'model.llm_config_id' field".to_string(),
+                )
+            })?;
+
+        let config = self
+            .llm_config_repository
+            .find_by_id(ConfigId::from_string(llm_config_id).map_err(|e| {
+                crate::error::PlatformError::ValidationError(format!(
+                    "Invalid UUID: {}. Error: {}",
                     llm_config_id, e
                 ))
             })?)
@@ -1621,6 +2651,107 @@ impl ParameterExtractorNodeExecutor {
             })
             .unwrap_or_default()
     }
+
+    /// The optional `output_schema`, compiled for validation
+    fn extract_output_schema(node: &FlowNode) -> Result<Option<(Value, jsonschema::Validator)>> {
+        let Some(schema) = node.data.get("output_schema").filter(|v| !v.is_null()) else {
+            return Ok(None);
+        };
+
+        if !schema.is_object() {
+            return Err(crate::error::PlatformError::ValidationError(
+                "Parameter extractor 'output_schema' must be a JSON Schema object".to_string(),
+            ));
+        }
+
+        let validator = jsonschema::validator_for(schema).map_err(|e| {
+            crate::error::PlatformError::ValidationError(format!(
+                "Invalid parameter extractor 'output_schema': {}",
+                e
+            ))
+        })?;
+
+        Ok(Some((schema.clone(), validator)))
+    }
+
+    /// Parse the JSON value in an LLM response, ignoring any text around it
+    fn parse_json_response(content: &str) -> Option<Value> {
+        if let Ok(value) = serde_json::from_str(content.trim()) {
+            return Some(value);
+        }
+
+        let start = content.find(['{', '['])?;
+        let end = content.rfind(['}', ']'])?;
+        if end < start {
+            return None;
+        }
+        serde_json::from_str(&content[start..=end]).ok()
+    }
+
+    /// The JSON value of `content` if it conforms to the schema, otherwise
+    /// what is wrong with it
+    pub fn validate_schema_output(
+        validator: &jsonschema::Validator,
+        content: &str,
+    ) -> std::result::Result<Value, Vec<String>> {
+        let value = Self::parse_json_response(content)
+            .ok_or_else(|| vec!["response is not valid JSON".to_string()])?;
+
+        let violations: Vec<String> = validator
+            .iter_errors(&value)
+            .map(|error| {
+                let path = error.instance_path.to_string();
+                if path.is_empty() {
+                    error.to_string()
+                } else {
+                    format!("{} (at {})", error, path)
+                }
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(value)
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Parameters of a response without `output_schema`: a JSON array of
+    /// strings, or the whole response as the only parameter
+    fn parse_string_array(content: &str) -> Vec<String> {
+        match serde_json::from_str(content) {
+            Ok(params) => params,
+            Err(_) => {
+                // If parsing fails, try to extract JSON array from the response
+                if let (Some(start), Some(end)) = (content.find('['), content.rfind(']')) {
+                    serde_json::from_str(&content[start..=end.max(start)])
+                        .unwrap_or_else(|_| vec![content.to_string()])
+                } else {
+                    vec![content.to_string()]
+                }
+            }
+        }
+    }
+
+    fn failed(node: &FlowNode, started_at: chrono::DateTime<Utc>, error: String) -> NodeExecutionResult {
+        let completed_at = Utc::now();
+        let execution_time_ms = completed_at
+            .signed_duration_since(started_at)
+            .num_milliseconds();
+        NodeExecutionResult {
+            node_id: node.id.clone(),
+            status: NodeExecutionStatus::Failed,
+            output: None,
+            error: Some(error),
+            started_at,
+            completed_at,
+            execution_time_ms,
+            cancelled: false,
+            token_usage: None,
+            retry_count: 0,
+            cached: false,
+        }
+    }
 }
 
 #[async_trait]
@@ -1635,21 +2766,12 @@ impl NodeExecutor for ParameterExtractorNodeExecutor {
         // Extract model configuration
         let model_config = match self.extract_model_config(node, state).await {
             Ok(config) => config,
-            Err(e) => {
-                let completed_at = Utc::now();
-                let execution_time_ms = completed_at
-                    .signed_duration_since(started_at)
-                    .num_milliseconds();
-                return Ok(NodeExecutionResult {
-                    node_id: node.id.clone(),
-                    status: NodeExecutionStatus::Failed,
-                    output: None,
-                    error: Some(e.to_string()),
-                    started_at,
-                    completed_at,
-                    execution_time_ms,
-                });
-            }
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
+        };
+
+        let output_schema = match Self::extract_output_schema(node) {
+            Ok(schema) => schema,
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
         };
 
         // Extract system instruction
@@ -1657,35 +2779,23 @@ impl NodeExecutor for ParameterExtractorNodeExecutor {
             .data
             .get("instruction")
             .and_then(|v| v.as_str())
-            .unwrap_or("Extract parameters from the following text and return them as a JSON array of strings.");
-
-        // Extract query paths and build user prompt
-        let query_paths = node
-            .data
-            .get("query")
-            .and_then(|v| v.as_array())
-            .ok_or_else(|| {
-                crate::error::PlatformError::ValidationError(
-                    "Parameter extractor node missing 'query' field".to_string(),
-                )
+            .unwrap_or(match output_schema {
+                Some(_) => "Extract parameters from the following text",
+                None => "Extract parameters from the following text and return them as a JSON array of strings.",
             });
 
-        let query_paths = match query_paths {
-            Ok(paths) => paths,
-            Err(e) => {
-                let completed_at = Utc::now();
-                let execution_time_ms = completed_at
-                    .signed_duration_since(started_at)
-                    .num_milliseconds();
-                return Ok(NodeExecutionResult {
-                    node_id: node.id.clone(),
-                    status: NodeExecutionStatus::Failed,
-                    output: None,
-                    error: Some(e.to_string()),
+        // Extract query paths and build user prompt
+        let query_paths = match node.data.get("query").and_then(|v| v.as_array()) {
+            Some(paths) => paths,
+            None => {
+                return Ok(Self::failed(
+                    node,
                     started_at,
-                    completed_at,
-                    execution_time_ms,
-                });
+                    crate::error::PlatformError::ValidationError(
+                        "Parameter extractor node missing 'query' field".to_string(),
+                    )
+                    .to_string(),
+                ))
             }
         };
 
@@ -1697,74 +2807,84 @@ impl NodeExecutor for ParameterExtractorNodeExecutor {
         let content = self.resolve_query_content(&path, state);
 
         // Build messages for LLM
-        let messages = vec![
-            crate::domain::value_objects::ChatMessage::new_system_message(format!(
+        let system_prompt = match &output_schema {
+            Some((schema, _)) => format!(
+                "{}. You must respond with a single JSON value that conforms to the following JSON Schema, no other text:\n{}",
+                instruction,
+                serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string())
+            ),
+            None => format!(
                 "{}. You must respond with a valid JSON array of strings only, no other text.",
                 instruction
-            )),
+            ),
+        };
+        let mut messages = vec![
+            crate::domain::value_objects::ChatMessage::new_system_message(system_prompt),
             crate::domain::value_objects::ChatMessage::new_user_message(content),
         ];
 
         let tenant_id = match self.extract_tenant_id(state) {
             Ok(id) => id,
-            Err(e) => {
-                let completed_at = Utc::now();
-                let execution_time_ms = completed_at
-                    .signed_duration_since(started_at)
-                    .num_milliseconds();
-                return Ok(NodeExecutionResult {
-                    node_id: node.id.clone(),
-                    status: NodeExecutionStatus::Failed,
-                    output: None,
-                    error: Some(e.to_string()),
-                    started_at,
-                    completed_at,
-                    execution_time_ms,
-                });
-            }
+            Err(e) => return Ok(Self::failed(node, started_at, e.to_string())),
         };
 
         // Call LLM service (ParameterExtractor doesn't use structured output)
-        let response = match self
+        let mut response = match self
             .llm_service
-            .chat_completion(&model_config, messages, tenant_id, None)
+            .chat_completion(&model_config, messages.clone(), tenant_id, None)
             .await
         {
             Ok(resp) => resp,
-            Err(e) => {
-                let completed_at = Utc::now();
-                let execution_time_ms = completed_at
-                    .signed_duration_since(started_at)
-                    .num_milliseconds();
-                return Ok(NodeExecutionResult {
-                    node_id: node.id.clone(),
-                    status: NodeExecutionStatus::Failed,
-                    output: None,
-                    error: Some(format!("LLM call failed: {}", e)),
-                    started_at,
-                    completed_at,
-                    execution_time_ms,
-                });
-            }
+            Err(e) => return Ok(Self::failed(node, started_at, format!("LLM call failed: {}", e))),
         };
 
-        // Parse response as JSON array
-        let extracted_params: Vec<String> = match serde_json::from_str(&response.content) {
-            Ok(params) => params,
-            Err(_) => {
-                // If parsing fails, try to extract JSON array from the response
-                if let Some(start) = response.content.find('[') {
-                    if let Some(end) = response.content.rfind(']') {
-                        let json_str = &response.content[start..=end];
-                        serde_json::from_str(json_str)
-                            .unwrap_or_else(|_| vec![response.content.clone()])
-                    } else {
-                        vec![response.content.clone()]
+        let extracted_params = match &output_schema {
+            Some((_, validator)) => match Self::validate_schema_output(validator, &response.content) {
+                Ok(value) => value,
+                Err(violations) => {
+                    // Retry once, telling the model what was wrong
+                    messages.push(crate::domain::value_objects::ChatMessage::new_assistant_message(
+                        response.content.clone(),
+                    ));
+                    messages.push(crate::domain::value_objects::ChatMessage::new_user_message(
+                        format!(
+                            "Your response does not conform to the JSON Schema:\n- {}\nRespond again with only the corrected JSON.",
+                            violations.join("\n- ")
+                        ),
+                    ));
+
+                    let first_usage = response.usage.clone();
+                    response = match self
+                        .llm_service
+                        .chat_completion(&model_config, messages, tenant_id, None)
+                        .await
+                    {
+                        Ok(resp) => resp,
+                        Err(e) => {
+                            return Ok(Self::failed(node, started_at, format!("LLM call failed: {}", e)))
+                        }
+                    };
+                    response.usage = crate::domain::services::llm_service::TokenUsage::new(
+                        first_usage.prompt_tokens + response.usage.prompt_tokens,
+                        first_usage.completion_tokens + response.usage.completion_tokens,
+                    );
+
+                    match Self::validate_schema_output(validator, &response.content) {
+                        Ok(value) => value,
+                        Err(violations) => {
+                            return Ok(Self::failed(
+                                node,
+                                started_at,
+                                format!(
+                                    "LLM response does not conform to output_schema: {}",
+                                    violations.join("; ")
+                                ),
+                            ))
+                        }
                     }
-                } else {
-                    vec![response.content.clone()]
                 }
-            }
+            },
+            None => json!(Self::parse_string_array(&response.content)),
         };
 
         // Extract output parameter name
@@ -1780,7 +2900,7 @@ impl NodeExecutor for ParameterExtractorNodeExecutor {
 
         // Store result in state with node ID prefix
         let output_var = format!("#{}.{}#", node.id, parameters);
-        state.set_variable(output_var, serde_json::json!(extracted_params));
+        state.set_variable(output_var, extracted_params.clone());
 
         let output = serde_json::json!({
             "extracted_parameters": extracted_params,
@@ -1801,6 +2921,10 @@ impl NodeExecutor for ParameterExtractorNodeExecutor {
             started_at,
             completed_at,
             execution_time_ms,
+            cancelled: false,
+            token_usage: Some(response.usage),
+            retry_count: 0,
+            cached: false,
         })
     }
 
@@ -2200,4 +3324,436 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_tool_call_result_content() {
+        let mut call = crate::domain::value_objects::chat_message::ToolCall {
+            call_id: Some("call_1".to_string()),
+            tool_id: String::new(),
+            tool_name: "get_weather".to_string(),
+            parameters: serde_json::json!({"city": "Paris"}),
+            result: Some(serde_json::json!({"temperature": 21})),
+            error: None,
+            execution_time_ms: None,
+        };
+        assert_eq!(ToolCallNodeExecutor::result_content(&call), r#"{"temperature":21}"#);
+
+        call.result = Some(serde_json::json!("Sunny"));
+        assert_eq!(ToolCallNodeExecutor::result_content(&call), "Sunny");
+
+        call.error = Some("Unknown tool: get_weather".to_string());
+        assert_eq!(
+            ToolCallNodeExecutor::result_content(&call),
+            r#"{"error":"Unknown tool: get_weather"}"#
+        );
+    }
+
+    #[test]
+    fn test_http_request_build_resolves_templates() {
+        let executor = HttpRequestNodeExecutor::new();
+        let node = FlowNode {
+            id: "http_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::HttpRequest,
+            data: serde_json::json!({
+                "url": "https://example.com/items/{{test_var}}",
+                "method": "post",
+                "headers": {"X-Trace": "{{test_var}}"},
+                "body": {"query": "{{test_var}}"},
+                "timeout_ms": 500,
+                "auth": {"type": "bearer", "token": "secret"}
+            }),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let state = create_test_state();
+
+        let request = executor.build_request(&node, &state).unwrap();
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(request.url().as_str(), "https://example.com/items/test_value");
+        assert_eq!(request.headers()["X-Trace"], "test_value");
+        assert_eq!(request.headers()["Authorization"], "Bearer secret");
+        assert_eq!(request.headers()["Content-Type"], "application/json");
+        assert_eq!(
+            request.body().and_then(|b| b.as_bytes()),
+            Some(br#"{"query":"test_value"}"#.as_slice())
+        );
+    }
+
+    #[test]
+    fn test_http_request_body_values_cannot_inject_fields() {
+        let executor = HttpRequestNodeExecutor::new();
+        let node = FlowNode {
+            id: "http_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::HttpRequest,
+            data: serde_json::json!({
+                "url": "https://example.com/items",
+                "method": "POST",
+                "body": {"query": "{{user_input}}", "tags": ["{{user_input}}"], "limit": 5},
+                "timeout_ms": 86_400_000u64
+            }),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let mut state = create_test_state();
+        let input = r#"x", "admin": true, "y": "}"#;
+        state.set_variable("user_input".to_string(), serde_json::json!(input));
+
+        let request = executor.build_request(&node, &state).unwrap();
+        let body: Value = serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({"query": input, "tags": [input], "limit": 5}));
+        assert_eq!(
+            request.timeout(),
+            Some(&std::time::Duration::from_millis(HttpRequestNodeExecutor::MAX_TIMEOUT_MS))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_request_invalid_url_fails() {
+        let executor = HttpRequestNodeExecutor::new();
+        let node = FlowNode {
+            id: "http_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::HttpRequest,
+            data: serde_json::json!({"url": "not a url"}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.is_some());
+        assert!(state.get_variable("#http_1.status_code#").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_request_to_internal_address_is_refused() {
+        let executor = HttpRequestNodeExecutor::new();
+        let node = FlowNode {
+            id: "http_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::HttpRequest,
+            data: serde_json::json!({"url": "http://169.254.169.254/latest/meta-data/"}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.unwrap().contains("non-public address"));
+        assert!(state.get_variable("#http_1.status_code#").is_none());
+    }
+
+    #[cfg(feature = "js-sandbox")]
+    #[tokio::test]
+    async fn test_code_node_returns_output() {
+        let executor = CodeNodeExecutor::new();
+        let node = FlowNode {
+            id: "code_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::Code,
+            data: serde_json::json!({
+                "code": "return { greeting: inputs.value + '!', length: inputs.value.length };",
+                "inputs": {"value": "test_var"}
+            }),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Success);
+        assert_eq!(
+            state.get_variable("#code_1.output#"),
+            Some(&serde_json::json!({"greeting": "test_value!", "length": 10}))
+        );
+    }
+
+    #[cfg(feature = "js-sandbox")]
+    #[tokio::test]
+    async fn test_code_node_runtime_error_fails() {
+        let executor = CodeNodeExecutor::new();
+        let node = FlowNode {
+            id: "code_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::Code,
+            data: serde_json::json!({"code": "throw new Error('boom');"}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.unwrap().contains("boom"));
+    }
+
+    #[cfg(feature = "js-sandbox")]
+    #[tokio::test]
+    async fn test_code_node_timeout() {
+        let executor = CodeNodeExecutor::new();
+        let node = FlowNode {
+            id: "code_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::Code,
+            data: serde_json::json!({"code": "while (true) {}", "timeout_ms": 100}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.unwrap().contains("timed out"));
+    }
+
+    #[cfg(not(feature = "js-sandbox"))]
+    #[tokio::test]
+    async fn test_code_node_fails_without_js_sandbox() {
+        let executor = CodeNodeExecutor::new();
+        let node = FlowNode {
+            id: "code_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::Code,
+            data: serde_json::json!({"code": "return 1;"}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.unwrap().contains("js-sandbox"));
+    }
+
+    fn condition_node(conditions: Value) -> FlowNode {
+        FlowNode {
+            id: "condition_1".to_string(),
+            parent_id: None,
+            node_type: NodeType::Condition,
+            data: serde_json::json!({
+                "conditions": conditions,
+                "default_next_node_id": "fallback",
+            }),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        }
+    }
+
+    async fn branch_for(variable: Value, operator: &str, value: Value) -> Option<String> {
+        let executor = ConditionNodeExecutor::new();
+        let node = condition_node(serde_json::json!([
+            {"variable": "input", "operator": operator, "value": value, "next_node_id": "matched"}
+        ]));
+        let mut state = create_test_state();
+        state.set_variable("input".to_string(), variable);
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Success);
+        state.next_node_id
+    }
+
+    fn matched() -> Option<String> {
+        Some("matched".to_string())
+    }
+
+    fn fallback() -> Option<String> {
+        Some("fallback".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_condition_eq_and_ne() {
+        assert_eq!(branch_for(serde_json::json!("yes"), "eq", serde_json::json!("yes")).await, matched());
+        assert_eq!(branch_for(serde_json::json!("yes"), "eq", serde_json::json!("no")).await, fallback());
+        assert_eq!(branch_for(serde_json::json!("yes"), "ne", serde_json::json!("no")).await, matched());
+        assert_eq!(branch_for(serde_json::json!("yes"), "ne", serde_json::json!("yes")).await, fallback());
+    }
+
+    #[tokio::test]
+    async fn test_condition_eq_coerces_strings_and_numbers() {
+        assert_eq!(branch_for(serde_json::json!("42"), "eq", serde_json::json!(42)).await, matched());
+        assert_eq!(branch_for(serde_json::json!(42), "eq", serde_json::json!("42.0")).await, matched());
+        assert_eq!(branch_for(serde_json::json!(true), "eq", serde_json::json!("true")).await, matched());
+        assert_eq!(branch_for(serde_json::json!("42"), "ne", serde_json::json!(42)).await, fallback());
+        assert_eq!(branch_for(serde_json::json!("abc"), "eq", serde_json::json!(0)).await, fallback());
+    }
+
+    #[tokio::test]
+    async fn test_condition_numeric_comparisons() {
+        assert_eq!(branch_for(serde_json::json!(10), "gt", serde_json::json!(5)).await, matched());
+        assert_eq!(branch_for(serde_json::json!(5), "gt", serde_json::json!(5)).await, fallback());
+        assert_eq!(branch_for(serde_json::json!(3), "lt", serde_json::json!(5)).await, matched());
+        assert_eq!(branch_for(serde_json::json!(5), "lt", serde_json::json!(5)).await, fallback());
+        assert_eq!(branch_for(serde_json::json!(5), "gte", serde_json::json!(5)).await, matched());
+        assert_eq!(branch_for(serde_json::json!(4.9), "gte", serde_json::json!(5)).await, fallback());
+        assert_eq!(branch_for(serde_json::json!(5), "lte", serde_json::json!(5)).await, matched());
+        assert_eq!(branch_for(serde_json::json!(5.1), "lte", serde_json::json!(5)).await, fallback());
+    }
+
+    #[tokio::test]
+    async fn test_condition_numeric_comparisons_coerce_strings() {
+        // "10" > "9" numerically, although not lexically
+        assert_eq!(branch_for(serde_json::json!("10"), "gt", serde_json::json!("9")).await, matched());
+        assert_eq!(branch_for(serde_json::json!(" 2.5 "), "lt", serde_json::json!(3)).await, matched());
+        assert_eq!(branch_for(serde_json::json!(7), "gte", serde_json::json!("7")).await, matched());
+        assert_eq!(branch_for(serde_json::json!("n/a"), "gt", serde_json::json!(1)).await, fallback());
+        assert_eq!(branch_for(serde_json::json!(null), "lte", serde_json::json!(1)).await, fallback());
+    }
+
+    #[tokio::test]
+    async fn test_condition_contains() {
+        assert_eq!(branch_for(serde_json::json!("hello world"), "contains", serde_json::json!("lo w")).await, matched());
+        assert_eq!(branch_for(serde_json::json!("hello"), "contains", serde_json::json!("bye")).await, fallback());
+        assert_eq!(branch_for(serde_json::json!("order-42"), "contains", serde_json::json!(42)).await, matched());
+        assert_eq!(branch_for(serde_json::json!([1, 2, 3]), "contains", serde_json::json!("2")).await, matched());
+        assert_eq!(branch_for(serde_json::json!(["a", "b"]), "contains", serde_json::json!("c")).await, fallback());
+        assert_eq!(branch_for(serde_json::json!({"key": 1}), "contains", serde_json::json!("key")).await, matched());
+    }
+
+    #[tokio::test]
+    async fn test_condition_starts_with() {
+        assert_eq!(branch_for(serde_json::json!("https://example.com"), "starts_with", serde_json::json!("https://")).await, matched());
+        assert_eq!(branch_for(serde_json::json!("ftp://example.com"), "starts_with", serde_json::json!("https://")).await, fallback());
+        assert_eq!(branch_for(serde_json::json!(12345), "starts_with", serde_json::json!("123")).await, matched());
+        assert_eq!(branch_for(serde_json::json!(["abc"]), "starts_with", serde_json::json!("a")).await, fallback());
+    }
+
+    #[tokio::test]
+    async fn test_condition_is_empty() {
+        assert_eq!(branch_for(serde_json::json!(""), "is_empty", Value::Null).await, matched());
+        assert_eq!(branch_for(serde_json::json!("   "), "is_empty", Value::Null).await, matched());
+        assert_eq!(branch_for(serde_json::json!([]), "is_empty", Value::Null).await, matched());
+        assert_eq!(branch_for(serde_json::json!({}), "is_empty", Value::Null).await, matched());
+        assert_eq!(branch_for(Value::Null, "is_empty", Value::Null).await, matched());
+        assert_eq!(branch_for(serde_json::json!("x"), "is_empty", Value::Null).await, fallback());
+        assert_eq!(branch_for(serde_json::json!(0), "is_empty", Value::Null).await, fallback());
+    }
+
+    #[tokio::test]
+    async fn test_condition_first_match_wins_and_unset_variables_are_empty() {
+        let executor = ConditionNodeExecutor::new();
+        let node = condition_node(serde_json::json!([
+            {"variable": "missing", "operator": "eq", "value": "x", "next_node_id": "first"},
+            {"variable": "missing", "operator": "is_empty", "next_node_id": "second"},
+            {"variable": "test_var", "operator": "eq", "value": "test_value", "next_node_id": "third"}
+        ]));
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(state.next_node_id, Some("second".to_string()));
+        assert_eq!(result.output.unwrap()["matched_condition"], serde_json::json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_condition_unknown_operator_fails() {
+        let executor = ConditionNodeExecutor::new();
+        let node = condition_node(serde_json::json!([
+            {"variable": "test_var", "operator": "matches", "value": ".*", "next_node_id": "matched"}
+        ]));
+        let mut state = create_test_state();
+
+        let result = executor.execute(&node, &mut state).await.unwrap();
+        assert_eq!(result.status, NodeExecutionStatus::Failed);
+        assert!(result.error.unwrap().contains("Unknown operator"));
+        assert_eq!(state.next_node_id, None);
+    }
+
+    fn schema_validator(schema: Value) -> jsonschema::Validator {
+        jsonschema::validator_for(&schema).unwrap()
+    }
+
+    #[test]
+    fn test_parameter_extractor_schema_required_fields() {
+        let validator = schema_validator(serde_json::json!({
+            "type": "object",
+            "properties": {"city": {"type": "string"}, "days": {"type": "integer"}},
+            "required": ["city", "days"]
+        }));
+
+        let value = ParameterExtractorNodeExecutor::validate_schema_output(
+            &validator,
+            "Here you go:\n```json\n{\"city\": \"Paris\", \"days\": 3}\n```",
+        )
+        .unwrap();
+        assert_eq!(value, serde_json::json!({"city": "Paris", "days": 3}));
+
+        let violations =
+            ParameterExtractorNodeExecutor::validate_schema_output(&validator, r#"{"city": "Paris"}"#)
+                .unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("days"));
+    }
+
+    #[test]
+    fn test_parameter_extractor_schema_enum_constraint() {
+        let validator = schema_validator(serde_json::json!({
+            "type": "object",
+            "properties": {"priority": {"enum": ["low", "medium", "high"]}},
+            "required": ["priority"]
+        }));
+
+        assert!(
+            ParameterExtractorNodeExecutor::validate_schema_output(&validator, r#"{"priority": "high"}"#)
+                .is_ok()
+        );
+        let violations = ParameterExtractorNodeExecutor::validate_schema_output(
+            &validator,
+            r#"{"priority": "urgent"}"#,
+        )
+        .unwrap_err();
+        assert!(violations[0].contains("/priority"));
+    }
+
+    #[test]
+    fn test_parameter_extractor_schema_nested_objects() {
+        let validator = schema_validator(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "customer": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "address": {
+                            "type": "object",
+                            "properties": {"zip": {"type": "string", "pattern": "^[0-9]{5}$"}},
+                            "required": ["zip"]
+                        }
+                    },
+                    "required": ["name", "address"]
+                }
+            },
+            "required": ["customer"]
+        }));
+
+        assert!(ParameterExtractorNodeExecutor::validate_schema_output(
+            &validator,
+            r#"{"customer": {"name": "Ann", "address": {"zip": "94107"}}}"#,
+        )
+        .is_ok());
+
+        let violations = ParameterExtractorNodeExecutor::validate_schema_output(
+            &validator,
+            r#"{"customer": {"name": "Ann", "address": {"zip": "9410"}}}"#,
+        )
+        .unwrap_err();
+        assert!(violations[0].contains("/customer/address/zip"));
+
+        let violations =
+            ParameterExtractorNodeExecutor::validate_schema_output(&validator, "no JSON here").unwrap_err();
+        assert_eq!(violations, vec!["response is not valid JSON".to_string()]);
+    }
+
+    #[test]
+    fn test_parameter_extractor_rejects_invalid_schema() {
+        let node = |schema: Value| FlowNode {
+            id: "extract".to_string(),
+            parent_id: None,
+            node_type: NodeType::ParameterExtractor,
+            data: serde_json::json!({"output_schema": schema}),
+            position: NodePosition { x: 0.0, y: 0.0 },
+        };
+
+        assert!(ParameterExtractorNodeExecutor::extract_output_schema(&node(Value::Null))
+            .unwrap()
+            .is_none());
+        assert!(ParameterExtractorNodeExecutor::extract_output_schema(&node(serde_json::json!("object"))).is_err());
+        assert!(ParameterExtractorNodeExecutor::extract_output_schema(&node(
+            serde_json::json!({"type": "no-such-type"})
+        ))
+        .is_err());
+    }
 }
@@ -0,0 +1,173 @@
+use serde_json::{Map, Value};
+
+use crate::domain::services::execution_engine::ExecutionState;
+use crate::domain::value_objects::{FlowNode, NodeType};
+use crate::error::{PlatformError, Result};
+
+/// Check the state variables a node reads against its optional
+/// `data.input_schema`. The schema describes an object whose property names
+/// are variable names, e.g. `{"properties": {"#start.query#": {"type":
+/// "string"}}, "required": ["#start.query#"]}`; unset variables are absent
+/// from the object.
+pub fn validate_node_input(node: &FlowNode, state: &ExecutionState) -> Result<()> {
+    let Some(schema) = declared_schema(node, "input_schema")? else {
+        return Ok(());
+    };
+
+    let names = schema
+        .get("properties")
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flat_map(|properties| properties.keys().map(String::as_str))
+        .chain(
+            schema
+                .get("required")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str()),
+        );
+    let mut input = Map::new();
+    for name in names {
+        if let Some(value) = state.get_variable(name) {
+            input.insert(name.to_string(), value.clone());
+        }
+    }
+
+    validate(node, "input_schema", schema, &Value::Object(input))
+}
+
+/// Check a node's output against its optional `data.output_schema`. A
+/// missing output is validated as `null`. Parameter extractor nodes use
+/// `output_schema` for the extracted value and validate it themselves.
+pub fn validate_node_output(node: &FlowNode, output: Option<&Value>) -> Result<()> {
+    if node.node_type == NodeType::ParameterExtractor {
+        return Ok(());
+    }
+    let Some(schema) = declared_schema(node, "output_schema")? else {
+        return Ok(());
+    };
+
+    validate(node, "output_schema", schema, output.unwrap_or(&Value::Null))
+}
+
+fn declared_schema<'a>(node: &'a FlowNode, field: &str) -> Result<Option<&'a Value>> {
+    match node.data.get(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(schema @ (Value::Object(_) | Value::Bool(_))) => Ok(Some(schema)),
+        Some(_) => Err(PlatformError::ValidationError(format!(
+            "Node {} {} must be a JSON Schema object",
+            node.id, field
+        ))),
+    }
+}
+
+fn validate(node: &FlowNode, field: &str, schema: &Value, instance: &Value) -> Result<()> {
+    let validator = jsonschema::validator_for(schema).map_err(|e| {
+        PlatformError::ValidationError(format!("Node {} has an invalid {}: {}", node.id, field, e))
+    })?;
+
+    let violations: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            if path.is_empty() {
+                error.to_string()
+            } else {
+                format!("{} (at {})", error, path)
+            }
+        })
+        .collect();
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(PlatformError::ValidationError(format!(
+            "Node {} failed {} validation: {}",
+            node.id,
+            field,
+            violations.join("; ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{FlowExecutionId, NodePosition};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn node(node_type: NodeType, data: Value) -> FlowNode {
+        FlowNode {
+            id: "node_1".to_string(),
+            parent_id: None,
+            node_type,
+            data,
+            position: NodePosition { x: 0.0, y: 0.0 },
+        }
+    }
+
+    fn state() -> ExecutionState {
+        ExecutionState::new(
+            FlowExecutionId::new(),
+            HashMap::from([
+                ("#start.query#".to_string(), json!("hello")),
+                ("#start.limit#".to_string(), json!("ten")),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_input_schema_checks_state_variables() {
+        let valid = node(
+            NodeType::Llm,
+            json!({"input_schema": {
+                "properties": {"#start.query#": {"type": "string"}},
+                "required": ["#start.query#"]
+            }}),
+        );
+        assert!(validate_node_input(&valid, &state()).is_ok());
+
+        let wrong_type = node(
+            NodeType::Llm,
+            json!({"input_schema": {"properties": {"#start.limit#": {"type": "integer"}}}}),
+        );
+        let error = validate_node_input(&wrong_type, &state()).unwrap_err().to_string();
+        assert!(error.contains("input_schema"));
+        assert!(error.contains("#start.limit#"));
+
+        let missing = node(NodeType::Llm, json!({"input_schema": {"required": ["#start.user#"]}}));
+        assert!(validate_node_input(&missing, &state()).is_err());
+    }
+
+    #[test]
+    fn test_output_schema_checks_node_output() {
+        let node = node(
+            NodeType::Code,
+            json!({"output_schema": {
+                "type": "object",
+                "properties": {"score": {"type": "number"}},
+                "required": ["score"]
+            }}),
+        );
+
+        assert!(validate_node_output(&node, Some(&json!({"score": 0.5}))).is_ok());
+        assert!(validate_node_output(&node, Some(&json!({"score": "high"}))).is_err());
+        assert!(validate_node_output(&node, None).is_err());
+    }
+
+    #[test]
+    fn test_schemas_are_optional_and_must_be_objects() {
+        assert!(validate_node_input(&node(NodeType::Llm, json!({})), &state()).is_ok());
+        assert!(validate_node_output(&node(NodeType::Llm, json!({})), None).is_ok());
+        assert!(validate_node_output(&node(NodeType::Llm, json!({"output_schema": "object"})), None).is_err());
+
+        // The extractor's output_schema describes the extracted value
+        let extractor = node(
+            NodeType::ParameterExtractor,
+            json!({"output_schema": {"type": "string"}}),
+        );
+        assert!(validate_node_output(&extractor, Some(&json!({"extracted_parameters": {}}))).is_ok());
+    }
+}
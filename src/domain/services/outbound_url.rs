@@ -0,0 +1,123 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::error::{PlatformError, Result};
+
+/// Check that a user-supplied URL is http(s) and that its host resolves
+/// only to public addresses, so requests to it cannot reach loopback,
+/// private, link-local (e.g. cloud metadata) or other internal endpoints.
+/// Clients fetching such URLs must not follow redirects.
+pub async fn ensure_public_http_url(raw: &str) -> Result<url::Url> {
+    let url = url::Url::parse(raw)
+        .map_err(|e| PlatformError::ValidationError(format!("Invalid URL '{}': {}", raw, e)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(PlatformError::ValidationError(format!(
+            "URL '{}' must use http or https",
+            raw
+        )));
+    }
+
+    let port = url.port_or_known_default().unwrap_or(80);
+    let addrs: Vec<IpAddr> = match url.host() {
+        Some(url::Host::Ipv4(ip)) => vec![IpAddr::V4(ip)],
+        Some(url::Host::Ipv6(ip)) => vec![IpAddr::V6(ip)],
+        Some(url::Host::Domain(host)) => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| PlatformError::ValidationError(format!("Cannot resolve host '{}': {}", host, e)))?
+            .map(|addr| addr.ip())
+            .collect(),
+        None => {
+            return Err(PlatformError::ValidationError(format!("URL '{}' has no host", raw)));
+        }
+    };
+
+    if addrs.is_empty() {
+        return Err(PlatformError::ValidationError(format!("Cannot resolve host of '{}'", raw)));
+    }
+    if let Some(ip) = addrs.iter().find(|ip| !is_public_ip(ip)) {
+        return Err(PlatformError::ValidationError(format!(
+            "URL '{}' points to a non-public address ({})",
+            raw, ip
+        )));
+    }
+
+    Ok(url)
+}
+
+/// Whether an address is routable on the public internet
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(&mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: &Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // Carrier-grade NAT, 100.64.0.0/10
+        || (a == 100 && (64..128).contains(&b))
+        // "This network", 0.0.0.0/8
+        || a == 0)
+}
+
+fn is_public_ipv6(ip: &Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // Unique local, fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (first & 0xffc0) == 0xfe80)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["8.8.8.8", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(&ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.5",
+            "172.16.3.4",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(&ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_public_http_url_rejects_internal_targets() {
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]/",
+            "http://localhost/",
+            "file:///etc/passwd",
+            "/etc/passwd",
+        ] {
+            assert!(ensure_public_http_url(url).await.is_err(), "{}", url);
+        }
+        assert!(ensure_public_http_url("https://8.8.8.8/").await.is_ok());
+    }
+}
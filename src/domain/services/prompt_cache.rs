@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::domain::services::llm_service::{ChatResponse, ResponseFormat};
+use crate::domain::value_objects::{ChatMessage, ModelConfig};
+use crate::error::Result;
+
+/// LLM responses of flow nodes, keyed by the hash of everything that
+/// determines the response
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PromptCache: Send + Sync {
+    async fn get(&self, prompt_hash: &str) -> Result<Option<ChatResponse>>;
+
+    async fn set(&self, prompt_hash: &str, response: &ChatResponse, ttl: Duration) -> Result<()>;
+}
+
+/// Hex SHA-256 over the tenant, the model and its endpoint, the resolved
+/// messages without timestamps and the response format. The tenant is
+/// included so tenants never share cached responses.
+pub fn prompt_hash(
+    tenant_id: uuid::Uuid,
+    model_config: &ModelConfig,
+    messages: &[ChatMessage],
+    response_format: Option<&ResponseFormat>,
+) -> String {
+    let messages: Vec<_> = messages
+        .iter()
+        .map(|message| json!({"role": message.role, "content": message.content}))
+        .collect();
+    // Sorted so the hash does not depend on map iteration order
+    let mut parameters = json!(model_config.parameters);
+    parameters["custom_parameters"] = json!(model_config
+        .parameters
+        .custom_parameters
+        .iter()
+        .collect::<BTreeMap<_, _>>());
+    let input = json!({
+        "tenant_id": tenant_id,
+        "provider": model_config.provider,
+        "api_base": model_config.credentials.api_base,
+        "model_name": model_config.model_name,
+        "parameters": parameters,
+        "messages": messages,
+        "response_format": response_format,
+    });
+
+    let digest = Sha256::digest(input.to_string().as_bytes());
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ModelCredentials, ModelParameters, ModelProvider};
+
+    fn model_config(api_key: &str) -> ModelConfig {
+        ModelConfig {
+            provider: ModelProvider::OpenAI,
+            model_name: "gpt-4o".to_string(),
+            parameters: ModelParameters::default(),
+            credentials: ModelCredentials {
+                api_key: Some(api_key.to_string()),
+                ..ModelCredentials::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_prompt_hash_ignores_timestamps_and_credentials() {
+        let tenant_id = uuid::Uuid::new_v4();
+        let first = vec![ChatMessage::new_user_message("Hello".to_string())];
+        let mut second = first.clone();
+        second[0].timestamp = second[0].timestamp + chrono::Duration::seconds(5);
+
+        assert_eq!(
+            prompt_hash(tenant_id, &model_config("key-1"), &first, None),
+            prompt_hash(tenant_id, &model_config("key-2"), &second, None)
+        );
+    }
+
+    #[test]
+    fn test_prompt_hash_differs_by_tenant_and_prompt() {
+        let tenant_id = uuid::Uuid::new_v4();
+        let config = model_config("key");
+        let hello = vec![ChatMessage::new_user_message("Hello".to_string())];
+        let bye = vec![ChatMessage::new_user_message("Bye".to_string())];
+
+        let hash = prompt_hash(tenant_id, &config, &hello, None);
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, prompt_hash(uuid::Uuid::new_v4(), &config, &hello, None));
+        assert_ne!(hash, prompt_hash(tenant_id, &config, &bye, None));
+    }
+}
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use crate::domain::value_objects::{EnvironmentVariable, TenantId};
+use crate::error::{PlatformError, Result};
+
+/// Longest secret key accepted
+pub const MAX_SECRET_KEY_LENGTH: usize = 128;
+
+/// Longest secret value accepted
+pub const MAX_SECRET_VALUE_LENGTH: usize = 64 * 1024;
+
+/// Tenant secrets, the values of `secret` flow environment variables.
+/// Implementations must never log or return values other than from `get`.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SecretStore: Send + Sync {
+    /// Store `value` as the tenant's secret `key`, replacing any previous value
+    async fn set(&self, tenant_id: TenantId, key: &str, value: String) -> Result<()>;
+
+    /// Value of the tenant's secret `key`; `NotFound` if it is not set
+    async fn get(&self, tenant_id: TenantId, key: &str) -> Result<String>;
+
+    /// Remove a secret; `NotFound` if it is not set
+    async fn delete(&self, tenant_id: TenantId, key: &str) -> Result<()>;
+
+    /// Keys of the tenant's secrets, sorted
+    async fn list_keys(&self, tenant_id: TenantId) -> Result<Vec<String>>;
+}
+
+/// Check a secret before it is stored. Keys follow environment variable
+/// naming so flows can refer to them.
+pub fn validate_secret(key: &str, value: &str) -> Result<()> {
+    if !EnvironmentVariable::is_valid_name(key) || key.len() > MAX_SECRET_KEY_LENGTH {
+        return Err(PlatformError::ValidationError(format!("Invalid secret key: '{}'", key)));
+    }
+    if value.is_empty() {
+        return Err(PlatformError::ValidationError("Secret value must not be empty".to_string()));
+    }
+    if value.len() > MAX_SECRET_VALUE_LENGTH {
+        return Err(PlatformError::ValidationError(format!(
+            "Secret value must be at most {} bytes",
+            MAX_SECRET_VALUE_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_secret() {
+        assert!(validate_secret("API_KEY", "sk-123").is_ok());
+        assert!(validate_secret("api-key", "sk-123").is_err());
+        assert!(validate_secret(&"K".repeat(MAX_SECRET_KEY_LENGTH + 1), "sk-123").is_err());
+        assert!(validate_secret("API_KEY", "").is_err());
+        assert!(validate_secret("API_KEY", &"x".repeat(MAX_SECRET_VALUE_LENGTH + 1)).is_err());
+    }
+}
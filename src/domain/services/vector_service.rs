@@ -3,7 +3,8 @@ use std::collections::HashMap;
 
 use crate::domain::value_objects::{
     TenantId, VectorRecord, SearchQuery, SearchResult, IndexConfig, 
-    VectorStats, BatchOperation, SearchFilter
+    VectorStats, BatchOperation, SearchFilter, FilterCondition, FilterOperator,
+    ComparisonOperator, TENANT_ISOLATION_KEY_FIELD, tenant_isolation_key,
 };
 use crate::error::PlatformError;
 
@@ -54,6 +55,12 @@ pub trait VectorStoreDomainService: Send + Sync {
     
     /// Generate tenant-specific namespace
     fn generate_tenant_namespace(&self, tenant_id: TenantId, namespace: Option<&str>) -> String;
+    
+    /// Stamp the tenant isolation key onto the record's metadata
+    fn apply_record_isolation(&self, record: &mut VectorRecord);
+    
+    /// Add the mandatory tenant isolation condition to the query's filter
+    fn apply_query_isolation(&self, query: &mut SearchQuery, tenant_id: TenantId) -> Result<(), PlatformError>;
 }
 
 /// Implementation of vector storage domain service
@@ -89,6 +96,7 @@ impl VectorStoreDomainServiceImpl {
 impl VectorStoreDomainService for VectorStoreDomainServiceImpl {
     async fn store_vector(&self, mut record: VectorRecord) -> Result<(), PlatformError> {
         self.validate_vector_record(&record)?;
+        self.apply_record_isolation(&mut record);
         
         // Apply tenant isolation by modifying namespace
         let tenant_namespace = self.generate_tenant_namespace(
@@ -117,6 +125,7 @@ impl VectorStoreDomainService for VectorStoreDomainServiceImpl {
         
         // Apply tenant isolation to all records
         for record in &mut records {
+            self.apply_record_isolation(record);
             let tenant_namespace = self.generate_tenant_namespace(
                 record.tenant_id, 
                 record.namespace.as_deref()
@@ -134,6 +143,7 @@ impl VectorStoreDomainService for VectorStoreDomainServiceImpl {
     ) -> Result<Vec<SearchResult>, PlatformError> {
         self.validate_search_query(&query)?;
         self.validate_tenant_namespace_access(tenant_id, query.namespace.as_deref())?;
+        self.apply_query_isolation(&mut query, tenant_id)?;
         
         // Apply tenant isolation
         let tenant_namespace = self.generate_tenant_namespace(tenant_id, query.namespace.as_deref());
@@ -202,6 +212,7 @@ impl VectorStoreDomainService for VectorStoreDomainServiceImpl {
         // Validate and apply tenant isolation to upsert operations
         for record in &mut operation.upsert {
             self.validate_vector_record(record)?;
+            self.apply_record_isolation(record);
             let tenant_namespace = self.generate_tenant_namespace(
                 record.tenant_id, 
                 record.namespace.as_deref()
@@ -239,6 +250,7 @@ impl VectorStoreDomainService for VectorStoreDomainServiceImpl {
             dimension: 0,
             index_fullness: 0.0,
             namespace_stats: HashMap::new(),
+            index_state: None,
         })
     }
     
@@ -327,6 +339,44 @@ impl VectorStoreDomainService for VectorStoreDomainServiceImpl {
             _ => tenant_id.to_string(),
         }
     }
+    
+    fn apply_record_isolation(&self, record: &mut VectorRecord) {
+        // Always derived from the record's own tenant, so callers cannot spoof it
+        record.metadata.insert(
+            TENANT_ISOLATION_KEY_FIELD.to_string(),
+            serde_json::Value::String(record.tenant_isolation_key()),
+        );
+    }
+    
+    fn apply_query_isolation(&self, query: &mut SearchQuery, tenant_id: TenantId) -> Result<(), PlatformError> {
+        let isolation_condition = FilterCondition {
+            field: TENANT_ISOLATION_KEY_FIELD.to_string(),
+            operator: ComparisonOperator::Equal,
+            value: serde_json::Value::String(tenant_isolation_key(tenant_id, query.namespace.as_deref())),
+        };
+        
+        match query.filter {
+            Some(ref mut filter) => {
+                // An OR filter would let other conditions bypass the isolation condition
+                if filter.operator == FilterOperator::Or && !filter.conditions.is_empty() {
+                    return Err(PlatformError::ValidationError(
+                        "OR filters cannot be combined with tenant isolation".to_string()
+                    ));
+                }
+                filter.conditions.retain(|c| c.field != TENANT_ISOLATION_KEY_FIELD);
+                filter.operator = FilterOperator::And;
+                filter.conditions.push(isolation_condition);
+            }
+            None => {
+                query.filter = Some(SearchFilter {
+                    conditions: vec![isolation_condition],
+                    operator: FilterOperator::And,
+                });
+            }
+        }
+        
+        Ok(())
+    }
 }
 
 impl Default for VectorStoreDomainServiceImpl {
@@ -425,4 +475,73 @@ mod tests {
         let result = service.validate_tenant_namespace_access(tenant_id, Some(invalid_namespace));
         assert!(result.is_err());
     }
+
+    fn matches_filter(record: &VectorRecord, filter: &SearchFilter) -> bool {
+        let check = |c: &FilterCondition| match c.operator {
+            ComparisonOperator::Equal => record.metadata.get(&c.field) == Some(&c.value),
+            _ => true,
+        };
+        match filter.operator {
+            FilterOperator::And => filter.conditions.iter().all(check),
+            FilterOperator::Or => filter.conditions.iter().any(check),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_isolation_never_crosses_tenants() {
+        let service = VectorStoreDomainServiceImpl::new();
+        let tenant_a = create_test_tenant_id();
+        let tenant_b = create_test_tenant_id();
+
+        // Index records for both tenants in a shared index
+        let mut index = Vec::new();
+        for (i, tenant_id) in [tenant_a, tenant_b, tenant_a, tenant_b].into_iter().enumerate() {
+            let mut record = VectorRecord::new(format!("vec_{}", i), vec![0.1, 0.2], tenant_id)
+                .unwrap()
+                .with_namespace("docs".to_string());
+            service.apply_record_isolation(&mut record);
+            index.push(record);
+        }
+
+        // A caller-supplied filter on tenant B's key must not widen the query
+        let mut query = SearchQuery::new(vec![0.1, 0.2], 10)
+            .unwrap()
+            .with_namespace("docs".to_string())
+            .with_filter(SearchFilter {
+                conditions: vec![FilterCondition {
+                    field: TENANT_ISOLATION_KEY_FIELD.to_string(),
+                    operator: ComparisonOperator::Equal,
+                    value: serde_json::Value::String(tenant_isolation_key(tenant_b, Some("docs"))),
+                }],
+                operator: FilterOperator::And,
+            });
+        service.apply_query_isolation(&mut query, tenant_a).unwrap();
+
+        let filter = query.filter.as_ref().unwrap();
+        let results: Vec<&VectorRecord> = index.iter().filter(|r| matches_filter(r, filter)).collect();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.tenant_id == tenant_a));
+        assert_eq!(
+            results[0].metadata[TENANT_ISOLATION_KEY_FIELD],
+            serde_json::Value::String(format!("{}::docs", tenant_a))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_isolation_rejects_or_filters() {
+        let service = VectorStoreDomainServiceImpl::new();
+        let mut query = SearchQuery::new(vec![0.1, 0.2], 10)
+            .unwrap()
+            .with_filter(SearchFilter {
+                conditions: vec![FilterCondition {
+                    field: "category".to_string(),
+                    operator: ComparisonOperator::Equal,
+                    value: serde_json::json!("news"),
+                }],
+                operator: FilterOperator::Or,
+            });
+
+        assert!(service.apply_query_isolation(&mut query, create_test_tenant_id()).is_err());
+    }
 }
\ No newline at end of file
@@ -2,6 +2,14 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
+/// `custom_data` key of a tool message holding the id of the call it answers
+pub const TOOL_CALL_ID_KEY: &str = "tool_call_id";
+
+/// Scheme of image URLs naming an object uploaded to our file storage, e.g.
+/// `oss://uploads/<tenant>/<file>/cat.png`; they are resolved to download
+/// URLs before the message is stored
+pub const OSS_URL_SCHEME: &str = "oss://";
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub role: MessageRole,
@@ -18,12 +26,23 @@ pub enum MessageContent {
     Multimodal(Vec<ContentPart>),
 }
 
+/// How the `content` of a new message is to be read: a plain string, or a
+/// list of content parts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageContentType {
+    #[default]
+    Text,
+    Multimodal,
+}
+
 /// Individual content part (text or image)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentPart {
     Text { text: String },
     ImageUrl { image_url: ImageUrl },
+    ImageData { image_data: ImageData },
 }
 
 /// Image URL with optional detail level
@@ -34,6 +53,62 @@ pub struct ImageUrl {
     pub detail: Option<String>,
 }
 
+impl ImageUrl {
+    /// Object key of an `oss://` URL
+    pub fn oss_key(&self) -> Option<&str> {
+        self.url.strip_prefix(OSS_URL_SCHEME)
+    }
+
+    /// The image itself, for `data:` URLs
+    pub fn inline_data(&self) -> Option<ImageData> {
+        ImageData::from_data_url(&self.url)
+    }
+}
+
+/// Image sent inline as base64
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageData {
+    pub base64: String,
+    /// e.g. `image/png`
+    pub media_type: String,
+}
+
+impl ImageData {
+    /// Parse a `data:<media type>;base64,<data>` URL
+    pub fn from_data_url(url: &str) -> Option<Self> {
+        let (media_type, base64) = url.strip_prefix("data:")?.split_once(";base64,")?;
+        Some(Self {
+            base64: base64.to_string(),
+            media_type: media_type.to_string(),
+        })
+    }
+
+    pub fn to_data_url(&self) -> String {
+        format!("data:{};base64,{}", self.media_type, self.base64)
+    }
+}
+
+impl MessageContent {
+    /// Read request content according to `content_type`: a string for
+    /// text, an array of content parts for multimodal content
+    pub fn from_json(content_type: MessageContentType, content: serde_json::Value) -> Result<Self, String> {
+        match (content_type, content) {
+            (MessageContentType::Text, serde_json::Value::String(text)) => Ok(MessageContent::Text(text)),
+            (MessageContentType::Text, _) => Err("Text content must be a string".to_string()),
+            (MessageContentType::Multimodal, content) => serde_json::from_value(content)
+                .map(MessageContent::Multimodal)
+                .map_err(|e| format!("Invalid multimodal content: {}", e)),
+        }
+    }
+
+    pub fn content_type(&self) -> MessageContentType {
+        match self {
+            MessageContent::Text(_) => MessageContentType::Text,
+            MessageContent::Multimodal(_) => MessageContentType::Multimodal,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MessageRole {
@@ -54,6 +129,9 @@ pub struct MessageMetadata {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ToolCall {
+    /// Id the LLM gave the call, echoed back with the result
+    #[serde(default)]
+    pub call_id: Option<String>,
     pub tool_id: String,
     pub tool_name: String,
     pub parameters: serde_json::Value,
@@ -117,6 +195,40 @@ impl ChatMessage {
         }
     }
 
+    /// Assistant message asking for tools to be called
+    pub fn new_tool_call_message(content: String, tool_calls: Vec<ToolCall>) -> Self {
+        ChatMessage {
+            role: MessageRole::Assistant,
+            content: MessageContent::Text(content),
+            metadata: Some(MessageMetadata {
+                model_used: None,
+                tokens_used: None,
+                response_time_ms: None,
+                tool_calls: Some(tool_calls),
+                custom_data: HashMap::new(),
+            }),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Tool message with the result of the call `tool_call_id`
+    pub fn new_tool_result_message(tool_call_id: String, content: String) -> Self {
+        ChatMessage {
+            role: MessageRole::Tool,
+            content: MessageContent::Text(content),
+            metadata: Some(MessageMetadata {
+                model_used: None,
+                tokens_used: None,
+                response_time_ms: None,
+                tool_calls: None,
+                custom_data: HashMap::from([
+                    (TOOL_CALL_ID_KEY.to_string(), serde_json::json!(tool_call_id)),
+                ]),
+            }),
+            timestamp: Utc::now(),
+        }
+    }
+
     pub fn with_metadata(mut self, metadata: MessageMetadata) -> Self {
         self.metadata = Some(metadata);
         self
@@ -125,7 +237,8 @@ impl ChatMessage {
     pub fn validate(&self) -> Result<(), String> {
         match &self.content {
             MessageContent::Text(text) => {
-                if text.trim().is_empty() {
+                // Tool call requests often come without any text
+                if text.trim().is_empty() && !self.has_tool_calls() {
                     return Err("Message content cannot be empty".to_string());
                 }
                 if text.len() > 100_000 {
@@ -148,6 +261,14 @@ impl ChatMessage {
                                 return Err("Image URL cannot be empty".to_string());
                             }
                         }
+                        ContentPart::ImageData { image_data } => {
+                            if image_data.base64.trim().is_empty() {
+                                return Err("Image data cannot be empty".to_string());
+                            }
+                            if !image_data.media_type.starts_with("image/") {
+                                return Err(format!("Unsupported image media type: {}", image_data.media_type));
+                            }
+                        }
                     }
                 }
             }
@@ -187,6 +308,15 @@ impl ChatMessage {
             .map(|calls| !calls.is_empty())
             .unwrap_or(false)
     }
+
+    /// Id of the call a tool message answers
+    pub fn tool_call_id(&self) -> Option<&str> {
+        self.metadata
+            .as_ref()?
+            .custom_data
+            .get(TOOL_CALL_ID_KEY)?
+            .as_str()
+    }
 }
 
 impl SessionContext {
@@ -240,4 +370,59 @@ impl Default for MessageMetadata {
             custom_data: HashMap::new(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_message_content_from_json() {
+        let text = MessageContent::from_json(MessageContentType::Text, json!("Hello")).unwrap();
+        assert_eq!(text, MessageContent::Text("Hello".to_string()));
+        assert!(MessageContent::from_json(MessageContentType::Text, json!(["Hello"])).is_err());
+
+        let multimodal = MessageContent::from_json(
+            MessageContentType::Multimodal,
+            json!([
+                {"type": "text", "text": "What is this?"},
+                {"type": "image_url", "image_url": {"url": "oss://uploads/t/f/cat.png"}},
+                {"type": "image_data", "image_data": {"base64": "iVBORw0KGgo=", "media_type": "image/png"}}
+            ]),
+        )
+        .unwrap();
+        assert_eq!(multimodal.content_type(), MessageContentType::Multimodal);
+
+        let MessageContent::Multimodal(parts) = multimodal else { unreachable!() };
+        assert!(matches!(&parts[1], ContentPart::ImageUrl { image_url } if image_url.oss_key() == Some("uploads/t/f/cat.png")));
+        assert!(matches!(&parts[2], ContentPart::ImageData { image_data } if image_data.media_type == "image/png"));
+    }
+
+    #[test]
+    fn test_image_data_url_round_trip() {
+        let data = ImageData::from_data_url("data:image/jpeg;base64,/9j/4AAQ").unwrap();
+        assert_eq!(data.media_type, "image/jpeg");
+        assert_eq!(data.base64, "/9j/4AAQ");
+        assert_eq!(data.to_data_url(), "data:image/jpeg;base64,/9j/4AAQ");
+
+        assert!(ImageData::from_data_url("https://example.com/cat.png").is_none());
+    }
+
+    #[test]
+    fn test_validate_rejects_non_image_data() {
+        let message = ChatMessage {
+            role: MessageRole::User,
+            content: MessageContent::Multimodal(vec![ContentPart::ImageData {
+                image_data: ImageData {
+                    base64: "JVBERi0=".to_string(),
+                    media_type: "application/pdf".to_string(),
+                },
+            }]),
+            metadata: None,
+            timestamp: Utc::now(),
+        };
+
+        assert!(message.validate().is_err());
+    }
+}
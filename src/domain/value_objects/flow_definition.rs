@@ -11,6 +11,9 @@ pub struct FlowDefinition {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlowWorkflow {
     pub graph: FlowGraph,
+    /// Dify `environment_variables`, kept so imported flows export unchanged
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub environment_variables: Vec<Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -30,6 +33,30 @@ pub struct FlowNode {
     pub position: NodePosition,
 }
 
+impl FlowNode {
+    /// LLM config the node calls: `model.llm_config_id` for LLM and
+    /// parameter extractor nodes, `llm_config_id` for knowledge base nodes
+    pub fn llm_config_id(&self) -> Option<uuid::Uuid> {
+        self.data
+            .get("model")
+            .and_then(|model| model.get("llm_config_id"))
+            .or_else(|| self.data.get("llm_config_id"))
+            .and_then(Value::as_str)
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())
+    }
+
+    /// MCP tool called by an MCP tool node
+    pub fn mcp_tool_id(&self) -> Option<uuid::Uuid> {
+        if self.node_type != NodeType::McpTool {
+            return None;
+        }
+        self.data
+            .get("tool_id")
+            .and_then(Value::as_str)
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlowEdge {
     pub id: String,
@@ -50,6 +77,79 @@ pub struct FlowVariable {
     pub description: Option<String>,
 }
 
+/// Prefix of the state variables environment variables are injected as
+pub const ENVIRONMENT_VARIABLE_PREFIX: &str = "env.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvironmentVariableType {
+    String,
+    Number,
+    /// Value is kept in the tenant's secret store, never in the flow definition
+    Secret,
+}
+
+/// Dify `environment_variables` entry, injected into the execution state as
+/// `env.{name}` when a flow starts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    pub name: String,
+    pub value_type: EnvironmentVariableType,
+    /// Always `Null` for secrets
+    #[serde(default)]
+    pub value: Value,
+}
+
+impl EnvironmentVariable {
+    /// Parse one entry, dropping the value of secrets
+    pub fn from_dify(entry: &Value) -> Result<Self, String> {
+        let mut variable: EnvironmentVariable = serde_json::from_value(entry.clone())
+            .map_err(|e| format!("Invalid environment variable: {}", e))?;
+
+        if !Self::is_valid_name(&variable.name) {
+            return Err(format!("Invalid environment variable name: '{}'", variable.name));
+        }
+
+        match variable.value_type {
+            EnvironmentVariableType::Secret => variable.value = Value::Null,
+            EnvironmentVariableType::Number => {
+                variable.value = match &variable.value {
+                    Value::Number(_) => variable.value.clone(),
+                    Value::String(s) => s.trim().parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map(Value::Number)
+                        .ok_or_else(|| format!("Environment variable '{}' is not a number", variable.name))?,
+                    _ => return Err(format!("Environment variable '{}' is not a number", variable.name)),
+                };
+            }
+            EnvironmentVariableType::String => {
+                if !variable.value.is_string() {
+                    return Err(format!("Environment variable '{}' is not a string", variable.name));
+                }
+            }
+        }
+
+        Ok(variable)
+    }
+
+    /// Letters, digits and underscores, not starting with a digit
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// Key of the variable in the execution state
+    pub fn state_key(&self) -> String {
+        format!("{}{}", ENVIRONMENT_VARIABLE_PREFIX, self.name)
+    }
+
+    pub fn is_secret(&self) -> bool {
+        self.value_type == EnvironmentVariableType::Secret
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlowMetadata {
     pub description: Option<String>,
@@ -80,6 +180,33 @@ pub enum NodeType {
     Answer,
     ParameterExtractor,
     Iteration,
+    DocumentIngestion,
+    KnowledgeBaseRetrieval,
+    Embedding,
+}
+
+impl NodeType {
+    /// Serialized name of the node type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeType::Start => "start",
+            NodeType::End => "end",
+            NodeType::Llm => "llm",
+            NodeType::VectorSearch => "vector_search",
+            NodeType::McpTool => "mcp_tool",
+            NodeType::Condition => "condition",
+            NodeType::Loop => "loop",
+            NodeType::Variable => "variable",
+            NodeType::HttpRequest => "http_request",
+            NodeType::Code => "code",
+            NodeType::Answer => "answer",
+            NodeType::ParameterExtractor => "parameter_extractor",
+            NodeType::Iteration => "iteration",
+            NodeType::DocumentIngestion => "document_ingestion",
+            NodeType::KnowledgeBaseRetrieval => "knowledge_base_retrieval",
+            NodeType::Embedding => "embedding",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -92,6 +219,23 @@ pub enum VariableType {
     Object,
 }
 
+impl FlowWorkflow {
+    /// Typed view of `environment_variables`; names must be unique
+    pub fn parsed_environment_variables(&self) -> Result<Vec<EnvironmentVariable>, String> {
+        let mut names = std::collections::HashSet::new();
+        self.environment_variables
+            .iter()
+            .map(|entry| {
+                let variable = EnvironmentVariable::from_dify(entry)?;
+                if !names.insert(variable.name.clone()) {
+                    return Err(format!("Duplicate environment variable: {}", variable.name));
+                }
+                Ok(variable)
+            })
+            .collect()
+    }
+}
+
 impl FlowDefinition {
     pub fn new() -> Self {
         FlowDefinition {
@@ -100,6 +244,7 @@ impl FlowDefinition {
                     nodes: Vec::new(),
                     edges: Vec::new(),
                 },
+                environment_variables: Vec::new(),
             },
             // variables: Vec::new(),
             // metadata: FlowMetadata {
@@ -217,6 +362,75 @@ impl FlowDefinition {
             .filter(|n| n.node_type == NodeType::Answer)
             .collect()
     }
+
+    /// Copy of the definition with new node and edge IDs. Edges, parent
+    /// links and node references inside node data (`{{#node.var#}}`
+    /// templates, variable selectors, iteration start nodes) follow the
+    /// new IDs, so the copy runs exactly like the original.
+    pub fn with_regenerated_ids(&self) -> Self {
+        use std::collections::HashMap;
+
+        let node_ids: HashMap<String, String> = self
+            .workflow
+            .graph
+            .nodes
+            .iter()
+            .map(|n| (n.id.clone(), uuid::Uuid::new_v4().to_string()))
+            .collect();
+        let remap = |id: &str| node_ids.get(id).cloned().unwrap_or_else(|| id.to_string());
+
+        let mut definition = self.clone();
+        for node in &mut definition.workflow.graph.nodes {
+            node.id = remap(&node.id);
+            node.parent_id = node.parent_id.as_deref().map(remap);
+            remap_node_references(&mut node.data, &node_ids);
+        }
+        for edge in &mut definition.workflow.graph.edges {
+            edge.id = uuid::Uuid::new_v4().to_string();
+            edge.source = remap(&edge.source);
+            edge.target = remap(&edge.target);
+        }
+        definition
+    }
+}
+
+/// Point node references in `value` at the IDs in `node_ids`. Selectors
+/// (`value_selector: ["node", "var"]`) and `*node_id` fields are matched
+/// exactly; templates are matched on their `#node.` prefix.
+fn remap_node_references(value: &mut Value, node_ids: &std::collections::HashMap<String, String>) {
+    match value {
+        Value::String(text) => {
+            for (old_id, new_id) in node_ids {
+                let pattern = format!("#{}.", old_id);
+                if text.contains(&pattern) {
+                    *text = text.replace(&pattern, &format!("#{}.", new_id));
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                remap_node_references(item, node_ids);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                let reference = if key.ends_with("_selector") {
+                    field.as_array_mut().and_then(|selector| selector.first_mut())
+                } else if key.ends_with("node_id") || key == "iteration_id" {
+                    Some(&mut *field)
+                } else {
+                    None
+                };
+                if let Some(Value::String(id)) = reference {
+                    if let Some(new_id) = node_ids.get(id.as_str()) {
+                        *id = new_id.clone();
+                    }
+                }
+                remap_node_references(field, node_ids);
+            }
+        }
+        _ => {}
+    }
 }
 
 impl Default for FlowDefinition {
@@ -224,3 +438,219 @@ impl Default for FlowDefinition {
         Self::new()
     }
 }
+
+/// A topology problem found by `FlowGraph::validate`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValidationError {
+    /// Nodes on or between cycles. Edges back into loop nodes are allowed.
+    Cycle { node_ids: Vec<String> },
+    /// An edge whose source or target is not a node of the graph
+    UnknownEdgeNode { edge_id: String, node_id: String },
+    /// A node other than the start node that nothing leads to
+    OrphanNode { node_id: String },
+    MissingStartNode,
+    MultipleStartNodes { node_ids: Vec<String> },
+    MissingEndNode,
+    /// A condition node without exactly one `true` and one `false` edge
+    InvalidConditionBranches { node_id: String, handles: Vec<String> },
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle { node_ids } => write!(f, "Cycle through nodes: {}", node_ids.join(", ")),
+            Self::UnknownEdgeNode { edge_id, node_id } => {
+                write!(f, "Edge {} references non-existent node: {}", edge_id, node_id)
+            }
+            Self::OrphanNode { node_id } => write!(f, "Node {} has no incoming edge", node_id),
+            Self::MissingStartNode => write!(f, "Flow must have a start node"),
+            Self::MultipleStartNodes { node_ids } => {
+                write!(f, "Flow must have exactly one start node, found: {}", node_ids.join(", "))
+            }
+            Self::MissingEndNode => write!(f, "Flow must have at least one end node"),
+            Self::InvalidConditionBranches { node_id, handles } => write!(
+                f,
+                "Condition node {} must have exactly two outgoing edges labeled true and false, found: [{}]",
+                node_id,
+                handles.join(", ")
+            ),
+        }
+    }
+}
+
+impl FlowGraph {
+    /// Check the graph topology, collecting every problem instead of
+    /// stopping at the first one. Answer nodes end a run just like end nodes,
+    /// so either satisfies the end node check.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        use std::collections::{HashMap, HashSet};
+
+        let mut errors = Vec::new();
+        let node_ids: HashSet<&str> = self.nodes.iter().map(|n| n.id.as_str()).collect();
+
+        // Start and end nodes; nested graphs have start nodes of their own
+        let start_nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Start && n.parent_id.is_none())
+            .map(|n| n.id.clone())
+            .collect();
+        match start_nodes.len() {
+            0 => errors.push(ValidationError::MissingStartNode),
+            1 => {}
+            _ => errors.push(ValidationError::MultipleStartNodes { node_ids: start_nodes }),
+        }
+        if !self
+            .nodes
+            .iter()
+            .any(|n| matches!(n.node_type, NodeType::End | NodeType::Answer))
+        {
+            errors.push(ValidationError::MissingEndNode);
+        }
+
+        // Edges must connect existing nodes; the rest of the checks only
+        // look at edges that do
+        let mut edges = Vec::with_capacity(self.edges.len());
+        for edge in &self.edges {
+            let mut known = true;
+            for node_id in [&edge.source, &edge.target] {
+                if !node_ids.contains(node_id.as_str()) {
+                    errors.push(ValidationError::UnknownEdgeNode {
+                        edge_id: edge.id.clone(),
+                        node_id: node_id.clone(),
+                    });
+                    known = false;
+                }
+            }
+            if known {
+                edges.push(edge);
+            }
+        }
+
+        let targets: HashSet<&str> = edges.iter().map(|e| e.target.as_str()).collect();
+        for node in &self.nodes {
+            if node.node_type != NodeType::Start && !targets.contains(node.id.as_str()) {
+                errors.push(ValidationError::OrphanNode { node_id: node.id.clone() });
+            }
+        }
+
+        for node in self.nodes.iter().filter(|n| n.node_type == NodeType::Condition) {
+            let mut handles: Vec<String> = edges
+                .iter()
+                .filter(|e| e.source == node.id)
+                .map(|e| e.source_handle.clone().unwrap_or_default())
+                .collect();
+            handles.sort();
+            if handles != ["false", "true"] {
+                errors.push(ValidationError::InvalidConditionBranches {
+                    node_id: node.id.clone(),
+                    handles,
+                });
+            }
+        }
+
+        // Topological sort. Loop nodes re-enter themselves by design, so
+        // edges into them are left out.
+        let loop_nodes: HashSet<&str> = self
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Loop)
+            .map(|n| n.id.as_str())
+            .collect();
+        let sorted_edges: Vec<(&str, &str)> = edges
+            .iter()
+            .filter(|e| !loop_nodes.contains(e.target.as_str()))
+            .map(|e| (e.source.as_str(), e.target.as_str()))
+            .collect();
+
+        let mut in_degree: HashMap<&str, usize> = node_ids.iter().map(|id| (*id, 0)).collect();
+        for (_, target) in &sorted_edges {
+            *in_degree.entry(*target).or_default() += 1;
+        }
+        let mut ready: Vec<&str> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut remaining: HashSet<&str> = node_ids.clone();
+        while let Some(node_id) = ready.pop() {
+            remaining.remove(node_id);
+            for (_, target) in sorted_edges.iter().filter(|(source, _)| *source == node_id) {
+                let degree = in_degree.entry(*target).or_default();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(*target);
+                }
+            }
+        }
+
+        // Nodes left over are on a cycle or downstream of one; peel off the
+        // downstream ones so only the cycle is reported
+        loop {
+            let dead_ends: Vec<&str> = remaining
+                .iter()
+                .filter(|id| {
+                    !sorted_edges
+                        .iter()
+                        .any(|(source, target)| source == *id && remaining.contains(target))
+                })
+                .copied()
+                .collect();
+            if dead_ends.is_empty() {
+                break;
+            }
+            for id in dead_ends {
+                remaining.remove(id);
+            }
+        }
+        if !remaining.is_empty() {
+            let mut node_ids: Vec<String> = remaining.into_iter().map(str::to_string).collect();
+            node_ids.sort();
+            errors.push(ValidationError::Cycle { node_ids });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(node_type: NodeType, data: Value) -> FlowNode {
+        FlowNode {
+            id: "node".to_string(),
+            parent_id: None,
+            node_type,
+            data,
+            position: NodePosition { x: 0.0, y: 0.0 },
+        }
+    }
+
+    #[test]
+    fn test_node_config_references() {
+        let config_id = uuid::Uuid::new_v4();
+        let tool_id = uuid::Uuid::new_v4();
+
+        let llm = node(NodeType::Llm, json!({"model": {"llm_config_id": config_id.to_string()}}));
+        let retrieval = node(NodeType::KnowledgeBaseRetrieval, json!({"llm_config_id": config_id.to_string()}));
+        let tool = node(NodeType::McpTool, json!({"tool_id": tool_id.to_string()}));
+        let code = node(NodeType::Code, json!({"tool_id": tool_id.to_string()}));
+
+        assert_eq!(llm.llm_config_id(), Some(config_id));
+        assert_eq!(retrieval.llm_config_id(), Some(config_id));
+        assert_eq!(tool.mcp_tool_id(), Some(tool_id));
+        assert_eq!(tool.llm_config_id(), None);
+        assert_eq!(code.mcp_tool_id(), None);
+        assert_eq!(
+            serde_json::to_value(NodeType::KnowledgeBaseRetrieval).unwrap(),
+            json!(NodeType::KnowledgeBaseRetrieval.as_str())
+        );
+    }
+}
@@ -36,6 +36,9 @@ pub struct AgentId(pub Uuid);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct APIKeyId(pub Uuid);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FlowNodeAnnotationId(pub Uuid);
+
 // Implementations for ID value objects
 impl UserId {
     pub fn new() -> Self {
@@ -176,6 +179,22 @@ impl From<Uuid> for APIKeyId {
     }
 }
 
+impl FlowNodeAnnotationId {
+    pub fn new() -> Self {
+        FlowNodeAnnotationId(Uuid::new_v4())
+    }
+    
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        FlowNodeAnnotationId(uuid)
+    }
+}
+
+impl From<Uuid> for FlowNodeAnnotationId {
+    fn from(uuid: Uuid) -> Self {
+        FlowNodeAnnotationId(uuid)
+    }
+}
+
 // Display implementations for all ID types
 impl fmt::Display for UserId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -241,4 +260,10 @@ impl fmt::Display for APIKeyId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
     }
-}
\ No newline at end of file
+}
+
+impl fmt::Display for FlowNodeAnnotationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -87,4 +87,15 @@ impl TenantName {
         }
         Ok(TenantName(name.trim().to_string()))
     }
+
+    /// URL-safe form of the name used in tenant-scoped paths, e.g.
+    /// "Acme Corp." becomes "acme-corp"
+    pub fn slug(&self) -> String {
+        self.0
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
 }
\ No newline at end of file
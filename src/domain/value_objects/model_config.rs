@@ -19,6 +19,8 @@ pub enum ModelProvider {
     LocalLLM,
     Ollama,
     HuggingFace,
+    #[serde(rename = "deepseek")]
+    DeepSeek,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -40,6 +42,22 @@ pub struct ModelCredentials {
     pub custom_headers: HashMap<String, String>,
 }
 
+/// How an agent picks an LLM config when its preferred one is unset or
+/// unavailable
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", content = "models", rename_all = "snake_case")]
+pub enum LLMSelectionStrategy {
+    /// The tenant's default config, otherwise the first active one
+    #[default]
+    FirstAvailable,
+    /// Rotate through the tenant's active configs
+    RoundRobin,
+    /// The config with the lowest rolling average response time
+    LeastLatency,
+    /// The first config whose model name appears earliest in the list
+    ModelPreference(Vec<String>),
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VectorConfig {
     pub provider: VectorProvider,
@@ -81,7 +99,72 @@ pub enum VectorMetric {
     DotProduct,
 }
 
+/// Context window assumed for models not in `KNOWN_CONTEXT_LENGTHS`
+pub const DEFAULT_CONTEXT_LENGTH: u32 = 8192;
+
+/// Share of the context window a prompt may take up; the rest is left for the reply
+pub const PROMPT_CONTEXT_SHARE: f64 = 0.6;
+
+/// Context windows of well-known models, matched by model name prefix in order
+const KNOWN_CONTEXT_LENGTHS: &[(&str, u32)] = &[
+    ("gpt-4o", 128_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("claude", 200_000),
+    ("gemini-1.5-pro", 2_097_152),
+    ("gemini", 1_048_576),
+    ("deepseek", 65_536),
+    ("qwen", 131_072),
+];
+
 impl ModelConfig {
+    /// Context window in tokens: the `context_length` custom parameter if
+    /// set, else the known window of the model, else `DEFAULT_CONTEXT_LENGTH`
+    pub fn context_length(&self) -> u32 {
+        if let Some(context_length) = self
+            .parameters
+            .custom_parameters
+            .get("context_length")
+            .and_then(|v| v.as_u64())
+            .filter(|&n| n > 0)
+        {
+            return context_length.min(u32::MAX as u64) as u32;
+        }
+
+        // Names may carry a provider prefix, e.g. `openai/gpt-4o`
+        let name = self.model_name.rsplit('/').next().unwrap_or_default().to_lowercase();
+        KNOWN_CONTEXT_LENGTHS
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+            .map(|&(_, context_length)| context_length)
+            .unwrap_or(DEFAULT_CONTEXT_LENGTH)
+    }
+
+    /// Whether the config is for an embedding model: the `model_type` custom
+    /// parameter if set, else guessed from the model name
+    pub fn is_embedding_model(&self) -> bool {
+        if let Some(model_type) = self
+            .parameters
+            .custom_parameters
+            .get("model_type")
+            .and_then(|v| v.as_str())
+        {
+            return model_type.eq_ignore_ascii_case("embedding");
+        }
+
+        let name = self.model_name.rsplit('/').next().unwrap_or_default().to_lowercase();
+        name.contains("embed") || name.starts_with("bge-")
+    }
+
+    /// Tokens a prompt may take up, leaving room for the reply
+    pub fn prompt_token_budget(&self) -> usize {
+        (self.context_length() as f64 * PROMPT_CONTEXT_SHARE) as usize
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         if self.model_name.trim().is_empty() {
             return Err("Model name cannot be empty".to_string());
@@ -109,7 +192,10 @@ impl ModelConfig {
     }
 
     pub fn supports_streaming(&self) -> bool {
-        matches!(self.provider, ModelProvider::OpenAI | ModelProvider::Claude | ModelProvider::LocalLLM)
+        matches!(
+            self.provider,
+            ModelProvider::OpenAI | ModelProvider::Claude | ModelProvider::LocalLLM | ModelProvider::DeepSeek
+        )
     }
 }
 
@@ -161,10 +247,56 @@ impl ModelProvider {
         match provider.to_lowercase().as_str() {
             "openai" => Ok(ModelProvider::OpenAI),
             "claude" | "anthropic" => Ok(ModelProvider::Claude),
+            "deepseek" => Ok(ModelProvider::DeepSeek),
             _ => Err(PlatformError::ValidationError(format!(
                 "Unknown provider: {}",
                 provider
             ))),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(model_name: &str) -> ModelConfig {
+        ModelConfig {
+            provider: ModelProvider::OpenAI,
+            model_name: model_name.to_string(),
+            parameters: ModelParameters::default(),
+            credentials: ModelCredentials::default(),
+        }
+    }
+
+    #[test]
+    fn test_context_length() {
+        assert_eq!(model("gpt-4o-mini").context_length(), 128_000);
+        assert_eq!(model("gpt-4").context_length(), 8_192);
+        assert_eq!(model("anthropic/claude-3-haiku").context_length(), 200_000);
+        assert_eq!(model("my-local-model").context_length(), DEFAULT_CONTEXT_LENGTH);
+
+        let mut custom = model("gpt-4");
+        custom
+            .parameters
+            .custom_parameters
+            .insert("context_length".to_string(), serde_json::json!(32_768));
+        assert_eq!(custom.context_length(), 32_768);
+        assert_eq!(custom.prompt_token_budget(), 19_660);
+    }
+
+    #[test]
+    fn test_is_embedding_model() {
+        assert!(model("text-embedding-3-small").is_embedding_model());
+        assert!(model("ollama/nomic-embed-text").is_embedding_model());
+        assert!(model("bge-m3").is_embedding_model());
+        assert!(!model("gpt-4o").is_embedding_model());
+
+        let mut custom = model("my-vectorizer");
+        custom
+            .parameters
+            .custom_parameters
+            .insert("model_type".to_string(), serde_json::json!("embedding"));
+        assert!(custom.is_embedding_model());
+    }
+}
@@ -287,26 +287,75 @@ impl HTTPToolConfig {
         Ok(())
     }
 
-    /// 验证调用参数
-    pub fn validate_call_parameters(&self, params: &serde_json::Value) -> Result<(), String> {
-        let params_obj = params.as_object()
-            .ok_or("Parameters must be a JSON object")?;
-
-        // 验证每个定义的参数
-        for param_schema in &self.parameters {
-            let param_value = params_obj.get(&param_schema.name)
-                .unwrap_or(&serde_json::Value::Null);
-            param_schema.validate_value(param_value)?;
-        }
+    /// 调用参数的JSON Schema，包含所有位置的参数，不允许未定义的参数
+    pub fn parameter_schema(&self) -> serde_json::Value {
+        let mut properties = serde_json::Map::new();
+        let mut required = Vec::new();
 
-        // 检查是否有未定义的参数
-        for (param_name, _) in params_obj {
-            if !self.parameters.iter().any(|p| &p.name == param_name) {
-                return Err(format!("Unknown parameter: {}", param_name));
+        for param in &self.parameters {
+            let type_str = match param.parameter_type {
+                ParameterType::String => "string",
+                ParameterType::Number => "number",
+                ParameterType::Boolean => "boolean",
+                ParameterType::Object => "object",
+                ParameterType::Array => "array",
+            };
+
+            // 可选参数允许显式传null
+            let mut property = if param.required {
+                serde_json::json!({ "type": type_str })
+            } else {
+                serde_json::json!({ "type": [type_str, "null"] })
+            };
+            if let Some(enum_values) = &param.enum_values {
+                let mut enum_values = enum_values.clone();
+                if !param.required {
+                    enum_values.push(serde_json::Value::Null);
+                }
+                property["enum"] = serde_json::Value::Array(enum_values);
+            }
+
+            properties.insert(param.name.clone(), property);
+            if param.required {
+                required.push(param.name.clone());
             }
         }
 
-        Ok(())
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    /// 验证调用参数，错误信息列出每个不合法的字段路径
+    pub fn validate_call_parameters(&self, params: &serde_json::Value) -> Result<(), String> {
+        if !params.is_object() {
+            return Err("Parameters must be a JSON object".to_string());
+        }
+
+        let schema = self.parameter_schema();
+        let validator = jsonschema::validator_for(&schema)
+            .map_err(|e| format!("Invalid parameter schema: {}", e))?;
+
+        let violations: Vec<String> = validator
+            .iter_errors(params)
+            .map(|error| {
+                let path = error.instance_path.to_string();
+                if path.is_empty() {
+                    error.to_string()
+                } else {
+                    format!("{} (at {})", error, path)
+                }
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations.join("; "))
+        }
     }
 }
 
@@ -336,6 +385,13 @@ impl ToolConfig {
         }
     }
 
+    /// 调用参数的JSON Schema
+    pub fn parameter_schema(&self) -> serde_json::Value {
+        match self {
+            ToolConfig::HTTP(config) => config.parameter_schema(),
+        }
+    }
+
     /// 验证调用参数
     pub fn validate_call_parameters(&self, params: &serde_json::Value) -> Result<(), String> {
         match self {
@@ -448,6 +504,41 @@ mod tests {
         assert!(config.validate_call_parameters(&unknown_params).is_err());
     }
 
+    #[test]
+    fn test_call_parameter_errors_list_each_field() {
+        let config = HTTPToolConfig::new(
+            "https://api.example.com/users/{userId}".to_string(),
+            HttpMethod::POST,
+        )
+        .with_parameter(
+            ParameterSchema::new("userId".to_string(), ParameterType::String, true)
+                .with_position(ParameterPosition::Path)
+        )
+        .with_parameter(ParameterSchema::new("age".to_string(), ParameterType::Number, false))
+        .with_parameter(
+            ParameterSchema::new("status".to_string(), ParameterType::String, false)
+                .with_enum_values(vec![json!("active"), json!("inactive")])
+        );
+
+        // Optional parameters may be null
+        assert!(config
+            .validate_call_parameters(&json!({"userId": "u1", "age": null, "status": null}))
+            .is_ok());
+
+        let error = config
+            .validate_call_parameters(&json!({"age": "old", "status": "gone", "extra": 1}))
+            .unwrap_err();
+        assert!(error.contains("\"userId\" is a required property"), "{}", error);
+        assert!(error.contains("(at /age)"), "{}", error);
+        assert!(error.contains("(at /status)"), "{}", error);
+        assert!(error.contains("extra"), "{}", error);
+
+        assert_eq!(
+            config.validate_call_parameters(&json!(["u1"])).unwrap_err(),
+            "Parameters must be a JSON object"
+        );
+    }
+
     #[test]
     fn test_path_parameter_validation_success() {
         let config = HTTPToolConfig::new(
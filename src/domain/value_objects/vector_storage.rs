@@ -1,6 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::domain::value_objects::TenantId;
+use crate::domain::value_objects::{ConfigId, TenantId};
 
 /// Vector record containing the vector data and metadata
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -10,6 +11,12 @@ pub struct VectorRecord {
     pub metadata: HashMap<String, serde_json::Value>,
     pub tenant_id: TenantId,
     pub namespace: Option<String>,
+    /// Sparse vector as dimension index to weight, used by hybrid search
+    #[serde(default)]
+    pub sparse_vector: Option<HashMap<u32, f32>>,
+    /// When the record stops being returned and becomes eligible for deletion
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl VectorRecord {
@@ -31,6 +38,8 @@ impl VectorRecord {
             metadata: HashMap::new(),
             tenant_id,
             namespace: None,
+            sparse_vector: None,
+            expires_at: None,
         })
     }
     
@@ -44,11 +53,47 @@ impl VectorRecord {
         self
     }
     
+    pub fn with_sparse_vector(mut self, sparse_vector: HashMap<u32, f32>) -> Self {
+        self.sparse_vector = Some(sparse_vector);
+        self
+    }
+    
+    pub fn with_expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+    
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+    
     pub fn dimension(&self) -> usize {
         self.vector.len()
     }
+
+    /// Tenant isolation key for this record, formatted as `{tenant_id}::{namespace}`
+    pub fn tenant_isolation_key(&self) -> String {
+        tenant_isolation_key(self.tenant_id, self.namespace.as_deref())
+    }
+}
+
+/// Metadata field holding the tenant isolation key on every stored vector
+pub const TENANT_ISOLATION_KEY_FIELD: &str = "tenant_isolation_key";
+
+/// Namespace segment used in isolation keys when no namespace is given
+pub const DEFAULT_ISOLATION_NAMESPACE: &str = "default";
+
+/// Build the tenant isolation key, formatted as `{tenant_id}::{namespace}`
+pub fn tenant_isolation_key(tenant_id: TenantId, namespace: Option<&str>) -> String {
+    let namespace = namespace
+        .filter(|ns| !ns.is_empty())
+        .unwrap_or(DEFAULT_ISOLATION_NAMESPACE);
+    format!("{}::{}", tenant_id, namespace)
 }
 
+/// Dense weight of a hybrid search when the query does not set one
+pub const DEFAULT_HYBRID_WEIGHT: f32 = 0.5;
+
 /// Search query for vector similarity search
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchQuery {
@@ -58,6 +103,14 @@ pub struct SearchQuery {
     pub namespace: Option<String>,
     pub include_metadata: bool,
     pub include_values: bool,
+    /// Sparse query vector; makes the search hybrid on providers that
+    /// support it and is ignored by the others
+    #[serde(default)]
+    pub sparse_vector: Option<HashMap<u32, f32>>,
+    /// Weight of the dense results in a hybrid search, from 0.0 (pure
+    /// sparse) to 1.0 (pure dense)
+    #[serde(default)]
+    pub hybrid_weight: Option<f32>,
 }
 
 impl SearchQuery {
@@ -79,6 +132,8 @@ impl SearchQuery {
             namespace: None,
             include_metadata: true,
             include_values: false,
+            sparse_vector: None,
+            hybrid_weight: None,
         })
     }
     
@@ -101,6 +156,36 @@ impl SearchQuery {
         self.include_metadata = include;
         self
     }
+    
+    pub fn with_sparse_vector(mut self, sparse_vector: HashMap<u32, f32>) -> Self {
+        self.sparse_vector = Some(sparse_vector);
+        self
+    }
+    
+    pub fn with_hybrid_weight(mut self, weight: f32) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&weight) {
+            return Err("hybrid_weight must be between 0.0 and 1.0".to_string());
+        }
+        self.hybrid_weight = Some(weight);
+        Ok(self)
+    }
+    
+    /// Whether the query carries a sparse vector
+    pub fn is_hybrid(&self) -> bool {
+        self.sparse_vector.is_some()
+    }
+    
+    /// Weight of the dense results in a hybrid search
+    pub fn dense_weight(&self) -> f32 {
+        self.hybrid_weight.unwrap_or(DEFAULT_HYBRID_WEIGHT)
+    }
+    
+    /// Drop the sparse part of the query, for providers without hybrid search
+    pub fn into_dense(mut self) -> Self {
+        self.sparse_vector = None;
+        self.hybrid_weight = None;
+        self
+    }
 }
 
 /// Filter for vector search
@@ -226,6 +311,9 @@ pub struct VectorStats {
     pub dimension: usize,
     pub index_fullness: f32,
     pub namespace_stats: HashMap<String, NamespaceStats>,
+    /// Provider-reported build state of the vector index, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_state: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -233,11 +321,25 @@ pub struct NamespaceStats {
     pub vector_count: u64,
 }
 
+/// Expiry of a record held by a store without native TTL support, kept in
+/// `vector_record_metadata` until `VectorTTLReaper` deletes the record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorRecordExpiry {
+    pub config_id: ConfigId,
+    pub tenant_id: TenantId,
+    pub namespace: Option<String>,
+    pub record_id: String,
+    pub expires_at: DateTime<Utc>,
+}
+
 /// Batch operation for vector storage
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BatchOperation {
     pub upsert: Vec<VectorRecord>,
     pub delete: Vec<String>,
+    /// Expiry for upserted records that do not set their own
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl BatchOperation {
@@ -245,6 +347,22 @@ impl BatchOperation {
         BatchOperation {
             upsert: Vec::new(),
             delete: Vec::new(),
+            expires_at: None,
+        }
+    }
+    
+    pub fn with_expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+    
+    /// Copy the batch expiry onto upserted records without one
+    pub fn apply_expiry(&mut self) {
+        let Some(expires_at) = self.expires_at else {
+            return;
+        };
+        for record in &mut self.upsert {
+            record.expires_at.get_or_insert(expires_at);
         }
     }
     
@@ -267,4 +385,38 @@ impl Default for BatchOperation {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_expiry_applies_to_records_without_one() {
+        let tenant_id = TenantId::new();
+        let batch_expiry = Utc::now() + chrono::Duration::hours(1);
+        let own_expiry = Utc::now() + chrono::Duration::days(1);
+
+        let mut operation = BatchOperation::new()
+            .add_upsert(VectorRecord::new("a".to_string(), vec![1.0], tenant_id).unwrap())
+            .add_upsert(
+                VectorRecord::new("b".to_string(), vec![1.0], tenant_id)
+                    .unwrap()
+                    .with_expires_at(own_expiry),
+            )
+            .with_expires_at(batch_expiry);
+        operation.apply_expiry();
+
+        assert_eq!(operation.upsert[0].expires_at, Some(batch_expiry));
+        assert_eq!(operation.upsert[1].expires_at, Some(own_expiry));
+    }
+
+    #[test]
+    fn test_record_expiry() {
+        let now = Utc::now();
+        let record = VectorRecord::new("a".to_string(), vec![1.0], TenantId::new()).unwrap();
+        assert!(!record.is_expired(now));
+        assert!(record.clone().with_expires_at(now).is_expired(now));
+        assert!(!record.with_expires_at(now + chrono::Duration::seconds(1)).is_expired(now));
+    }
+}
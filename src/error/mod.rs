@@ -1,11 +1,14 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
 use chrono::Utc;
 
+/// Retry hint sent with `PlatformError::RateLimit` responses
+pub const DEFAULT_RETRY_AFTER_SECONDS: u64 = 60;
+
 pub type Result<T> = std::result::Result<T, PlatformError>;
 
 #[derive(Debug, thiserror::Error)]
@@ -30,6 +33,9 @@ pub enum PlatformError {
     
     #[error("MCP tool error: {0}")]
     MCPToolError(String),
+
+    #[error("Template error: {message} (template: {template})")]
+    TemplateEngineError { template: String, message: String },
     
     #[error("Agent not found: {0}")]
     AgentNotFound(String),
@@ -80,6 +86,21 @@ pub enum PlatformError {
     
     #[error("Internal server error: {0}")]
     InternalError(String),
+
+    #[error("Rate limit exceeded: {0}")]
+    RateLimit(String),
+
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    #[error("Connection test failed: {0}")]
+    ConnectionTestFailed(String),
     
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -96,21 +117,50 @@ impl From<crate::domain::services::llm_service::LLMError> for PlatformError {
         match err {
             crate::domain::services::llm_service::LLMError::InvalidConfiguration(msg) => PlatformError::ValidationError(msg),
             crate::domain::services::llm_service::LLMError::AuthenticationFailed(msg) => PlatformError::AuthenticationFailed(msg),
-            crate::domain::services::llm_service::LLMError::NetworkError(msg) => PlatformError::InternalError(format!("Network error: {}", msg)),
+            crate::domain::services::llm_service::LLMError::RateLimitExceeded(msg) => PlatformError::RateLimit(msg),
+            crate::domain::services::llm_service::LLMError::ContextLengthExceeded(_)
+            | crate::domain::services::llm_service::LLMError::ContentPolicyViolation(_) => PlatformError::ValidationError(err.to_string()),
+            crate::domain::services::llm_service::LLMError::ProviderOverloaded(_) => PlatformError::ServiceUnavailable(err.to_string()),
+            crate::domain::services::llm_service::LLMError::Timeout(msg) => PlatformError::Timeout(msg),
+            crate::domain::services::llm_service::LLMError::NetworkError(msg) => PlatformError::ServiceUnavailable(format!("Network error: {}", msg)),
             _ => PlatformError::InternalError(err.to_string()),
         }
     }
 }
 
+/// 429 response carrying both a `Retry-After` header and a
+/// `retry_after_seconds` field in the JSON body
+pub fn rate_limit_response(message: String, retry_after_seconds: u64) -> Response {
+    let retry_after_seconds = retry_after_seconds.max(1);
+    let body = Json(json!({
+        "error": message,
+        "retry_after_seconds": retry_after_seconds,
+        "timestamp": Utc::now().to_rfc3339()
+    }));
+
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, body).into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(retry_after_seconds),
+    );
+    response
+}
+
 impl IntoResponse for PlatformError {
     fn into_response(self) -> Response {
+        if let PlatformError::RateLimit(_) = self {
+            return rate_limit_response(self.to_string(), DEFAULT_RETRY_AFTER_SECONDS);
+        }
+
         let (status, error_message) = match self {
             PlatformError::AuthenticationFailed(_) => (StatusCode::UNAUTHORIZED, self.to_string()),
             PlatformError::AuthorizationFailed(_) => (StatusCode::FORBIDDEN, self.to_string()),
             PlatformError::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
             PlatformError::ValidationError(_) => (StatusCode::BAD_REQUEST, self.to_string()),
             PlatformError::ConfigurationError(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            PlatformError::TemplateEngineError { .. } => (StatusCode::BAD_REQUEST, self.to_string()),
             PlatformError::Conflict(_) => (StatusCode::CONFLICT, self.to_string()),
+            PlatformError::FlowExecutionFailed(_) => (StatusCode::CONFLICT, self.to_string()),
             PlatformError::AgentNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
             PlatformError::AgentUnauthorized(_) => (StatusCode::FORBIDDEN, self.to_string()),
             PlatformError::AgentValidationError(_) => (StatusCode::BAD_REQUEST, self.to_string()),
@@ -119,6 +169,10 @@ impl IntoResponse for PlatformError {
             PlatformError::AgentAlreadyAllocated(_) => (StatusCode::CONFLICT, self.to_string()),
             PlatformError::AgentNotAllocated(_) => (StatusCode::NOT_FOUND, self.to_string()),
             PlatformError::PresetQuestionsLimitExceeded => (StatusCode::BAD_REQUEST, self.to_string()),
+            PlatformError::QuotaExceeded(_) => (StatusCode::FORBIDDEN, self.to_string()),
+            PlatformError::ServiceUnavailable(_) => (StatusCode::SERVICE_UNAVAILABLE, self.to_string()),
+            PlatformError::Timeout(_) => (StatusCode::GATEWAY_TIMEOUT, self.to_string()),
+            PlatformError::ConnectionTestFailed(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
             // _ => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()),
             // FIXME For debugging only.
             _ => (StatusCode::INTERNAL_SERVER_ERROR, format!("Internal server error: {}", self.to_string())),
@@ -174,6 +228,36 @@ macro_rules! internal_error {
     };
 }
 
+#[macro_export]
+macro_rules! rate_limit_error {
+    ($msg:expr) => {
+        $crate::error::PlatformError::RateLimit($msg.to_string())
+    };
+    ($fmt:expr, $($arg:tt)*) => {
+        $crate::error::PlatformError::RateLimit(format!($fmt, $($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! service_unavailable_error {
+    ($msg:expr) => {
+        $crate::error::PlatformError::ServiceUnavailable($msg.to_string())
+    };
+    ($fmt:expr, $($arg:tt)*) => {
+        $crate::error::PlatformError::ServiceUnavailable(format!($fmt, $($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! timeout_error {
+    ($msg:expr) => {
+        $crate::error::PlatformError::Timeout($msg.to_string())
+    };
+    ($fmt:expr, $($arg:tt)*) => {
+        $crate::error::PlatformError::Timeout(format!($fmt, $($arg)*))
+    };
+}
+
 // Agent-specific error macros
 #[macro_export]
 macro_rules! agent_not_found {
@@ -243,4 +327,80 @@ macro_rules! agent_not_allocated {
     ($fmt:expr, $($arg:tt)*) => {
         $crate::error::PlatformError::AgentNotAllocated(format!($fmt, $($arg)*))
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_maps_to_429_with_retry_after() {
+        let response = rate_limit_error!("too many requests for {}", "tenant").into_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response.headers().get(header::RETRY_AFTER).unwrap(),
+            &DEFAULT_RETRY_AFTER_SECONDS.to_string()
+        );
+        let body = body_json(response).await;
+        assert_eq!(body["retry_after_seconds"], DEFAULT_RETRY_AFTER_SECONDS);
+        assert_eq!(body["error"], "Rate limit exceeded: too many requests for tenant");
+    }
+
+    #[test]
+    fn test_service_unavailable_and_timeout_status_codes() {
+        assert_eq!(
+            service_unavailable_error!("provider down").into_response().status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            timeout_error!("provider slow").into_response().status(),
+            StatusCode::GATEWAY_TIMEOUT
+        );
+    }
+
+    #[test]
+    fn test_connection_test_failed_maps_to_422() {
+        assert_eq!(
+            PlatformError::ConnectionTestFailed("invalid api key".to_string())
+                .into_response()
+                .status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn test_llm_errors_map_to_specific_variants() {
+        use crate::domain::services::llm_service::LLMError;
+
+        assert!(matches!(
+            PlatformError::from(LLMError::RateLimitExceeded("quota".to_string())),
+            PlatformError::RateLimit(_)
+        ));
+        assert!(matches!(
+            PlatformError::from(LLMError::Timeout("chat completion".to_string())),
+            PlatformError::Timeout(_)
+        ));
+        assert!(matches!(
+            PlatformError::from(LLMError::NetworkError("Request timeout".to_string())),
+            PlatformError::ServiceUnavailable(_)
+        ));
+        assert!(matches!(
+            PlatformError::from(LLMError::NetworkError("Connection error".to_string())),
+            PlatformError::ServiceUnavailable(_)
+        ));
+        assert!(matches!(
+            PlatformError::from(LLMError::ProviderOverloaded("busy".to_string())),
+            PlatformError::ServiceUnavailable(_)
+        ));
+        assert!(matches!(
+            PlatformError::from(LLMError::ContextLengthExceeded("too long".to_string())),
+            PlatformError::ValidationError(_)
+        ));
+    }
+}
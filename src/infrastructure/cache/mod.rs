@@ -1,141 +1,466 @@
-use redis::Client;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use deadpool_redis::{Config, Connection, Pool, PoolConfig, Runtime};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Duration;
-use crate::error::Result;
+use crate::domain::entities::APIKeyUsageDelta;
+use crate::domain::services::{
+    llm_service::ChatResponse, APIKeyUsageCounter, ChatStreamBuffer, ChatStreamEvent, PromptCache,
+    CHAT_STREAM_BUFFER_SIZE,
+};
+use crate::domain::value_objects::{APIKeyId, SessionId};
+use crate::error::{PlatformError, Result};
 use uuid::Uuid;
 
+/// Default number of pooled Redis connections
+pub const DEFAULT_POOL_SIZE: usize = 16;
+
+/// Number of keys fetched per SCAN round trip during pattern invalidation
+const SCAN_BATCH_SIZE: usize = 500;
+
+/// Token bucket kept in a Redis hash. The bucket refills continuously, so the
+/// limit behaves like a sliding window rather than resetting on a fixed tick.
+///
+/// KEYS[1]: bucket key
+/// ARGV[1]: capacity (burst size)
+/// ARGV[2]: refill rate in tokens per millisecond
+///
+/// Returns `{allowed, retry_after_ms}`.
+pub const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_per_ms = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1])
+local ts = tonumber(bucket[2])
+if tokens == nil or ts == nil then
+    tokens = capacity
+    ts = now
+end
+
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * refill_per_ms)
+
+local allowed = 0
+local retry_after = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_after = math.ceil((1 - tokens) / refill_per_ms)
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity / refill_per_ms))
+
+return {allowed, retry_after}
+"#;
+
+/// Set of API key IDs with usage not yet drained
+const API_KEY_USAGE_PENDING_KEY: &str = "api_key_usage:pending";
+
+/// API key IDs popped per round trip while draining usage
+const USAGE_DRAIN_BATCH_SIZE: usize = 500;
+
+/// Read and delete a usage hash in one step, so no increment is lost between the two
+const TAKE_HASH_SCRIPT: &str = r#"
+local fields = redis.call('HGETALL', KEYS[1])
+redis.call('DEL', KEYS[1])
+return fields
+"#;
+
+/// How long a chat stream stays resumable after its last event
+const CHAT_STREAM_TTL: Duration = Duration::from_secs(600);
+
 pub struct RedisCache {
+    pool: Pool,
 }
 
 impl RedisCache {
     pub async fn new(redis_url: &str) -> Result<Self> {
-        Ok(RedisCache {})
+        Self::with_pool_size(redis_url, DEFAULT_POOL_SIZE).await
+    }
+
+    pub async fn with_pool_size(redis_url: &str, pool_size: usize) -> Result<Self> {
+        let mut config = Config::from_url(redis_url);
+        config.pool = Some(PoolConfig::new(pool_size));
+
+        let pool = config
+            .create_pool(Some(Runtime::Tokio1))
+            .map_err(|e| PlatformError::ConfigurationError(format!("Failed to create Redis pool: {}", e)))?;
+
+        let cache = RedisCache { pool };
+
+        // Test connection
+        let mut conn = cache.connection().await?;
+        redis::cmd("PING").query_async::<_, String>(&mut conn).await?;
+
+        Ok(cache)
+    }
+
+    async fn connection(&self) -> Result<Connection> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("Failed to get Redis connection: {}", e)))
+    }
+
+    pub async fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let mut conn = self.connection().await?;
+        let value: Option<String> = redis::cmd("GET")
+            .arg(key)
+            .query_async(&mut conn)
+            .await?;
+
+        match value {
+            Some(json_str) => {
+                let deserialized = serde_json::from_str(&json_str)?;
+                Ok(Some(deserialized))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let mut conn = self.connection().await?;
+        let json_str = serde_json::to_string(value)?;
+
+        match ttl {
+            Some(duration) => {
+                redis::cmd("SETEX")
+                    .arg(key)
+                    .arg(duration.as_secs())
+                    .arg(json_str)
+                    .query_async::<_, ()>(&mut conn)
+                    .await?;
+            }
+            None => {
+                redis::cmd("SET")
+                    .arg(key)
+                    .arg(json_str)
+                    .query_async::<_, ()>(&mut conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<()> {
+        let mut conn = self.connection().await?;
+        redis::cmd("DEL")
+            .arg(key)
+            .query_async::<_, ()>(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    // Requirement 2.3, 6.3, 7.3: Cache invalidation patterns
+    // Uses SCAN rather than KEYS so large keyspaces don't block the server
+    pub async fn invalidate_pattern(&self, pattern: &str) -> Result<()> {
+        let mut conn = self.connection().await?;
+        let mut cursor: u64 = 0;
+
+        loop {
+            let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(SCAN_BATCH_SIZE)
+                .query_async(&mut conn)
+                .await?;
+
+            if !keys.is_empty() {
+                redis::cmd("DEL")
+                    .arg(&keys)
+                    .query_async::<_, ()>(&mut conn)
+                    .await?;
+            }
+
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+
+        Ok(())
+    }
+
+    pub async fn exists(&self, key: &str) -> Result<bool> {
+        let mut conn = self.connection().await?;
+        let exists: bool = redis::cmd("EXISTS")
+            .arg(key)
+            .query_async(&mut conn)
+            .await?;
+        Ok(exists)
+    }
+
+    /// Round trip a PING through the pool, for health checks
+    pub async fn ping(&self) -> Result<()> {
+        let mut conn = self.connection().await?;
+        redis::cmd("PING").query_async::<_, String>(&mut conn).await?;
+        Ok(())
+    }
+
+    /// Run a Lua script atomically, loading it on the server if needed
+    pub async fn invoke_script<T>(&self, invocation: &redis::ScriptInvocation<'_>) -> Result<T>
+    where
+        T: redis::FromRedisValue,
+    {
+        let mut conn = self.connection().await?;
+        Ok(invocation.invoke_async(&mut conn).await?)
+    }
+
+    // Cache key builders for consistent naming
+    pub fn flow_key(tenant_id: &Uuid, flow_id: &Uuid) -> String {
+        format!("flow:{}:{}", tenant_id, flow_id)
+    }
+
+    pub fn flow_list_key(tenant_id: &Uuid, page: u64, limit: u64) -> String {
+        format!("flows:{}:{}:{}", tenant_id, page, limit)
+    }
+
+    pub fn llm_config_key(tenant_id: &Uuid, config_id: &Uuid) -> String {
+        format!("llm_config:{}:{}", tenant_id, config_id)
+    }
+
+    pub fn vector_config_key(tenant_id: &Uuid, config_id: &Uuid) -> String {
+        format!("vector_config:{}:{}", tenant_id, config_id)
+    }
+
+    pub fn session_key(session_id: &Uuid) -> String {
+        format!("session:{}", session_id)
+    }
+
+    pub fn mcp_tool_key(tenant_id: &Uuid, tool_id: &Uuid) -> String {
+        format!("mcp_tool:{}:{}", tenant_id, tool_id)
+    }
+
+    pub fn llm_cache_key(prompt_hash: &str) -> String {
+        format!("llm_cache:{}", prompt_hash)
+    }
+
+    pub fn api_key_usage_key(api_key_id: &Uuid) -> String {
+        format!("api_key_usage:{}", api_key_id)
+    }
+
+    pub fn api_key_rate_limit_key(api_key_id: &Uuid) -> String {
+        format!("rate_limit:api_key:{}", api_key_id)
+    }
+
+    pub fn chat_stream_key(session_id: &Uuid) -> String {
+        format!("chat_stream:{}", session_id)
+    }
+
+    /// Turn a drained usage hash (`requests:<day>`, `tokens:<day>` and
+    /// `last_used` in epoch milliseconds) into one delta per day
+    fn parse_api_key_usage(api_key_id: APIKeyId, fields: Vec<String>) -> Vec<APIKeyUsageDelta> {
+        let mut last_used_at = Utc::now();
+        let mut days: BTreeMap<NaiveDate, (u64, u64)> = BTreeMap::new();
+
+        for pair in fields.chunks_exact(2) {
+            let (field, value) = (&pair[0], &pair[1]);
+            if field == "last_used" {
+                if let Some(at) = value.parse().ok().and_then(|ms| Utc.timestamp_millis_opt(ms).single()) {
+                    last_used_at = at;
+                }
+                continue;
+            }
+
+            let Some((kind, day)) = field.split_once(':') else { continue };
+            let (Ok(date), Ok(count)) = (NaiveDate::parse_from_str(day, "%Y-%m-%d"), value.parse::<u64>()) else {
+                continue;
+            };
+            let entry = days.entry(date).or_default();
+            match kind {
+                "requests" => entry.0 += count,
+                "tokens" => entry.1 += count,
+                _ => {}
+            }
+        }
+
+        days.into_iter()
+            .map(|(date, (request_count, tokens_used))| APIKeyUsageDelta {
+                api_key_id,
+                date,
+                request_count,
+                tokens_used,
+                last_used_at,
+            })
+            .collect()
     }
 }
 
-// pub struct RedisCache {
-//     client: Client,
-// }
+#[async_trait]
+impl PromptCache for RedisCache {
+    async fn get(&self, prompt_hash: &str) -> Result<Option<ChatResponse>> {
+        RedisCache::get(self, &Self::llm_cache_key(prompt_hash)).await
+    }
 
-// impl RedisCache {
-//     pub async fn new(redis_url: &str) -> Result<Self> {
-//         let client = Client::open(redis_url)?;
-        
-//         // Test connection
-//         let mut conn = client.get_async_connection().await?;
-//         redis::cmd("PING").query_async::<_, String>(&mut conn).await?;
-        
-//         Ok(RedisCache { client })
-//     }
-    
-//     pub async fn get<T>(&self, key: &str) -> Result<Option<T>>
-//     where
-//         T: for<'de> Deserialize<'de>,
-//     {
-//         let mut conn = self.client.get_async_connection().await?;
-//         let value: Option<String> = redis::cmd("GET")
-//             .arg(key)
-//             .query_async(&mut conn)
-//             .await?;
-            
-//         match value {
-//             Some(json_str) => {
-//                 let deserialized = serde_json::from_str(&json_str)?;
-//                 Ok(Some(deserialized))
-//             }
-//             None => Ok(None),
-//         }
-//     }
-    
-//     pub async fn set<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<()>
-//     where
-//         T: Serialize,
-//     {
-//         let mut conn = self.client.get_async_connection().await?;
-//         let json_str = serde_json::to_string(value)?;
-        
-//         match ttl {
-//             Some(duration) => {
-//                 redis::cmd("SETEX")
-//                     .arg(key)
-//                     .arg(duration.as_secs())
-//                     .arg(json_str)
-//                     .query_async::<_, ()>(&mut conn)
-//                     .await?;
-//             }
-//             None => {
-//                 redis::cmd("SET")
-//                     .arg(key)
-//                     .arg(json_str)
-//                     .query_async::<_, ()>(&mut conn)
-//                     .await?;
-//             }
-//         }
-        
-//         Ok(())
-//     }
-    
-//     pub async fn delete(&self, key: &str) -> Result<()> {
-//         let mut conn = self.client.get_async_connection().await?;
-//         redis::cmd("DEL")
-//             .arg(key)
-//             .query_async::<_, ()>(&mut conn)
-//             .await?;
-//         Ok(())
-//     }
-
-//     // Requirement 2.3, 6.3, 7.3: Cache invalidation patterns
-//     pub async fn invalidate_pattern(&self, pattern: &str) -> Result<()> {
-//         let mut conn = self.client.get_async_connection().await?;
-//         let keys: Vec<String> = redis::cmd("KEYS")
-//             .arg(pattern)
-//             .query_async(&mut conn)
-//             .await?;
-        
-//         if !keys.is_empty() {
-//             redis::cmd("DEL")
-//                 .arg(&keys)
-//                 .query_async::<_, ()>(&mut conn)
-//                 .await?;
-//         }
-        
-//         Ok(())
-//     }
-
-//     pub async fn exists(&self, key: &str) -> Result<bool> {
-//         let mut conn = self.client.get_async_connection().await?;
-//         let exists: bool = redis::cmd("EXISTS")
-//             .arg(key)
-//             .query_async(&mut conn)
-//             .await?;
-//         Ok(exists)
-//     }
-
-//     // Cache key builders for consistent naming
-//     pub fn flow_key(tenant_id: &Uuid, flow_id: &Uuid) -> String {
-//         format!("flow:{}:{}", tenant_id, flow_id)
-//     }
-
-//     pub fn flow_list_key(tenant_id: &Uuid, page: u64, limit: u64) -> String {
-//         format!("flows:{}:{}:{}", tenant_id, page, limit)
-//     }
-
-//     pub fn llm_config_key(tenant_id: &Uuid, config_id: &Uuid) -> String {
-//         format!("llm_config:{}:{}", tenant_id, config_id)
-//     }
-
-//     pub fn vector_config_key(tenant_id: &Uuid, config_id: &Uuid) -> String {
-//         format!("vector_config:{}:{}", tenant_id, config_id)
-//     }
-
-//     pub fn session_key(session_id: &Uuid) -> String {
-//         format!("session:{}", session_id)
-//     }
-
-//     pub fn mcp_tool_key(tenant_id: &Uuid, tool_id: &Uuid) -> String {
-//         format!("mcp_tool:{}:{}", tenant_id, tool_id)
-//     }
-// }
+    async fn set(&self, prompt_hash: &str, response: &ChatResponse, ttl: Duration) -> Result<()> {
+        RedisCache::set(self, &Self::llm_cache_key(prompt_hash), response, Some(ttl)).await
+    }
+}
+
+#[async_trait]
+impl APIKeyUsageCounter for RedisCache {
+    async fn record(&self, api_key_id: APIKeyId, tokens_used: u64, at: DateTime<Utc>) -> Result<()> {
+        let key = Self::api_key_usage_key(&api_key_id.0);
+        let day = at.format("%Y-%m-%d");
+
+        let mut conn = self.connection().await?;
+        redis::pipe()
+            .atomic()
+            .cmd("HINCRBY").arg(&key).arg(format!("requests:{}", day)).arg(1).ignore()
+            .cmd("HINCRBY").arg(&key).arg(format!("tokens:{}", day)).arg(tokens_used).ignore()
+            .cmd("HSET").arg(&key).arg("last_used").arg(at.timestamp_millis()).ignore()
+            .cmd("SADD").arg(API_KEY_USAGE_PENDING_KEY).arg(api_key_id.0.to_string()).ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn acquire(&self, api_key_id: APIKeyId, requests_per_minute: u32) -> Result<Option<Duration>> {
+        let capacity = requests_per_minute.max(1);
+        let refill_per_ms = capacity as f64 / 60_000.0;
+
+        let script = redis::Script::new(TOKEN_BUCKET_SCRIPT);
+        let (allowed, retry_after_ms): (i64, i64) = self
+            .invoke_script(
+                script
+                    .key(Self::api_key_rate_limit_key(&api_key_id.0))
+                    .arg(capacity)
+                    .arg(refill_per_ms.to_string()),
+            )
+            .await?;
+
+        Ok((allowed != 1).then(|| Duration::from_millis(retry_after_ms.max(0) as u64)))
+    }
+
+    async fn drain(&self) -> Result<Vec<APIKeyUsageDelta>> {
+        let mut usage = Vec::new();
+        if let Err(e) = self.drain_api_key_usage(&mut usage).await {
+            // Hashes already taken exist only in `usage` now, so hand them
+            // over instead of dropping them with the error
+            if usage.is_empty() {
+                return Err(e);
+            }
+            tracing::warn!("API key usage drain stopped early: {}", e);
+        }
+
+        Ok(usage)
+    }
+
+    async fn restore(&self, usage: &[APIKeyUsageDelta]) -> Result<()> {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for delta in usage {
+            let key = Self::api_key_usage_key(&delta.api_key_id.0);
+            let day = delta.date.format("%Y-%m-%d");
+            pipe.cmd("HINCRBY").arg(&key).arg(format!("requests:{}", day)).arg(delta.request_count).ignore()
+                .cmd("HINCRBY").arg(&key).arg(format!("tokens:{}", day)).arg(delta.tokens_used).ignore()
+                // A request recorded since the drain has the newer timestamp
+                .cmd("HSETNX").arg(&key).arg("last_used").arg(delta.last_used_at.timestamp_millis()).ignore()
+                .cmd("SADD").arg(API_KEY_USAGE_PENDING_KEY).arg(delta.api_key_id.0.to_string()).ignore();
+        }
+
+        let mut conn = self.connection().await?;
+        pipe.query_async::<_, ()>(&mut conn).await?;
+        Ok(())
+    }
+}
+
+impl RedisCache {
+    /// Take pending usage hashes into `usage` until none are left
+    async fn drain_api_key_usage(&self, usage: &mut Vec<APIKeyUsageDelta>) -> Result<()> {
+        let script = redis::Script::new(TAKE_HASH_SCRIPT);
+
+        let mut conn = self.connection().await?;
+        loop {
+            let ids: Vec<String> = redis::cmd("SPOP")
+                .arg(API_KEY_USAGE_PENDING_KEY)
+                .arg(USAGE_DRAIN_BATCH_SIZE)
+                .query_async(&mut conn)
+                .await?;
+            if ids.is_empty() {
+                break;
+            }
+
+            for (i, id) in ids.iter().enumerate() {
+                let Ok(api_key_id) = Uuid::parse_str(id) else { continue };
+                // A key recorded again after SPOP is back in the pending set;
+                // its hash is empty by the next drain and yields nothing
+                let fields: Vec<String> = match script
+                    .key(Self::api_key_usage_key(&api_key_id))
+                    .invoke_async(&mut conn)
+                    .await
+                {
+                    Ok(fields) => fields,
+                    Err(e) => {
+                        // Popped keys not taken yet go back to the pending set
+                        let _: redis::RedisResult<()> = redis::cmd("SADD")
+                            .arg(API_KEY_USAGE_PENDING_KEY)
+                            .arg(&ids[i..])
+                            .query_async(&mut conn)
+                            .await;
+                        return Err(e.into());
+                    }
+                };
+                usage.extend(Self::parse_api_key_usage(APIKeyId(api_key_id), fields));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ChatStreamBuffer for RedisCache {
+    async fn append(&self, session_id: SessionId, event: ChatStreamEvent) -> Result<()> {
+        let key = Self::chat_stream_key(&session_id.0);
+        let json_str = serde_json::to_string(&event)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        if event.id <= 1 {
+            pipe.cmd("DEL").arg(&key).ignore();
+        }
+        pipe.cmd("RPUSH").arg(&key).arg(json_str).ignore()
+            .cmd("LTRIM").arg(&key).arg(-(CHAT_STREAM_BUFFER_SIZE as i64)).arg(-1).ignore()
+            .cmd("EXPIRE").arg(&key).arg(CHAT_STREAM_TTL.as_secs()).ignore();
+
+        let mut conn = self.connection().await?;
+        pipe.query_async::<_, ()>(&mut conn).await?;
+
+        Ok(())
+    }
+
+    async fn replay(&self, session_id: SessionId, after_id: u64) -> Result<Vec<ChatStreamEvent>> {
+        let mut conn = self.connection().await?;
+        let entries: Vec<String> = redis::cmd("LRANGE")
+            .arg(Self::chat_stream_key(&session_id.0))
+            .arg(0)
+            .arg(-1)
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(entries
+            .iter()
+            .filter_map(|entry| serde_json::from_str::<ChatStreamEvent>(entry).ok())
+            .filter(|event| event.id > after_id)
+            .collect())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -174,6 +499,58 @@ mod tests {
         assert_eq!(retrieved, None);
     }
 
+    #[test]
+    fn test_key_builders() {
+        let tenant_id = Uuid::new_v4();
+        let id = Uuid::new_v4();
+
+        assert_eq!(RedisCache::flow_key(&tenant_id, &id), format!("flow:{}:{}", tenant_id, id));
+        assert_eq!(RedisCache::flow_list_key(&tenant_id, 2, 20), format!("flows:{}:2:20", tenant_id));
+        assert_eq!(RedisCache::llm_config_key(&tenant_id, &id), format!("llm_config:{}:{}", tenant_id, id));
+        assert_eq!(RedisCache::session_key(&id), format!("session:{}", id));
+        assert_eq!(RedisCache::mcp_tool_key(&tenant_id, &id), format!("mcp_tool:{}:{}", tenant_id, id));
+        assert_eq!(RedisCache::api_key_usage_key(&id), format!("api_key_usage:{}", id));
+        assert_eq!(RedisCache::chat_stream_key(&id), format!("chat_stream:{}", id));
+    }
+
+    #[test]
+    fn test_parse_api_key_usage() {
+        let api_key_id = APIKeyId(Uuid::new_v4());
+        let fields = [
+            "requests:2024-12-16", "3",
+            "tokens:2024-12-16", "120",
+            "requests:2024-12-17", "1",
+            "last_used", "1734393600000",
+            "unknown", "7",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let usage = RedisCache::parse_api_key_usage(api_key_id, fields);
+        let last_used_at = Utc.timestamp_millis_opt(1734393600000).unwrap();
+
+        assert_eq!(
+            usage,
+            vec![
+                APIKeyUsageDelta {
+                    api_key_id,
+                    date: NaiveDate::from_ymd_opt(2024, 12, 16).unwrap(),
+                    request_count: 3,
+                    tokens_used: 120,
+                    last_used_at,
+                },
+                APIKeyUsageDelta {
+                    api_key_id,
+                    date: NaiveDate::from_ymd_opt(2024, 12, 17).unwrap(),
+                    request_count: 1,
+                    tokens_used: 0,
+                    last_used_at,
+                },
+            ]
+        );
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_pattern_invalidation() {
@@ -12,6 +12,7 @@ pub struct Model {
     pub avatar: Option<String>,
     pub greeting: Option<String>,
     pub llm_config_id: Option<Uuid>,
+    pub llm_fallback_strategy: Option<Json>,
     pub knowledge_base_ids: Json,
     pub mcp_tool_ids: Json,
     pub flow_ids: Json,
@@ -16,6 +16,9 @@ pub struct Model {
     pub enabled: bool,
     pub expires_at: Option<DateTime<Utc>>,
     pub last_used_at: Option<DateTime<Utc>>,
+    pub request_count: u64,
+    pub total_tokens_used: u64,
+    pub rate_limit_rpm: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
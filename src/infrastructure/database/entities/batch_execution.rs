@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "batch_executions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub flow_id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub total: i32,
+    pub succeeded: i32,
+    pub failed: i32,
+    pub concurrency: i32,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::flow::Entity",
+        from = "Column::FlowId",
+        to = "super::flow::Column::Id"
+    )]
+    Flow,
+}
+
+impl Related<super::flow::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Flow.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -23,6 +23,7 @@ pub struct Model {
     pub content: String,
     pub metadata: Option<Json>,
     pub created_at: DateTime<Utc>,
+    pub superseded_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -11,6 +11,8 @@ pub struct Model {
     pub user_id: Uuid,
     pub title: Option<String>,
     pub context: Option<Json>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub summary: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -23,6 +23,10 @@ pub struct Model {
     pub description: Option<String>,
     pub current_version: i32,
     pub status: FlowStatus,
+    pub timeout_ms: Option<i64>,
+    pub is_published: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub is_enabled: bool,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -34,6 +34,7 @@ pub struct Model {
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub execution_time_ms: Option<i32>,
+    pub correlation_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One node of a flow's current version, with the configs it calls
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "flow_node_configs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub flow_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub node_id: String,
+    pub tenant_id: Uuid,
+    pub node_type: String,
+    pub llm_config_id: Option<Uuid>,
+    pub mcp_tool_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::flow::Entity",
+        from = "Column::FlowId",
+        to = "super::flow::Column::Id"
+    )]
+    Flow,
+}
+
+impl Related<super::flow::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Flow.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -16,7 +16,19 @@ pub mod agent;
 pub mod agent_allocation;
 pub mod agent_daily_stats;
 pub mod interview_record;
+pub mod flow_node_annotation;
 pub mod api_key;
+pub mod domain_event;
+pub mod domain_event_snapshot;
+pub mod llm_usage_log;
+pub mod tenant_settings;
+pub mod refresh_token;
+pub mod batch_execution;
+pub mod message_branch;
+pub mod tenant_secret;
+pub mod vector_record_metadata;
+pub mod api_key_usage_daily;
+pub mod flow_node_config;
 
 pub use tenant::Entity as Tenant;
 pub use user::Entity as User;
@@ -36,4 +48,16 @@ pub use agent::Entity as Agent;
 pub use agent_allocation::Entity as AgentAllocation;
 pub use agent_daily_stats::Entity as AgentDailyStats;
 pub use interview_record::Entity as InterviewRecord;
-pub use api_key::Entity as ApiKey;
\ No newline at end of file
+pub use flow_node_annotation::Entity as FlowNodeAnnotation;
+pub use api_key::Entity as ApiKey;
+pub use domain_event::Entity as DomainEvent;
+pub use domain_event_snapshot::Entity as DomainEventSnapshot;
+pub use llm_usage_log::Entity as LlmUsageLog;
+pub use tenant_settings::Entity as TenantSettings;
+pub use refresh_token::Entity as RefreshToken;
+pub use batch_execution::Entity as BatchExecution;
+pub use message_branch::Entity as MessageBranch;
+pub use tenant_secret::Entity as TenantSecret;
+pub use vector_record_metadata::Entity as VectorRecordMetadata;
+pub use api_key_usage_daily::Entity as ApiKeyUsageDaily;
+pub use flow_node_config::Entity as FlowNodeConfig;
@@ -10,6 +10,9 @@ pub struct Model {
     pub tenant_id: Uuid,
     pub username: String,
     pub nickname: Option<String>,
+    pub email: Option<String>,
+    pub language: Option<String>,
+    pub avatar_url: Option<String>,
     pub password_hash: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
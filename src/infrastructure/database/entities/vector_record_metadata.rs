@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "vector_record_metadata")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub config_id: Uuid,
+    /// Empty for records outside any namespace
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub namespace: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub record_id: String,
+    pub tenant_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::vector_config::Entity",
+        from = "Column::ConfigId",
+        to = "super::vector_config::Column::Id"
+    )]
+    VectorConfig,
+}
+
+impl Related<super::vector_config::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VectorConfig.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Add correlation_id column linking executions to audit events
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FlowExecutions::Table)
+                    .add_column(
+                        ColumnDef::new(FlowExecutions::CorrelationId)
+                            .binary_len(16)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_flow_executions_correlation_id")
+                    .table(FlowExecutions::Table)
+                    .col(FlowExecutions::CorrelationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_flow_executions_correlation_id")
+                    .table(FlowExecutions::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FlowExecutions::Table)
+                    .drop_column(FlowExecutions::CorrelationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum FlowExecutions {
+    Table,
+    CorrelationId,
+}
@@ -0,0 +1,92 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FlowNodeAnnotations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FlowNodeAnnotations::Id)
+                            .binary_len(16)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FlowNodeAnnotations::FlowId).binary_len(16).not_null())
+                    .col(ColumnDef::new(FlowNodeAnnotations::NodeId).string_len(255).not_null())
+                    .col(ColumnDef::new(FlowNodeAnnotations::Version).integer().not_null())
+                    .col(ColumnDef::new(FlowNodeAnnotations::AuthorId).binary_len(16).not_null())
+                    .col(ColumnDef::new(FlowNodeAnnotations::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(FlowNodeAnnotations::Resolved)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(FlowNodeAnnotations::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_flow_node_annotations_flow")
+                            .from(FlowNodeAnnotations::Table, FlowNodeAnnotations::FlowId)
+                            .to(Flows::Table, Flows::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_flow_node_annotations_author")
+                            .from(FlowNodeAnnotations::Table, FlowNodeAnnotations::AuthorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_flow_node_annotations_flow_node")
+                            .col(FlowNodeAnnotations::FlowId)
+                            .col(FlowNodeAnnotations::NodeId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FlowNodeAnnotations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum FlowNodeAnnotations {
+    Table,
+    Id,
+    FlowId,
+    NodeId,
+    Version,
+    AuthorId,
+    Content,
+    Resolved,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Flows {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
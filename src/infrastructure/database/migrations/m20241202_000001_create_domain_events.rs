@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DomainEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DomainEvents::Id)
+                            .binary_len(16)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(DomainEvents::AggregateId).binary_len(16).not_null())
+                    .col(ColumnDef::new(DomainEvents::EventType).string_len(100).not_null())
+                    .col(ColumnDef::new(DomainEvents::Version).big_integer().not_null())
+                    .col(ColumnDef::new(DomainEvents::Payload).json().not_null())
+                    .col(ColumnDef::new(DomainEvents::CorrelationId).binary_len(16))
+                    .col(
+                        ColumnDef::new(DomainEvents::OccurredAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DomainEvents::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One event per aggregate version; also serves replay queries
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_domain_events_aggregate_version")
+                    .table(DomainEvents::Table)
+                    .col(DomainEvents::AggregateId)
+                    .col(DomainEvents::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(DomainEventSnapshots::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DomainEventSnapshots::AggregateId)
+                            .binary_len(16)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(DomainEventSnapshots::Version).big_integer().not_null())
+                    .col(ColumnDef::new(DomainEventSnapshots::State).json().not_null())
+                    .col(
+                        ColumnDef::new(DomainEventSnapshots::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DomainEventSnapshots::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(DomainEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum DomainEvents {
+    Table,
+    Id,
+    AggregateId,
+    EventType,
+    Version,
+    Payload,
+    CorrelationId,
+    OccurredAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum DomainEventSnapshots {
+    Table,
+    AggregateId,
+    Version,
+    State,
+    CreatedAt,
+}
@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LlmUsageLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LlmUsageLog::Id)
+                            .binary_len(16)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LlmUsageLog::TenantId).binary_len(16).not_null())
+                    .col(ColumnDef::new(LlmUsageLog::AgentId).binary_len(16).not_null())
+                    .col(ColumnDef::new(LlmUsageLog::UserId).binary_len(16).not_null())
+                    .col(ColumnDef::new(LlmUsageLog::SessionId).binary_len(16))
+                    .col(ColumnDef::new(LlmUsageLog::Model).string_len(255).not_null())
+                    .col(ColumnDef::new(LlmUsageLog::PromptTokens).big_integer().not_null())
+                    .col(ColumnDef::new(LlmUsageLog::CompletionTokens).big_integer().not_null())
+                    .col(ColumnDef::new(LlmUsageLog::TotalTokens).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(LlmUsageLog::Estimated)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(LlmUsageLog::Cost)
+                            .decimal_len(20, 8)
+                            .not_null()
+                            .default(0.0),
+                    )
+                    .col(ColumnDef::new(LlmUsageLog::UsageDate).date().not_null())
+                    .col(
+                        ColumnDef::new(LlmUsageLog::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_llm_usage_log_agent")
+                            .from(LlmUsageLog::Table, LlmUsageLog::AgentId)
+                            .to(Agents::Table, Agents::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_llm_usage_log_tenant")
+                            .from(LlmUsageLog::Table, LlmUsageLog::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_llm_usage_log_agent_date")
+                            .col(LlmUsageLog::AgentId)
+                            .col(LlmUsageLog::UsageDate),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_llm_usage_log_tenant_date")
+                            .col(LlmUsageLog::TenantId)
+                            .col(LlmUsageLog::UsageDate),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LlmUsageLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum LlmUsageLog {
+    Table,
+    Id,
+    TenantId,
+    AgentId,
+    UserId,
+    SessionId,
+    Model,
+    PromptTokens,
+    CompletionTokens,
+    TotalTokens,
+    Estimated,
+    Cost,
+    UsageDate,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Agents {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Tenants {
+    Table,
+    Id,
+}
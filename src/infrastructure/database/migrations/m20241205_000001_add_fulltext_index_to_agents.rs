@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves keyword search over agent names and system prompts
+        manager
+            .create_index(
+                Index::create()
+                    .name("ft_agents_name_system_prompt")
+                    .table(Agents::Table)
+                    .col(Agents::Name)
+                    .col(Agents::SystemPrompt)
+                    .full_text()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Serves the cross-tenant marketplace search, which must not look
+        // into other tenants' system prompts
+        manager
+            .create_index(
+                Index::create()
+                    .name("ft_agents_name_greeting")
+                    .table(Agents::Table)
+                    .col(Agents::Name)
+                    .col(Agents::Greeting)
+                    .full_text()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("ft_agents_name_greeting")
+                    .table(Agents::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("ft_agents_name_system_prompt")
+                    .table(Agents::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Agents {
+    Table,
+    Name,
+    SystemPrompt,
+    Greeting,
+}
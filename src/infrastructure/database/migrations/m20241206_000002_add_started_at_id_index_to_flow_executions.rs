@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves cursor pagination of a tenant's executions, newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_flow_executions_tenant_started_at_id")
+                    .table(FlowExecutions::Table)
+                    .col(FlowExecutions::TenantId)
+                    .col(FlowExecutions::StartedAt)
+                    .col(FlowExecutions::Id)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_flow_executions_tenant_started_at_id")
+                    .table(FlowExecutions::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum FlowExecutions {
+    Table,
+    Id,
+    TenantId,
+    StartedAt,
+}
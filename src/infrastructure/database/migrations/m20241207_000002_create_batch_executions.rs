@@ -0,0 +1,77 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BatchExecutions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BatchExecutions::Id)
+                            .binary_len(16)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BatchExecutions::FlowId).binary_len(16).not_null())
+                    .col(ColumnDef::new(BatchExecutions::TenantId).binary_len(16).not_null())
+                    .col(ColumnDef::new(BatchExecutions::UserId).binary_len(16).not_null())
+                    .col(ColumnDef::new(BatchExecutions::Total).integer().not_null())
+                    .col(ColumnDef::new(BatchExecutions::Succeeded).integer().not_null())
+                    .col(ColumnDef::new(BatchExecutions::Failed).integer().not_null())
+                    .col(ColumnDef::new(BatchExecutions::Concurrency).integer().not_null())
+                    .col(
+                        ColumnDef::new(BatchExecutions::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(BatchExecutions::CompletedAt).timestamp_with_time_zone())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_batch_execution_flow")
+                            .from(BatchExecutions::Table, BatchExecutions::FlowId)
+                            .to(Flows::Table, Flows::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_batch_executions_tenant_flow")
+                            .col(BatchExecutions::TenantId)
+                            .col(BatchExecutions::FlowId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BatchExecutions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum BatchExecutions {
+    Table,
+    Id,
+    FlowId,
+    TenantId,
+    UserId,
+    Total,
+    Succeeded,
+    Failed,
+    Concurrency,
+    StartedAt,
+    CompletedAt,
+}
+
+#[derive(Iden)]
+enum Flows {
+    Table,
+    Id,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AgentAllocations::Table)
+                    .add_column(
+                        ColumnDef::new(AgentAllocations::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Used by the cleanup job to find expired allocations
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_agent_allocations_expires_at")
+                    .table(AgentAllocations::Table)
+                    .col(AgentAllocations::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_agent_allocations_expires_at")
+                    .table(AgentAllocations::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AgentAllocations::Table)
+                    .drop_column(AgentAllocations::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum AgentAllocations {
+    Table,
+    ExpiresAt,
+}
@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ChatMessages::Table)
+                    .add_column(ColumnDef::new(ChatMessages::SupersededAt).timestamp_with_time_zone().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageBranches::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MessageBranches::Id)
+                            .binary_len(16)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MessageBranches::SessionId).binary_len(16).not_null())
+                    .col(ColumnDef::new(MessageBranches::OriginalMessageId).binary_len(16).not_null())
+                    .col(ColumnDef::new(MessageBranches::EditedMessageId).binary_len(16).not_null())
+                    .col(ColumnDef::new(MessageBranches::ResponseMessageId).binary_len(16))
+                    .col(ColumnDef::new(MessageBranches::CreatedBy).binary_len(16).not_null())
+                    .col(
+                        ColumnDef::new(MessageBranches::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_branch_original")
+                            .from(MessageBranches::Table, MessageBranches::OriginalMessageId)
+                            .to(ChatMessages::Table, ChatMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_branch_edited")
+                            .from(MessageBranches::Table, MessageBranches::EditedMessageId)
+                            .to(ChatMessages::Table, ChatMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_message_branches_original")
+                            .col(MessageBranches::OriginalMessageId),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_message_branches_edited")
+                            .col(MessageBranches::EditedMessageId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageBranches::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ChatMessages::Table)
+                    .drop_column(ChatMessages::SupersededAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum MessageBranches {
+    Table,
+    Id,
+    SessionId,
+    OriginalMessageId,
+    EditedMessageId,
+    ResponseMessageId,
+    CreatedBy,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum ChatMessages {
+    Table,
+    Id,
+    SupersededAt,
+}
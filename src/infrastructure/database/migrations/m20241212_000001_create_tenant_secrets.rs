@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TenantSecrets::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(TenantSecrets::TenantId).binary_len(16).not_null())
+                    .col(ColumnDef::new(TenantSecrets::SecretKey).string_len(128).not_null())
+                    .col(ColumnDef::new(TenantSecrets::Ciphertext).text().not_null())
+                    .col(
+                        ColumnDef::new(TenantSecrets::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantSecrets::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .name("pk_tenant_secrets")
+                            .col(TenantSecrets::TenantId)
+                            .col(TenantSecrets::SecretKey),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tenant_secret_tenant")
+                            .from(TenantSecrets::Table, TenantSecrets::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Secrets were kept in plain text in the tenant settings. Encryption
+        // needs `JWT_SECRET`, which migrations do not have, so the column is
+        // only renamed here; the server encrypts its values into
+        // tenant_secrets at startup and then clears it
+        // (`SecretStoreImpl::import_legacy_secrets`)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TenantSettings::Table)
+                    .rename_column(TenantSettings::Secrets, TenantSettings::LegacySecrets)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TenantSettings::Table)
+                    .rename_column(TenantSettings::LegacySecrets, TenantSettings::Secrets)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TenantSecrets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum TenantSecrets {
+    Table,
+    TenantId,
+    SecretKey,
+    Ciphertext,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Tenants {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum TenantSettings {
+    Table,
+    Secrets,
+    LegacySecrets,
+}
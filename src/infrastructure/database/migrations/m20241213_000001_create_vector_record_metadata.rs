@@ -0,0 +1,84 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(VectorRecordMetadata::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(VectorRecordMetadata::ConfigId).binary_len(16).not_null())
+                    .col(
+                        ColumnDef::new(VectorRecordMetadata::Namespace)
+                            .string_len(255)
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(VectorRecordMetadata::RecordId).string_len(255).not_null())
+                    .col(ColumnDef::new(VectorRecordMetadata::TenantId).binary_len(16).not_null())
+                    .col(
+                        ColumnDef::new(VectorRecordMetadata::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VectorRecordMetadata::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .name("pk_vector_record_metadata")
+                            .col(VectorRecordMetadata::ConfigId)
+                            .col(VectorRecordMetadata::Namespace)
+                            .col(VectorRecordMetadata::RecordId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_vector_record_metadata_config")
+                            .from(VectorRecordMetadata::Table, VectorRecordMetadata::ConfigId)
+                            .to(VectorConfigs::Table, VectorConfigs::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_vector_record_metadata_expires_at")
+                    .table(VectorRecordMetadata::Table)
+                    .col(VectorRecordMetadata::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VectorRecordMetadata::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum VectorRecordMetadata {
+    Table,
+    ConfigId,
+    Namespace,
+    RecordId,
+    TenantId,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum VectorConfigs {
+    Table,
+    Id,
+}
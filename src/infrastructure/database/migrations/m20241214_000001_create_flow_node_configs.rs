@@ -0,0 +1,163 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::value_objects::FlowDefinition;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FlowNodeConfigs::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(FlowNodeConfigs::FlowId).binary_len(16).not_null())
+                    .col(ColumnDef::new(FlowNodeConfigs::NodeId).string_len(255).not_null())
+                    .col(ColumnDef::new(FlowNodeConfigs::TenantId).binary_len(16).not_null())
+                    .col(ColumnDef::new(FlowNodeConfigs::NodeType).string_len(64).not_null())
+                    .col(ColumnDef::new(FlowNodeConfigs::LlmConfigId).binary_len(16).null())
+                    .col(ColumnDef::new(FlowNodeConfigs::McpToolId).binary_len(16).null())
+                    .primary_key(
+                        Index::create()
+                            .name("pk_flow_node_configs")
+                            .col(FlowNodeConfigs::FlowId)
+                            .col(FlowNodeConfigs::NodeId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_flow_node_configs_flow")
+                            .from(FlowNodeConfigs::Table, FlowNodeConfigs::FlowId)
+                            .to(Flows::Table, Flows::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_flow_node_configs_tenant_type")
+                            .col(FlowNodeConfigs::TenantId)
+                            .col(FlowNodeConfigs::NodeType),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_flow_node_configs_llm_config")
+                            .col(FlowNodeConfigs::LlmConfigId),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_flow_node_configs_mcp_tool")
+                            .col(FlowNodeConfigs::McpToolId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        backfill(manager).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FlowNodeConfigs::Table).to_owned())
+            .await
+    }
+}
+
+/// Index the nodes of every flow's current version
+async fn backfill(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    let backend = manager.get_database_backend();
+
+    let select = Query::select()
+        .column((FlowVersions::Table, FlowVersions::FlowId))
+        .column((FlowVersions::Table, FlowVersions::Definition))
+        .column((Flows::Table, Flows::TenantId))
+        .from(FlowVersions::Table)
+        .inner_join(
+            Flows::Table,
+            Expr::col((Flows::Table, Flows::Id))
+                .equals((FlowVersions::Table, FlowVersions::FlowId))
+                .and(
+                    Expr::col((Flows::Table, Flows::CurrentVersion))
+                        .equals((FlowVersions::Table, FlowVersions::Version)),
+                ),
+        )
+        .to_owned();
+
+    let rows = db.query_all(backend.build(&select)).await?;
+    for row in rows {
+        let flow_id: Uuid = row.try_get("", "flow_id")?;
+        let tenant_id: Uuid = row.try_get("", "tenant_id")?;
+        let definition: serde_json::Value = row.try_get("", "definition")?;
+
+        // Unparseable definitions are indexed the next time the flow is saved
+        let Ok(definition) = FlowDefinition::from_json(&definition) else {
+            continue;
+        };
+        let mut node_ids = HashSet::new();
+        let nodes: Vec<_> = definition
+            .workflow
+            .graph
+            .nodes
+            .iter()
+            .filter(|node| node_ids.insert(node.id.as_str()))
+            .collect();
+        if nodes.is_empty() {
+            continue;
+        }
+
+        let mut insert = Query::insert()
+            .into_table(FlowNodeConfigs::Table)
+            .columns([
+                FlowNodeConfigs::FlowId,
+                FlowNodeConfigs::NodeId,
+                FlowNodeConfigs::TenantId,
+                FlowNodeConfigs::NodeType,
+                FlowNodeConfigs::LlmConfigId,
+                FlowNodeConfigs::McpToolId,
+            ])
+            .to_owned();
+        for node in nodes {
+            insert.values_panic([
+                flow_id.into(),
+                node.id.clone().into(),
+                tenant_id.into(),
+                node.node_type.as_str().into(),
+                node.llm_config_id().into(),
+                node.mcp_tool_id().into(),
+            ]);
+        }
+        db.execute(backend.build(&insert)).await?;
+    }
+
+    Ok(())
+}
+
+#[derive(Iden)]
+enum FlowNodeConfigs {
+    Table,
+    FlowId,
+    NodeId,
+    TenantId,
+    NodeType,
+    LlmConfigId,
+    McpToolId,
+}
+
+#[derive(Iden)]
+enum Flows {
+    Table,
+    Id,
+    TenantId,
+    CurrentVersion,
+}
+
+#[derive(Iden)]
+enum FlowVersions {
+    Table,
+    FlowId,
+    Version,
+    Definition,
+}
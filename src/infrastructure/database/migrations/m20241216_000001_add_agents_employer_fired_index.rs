@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves the employed-agents listing, which filters on both columns
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_agents_employer_fired")
+                    .table(Agents::Table)
+                    .col(Agents::EmployerId)
+                    .col(Agents::FiredAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_agents_employer_fired")
+                    .table(Agents::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Agents {
+    Table,
+    EmployerId,
+    FiredAt,
+}
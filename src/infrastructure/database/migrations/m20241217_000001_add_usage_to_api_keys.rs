@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiKeys::Table)
+                    .add_column(
+                        ColumnDef::new(ApiKeys::RequestCount)
+                            .big_unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(
+                        ColumnDef::new(ApiKeys::TotalTokensUsed)
+                            .big_unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(ColumnDef::new(ApiKeys::RateLimitRpm).unsigned().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApiKeyUsageDaily::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ApiKeyUsageDaily::ApiKeyId).binary_len(16).not_null())
+                    .col(ColumnDef::new(ApiKeyUsageDaily::UsageDate).date().not_null())
+                    .col(
+                        ColumnDef::new(ApiKeyUsageDaily::RequestCount)
+                            .big_unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ApiKeyUsageDaily::TokensUsed)
+                            .big_unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .name("pk_api_key_usage_daily")
+                            .col(ApiKeyUsageDaily::ApiKeyId)
+                            .col(ApiKeyUsageDaily::UsageDate),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_api_key_usage_daily_api_key")
+                            .from(ApiKeyUsageDaily::Table, ApiKeyUsageDaily::ApiKeyId)
+                            .to(ApiKeys::Table, ApiKeys::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ApiKeyUsageDaily::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiKeys::Table)
+                    .drop_column(ApiKeys::RequestCount)
+                    .drop_column(ApiKeys::TotalTokensUsed)
+                    .drop_column(ApiKeys::RateLimitRpm)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ApiKeys {
+    Table,
+    Id,
+    RequestCount,
+    TotalTokensUsed,
+    RateLimitRpm,
+}
+
+#[derive(Iden)]
+enum ApiKeyUsageDaily {
+    Table,
+    ApiKeyId,
+    UsageDate,
+    RequestCount,
+    TokensUsed,
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Serves the tenant's audit log listing, a date range ordered by time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_tenant_created")
+                    .table(AuditLogs::Table)
+                    .col(AuditLogs::TenantId)
+                    .col(AuditLogs::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_audit_log_tenant_created")
+                    .table(AuditLogs::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum AuditLogs {
+    Table,
+    TenantId,
+    CreatedAt,
+}
@@ -0,0 +1,34 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// Vector configurations created before namespaces were isolated per tenant
+/// keep reading the unprefixed namespaces their records were written to.
+/// Pinecone stores are not isolated by the wrapper and are left alone.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE vector_configs \
+                 SET config = JSON_SET(config, '$.legacy_namespace_fallback', 'true') \
+                 WHERE provider <> 'pinecone'",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE vector_configs \
+                 SET config = JSON_REMOVE(config, '$.legacy_namespace_fallback')",
+            )
+            .await?;
+        Ok(())
+    }
+}
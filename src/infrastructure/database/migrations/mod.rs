@@ -23,4 +23,30 @@ pub mod m20241127_000002_add_price_to_agents;
 pub mod m20241127_000003_create_interview_records;
 pub mod m20241127_000004_make_timestamp_fields_nullable;
 pub mod m20241129_000001_add_llm_config_id_to_agents;
-pub mod m20241129_000002_create_user_tenant_relations;
\ No newline at end of file
+pub mod m20241129_000002_create_user_tenant_relations;
+pub mod m20241201_000001_add_correlation_id_to_flow_executions;
+pub mod m20241201_000002_create_flow_node_annotations;
+pub mod m20241202_000001_create_domain_events;
+pub mod m20241202_000002_create_llm_usage_log;
+pub mod m20241203_000001_add_llm_fallback_strategy_to_agents;
+pub mod m20241203_000002_add_timeout_ms_to_flows;
+pub mod m20241204_000001_add_summary_to_chat_sessions;
+pub mod m20241205_000001_add_fulltext_index_to_agents;
+pub mod m20241206_000001_create_tenant_settings;
+pub mod m20241206_000002_add_started_at_id_index_to_flow_executions;
+pub mod m20241207_000001_create_refresh_tokens;
+pub mod m20241207_000002_create_batch_executions;
+pub mod m20241208_000001_add_quota_to_tenant_settings;
+pub mod m20241209_000001_add_secrets_to_tenant_settings;
+pub mod m20241210_000001_add_expires_at_to_agent_allocations;
+pub mod m20241211_000001_create_message_branches;
+pub mod m20241212_000001_create_tenant_secrets;
+pub mod m20241213_000001_create_vector_record_metadata;
+pub mod m20241214_000001_create_flow_node_configs;
+pub mod m20241215_000001_add_profile_to_users;
+pub mod m20241216_000001_add_agents_employer_fired_index;
+pub mod m20241217_000001_add_usage_to_api_keys;
+pub mod m20241218_000001_add_audit_logs_tenant_created_index;
+pub mod m20241219_000001_add_published_to_flows;
+pub mod m20241220_000001_add_enabled_to_flows;
+pub mod m20241221_000001_enable_legacy_vector_namespaces;
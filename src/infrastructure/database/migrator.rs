@@ -34,6 +34,32 @@ impl MigratorTrait for Migrator {
             Box::new(migrations::m20241127_000004_make_timestamp_fields_nullable::Migration),
             Box::new(migrations::m20241129_000001_add_llm_config_id_to_agents::Migration),
             Box::new(migrations::m20241129_000002_create_user_tenant_relations::Migration),
+            Box::new(migrations::m20241201_000001_add_correlation_id_to_flow_executions::Migration),
+            Box::new(migrations::m20241201_000002_create_flow_node_annotations::Migration),
+            Box::new(migrations::m20241202_000001_create_domain_events::Migration),
+            Box::new(migrations::m20241202_000002_create_llm_usage_log::Migration),
+            Box::new(migrations::m20241203_000001_add_llm_fallback_strategy_to_agents::Migration),
+            Box::new(migrations::m20241203_000002_add_timeout_ms_to_flows::Migration),
+            Box::new(migrations::m20241204_000001_add_summary_to_chat_sessions::Migration),
+            Box::new(migrations::m20241205_000001_add_fulltext_index_to_agents::Migration),
+            Box::new(migrations::m20241206_000001_create_tenant_settings::Migration),
+            Box::new(migrations::m20241206_000002_add_started_at_id_index_to_flow_executions::Migration),
+            Box::new(migrations::m20241207_000001_create_refresh_tokens::Migration),
+            Box::new(migrations::m20241207_000002_create_batch_executions::Migration),
+            Box::new(migrations::m20241208_000001_add_quota_to_tenant_settings::Migration),
+            Box::new(migrations::m20241209_000001_add_secrets_to_tenant_settings::Migration),
+            Box::new(migrations::m20241210_000001_add_expires_at_to_agent_allocations::Migration),
+            Box::new(migrations::m20241211_000001_create_message_branches::Migration),
+            Box::new(migrations::m20241212_000001_create_tenant_secrets::Migration),
+            Box::new(migrations::m20241213_000001_create_vector_record_metadata::Migration),
+            Box::new(migrations::m20241214_000001_create_flow_node_configs::Migration),
+            Box::new(migrations::m20241215_000001_add_profile_to_users::Migration),
+            Box::new(migrations::m20241216_000001_add_agents_employer_fired_index::Migration),
+            Box::new(migrations::m20241217_000001_add_usage_to_api_keys::Migration),
+            Box::new(migrations::m20241218_000001_add_audit_logs_tenant_created_index::Migration),
+            Box::new(migrations::m20241219_000001_add_published_to_flows::Migration),
+            Box::new(migrations::m20241220_000001_add_enabled_to_flows::Migration),
+            Box::new(migrations::m20241221_000001_enable_legacy_vector_namespaces::Migration),
         ]
     }
 }
\ No newline at end of file
@@ -9,7 +9,7 @@ pub mod entities;
 pub mod query_optimizer;
 
 pub use migrator::Migrator;
-pub use query_optimizer::QueryOptimizer;
+pub use query_optimizer::{DbStatsReport, IndexHint, QueryOptimizer, QueryStats};
 
 pub struct Database {
     pub connection: Arc<DatabaseConnection>,
@@ -32,4 +32,10 @@ impl Database {
     pub fn connection(&self) -> Arc<DatabaseConnection> {
         Arc::clone(&self.connection)
     }
+
+    /// Close the connection pool; later queries fail
+    pub async fn close(&self) -> Result<()> {
+        self.connection.close_by_ref().await?;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -1,24 +1,211 @@
 // Database query optimization utilities
 // Requirement 2.3: Optimize database queries
 
-use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryTrait, Select, Statement,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use log::{info, warn};
+use tracing::{debug, warn};
+
+/// Queries slower than this are logged as warnings
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(200);
+
+/// Distinct SQL shapes kept in the plan cache before it is reset
+const PLAN_CACHE_CAPACITY: usize = 256;
+
+/// Index to force for a known access pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexHint {
+    pub table: &'static str,
+    pub index: &'static str,
+}
+
+impl IndexHint {
+    pub const AGENTS_BY_TENANT: IndexHint = IndexHint {
+        table: "agents",
+        index: "idx_agents_tenant_id",
+    };
+    pub const AGENTS_BY_CREATOR: IndexHint = IndexHint {
+        table: "agents",
+        index: "idx_agents_creator_id",
+    };
+    pub const FLOWS_BY_TENANT: IndexHint = IndexHint {
+        table: "flows",
+        index: "idx_flow_tenant_id",
+    };
+
+    /// Insert `USE INDEX` after the table in the FROM clause. Only MySQL
+    /// supports index hints; other backends get the SQL back unchanged.
+    pub fn apply(&self, sql: &str, backend: DbBackend) -> String {
+        if backend != DbBackend::MySql {
+            return sql.to_string();
+        }
+
+        let from_clause = format!("FROM `{}`", self.table);
+        match sql.find(&from_clause) {
+            Some(pos) => {
+                let insert_at = pos + from_clause.len();
+                format!(
+                    "{} USE INDEX (`{}`){}",
+                    &sql[..insert_at],
+                    self.index,
+                    &sql[insert_at..]
+                )
+            }
+            None => sql.to_string(),
+        }
+    }
+}
+
+/// Caches rewritten SQL per query shape.
+///
+/// SeaORM renders the same query shape to the same placeholder SQL, so the
+/// rewrite runs once per shape, and handing the driver identical text lets its
+/// prepared statement cache reuse the server-side statement and plan.
+#[derive(Default)]
+pub struct QueryPlanCache {
+    plans: Mutex<HashMap<String, Arc<str>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanCacheStats {
+    pub size: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl QueryPlanCache {
+    pub fn get_or_insert_with(&self, sql: &str, build: impl FnOnce() -> String) -> Arc<str> {
+        let mut plans = self.plans.lock().unwrap();
+        if let Some(plan) = plans.get(sql) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return plan.clone();
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        if plans.len() >= PLAN_CACHE_CAPACITY {
+            plans.clear();
+        }
+        let plan: Arc<str> = build().into();
+        plans.insert(sql.to_string(), plan.clone());
+        plan
+    }
+
+    pub fn stats(&self) -> PlanCacheStats {
+        PlanCacheStats {
+            size: self.plans.lock().unwrap().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct QueryTypeAccumulator {
+    count: u64,
+    error_count: u64,
+    slow_count: u64,
+    total_time: Duration,
+    max_time: Duration,
+}
+
+/// Aggregated timings for one query type
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryTypeStats {
+    pub query_type: String,
+    pub count: u64,
+    pub error_count: u64,
+    pub slow_count: u64,
+    pub total_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Collects query counts and latencies per query type
+#[derive(Default)]
+pub struct QueryStats {
+    queries: Mutex<HashMap<String, QueryTypeAccumulator>>,
+}
+
+impl QueryStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, query_type: &str, duration: Duration, slow: bool, failed: bool) {
+        let mut queries = self.queries.lock().unwrap();
+        let entry = queries.entry(query_type.to_string()).or_default();
+        entry.count += 1;
+        entry.total_time += duration;
+        entry.max_time = entry.max_time.max(duration);
+        if slow {
+            entry.slow_count += 1;
+        }
+        if failed {
+            entry.error_count += 1;
+        }
+    }
+
+    /// Per-type stats, slowest total time first
+    pub fn snapshot(&self) -> Vec<QueryTypeStats> {
+        let queries = self.queries.lock().unwrap();
+        let mut stats: Vec<QueryTypeStats> = queries
+            .iter()
+            .map(|(query_type, acc)| {
+                let total_ms = acc.total_time.as_secs_f64() * 1000.0;
+                QueryTypeStats {
+                    query_type: query_type.clone(),
+                    count: acc.count,
+                    error_count: acc.error_count,
+                    slow_count: acc.slow_count,
+                    total_ms,
+                    avg_ms: if acc.count > 0 { total_ms / acc.count as f64 } else { 0.0 },
+                    max_ms: acc.max_time.as_secs_f64() * 1000.0,
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+        stats
+    }
+}
+
+/// Payload of `GET /internal/db-stats`
+#[derive(Debug, Clone, Serialize)]
+pub struct DbStatsReport {
+    pub slow_query_threshold_ms: u64,
+    pub plan_cache: PlanCacheStats,
+    pub queries: Vec<QueryTypeStats>,
+}
 
 pub struct QueryOptimizer {
-    db: DatabaseConnection,
+    db: Arc<DatabaseConnection>,
     slow_query_threshold: Duration,
+    stats: QueryStats,
+    plan_cache: QueryPlanCache,
 }
 
 impl QueryOptimizer {
-    pub fn new(db: DatabaseConnection, slow_query_threshold: Duration) -> Self {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
         Self {
             db,
-            slow_query_threshold,
+            slow_query_threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+            stats: QueryStats::new(),
+            plan_cache: QueryPlanCache::default(),
         }
     }
 
-    /// Execute a query and log if it's slow
+    pub fn with_slow_query_threshold(mut self, slow_query_threshold: Duration) -> Self {
+        self.slow_query_threshold = slow_query_threshold;
+        self
+    }
+
+    /// Execute a query, recording its latency and logging it if it's slow
     pub async fn execute_with_timing<F, T>(&self, query_name: &str, f: F) -> Result<T, sea_orm::DbErr>
     where
         F: std::future::Future<Output = Result<T, sea_orm::DbErr>>,
@@ -26,19 +213,58 @@ impl QueryOptimizer {
         let start = Instant::now();
         let result = f.await;
         let duration = start.elapsed();
+        let slow = duration > self.slow_query_threshold;
+
+        self.stats.record(query_name, duration, slow, result.is_err());
 
-        if duration > self.slow_query_threshold {
+        if slow {
             warn!(
                 "Slow query detected: {} took {:?}",
                 query_name, duration
             );
         } else {
-            info!("Query {} completed in {:?}", query_name, duration);
+            debug!("Query {} completed in {:?}", query_name, duration);
         }
 
         result
     }
 
+    /// Run a select with an optional index hint, reusing the rewritten SQL
+    /// for repeated parameterized queries
+    pub async fn find_all<E>(
+        &self,
+        query_name: &str,
+        query: Select<E>,
+        hint: Option<IndexHint>,
+    ) -> Result<Vec<E::Model>, sea_orm::DbErr>
+    where
+        E: EntityTrait,
+    {
+        let backend = self.db.get_database_backend();
+        let statement = query.build(backend);
+        let sql = self.plan_cache.get_or_insert_with(&statement.sql, || match hint {
+            Some(hint) => hint.apply(&statement.sql, backend),
+            None => statement.sql.clone(),
+        });
+
+        let values = statement.values.map(|values| values.0).unwrap_or_default();
+        let statement = Statement::from_sql_and_values(backend, sql.as_ref(), values);
+
+        self.execute_with_timing(
+            query_name,
+            E::find().from_raw_sql(statement).all(self.db.as_ref()),
+        )
+        .await
+    }
+
+    pub fn report(&self) -> DbStatsReport {
+        DbStatsReport {
+            slow_query_threshold_ms: self.slow_query_threshold.as_millis() as u64,
+            plan_cache: self.plan_cache.stats(),
+            queries: self.stats.snapshot(),
+        }
+    }
+
     /// Analyze query execution plan
     pub async fn explain_query(&self, sql: &str) -> Result<String, sea_orm::DbErr> {
         let explain_sql = format!("EXPLAIN {}", sql);
@@ -57,8 +283,7 @@ impl QueryOptimizer {
 
 // Common query optimization patterns
 pub mod patterns {
-    use sea_orm::{EntityTrait, QueryFilter, QuerySelect, Select};
-    use sea_orm::sea_query::Expr;
+    use sea_orm::{EntityTrait, QuerySelect, Select};
 
     /// Add pagination to query efficiently
     pub fn paginate<E>(query: Select<E>, page: u64, page_size: u64) -> Select<E>
@@ -69,15 +294,7 @@ pub mod patterns {
         query.limit(page_size).offset(offset)
     }
 
-    /// Add index hints for better query performance
-    pub fn with_index_hint<E>(query: Select<E>, index_name: &str) -> Select<E>
-    where
-        E: EntityTrait,
-    {
-        // Note: SeaORM doesn't directly support index hints
-        // This would need to be implemented with raw SQL if needed
-        query
-    }
+    // Index hints need raw SQL, see `QueryOptimizer::find_all` and `IndexHint`
 
     /// Optimize COUNT queries by using approximate counts for large tables
     pub fn approximate_count_hint() -> &'static str {
@@ -152,4 +369,49 @@ mod tests {
         assert_eq!(query.cache_key(), "test_key");
         assert_eq!(query.ttl(), Duration::from_secs(300));
     }
+
+    #[test]
+    fn test_index_hint_injected_for_mysql_only() {
+        let sql = "SELECT `agents`.`id` FROM `agents` WHERE `agents`.`tenant_id` = ?";
+
+        assert_eq!(
+            IndexHint::AGENTS_BY_TENANT.apply(sql, DbBackend::MySql),
+            "SELECT `agents`.`id` FROM `agents` USE INDEX (`idx_agents_tenant_id`) WHERE `agents`.`tenant_id` = ?"
+        );
+        assert_eq!(IndexHint::AGENTS_BY_TENANT.apply(sql, DbBackend::Postgres), sql);
+        assert_eq!(IndexHint::FLOWS_BY_TENANT.apply(sql, DbBackend::MySql), sql);
+    }
+
+    #[test]
+    fn test_plan_cache_reuses_rewritten_sql() {
+        let cache = QueryPlanCache::default();
+        let mut builds = 0;
+
+        for _ in 0..3 {
+            cache.get_or_insert_with("SELECT 1", || {
+                builds += 1;
+                "SELECT 1 /* hinted */".to_string()
+            });
+        }
+
+        let stats = cache.stats();
+        assert_eq!(builds, 1);
+        assert_eq!((stats.size, stats.hits, stats.misses), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_query_stats_aggregates_per_type() {
+        let stats = QueryStats::new();
+        stats.record("agents.find_by_tenant", Duration::from_millis(10), false, false);
+        stats.record("agents.find_by_tenant", Duration::from_millis(300), true, false);
+        stats.record("flows.find_by_tenant", Duration::from_millis(5), false, true);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot[0].query_type, "agents.find_by_tenant");
+        assert_eq!(snapshot[0].count, 2);
+        assert_eq!(snapshot[0].slow_count, 1);
+        assert_eq!(snapshot[0].max_ms, 300.0);
+        assert_eq!(snapshot[0].avg_ms, 155.0);
+        assert_eq!(snapshot[1].error_count, 1);
+    }
 }
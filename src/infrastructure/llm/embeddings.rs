@@ -0,0 +1,419 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::providers::{HttpClient, HttpClientConfig, ProviderUtils};
+use crate::domain::services::embedding_service::EmbeddingProvider;
+use crate::domain::services::llm_service::LLMError;
+
+/// OpenAI embedding models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenAIEmbeddingModel {
+    TextEmbedding3Small,
+    TextEmbedding3Large,
+}
+
+impl OpenAIEmbeddingModel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpenAIEmbeddingModel::TextEmbedding3Small => "text-embedding-3-small",
+            OpenAIEmbeddingModel::TextEmbedding3Large => "text-embedding-3-large",
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        match self {
+            OpenAIEmbeddingModel::TextEmbedding3Small => 1536,
+            OpenAIEmbeddingModel::TextEmbedding3Large => 3072,
+        }
+    }
+}
+
+impl std::str::FromStr for OpenAIEmbeddingModel {
+    type Err = LLMError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text-embedding-3-small" => Ok(OpenAIEmbeddingModel::TextEmbedding3Small),
+            "text-embedding-3-large" => Ok(OpenAIEmbeddingModel::TextEmbedding3Large),
+            other => Err(LLMError::ModelNotFound(format!(
+                "Unsupported OpenAI embedding model: {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAIEmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIEmbeddingsResponse {
+    data: Vec<OpenAIEmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIEmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Embeddings from the OpenAI `/embeddings` endpoint
+pub struct OpenAIEmbedding {
+    api_key: String,
+    base_url: String,
+    model: OpenAIEmbeddingModel,
+    http_client: HttpClient,
+}
+
+impl OpenAIEmbedding {
+    pub fn new(api_key: String, model: OpenAIEmbeddingModel) -> Result<Self, LLMError> {
+        ProviderUtils::validate_api_key(&api_key, "openai")?;
+
+        Ok(Self {
+            api_key,
+            base_url: "https://api.openai.com/v1".to_string(),
+            model,
+            http_client: HttpClient::new(HttpClientConfig::default())?,
+        })
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Result<Self, LLMError> {
+        ProviderUtils::validate_base_url(&base_url)?;
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        Ok(self)
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.model.dimension()
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAIEmbedding {
+    async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!("{}/embeddings", self.base_url);
+        let headers = HashMap::from([
+            ("Authorization".to_string(), format!("Bearer {}", self.api_key)),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ]);
+        let request = OpenAIEmbeddingsRequest {
+            model: self.model.as_str(),
+            input: &texts,
+        };
+
+        let response: OpenAIEmbeddingsResponse = self
+            .http_client
+            .post_json(&url, &headers, &request)
+            .await?;
+
+        // The API does not promise to return embeddings in input order
+        let mut data = response.data;
+        data.sort_by_key(|d| d.index);
+        let embeddings: Vec<Vec<f32>> = data.into_iter().map(|d| d.embedding).collect();
+        check_count(texts.len(), embeddings)
+    }
+
+    fn model_name(&self) -> &str {
+        self.model.as_str()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaEmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Embeddings from a local Ollama server's `/api/embed` endpoint
+pub struct OllamaEmbedding {
+    base_url: String,
+    model: String,
+    http_client: HttpClient,
+}
+
+impl OllamaEmbedding {
+    pub const DEFAULT_MODEL: &'static str = "nomic-embed-text";
+
+    pub fn new(base_url: String, model: Option<String>) -> Result<Self, LLMError> {
+        ProviderUtils::validate_base_url(&base_url)?;
+
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.unwrap_or_else(|| Self::DEFAULT_MODEL.to_string()),
+            http_client: HttpClient::new(HttpClientConfig {
+                timeout: std::time::Duration::from_secs(60),
+                ..Default::default()
+            })?,
+        })
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaEmbedding {
+    async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!("{}/api/embed", self.base_url);
+        let headers = HashMap::from([(
+            "Content-Type".to_string(),
+            "application/json".to_string(),
+        )]);
+        let request = OllamaEmbedRequest {
+            model: &self.model,
+            input: &texts,
+        };
+
+        let response: OllamaEmbedResponse = self
+            .http_client
+            .post_json(&url, &headers, &request)
+            .await?;
+
+        check_count(texts.len(), response.embeddings)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiBatchEmbedRequest {
+    requests: Vec<GeminiEmbedContentRequest>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiEmbedContentRequest {
+    model: String,
+    content: GeminiEmbedContent,
+    task_type: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct GeminiEmbedContent {
+    parts: Vec<GeminiEmbedPart>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeminiEmbedPart {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiBatchEmbedResponse {
+    embeddings: Vec<GeminiEmbeddingValues>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiEmbeddingValues {
+    values: Vec<f32>,
+}
+
+/// Embeddings from the Gemini `batchEmbedContents` endpoint
+pub struct GeminiEmbedding {
+    api_key: String,
+    base_url: String,
+    model: String,
+    http_client: HttpClient,
+}
+
+impl GeminiEmbedding {
+    pub const DEFAULT_MODEL: &'static str = "text-embedding-004";
+
+    pub fn new(api_key: String, model: Option<String>) -> Result<Self, LLMError> {
+        ProviderUtils::validate_api_key(&api_key, "gemini")?;
+
+        Ok(Self {
+            api_key,
+            base_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
+            model: model.unwrap_or_else(|| Self::DEFAULT_MODEL.to_string()),
+            http_client: HttpClient::new(HttpClientConfig::default())?,
+        })
+    }
+
+    async fn embed(&self, texts: Vec<String>, task_type: &'static str) -> Result<Vec<Vec<f32>>, LLMError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!("{}/models/{}:batchEmbedContents", self.base_url, self.model);
+        let headers = HashMap::from([
+            ("x-goog-api-key".to_string(), self.api_key.clone()),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ]);
+        let count = texts.len();
+        let request = GeminiBatchEmbedRequest {
+            requests: texts
+                .into_iter()
+                .map(|text| GeminiEmbedContentRequest {
+                    model: format!("models/{}", self.model),
+                    content: GeminiEmbedContent {
+                        parts: vec![GeminiEmbedPart { text }],
+                    },
+                    task_type,
+                })
+                .collect(),
+        };
+
+        let response: GeminiBatchEmbedResponse = self
+            .http_client
+            .post_json(&url, &headers, &request)
+            .await?;
+
+        check_count(
+            count,
+            response.embeddings.into_iter().map(|e| e.values).collect(),
+        )
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for GeminiEmbedding {
+    async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        self.embed(texts, "RETRIEVAL_DOCUMENT").await
+    }
+
+    async fn embed_query(&self, text: String) -> Result<Vec<f32>, LLMError> {
+        self.embed(vec![text], "RETRIEVAL_QUERY")
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| LLMError::ProviderError("No embedding data in response".to_string()))
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+fn check_count(expected: usize, embeddings: Vec<Vec<f32>>) -> Result<Vec<Vec<f32>>, LLMError> {
+    if embeddings.len() != expected {
+        return Err(LLMError::ProviderError(format!(
+            "Expected {} embeddings, got {}",
+            expected,
+            embeddings.len()
+        )));
+    }
+    Ok(embeddings)
+}
+
+/// Factory for creating embedding providers
+pub struct EmbeddingProviderFactory;
+
+impl EmbeddingProviderFactory {
+    /// Pick an embedding provider from the environment.
+    ///
+    /// `EMBEDDING_PROVIDER` selects `openai`, `ollama` or `gemini`; when unset,
+    /// the first provider with credentials configured is used.
+    pub fn create_from_env() -> Option<Arc<dyn EmbeddingProvider>> {
+        let model = std::env::var("EMBEDDING_MODEL").ok();
+        let provider = std::env::var("EMBEDDING_PROVIDER").ok();
+
+        let result = match provider.as_deref() {
+            Some("openai") => Self::openai_from_env(model),
+            Some("ollama") => Self::ollama_from_env(model),
+            Some("gemini") => Self::gemini_from_env(model),
+            Some(other) => Err(LLMError::InvalidConfiguration(format!(
+                "Unknown embedding provider: {}",
+                other
+            ))),
+            None => Self::openai_from_env(model.clone())
+                .or_else(|_| Self::ollama_from_env(model.clone()))
+                .or_else(|_| Self::gemini_from_env(model)),
+        };
+
+        match result {
+            Ok(provider) => {
+                tracing::info!("Using embedding model {}", provider.model_name());
+                Some(provider)
+            }
+            Err(e) => {
+                tracing::warn!("No embedding provider configured: {}", e);
+                None
+            }
+        }
+    }
+
+    fn openai_from_env(model: Option<String>) -> Result<Arc<dyn EmbeddingProvider>, LLMError> {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .map_err(|_| LLMError::InvalidConfiguration("OPENAI_API_KEY is not set".to_string()))?;
+        let model = model
+            .as_deref()
+            .unwrap_or("text-embedding-3-small")
+            .parse::<OpenAIEmbeddingModel>()?;
+
+        let mut provider = OpenAIEmbedding::new(api_key, model)?;
+        if let Ok(base_url) = std::env::var("OPENAI_API_BASE") {
+            provider = provider.with_base_url(base_url)?;
+        }
+        Ok(Arc::new(provider))
+    }
+
+    fn ollama_from_env(model: Option<String>) -> Result<Arc<dyn EmbeddingProvider>, LLMError> {
+        let base_url = std::env::var("OLLAMA_URL")
+            .map_err(|_| LLMError::InvalidConfiguration("OLLAMA_URL is not set".to_string()))?;
+        Ok(Arc::new(OllamaEmbedding::new(base_url, model)?))
+    }
+
+    fn gemini_from_env(model: Option<String>) -> Result<Arc<dyn EmbeddingProvider>, LLMError> {
+        let api_key = std::env::var("GOOGLE_API_KEY")
+            .map_err(|_| LLMError::InvalidConfiguration("GOOGLE_API_KEY is not set".to_string()))?;
+        Ok(Arc::new(GeminiEmbedding::new(api_key, model)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openai_embedding_model_names() {
+        assert_eq!(
+            "text-embedding-3-small".parse::<OpenAIEmbeddingModel>().unwrap(),
+            OpenAIEmbeddingModel::TextEmbedding3Small
+        );
+        assert_eq!(OpenAIEmbeddingModel::TextEmbedding3Large.as_str(), "text-embedding-3-large");
+        assert_eq!(OpenAIEmbeddingModel::TextEmbedding3Large.dimension(), 3072);
+        assert!("text-embedding-ada-002".parse::<OpenAIEmbeddingModel>().is_err());
+    }
+
+    #[test]
+    fn test_gemini_request_format() {
+        let request = GeminiBatchEmbedRequest {
+            requests: vec![GeminiEmbedContentRequest {
+                model: "models/text-embedding-004".to_string(),
+                content: GeminiEmbedContent {
+                    parts: vec![GeminiEmbedPart { text: "hello".to_string() }],
+                },
+                task_type: "RETRIEVAL_QUERY",
+            }],
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["requests"][0]["taskType"], "RETRIEVAL_QUERY");
+        assert_eq!(json["requests"][0]["content"]["parts"][0]["text"], "hello");
+    }
+
+    #[test]
+    fn test_check_count_rejects_mismatch() {
+        assert!(check_count(2, vec![vec![0.1]]).is_err());
+        assert_eq!(check_count(1, vec![vec![0.1]]).unwrap(), vec![vec![0.1]]);
+    }
+}
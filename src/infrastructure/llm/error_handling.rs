@@ -2,6 +2,9 @@ use crate::domain::services::llm_service::LLMError;
 use std::time::Duration;
 use tokio::time::sleep;
 
+/// Status Anthropic answers with when its API is overloaded
+pub const OVERLOADED_STATUS: u16 = 529;
+
 /// Retry configuration for LLM operations
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -18,6 +21,7 @@ pub enum RetryableErrorType {
     NetworkError,
     InternalServerError,
     Timeout,
+    Overloaded,
 }
 
 impl Default for RetryConfig {
@@ -32,6 +36,7 @@ impl Default for RetryConfig {
                 RetryableErrorType::NetworkError,
                 RetryableErrorType::InternalServerError,
                 RetryableErrorType::Timeout,
+                RetryableErrorType::Overloaded,
             ],
         }
     }
@@ -65,7 +70,7 @@ impl RetryWrapper {
                         break;
                     }
 
-                    log::warn!(
+                    tracing::warn!(
                         "LLM operation failed on attempt {}/{}: {}. Retrying in {:?}",
                         attempt,
                         self.config.max_attempts,
@@ -89,8 +94,9 @@ impl RetryWrapper {
     fn is_retryable(&self, error: &LLMError) -> bool {
         let error_type = match error {
             LLMError::RateLimitExceeded(_) => RetryableErrorType::RateLimit,
-            LLMError::NetworkError(_) => RetryableErrorType::NetworkError,
+            LLMError::NetworkError(_) | LLMError::Timeout(_) => RetryableErrorType::NetworkError,
             LLMError::InternalError(_) => RetryableErrorType::InternalServerError,
+            LLMError::ProviderOverloaded(_) => RetryableErrorType::Overloaded,
             _ => return false,
         };
 
@@ -183,11 +189,85 @@ impl CircuitBreaker {
     }
 }
 
+/// Error codes and types providers use for prompts that exceed the context window
+const CONTEXT_LENGTH_CODES: [&str; 2] = ["context_length_exceeded", "string_above_max_length"];
+
+/// Error codes and types providers use for requests refused by content policy
+const CONTENT_POLICY_CODES: [&str; 3] = ["content_policy_violation", "content_filter", "moderation_error"];
+
+/// Error code and message from a provider's JSON error body, e.g. OpenAI's
+/// `{"error": {"code": "...", "message": "..."}}` or Claude's
+/// `{"type": "error", "error": {"type": "overloaded_error", "message": "..."}}`
+#[derive(Debug, Default, PartialEq)]
+struct ProviderErrorBody {
+    code: Option<String>,
+    kind: Option<String>,
+    message: Option<String>,
+}
+
+impl ProviderErrorBody {
+    fn parse(body: &str) -> Self {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+            return Self::default();
+        };
+        let Some(error) = value.get("error") else {
+            return Self::default();
+        };
+
+        // Some gateways send the message alone: `{"error": "..."}`
+        if let Some(message) = error.as_str() {
+            return Self { message: Some(message.to_string()), ..Self::default() };
+        }
+
+        let field = |name: &str| error.get(name).and_then(|v| v.as_str()).map(str::to_string);
+        Self {
+            code: field("code"),
+            kind: field("type"),
+            message: field("message"),
+        }
+    }
+
+    fn has_code(&self, codes: &[&str]) -> bool {
+        [&self.code, &self.kind]
+            .into_iter()
+            .flatten()
+            .any(|code| codes.contains(&code.as_str()))
+    }
+
+    fn message_contains(&self, needles: &[&str]) -> bool {
+        self.message.as_deref().is_some_and(|message| {
+            let message = message.to_lowercase();
+            needles.iter().any(|needle| message.contains(needle))
+        })
+    }
+}
+
 /// Error mapper for converting HTTP errors to LLM errors
 pub struct ErrorMapper;
 
 impl ErrorMapper {
+    /// Map a failed response to an error. Provider error codes in the body
+    /// take precedence over the status, so callers can tell input that must
+    /// change (context length, content policy) from load worth retrying.
     pub fn map_http_error(status: u16, body: &str) -> LLMError {
+        let error = ProviderErrorBody::parse(body);
+        let message = error.message.clone().unwrap_or_else(|| body.to_string());
+
+        if error.has_code(&CONTEXT_LENGTH_CODES)
+            || error.message_contains(&["maximum context length", "prompt is too long"])
+        {
+            return LLMError::ContextLengthExceeded(message);
+        }
+        if error.has_code(&CONTENT_POLICY_CODES) {
+            return LLMError::ContentPolicyViolation(message);
+        }
+        if status == OVERLOADED_STATUS
+            || error.has_code(&["overloaded_error"])
+            || (status == 503 && error.message_contains(&["overloaded"]))
+        {
+            return LLMError::ProviderOverloaded(message);
+        }
+
         match status {
             400 => LLMError::InvalidConfiguration(format!("Bad request: {}", body)),
             401 => LLMError::AuthenticationFailed("Invalid API key or authentication failed".to_string()),
@@ -201,7 +281,7 @@ impl ErrorMapper {
 
     pub fn map_network_error(error: &str) -> LLMError {
         if error.contains("timeout") {
-            LLMError::NetworkError("Request timeout".to_string())
+            LLMError::Timeout("Request timeout".to_string())
         } else if error.contains("connection") {
             LLMError::NetworkError("Connection error".to_string())
         } else {
@@ -307,4 +387,95 @@ mod tests {
             LLMError::InternalError(_)
         ));
     }
+
+    #[test]
+    fn test_error_mapper_openai_context_length() {
+        let body = r#"{"error": {"message": "This model's maximum context length is 8192 tokens.", "type": "invalid_request_error", "param": "messages", "code": "context_length_exceeded"}}"#;
+
+        match ErrorMapper::map_http_error(400, body) {
+            LLMError::ContextLengthExceeded(message) => {
+                assert_eq!(message, "This model's maximum context length is 8192 tokens.");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let body = r#"{"type": "error", "error": {"type": "invalid_request_error", "message": "prompt is too long: 210000 tokens > 200000 maximum"}}"#;
+        assert!(matches!(
+            ErrorMapper::map_http_error(400, body),
+            LLMError::ContextLengthExceeded(_)
+        ));
+    }
+
+    #[test]
+    fn test_error_mapper_claude_overloaded() {
+        let body = r#"{"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}"#;
+
+        assert!(matches!(
+            ErrorMapper::map_http_error(OVERLOADED_STATUS, body),
+            LLMError::ProviderOverloaded(_)
+        ));
+        assert!(matches!(
+            ErrorMapper::map_http_error(500, body),
+            LLMError::ProviderOverloaded(_)
+        ));
+        assert!(matches!(
+            ErrorMapper::map_http_error(OVERLOADED_STATUS, "<html>busy</html>"),
+            LLMError::ProviderOverloaded(_)
+        ));
+    }
+
+    #[test]
+    fn test_error_mapper_content_policy() {
+        let body = r#"{"error": {"message": "Your request was rejected as a result of our safety system.", "type": "invalid_request_error", "code": "content_policy_violation"}}"#;
+        assert!(matches!(
+            ErrorMapper::map_http_error(400, body),
+            LLMError::ContentPolicyViolation(_)
+        ));
+
+        let body = r#"{"error": {"message": "The response was filtered", "code": "content_filter", "status": 400}}"#;
+        assert!(matches!(
+            ErrorMapper::map_http_error(400, body),
+            LLMError::ContentPolicyViolation(_)
+        ));
+    }
+
+    #[test]
+    fn test_error_mapper_falls_back_to_status() {
+        let body = r#"{"error": {"message": "Rate limit reached", "type": "requests", "code": "rate_limit_exceeded"}}"#;
+        assert!(matches!(
+            ErrorMapper::map_http_error(429, body),
+            LLMError::RateLimitExceeded(_)
+        ));
+        assert!(matches!(
+            ErrorMapper::map_http_error(400, r#"{"error": "bad model"}"#),
+            LLMError::InvalidConfiguration(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry_wrapper_retries_overloaded_but_not_context_length() {
+        let retry_wrapper = RetryWrapper::new(RetryConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        });
+
+        for (error, expected_attempts) in [
+            (LLMError::ProviderOverloaded("Overloaded".to_string()), 3),
+            (LLMError::ContextLengthExceeded("too long".to_string()), 1),
+        ] {
+            let counter = Arc::new(AtomicU32::new(0));
+            let result = retry_wrapper.execute_with_retry(|| {
+                let counter = counter.clone();
+                let error = error.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    Err::<i32, LLMError>(error)
+                }
+            }).await;
+
+            assert!(result.is_err());
+            assert_eq!(counter.load(Ordering::Relaxed), expected_attempts);
+        }
+    }
 }
\ No newline at end of file
@@ -1,16 +1,26 @@
 pub mod providers;
 pub mod error_handling;
 pub mod streaming;
+pub mod usage;
+pub mod embeddings;
+pub mod telemetry;
 
 pub use providers::*;
 pub use error_handling::*;
+pub use usage::{StreamUsageCollector, TokenCounter};
+pub use embeddings::*;
 
 
 use crate::domain::services::llm_service::{LLMProvider, LLMError, ConnectionTestResult};
 use crate::domain::ModelConfig;
+use crate::domain::value_objects::ModelProvider;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Custom model parameter enabling Claude extended thinking with the given
+/// token budget
+pub const THINKING_BUDGET_TOKENS_PARAM: &str = "thinking_budget_tokens";
+
 /// LLM Provider Registry for managing multiple providers
 pub struct LLMProviderRegistry {
     providers: HashMap<String, Arc<dyn LLMProvider>>,
@@ -28,33 +38,32 @@ impl LLMProviderRegistry {
     }
 
     pub fn create_provider(&self, model_config: &ModelConfig) -> Option<Arc<dyn LLMProvider>> {
-        let provider_name = format!("{:?}", model_config.provider).to_lowercase();
-        if provider_name == "openai" {
-            match &model_config.credentials.api_key {
-                Some(api_key) => {
-                    match &model_config.credentials.api_base {
-                        Some(api_base) => {
-                            match OpenAIProvider::new(api_key.clone(), Some(api_base.clone()))
-                                               .map_or_else(|e| None, |v| Some(v)) {
-                                Some(provider) => {
-                                    Some(Arc::new(provider))
-                                },
-                                None => {
-                                    None
-                                }
-                            }
-                        },
-                        None => {
-                            None
-                        }
-                    }
-                },
-                None => {
-                    None
+        let credentials = &model_config.credentials;
+        let api_key = credentials.api_key.clone()?;
+
+        match model_config.provider {
+            ModelProvider::OpenAI => {
+                let api_base = credentials.api_base.clone()?;
+                let provider = OpenAIProvider::new(api_key, Some(api_base)).ok()?;
+                Some(Arc::new(provider))
+            }
+            ModelProvider::Claude => {
+                let mut provider = ClaudeProvider::new(api_key).ok()?;
+                if let Some(budget_tokens) = model_config
+                    .parameters
+                    .custom_parameters
+                    .get(THINKING_BUDGET_TOKENS_PARAM)
+                    .and_then(|v| v.as_u64())
+                {
+                    provider = provider.with_thinking(budget_tokens as u32);
                 }
+                Some(Arc::new(provider))
+            }
+            ModelProvider::DeepSeek => {
+                let provider = DeepSeekProvider::new(api_key, credentials.api_base.clone()).ok()?;
+                Some(Arc::new(provider))
             }
-        } else {
-            None
+            _ => None,
         }
     }
 
@@ -108,8 +117,41 @@ impl LLMProviderFactory {
         Ok(Arc::new(ClaudeProvider::new(api_key)?))
     }
 
+    pub fn create_gemini_provider(api_key: String) -> Result<Arc<dyn LLMProvider>, LLMError> {
+        Ok(Arc::new(GeminiProvider::new(api_key)?))
+    }
+
+    pub fn create_deepseek_provider(api_key: String, base_url: Option<String>) -> Result<Arc<dyn LLMProvider>, LLMError> {
+        Ok(Arc::new(DeepSeekProvider::new(api_key, base_url)?))
+    }
+
+    /// Create a provider for a local LLM server. The URL may carry connection
+    /// params as a query string: `driver=ollama` selects Ollama's native API
+    /// instead of the OpenAI-compatible one.
     pub fn create_local_llm_provider(base_url: String) -> Result<Arc<dyn LLMProvider>, LLMError> {
-        Ok(Arc::new(LocalLLMProvider::new(base_url)?))
+        let (base_url, params) = Self::split_connection_params(&base_url)?;
+
+        match params.get("driver").map(String::as_str) {
+            Some("ollama") => Ok(Arc::new(OllamaProvider::new(base_url)?)),
+            Some("openai") | None => Ok(Arc::new(LocalLLMProvider::new(base_url)?)),
+            Some(driver) => Err(LLMError::InvalidConfiguration(format!(
+                "Unknown local LLM driver: {}",
+                driver
+            ))),
+        }
+    }
+
+    /// Split `http://host:port?key=value` into the base URL and its params
+    fn split_connection_params(url: &str) -> Result<(String, HashMap<String, String>), LLMError> {
+        let Some((base_url, _)) = url.split_once('?') else {
+            return Ok((url.to_string(), HashMap::new()));
+        };
+
+        let parsed = url::Url::parse(url)
+            .map_err(|e| LLMError::InvalidConfiguration(format!("Invalid base URL: {}", e)))?;
+        let params = parsed.query_pairs().into_owned().collect();
+
+        Ok((base_url.to_string(), params))
     }
 
     pub fn create_registry_with_defaults() -> LLMProviderRegistry {
@@ -128,6 +170,18 @@ impl LLMProviderFactory {
             }
         }
 
+        if let Ok(google_key) = std::env::var("GOOGLE_API_KEY") {
+            if let Ok(provider) = Self::create_gemini_provider(google_key) {
+                registry.register_provider("gemini".to_string(), provider);
+            }
+        }
+
+        if let Ok(deepseek_key) = std::env::var("DEEPSEEK_API_KEY") {
+            if let Ok(provider) = Self::create_deepseek_provider(deepseek_key, None) {
+                registry.register_provider("deepseek".to_string(), provider);
+            }
+        }
+
         if let Ok(local_url) = std::env::var("LOCAL_LLM_URL") {
             if let Ok(provider) = Self::create_local_llm_provider(local_url) {
                 registry.register_provider("local_llm".to_string(), provider);
@@ -136,4 +190,34 @@ impl LLMProviderFactory {
 
         registry
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_connection_params() {
+        let (base_url, params) =
+            LLMProviderFactory::split_connection_params("http://localhost:11434?driver=ollama").unwrap();
+        assert_eq!(base_url, "http://localhost:11434");
+        assert_eq!(params.get("driver").map(String::as_str), Some("ollama"));
+
+        let (base_url, params) =
+            LLMProviderFactory::split_connection_params("http://localhost:8000/v1").unwrap();
+        assert_eq!(base_url, "http://localhost:8000/v1");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_create_local_llm_provider_rejects_unknown_driver() {
+        assert!(LLMProviderFactory::create_local_llm_provider(
+            "http://localhost:11434?driver=ollama".to_string()
+        )
+        .is_ok());
+        assert!(LLMProviderFactory::create_local_llm_provider(
+            "http://localhost:11434?driver=tgi".to_string()
+        )
+        .is_err());
+    }
+}
@@ -3,8 +3,9 @@ use crate::domain::services::llm_service::{
     ConnectionTestResult, TokenUsage, FinishReason
 };
 use crate::infrastructure::llm::providers::{
-    HttpClient, HttpClientConfig, ProviderConfig, ProviderUtils
+    HttpClient, HttpClientConfig, ProviderConfig, ProviderUtils, REASONING_CONTENT_KEY
 };
+use crate::domain::value_objects::chat_message::MessageMetadata;
 use crate::infrastructure::llm::streaming::StreamAdapter;
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
@@ -16,6 +17,8 @@ use std::collections::HashMap;
 pub struct ClaudeProvider {
     config: ProviderConfig,
     http_client: HttpClient,
+    /// Token budget for extended thinking, `None` leaves it off
+    thinking_budget_tokens: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -32,6 +35,14 @@ struct ClaudeChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     stop_sequences: Option<Vec<String>>,
     stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<ClaudeThinking>,
+}
+
+#[derive(Debug, Serialize)]
+struct ClaudeThinking {
+    r#type: String,
+    budget_tokens: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,10 +63,14 @@ struct ClaudeChatResponse {
     usage: ClaudeUsage,
 }
 
+/// A response content block; `thinking` blocks precede the `text` ones
 #[derive(Debug, Deserialize)]
 struct ClaudeContent {
     r#type: String,
-    text: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    thinking: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,6 +92,9 @@ struct ClaudeError {
 }
 
 impl ClaudeProvider {
+    /// Smallest thinking budget the API accepts
+    const MIN_THINKING_BUDGET_TOKENS: u32 = 1024;
+
     pub fn new(api_key: String) -> Result<Self, LLMError> {
         ProviderUtils::validate_api_key(&api_key, "claude")?;
         
@@ -90,16 +108,24 @@ impl ClaudeProvider {
             custom_headers: HashMap::new(),
         };
 
-        let http_client = HttpClient::new(config.http_config.clone())?;
+        let http_client = HttpClient::new(config.http_config.clone())?.with_provider("claude");
 
         Ok(Self {
             config,
             http_client,
+            thinking_budget_tokens: None,
         })
     }
 
+    /// Enable extended thinking. The thinking tokens are streamed as
+    /// reasoning content, separately from the answer.
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.thinking_budget_tokens = Some(budget_tokens.max(Self::MIN_THINKING_BUDGET_TOKENS));
+        self
+    }
+
     pub fn with_custom_config(mut self, http_config: HttpClientConfig) -> Result<Self, LLMError> {
-        self.http_client = HttpClient::new(http_config.clone())?;
+        self.http_client = HttpClient::new(http_config.clone())?.with_provider("claude");
         self.config.http_config = http_config;
         Ok(self)
     }
@@ -122,36 +148,11 @@ impl ClaudeProvider {
     }
 
     fn convert_request(&self, request: ChatRequest) -> Result<ClaudeChatRequest, LLMError> {
-        use crate::domain::value_objects::chat_message::{MessageContent, ContentPart};
-        
         let mut messages = Vec::new();
         let mut system_message = None;
 
         for msg in request.messages {
-            let content = match &msg.content {
-                MessageContent::Text(text) => serde_json::json!(text),
-                MessageContent::Multimodal(parts) => {
-                    let content_parts: Vec<serde_json::Value> = parts
-                        .iter()
-                        .map(|part| match part {
-                            ContentPart::Text { text } => serde_json::json!({
-                                "type": "text",
-                                "text": text
-                            }),
-                            ContentPart::ImageUrl { image_url } => {
-                                serde_json::json!({
-                                    "type": "image",
-                                    "source": {
-                                        "type": "url",
-                                        "url": image_url.url
-                                    }
-                                })
-                            }
-                        })
-                        .collect();
-                    serde_json::json!(content_parts)
-                }
-            };
+            let content = ProviderUtils::convert_content_to_claude(&msg.content);
             
             match msg.role {
                 crate::domain::value_objects::MessageRole::System => {
@@ -183,22 +184,46 @@ impl ClaudeProvider {
         // Claude requires at least max_tokens to be set
         let max_tokens = request.max_tokens.unwrap_or(1000);
 
+        let thinking = self.thinking_budget_tokens.map(|budget_tokens| ClaudeThinking {
+            r#type: "enabled".to_string(),
+            budget_tokens,
+        });
+
+        // The thinking budget counts towards max_tokens, and thinking cannot
+        // be combined with sampling parameters
+        let (max_tokens, temperature, top_p) = match &thinking {
+            Some(thinking) => (max_tokens + thinking.budget_tokens, None, None),
+            None => (max_tokens, request.temperature, request.top_p),
+        };
+
         Ok(ClaudeChatRequest {
             model: request.model,
             messages,
             system: system_message,
             max_tokens,
-            temperature: request.temperature,
-            top_p: request.top_p,
+            temperature,
+            top_p,
             stop_sequences: request.stop_sequences,
             stream: request.stream,
+            thinking,
         })
     }
 
     fn convert_response(&self, response: ClaudeChatResponse) -> Result<ChatResponse, LLMError> {
-        let content = response.content
-            .first()
-            .ok_or_else(|| LLMError::ProviderError("No content in response".to_string()))?;
+        let content: String = response.content
+            .iter()
+            .filter(|block| block.r#type == "text")
+            .filter_map(|block| block.text.as_deref())
+            .collect();
+        if response.content.is_empty() {
+            return Err(LLMError::ProviderError("No content in response".to_string()));
+        }
+
+        let reasoning: String = response.content
+            .iter()
+            .filter(|block| block.r#type == "thinking")
+            .filter_map(|block| block.thinking.as_deref())
+            .collect();
 
         let finish_reason = match response.stop_reason.as_deref() {
             Some("end_turn") => FinishReason::Stop,
@@ -213,12 +238,22 @@ impl ClaudeProvider {
             total_tokens: response.usage.input_tokens + response.usage.output_tokens,
         };
 
+        let metadata = (!reasoning.is_empty()).then(|| MessageMetadata {
+            model_used: Some(response.model.clone()),
+            tokens_used: None,
+            response_time_ms: None,
+            tool_calls: None,
+            custom_data: HashMap::from([
+                (REASONING_CONTENT_KEY.to_string(), serde_json::json!(reasoning)),
+            ]),
+        });
+
         Ok(ChatResponse {
-            content: content.text.clone(),
+            content,
             model_used: response.model,
             usage,
             finish_reason,
-            metadata: None,
+            metadata,
         })
     }
 
@@ -238,6 +273,7 @@ impl ClaudeProvider {
             top_p: None,
             stop_sequences: None,
             stream: false,
+            thinking: None,
         };
 
         let _response: ClaudeChatResponse = self.http_client
@@ -384,7 +420,10 @@ mod tests {
             presence_penalty: None,
             stop_sequences: None,
             stream: false,
+            stream_options: None,
             tenant_id: uuid::Uuid::new_v4(),
+            response_format: None,
+            tools: None,
         };
 
         let claude_request = provider.convert_request(request).unwrap();
@@ -433,6 +472,58 @@ mod tests {
         assert!(models.iter().any(|m| m.supports_streaming));
     }
 
+    #[test]
+    fn test_convert_request_with_thinking() {
+        let provider = create_test_provider().with_thinking(2048);
+        let request = ChatRequest {
+            messages: vec![ChatMessage::new_user_message("Why is the sky blue?".to_string())],
+            model: "claude-3-7-sonnet-20250219".to_string(),
+            temperature: Some(0.7),
+            max_tokens: Some(1000),
+            top_p: Some(1.0),
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop_sequences: None,
+            stream: true,
+            stream_options: None,
+            tenant_id: uuid::Uuid::new_v4(),
+            response_format: None,
+            tools: None,
+        };
+
+        let claude_request = provider.convert_request(request).unwrap();
+        assert_eq!(claude_request.thinking.as_ref().unwrap().budget_tokens, 2048);
+        assert_eq!(claude_request.max_tokens, 3048);
+        assert!(claude_request.temperature.is_none());
+        assert!(claude_request.top_p.is_none());
+    }
+
+    #[test]
+    fn test_convert_response_separates_thinking() {
+        let provider = create_test_provider();
+        let response: ClaudeChatResponse = serde_json::from_value(serde_json::json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                { "type": "thinking", "thinking": "Rayleigh scattering.", "signature": "sig" },
+                { "type": "text", "text": "Because of Rayleigh scattering." }
+            ],
+            "model": "claude-3-7-sonnet-20250219",
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": { "input_tokens": 12, "output_tokens": 30 }
+        }))
+        .unwrap();
+
+        let chat_response = provider.convert_response(response).unwrap();
+        assert_eq!(chat_response.content, "Because of Rayleigh scattering.");
+        assert_eq!(
+            chat_response.metadata.unwrap().custom_data.get(REASONING_CONTENT_KEY),
+            Some(&serde_json::json!("Rayleigh scattering."))
+        );
+    }
+
     #[tokio::test]
     async fn test_embedding_not_supported() {
         let provider = create_test_provider();
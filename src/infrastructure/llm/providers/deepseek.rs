@@ -0,0 +1,375 @@
+use crate::domain::services::llm_service::{
+    LLMProvider, LLMError, ChatRequest, ChatResponse, ChatStreamChunk, ModelInfo,
+    ConnectionTestResult, TokenUsage, FinishReason
+};
+use crate::domain::services::StreamOptions;
+use crate::domain::value_objects::chat_message::MessageMetadata;
+use crate::infrastructure::llm::providers::{
+    HttpClient, HttpClientConfig, ProviderConfig, ProviderUtils, StandardTool, StandardToolCall,
+    StandardUsage, REASONING_CONTENT_KEY
+};
+use crate::infrastructure::llm::streaming::StreamAdapter;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// DeepSeek provider. The API is OpenAI compatible, except that reasoning
+/// models (deepseek-reasoner, R1) return their chain of thought in a separate
+/// `reasoning_content` field next to the answer.
+pub struct DeepSeekProvider {
+    config: ProviderConfig,
+    http_client: HttpClient,
+}
+
+#[derive(Debug, Serialize)]
+struct DeepSeekChatRequest {
+    model: String,
+    messages: Vec<DeepSeekMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<StandardTool>>,
+}
+
+#[derive(Debug, Serialize)]
+struct DeepSeekMessage {
+    role: String,
+    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<StandardToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepSeekChatResponse {
+    model: String,
+    choices: Vec<DeepSeekChoice>,
+    usage: Option<StandardUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepSeekChoice {
+    message: DeepSeekResponseMessage,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepSeekResponseMessage {
+    content: Option<String>,
+    reasoning_content: Option<String>,
+    tool_calls: Option<Vec<StandardToolCall>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepSeekModelsResponse {
+    data: Vec<DeepSeekModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepSeekModel {
+    id: String,
+}
+
+impl DeepSeekProvider {
+    pub fn new(api_key: String, base_url: Option<String>) -> Result<Self, LLMError> {
+        ProviderUtils::validate_api_key(&api_key, "deepseek")?;
+
+        let base_url = base_url.unwrap_or_else(|| "https://api.deepseek.com/v1".to_string());
+        ProviderUtils::validate_base_url(&base_url)?;
+
+        let config = ProviderConfig {
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            default_model: "deepseek-chat".to_string(),
+            http_config: HttpClientConfig::default(),
+            custom_headers: HashMap::new(),
+        };
+
+        let http_client = HttpClient::new(config.http_config.clone())?.with_provider("deepseek");
+
+        Ok(Self {
+            config,
+            http_client,
+        })
+    }
+
+    pub fn with_custom_config(mut self, http_config: HttpClientConfig) -> Result<Self, LLMError> {
+        self.http_client = HttpClient::new(http_config.clone())?.with_provider("deepseek");
+        self.config.http_config = http_config;
+        Ok(self)
+    }
+
+    pub fn add_custom_header(&mut self, key: String, value: String) {
+        self.config.custom_headers.insert(key, value);
+    }
+
+    fn build_headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), format!("Bearer {}", self.config.api_key));
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+
+        for (key, value) in &self.config.custom_headers {
+            headers.insert(key.clone(), value.clone());
+        }
+
+        headers
+    }
+
+    fn convert_request(&self, request: ChatRequest) -> DeepSeekChatRequest {
+        // DeepSeek chat models take text only
+        let messages = request.messages
+            .iter()
+            .map(|msg| DeepSeekMessage {
+                role: format!("{:?}", msg.role).to_lowercase(),
+                content: msg.get_text_content(),
+                tool_calls: msg
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.tool_calls.as_deref())
+                    .filter(|calls| !calls.is_empty())
+                    .map(ProviderUtils::convert_tool_calls_to_standard),
+                tool_call_id: msg.tool_call_id().map(str::to_string),
+            })
+            .collect();
+
+        DeepSeekChatRequest {
+            model: request.model,
+            messages,
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            top_p: request.top_p,
+            frequency_penalty: request.frequency_penalty,
+            presence_penalty: request.presence_penalty,
+            stop: request.stop_sequences,
+            stream: request.stream,
+            stream_options: request.stream_options,
+            tools: request.tools.as_deref().map(ProviderUtils::convert_tools_to_standard),
+        }
+    }
+
+    fn convert_response(&self, response: DeepSeekChatResponse) -> Result<ChatResponse, LLMError> {
+        let choice = response.choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| LLMError::ProviderError("No choices in response".to_string()))?;
+
+        let finish_reason = match choice.finish_reason.as_deref() {
+            Some("length") => FinishReason::Length,
+            Some("content_filter") => FinishReason::ContentFilter,
+            Some("tool_calls") => FinishReason::ToolCalls,
+            _ => FinishReason::Stop,
+        };
+
+        let usage = response.usage
+            .map(|u| TokenUsage {
+                prompt_tokens: u.prompt_tokens,
+                completion_tokens: u.completion_tokens,
+                total_tokens: u.total_tokens,
+            })
+            .unwrap_or(TokenUsage {
+                prompt_tokens: 0,
+                completion_tokens: 0,
+                total_tokens: 0,
+            });
+
+        let reasoning = choice.message.reasoning_content
+            .filter(|reasoning| !reasoning.is_empty());
+        let tool_calls = choice.message.tool_calls
+            .as_deref()
+            .filter(|calls| !calls.is_empty())
+            .map(ProviderUtils::convert_tool_calls_from_standard);
+
+        let metadata = (reasoning.is_some() || tool_calls.is_some()).then(|| MessageMetadata {
+            model_used: Some(response.model.clone()),
+            tokens_used: None,
+            response_time_ms: None,
+            tool_calls,
+            custom_data: reasoning
+                .map(|reasoning| {
+                    HashMap::from([
+                        (REASONING_CONTENT_KEY.to_string(), serde_json::json!(reasoning)),
+                    ])
+                })
+                .unwrap_or_default(),
+        });
+
+        Ok(ChatResponse {
+            content: choice.message.content.unwrap_or_default(),
+            model_used: response.model,
+            usage,
+            finish_reason,
+            metadata,
+        })
+    }
+}
+
+#[async_trait]
+impl LLMProvider for DeepSeekProvider {
+    async fn chat_completion(&self, request: ChatRequest) -> Result<ChatResponse, LLMError> {
+        let url = format!("{}/chat/completions", self.config.base_url);
+        let headers = self.build_headers();
+        let deepseek_request = self.convert_request(request);
+
+        let response: DeepSeekChatResponse = self.http_client
+            .post_json(&url, &headers, &deepseek_request)
+            .await?;
+
+        self.convert_response(response)
+    }
+
+    async fn generate_embedding(&self, _text: &str) -> Result<Vec<f32>, LLMError> {
+        Err(LLMError::ProviderError(
+            "DeepSeek does not support embeddings. Use OpenAI or another provider for embeddings.".to_string()
+        ))
+    }
+
+    async fn stream_chat_completion(
+        &self,
+        request: ChatRequest,
+    ) -> Result<Box<dyn Stream<Item = Result<ChatStreamChunk, LLMError>> + Send + Unpin>, LLMError> {
+        let url = format!("{}/chat/completions", self.config.base_url);
+        let headers = self.build_headers();
+        let mut deepseek_request = self.convert_request(request);
+        deepseek_request.stream = true;
+
+        let response = self.http_client
+            .post_stream(&url, &headers, &deepseek_request)
+            .await?;
+
+        let byte_stream = response.bytes_stream().map(|result| {
+            result.map_err(|e| LLMError::NetworkError(format!("Stream error: {}", e)))
+        });
+
+        // Reasoning arrives as `delta.reasoning_content` before the answer
+        Ok(StreamAdapter::from_bytes_stream(Box::pin(byte_stream)))
+    }
+
+    fn get_model_info(&self) -> Vec<ModelInfo> {
+        ProviderUtils::create_default_models("deepseek")
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self) -> Result<ConnectionTestResult, LLMError> {
+        let start_time = std::time::Instant::now();
+        let url = format!("{}/models", self.config.base_url);
+
+        let result: Result<DeepSeekModelsResponse, LLMError> = self.http_client
+            .get(&url, &self.build_headers())
+            .await;
+        let response_time = start_time.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(response) => {
+                let models = self.get_model_info();
+                let model_info = response.data
+                    .first()
+                    .and_then(|m| models.iter().find(|info| info.id == m.id).cloned())
+                    .or_else(|| models.first().cloned());
+
+                Ok(ConnectionTestResult {
+                    success: true,
+                    response_time_ms: response_time,
+                    error_message: None,
+                    model_info,
+                })
+            }
+            Err(e) => Ok(ConnectionTestResult {
+                success: false,
+                response_time_ms: response_time,
+                error_message: Some(e.to_string()),
+                model_info: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::ChatMessage;
+
+    fn create_test_provider() -> DeepSeekProvider {
+        DeepSeekProvider::new("sk-test1234567890".to_string(), None).unwrap()
+    }
+
+    #[test]
+    fn test_provider_creation() {
+        let provider = create_test_provider();
+        assert_eq!(provider.config.base_url, "https://api.deepseek.com/v1");
+        assert!(DeepSeekProvider::new("".to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_convert_request_flattens_messages() {
+        let provider = create_test_provider();
+        let request = ChatRequest {
+            messages: vec![
+                ChatMessage::new_system_message("Think step by step".to_string()),
+                ChatMessage::new_user_message("2 + 2?".to_string()),
+            ],
+            model: "deepseek-reasoner".to_string(),
+            temperature: None,
+            max_tokens: Some(512),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop_sequences: None,
+            stream: true,
+            stream_options: None,
+            tenant_id: uuid::Uuid::new_v4(),
+            response_format: None,
+            tools: None,
+        };
+
+        let deepseek_request = provider.convert_request(request);
+        assert_eq!(deepseek_request.messages.len(), 2);
+        assert_eq!(deepseek_request.messages[0].role, "system");
+        assert_eq!(deepseek_request.messages[1].content, "2 + 2?");
+        assert!(deepseek_request.stream);
+    }
+
+    #[test]
+    fn test_convert_response_keeps_reasoning() {
+        let provider = create_test_provider();
+        let response: DeepSeekChatResponse = serde_json::from_value(serde_json::json!({
+            "model": "deepseek-reasoner",
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": "4",
+                    "reasoning_content": "2 plus 2 is 4."
+                },
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 10, "completion_tokens": 8, "total_tokens": 18 }
+        }))
+        .unwrap();
+
+        let chat_response = provider.convert_response(response).unwrap();
+        assert_eq!(chat_response.content, "4");
+        assert_eq!(chat_response.usage.total_tokens, 18);
+        let metadata = chat_response.metadata.unwrap();
+        assert_eq!(
+            metadata.custom_data.get(REASONING_CONTENT_KEY),
+            Some(&serde_json::json!("2 plus 2 is 4."))
+        );
+    }
+}
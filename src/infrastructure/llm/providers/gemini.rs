@@ -0,0 +1,437 @@
+use crate::domain::services::llm_service::{
+    LLMProvider, LLMError, ChatRequest, ChatResponse, ChatStreamChunk, ModelInfo,
+    ConnectionTestResult, TokenUsage, FinishReason
+};
+use crate::infrastructure::llm::providers::{
+    GeminiContent, GeminiPart, HttpClient, HttpClientConfig, ProviderConfig, ProviderUtils
+};
+use crate::infrastructure::llm::streaming::StreamAdapter;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+
+/// Google Gemini API provider implementation
+pub struct GeminiProvider {
+    config: ProviderConfig,
+    http_client: HttpClient,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiChatRequest {
+    contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    generation_config: GeminiGenerationConfig,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiGenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiChatResponse {
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+    usage_metadata: Option<GeminiUsage>,
+    model_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiCandidate {
+    content: Option<GeminiContent>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsage {
+    #[serde(default)]
+    prompt_token_count: u32,
+    #[serde(default)]
+    candidates_token_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct GeminiEmbeddingRequest {
+    model: String,
+    content: GeminiContent,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiEmbeddingResponse {
+    embedding: GeminiEmbedding,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiEmbedding {
+    values: Vec<f32>,
+}
+
+impl GeminiProvider {
+    const EMBEDDING_MODEL: &'static str = "text-embedding-004";
+
+    pub fn new(api_key: String) -> Result<Self, LLMError> {
+        ProviderUtils::validate_api_key(&api_key, "gemini")?;
+
+        let base_url = "https://generativelanguage.googleapis.com/v1beta".to_string();
+
+        let config = ProviderConfig {
+            api_key,
+            base_url,
+            default_model: "gemini-1.5-flash".to_string(),
+            http_config: HttpClientConfig::default(),
+            custom_headers: HashMap::new(),
+        };
+
+        let http_client = HttpClient::new(config.http_config.clone())?.with_provider("gemini");
+
+        Ok(Self {
+            config,
+            http_client,
+        })
+    }
+
+    pub fn with_custom_config(mut self, http_config: HttpClientConfig) -> Result<Self, LLMError> {
+        self.http_client = HttpClient::new(http_config.clone())?.with_provider("gemini");
+        self.config.http_config = http_config;
+        Ok(self)
+    }
+
+    pub fn add_custom_header(&mut self, key: String, value: String) {
+        self.config.custom_headers.insert(key, value);
+    }
+
+    fn build_headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("x-goog-api-key".to_string(), self.config.api_key.clone());
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+
+        for (key, value) in &self.config.custom_headers {
+            headers.insert(key.clone(), value.clone());
+        }
+
+        headers
+    }
+
+    fn generate_url(&self, model: &str) -> String {
+        format!("{}/models/{}:generateContent", self.config.base_url, model)
+    }
+
+    fn stream_url(&self, model: &str) -> String {
+        format!("{}/models/{}:streamGenerateContent?alt=sse", self.config.base_url, model)
+    }
+
+    fn convert_request(&self, request: &ChatRequest) -> GeminiChatRequest {
+        let (system_instruction, contents) = ProviderUtils::convert_messages_to_gemini(&request.messages);
+
+        // Gemini has no json_schema mode on this endpoint, any structured format maps to JSON output
+        let response_mime_type = request
+            .response_format
+            .as_ref()
+            .map(|_| "application/json".to_string());
+
+        GeminiChatRequest {
+            contents,
+            system_instruction,
+            generation_config: GeminiGenerationConfig {
+                temperature: request.temperature,
+                max_output_tokens: request.max_tokens,
+                top_p: request.top_p,
+                stop_sequences: request.stop_sequences.clone(),
+                response_mime_type,
+            },
+        }
+    }
+
+    fn convert_response(&self, response: GeminiChatResponse, model: String) -> Result<ChatResponse, LLMError> {
+        let candidate = response.candidates
+            .first()
+            .ok_or_else(|| LLMError::ProviderError("No candidates in response".to_string()))?;
+
+        let content = candidate.content
+            .as_ref()
+            .map(|c| c.parts.iter().filter_map(|p| p.text.as_deref()).collect::<String>())
+            .unwrap_or_default();
+
+        let finish_reason = match candidate.finish_reason.as_deref() {
+            Some("MAX_TOKENS") => FinishReason::Length,
+            Some("SAFETY") | Some("RECITATION") | Some("BLOCKLIST") | Some("PROHIBITED_CONTENT") => {
+                FinishReason::ContentFilter
+            }
+            _ => FinishReason::Stop,
+        };
+
+        let usage = response.usage_metadata
+            .map(|u| TokenUsage {
+                prompt_tokens: u.prompt_token_count,
+                completion_tokens: u.candidates_token_count,
+                total_tokens: u.prompt_token_count + u.candidates_token_count,
+            })
+            .unwrap_or(TokenUsage {
+                prompt_tokens: 0,
+                completion_tokens: 0,
+                total_tokens: 0,
+            });
+
+        Ok(ChatResponse {
+            content,
+            model_used: response.model_version.unwrap_or(model),
+            usage,
+            finish_reason,
+            metadata: None,
+        })
+    }
+
+    async fn make_test_request(&self) -> Result<(), LLMError> {
+        let url = self.generate_url(&self.config.default_model);
+        let headers = self.build_headers();
+
+        let test_request = GeminiChatRequest {
+            contents: vec![GeminiContent {
+                role: Some("user".to_string()),
+                parts: vec![GeminiPart {
+                    text: Some("Hello".to_string()),
+                    ..Default::default()
+                }],
+            }],
+            system_instruction: None,
+            generation_config: GeminiGenerationConfig {
+                temperature: Some(0.1),
+                max_output_tokens: Some(10),
+                ..Default::default()
+            },
+        };
+
+        let _response: GeminiChatResponse = self.http_client
+            .post_json(&url, &headers, &test_request)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LLMProvider for GeminiProvider {
+    async fn chat_completion(&self, request: ChatRequest) -> Result<ChatResponse, LLMError> {
+        let url = self.generate_url(&request.model);
+        let headers = self.build_headers();
+        let gemini_request = self.convert_request(&request);
+
+        let response: GeminiChatResponse = self.http_client
+            .post_json(&url, &headers, &gemini_request)
+            .await?;
+
+        self.convert_response(response, request.model)
+    }
+
+    async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, LLMError> {
+        let url = format!("{}/models/{}:embedContent", self.config.base_url, Self::EMBEDDING_MODEL);
+        let headers = self.build_headers();
+
+        let request = GeminiEmbeddingRequest {
+            model: format!("models/{}", Self::EMBEDDING_MODEL),
+            content: GeminiContent {
+                role: None,
+                parts: vec![GeminiPart {
+                    text: Some(text.to_string()),
+                    ..Default::default()
+                }],
+            },
+        };
+
+        let response: GeminiEmbeddingResponse = self.http_client
+            .post_json(&url, &headers, &request)
+            .await?;
+
+        Ok(response.embedding.values)
+    }
+
+    async fn stream_chat_completion(
+        &self,
+        request: ChatRequest,
+    ) -> Result<Box<dyn Stream<Item = Result<ChatStreamChunk, LLMError>> + Send + Unpin>, LLMError> {
+        let url = self.stream_url(&request.model);
+        let headers = self.build_headers();
+        let gemini_request = self.convert_request(&request);
+
+        let response = self.http_client
+            .post_stream(&url, &headers, &gemini_request)
+            .await?;
+
+        // Gemini separates SSE events with CRLF, normalize so events split on "\n\n"
+        let byte_stream = response.bytes_stream().map(|result| {
+            result
+                .map(|bytes| bytes::Bytes::from(bytes.iter().copied().filter(|b| *b != b'\r').collect::<Vec<u8>>()))
+                .map_err(|e| LLMError::NetworkError(format!("Stream error: {}", e)))
+        });
+
+        Ok(StreamAdapter::from_bytes_stream(Box::pin(byte_stream)))
+    }
+
+    fn get_model_info(&self) -> Vec<ModelInfo> {
+        ProviderUtils::create_default_models("gemini")
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self) -> Result<ConnectionTestResult, LLMError> {
+        let start_time = std::time::Instant::now();
+
+        match self.make_test_request().await {
+            Ok(()) => {
+                let response_time = start_time.elapsed().as_millis() as u64;
+                Ok(ConnectionTestResult {
+                    success: true,
+                    response_time_ms: response_time,
+                    error_message: None,
+                    model_info: self
+                        .get_model_info()
+                        .into_iter()
+                        .find(|m| m.id == self.config.default_model),
+                })
+            }
+            Err(e) => {
+                let response_time = start_time.elapsed().as_millis() as u64;
+                Ok(ConnectionTestResult {
+                    success: false,
+                    response_time_ms: response_time,
+                    error_message: Some(e.to_string()),
+                    model_info: None,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ChatMessage, MessageRole};
+    use crate::domain::value_objects::chat_message::MessageContent;
+    use chrono::Utc;
+
+    fn create_test_provider() -> GeminiProvider {
+        GeminiProvider::new("AIza-test1234567890".to_string()).unwrap()
+    }
+
+    fn message(role: MessageRole, text: &str) -> ChatMessage {
+        ChatMessage {
+            role,
+            content: MessageContent::Text(text.to_string()),
+            metadata: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn create_test_request(messages: Vec<ChatMessage>) -> ChatRequest {
+        ChatRequest {
+            messages,
+            model: "gemini-1.5-flash".to_string(),
+            temperature: Some(0.7),
+            max_tokens: Some(1000),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop_sequences: None,
+            stream: false,
+            stream_options: None,
+            tenant_id: uuid::Uuid::new_v4(),
+            response_format: None,
+            tools: None,
+        }
+    }
+
+    #[test]
+    fn test_empty_api_key() {
+        assert!(GeminiProvider::new(String::new()).is_err());
+    }
+
+    #[test]
+    fn test_convert_request() {
+        let provider = create_test_provider();
+        let request = create_test_request(vec![
+            message(MessageRole::System, "You are a helpful assistant"),
+            message(MessageRole::User, "Hello"),
+            message(MessageRole::Assistant, "Hi!"),
+            message(MessageRole::User, "How are you?"),
+        ]);
+
+        let gemini_request = provider.convert_request(&request);
+        assert_eq!(gemini_request.contents.len(), 3);
+        assert_eq!(gemini_request.contents[0].role.as_deref(), Some("user"));
+        assert_eq!(gemini_request.contents[1].role.as_deref(), Some("model"));
+        assert_eq!(
+            gemini_request.system_instruction.unwrap().parts[0].text.as_deref(),
+            Some("You are a helpful assistant")
+        );
+        assert_eq!(gemini_request.generation_config.max_output_tokens, Some(1000));
+    }
+
+    #[test]
+    fn test_consecutive_roles_are_merged() {
+        let (_, contents) = ProviderUtils::convert_messages_to_gemini(&[
+            message(MessageRole::User, "First"),
+            message(MessageRole::Tool, "Tool output"),
+        ]);
+
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0].parts.len(), 2);
+    }
+
+    #[test]
+    fn test_convert_response() {
+        let provider = create_test_provider();
+        let response: GeminiChatResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "Hello "}, {"text": "there"}]},
+                "finishReason": "MAX_TOKENS"
+            }],
+            "usageMetadata": {"promptTokenCount": 4, "candidatesTokenCount": 2, "totalTokenCount": 6}
+        }))
+        .unwrap();
+
+        let chat_response = provider.convert_response(response, "gemini-1.5-flash".to_string()).unwrap();
+        assert_eq!(chat_response.content, "Hello there");
+        assert_eq!(chat_response.finish_reason, FinishReason::Length);
+        assert_eq!(chat_response.usage.total_tokens, 6);
+        assert_eq!(chat_response.model_used, "gemini-1.5-flash");
+    }
+
+    #[test]
+    fn test_urls() {
+        let provider = create_test_provider();
+        assert_eq!(
+            provider.generate_url("gemini-2.0-flash"),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent"
+        );
+        assert!(provider.stream_url("gemini-2.0-flash").ends_with(":streamGenerateContent?alt=sse"));
+    }
+
+    #[test]
+    fn test_get_model_info() {
+        let provider = create_test_provider();
+        let models = provider.get_model_info();
+
+        assert!(models.iter().any(|m| m.id == "gemini-1.5-pro"));
+        assert!(models.iter().any(|m| m.id == "gemini-1.5-flash"));
+        assert!(models.iter().any(|m| m.id == "gemini-2.0-flash"));
+    }
+}
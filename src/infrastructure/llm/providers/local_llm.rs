@@ -116,7 +116,7 @@ impl LocalLLMProvider {
             custom_headers: HashMap::new(),
         };
 
-        let http_client = HttpClient::new(config.http_config.clone())?;
+        let http_client = HttpClient::new(config.http_config.clone())?.with_provider("local_llm");
 
         Ok(Self {
             config,
@@ -135,7 +135,7 @@ impl LocalLLMProvider {
     }
 
     pub fn with_custom_config(mut self, http_config: HttpClientConfig) -> Result<Self, LLMError> {
-        self.http_client = HttpClient::new(http_config.clone())?;
+        self.http_client = HttpClient::new(http_config.clone())?.with_provider("local_llm");
         self.config.http_config = http_config;
         Ok(self)
     }
@@ -161,38 +161,10 @@ impl LocalLLMProvider {
     }
 
     fn convert_request(&self, request: ChatRequest) -> LocalLLMChatRequest {
-        use crate::domain::value_objects::chat_message::{MessageContent, ContentPart};
-        
         let messages = request.messages
             .iter()
             .map(|msg| {
-                let content = match &msg.content {
-                    MessageContent::Text(text) => serde_json::json!(text),
-                    MessageContent::Multimodal(parts) => {
-                        let content_parts: Vec<serde_json::Value> = parts
-                            .iter()
-                            .map(|part| match part {
-                                ContentPart::Text { text } => serde_json::json!({
-                                    "type": "text",
-                                    "text": text
-                                }),
-                                ContentPart::ImageUrl { image_url } => {
-                                    let mut img_obj = serde_json::json!({
-                                        "type": "image_url",
-                                        "image_url": {
-                                            "url": image_url.url
-                                        }
-                                    });
-                                    if let Some(detail) = &image_url.detail {
-                                        img_obj["image_url"]["detail"] = serde_json::json!(detail);
-                                    }
-                                    img_obj
-                                }
-                            })
-                            .collect();
-                        serde_json::json!(content_parts)
-                    }
-                };
+                let content = ProviderUtils::convert_content_to_standard(&msg.content);
                 
                 LocalLLMMessage {
                     role: format!("{:?}", msg.role).to_lowercase(),
@@ -1,21 +1,34 @@
 pub mod openai;
 pub mod claude;
 pub mod local_llm;
+pub mod gemini;
+pub mod ollama;
+pub mod deepseek;
 
 pub use openai::OpenAIProvider;
 pub use claude::ClaudeProvider;
 pub use local_llm::LocalLLMProvider;
+pub use gemini::GeminiProvider;
+pub use ollama::OllamaProvider;
+pub use deepseek::DeepSeekProvider;
 
-use crate::domain::services::llm_service::{LLMError, ModelInfo};
+use crate::domain::services::llm_service::{LLMError, ModelInfo, ToolDefinition};
+use crate::domain::value_objects::chat_message::{ContentPart, ImageData, MessageContent, ToolCall};
+use crate::infrastructure::llm::telemetry::{self, LlmCallSpan};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Key under `MessageMetadata::custom_data` holding the reasoning a model
+/// returned alongside its answer
+pub const REASONING_CONTENT_KEY: &str = "reasoning_content";
+
 /// Common HTTP client configuration
 #[derive(Debug, Clone)]
 pub struct HttpClientConfig {
     pub timeout: std::time::Duration,
     pub max_retries: u32,
     pub user_agent: String,
+    pub retry_policy: RetryPolicy,
 }
 
 impl Default for HttpClientConfig {
@@ -24,6 +37,58 @@ impl Default for HttpClientConfig {
             timeout: std::time::Duration::from_secs(300),
             max_retries: 3,
             user_agent: "agent-platform/0.1.0".to_string(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+/// How `HttpClient` waits between attempts on transient failures
+#[derive(Debug, Clone, PartialEq)]
+pub enum RetryPolicy {
+    NoRetry,
+    /// Delay doubles from `base_ms` up to `max_ms`, randomised by +/- `jitter`
+    /// (a fraction between 0.0 and 1.0 of the delay)
+    ExponentialBackoff { base_ms: u64, max_ms: u64, jitter: f64 },
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::ExponentialBackoff {
+            base_ms: 500,
+            max_ms: 30_000,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the retry following the zero-based `attempt`, or `None`
+    /// when the policy does not retry
+    pub fn backoff(&self, attempt: u32) -> Option<std::time::Duration> {
+        match self {
+            RetryPolicy::NoRetry => None,
+            RetryPolicy::ExponentialBackoff { base_ms, max_ms, jitter } => {
+                let delay = base_ms
+                    .saturating_mul(1u64 << attempt.min(32))
+                    .min(*max_ms) as f64;
+                let spread = delay * jitter.clamp(0.0, 1.0);
+                let offset = if spread > 0.0 {
+                    rand::Rng::gen_range(&mut rand::thread_rng(), -spread..=spread)
+                } else {
+                    0.0
+                };
+                Some(std::time::Duration::from_millis((delay + offset).max(0.0) as u64))
+            }
+        }
+    }
+
+    /// Longest wait between attempts, or `None` when the policy does not retry
+    pub fn max_delay(&self) -> Option<std::time::Duration> {
+        match self {
+            RetryPolicy::NoRetry => None,
+            RetryPolicy::ExponentialBackoff { max_ms, .. } => {
+                Some(std::time::Duration::from_millis(*max_ms))
+            }
         }
     }
 }
@@ -62,6 +127,8 @@ pub struct StandardMessage {
     pub role: String,
     pub content: Option<String>,
     pub tool_calls: Option<Vec<StandardToolCall>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +144,21 @@ pub struct StandardFunction {
     pub arguments: String,
 }
 
+/// Function offered to the model in the `tools` request field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandardTool {
+    pub r#type: String,
+    pub function: StandardFunctionDefinition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandardFunctionDefinition {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub parameters: serde_json::Value,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StandardUsage {
     pub prompt_tokens: u32,
@@ -84,10 +166,35 @@ pub struct StandardUsage {
     pub total_tokens: u32,
 }
 
+/// Gemini content block: a role plus a list of parts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    pub parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeminiPart {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, rename = "inlineData", skip_serializing_if = "Option::is_none")]
+    pub inline_data: Option<GeminiInlineData>,
+}
+
+/// Base64 media sent inside a Gemini part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiInlineData {
+    pub mime_type: String,
+    pub data: String,
+}
+
 /// HTTP client wrapper with common functionality
 pub struct HttpClient {
     client: reqwest::Client,
     config: HttpClientConfig,
+    provider: Option<String>,
 }
 
 impl HttpClient {
@@ -98,32 +205,204 @@ impl HttpClient {
             .build()
             .map_err(|e| LLMError::NetworkError(format!("Failed to create HTTP client: {}", e)))?;
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            provider: None,
+        })
     }
 
-    pub async fn post_json<T: Serialize, R: for<'de> Deserialize<'de>>(
+    /// Trace `post_json` and `post_stream` calls as calls to `provider`
+    pub fn with_provider(mut self, provider: &str) -> Self {
+        self.provider = Some(provider.to_string());
+        self
+    }
+
+    fn start_span<T: Serialize>(&self, url: &str, body: &T) -> Option<LlmCallSpan> {
+        let provider = self.provider.as_deref()?;
+        let model = serde_json::to_value(body)
+            .ok()
+            .and_then(|body| telemetry::request_model(&body, url));
+        Some(LlmCallSpan::start(provider, model.as_deref()))
+    }
+
+    /// Headers with the span's `X-Request-ID` unless the caller set one
+    fn traced_headers(
+        headers: &HashMap<String, String>,
+        span: Option<&LlmCallSpan>,
+    ) -> HashMap<String, String> {
+        let mut headers = headers.clone();
+        if let Some(span) = span {
+            if !headers
+                .keys()
+                .any(|key| key.eq_ignore_ascii_case(telemetry::REQUEST_ID_HEADER))
+            {
+                headers.insert(
+                    telemetry::REQUEST_ID_HEADER.to_string(),
+                    span.request_id().to_string(),
+                );
+            }
+        }
+        headers
+    }
+
+    fn build_post<T: Serialize>(
         &self,
         url: &str,
         headers: &HashMap<String, String>,
         body: &T,
-    ) -> Result<R, LLMError> {
+    ) -> reqwest::RequestBuilder {
         let mut request = self.client.post(url);
 
         for (key, value) in headers {
             request = request.header(key, value);
         }
 
-        let response = request
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| LLMError::NetworkError(format!("Request failed: {}", e)))?;
+        request.json(body)
+    }
+
+    /// Send a request, retrying connection failures, timeouts, 429 and 503
+    /// according to the configured `RetryPolicy`. Any other response is
+    /// returned as-is on the first attempt.
+    async fn send_with_retry<F>(
+        &self,
+        context: &str,
+        build_request: F,
+    ) -> Result<reqwest::Response, LLMError>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let mut attempt = 0;
+
+        loop {
+            let can_retry = attempt < self.config.max_retries;
+
+            match build_request().send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if !can_retry || !Self::is_retryable_status(status) {
+                        return Ok(response);
+                    }
+                    let Some(backoff) = self.config.retry_policy.backoff(attempt) else {
+                        return Ok(response);
+                    };
+                    // A server asking for a longer wait than the policy allows
+                    // gets the policy's longest delay instead
+                    let max_delay = self.config.retry_policy.max_delay().unwrap_or(backoff);
+                    let delay = Self::retry_after(&response)
+                        .map_or(backoff, |wait| wait.min(max_delay));
+
+                    tracing::warn!(
+                        "LLM request returned {} on attempt {}/{}. Retrying in {:?}",
+                        status,
+                        attempt + 1,
+                        self.config.max_retries + 1,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    let retryable = e.is_connect() || e.is_timeout();
+                    let backoff = if can_retry && retryable {
+                        self.config.retry_policy.backoff(attempt)
+                    } else {
+                        None
+                    };
+                    let Some(delay) = backoff else {
+                        return Err(Self::network_error(context, e));
+                    };
+
+                    tracing::warn!(
+                        "LLM request failed on attempt {}/{}: {}. Retrying in {:?}",
+                        attempt + 1,
+                        self.config.max_retries + 1,
+                        e,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Map a transport failure, keeping timeouts distinguishable from other
+    /// network errors
+    fn network_error(context: &str, e: reqwest::Error) -> LLMError {
+        if e.is_timeout() {
+            LLMError::Timeout(format!("{}: {}", context, e))
+        } else {
+            LLMError::NetworkError(format!("{}: {}", context, e))
+        }
+    }
+
+    fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+        status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+            || status.as_u16() == crate::infrastructure::llm::error_handling::OVERLOADED_STATUS
+    }
+
+    /// Parse a `Retry-After` header given either as delay seconds or an HTTP date
+    fn retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+        let value = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?;
+        Self::parse_retry_after(value, chrono::Utc::now())
+    }
+
+    fn parse_retry_after(
+        value: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<std::time::Duration> {
+        let value = value.trim();
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(std::time::Duration::from_secs(seconds));
+        }
+
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        let millis = (date.with_timezone(&chrono::Utc) - now).num_milliseconds().max(0);
+        Some(std::time::Duration::from_millis(millis as u64))
+    }
+
+    pub async fn post_json<T: Serialize, R: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+        body: &T,
+    ) -> Result<R, LLMError> {
+        let mut span = self.start_span(url, body);
+        let headers = Self::traced_headers(headers, span.as_ref());
+
+        let result = self.send_json(url, &headers, body, &mut span).await;
+        if let Some(span) = span {
+            span.end(result.as_ref().err());
+        }
+        result
+    }
+
+    async fn send_json<T: Serialize, R: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+        body: &T,
+        span: &mut Option<LlmCallSpan>,
+    ) -> Result<R, LLMError> {
+        let response = self
+            .send_with_retry("Request failed", || self.build_post(url, headers, body))
+            .await?;
 
         let status = response.status();
+        if let Some(span) = span.as_mut() {
+            span.record_status(status.as_u16());
+        }
+
         let response_text = response
             .text()
             .await
-            .map_err(|e| LLMError::NetworkError(format!("Failed to read response: {}", e)))?;
+            .map_err(|e| Self::network_error("Failed to read response", e))?;
 
         if !status.is_success() {
             return Err(crate::infrastructure::llm::ErrorMapper::map_http_error(
@@ -132,6 +411,12 @@ impl HttpClient {
             ));
         }
 
+        if let Some(span) = span.as_mut() {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(&response_text) {
+                span.record_usage(&value);
+            }
+        }
+
         serde_json::from_str(&response_text)
             .map_err(|e| LLMError::SerializationError(format!("Failed to parse response: {}", e)))
     }
@@ -142,31 +427,43 @@ impl HttpClient {
         headers: &HashMap<String, String>,
         body: &T,
     ) -> Result<reqwest::Response, LLMError> {
-        let mut request = self.client.post(url);
+        // Streamed spans end once the response headers arrive; token usage
+        // arrives in the stream and is not recorded on them
+        let mut span = self.start_span(url, body);
+        let headers = Self::traced_headers(headers, span.as_ref());
 
-        for (key, value) in headers {
-            request = request.header(key, value);
-        }
+        let result = self
+            .send_with_retry("Stream request failed", || self.build_post(url, &headers, body))
+            .await;
 
-        let response = request
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| LLMError::NetworkError(format!("Stream request failed: {}", e)))?;
+        let result = match result {
+            Ok(response) if !response.status().is_success() => {
+                let status = response.status();
+                if let Some(span) = span.as_mut() {
+                    span.record_status(status.as_u16());
+                }
+                let error_text = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                Err(crate::infrastructure::llm::ErrorMapper::map_http_error(
+                    status.as_u16(),
+                    &error_text,
+                ))
+            }
+            Ok(response) => {
+                if let Some(span) = span.as_mut() {
+                    span.record_status(response.status().as_u16());
+                }
+                Ok(response)
+            }
+            Err(e) => Err(e),
+        };
 
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(crate::infrastructure::llm::ErrorMapper::map_http_error(
-                status.as_u16(),
-                &error_text,
-            ));
+        if let Some(span) = span {
+            span.end(result.as_ref().err());
         }
-
-        Ok(response)
+        result
     }
 
     pub async fn get<R: for<'de> Deserialize<'de>>(
@@ -183,13 +480,13 @@ impl HttpClient {
         let response = request
             .send()
             .await
-            .map_err(|e| LLMError::NetworkError(format!("GET request failed: {}", e)))?;
+            .map_err(|e| Self::network_error("GET request failed", e))?;
 
         let status = response.status();
         let response_text = response
             .text()
             .await
-            .map_err(|e| LLMError::NetworkError(format!("Failed to read response: {}", e)))?;
+            .map_err(|e| Self::network_error("Failed to read response", e))?;
 
         if !status.is_success() {
             return Err(crate::infrastructure::llm::ErrorMapper::map_http_error(
@@ -216,11 +513,215 @@ impl ProviderUtils {
             .map(|msg| StandardMessage {
                 role: format!("{:?}", msg.role).to_lowercase(),
                 content: Some(msg.get_text_content()),
-                tool_calls: None, // TODO: Implement tool calls conversion
+                tool_calls: msg
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.tool_calls.as_deref())
+                    .filter(|calls| !calls.is_empty())
+                    .map(Self::convert_tool_calls_to_standard),
+                tool_call_id: msg.tool_call_id().map(str::to_string),
             })
             .collect()
     }
 
+    /// Convert message content to the OpenAI `content` format: a string, or
+    /// a list of `text` and `image_url` parts with inline images as data URLs
+    pub fn convert_content_to_standard(content: &MessageContent) -> serde_json::Value {
+        let parts = match content {
+            MessageContent::Text(text) => return serde_json::json!(text),
+            MessageContent::Multimodal(parts) => parts,
+        };
+
+        let parts: Vec<serde_json::Value> = parts
+            .iter()
+            .map(|part| match part {
+                ContentPart::Text { text } => serde_json::json!({
+                    "type": "text",
+                    "text": text
+                }),
+                ContentPart::ImageUrl { image_url } => {
+                    let mut img_obj = serde_json::json!({
+                        "type": "image_url",
+                        "image_url": {
+                            "url": image_url.url
+                        }
+                    });
+                    if let Some(detail) = &image_url.detail {
+                        img_obj["image_url"]["detail"] = serde_json::json!(detail);
+                    }
+                    img_obj
+                }
+                ContentPart::ImageData { image_data } => serde_json::json!({
+                    "type": "image_url",
+                    "image_url": {
+                        "url": image_data.to_data_url()
+                    }
+                }),
+            })
+            .collect();
+        serde_json::json!(parts)
+    }
+
+    /// Convert message content to Claude content blocks. Inline images,
+    /// including `data:` URLs, go in a `base64` source.
+    pub fn convert_content_to_claude(content: &MessageContent) -> serde_json::Value {
+        let parts = match content {
+            MessageContent::Text(text) => return serde_json::json!(text),
+            MessageContent::Multimodal(parts) => parts,
+        };
+
+        let base64_image = |image_data: &ImageData| serde_json::json!({
+            "type": "image",
+            "source": {
+                "type": "base64",
+                "media_type": image_data.media_type,
+                "data": image_data.base64
+            }
+        });
+
+        let parts: Vec<serde_json::Value> = parts
+            .iter()
+            .map(|part| match part {
+                ContentPart::Text { text } => serde_json::json!({
+                    "type": "text",
+                    "text": text
+                }),
+                ContentPart::ImageUrl { image_url } => match image_url.inline_data() {
+                    Some(image_data) => base64_image(&image_data),
+                    None => serde_json::json!({
+                        "type": "image",
+                        "source": {
+                            "type": "url",
+                            "url": image_url.url
+                        }
+                    }),
+                },
+                ContentPart::ImageData { image_data } => base64_image(image_data),
+            })
+            .collect();
+        serde_json::json!(parts)
+    }
+
+    /// Inline images of the content, for APIs that only take base64 images
+    pub fn inline_images(content: &MessageContent) -> Vec<ImageData> {
+        match content {
+            MessageContent::Text(_) => Vec::new(),
+            MessageContent::Multimodal(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::ImageUrl { image_url } => image_url.inline_data(),
+                    ContentPart::ImageData { image_data } => Some(image_data.clone()),
+                    ContentPart::Text { .. } => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Convert tool definitions to the OpenAI `tools` format
+    pub fn convert_tools_to_standard(tools: &[ToolDefinition]) -> Vec<StandardTool> {
+        tools
+            .iter()
+            .map(|tool| StandardTool {
+                r#type: "function".to_string(),
+                function: StandardFunctionDefinition {
+                    name: tool.name.clone(),
+                    description: tool.description.clone(),
+                    parameters: tool.parameters.clone(),
+                },
+            })
+            .collect()
+    }
+
+    /// Convert the tool calls of an assistant message to the OpenAI format
+    pub fn convert_tool_calls_to_standard(tool_calls: &[ToolCall]) -> Vec<StandardToolCall> {
+        tool_calls
+            .iter()
+            .map(|call| StandardToolCall {
+                id: call.call_id.clone().unwrap_or_else(|| call.tool_id.clone()),
+                r#type: "function".to_string(),
+                function: StandardFunction {
+                    name: call.tool_name.clone(),
+                    arguments: call.parameters.to_string(),
+                },
+            })
+            .collect()
+    }
+
+    /// Convert tool calls returned by the model. Arguments that are not
+    /// valid JSON are kept as a string so the tool rejects them.
+    pub fn convert_tool_calls_from_standard(tool_calls: &[StandardToolCall]) -> Vec<ToolCall> {
+        tool_calls
+            .iter()
+            .map(|call| ToolCall {
+                call_id: Some(call.id.clone()),
+                tool_id: String::new(),
+                tool_name: call.function.name.clone(),
+                parameters: serde_json::from_str(&call.function.arguments)
+                    .unwrap_or_else(|_| serde_json::Value::String(call.function.arguments.clone())),
+                result: None,
+                error: None,
+                execution_time_ms: None,
+            })
+            .collect()
+    }
+
+    /// Convert domain ChatMessage to Gemini `contents`, returning the system
+    /// instruction separately. Gemini names the assistant role `model` and has
+    /// no tool role, so tool output is sent back as user content. Consecutive
+    /// messages with the same role are merged into one content block.
+    pub fn convert_messages_to_gemini(
+        messages: &[crate::domain::value_objects::ChatMessage],
+    ) -> (Option<GeminiContent>, Vec<GeminiContent>) {
+        use crate::domain::value_objects::MessageRole;
+
+        let mut system_parts = Vec::new();
+        let mut contents: Vec<GeminiContent> = Vec::new();
+
+        for msg in messages {
+            // Remote image URLs would have to go through the Files API first,
+            // so only inline images are sent
+            let mut parts = vec![GeminiPart {
+                text: Some(msg.get_text_content()),
+                ..Default::default()
+            }];
+            parts.extend(Self::inline_images(&msg.content).into_iter().map(|image| GeminiPart {
+                text: None,
+                inline_data: Some(GeminiInlineData {
+                    mime_type: image.media_type,
+                    data: image.base64,
+                }),
+            }));
+
+            let role = match msg.role {
+                MessageRole::System => {
+                    system_parts.extend(parts);
+                    continue;
+                }
+                MessageRole::Assistant => "model",
+                MessageRole::User | MessageRole::Tool => "user",
+            };
+
+            match contents.last_mut() {
+                Some(last) if last.role.as_deref() == Some(role) => last.parts.extend(parts),
+                _ => contents.push(GeminiContent {
+                    role: Some(role.to_string()),
+                    parts,
+                }),
+            }
+        }
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(GeminiContent {
+                role: None,
+                parts: system_parts,
+            })
+        };
+
+        (system_instruction, contents)
+    }
+
     /// Create default model info for a provider
     pub fn create_default_models(provider_name: &str) -> Vec<ModelInfo> {
         match provider_name {
@@ -282,6 +783,55 @@ impl ProviderUtils {
                     supports_vision: true,
                 },
             ],
+            "gemini" => vec![
+                ModelInfo {
+                    id: "gemini-1.5-pro".to_string(),
+                    name: "Gemini 1.5 Pro".to_string(),
+                    description: Some("Long-context model for complex reasoning tasks".to_string()),
+                    context_length: Some(2097152),
+                    supports_streaming: true,
+                    supports_tools: true,
+                    supports_vision: true,
+                },
+                ModelInfo {
+                    id: "gemini-1.5-flash".to_string(),
+                    name: "Gemini 1.5 Flash".to_string(),
+                    description: Some("Fast and cost-effective multimodal model".to_string()),
+                    context_length: Some(1048576),
+                    supports_streaming: true,
+                    supports_tools: true,
+                    supports_vision: true,
+                },
+                ModelInfo {
+                    id: "gemini-2.0-flash".to_string(),
+                    name: "Gemini 2.0 Flash".to_string(),
+                    description: Some("Next generation fast model with improved capabilities".to_string()),
+                    context_length: Some(1048576),
+                    supports_streaming: true,
+                    supports_tools: true,
+                    supports_vision: true,
+                },
+            ],
+            "deepseek" => vec![
+                ModelInfo {
+                    id: "deepseek-chat".to_string(),
+                    name: "DeepSeek Chat".to_string(),
+                    description: Some("General purpose chat model".to_string()),
+                    context_length: Some(65536),
+                    supports_streaming: true,
+                    supports_tools: true,
+                    supports_vision: false,
+                },
+                ModelInfo {
+                    id: "deepseek-reasoner".to_string(),
+                    name: "DeepSeek Reasoner".to_string(),
+                    description: Some("Reasoning model that streams its chain of thought".to_string()),
+                    context_length: Some(65536),
+                    supports_streaming: true,
+                    supports_tools: false,
+                    supports_vision: false,
+                },
+            ],
             _ => vec![
                 ModelInfo {
                     id: "default".to_string(),
@@ -349,24 +899,73 @@ impl ProviderUtils {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::value_objects::chat_message::ImageUrl;
     use crate::domain::value_objects::{ChatMessage, MessageRole};
     use chrono::Utc;
 
+    #[test]
+    fn test_retry_policy_backoff_without_jitter() {
+        let policy = RetryPolicy::ExponentialBackoff {
+            base_ms: 500,
+            max_ms: 3_000,
+            jitter: 0.0,
+        };
+
+        let delays: Vec<u64> = (0..4)
+            .map(|attempt| policy.backoff(attempt).unwrap().as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![500, 1_000, 2_000, 3_000]);
+        assert_eq!(RetryPolicy::NoRetry.backoff(0), None);
+        assert_eq!(policy.max_delay(), Some(std::time::Duration::from_millis(3_000)));
+        assert_eq!(RetryPolicy::NoRetry.max_delay(), None);
+    }
+
+    #[test]
+    fn test_retry_policy_backoff_jitter_bounds() {
+        let policy = RetryPolicy::ExponentialBackoff {
+            base_ms: 1_000,
+            max_ms: 30_000,
+            jitter: 0.5,
+        };
+
+        for _ in 0..50 {
+            let delay = policy.backoff(0).unwrap().as_millis() as u64;
+            assert!((500..=1_500).contains(&delay), "delay {} out of bounds", delay);
+        }
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(HttpClient::is_retryable_status(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(HttpClient::is_retryable_status(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert!(HttpClient::is_retryable_status(reqwest::StatusCode::from_u16(529).unwrap()));
+        assert!(!HttpClient::is_retryable_status(reqwest::StatusCode::BAD_REQUEST));
+        assert!(!HttpClient::is_retryable_status(reqwest::StatusCode::UNAUTHORIZED));
+        assert!(!HttpClient::is_retryable_status(reqwest::StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            HttpClient::parse_retry_after("120", now),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(
+            HttpClient::parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(std::time::Duration::from_secs(30))
+        );
+        assert_eq!(HttpClient::parse_retry_after("soon", now), None);
+    }
+
     #[test]
     fn test_convert_messages_to_standard() {
         let messages = vec![
-            ChatMessage {
-                role: MessageRole::User,
-                content: "Hello".to_string(),
-                metadata: None,
-                timestamp: Utc::now(),
-            },
-            ChatMessage {
-                role: MessageRole::Assistant,
-                content: "Hi there!".to_string(),
-                metadata: None,
-                timestamp: Utc::now(),
-            },
+            ChatMessage::new_user_message("Hello".to_string()),
+            ChatMessage::new_assistant_message("Hi there!".to_string()),
         ];
 
         let standard_messages = ProviderUtils::convert_messages_to_standard(&messages);
@@ -377,6 +976,86 @@ mod tests {
         assert_eq!(standard_messages[1].content, Some("Hi there!".to_string()));
     }
 
+    #[test]
+    fn test_convert_content_serializes_images() {
+        let content = MessageContent::Multimodal(vec![
+            ContentPart::Text { text: "What is this?".to_string() },
+            ContentPart::ImageData {
+                image_data: ImageData {
+                    base64: "aGVsbG8=".to_string(),
+                    media_type: "image/png".to_string(),
+                },
+            },
+            ContentPart::ImageUrl {
+                image_url: ImageUrl {
+                    url: "https://example.com/cat.jpg".to_string(),
+                    detail: None,
+                },
+            },
+        ]);
+
+        let standard = ProviderUtils::convert_content_to_standard(&content);
+        assert_eq!(standard[0]["type"], "text");
+        assert_eq!(standard[1]["image_url"]["url"], "data:image/png;base64,aGVsbG8=");
+        assert_eq!(standard[2]["image_url"]["url"], "https://example.com/cat.jpg");
+
+        let claude = ProviderUtils::convert_content_to_claude(&content);
+        assert_eq!(claude[1]["source"]["type"], "base64");
+        assert_eq!(claude[1]["source"]["media_type"], "image/png");
+        assert_eq!(claude[2]["source"]["type"], "url");
+
+        assert_eq!(
+            ProviderUtils::convert_content_to_standard(&MessageContent::Text("Hi".to_string())),
+            serde_json::json!("Hi")
+        );
+    }
+
+    #[test]
+    fn test_convert_messages_to_gemini_sends_inline_images() {
+        let messages = vec![ChatMessage::new_user_message_with_images(
+            "Describe".to_string(),
+            vec![
+                "data:image/jpeg;base64,/9j/".to_string(),
+                "https://example.com/cat.jpg".to_string(),
+            ],
+        )];
+
+        let (_, contents) = ProviderUtils::convert_messages_to_gemini(&messages);
+        assert_eq!(contents[0].parts.len(), 2);
+        assert_eq!(contents[0].parts[0].text.as_deref(), Some("Describe"));
+        let inline = contents[0].parts[1].inline_data.as_ref().unwrap();
+        assert_eq!(inline.mime_type, "image/jpeg");
+        assert_eq!(inline.data, "/9j/");
+    }
+
+    #[test]
+    fn test_convert_tool_calls_round_trip() {
+        let standard = vec![StandardToolCall {
+            id: "call_1".to_string(),
+            r#type: "function".to_string(),
+            function: StandardFunction {
+                name: "get_weather".to_string(),
+                arguments: r#"{"city":"Paris"}"#.to_string(),
+            },
+        }];
+
+        let calls = ProviderUtils::convert_tool_calls_from_standard(&standard);
+        assert_eq!(calls[0].call_id.as_deref(), Some("call_1"));
+        assert_eq!(calls[0].tool_name, "get_weather");
+        assert_eq!(calls[0].parameters, serde_json::json!({"city": "Paris"}));
+
+        let messages = vec![
+            ChatMessage::new_tool_call_message(String::new(), calls),
+            ChatMessage::new_tool_result_message("call_1".to_string(), "Sunny".to_string()),
+        ];
+        let standard_messages = ProviderUtils::convert_messages_to_standard(&messages);
+        let tool_calls = standard_messages[0].tool_calls.as_ref().unwrap();
+        assert_eq!(tool_calls[0].id, "call_1");
+        assert_eq!(tool_calls[0].function.arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(standard_messages[1].role, "tool");
+        assert_eq!(standard_messages[1].tool_call_id.as_deref(), Some("call_1"));
+    }
+
     #[test]
     fn test_validate_openai_api_key() {
         assert!(ProviderUtils::validate_api_key("sk-1234567890", "openai").is_ok());
@@ -0,0 +1,588 @@
+use crate::domain::services::llm_service::{
+    LLMProvider, LLMError, ChatRequest, ChatResponse, ChatStreamChunk, ModelInfo,
+    ConnectionTestResult, TokenUsage, FinishReason
+};
+use crate::infrastructure::llm::providers::{
+    HttpClient, HttpClientConfig, ProviderConfig, ProviderUtils
+};
+use async_trait::async_trait;
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Ollama provider using the native `/api/chat` endpoint, which streams
+/// newline-delimited JSON objects rather than SSE
+pub struct OllamaProvider {
+    config: ProviderConfig,
+    http_client: HttpClient,
+    /// Context window requested per call, `None` keeps the model default
+    num_ctx: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<OllamaMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
+    options: OllamaOptions,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct OllamaOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OllamaMessage {
+    role: String,
+    #[serde(default)]
+    content: String,
+    /// Base64 encoded images, without a data URL prefix
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
+}
+
+/// One `/api/chat` response object; streaming sends many, the last with `done`
+#[derive(Debug, Deserialize)]
+struct OllamaChatResponse {
+    #[serde(default)]
+    model: String,
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    done_reason: Option<String>,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagsResponse {
+    #[serde(default)]
+    models: Vec<OllamaModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaModel {
+    name: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    details: Option<OllamaModelDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaModelDetails {
+    #[serde(default)]
+    family: Option<String>,
+    /// e.g. "8.0B" or "137M"
+    #[serde(default)]
+    parameter_size: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaEmbeddingRequest {
+    model: String,
+    input: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+impl OllamaProvider {
+    pub fn new(base_url: String) -> Result<Self, LLMError> {
+        ProviderUtils::validate_base_url(&base_url)?;
+
+        let config = ProviderConfig {
+            api_key: String::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            default_model: "llama3".to_string(),
+            http_config: HttpClientConfig {
+                timeout: std::time::Duration::from_secs(60), // Local models might be slower
+                ..Default::default()
+            },
+            custom_headers: HashMap::new(),
+        };
+
+        let http_client = HttpClient::new(config.http_config.clone())?.with_provider("ollama");
+
+        Ok(Self {
+            config,
+            http_client,
+            num_ctx: None,
+        })
+    }
+
+    pub fn with_default_model(mut self, model: String) -> Self {
+        self.config.default_model = model;
+        self
+    }
+
+    pub fn with_num_ctx(mut self, num_ctx: u32) -> Self {
+        self.num_ctx = Some(num_ctx);
+        self
+    }
+
+    pub fn with_custom_config(mut self, http_config: HttpClientConfig) -> Result<Self, LLMError> {
+        self.http_client = HttpClient::new(http_config.clone())?.with_provider("ollama");
+        self.config.http_config = http_config;
+        Ok(self)
+    }
+
+    pub fn add_custom_header(&mut self, key: String, value: String) {
+        self.config.custom_headers.insert(key, value);
+    }
+
+    fn build_headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+
+        for (key, value) in &self.config.custom_headers {
+            headers.insert(key.clone(), value.clone());
+        }
+
+        headers
+    }
+
+    fn convert_request(&self, request: &ChatRequest) -> OllamaChatRequest {
+        let messages = request.messages
+            .iter()
+            .map(|msg| {
+                // Ollama only takes inline images, remote image URLs are dropped
+                let images = ProviderUtils::inline_images(&msg.content)
+                    .into_iter()
+                    .map(|image| image.base64)
+                    .collect();
+
+                OllamaMessage {
+                    role: format!("{:?}", msg.role).to_lowercase(),
+                    content: msg.get_text_content(),
+                    images,
+                }
+            })
+            .collect();
+
+        // `format` takes either "json" or a JSON schema
+        let format = request.response_format.as_ref().map(|format| {
+            match &format.json_schema {
+                Some(schema) => schema.schema.clone(),
+                None => serde_json::json!("json"),
+            }
+        });
+
+        OllamaChatRequest {
+            model: request.model.clone(),
+            messages,
+            stream: request.stream,
+            format,
+            options: OllamaOptions {
+                temperature: request.temperature,
+                num_ctx: self.num_ctx,
+                top_p: request.top_p,
+                num_predict: request.max_tokens,
+                stop: request.stop_sequences.clone(),
+            },
+        }
+    }
+
+    fn finish_reason(done_reason: Option<&str>) -> FinishReason {
+        match done_reason {
+            Some("length") => FinishReason::Length,
+            _ => FinishReason::Stop,
+        }
+    }
+
+    fn usage(response: &OllamaChatResponse) -> TokenUsage {
+        let prompt_tokens = response.prompt_eval_count.unwrap_or(0);
+        let completion_tokens = response.eval_count.unwrap_or(0);
+        TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    }
+
+    fn convert_response(&self, response: OllamaChatResponse) -> Result<ChatResponse, LLMError> {
+        if let Some(error) = response.error {
+            return Err(LLMError::ProviderError(error));
+        }
+
+        let usage = Self::usage(&response);
+        let message = response.message
+            .ok_or_else(|| LLMError::ProviderError("No message in response".to_string()))?;
+
+        Ok(ChatResponse {
+            content: message.content,
+            model_used: response.model,
+            usage,
+            finish_reason: Self::finish_reason(response.done_reason.as_deref()),
+            metadata: None,
+        })
+    }
+
+    /// Parse one line of a streamed `/api/chat` response
+    fn parse_stream_line(line: &str) -> Result<Option<ChatStreamChunk>, LLMError> {
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let response: OllamaChatResponse = serde_json::from_str(line)
+            .map_err(|e| LLMError::SerializationError(format!("Failed to parse stream chunk: {}", e)))?;
+
+        if let Some(error) = response.error {
+            return Err(LLMError::ProviderError(error));
+        }
+
+        let content = response.message
+            .as_ref()
+            .map(|m| m.content.clone())
+            .filter(|c| !c.is_empty());
+
+        let (finish_reason, usage) = if response.done {
+            (
+                Some(Self::finish_reason(response.done_reason.as_deref())),
+                Some(Self::usage(&response)),
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(Some(ChatStreamChunk {
+            content,
+            reasoning_content: None,
+            finish_reason,
+            usage,
+        }))
+    }
+
+    /// Split a byte stream into lines, carrying partial lines over to the
+    /// next read
+    fn ndjson_stream<S>(byte_stream: S) -> impl Stream<Item = Result<ChatStreamChunk, LLMError>> + Send
+    where
+        S: Stream<Item = Result<bytes::Bytes, LLMError>> + Send + 'static,
+    {
+        byte_stream
+            .map(Some)
+            .chain(stream::once(async { None }))
+            .scan(Vec::<u8>::new(), |buffer, item| {
+                let lines: Vec<Result<String, LLMError>> = match item {
+                    Some(Ok(bytes)) => {
+                        buffer.extend_from_slice(&bytes);
+                        let mut lines = Vec::new();
+                        while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                            let line: Vec<u8> = buffer.drain(..=pos).collect();
+                            lines.push(String::from_utf8(line).map_err(|e| {
+                                LLMError::SerializationError(format!("Invalid UTF-8: {}", e))
+                            }));
+                        }
+                        lines
+                    }
+                    Some(Err(e)) => vec![Err(e)],
+                    // The final object may arrive without a trailing newline
+                    None => {
+                        let rest = std::mem::take(buffer);
+                        vec![String::from_utf8(rest).map_err(|e| {
+                            LLMError::SerializationError(format!("Invalid UTF-8: {}", e))
+                        })]
+                    }
+                };
+                futures::future::ready(Some(stream::iter(lines)))
+            })
+            .flatten()
+            .filter_map(|line| {
+                let chunk = line.and_then(|line| Self::parse_stream_line(&line)).transpose();
+                futures::future::ready(chunk)
+            })
+    }
+
+    /// Rough context window for a model of the given size. `/api/tags` does
+    /// not report the context length, and larger models generally ship with
+    /// larger windows.
+    fn context_length_for(parameter_size: &str) -> Option<u32> {
+        let size = parameter_size.trim().to_uppercase();
+        let (number, scale) = match size.chars().last()? {
+            'B' => (&size[..size.len() - 1], 1.0),
+            'M' => (&size[..size.len() - 1], 0.001),
+            _ => return None,
+        };
+        let billions = number.parse::<f64>().ok()? * scale;
+
+        Some(if billions >= 30.0 {
+            32_768
+        } else if billions >= 7.0 {
+            8_192
+        } else {
+            4_096
+        })
+    }
+
+    fn convert_model(model: OllamaModel) -> ModelInfo {
+        let details = model.details.as_ref();
+        let context_length = details
+            .and_then(|d| d.parameter_size.as_deref())
+            .and_then(Self::context_length_for);
+        let description = details.map(|d| {
+            format!(
+                "Ollama model{}{}",
+                d.family.as_deref().map(|f| format!(" ({})", f)).unwrap_or_default(),
+                d.parameter_size.as_deref().map(|s| format!(", {} parameters", s)).unwrap_or_default(),
+            )
+        });
+
+        ModelInfo {
+            id: model.model.unwrap_or_else(|| model.name.clone()),
+            name: model.name,
+            description,
+            context_length,
+            supports_streaming: true,
+            supports_tools: false,
+            supports_vision: false,
+        }
+    }
+
+    /// Models pulled on the Ollama server, from `GET /api/tags`
+    pub async fn list_models(&self) -> Result<Vec<ModelInfo>, LLMError> {
+        let url = format!("{}/api/tags", self.config.base_url);
+        let response: OllamaTagsResponse = self.http_client
+            .get(&url, &self.build_headers())
+            .await?;
+
+        Ok(response.models.into_iter().map(Self::convert_model).collect())
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OllamaProvider {
+    async fn chat_completion(&self, request: ChatRequest) -> Result<ChatResponse, LLMError> {
+        let url = format!("{}/api/chat", self.config.base_url);
+        let headers = self.build_headers();
+        let mut ollama_request = self.convert_request(&request);
+        ollama_request.stream = false;
+
+        let response: OllamaChatResponse = self.http_client
+            .post_json(&url, &headers, &ollama_request)
+            .await?;
+
+        self.convert_response(response)
+    }
+
+    async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>, LLMError> {
+        let url = format!("{}/api/embed", self.config.base_url);
+        let headers = self.build_headers();
+
+        let request = OllamaEmbeddingRequest {
+            model: self.config.default_model.clone(),
+            input: text.to_string(),
+        };
+
+        let response: OllamaEmbeddingResponse = self.http_client
+            .post_json(&url, &headers, &request)
+            .await?;
+
+        response.embeddings
+            .into_iter()
+            .next()
+            .ok_or_else(|| LLMError::ProviderError("No embedding data in response".to_string()))
+    }
+
+    async fn stream_chat_completion(
+        &self,
+        request: ChatRequest,
+    ) -> Result<Box<dyn Stream<Item = Result<ChatStreamChunk, LLMError>> + Send + Unpin>, LLMError> {
+        let url = format!("{}/api/chat", self.config.base_url);
+        let headers = self.build_headers();
+        let mut ollama_request = self.convert_request(&request);
+        ollama_request.stream = true;
+
+        let response = self.http_client
+            .post_stream(&url, &headers, &ollama_request)
+            .await?;
+
+        let byte_stream = response.bytes_stream().map(|result| {
+            result.map_err(|e| LLMError::NetworkError(format!("Stream error: {}", e)))
+        });
+
+        Ok(Box::new(Box::pin(Self::ndjson_stream(byte_stream))))
+    }
+
+    fn get_model_info(&self) -> Vec<ModelInfo> {
+        ProviderUtils::create_default_models("ollama")
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn test_connection(&self) -> Result<ConnectionTestResult, LLMError> {
+        let start_time = std::time::Instant::now();
+
+        match self.list_models().await {
+            Ok(models) => {
+                let response_time = start_time.elapsed().as_millis() as u64;
+                Ok(ConnectionTestResult {
+                    success: true,
+                    response_time_ms: response_time,
+                    error_message: None,
+                    model_info: models
+                        .into_iter()
+                        .find(|m| m.id == self.config.default_model || m.name == self.config.default_model),
+                })
+            }
+            Err(e) => {
+                let response_time = start_time.elapsed().as_millis() as u64;
+                Ok(ConnectionTestResult {
+                    success: false,
+                    response_time_ms: response_time,
+                    error_message: Some(e.to_string()),
+                    model_info: None,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::llm_service::{JsonSchema, ResponseFormat};
+    use crate::domain::value_objects::{ChatMessage, MessageRole};
+    use crate::domain::value_objects::chat_message::MessageContent;
+    use chrono::Utc;
+
+    fn create_test_provider() -> OllamaProvider {
+        OllamaProvider::new("http://localhost:11434/".to_string()).unwrap()
+    }
+
+    fn create_test_request() -> ChatRequest {
+        ChatRequest {
+            messages: vec![ChatMessage {
+                role: MessageRole::User,
+                content: MessageContent::Text("Hello".to_string()),
+                metadata: None,
+                timestamp: Utc::now(),
+            }],
+            model: "llama3".to_string(),
+            temperature: Some(0.7),
+            max_tokens: Some(256),
+            top_p: Some(0.9),
+            frequency_penalty: None,
+            presence_penalty: None,
+            stop_sequences: None,
+            stream: false,
+            stream_options: None,
+            tenant_id: uuid::Uuid::new_v4(),
+            response_format: None,
+            tools: None,
+        }
+    }
+
+    #[test]
+    fn test_convert_request_uses_options() {
+        let provider = create_test_provider().with_num_ctx(8192);
+        let body = serde_json::to_value(provider.convert_request(&create_test_request())).unwrap();
+
+        assert_eq!(body["messages"][0], serde_json::json!({"role": "user", "content": "Hello"}));
+        assert_eq!(body["options"]["num_ctx"], 8192);
+        assert_eq!(body["options"]["num_predict"], 256);
+        assert!((body["options"]["top_p"].as_f64().unwrap() - 0.9).abs() < 1e-6);
+        assert!(body.get("format").is_none());
+    }
+
+    #[test]
+    fn test_convert_request_maps_json_schema_to_format() {
+        let provider = create_test_provider();
+        let mut request = create_test_request();
+        request.response_format = Some(ResponseFormat {
+            format_type: "json_schema".to_string(),
+            json_schema: Some(JsonSchema {
+                name: "answer".to_string(),
+                strict: true,
+                schema: serde_json::json!({"type": "object"}),
+            }),
+        });
+
+        let body = serde_json::to_value(provider.convert_request(&request)).unwrap();
+        assert_eq!(body["format"], serde_json::json!({"type": "object"}));
+    }
+
+    #[test]
+    fn test_convert_response() {
+        let provider = create_test_provider();
+        let response: OllamaChatResponse = serde_json::from_value(serde_json::json!({
+            "model": "llama3",
+            "message": {"role": "assistant", "content": "Hi there"},
+            "done": true,
+            "done_reason": "length",
+            "prompt_eval_count": 12,
+            "eval_count": 3
+        }))
+        .unwrap();
+
+        let chat_response = provider.convert_response(response).unwrap();
+        assert_eq!(chat_response.content, "Hi there");
+        assert_eq!(chat_response.finish_reason, FinishReason::Length);
+        assert_eq!(chat_response.usage.total_tokens, 15);
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_stream_handles_split_lines() {
+        let body = concat!(
+            r#"{"model":"llama3","message":{"role":"assistant","content":"Hel"},"done":false}"#, "\n",
+            r#"{"model":"llama3","message":{"role":"assistant","content":"lo"},"done":false}"#, "\n",
+            r#"{"model":"llama3","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop","prompt_eval_count":5,"eval_count":2}"#,
+        );
+        let (first, second) = body.split_at(50);
+        let byte_stream = stream::iter(vec![
+            Ok(bytes::Bytes::from(first.to_string())),
+            Ok(bytes::Bytes::from(second.to_string())),
+        ]);
+
+        let chunks: Vec<ChatStreamChunk> = OllamaProvider::ndjson_stream(byte_stream)
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].content.as_deref(), Some("Hel"));
+        assert_eq!(chunks[1].content.as_deref(), Some("lo"));
+        assert_eq!(chunks[2].content, None);
+        assert_eq!(chunks[2].finish_reason, Some(FinishReason::Stop));
+        assert_eq!(chunks[2].usage.as_ref().unwrap().total_tokens, 7);
+    }
+
+    #[test]
+    fn test_stream_error_line() {
+        let result = OllamaProvider::parse_stream_line(r#"{"error":"model not found"}"#);
+        assert!(matches!(result, Err(LLMError::ProviderError(msg)) if msg == "model not found"));
+    }
+
+    #[test]
+    fn test_convert_model_estimates_context_length() {
+        let model: OllamaModel = serde_json::from_value(serde_json::json!({
+            "name": "llama3:8b",
+            "model": "llama3:8b",
+            "details": {"family": "llama", "parameter_size": "8.0B"}
+        }))
+        .unwrap();
+
+        let info = OllamaProvider::convert_model(model);
+        assert_eq!(info.id, "llama3:8b");
+        assert_eq!(info.context_length, Some(8_192));
+        assert_eq!(OllamaProvider::context_length_for("137M"), Some(4_096));
+        assert_eq!(OllamaProvider::context_length_for("70.6B"), Some(32_768));
+        assert_eq!(OllamaProvider::context_length_for("unknown"), None);
+    }
+}
@@ -3,8 +3,10 @@ use crate::domain::services::llm_service::{
     ConnectionTestResult, TokenUsage, FinishReason
 };
 use crate::domain::services::StreamOptions;
+use crate::domain::value_objects::chat_message::MessageMetadata;
 use crate::infrastructure::llm::providers::{
-    HttpClient, HttpClientConfig, ProviderConfig, ProviderUtils, StandardChatResponse
+    HttpClient, HttpClientConfig, ProviderConfig, ProviderUtils, StandardChatResponse, StandardTool,
+    StandardToolCall
 };
 use crate::infrastructure::llm::streaming::StreamAdapter;
 use async_trait::async_trait;
@@ -12,7 +14,7 @@ use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use log::{debug};
+use tracing::{debug};
 
 /// OpenAI API provider implementation
 pub struct OpenAIProvider {
@@ -40,12 +42,18 @@ struct OpenAIChatRequest {
     stream_options: Option<StreamOptions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<StandardTool>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct OpenAIMessage {
     role: String,
     content: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<StandardToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -100,7 +108,7 @@ impl OpenAIProvider {
             custom_headers: HashMap::new(),
         };
 
-        let http_client = HttpClient::new(config.http_config.clone())?;
+        let http_client = HttpClient::new(config.http_config.clone())?.with_provider("openai");
 
         Ok(Self {
             config,
@@ -109,7 +117,7 @@ impl OpenAIProvider {
     }
 
     pub fn with_custom_config(mut self, http_config: HttpClientConfig) -> Result<Self, LLMError> {
-        self.http_client = HttpClient::new(http_config.clone())?;
+        self.http_client = HttpClient::new(http_config.clone())?.with_provider("openai");
         self.config.http_config = http_config;
         Ok(self)
     }
@@ -131,42 +139,21 @@ impl OpenAIProvider {
     }
 
     fn convert_request(&self, request: ChatRequest) -> OpenAIChatRequest {
-        use crate::domain::value_objects::chat_message::{MessageContent, ContentPart};
-        
         let messages = request.messages
             .iter()
             .map(|msg| {
-                let content = match &msg.content {
-                    MessageContent::Text(text) => serde_json::json!(text),
-                    MessageContent::Multimodal(parts) => {
-                        let content_parts: Vec<serde_json::Value> = parts
-                            .iter()
-                            .map(|part| match part {
-                                ContentPart::Text { text } => serde_json::json!({
-                                    "type": "text",
-                                    "text": text
-                                }),
-                                ContentPart::ImageUrl { image_url } => {
-                                    let mut img_obj = serde_json::json!({
-                                        "type": "image_url",
-                                        "image_url": {
-                                            "url": image_url.url
-                                        }
-                                    });
-                                    if let Some(detail) = &image_url.detail {
-                                        img_obj["image_url"]["detail"] = serde_json::json!(detail);
-                                    }
-                                    img_obj
-                                }
-                            })
-                            .collect();
-                        serde_json::json!(content_parts)
-                    }
-                };
+                let content = ProviderUtils::convert_content_to_standard(&msg.content);
                 
                 OpenAIMessage {
                     role: format!("{:?}", msg.role).to_lowercase(),
                     content,
+                    tool_calls: msg
+                        .metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.tool_calls.as_deref())
+                        .filter(|calls| !calls.is_empty())
+                        .map(ProviderUtils::convert_tool_calls_to_standard),
+                    tool_call_id: msg.tool_call_id().map(str::to_string),
                 }
             })
             .collect();
@@ -195,6 +182,7 @@ impl OpenAIProvider {
             stream: request.stream,
             stream_options: request.stream_options,
             response_format,
+            tools: request.tools.as_deref().map(ProviderUtils::convert_tools_to_standard),
         }
     }
 
@@ -207,9 +195,19 @@ impl OpenAIProvider {
             .as_ref()
             .ok_or_else(|| LLMError::ProviderError("No message in choice".to_string()))?;
 
-        let content = message.content
-            .as_ref()
-            .ok_or_else(|| LLMError::ProviderError("No content in message".to_string()))?;
+        // Tool call requests usually come without content
+        let tool_calls = message.tool_calls
+            .as_deref()
+            .filter(|calls| !calls.is_empty())
+            .map(ProviderUtils::convert_tool_calls_from_standard);
+
+        let content = match (&message.content, &tool_calls) {
+            (Some(content), _) => content.clone(),
+            (None, Some(_)) => String::new(),
+            (None, None) => {
+                return Err(LLMError::ProviderError("No content in message".to_string()));
+            }
+        };
 
         let finish_reason = match choice.finish_reason.as_deref() {
             Some("stop") => FinishReason::Stop,
@@ -231,12 +229,20 @@ impl OpenAIProvider {
                 total_tokens: 0,
             });
 
+        let metadata = tool_calls.map(|tool_calls| MessageMetadata {
+            model_used: Some(response.model.clone()),
+            tokens_used: None,
+            response_time_ms: None,
+            tool_calls: Some(tool_calls),
+            custom_data: HashMap::new(),
+        });
+
         Ok(ChatResponse {
-            content: content.clone(),
+            content,
             model_used: response.model,
             usage,
             finish_reason,
-            metadata: None,
+            metadata,
         })
     }
 
@@ -431,6 +437,7 @@ mod tests {
             stream_options: None,
             tenant_id: uuid::Uuid::new_v4(),
             response_format: None,
+            tools: None,
         };
 
         let openai_request = provider.convert_request(request);
@@ -442,6 +449,38 @@ mod tests {
         assert!(!openai_request.stream);
     }
 
+    #[test]
+    fn test_convert_response_with_tool_calls() {
+        let provider = create_test_provider();
+        let response: StandardChatResponse = serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1700000000,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }]
+        }))
+        .unwrap();
+
+        let chat_response = provider.convert_response(response).unwrap();
+        assert_eq!(chat_response.content, "");
+        assert_eq!(chat_response.finish_reason, FinishReason::ToolCalls);
+        let tool_calls = chat_response.metadata.unwrap().tool_calls.unwrap();
+        assert_eq!(tool_calls[0].call_id.as_deref(), Some("call_1"));
+        assert_eq!(tool_calls[0].parameters, serde_json::json!({"city": "Paris"}));
+    }
+
     #[test]
     fn test_build_headers() {
         let provider = create_test_provider();
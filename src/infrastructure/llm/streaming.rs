@@ -122,19 +122,80 @@ impl LLMStream {
             }
         }
 
+        // Gemini format
+        if let Some(candidates) = json.get("candidates").and_then(|c| c.as_array()) {
+            let candidate = candidates.first();
+
+            let content = candidate
+                .and_then(|c| c.get("content"))
+                .and_then(|c| c.get("parts"))
+                .and_then(|p| p.as_array())
+                .map(|parts| {
+                    parts
+                        .iter()
+                        .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+                        .collect::<String>()
+                })
+                .filter(|text| !text.is_empty());
+
+            let finish_reason = candidate
+                .and_then(|c| c.get("finishReason"))
+                .and_then(|r| r.as_str())
+                .map(|r| match r {
+                    "MAX_TOKENS" => FinishReason::Length,
+                    "SAFETY" | "RECITATION" | "BLOCKLIST" | "PROHIBITED_CONTENT" => FinishReason::ContentFilter,
+                    _ => FinishReason::Stop,
+                });
+
+            // Gemini repeats usageMetadata on intermediate chunks, only the final one is complete
+            let usage = if finish_reason.is_some() {
+                json.get("usageMetadata").and_then(|u| {
+                    let prompt_tokens = u.get("promptTokenCount")?.as_u64()? as u32;
+                    let completion_tokens = u
+                        .get("candidatesTokenCount")
+                        .and_then(|c| c.as_u64())
+                        .unwrap_or(0) as u32;
+                    Some(TokenUsage {
+                        prompt_tokens,
+                        completion_tokens,
+                        total_tokens: prompt_tokens + completion_tokens,
+                    })
+                })
+            } else {
+                None
+            };
+
+            return Ok(Some(ChatStreamChunk {
+                content,
+                reasoning_content: None,
+                finish_reason,
+                usage,
+            }));
+        }
+
         // Claude format
         if let Some(event_type) = json.get("type").and_then(|t| t.as_str()) {
             match event_type {
                 "content_block_delta" => {
-                    let content = json
-                        .get("delta")
-                        .and_then(|d| d.get("text"))
-                        .and_then(|t| t.as_str())
-                        .map(|s| s.to_string());
+                    let delta = json.get("delta");
+                    let delta_text = |field: &str| {
+                        delta
+                            .and_then(|d| d.get(field))
+                            .and_then(|t| t.as_str())
+                            .map(|s| s.to_string())
+                    };
+
+                    // Extended thinking streams `thinking_delta`s ahead of the answer
+                    let (content, reasoning_content) =
+                        match delta.and_then(|d| d.get("type")).and_then(|t| t.as_str()) {
+                            Some("thinking_delta") => (None, delta_text("thinking")),
+                            Some("signature_delta") => return Ok(None),
+                            _ => (delta_text("text"), None),
+                        };
 
                     return Ok(Some(ChatStreamChunk {
                         content,
-                        reasoning_content: None,
+                        reasoning_content,
                         finish_reason: None,
                         usage: None,
                     }));
@@ -484,6 +545,38 @@ mod tests {
         assert!(chunk.finish_reason.is_none());
     }
 
+    #[test]
+    fn test_parse_claude_thinking_chunk() {
+        let sse_data = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Let me see"}}"#;
+        let chunk = LLMStream::parse_sse_chunk(sse_data).unwrap().unwrap();
+        assert!(chunk.content.is_none());
+        assert_eq!(chunk.reasoning_content, Some("Let me see".to_string()));
+
+        let sse_data = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"abc"}}"#;
+        assert!(LLMStream::parse_sse_chunk(sse_data).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_deepseek_reasoning_chunk() {
+        let sse_data = r#"data: {"choices":[{"delta":{"content":null,"reasoning_content":"First"},"finish_reason":null}]}"#;
+        let chunk = LLMStream::parse_sse_chunk(sse_data).unwrap().unwrap();
+        assert!(chunk.content.is_none());
+        assert_eq!(chunk.reasoning_content, Some("First".to_string()));
+    }
+
+    #[test]
+    fn test_parse_gemini_sse_chunk() {
+        let sse_data = r#"data: {"candidates":[{"content":{"role":"model","parts":[{"text":"Hello"}]}}],"usageMetadata":{"promptTokenCount":5}}"#;
+        let chunk = LLMStream::parse_sse_chunk(sse_data).unwrap().unwrap();
+        assert_eq!(chunk.content, Some("Hello".to_string()));
+        assert!(chunk.usage.is_none());
+
+        let sse_data = r#"data: {"candidates":[{"content":{"role":"model","parts":[{"text":"!"}]},"finishReason":"STOP"}],"usageMetadata":{"promptTokenCount":5,"candidatesTokenCount":2,"totalTokenCount":7}}"#;
+        let chunk = LLMStream::parse_sse_chunk(sse_data).unwrap().unwrap();
+        assert_eq!(chunk.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(chunk.usage.unwrap().total_tokens, 7);
+    }
+
     #[test]
     fn test_parse_done_chunk() {
         let sse_data = "data: [DONE]";
@@ -0,0 +1,200 @@
+use opentelemetry::global::{self, BoxedSpan};
+use opentelemetry::trace::{Span, SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::domain::services::llm_service::LLMError;
+
+/// OTLP collector endpoint; LLM call traces are not exported when unset
+pub const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// Header carrying the trace context of an LLM call to the provider
+pub const REQUEST_ID_HEADER: &str = "X-Request-ID";
+
+const TRACER_NAME: &str = "avalon.llm";
+const SERVICE_NAME: &str = "agent-platform";
+
+/// Export traces over OTLP/gRPC to the endpoint in `OTEL_EXPORTER_OTLP_ENDPOINT`.
+/// Returns the provider so pending spans can be flushed on shutdown, or
+/// `None` when no endpoint is configured.
+pub fn init_tracer_from_env() -> Result<Option<TracerProvider>, String> {
+    let endpoint = match std::env::var(OTLP_ENDPOINT_ENV) {
+        Ok(endpoint) if !endpoint.trim().is_empty() => endpoint,
+        _ => return Ok(None),
+    };
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint.trim())
+        .build()
+        .map_err(|e| format!("Failed to create OTLP exporter: {}", e))?;
+
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new("service.name", SERVICE_NAME)]))
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+
+    Ok(Some(provider))
+}
+
+/// Span around one call to an LLM provider, named `llm.{provider}.chat_completion`
+pub struct LlmCallSpan {
+    span: BoxedSpan,
+    started_at: Instant,
+    request_id: String,
+}
+
+impl LlmCallSpan {
+    pub fn start(provider: &str, model: Option<&str>) -> Self {
+        let tracer = global::tracer(TRACER_NAME);
+
+        let mut attributes = vec![KeyValue::new("llm.provider", provider.to_string())];
+        if let Some(model) = model {
+            attributes.push(KeyValue::new("llm.model", model.to_string()));
+        }
+
+        let span = tracer
+            .span_builder(format!("llm.{}.chat_completion", provider))
+            .with_kind(SpanKind::Client)
+            .with_attributes(attributes)
+            .start(&tracer);
+
+        // The W3C traceparent of the span, so provider-side request logs can
+        // be joined to the trace; a random id when tracing is off
+        let context = Context::current().with_remote_span_context(span.span_context().clone());
+        let mut carrier = HashMap::new();
+        global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut carrier));
+        let request_id = carrier
+            .remove("traceparent")
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        Self {
+            span,
+            started_at: Instant::now(),
+            request_id,
+        }
+    }
+
+    /// Value to send as `X-Request-ID`
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    pub fn record_status(&mut self, status: u16) {
+        self.span.set_attribute(KeyValue::new("http.status_code", status as i64));
+    }
+
+    /// Record token usage from a response body of any supported provider
+    pub fn record_usage(&mut self, response: &Value) {
+        let (prompt_tokens, completion_tokens) = usage_tokens(response);
+        if let Some(tokens) = prompt_tokens {
+            self.span.set_attribute(KeyValue::new("llm.prompt_tokens", tokens as i64));
+        }
+        if let Some(tokens) = completion_tokens {
+            self.span.set_attribute(KeyValue::new("llm.completion_tokens", tokens as i64));
+        }
+    }
+
+    pub fn end(mut self, error: Option<&LLMError>) {
+        self.span.set_attribute(KeyValue::new(
+            "llm.latency_ms",
+            self.started_at.elapsed().as_millis() as i64,
+        ));
+        if let Some(error) = error {
+            self.span.set_status(Status::error(error.to_string()));
+        }
+        self.span.end();
+    }
+}
+
+/// Model of a request: the `model` field of the body, or the `models/{model}`
+/// segment of the URL for providers that put it there
+pub fn request_model(body: &Value, url: &str) -> Option<String> {
+    if let Some(model) = body.get("model").and_then(Value::as_str) {
+        return Some(model.to_string());
+    }
+
+    let (_, rest) = url.split_once("/models/")?;
+    let model = rest.split([':', '/', '?']).next()?;
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+/// Prompt and completion tokens reported in an OpenAI, Claude, Gemini or
+/// Ollama response body
+pub fn usage_tokens(response: &Value) -> (Option<u64>, Option<u64>) {
+    let usage = response.get("usage");
+    let gemini_usage = response.get("usageMetadata");
+    let first = |candidates: [Option<&Value>; 4]| {
+        candidates.into_iter().flatten().find_map(Value::as_u64)
+    };
+
+    let prompt_tokens = first([
+        usage.and_then(|usage| usage.get("prompt_tokens")),
+        usage.and_then(|usage| usage.get("input_tokens")),
+        gemini_usage.and_then(|usage| usage.get("promptTokenCount")),
+        response.get("prompt_eval_count"),
+    ]);
+    let completion_tokens = first([
+        usage.and_then(|usage| usage.get("completion_tokens")),
+        usage.and_then(|usage| usage.get("output_tokens")),
+        gemini_usage.and_then(|usage| usage.get("candidatesTokenCount")),
+        response.get("eval_count"),
+    ]);
+
+    (prompt_tokens, completion_tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_usage_tokens_across_providers() {
+        let openai = json!({"usage": {"prompt_tokens": 12, "completion_tokens": 34}});
+        let claude = json!({"usage": {"input_tokens": 5, "output_tokens": 6}});
+        let gemini = json!({"usageMetadata": {"promptTokenCount": 7, "candidatesTokenCount": 8}});
+        let ollama = json!({"prompt_eval_count": 9, "eval_count": 10});
+
+        assert_eq!(usage_tokens(&openai), (Some(12), Some(34)));
+        assert_eq!(usage_tokens(&claude), (Some(5), Some(6)));
+        assert_eq!(usage_tokens(&gemini), (Some(7), Some(8)));
+        assert_eq!(usage_tokens(&ollama), (Some(9), Some(10)));
+        assert_eq!(usage_tokens(&json!({"choices": []})), (None, None));
+    }
+
+    #[test]
+    fn test_request_model_from_body_or_url() {
+        assert_eq!(
+            request_model(&json!({"model": "gpt-4o"}), "https://api.openai.com/v1/chat/completions"),
+            Some("gpt-4o".to_string())
+        );
+        assert_eq!(
+            request_model(
+                &json!({"contents": []}),
+                "https://generativelanguage.googleapis.com/v1beta/models/gemini-pro:generateContent?key=k"
+            ),
+            Some("gemini-pro".to_string())
+        );
+        assert_eq!(request_model(&json!({}), "http://localhost:8000/v1/chat"), None);
+    }
+
+    #[test]
+    fn test_request_id_without_tracing_is_unique() {
+        let first = LlmCallSpan::start("openai", Some("gpt-4o"));
+        let second = LlmCallSpan::start("openai", Some("gpt-4o"));
+        assert!(!first.request_id().is_empty());
+        assert_ne!(first.request_id(), second.request_id());
+        first.end(None);
+        second.end(None);
+    }
+}
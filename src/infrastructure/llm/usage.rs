@@ -0,0 +1,179 @@
+use std::sync::OnceLock;
+
+use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer};
+use tiktoken_rs::CoreBPE;
+
+use crate::domain::services::llm_service::{ChatStreamChunk, TokenUsage};
+use crate::domain::value_objects::ChatMessage;
+
+/// Tokens OpenAI-style chat formats add around every message
+const TOKENS_PER_MESSAGE: u32 = 4;
+/// Tokens priming the assistant reply
+const TOKENS_PER_REPLY: u32 = 3;
+
+static CL100K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+static O200K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+
+/// Counts prompt tokens the way OpenAI-style chat formats bill them.
+///
+/// Falls back to ~4 characters per token when no tokenizer is available.
+pub struct TokenCounter {
+    bpe: Option<&'static CoreBPE>,
+}
+
+impl TokenCounter {
+    pub fn new(model: &str) -> Self {
+        Self {
+            bpe: tokenizer_for(model),
+        }
+    }
+
+    pub fn count_text(&self, text: &str) -> u32 {
+        match self.bpe {
+            Some(bpe) => count(bpe, text),
+            None => (text.len() / 4) as u32,
+        }
+    }
+
+    /// Tokens one message takes up in the prompt, framing included
+    pub fn count_message(&self, message: &ChatMessage) -> u32 {
+        TOKENS_PER_MESSAGE + self.count_text(&message.get_text_content())
+    }
+
+    /// Tokens a whole prompt takes up, including the reply primer
+    pub fn count_messages(&self, messages: &[ChatMessage]) -> u32 {
+        messages
+            .iter()
+            .map(|message| self.count_message(message))
+            .sum::<u32>()
+            + TOKENS_PER_REPLY
+    }
+}
+
+/// Collects token usage for a streamed completion.
+///
+/// Usage reported by the provider always wins. Providers that never report
+/// usage in the stream fall back to counting the prompt and the streamed
+/// content with a `TokenCounter`.
+pub struct StreamUsageCollector {
+    counter: TokenCounter,
+    prompt_tokens: u32,
+    completion_text: String,
+    provider_usage: Option<TokenUsage>,
+}
+
+impl StreamUsageCollector {
+    pub fn new(model: &str, prompt: &[ChatMessage]) -> Self {
+        let counter = TokenCounter::new(model);
+        let prompt_tokens = counter.count_messages(prompt);
+
+        Self {
+            counter,
+            prompt_tokens,
+            completion_text: String::new(),
+            provider_usage: None,
+        }
+    }
+
+    /// Record a chunk of the stream
+    pub fn observe(&mut self, chunk: &ChatStreamChunk) {
+        if let Some(content) = &chunk.content {
+            self.completion_text.push_str(content);
+        }
+        // Reasoning tokens are billed as completion tokens
+        if let Some(reasoning) = &chunk.reasoning_content {
+            self.completion_text.push_str(reasoning);
+        }
+        if let Some(usage) = &chunk.usage {
+            self.provider_usage = Some(usage.clone());
+        }
+    }
+
+    /// Whether the usage returned by `usage` was counted locally
+    pub fn is_estimated(&self) -> bool {
+        self.provider_usage.is_none()
+    }
+
+    /// Usage reported by the provider, or the local estimate when it sent none
+    pub fn usage(&self) -> TokenUsage {
+        if let Some(usage) = &self.provider_usage {
+            return usage.clone();
+        }
+
+        let completion_tokens = self.counter.count_text(&self.completion_text);
+
+        TokenUsage {
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens,
+            total_tokens: self.prompt_tokens + completion_tokens,
+        }
+    }
+
+}
+
+fn count(bpe: &CoreBPE, text: &str) -> u32 {
+    bpe.encode_with_special_tokens(text).len() as u32
+}
+
+/// Newer OpenAI models use o200k; everything else, including non-OpenAI
+/// models, is approximated with cl100k.
+fn tokenizer_for(model: &str) -> Option<&'static CoreBPE> {
+    let bpe = match get_tokenizer(model) {
+        Some(Tokenizer::O200kBase) => {
+            O200K.get_or_init(|| tiktoken_rs::o200k_base().ok())
+        }
+        _ => CL100K.get_or_init(|| tiktoken_rs::cl100k_base().ok()),
+    };
+
+    if bpe.is_none() {
+        tracing::warn!("Tokenizer unavailable for model {}, token counts are approximated", model);
+    }
+    bpe.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(content: &str, usage: Option<TokenUsage>) -> ChatStreamChunk {
+        ChatStreamChunk {
+            content: Some(content.to_string()),
+            reasoning_content: None,
+            finish_reason: None,
+            usage,
+        }
+    }
+
+    #[test]
+    fn test_estimates_usage_without_provider_data() {
+        let prompt = vec![ChatMessage::new_user_message("Hello there".to_string())];
+        let mut collector = StreamUsageCollector::new("gpt-4", &prompt);
+
+        collector.observe(&chunk("Hi! How can ", None));
+        collector.observe(&chunk("I help you today?", None));
+
+        let usage = collector.usage();
+        assert!(collector.is_estimated());
+        assert!(usage.prompt_tokens > TOKENS_PER_MESSAGE + TOKENS_PER_REPLY);
+        assert_eq!(usage.prompt_tokens, TokenCounter::new("gpt-4").count_messages(&prompt));
+        assert!(usage.completion_tokens > 0);
+        assert_eq!(usage.total_tokens, usage.prompt_tokens + usage.completion_tokens);
+    }
+
+    #[test]
+    fn test_provider_usage_takes_precedence() {
+        let prompt = vec![ChatMessage::new_user_message("Hello".to_string())];
+        let mut collector = StreamUsageCollector::new("claude-3-5-sonnet", &prompt);
+
+        let reported = TokenUsage {
+            prompt_tokens: 12,
+            completion_tokens: 30,
+            total_tokens: 42,
+        };
+        collector.observe(&chunk("Hi", None));
+        collector.observe(&chunk("", Some(reported.clone())));
+
+        assert!(!collector.is_estimated());
+        assert_eq!(collector.usage(), reported);
+    }
+}
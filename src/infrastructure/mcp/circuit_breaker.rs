@@ -0,0 +1,183 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::McpCircuitBreakerConfig;
+
+/// 熔断器状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 正常放行调用
+    Closed,
+    /// 连续失败过多，拒绝调用直到恢复超时
+    Open,
+    /// 恢复超时已过，只放行一个探测调用
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct CircuitBreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// 半开状态下正在进行的探测调用的开始时间
+    probe_started_at: Option<Instant>,
+}
+
+/// 熔断器状态快照，用于健康检查
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitSnapshot {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+}
+
+/// 单个MCP工具端点的熔断器
+///
+/// 连续失败 `failure_threshold` 次后由 `Closed` 转为 `Open`，
+/// `Open` 保持 `recovery_timeout_ms` 后转为 `HalfOpen` 并放行一个探测调用：
+/// 探测成功则恢复 `Closed`，失败则重新 `Open`
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    recovery_timeout: Duration,
+    state: Arc<Mutex<CircuitBreakerState>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: &McpCircuitBreakerConfig) -> Self {
+        Self {
+            failure_threshold: config.failure_threshold.max(1),
+            recovery_timeout: Duration::from_millis(config.recovery_timeout_ms),
+            state: Arc::new(Mutex::new(CircuitBreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started_at: None,
+            })),
+        }
+    }
+
+    /// 是否放行本次调用；放行后必须以 `record_success` 或 `record_failure` 报告结果
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                let recovered = inner
+                    .opened_at
+                    .map_or(true, |opened_at| now.duration_since(opened_at) >= self.recovery_timeout);
+                if recovered {
+                    inner.state = CircuitState::HalfOpen;
+                    inner.probe_started_at = Some(now);
+                }
+                recovered
+            }
+            CircuitState::HalfOpen => {
+                // 探测调用被取消时不会报告结果，超时后允许新的探测
+                let probe_abandoned = inner
+                    .probe_started_at
+                    .map_or(true, |started_at| now.duration_since(started_at) >= self.recovery_timeout);
+                if probe_abandoned {
+                    inner.probe_started_at = Some(now);
+                }
+                probe_abandoned
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.state.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started_at = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.state.lock().unwrap();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+
+        if inner.state == CircuitState::HalfOpen || inner.consecutive_failures >= self.failure_threshold {
+            if inner.state != CircuitState::Open {
+                tracing::warn!(
+                    consecutive_failures = inner.consecutive_failures,
+                    "MCP tool circuit opened"
+                );
+            }
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            inner.probe_started_at = None;
+        }
+    }
+
+    pub fn snapshot(&self) -> CircuitSnapshot {
+        let inner = self.state.lock().unwrap();
+        CircuitSnapshot {
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(failure_threshold: u32, recovery_timeout_ms: u64) -> CircuitBreaker {
+        CircuitBreaker::new(&McpCircuitBreakerConfig {
+            failure_threshold,
+            recovery_timeout_ms,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = breaker(3, 60_000);
+
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+        assert!(breaker.try_acquire());
+
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+        assert!(!breaker.try_acquire());
+    }
+
+    #[test]
+    fn test_half_open_allows_one_probe() {
+        let breaker = breaker(1, 0);
+        breaker.record_failure();
+
+        assert!(breaker.try_acquire());
+        assert_eq!(breaker.snapshot().state, CircuitState::HalfOpen);
+
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+
+        assert!(breaker.try_acquire());
+        breaker.record_success();
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+        assert_eq!(breaker.snapshot().consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_half_open_rejects_concurrent_calls() {
+        let breaker = breaker(1, 60_000);
+        {
+            let mut inner = breaker.state.lock().unwrap();
+            inner.state = CircuitState::Open;
+            inner.opened_at = Instant::now().checked_sub(Duration::from_secs(120));
+        }
+
+        assert!(breaker.try_acquire());
+        assert!(!breaker.try_acquire());
+    }
+}
@@ -54,6 +54,20 @@ pub enum MCPError {
     InternalError(String),
 }
 
+impl MCPError {
+    /// 是否表示工具端点本身不可用，计入熔断器的失败次数
+    pub fn is_endpoint_failure(&self) -> bool {
+        matches!(
+            self,
+            MCPError::HttpRequestFailed(_)
+                | MCPError::ExecutionTimeout
+                | MCPError::NetworkError(_)
+                | MCPError::RateLimitExceeded
+                | MCPError::InternalError(_)
+        )
+    }
+}
+
 impl From<reqwest::Error> for MCPError {
     fn from(error: reqwest::Error) -> Self {
         if error.is_timeout() {
@@ -248,6 +262,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_endpoint_failures() {
+        assert!(MCPError::ExecutionTimeout.is_endpoint_failure());
+        assert!(MCPError::NetworkError("connection refused".to_string()).is_endpoint_failure());
+        assert!(!MCPError::ParameterValidationFailed("missing city".to_string()).is_endpoint_failure());
+        assert!(!MCPError::AuthenticationFailed("bad token".to_string()).is_endpoint_failure());
+    }
+
     #[test]
     fn test_template_error_conversion() {
         use crate::infrastructure::mcp::template_engine::TemplateError;
@@ -196,10 +196,8 @@ impl HTTPRequestBuilder {
         config: &HTTPToolConfig,
         parameters: &Value,
     ) -> Result<RequestBuilder, MCPError> {
-        // 验证参数
-        config.validate_call_parameters(parameters)
-            .map_err(|e| MCPError::ParameterValidationFailed(e))?;
-
+        // 参数的JSON Schema校验由调用方完成（非严格模式下允许带着不合规的参数继续调用），
+        // 这里只处理无法构建请求的情况
         // 提取并分组参数
         let param_groups = ParameterGroups::extract_parameters(config, parameters)?;
 
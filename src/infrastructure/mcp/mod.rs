@@ -4,8 +4,13 @@ pub mod mcp_server_handler;
 pub mod protocol_handler;
 pub mod proxy_service;
 pub mod error_handling;
+pub mod circuit_breaker;
 pub mod template_engine;
 pub mod rmcp_server_handler;
+pub mod tenant_sse_server;
 
+pub use circuit_breaker::{CircuitBreaker, CircuitSnapshot, CircuitState};
 pub use proxy_service::*;
-pub use rmcp_server_handler::{RMCPServerConfig, RMCPServerHandler};
\ No newline at end of file
+pub use rmcp_server_handler::{RMCPServerConfig, RMCPServerHandler};
+pub use template_engine::ParameterTemplateEngine;
+pub use tenant_sse_server::TenantMCPSessions;
\ No newline at end of file
@@ -286,6 +286,14 @@ impl MCPProtocolHandler {
             );
         }
 
+        // 验证参数
+        if let Err(e) = tool.config.validate_call_parameters(&tool_call.arguments) {
+            return MCPResponse::error(
+                request.id,
+                MCPErrorResponse::invalid_params(e),
+            );
+        }
+
         // 执行工具
         match self.converter.execute_tool(tool, &tool_call.arguments).await {
             Ok(result) => {
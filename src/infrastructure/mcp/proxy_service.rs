@@ -1,5 +1,6 @@
 use async_trait::async_trait;
-use serde_json::Value;
+use serde::Serialize;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -7,11 +8,16 @@ use tokio::sync::RwLock;
 use crate::domain::{
     entities::MCPTool,
     services::mcp_tool_service::{ToolCallContext, ToolCallResult},
-    value_objects::ids::{MCPToolId, TenantId, UserId},
+    value_objects::{
+        ids::{MCPToolId, TenantId, UserId},
+        tool_config::ToolConfig,
+    },
 };
+use crate::config::McpCircuitBreakerConfig;
 use crate::error::PlatformError;
 use crate::infrastructure::mcp::{
-    error_handling::MCPError,
+    circuit_breaker::{CircuitBreaker, CircuitSnapshot},
+    error_handling::{MCPError, MCPErrorHandler},
     http_converter::HTTPToMCPConverter,
     protocol_handler::{MCPProtocolHandler, MCPRequest, MCPResponse},
 };
@@ -36,6 +42,14 @@ pub trait MCPProxyService: Send + Sync {
         context: ToolCallContext,
     ) -> Result<ToolCallResult, PlatformError>;
 
+    /// 以JSON-RPC `tools/call` 请求将调用转发到工具配置的端点
+    async fn forward_tool_call(
+        &self,
+        tool: &MCPTool,
+        parameters: Value,
+        context: ToolCallContext,
+    ) -> Result<ToolCallResult, PlatformError>;
+
     /// 处理MCP协议请求
     async fn handle_mcp_request(
         &self,
@@ -59,6 +73,20 @@ pub struct MCPToolStats {
     pub tools_by_type: HashMap<String, usize>,
 }
 
+/// 工具端点的熔断器状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCircuitStatus {
+    pub tool_id: MCPToolId,
+    pub tool_name: String,
+    #[serde(flatten)]
+    pub circuit: CircuitSnapshot,
+}
+
+struct ToolCircuit {
+    tool_name: String,
+    breaker: CircuitBreaker,
+}
+
 /// MCP代理服务实现
 pub struct MCPProxyServiceImpl {
     /// 按租户组织的协议处理器
@@ -67,6 +95,12 @@ pub struct MCPProxyServiceImpl {
     converter: HTTPToMCPConverter,
     /// 工具存储（按租户和工具ID组织）
     tools: Arc<RwLock<HashMap<TenantId, HashMap<MCPToolId, MCPTool>>>>,
+    /// 转发JSON-RPC请求的HTTP客户端
+    client: reqwest::Client,
+    /// 熔断器配置
+    circuit_config: McpCircuitBreakerConfig,
+    /// 每个工具端点的熔断器
+    circuits: Arc<RwLock<HashMap<MCPToolId, ToolCircuit>>>,
 }
 
 impl MCPProxyServiceImpl {
@@ -75,7 +109,110 @@ impl MCPProxyServiceImpl {
             handlers: Arc::new(RwLock::new(HashMap::new())),
             converter: HTTPToMCPConverter::new(),
             tools: Arc::new(RwLock::new(HashMap::new())),
+            client: reqwest::Client::new(),
+            circuit_config: McpCircuitBreakerConfig::default(),
+            circuits: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// 设置熔断器配置
+    pub fn with_circuit_breaker_config(mut self, config: McpCircuitBreakerConfig) -> Self {
+        self.circuit_config = config;
+        self
+    }
+
+    /// 各工具端点的熔断器状态，按工具名排序
+    pub async fn circuit_statuses(&self) -> Vec<ToolCircuitStatus> {
+        let circuits = self.circuits.read().await;
+
+        let mut statuses: Vec<_> = circuits
+            .iter()
+            .map(|(tool_id, circuit)| ToolCircuitStatus {
+                tool_id: *tool_id,
+                tool_name: circuit.tool_name.clone(),
+                circuit: circuit.breaker.snapshot(),
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.tool_name.cmp(&b.tool_name));
+        statuses
+    }
+
+    /// 获取或创建工具的熔断器
+    async fn circuit_breaker(&self, tool: &MCPTool) -> CircuitBreaker {
+        if let Some(circuit) = self.circuits.read().await.get(&tool.id) {
+            return circuit.breaker.clone();
         }
+
+        let mut circuits = self.circuits.write().await;
+        circuits
+            .entry(tool.id)
+            .or_insert_with(|| ToolCircuit {
+                tool_name: tool.name.clone(),
+                breaker: CircuitBreaker::new(&self.circuit_config),
+            })
+            .breaker
+            .clone()
+    }
+
+    /// 熔断器打开时返回的调用结果
+    fn circuit_open_result(&self) -> ToolCallResult {
+        ToolCallResult::error(self.circuit_config.open_message.clone(), 0)
+    }
+
+    /// 以JSON-RPC `tools/call` 请求调用工具端点
+    ///
+    /// 端点不可用时返回 `Err`；端点正常响应时返回 `Ok`，其中包含工具自身报告的错误
+    async fn send_tool_call(
+        &self,
+        tool: &MCPTool,
+        parameters: Value,
+        context: &ToolCallContext,
+    ) -> Result<Result<Value, String>, String> {
+        let ToolConfig::HTTP(config) = &tool.config;
+        let timeout_seconds = config.timeout_seconds.unwrap_or(30);
+
+        let request = MCPRequest::new(
+            "tools/call".to_string(),
+            Some(json!({
+                "name": tool.name,
+                "arguments": parameters,
+            })),
+        );
+
+        let mut builder = self
+            .client
+            .post(&config.endpoint)
+            .timeout(std::time::Duration::from_secs(timeout_seconds))
+            .header("X-Request-ID", &context.request_id)
+            .json(&request);
+        for (name, value) in &config.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder.send().await.map_err(|e| {
+            if e.is_timeout() {
+                MCPErrorHandler::timeout_error(timeout_seconds).to_string()
+            } else {
+                MCPErrorHandler::network_error(e.to_string()).to_string()
+            }
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.ok();
+            let error = MCPErrorHandler::handle_http_status(status, body);
+            return if error.is_endpoint_failure() {
+                Err(error.to_string())
+            } else {
+                Ok(Err(error.to_string()))
+            };
+        }
+
+        let response = response
+            .json::<MCPResponse>()
+            .await
+            .map_err(|e| format!("Invalid JSON-RPC response: {}", e))?;
+        Ok(parse_tool_call_response(response))
     }
 
     /// 获取或创建租户的协议处理器
@@ -128,6 +265,37 @@ impl MCPProxyServiceImpl {
     }
 }
 
+/// 解析 `tools/call` 的JSON-RPC 2.0响应
+///
+/// `error` 字段和结果中的 `isError` 标记都视为工具调用失败
+pub fn parse_tool_call_response(response: MCPResponse) -> Result<Value, String> {
+    if let Some(error) = response.error {
+        return Err(error.to_string());
+    }
+
+    let result = response
+        .result
+        .ok_or_else(|| "JSON-RPC response has neither result nor error".to_string())?;
+
+    if result.get("isError").and_then(|v| v.as_bool()).unwrap_or(false) {
+        let message = result
+            .get("content")
+            .and_then(|v| v.as_array())
+            .map(|content| {
+                content
+                    .iter()
+                    .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .filter(|message| !message.is_empty())
+            .unwrap_or_else(|| "Tool reported an error".to_string());
+        return Err(message);
+    }
+
+    Ok(result)
+}
+
 #[async_trait]
 impl MCPProxyService for MCPProxyServiceImpl {
     async fn register_tool(&self, tool: MCPTool) -> Result<(), PlatformError> {
@@ -167,6 +335,8 @@ impl MCPProxyService for MCPProxyServiceImpl {
             }
         }
 
+        self.circuits.write().await.remove(&tool_id);
+
         // 更新协议处理器
         let mut handler = self.get_or_create_handler(tenant_id).await;
         handler.unregister_tool(&tool.name);
@@ -198,11 +368,17 @@ impl MCPProxyService for MCPProxyServiceImpl {
         // 验证访问权限
         self.validate_tool_access(&tool, &context)?;
 
+        let breaker = self.circuit_breaker(&tool).await;
+        if !breaker.try_acquire() {
+            return Ok(self.circuit_open_result());
+        }
+
         // 执行工具调用
         let start_time = std::time::Instant::now();
         
         match self.converter.execute_tool(&tool, &parameters).await {
             Ok(mcp_result) => {
+                breaker.record_success();
                 let execution_time = start_time.elapsed().as_millis() as u64;
                 
                 Ok(ToolCallResult::success(
@@ -211,6 +387,11 @@ impl MCPProxyService for MCPProxyServiceImpl {
                 ))
             }
             Err(mcp_error) => {
+                if mcp_error.is_endpoint_failure() {
+                    breaker.record_failure();
+                } else {
+                    breaker.record_success();
+                }
                 let execution_time = start_time.elapsed().as_millis() as u64;
                 
                 Ok(ToolCallResult::error(
@@ -221,6 +402,40 @@ impl MCPProxyService for MCPProxyServiceImpl {
         }
     }
 
+    async fn forward_tool_call(
+        &self,
+        tool: &MCPTool,
+        parameters: Value,
+        context: ToolCallContext,
+    ) -> Result<ToolCallResult, PlatformError> {
+        // 验证访问权限
+        self.validate_tool_access(tool, &context)?;
+
+        let breaker = self.circuit_breaker(tool).await;
+        if !breaker.try_acquire() {
+            return Ok(self.circuit_open_result());
+        }
+
+        let start_time = std::time::Instant::now();
+        let outcome = self.send_tool_call(tool, parameters, &context).await;
+        let execution_time = start_time.elapsed().as_millis() as u64;
+
+        Ok(match outcome {
+            Ok(Ok(result)) => {
+                breaker.record_success();
+                ToolCallResult::success(result, execution_time)
+            }
+            Ok(Err(message)) => {
+                breaker.record_success();
+                ToolCallResult::error(message, execution_time)
+            }
+            Err(message) => {
+                breaker.record_failure();
+                ToolCallResult::error(message, execution_time)
+            }
+        })
+    }
+
     async fn handle_mcp_request(
         &self,
         request: MCPRequest,
@@ -293,15 +508,22 @@ impl Default for MCPProxyServiceImpl {
 /// MCP代理服务构建器
 pub struct MCPProxyServiceBuilder {
     converter_timeout: Option<std::time::Duration>,
+    circuit_config: McpCircuitBreakerConfig,
 }
 
 impl MCPProxyServiceBuilder {
     pub fn new() -> Self {
         Self {
             converter_timeout: None,
+            circuit_config: McpCircuitBreakerConfig::default(),
         }
     }
 
+    pub fn with_circuit_breaker_config(mut self, config: McpCircuitBreakerConfig) -> Self {
+        self.circuit_config = config;
+        self
+    }
+
     pub fn with_converter_timeout(mut self, timeout: std::time::Duration) -> Self {
         self.converter_timeout = Some(timeout);
         self
@@ -318,6 +540,9 @@ impl MCPProxyServiceBuilder {
             handlers: Arc::new(RwLock::new(HashMap::new())),
             converter,
             tools: Arc::new(RwLock::new(HashMap::new())),
+            client: reqwest::Client::new(),
+            circuit_config: self.circuit_config,
+            circuits: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 }
@@ -418,4 +643,75 @@ mod tests {
         // Just test that it can be built
         assert!(true);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_forward_tool_call_opens_circuit() {
+        let service = MCPProxyServiceImpl::new().with_circuit_breaker_config(McpCircuitBreakerConfig {
+            failure_threshold: 2,
+            recovery_timeout_ms: 60_000,
+            open_message: "weather tool is down".to_string(),
+        });
+        let tenant_id = TenantId::new();
+        let mut tool = create_test_tool(tenant_id);
+        // Nothing listens on port 1, so every call fails to connect
+        tool.config = ToolConfig::HTTP(HTTPToolConfig::new(
+            "http://127.0.0.1:1/mcp".to_string(),
+            HttpMethod::POST,
+        ));
+        let context = create_test_context(tenant_id, UserId::new());
+
+        for _ in 0..2 {
+            let result = service.forward_tool_call(&tool, json!({}), context.clone()).await.unwrap();
+            assert!(!result.success);
+            assert_ne!(result.error.as_deref(), Some("weather tool is down"));
+        }
+
+        let result = service.forward_tool_call(&tool, json!({}), context).await.unwrap();
+        assert_eq!(result.error.as_deref(), Some("weather tool is down"));
+
+        let statuses = service.circuit_statuses().await;
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].tool_name, "test-tool");
+        assert_eq!(statuses[0].circuit.state, crate::infrastructure::mcp::CircuitState::Open);
+    }
+
+    fn rpc_response(body: Value) -> MCPResponse {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn test_parse_tool_call_response_result() {
+        let response = rpc_response(json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "result": {"content": [{"type": "text", "text": "42"}], "isError": false}
+        }));
+
+        let result = parse_tool_call_response(response).unwrap();
+        assert_eq!(result["content"][0]["text"], "42");
+    }
+
+    #[test]
+    fn test_parse_tool_call_response_error() {
+        let response = rpc_response(json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "error": {"code": -32602, "message": "Unknown tool", "data": null}
+        }));
+
+        let error = parse_tool_call_response(response).unwrap_err();
+        assert!(error.contains("-32602"));
+        assert!(error.contains("Unknown tool"));
+    }
+
+    #[test]
+    fn test_parse_tool_call_response_tool_error() {
+        let response = rpc_response(json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "result": {"content": [{"type": "text", "text": "city not found"}], "isError": true}
+        }));
+
+        assert_eq!(parse_tool_call_response(response).unwrap_err(), "city not found");
+    }
+}
@@ -7,7 +7,7 @@ use rmcp::{
 use serde_json::Value;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use log::{error, info, warn};
+use tracing::{error, info, warn};
 
 use crate::{
     application::{
@@ -285,6 +285,22 @@ mod tests {
                 }),
             })
         }
+
+        async fn register_tool(
+            &self,
+            _tool: crate::domain::entities::MCPTool,
+            _tenant_id: crate::domain::value_objects::TenantId,
+        ) -> Result<(), PlatformError> {
+            Ok(())
+        }
+
+        async fn unregister_tool(
+            &self,
+            _tool_id: crate::domain::value_objects::MCPToolId,
+            _tenant_id: crate::domain::value_objects::TenantId,
+        ) -> Result<(), PlatformError> {
+            Ok(())
+        }
     }
 
     fn create_test_auth_context() -> APIKeyAuthContext {
@@ -298,6 +314,7 @@ mod tests {
                 mcp_tool_ids: vec![Uuid::new_v4()],
                 vector_store_ids: vec![],
             },
+            rate_limit_rpm: None,
         }
     }
 
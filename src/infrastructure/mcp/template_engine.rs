@@ -1,9 +1,12 @@
-use handlebars::Handlebars;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use handlebars::{handlebars_helper, Handlebars};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
+use crate::error::PlatformError;
+
 /// Template engine errors
 #[derive(Debug, Error)]
 pub enum TemplateError {
@@ -154,6 +157,102 @@ impl Default for ResponseTemplateEngine {
     }
 }
 
+handlebars_helper!(json_encode: |value: Json| serde_json::to_string(value).unwrap_or_default());
+
+handlebars_helper!(base64_encode: |value: Json| match value {
+    Value::String(s) => STANDARD.encode(s),
+    other => STANDARD.encode(other.to_string()),
+});
+
+/// Template engine building MCP tool parameters from flow state variables
+///
+/// A string that is exactly one `{{name}}` reference to an existing variable
+/// is replaced by the variable's value, keeping its JSON type. Any other
+/// string containing `{{` is rendered as a Handlebars template with the
+/// variables as data, so it can use `{{#if}}`, `{{json_encode value}}` and
+/// `{{base64_encode value}}`. Names that are not plain identifiers, such as
+/// `#start.text#`, are written as `{{[#start.text#]}}` inside templates.
+/// Missing variables render as empty strings.
+pub struct ParameterTemplateEngine {
+    handlebars: Handlebars<'static>,
+}
+
+impl ParameterTemplateEngine {
+    pub fn new() -> Self {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_escape_fn(handlebars::no_escape);
+        handlebars.register_helper("json_encode", Box::new(json_encode));
+        handlebars.register_helper("base64_encode", Box::new(base64_encode));
+
+        Self { handlebars }
+    }
+
+    /// Resolve every string in `params`, recursing into objects and arrays
+    pub fn resolve(
+        &self,
+        params: &Value,
+        variables: &HashMap<String, Value>,
+    ) -> crate::error::Result<Value> {
+        let data = Value::Object(
+            variables
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+        );
+        self.resolve_value(params, variables, &data)
+    }
+
+    fn resolve_value(
+        &self,
+        params: &Value,
+        variables: &HashMap<String, Value>,
+        data: &Value,
+    ) -> crate::error::Result<Value> {
+        match params {
+            Value::Object(map) => {
+                let mut resolved = serde_json::Map::new();
+                for (key, value) in map {
+                    resolved.insert(key.clone(), self.resolve_value(value, variables, data)?);
+                }
+                Ok(Value::Object(resolved))
+            }
+            Value::Array(arr) => arr
+                .iter()
+                .map(|value| self.resolve_value(value, variables, data))
+                .collect::<crate::error::Result<Vec<_>>>()
+                .map(Value::Array),
+            Value::String(s) => {
+                let reference = s
+                    .strip_prefix("{{")
+                    .and_then(|s| s.strip_suffix("}}"))
+                    .and_then(|name| variables.get(name.trim()));
+                match reference {
+                    Some(value) => Ok(value.clone()),
+                    None if s.contains("{{") => self.render(s, data).map(Value::String),
+                    None => Ok(Value::String(s.clone())),
+                }
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// Render one template string against `data`
+    pub fn render(&self, template: &str, data: &Value) -> crate::error::Result<String> {
+        self.handlebars
+            .render_template(template, data)
+            .map_err(|e| PlatformError::TemplateEngineError {
+                template: template.to_string(),
+                message: e.to_string(),
+            })
+    }
+}
+
+impl Default for ParameterTemplateEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,4 +428,68 @@ Total: ${{ total }}"#;
             "Template rendering took {}μs, expected < 1000μs", 
             duration.as_micros());
     }
+
+    fn variables() -> HashMap<String, Value> {
+        HashMap::from([
+            ("query".to_string(), json!("rust")),
+            ("limit".to_string(), json!(5)),
+            ("verbose".to_string(), json!(false)),
+            ("filters".to_string(), json!({"lang": "en"})),
+            ("#start.text#".to_string(), json!("hello")),
+        ])
+    }
+
+    #[test]
+    fn test_parameter_reference_keeps_type() {
+        let engine = ParameterTemplateEngine::new();
+        let params = json!({
+            "limit": "{{limit}}",
+            "filters": "{{ filters }}",
+            "text": "{{#start.text#}}",
+            "tags": ["{{query}}", "fixed"],
+        });
+
+        let resolved = engine.resolve(&params, &variables()).unwrap();
+        assert_eq!(
+            resolved,
+            json!({
+                "limit": 5,
+                "filters": {"lang": "en"},
+                "text": "hello",
+                "tags": ["rust", "fixed"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parameter_template_helpers_and_conditionals() {
+        let engine = ParameterTemplateEngine::new();
+        let params = json!({
+            "q": "lang:{{filters.lang}} {{query}}",
+            "filters": "{{json_encode filters}}",
+            "auth": "{{base64_encode query}}",
+            "text": "{{[#start.text#]}} world",
+            "mode": "{{#if verbose}}full{{else}}brief{{/if}}",
+        });
+
+        let resolved = engine.resolve(&params, &variables()).unwrap();
+        assert_eq!(resolved["q"], "lang:en rust");
+        assert_eq!(resolved["filters"], r#"{"lang":"en"}"#);
+        assert_eq!(resolved["auth"], "cnVzdA==");
+        assert_eq!(resolved["text"], "hello world");
+        assert_eq!(resolved["mode"], "brief");
+    }
+
+    #[test]
+    fn test_parameter_template_error_carries_source() {
+        let engine = ParameterTemplateEngine::new();
+        let result = engine.resolve(&json!({"q": "{{#if query}}unclosed"}), &variables());
+
+        match result {
+            Err(PlatformError::TemplateEngineError { template, .. }) => {
+                assert_eq!(template, "{{#if query}}unclosed");
+            }
+            other => panic!("Expected TemplateEngineError, got {:?}", other),
+        }
+    }
 }
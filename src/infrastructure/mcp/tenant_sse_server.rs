@@ -0,0 +1,204 @@
+use futures::{channel::mpsc, SinkExt};
+use rmcp::{
+    model::{ClientJsonRpcMessage, ServerJsonRpcMessage},
+    service::serve_server,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dto::APIKeyAuthContext,
+        services::mcp_server_application_service::MCPServerApplicationService,
+    },
+    error::{PlatformError, Result},
+};
+
+use super::rmcp_server_handler::{RMCPServerConfig, RMCPServerHandler};
+
+/// Messages buffered in each direction of a session
+const SESSION_CHANNEL_CAPACITY: usize = 64;
+
+struct TenantSSESession {
+    tenant_id: Uuid,
+    api_key_id: Uuid,
+    to_server: mpsc::Sender<ClientJsonRpcMessage>,
+}
+
+type SessionStore = Arc<RwLock<HashMap<String, TenantSSESession>>>;
+
+/// Hosts tenant MCP servers over the SSE transport. Every session runs its
+/// own `RMCPServerHandler`, bound to the API key that opened it, so
+/// `tools/list` and `tools/call` only ever see that tenant's tools.
+pub struct TenantMCPSessions {
+    mcp_service: Arc<dyn MCPServerApplicationService>,
+    config: RMCPServerConfig,
+    sessions: SessionStore,
+}
+
+impl TenantMCPSessions {
+    pub fn new(mcp_service: Arc<dyn MCPServerApplicationService>, config: RMCPServerConfig) -> Self {
+        Self {
+            mcp_service,
+            config,
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Start a session for the API key. Returns the session id and the
+    /// messages the server sends back, to be relayed as SSE events.
+    pub async fn open(
+        &self,
+        auth_context: APIKeyAuthContext,
+    ) -> Result<(String, mpsc::Receiver<ServerJsonRpcMessage>)> {
+        let session_id = Uuid::new_v4().to_string();
+        let tenant_id = auth_context.tenant_id;
+        let (to_server, from_client) = mpsc::channel(SESSION_CHANNEL_CAPACITY);
+        let (to_client, from_server) = mpsc::channel(SESSION_CHANNEL_CAPACITY);
+
+        {
+            let mut sessions = self.sessions.write().await;
+            if sessions.len() >= self.config.max_connections {
+                return Err(PlatformError::ServiceUnavailable(
+                    "Too many MCP sessions, try again later".to_string(),
+                ));
+            }
+            sessions.insert(
+                session_id.clone(),
+                TenantSSESession {
+                    tenant_id,
+                    api_key_id: auth_context.api_key_id,
+                    to_server,
+                },
+            );
+        }
+
+        let handler = RMCPServerHandler::new(self.mcp_service.clone(), self.config.clone());
+        handler.set_auth_context(auth_context).await;
+
+        let sessions = self.sessions.clone();
+        let task_session_id = session_id.clone();
+        tokio::spawn(async move {
+            match serve_server(handler, (to_client, from_client)).await {
+                Ok(running) => {
+                    if let Err(e) = running.waiting().await {
+                        error!("MCP session {} ended abnormally: {}", task_session_id, e);
+                    }
+                }
+                Err(e) => error!("MCP session {} failed to initialize: {}", task_session_id, e),
+            }
+            sessions.write().await.remove(&task_session_id);
+            info!("MCP session {} closed", task_session_id);
+        });
+
+        info!("Opened MCP session {} for tenant {}", session_id, tenant_id);
+        Ok((session_id, from_server))
+    }
+
+    /// Forward a client message to its session. Only the API key that
+    /// opened the session may post to it.
+    pub async fn send(
+        &self,
+        session_id: &str,
+        auth_context: &APIKeyAuthContext,
+        message: ClientJsonRpcMessage,
+    ) -> Result<()> {
+        let mut to_server = {
+            let sessions = self.sessions.read().await;
+            let session = sessions
+                .get(session_id)
+                .ok_or_else(|| PlatformError::NotFound(format!("MCP session {} not found", session_id)))?;
+            if session.tenant_id != auth_context.tenant_id
+                || session.api_key_id != auth_context.api_key_id
+            {
+                return Err(PlatformError::AuthorizationFailed(
+                    "MCP session belongs to another API key".to_string(),
+                ));
+            }
+            session.to_server.clone()
+        };
+
+        to_server.send(message).await.map_err(|_| {
+            PlatformError::NotFound(format!("MCP session {} has closed", session_id))
+        })
+    }
+
+    /// End a session, e.g. once its SSE stream is dropped
+    pub async fn close(&self, session_id: &str) {
+        // Dropping the sender ends the server loop, which cleans up after itself
+        if self.sessions.write().await.remove(session_id).is_some() {
+            info!("MCP session {} disconnected", session_id);
+        }
+    }
+
+    pub async fn session_count(&self) -> usize {
+        self.sessions.read().await.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{
+        dto::PermissionScopeDTO,
+        services::mcp_server_application_service::MockMCPServerApplicationService,
+    };
+
+    fn auth_context(tenant_id: Uuid) -> APIKeyAuthContext {
+        APIKeyAuthContext {
+            api_key_id: Uuid::new_v4(),
+            tenant_id,
+            user_id: Uuid::new_v4(),
+            permission_scope: PermissionScopeDTO {
+                agent_ids: vec![],
+                flow_ids: vec![],
+                mcp_tool_ids: vec![],
+                vector_store_ids: vec![],
+            },
+            rate_limit_rpm: None,
+        }
+    }
+
+    fn sessions(max_connections: usize) -> TenantMCPSessions {
+        TenantMCPSessions::new(
+            Arc::new(MockMCPServerApplicationService::new()),
+            RMCPServerConfig {
+                max_connections,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_session_rejects_other_api_keys() {
+        let sessions = sessions(10);
+        let owner = auth_context(Uuid::new_v4());
+        let (session_id, _events) = sessions.open(owner.clone()).await.unwrap();
+
+        let intruder = auth_context(owner.tenant_id);
+        let message: ClientJsonRpcMessage = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized"
+        }))
+        .unwrap();
+        let result = sessions.send(&session_id, &intruder, message).await;
+
+        assert!(matches!(result, Err(PlatformError::AuthorizationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_open_respects_max_connections() {
+        let sessions = sessions(1);
+        let tenant_id = Uuid::new_v4();
+        let (session_id, _events) = sessions.open(auth_context(tenant_id)).await.unwrap();
+
+        let result = sessions.open(auth_context(tenant_id)).await;
+        assert!(matches!(result, Err(PlatformError::ServiceUnavailable(_))));
+
+        sessions.close(&session_id).await;
+        assert_eq!(sessions.session_count().await, 0);
+    }
+}
@@ -1,8 +1,12 @@
 use async_trait::async_trait;
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, Set, ActiveModelTrait};
+use sea_orm::{
+    DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, Set, ActiveModelTrait,
+    ConnectionTrait, DbBackend, Statement,
+};
 use sea_orm::prelude::Decimal;
 use std::sync::Arc;
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
 use crate::domain::entities::AgentDailyStats;
 use crate::domain::repositories::AgentDailyStatsRepository;
 use crate::domain::value_objects::{AgentId, TenantId};
@@ -157,4 +161,43 @@ impl AgentDailyStatsRepository for AgentDailyStatsRepositoryImpl {
         let new_stats = AgentDailyStats::new(*agent_id, *tenant_id, stat_date);
         self.create(&new_stats).await
     }
+
+    async fn add_revenue(
+        &self,
+        agent_id: &AgentId,
+        tenant_id: &TenantId,
+        stat_date: NaiveDate,
+        amount: Decimal,
+    ) -> Result<()> {
+        let now = Utc::now();
+
+        // Relies on the unique (agent_id, stat_date) index; LEAST keeps the
+        // running total inside DECIMAL(20, 8)
+        self.db
+            .execute(Statement::from_sql_and_values(
+                DbBackend::MySql,
+                r#"
+                INSERT INTO agent_daily_stats (id, agent_id, tenant_id, stat_date, revenue, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                ON DUPLICATE KEY UPDATE
+                    revenue = LEAST(revenue + ?, ?),
+                    updated_at = ?
+                "#,
+                vec![
+                    Uuid::new_v4().into(),
+                    agent_id.0.into(),
+                    tenant_id.0.into(),
+                    stat_date.into(),
+                    amount.into(),
+                    now.into(),
+                    now.into(),
+                    amount.into(),
+                    crate::domain::services::MAX_DAILY_REVENUE.into(),
+                    now.into(),
+                ],
+            ))
+            .await?;
+
+        Ok(())
+    }
 }
@@ -1,20 +1,56 @@
 use async_trait::async_trait;
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect, PaginatorTrait, QueryOrder, Set};
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, Condition, QuerySelect, PaginatorTrait, QueryOrder, QueryTrait, Select, Set};
+use sea_orm::sea_query::Expr;
+use rust_decimal::Decimal;
 use std::sync::Arc;
-use chrono::Utc;
-use crate::domain::entities::Agent;
-use crate::domain::repositories::{AgentRepository, AgentAllocationRepository};
-use crate::domain::value_objects::{AgentId, TenantId, UserId, ConfigId, MCPToolId, FlowId};
-use crate::infrastructure::database::entities;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::{Agent, AgentAllocation};
+use crate::domain::repositories::{AgentRepository, AgentAllocationRepository, MarketplaceAgentFilter};
+use crate::domain::value_objects::{AgentId, TenantId, UserId, ConfigId, MCPToolId, FlowId, LLMSelectionStrategy};
+use crate::infrastructure::database::{entities, IndexHint, QueryOptimizer};
 use crate::error::{Result, PlatformError};
 
+/// InnoDB leaves words shorter than `innodb_ft_min_token_size` (3 by
+/// default) out of full-text indexes
+const FULLTEXT_MIN_TOKEN_LEN: usize = 3;
+
+/// Allocations that have no expiry or expire after `now`
+fn unexpired_allocation(now: DateTime<Utc>) -> Condition {
+    Condition::any()
+        .add(entities::agent_allocation::Column::ExpiresAt.is_null())
+        .add(entities::agent_allocation::Column::ExpiresAt.gt(now))
+}
+
 pub struct AgentRepositoryImpl {
     db: Arc<DatabaseConnection>,
+    query_optimizer: Option<Arc<QueryOptimizer>>,
 }
 
 impl AgentRepositoryImpl {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        Self {
+            db,
+            query_optimizer: None,
+        }
+    }
+
+    /// Time hot list queries and pin them to their indexes
+    pub fn with_query_optimizer(mut self, query_optimizer: Arc<QueryOptimizer>) -> Self {
+        self.query_optimizer = Some(query_optimizer);
+        self
+    }
+
+    async fn find_all_hinted(
+        &self,
+        query_name: &str,
+        query: Select<entities::agent::Entity>,
+        hint: IndexHint,
+    ) -> Result<Vec<entities::agent::Model>> {
+        let models = match &self.query_optimizer {
+            Some(optimizer) => optimizer.find_all(query_name, query, Some(hint)).await?,
+            None => query.all(self.db.as_ref()).await?,
+        };
+        Ok(models)
     }
 
     fn entity_to_domain(entity: entities::agent::Model) -> Result<Agent> {
@@ -31,6 +67,12 @@ impl AgentRepositoryImpl {
         let preset_questions: Vec<String> = serde_json::from_value(entity.preset_questions.clone())
             .map_err(|e| PlatformError::ValidationError(format!("Invalid preset_questions: {}", e)))?;
 
+        let llm_fallback_strategy = match entity.llm_fallback_strategy.clone() {
+            Some(value) => serde_json::from_value(value)
+                .map_err(|e| PlatformError::ValidationError(format!("Invalid llm_fallback_strategy: {}", e)))?,
+            None => LLMSelectionStrategy::default(),
+        };
+
         Ok(Agent {
             id: AgentId::from_uuid(entity.id),
             tenant_id: TenantId::from_uuid(entity.tenant_id),
@@ -38,6 +80,7 @@ impl AgentRepositoryImpl {
             avatar: entity.avatar,
             greeting: entity.greeting,
             llm_config_id: entity.llm_config_id.map(ConfigId::from_uuid),
+            llm_fallback_strategy,
             knowledge_base_ids: knowledge_base_ids.into_iter().map(ConfigId::from_uuid).collect(),
             mcp_tool_ids: mcp_tool_ids.into_iter().map(MCPToolId::from_uuid).collect(),
             flow_ids: flow_ids.into_iter().map(FlowId::from_uuid).collect(),
@@ -74,6 +117,9 @@ impl AgentRepositoryImpl {
         let preset_questions_json = serde_json::to_value(&agent.preset_questions)
             .map_err(|e| PlatformError::ValidationError(format!("Failed to serialize preset_questions: {}", e)))?;
 
+        let llm_fallback_strategy_json = serde_json::to_value(&agent.llm_fallback_strategy)
+            .map_err(|e| PlatformError::ValidationError(format!("Failed to serialize llm_fallback_strategy: {}", e)))?;
+
         Ok(entities::agent::ActiveModel {
             id: Set(agent.id.0),
             tenant_id: Set(agent.tenant_id.0),
@@ -81,6 +127,7 @@ impl AgentRepositoryImpl {
             avatar: Set(agent.avatar.clone()),
             greeting: Set(agent.greeting.clone()),
             llm_config_id: Set(agent.llm_config_id.map(|id| id.0)),
+            llm_fallback_strategy: Set(Some(llm_fallback_strategy_json)),
             knowledge_base_ids: Set(knowledge_base_ids_json),
             mcp_tool_ids: Set(mcp_tool_ids_json),
             flow_ids: Set(flow_ids_json),
@@ -98,6 +145,118 @@ impl AgentRepositoryImpl {
             updated_at: Set(agent.updated_at),
         })
     }
+
+    /// Boolean-mode full-text query requiring every indexable word as a
+    /// prefix, or `None` when no word is long enough to be indexed
+    fn fulltext_query(keyword: &str) -> Option<String> {
+        let terms: Vec<String> = keyword
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|term| term.chars().count() >= FULLTEXT_MIN_TOKEN_LEN)
+            .map(|term| format!("+{}*", term))
+            .collect();
+
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" "))
+        }
+    }
+
+    /// Match name or system prompt through the `ft_agents_name_system_prompt`
+    /// index, falling back to LIKE for keywords the index cannot serve
+    fn keyword_condition(keyword: &str) -> Condition {
+        match Self::fulltext_query(keyword) {
+            Some(against) => Condition::all().add(Expr::cust_with_values(
+                "MATCH(`name`, `system_prompt`) AGAINST (? IN BOOLEAN MODE)",
+                [against],
+            )),
+            None => Condition::any()
+                .add(entities::agent::Column::Name.contains(keyword))
+                .add(entities::agent::Column::SystemPrompt.contains(keyword)),
+        }
+    }
+
+    /// Match name or greeting through the `ft_agents_name_greeting` index.
+    /// Used across tenants, where system prompts are private to their owner.
+    fn public_keyword_condition(keyword: &str) -> Condition {
+        match Self::fulltext_query(keyword) {
+            Some(against) => Condition::all().add(Expr::cust_with_values(
+                "MATCH(`name`, `greeting`) AGAINST (? IN BOOLEAN MODE)",
+                [against],
+            )),
+            None => Condition::any()
+                .add(entities::agent::Column::Name.contains(keyword))
+                .add(entities::agent::Column::Greeting.contains(keyword)),
+        }
+    }
+
+    /// Published, active agents that nobody has employed yet
+    fn listed_agents() -> Select<entities::agent::Entity> {
+        entities::agent::Entity::find()
+            .filter(entities::agent::Column::IsPublished.eq(true))
+            .filter(entities::agent::Column::EmployerId.is_null())
+            .filter(entities::agent::Column::FiredAt.is_null())
+    }
+
+    async fn search_listed(
+        &self,
+        query: Select<entities::agent::Entity>,
+        keyword: &str,
+        condition: fn(&str) -> Condition,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        let keyword = keyword.trim();
+        if keyword.is_empty() {
+            return Err(PlatformError::ValidationError(
+                "Search keyword cannot be empty".to_string(),
+            ));
+        }
+
+        let query = query.filter(condition(keyword));
+        self.fetch_page(query, offset, limit).await
+    }
+
+    /// Count the query's matches, then load one page ordered by newest first
+    async fn fetch_page(
+        &self,
+        query: Select<entities::agent::Entity>,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        let total = query.clone().count(self.db.as_ref()).await?;
+
+        let agents = query
+            .order_by_desc(entities::agent::Column::CreatedAt)
+            .offset(offset)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        let mut result = Vec::with_capacity(agents.len());
+        for entity in agents {
+            result.push(Self::entity_to_domain(entity)?);
+        }
+        Ok((result, total))
+    }
+
+    async fn find_by_ids_newest_first(&self, agent_ids: Vec<uuid::Uuid>) -> Result<Vec<Agent>> {
+        if agent_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let agents = entities::agent::Entity::find()
+            .filter(entities::agent::Column::Id.is_in(agent_ids))
+            .order_by_desc(entities::agent::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await?;
+
+        let mut result = Vec::with_capacity(agents.len());
+        for entity in agents {
+            result.push(Self::entity_to_domain(entity)?);
+        }
+        Ok(result)
+    }
 }
 
 #[async_trait]
@@ -114,10 +273,11 @@ impl AgentRepository for AgentRepositoryImpl {
     }
 
     async fn find_by_tenant(&self, tenant_id: &TenantId) -> Result<Vec<Agent>> {
-        let agents = entities::agent::Entity::find()
+        let query = entities::agent::Entity::find()
             .filter(entities::agent::Column::TenantId.eq(tenant_id.0))
-            .order_by_desc(entities::agent::Column::CreatedAt)
-            .all(self.db.as_ref())
+            .order_by_desc(entities::agent::Column::CreatedAt);
+        let agents = self
+            .find_all_hinted("agents.find_by_tenant", query, IndexHint::AGENTS_BY_TENANT)
             .await?;
 
         let mut result = Vec::new();
@@ -143,10 +303,11 @@ impl AgentRepository for AgentRepositoryImpl {
     }
 
     async fn find_by_creator(&self, creator_id: &UserId) -> Result<Vec<Agent>> {
-        let agents = entities::agent::Entity::find()
+        let query = entities::agent::Entity::find()
             .filter(entities::agent::Column::CreatorId.eq(creator_id.0))
-            .order_by_desc(entities::agent::Column::CreatedAt)
-            .all(self.db.as_ref())
+            .order_by_desc(entities::agent::Column::CreatedAt);
+        let agents = self
+            .find_all_hinted("agents.find_by_creator", query, IndexHint::AGENTS_BY_CREATOR)
             .await?;
 
         let mut result = Vec::new();
@@ -170,6 +331,20 @@ impl AgentRepository for AgentRepositoryImpl {
         Ok(result)
     }
 
+    async fn find_by_source_agent(&self, source_id: &AgentId) -> Result<Vec<Agent>> {
+        let agents = entities::agent::Entity::find()
+            .filter(entities::agent::Column::SourceAgentId.eq(source_id.0))
+            .order_by_desc(entities::agent::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await?;
+
+        let mut result = Vec::new();
+        for entity in agents {
+            result.push(Self::entity_to_domain(entity)?);
+        }
+        Ok(result)
+    }
+
     async fn find_allocated_to_user(&self, user_id: &UserId) -> Result<Vec<Agent>> {
         // Join with allocations table to find allocated agents
         let agent_ids: Vec<uuid::Uuid> = entities::agent_allocation::Entity::find()
@@ -180,21 +355,20 @@ impl AgentRepository for AgentRepositoryImpl {
             .map(|e| e.agent_id)
             .collect();
 
-        if agent_ids.is_empty() {
-            return Ok(Vec::new());
-        }
+        self.find_by_ids_newest_first(agent_ids).await
+    }
 
-        let agents = entities::agent::Entity::find()
-            .filter(entities::agent::Column::Id.is_in(agent_ids))
-            .order_by_desc(entities::agent::Column::CreatedAt)
+    async fn find_allocated_to_user_active(&self, user_id: &UserId) -> Result<Vec<Agent>> {
+        let agent_ids: Vec<uuid::Uuid> = entities::agent_allocation::Entity::find()
+            .filter(entities::agent_allocation::Column::UserId.eq(user_id.0))
+            .filter(unexpired_allocation(Utc::now()))
+            .select_only()
+            .column(entities::agent_allocation::Column::AgentId)
+            .into_tuple()
             .all(self.db.as_ref())
             .await?;
 
-        let mut result = Vec::new();
-        for entity in agents {
-            result.push(Self::entity_to_domain(entity)?);
-        }
-        Ok(result)
+        self.find_by_ids_newest_first(agent_ids).await
     }
 
     async fn save(&self, agent: &Agent) -> Result<()> {
@@ -304,6 +478,144 @@ impl AgentRepository for AgentRepositoryImpl {
         }
         Ok(result)
     }
+
+    async fn find_by_tenant_published_paginated(
+        &self,
+        tenant_id: &TenantId,
+        include_fired: bool,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        let mut query = entities::agent::Entity::find()
+            .filter(entities::agent::Column::TenantId.eq(tenant_id.0))
+            .filter(entities::agent::Column::IsPublished.eq(true))
+            .filter(entities::agent::Column::EmployerId.is_null());
+        if !include_fired {
+            query = query.filter(entities::agent::Column::FiredAt.is_null());
+        }
+
+        self.fetch_page(query, offset, limit).await
+    }
+
+    async fn search_marketplace_paginated(
+        &self,
+        tenant_id: &TenantId,
+        filter: &MarketplaceAgentFilter,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        let mut query = entities::agent::Entity::find()
+            .filter(entities::agent::Column::TenantId.eq(tenant_id.0))
+            .filter(entities::agent::Column::IsPublished.eq(true))
+            .filter(entities::agent::Column::EmployerId.is_null())
+            .filter(entities::agent::Column::FiredAt.is_null());
+
+        if let Some(keyword) = filter.keyword.as_deref().map(str::trim).filter(|k| !k.is_empty()) {
+            query = query.filter(
+                Condition::any()
+                    .add(entities::agent::Column::Name.contains(keyword))
+                    .add(entities::agent::Column::Greeting.contains(keyword)),
+            );
+        }
+
+        // A NULL price is a free agent
+        if let Some(min_price) = filter.min_price {
+            let mut condition = Condition::any().add(entities::agent::Column::Price.gte(min_price));
+            if min_price <= Decimal::ZERO {
+                condition = condition.add(entities::agent::Column::Price.is_null());
+            }
+            query = query.filter(condition);
+        }
+        if let Some(max_price) = filter.max_price {
+            query = query.filter(
+                Condition::any()
+                    .add(entities::agent::Column::Price.lte(max_price))
+                    .add(entities::agent::Column::Price.is_null()),
+            );
+        }
+
+        self.fetch_page(query, offset, limit).await
+    }
+
+    async fn search_by_keyword(
+        &self,
+        tenant_id: &TenantId,
+        keyword: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        let query = Self::listed_agents()
+            .filter(entities::agent::Column::TenantId.eq(tenant_id.0));
+
+        self.search_listed(query, keyword, Self::keyword_condition, offset, limit).await
+    }
+
+    async fn search_published_by_keyword(
+        &self,
+        keyword: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        self.search_listed(
+            Self::listed_agents(),
+            keyword,
+            Self::public_keyword_condition,
+            offset,
+            limit,
+        )
+        .await
+    }
+
+    async fn find_original_by_creator_paginated(
+        &self,
+        creator_id: &UserId,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        let query = entities::agent::Entity::find()
+            .filter(entities::agent::Column::CreatorId.eq(creator_id.0))
+            .filter(entities::agent::Column::SourceAgentId.is_null());
+
+        self.fetch_page(query, offset, limit).await
+    }
+
+    async fn find_by_employer_paginated(
+        &self,
+        employer_id: &UserId,
+        include_fired: bool,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        let mut query = entities::agent::Entity::find()
+            .filter(entities::agent::Column::EmployerId.eq(employer_id.0));
+        if !include_fired {
+            query = query.filter(entities::agent::Column::FiredAt.is_null());
+        }
+
+        self.fetch_page(query, offset, limit).await
+    }
+
+    async fn find_allocated_to_user_paginated(
+        &self,
+        user_id: &UserId,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<Agent>, u64)> {
+        // Subquery rather than loading the allocated IDs first, so the
+        // database pages the agents
+        let query = entities::agent::Entity::find().filter(
+            entities::agent::Column::Id.in_subquery(
+                entities::agent_allocation::Entity::find()
+                    .select_only()
+                    .column(entities::agent_allocation::Column::AgentId)
+                    .filter(entities::agent_allocation::Column::UserId.eq(user_id.0))
+                    .filter(unexpired_allocation(Utc::now()))
+                    .into_query(),
+            ),
+        );
+
+        self.fetch_page(query, offset, limit).await
+    }
 }
 
 pub struct AgentAllocationRepositoryImpl {
@@ -318,7 +630,14 @@ impl AgentAllocationRepositoryImpl {
 
 #[async_trait]
 impl AgentAllocationRepository for AgentAllocationRepositoryImpl {
-    async fn allocate(&self, agent_id: &AgentId, user_id: &UserId) -> Result<()> {
+    async fn allocate(
+        &self,
+        agent_id: &AgentId,
+        user_id: &UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let now = Utc::now();
+
         // Check if allocation already exists
         let existing = entities::agent_allocation::Entity::find()
             .filter(entities::agent_allocation::Column::AgentId.eq(agent_id.0))
@@ -326,16 +645,26 @@ impl AgentAllocationRepository for AgentAllocationRepositoryImpl {
             .one(self.db.as_ref())
             .await?;
 
-        if existing.is_some() {
-            return Err(PlatformError::AgentAlreadyAllocated(
-                format!("User {} has already been allocated agent {}", user_id.0, agent_id.0)
-            ));
+        if let Some(existing) = existing {
+            if existing.expires_at.map_or(true, |expires_at| expires_at > now) {
+                return Err(PlatformError::AgentAlreadyAllocated(
+                    format!("User {} has already been allocated agent {}", user_id.0, agent_id.0)
+                ));
+            }
+
+            // Replace the expired allocation the cleanup job has not removed yet
+            entities::agent_allocation::Entity::delete_many()
+                .filter(entities::agent_allocation::Column::AgentId.eq(agent_id.0))
+                .filter(entities::agent_allocation::Column::UserId.eq(user_id.0))
+                .exec(self.db.as_ref())
+                .await?;
         }
 
         let allocation = entities::agent_allocation::ActiveModel {
             agent_id: Set(agent_id.0),
             user_id: Set(user_id.0),
-            allocated_at: Set(Utc::now()),
+            allocated_at: Set(now),
+            expires_at: Set(expires_at),
         };
 
         entities::agent_allocation::Entity::insert(allocation)
@@ -365,6 +694,7 @@ impl AgentAllocationRepository for AgentAllocationRepositoryImpl {
         let count = entities::agent_allocation::Entity::find()
             .filter(entities::agent_allocation::Column::AgentId.eq(agent_id.0))
             .filter(entities::agent_allocation::Column::UserId.eq(user_id.0))
+            .filter(unexpired_allocation(Utc::now()))
             .count(self.db.as_ref())
             .await?;
 
@@ -374,6 +704,7 @@ impl AgentAllocationRepository for AgentAllocationRepositoryImpl {
     async fn find_by_agent(&self, agent_id: &AgentId) -> Result<Vec<UserId>> {
         let allocations = entities::agent_allocation::Entity::find()
             .filter(entities::agent_allocation::Column::AgentId.eq(agent_id.0))
+            .filter(unexpired_allocation(Utc::now()))
             .all(self.db.as_ref())
             .await?;
 
@@ -383,9 +714,74 @@ impl AgentAllocationRepository for AgentAllocationRepositoryImpl {
     async fn find_by_user(&self, user_id: &UserId) -> Result<Vec<AgentId>> {
         let allocations = entities::agent_allocation::Entity::find()
             .filter(entities::agent_allocation::Column::UserId.eq(user_id.0))
+            .filter(unexpired_allocation(Utc::now()))
             .all(self.db.as_ref())
             .await?;
 
         Ok(allocations.into_iter().map(|e| AgentId::from_uuid(e.agent_id)).collect())
     }
+
+    async fn find_agents_for_user_with_expiry(&self, user_id: &UserId) -> Result<Vec<AgentAllocation>> {
+        let allocations = entities::agent_allocation::Entity::find()
+            .filter(entities::agent_allocation::Column::UserId.eq(user_id.0))
+            .filter(unexpired_allocation(Utc::now()))
+            .order_by_desc(entities::agent_allocation::Column::AllocatedAt)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(allocations
+            .into_iter()
+            .map(|e| AgentAllocation {
+                agent_id: AgentId::from_uuid(e.agent_id),
+                user_id: UserId::from_uuid(e.user_id),
+                allocated_at: e.allocated_at,
+                expires_at: e.expires_at,
+            })
+            .collect())
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64> {
+        let result = entities::agent_allocation::Entity::delete_many()
+            .filter(entities::agent_allocation::Column::ExpiresAt.lte(now))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fulltext_query_requires_every_indexable_word() {
+        assert_eq!(
+            AgentRepositoryImpl::fulltext_query("sales  assistant"),
+            Some("+sales* +assistant*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fulltext_query_drops_operators_and_short_words() {
+        assert_eq!(
+            AgentRepositoryImpl::fulltext_query("+ai -(sql)* \"bot\""),
+            Some("+sql* +bot*".to_string())
+        );
+        assert_eq!(AgentRepositoryImpl::fulltext_query("ai"), None);
+    }
+
+    #[test]
+    fn test_public_keyword_condition_skips_system_prompt() {
+        for keyword in ["sales assistant", "ai"] {
+            let sql = AgentRepositoryImpl::listed_agents()
+                .filter(AgentRepositoryImpl::public_keyword_condition(keyword))
+                .build(sea_orm::DbBackend::MySql)
+                .to_string();
+            let condition = sql.split_once("WHERE").unwrap().1;
+
+            assert!(condition.contains("`greeting`"), "{}", sql);
+            assert!(!condition.contains("system_prompt"), "{}", sql);
+        }
+    }
 }
@@ -1,11 +1,12 @@
 use async_trait::async_trait;
 use sea_orm::{
     DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect, 
-    PaginatorTrait, QueryOrder, Set, ActiveModelTrait
+    PaginatorTrait, QueryOrder, Set, ActiveModelTrait, TransactionTrait
 };
+use sea_orm::sea_query::{Expr, OnConflict};
 use std::sync::Arc;
-use chrono::Utc;
-use crate::domain::entities::APIKey;
+use chrono::{NaiveDate, Utc};
+use crate::domain::entities::{APIKey, APIKeyDailyUsage, APIKeyUsageDelta};
 use crate::domain::repositories::{APIKeyRepository, QueryOptions, APIKeyQueryResult};
 use crate::domain::value_objects::{APIKeyId, TenantId, UserId, PermissionScope};
 use crate::infrastructure::database::entities;
@@ -36,6 +37,9 @@ impl APIKeyRepositoryImpl {
             enabled: entity.enabled,
             expires_at: entity.expires_at,
             last_used_at: entity.last_used_at,
+            request_count: entity.request_count,
+            total_tokens_used: entity.total_tokens_used,
+            rate_limit_rpm: entity.rate_limit_rpm,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
         })
@@ -57,6 +61,9 @@ impl APIKeyRepositoryImpl {
             enabled: Set(api_key.enabled),
             expires_at: Set(api_key.expires_at),
             last_used_at: Set(api_key.last_used_at),
+            request_count: Set(api_key.request_count),
+            total_tokens_used: Set(api_key.total_tokens_used),
+            rate_limit_rpm: Set(api_key.rate_limit_rpm),
             created_at: Set(api_key.created_at),
             updated_at: Set(api_key.updated_at),
         })
@@ -226,4 +233,78 @@ impl APIKeyRepository for APIKeyRepositoryImpl {
 
         Ok(count)
     }
+
+    async fn record_usage(&self, usage: &[APIKeyUsageDelta]) -> Result<()> {
+        use entities::api_key::Column as KeyColumn;
+        use entities::api_key_usage_daily::Column as DailyColumn;
+
+        if usage.is_empty() {
+            return Ok(());
+        }
+
+        let txn = self.db.begin().await?;
+
+        for delta in usage {
+            // Increment in SQL so requests counted by other instances are kept
+            entities::ApiKey::update_many()
+                .col_expr(
+                    KeyColumn::RequestCount,
+                    Expr::col(KeyColumn::RequestCount).add(delta.request_count),
+                )
+                .col_expr(
+                    KeyColumn::TotalTokensUsed,
+                    Expr::col(KeyColumn::TotalTokensUsed).add(delta.tokens_used),
+                )
+                .col_expr(KeyColumn::LastUsedAt, Expr::value(delta.last_used_at))
+                .filter(KeyColumn::Id.eq(delta.api_key_id.0))
+                .exec(&txn)
+                .await?;
+
+            let daily = entities::api_key_usage_daily::ActiveModel {
+                api_key_id: Set(delta.api_key_id.0),
+                usage_date: Set(delta.date),
+                request_count: Set(delta.request_count),
+                tokens_used: Set(delta.tokens_used),
+            };
+            entities::ApiKeyUsageDaily::insert(daily)
+                .on_conflict(
+                    OnConflict::columns([DailyColumn::ApiKeyId, DailyColumn::UsageDate])
+                        .value(
+                            DailyColumn::RequestCount,
+                            Expr::col(DailyColumn::RequestCount).add(delta.request_count),
+                        )
+                        .value(
+                            DailyColumn::TokensUsed,
+                            Expr::col(DailyColumn::TokensUsed).add(delta.tokens_used),
+                        )
+                        .to_owned(),
+                )
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_daily_usage(&self, id: APIKeyId, since: NaiveDate) -> Result<Vec<APIKeyDailyUsage>> {
+        use entities::api_key_usage_daily::Column;
+
+        let rows = entities::ApiKeyUsageDaily::find()
+            .filter(Column::ApiKeyId.eq(id.0))
+            .filter(Column::UsageDate.gte(since))
+            .order_by_asc(Column::UsageDate)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| APIKeyDailyUsage {
+                date: row.usage_date,
+                request_count: row.request_count,
+                tokens_used: row.tokens_used,
+            })
+            .collect())
+    }
 }
@@ -1,7 +1,10 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Days, Utc};
 use sea_orm::*;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use crate::domain::entities::{AuditAction, AuditLog, ResourceType};
@@ -9,13 +12,132 @@ use crate::domain::repositories::{AuditLogFilter, AuditLogRepository, AuditStati
 use crate::error::{PlatformError, Result};
 use crate::infrastructure::database::entities::audit_log;
 
+/// Records inserted per statement by the background writer
+const AUDIT_LOG_BATCH_SIZE: usize = 100;
+
+/// Longest a record waits for its batch to fill before it is written
+const AUDIT_LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Records queued for the writer before `create` waits for room
+const AUDIT_LOG_CHANNEL_CAPACITY: usize = 10_000;
+
+/// Audit logs are append-only: `create` queues the record for a background
+/// writer that batch-inserts them, so a record becomes visible to queries up
+/// to `AUDIT_LOG_FLUSH_INTERVAL` later. Records are only ever deleted by the
+/// retention job.
 pub struct AuditLogRepositoryImpl {
     db: Arc<DatabaseConnection>,
+    writer: mpsc::Sender<AuditLog>,
+    writer_task: Arc<AuditLogWriter>,
+    retention_policy: Option<Days>,
+}
+
+/// Handle on the background writer, used to flush queued records on shutdown
+#[derive(Debug)]
+pub struct AuditLogWriter {
+    stop: watch::Sender<bool>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl AuditLogWriter {
+    /// Stop accepting records and wait for the queued ones to be written.
+    /// Returns false if that took longer than `timeout`.
+    pub async fn close(&self, timeout: Duration) -> bool {
+        self.stop.send_replace(true);
+        let Some(task) = self.task.lock().await.take() else {
+            return true;
+        };
+        matches!(tokio::time::timeout(timeout, task).await, Ok(Ok(())))
+    }
 }
 
 impl AuditLogRepositoryImpl {
+    /// Must be called within a Tokio runtime, which runs the writer
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        let (writer, receiver) = mpsc::channel(AUDIT_LOG_CHANNEL_CAPACITY);
+        let (stop, stop_receiver) = watch::channel(false);
+        let task = tokio::spawn(Self::run_writer(db.clone(), receiver, stop_receiver));
+
+        Self {
+            db,
+            writer,
+            writer_task: Arc::new(AuditLogWriter {
+                stop,
+                task: Mutex::new(Some(task)),
+            }),
+            retention_policy: None,
+        }
+    }
+
+    /// The background writer, to be closed before the database
+    pub fn writer(&self) -> Arc<AuditLogWriter> {
+        self.writer_task.clone()
+    }
+
+    /// Keep records for `retention_policy`; `None` keeps them forever
+    pub fn with_retention_policy(mut self, retention_policy: Option<Days>) -> Self {
+        self.retention_policy = retention_policy;
+        self
+    }
+
+    /// Delete records older than the retention policy. Returns the number
+    /// deleted, 0 without a policy.
+    pub async fn apply_retention_policy(&self) -> Result<u64> {
+        let Some(retention) = self.retention_policy else {
+            return Ok(0);
+        };
+        let cutoff = Utc::now().checked_sub_days(retention).ok_or_else(|| {
+            PlatformError::InternalError("Audit log retention period is too long".to_string())
+        })?;
+
+        self.delete_older_than(cutoff).await
+    }
+
+    /// Collect queued records into batches of up to `AUDIT_LOG_BATCH_SIZE`,
+    /// writing each once full or `AUDIT_LOG_FLUSH_INTERVAL` after its first
+    /// record arrived. Ends once the queue is written after the last sender
+    /// is dropped or `stop` is set.
+    async fn run_writer(
+        db: Arc<DatabaseConnection>,
+        mut receiver: mpsc::Receiver<AuditLog>,
+        mut stop: watch::Receiver<bool>,
+    ) {
+        let mut batch = Vec::with_capacity(AUDIT_LOG_BATCH_SIZE);
+
+        loop {
+            let first = tokio::select! {
+                record = receiver.recv() => record,
+                // The watch guard must not be held across the await below,
+                // or the writer future is not Send
+                _ = async { let _ = stop.wait_for(|stop| *stop).await; } => {
+                    // Refuse new records; the queued ones are still received
+                    receiver.close();
+                    receiver.recv().await
+                }
+            };
+            let Some(first) = first else {
+                break;
+            };
+            batch.push(first);
+
+            let deadline = tokio::time::sleep(AUDIT_LOG_FLUSH_INTERVAL);
+            tokio::pin!(deadline);
+            while batch.len() < AUDIT_LOG_BATCH_SIZE {
+                tokio::select! {
+                    record = receiver.recv() => match record {
+                        Some(record) => batch.push(record),
+                        None => break,
+                    },
+                    _ = &mut deadline => break,
+                }
+            }
+
+            let count = batch.len();
+            let models = batch.drain(..).map(|log| Self::to_active_model(&log));
+            if let Err(e) = audit_log::Entity::insert_many(models).exec(db.as_ref()).await {
+                tracing::error!("Failed to write {} audit log records: {}", count, e);
+            }
+        }
     }
 
     fn to_domain(&self, model: audit_log::Model) -> AuditLog {
@@ -33,7 +155,7 @@ impl AuditLogRepositoryImpl {
         }
     }
 
-    fn to_active_model(&self, audit_log: &AuditLog) -> audit_log::ActiveModel {
+    fn to_active_model(audit_log: &AuditLog) -> audit_log::ActiveModel {
         audit_log::ActiveModel {
             id: Set(audit_log.id),
             tenant_id: Set(audit_log.tenant_id),
@@ -47,32 +169,21 @@ impl AuditLogRepositoryImpl {
             created_at: Set(audit_log.created_at),
         }
     }
-}
-
-#[async_trait]
-impl AuditLogRepository for AuditLogRepositoryImpl {
-    async fn create(&self, audit_log: &AuditLog) -> Result<()> {
-        let active_model = self.to_active_model(audit_log);
-        audit_log::Entity::insert(active_model)
-            .exec(self.db.as_ref())
-            .await
-            .map_err(PlatformError::from)?;
-        Ok(())
-    }
-
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<AuditLog>> {
-        let model = audit_log::Entity::find_by_id(id)
-            .one(self.db.as_ref())
-            .await
-            .map_err(PlatformError::from)?;
 
-        Ok(model.map(|m| self.to_domain(m)))
-    }
-
-    async fn find_with_filter(&self, filter: &AuditLogFilter) -> Result<Vec<AuditLog>> {
+    /// Query for the filter's records. Tenant and date range come first so
+    /// the `(tenant_id, created_at)` index serves them.
+    fn filtered_query(filter: &AuditLogFilter) -> Select<audit_log::Entity> {
         let mut query = audit_log::Entity::find()
             .filter(audit_log::Column::TenantId.eq(filter.tenant_id));
 
+        if let Some(start_date) = filter.start_date {
+            query = query.filter(audit_log::Column::CreatedAt.gte(start_date));
+        }
+
+        if let Some(end_date) = filter.end_date {
+            query = query.filter(audit_log::Column::CreatedAt.lte(end_date));
+        }
+
         if let Some(user_id) = filter.user_id {
             query = query.filter(audit_log::Column::UserId.eq(user_id));
         }
@@ -89,15 +200,29 @@ impl AuditLogRepository for AuditLogRepositoryImpl {
             query = query.filter(audit_log::Column::ResourceId.eq(resource_id));
         }
 
-        if let Some(start_date) = filter.start_date {
-            query = query.filter(audit_log::Column::CreatedAt.gte(start_date));
-        }
+        query
+    }
+}
 
-        if let Some(end_date) = filter.end_date {
-            query = query.filter(audit_log::Column::CreatedAt.lte(end_date));
-        }
+#[async_trait]
+impl AuditLogRepository for AuditLogRepositoryImpl {
+    async fn create(&self, audit_log: &AuditLog) -> Result<()> {
+        self.writer.send(audit_log.clone()).await.map_err(|_| {
+            PlatformError::InternalError("Audit log writer has stopped".to_string())
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<AuditLog>> {
+        let model = audit_log::Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await
+            .map_err(PlatformError::from)?;
 
-        query = query.order_by_desc(audit_log::Column::CreatedAt);
+        Ok(model.map(|m| self.to_domain(m)))
+    }
+
+    async fn find_with_filter(&self, filter: &AuditLogFilter) -> Result<Vec<AuditLog>> {
+        let mut query = Self::filtered_query(filter).order_by_desc(audit_log::Column::CreatedAt);
 
         if let Some(limit) = filter.limit {
             query = query.limit(limit);
@@ -116,34 +241,7 @@ impl AuditLogRepository for AuditLogRepositoryImpl {
     }
 
     async fn count_with_filter(&self, filter: &AuditLogFilter) -> Result<u64> {
-        let mut query = audit_log::Entity::find()
-            .filter(audit_log::Column::TenantId.eq(filter.tenant_id));
-
-        if let Some(user_id) = filter.user_id {
-            query = query.filter(audit_log::Column::UserId.eq(user_id));
-        }
-
-        if let Some(ref action) = filter.action {
-            query = query.filter(audit_log::Column::Action.eq(action.as_str()));
-        }
-
-        if let Some(ref resource_type) = filter.resource_type {
-            query = query.filter(audit_log::Column::ResourceType.eq(resource_type.as_str()));
-        }
-
-        if let Some(resource_id) = filter.resource_id {
-            query = query.filter(audit_log::Column::ResourceId.eq(resource_id));
-        }
-
-        if let Some(start_date) = filter.start_date {
-            query = query.filter(audit_log::Column::CreatedAt.gte(start_date));
-        }
-
-        if let Some(end_date) = filter.end_date {
-            query = query.filter(audit_log::Column::CreatedAt.lte(end_date));
-        }
-
-        query
+        Self::filtered_query(filter)
             .count(self.db.as_ref())
             .await
             .map_err(PlatformError::from)
@@ -289,3 +387,33 @@ impl AuditLogRepository for AuditLogRepositoryImpl {
         Ok(result.rows_affected)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    #[tokio::test]
+    async fn test_close_writes_queued_records() {
+        let db = MockDatabase::new(DatabaseBackend::MySql)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            }])
+            .into_connection();
+        let repository = AuditLogRepositoryImpl::new(Arc::new(db));
+
+        for _ in 0..2 {
+            let log = AuditLog::new(Uuid::new_v4(), None, AuditAction::Create, ResourceType::Flow, None);
+            repository.create(&log).await.unwrap();
+        }
+
+        assert!(repository.writer().close(Duration::from_secs(5)).await);
+        let log = AuditLog::new(Uuid::new_v4(), None, AuditAction::Create, ResourceType::Flow, None);
+        assert!(repository.create(&log).await.is_err());
+
+        let AuditLogRepositoryImpl { db, .. } = repository;
+        let db = Arc::try_unwrap(db).expect("writer released the connection");
+        assert_eq!(db.into_transaction_log().len(), 1);
+    }
+}
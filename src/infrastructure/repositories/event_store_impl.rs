@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    Set, SqlErr, TransactionTrait,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::events::{AggregateSnapshot, DomainEvent, EventStore, StoredEvent};
+use crate::error::{PlatformError, Result};
+use crate::infrastructure::database::entities;
+
+/// SeaORM-backed event store over the `domain_events` and
+/// `domain_event_snapshots` tables
+pub struct EventStoreImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl EventStoreImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_stored(entity: entities::domain_event::Model) -> StoredEvent {
+        StoredEvent {
+            event_id: entity.id,
+            aggregate_id: entity.aggregate_id,
+            event_type: entity.event_type,
+            version: entity.version,
+            payload: entity.payload,
+            correlation_id: entity.correlation_id,
+            occurred_at: entity.occurred_at,
+        }
+    }
+}
+
+#[async_trait]
+impl EventStore for EventStoreImpl {
+    async fn append(&self, event: &dyn DomainEvent) -> Result<i64> {
+        let txn = self.db.begin().await?;
+
+        let latest = entities::DomainEvent::find()
+            .filter(entities::domain_event::Column::AggregateId.eq(event.aggregate_id()))
+            .order_by_desc(entities::domain_event::Column::Version)
+            .one(&txn)
+            .await?;
+        let version = latest.map(|e| e.version).unwrap_or(0) + 1;
+
+        let stored = StoredEvent::from_event(event, version);
+        let active_model = entities::domain_event::ActiveModel {
+            id: Set(stored.event_id),
+            aggregate_id: Set(stored.aggregate_id),
+            event_type: Set(stored.event_type),
+            version: Set(stored.version),
+            payload: Set(stored.payload),
+            correlation_id: Set(stored.correlation_id),
+            occurred_at: Set(stored.occurred_at),
+            created_at: Set(chrono::Utc::now()),
+        };
+
+        // The unique (aggregate_id, version) index rejects a concurrent append
+        // that read the same latest version
+        entities::DomainEvent::insert(active_model)
+            .exec(&txn)
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => PlatformError::Conflict(format!(
+                    "Version {} of aggregate {} was appended concurrently",
+                    version,
+                    event.aggregate_id()
+                )),
+                _ => PlatformError::DatabaseError(e),
+            })?;
+
+        txn.commit().await?;
+
+        Ok(version)
+    }
+
+    async fn load_events(&self, aggregate_id: Uuid, after_version: i64) -> Result<Vec<Box<dyn DomainEvent>>> {
+        let events = entities::DomainEvent::find()
+            .filter(entities::domain_event::Column::AggregateId.eq(aggregate_id))
+            .filter(entities::domain_event::Column::Version.gt(after_version))
+            .order_by_asc(entities::domain_event::Column::Version)
+            .all(self.db.as_ref())
+            .await?;
+
+        events
+            .into_iter()
+            .map(|entity| Self::entity_to_stored(entity).decode())
+            .collect()
+    }
+
+    async fn load_snapshot(&self, aggregate_id: Uuid) -> Result<Option<AggregateSnapshot>> {
+        let snapshot = entities::DomainEventSnapshot::find_by_id(aggregate_id)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(snapshot.map(|entity| AggregateSnapshot {
+            aggregate_id: entity.aggregate_id,
+            version: entity.version,
+            state: entity.state,
+            created_at: entity.created_at,
+        }))
+    }
+
+    async fn save_snapshot(&self, snapshot: AggregateSnapshot) -> Result<()> {
+        let active_model = entities::domain_event_snapshot::ActiveModel {
+            aggregate_id: Set(snapshot.aggregate_id),
+            version: Set(snapshot.version),
+            state: Set(snapshot.state),
+            created_at: Set(snapshot.created_at),
+        };
+
+        entities::DomainEventSnapshot::insert(active_model)
+            .on_conflict(
+                OnConflict::column(entities::domain_event_snapshot::Column::AggregateId)
+                    .update_columns([
+                        entities::domain_event_snapshot::Column::Version,
+                        entities::domain_event_snapshot::Column::State,
+                        entities::domain_event_snapshot::Column::CreatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -5,11 +5,11 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::entities::{
-    ExecutionMetrics, ExecutionStatus, ExecutionStep, FlowExecutionHistory, StepStatus,
+    BatchExecution, ExecutionMetrics, ExecutionStatus, ExecutionStep, FlowExecutionHistory, StepStatus,
 };
 use crate::domain::repositories::{ExecutionFilter, ExecutionHistoryRepository};
 use crate::error::{PlatformError, Result};
-use crate::infrastructure::database::entities::{execution_step, flow_execution};
+use crate::infrastructure::database::entities::{batch_execution, execution_step, flow_execution};
 
 pub struct ExecutionHistoryRepositoryImpl {
     db: Arc<DatabaseConnection>,
@@ -35,6 +35,7 @@ impl ExecutionHistoryRepositoryImpl {
             started_at: model.started_at,
             completed_at: model.completed_at,
             execution_time_ms: model.execution_time_ms,
+            correlation_id: model.correlation_id,
         }
     }
 
@@ -53,9 +54,63 @@ impl ExecutionHistoryRepositoryImpl {
             started_at: Set(execution.started_at),
             completed_at: Set(execution.completed_at),
             execution_time_ms: Set(execution.execution_time_ms),
+            correlation_id: Set(execution.correlation_id),
         }
     }
 
+    /// Executions matching every condition of the filter
+    fn filtered_executions(filter: &ExecutionFilter) -> Select<flow_execution::Entity> {
+        let mut query = flow_execution::Entity::find()
+            .filter(flow_execution::Column::TenantId.eq(filter.tenant_id));
+
+        if let Some(flow_id) = filter.flow_id {
+            query = query.filter(flow_execution::Column::FlowId.eq(flow_id));
+        }
+
+        if let Some(ref flow_ids) = filter.flow_ids {
+            query = query.filter(flow_execution::Column::FlowId.is_in(flow_ids.clone()));
+        }
+
+        if let Some(user_id) = filter.user_id {
+            query = query.filter(flow_execution::Column::UserId.eq(user_id));
+        }
+
+        if let Some(session_id) = filter.session_id {
+            query = query.filter(flow_execution::Column::SessionId.eq(session_id));
+        }
+
+        if let Some(ref status) = filter.status {
+            query = query.filter(flow_execution::Column::Status.eq(status.as_str()));
+        }
+
+        if let Some(correlation_id) = filter.correlation_id {
+            query = query.filter(flow_execution::Column::CorrelationId.eq(correlation_id));
+        }
+
+        if let Some(start_date) = filter.start_date {
+            query = query.filter(flow_execution::Column::StartedAt.gte(start_date));
+        }
+
+        if let Some(end_date) = filter.end_date {
+            query = query.filter(flow_execution::Column::StartedAt.lte(end_date));
+        }
+
+        // Keyset condition matching the (started_at, id) descending order
+        if let Some(cursor) = filter.cursor {
+            query = query.filter(
+                Condition::any()
+                    .add(flow_execution::Column::StartedAt.lt(cursor.started_at))
+                    .add(
+                        Condition::all()
+                            .add(flow_execution::Column::StartedAt.eq(cursor.started_at))
+                            .add(flow_execution::Column::Id.lt(cursor.id)),
+                    ),
+            );
+        }
+
+        query
+    }
+
     fn step_to_domain(&self, model: execution_step::Model) -> ExecutionStep {
         ExecutionStep {
             id: model.id,
@@ -161,34 +216,9 @@ impl ExecutionHistoryRepository for ExecutionHistoryRepositoryImpl {
     }
 
     async fn find_executions_with_filter(&self, filter: &ExecutionFilter) -> Result<Vec<FlowExecutionHistory>> {
-        let mut query = flow_execution::Entity::find()
-            .filter(flow_execution::Column::TenantId.eq(filter.tenant_id));
-
-        if let Some(flow_id) = filter.flow_id {
-            query = query.filter(flow_execution::Column::FlowId.eq(flow_id));
-        }
-
-        if let Some(user_id) = filter.user_id {
-            query = query.filter(flow_execution::Column::UserId.eq(user_id));
-        }
-
-        if let Some(session_id) = filter.session_id {
-            query = query.filter(flow_execution::Column::SessionId.eq(session_id));
-        }
-
-        if let Some(ref status) = filter.status {
-            query = query.filter(flow_execution::Column::Status.eq(status.as_str()));
-        }
-
-        if let Some(start_date) = filter.start_date {
-            query = query.filter(flow_execution::Column::StartedAt.gte(start_date));
-        }
-
-        if let Some(end_date) = filter.end_date {
-            query = query.filter(flow_execution::Column::StartedAt.lte(end_date));
-        }
-
-        query = query.order_by_desc(flow_execution::Column::StartedAt);
+        let mut query = Self::filtered_executions(filter)
+            .order_by_desc(flow_execution::Column::StartedAt)
+            .order_by_desc(flow_execution::Column::Id);
 
         if let Some(limit) = filter.limit {
             query = query.limit(limit);
@@ -207,34 +237,7 @@ impl ExecutionHistoryRepository for ExecutionHistoryRepositoryImpl {
     }
 
     async fn count_executions_with_filter(&self, filter: &ExecutionFilter) -> Result<u64> {
-        let mut query = flow_execution::Entity::find()
-            .filter(flow_execution::Column::TenantId.eq(filter.tenant_id));
-
-        if let Some(flow_id) = filter.flow_id {
-            query = query.filter(flow_execution::Column::FlowId.eq(flow_id));
-        }
-
-        if let Some(user_id) = filter.user_id {
-            query = query.filter(flow_execution::Column::UserId.eq(user_id));
-        }
-
-        if let Some(session_id) = filter.session_id {
-            query = query.filter(flow_execution::Column::SessionId.eq(session_id));
-        }
-
-        if let Some(ref status) = filter.status {
-            query = query.filter(flow_execution::Column::Status.eq(status.as_str()));
-        }
-
-        if let Some(start_date) = filter.start_date {
-            query = query.filter(flow_execution::Column::StartedAt.gte(start_date));
-        }
-
-        if let Some(end_date) = filter.end_date {
-            query = query.filter(flow_execution::Column::StartedAt.lte(end_date));
-        }
-
-        query
+        Self::filtered_executions(filter)
             .count(self.db.as_ref())
             .await
             .map_err(PlatformError::from)
@@ -283,4 +286,25 @@ impl ExecutionHistoryRepository for ExecutionHistoryRepositoryImpl {
 
         Ok(result.rows_affected)
     }
+
+    async fn create_batch_execution(&self, batch: &BatchExecution) -> Result<()> {
+        let active_model = batch_execution::ActiveModel {
+            id: Set(batch.id),
+            flow_id: Set(batch.flow_id),
+            tenant_id: Set(batch.tenant_id),
+            user_id: Set(batch.user_id),
+            total: Set(batch.total as i32),
+            succeeded: Set(batch.succeeded as i32),
+            failed: Set(batch.failed as i32),
+            concurrency: Set(batch.concurrency as i32),
+            started_at: Set(batch.started_at),
+            completed_at: Set(batch.completed_at),
+        };
+
+        batch_execution::Entity::insert(active_model)
+            .exec(self.db.as_ref())
+            .await
+            .map_err(PlatformError::from)?;
+        Ok(())
+    }
 }
@@ -1,9 +1,10 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 use tokio::fs;
 
 use crate::{
-    domain::repositories::FileRepository,
+    domain::repositories::{FileRepository, PresignedPost},
     error::{PlatformError, Result},
 };
 
@@ -90,4 +91,34 @@ impl FileRepository for FileRepositoryImpl {
             Err(PlatformError::NotFound("File not found".to_string()))
         }
     }
+
+    async fn presign_upload(
+        &self,
+        _tenant_id: &str,
+        _file_id: &str,
+        _filename: &str,
+        _content_type: &str,
+        _max_size_bytes: u64,
+        _expires_at: DateTime<Utc>,
+    ) -> Result<PresignedPost> {
+        Err(PlatformError::ServiceUnavailable(
+            "Direct uploads are not supported by local file storage".to_string(),
+        ))
+    }
+
+    async fn confirm_upload(&self, tenant_id: &str, key: &str) -> Result<String> {
+        let relative = key.strip_prefix(&format!("{}/", tenant_id)).ok_or_else(|| {
+            PlatformError::AuthorizationFailed("Upload key does not belong to this tenant".to_string())
+        })?;
+        let parts: Vec<&str> = relative.split('/').collect();
+        let [file_id, filename] = parts.as_slice() else {
+            return Err(PlatformError::ValidationError("Invalid upload key".to_string()));
+        };
+
+        if !self.get_file_path(tenant_id, file_id, filename).is_file() {
+            return Err(PlatformError::NotFound("Uploaded file not found".to_string()));
+        }
+
+        Ok(self.get_file_url(tenant_id, file_id, filename))
+    }
 }
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Set,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::domain::entities::FlowNodeAnnotation;
+use crate::domain::repositories::FlowNodeAnnotationRepository;
+use crate::domain::value_objects::{FlowId, FlowNodeAnnotationId, TenantId, UserId, Version};
+use crate::infrastructure::database::entities;
+use crate::error::Result;
+
+pub struct FlowNodeAnnotationRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl FlowNodeAnnotationRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: entities::flow_node_annotation::Model) -> FlowNodeAnnotation {
+        FlowNodeAnnotation {
+            id: FlowNodeAnnotationId::from_uuid(entity.id),
+            flow_id: FlowId::from_uuid(entity.flow_id),
+            node_id: entity.node_id,
+            version: Version(entity.version),
+            author_id: UserId::from_uuid(entity.author_id),
+            content: entity.content,
+            resolved: entity.resolved,
+            created_at: entity.created_at,
+        }
+    }
+
+    fn domain_to_active_model(annotation: &FlowNodeAnnotation) -> entities::flow_node_annotation::ActiveModel {
+        entities::flow_node_annotation::ActiveModel {
+            id: Set(annotation.id.0),
+            flow_id: Set(annotation.flow_id.0),
+            node_id: Set(annotation.node_id.clone()),
+            version: Set(annotation.version.0),
+            author_id: Set(annotation.author_id.0),
+            content: Set(annotation.content.clone()),
+            resolved: Set(annotation.resolved),
+            created_at: Set(annotation.created_at),
+        }
+    }
+}
+
+#[async_trait]
+impl FlowNodeAnnotationRepository for FlowNodeAnnotationRepositoryImpl {
+    async fn find_by_id(&self, id: &FlowNodeAnnotationId) -> Result<Option<FlowNodeAnnotation>> {
+        let annotation = entities::FlowNodeAnnotation::find_by_id(id.0)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(annotation.map(Self::entity_to_domain))
+    }
+
+    async fn find_by_flow<'a>(&self, flow_id: &FlowId, node_id: Option<&'a str>) -> Result<Vec<FlowNodeAnnotation>> {
+        let mut query = entities::FlowNodeAnnotation::find()
+            .filter(entities::flow_node_annotation::Column::FlowId.eq(flow_id.0));
+
+        if let Some(node_id) = node_id {
+            query = query.filter(entities::flow_node_annotation::Column::NodeId.eq(node_id));
+        }
+
+        let annotations = query
+            .order_by_asc(entities::flow_node_annotation::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(annotations.into_iter().map(Self::entity_to_domain).collect())
+    }
+
+    async fn save(&self, annotation: &FlowNodeAnnotation) -> Result<()> {
+        let active_model = Self::domain_to_active_model(annotation);
+
+        // Check if annotation exists
+        let existing = entities::FlowNodeAnnotation::find_by_id(annotation.id.0)
+            .one(self.db.as_ref())
+            .await?;
+
+        if existing.is_some() {
+            entities::FlowNodeAnnotation::update(active_model)
+                .exec(self.db.as_ref())
+                .await?;
+        } else {
+            entities::FlowNodeAnnotation::insert(active_model)
+                .exec(self.db.as_ref())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn count_by_flow(&self, flow_id: &FlowId) -> Result<u64> {
+        let count = entities::FlowNodeAnnotation::find()
+            .filter(entities::flow_node_annotation::Column::FlowId.eq(flow_id.0))
+            .count(self.db.as_ref())
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn count_by_flows(&self, tenant_id: &TenantId, flow_ids: &[FlowId]) -> Result<HashMap<FlowId, u64>> {
+        use entities::flow_node_annotation::{Column, Relation};
+
+        if flow_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let counts: Vec<(Uuid, i64)> = entities::FlowNodeAnnotation::find()
+            .select_only()
+            .column(Column::FlowId)
+            .column_as(Expr::col((entities::flow_node_annotation::Entity, Column::Id)).count(), "count")
+            .join(JoinType::InnerJoin, Relation::Flow.def())
+            .filter(entities::flow::Column::TenantId.eq(tenant_id.0))
+            .filter(Column::FlowId.is_in(flow_ids.iter().map(|flow_id| flow_id.0)))
+            .group_by(Column::FlowId)
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(flow_id, count)| (FlowId::from_uuid(flow_id), count.max(0) as u64))
+            .collect())
+    }
+}
@@ -1,22 +1,45 @@
 use async_trait::async_trait;
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect, PaginatorTrait, QueryOrder};
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect, PaginatorTrait, QueryOrder, QueryTrait, Select, TransactionTrait};
 use std::sync::Arc;
 use chrono::{DateTime, Utc};
 use crate::domain::entities::{Flow, FlowVersion, FlowExecution, FlowStatus, FlowExecutionStatus};
-use crate::domain::repositories::{FlowRepository, FlowVersionRepository, FlowExecutionRepository};
+use crate::domain::repositories::{FlowQuery, FlowRepository, FlowVersionRepository, FlowExecutionRepository};
 use crate::domain::value_objects::{FlowId, TenantId, UserId, SessionId, FlowExecutionId, Version, FlowName, FlowDefinition};
 use crate::domain::NodeType;
 use serde_json::json;
-use crate::infrastructure::database::entities;
+use crate::infrastructure::database::{entities, IndexHint, QueryOptimizer};
 use crate::error::{Result, PlatformError};
 
 pub struct FlowRepositoryImpl {
     db: Arc<DatabaseConnection>,
+    query_optimizer: Option<Arc<QueryOptimizer>>,
 }
 
 impl FlowRepositoryImpl {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        Self {
+            db,
+            query_optimizer: None,
+        }
+    }
+
+    /// Time hot list queries and pin them to their indexes
+    pub fn with_query_optimizer(mut self, query_optimizer: Arc<QueryOptimizer>) -> Self {
+        self.query_optimizer = Some(query_optimizer);
+        self
+    }
+
+    async fn find_all_optimized(
+        &self,
+        query_name: &str,
+        query: Select<entities::Flow>,
+        hint: Option<IndexHint>,
+    ) -> Result<Vec<entities::flow::Model>> {
+        let models = match &self.query_optimizer {
+            Some(optimizer) => optimizer.find_all(query_name, query, hint).await?,
+            None => query.all(self.db.as_ref()).await?,
+        };
+        Ok(models)
     }
 
     fn entity_to_domain(entity: entities::flow::Model) -> Result<Flow> {
@@ -36,6 +59,10 @@ impl FlowRepositoryImpl {
             description: entity.description,
             current_version: Version(entity.current_version),
             status,
+            timeout_ms: entity.timeout_ms.map(|ms| ms as u64),
+            is_published: entity.is_published,
+            published_at: entity.published_at,
+            is_enabled: entity.is_enabled,
             created_by: UserId::from_uuid(entity.created_by),
             created_at: entity.created_at,
             updated_at: entity.updated_at,
@@ -58,6 +85,10 @@ impl FlowRepositoryImpl {
             description: Set(flow.description.clone()),
             current_version: Set(flow.current_version.0),
             status: Set(status),
+            timeout_ms: Set(flow.timeout_ms.map(|ms| ms as i64)),
+            is_published: Set(flow.is_published),
+            published_at: Set(flow.published_at),
+            is_enabled: Set(flow.is_enabled),
             created_by: Set(flow.created_by.0),
             created_at: Set(flow.created_at),
             updated_at: Set(flow.updated_at),
@@ -79,9 +110,10 @@ impl FlowRepository for FlowRepositoryImpl {
     }
 
     async fn find_by_tenant(&self, tenant_id: &TenantId) -> Result<Vec<Flow>> {
-        let flows = entities::Flow::find()
-            .filter(entities::flow::Column::TenantId.eq(tenant_id.0))
-            .all(self.db.as_ref())
+        let query = entities::Flow::find()
+            .filter(entities::flow::Column::TenantId.eq(tenant_id.0));
+        let flows = self
+            .find_all_optimized("flows.find_by_tenant", query, Some(IndexHint::FLOWS_BY_TENANT))
             .await?;
 
         let mut result = Vec::new();
@@ -112,9 +144,11 @@ impl FlowRepository for FlowRepositoryImpl {
     }
 
     async fn find_by_creator(&self, created_by: &UserId) -> Result<Vec<Flow>> {
-        let flows = entities::Flow::find()
-            .filter(entities::flow::Column::CreatedBy.eq(created_by.0))
-            .all(self.db.as_ref())
+        // flows has no created_by index to hint, the query is still timed
+        let query = entities::Flow::find()
+            .filter(entities::flow::Column::CreatedBy.eq(created_by.0));
+        let flows = self
+            .find_all_optimized("flows.find_by_creator", query, None)
             .await?;
 
         let mut result = Vec::new();
@@ -192,6 +226,56 @@ impl FlowRepository for FlowRepositoryImpl {
 
         Ok(count > 0)
     }
+
+    async fn find_by_query(
+        &self,
+        query: &FlowQuery,
+        offset: u64,
+        limit: u64
+    ) -> Result<(Vec<Flow>, u64)> {
+        use entities::flow_node_config::Column as NodeColumn;
+
+        // Flows of the tenant with at least one indexed node matching the condition
+        let flows_with_node = |condition: sea_orm::sea_query::SimpleExpr| {
+            entities::flow::Column::Id.in_subquery(
+                entities::FlowNodeConfig::find()
+                    .select_only()
+                    .column(NodeColumn::FlowId)
+                    .filter(NodeColumn::TenantId.eq(query.tenant_id.0))
+                    .filter(condition)
+                    .into_query(),
+            )
+        };
+
+        let mut select = entities::Flow::find()
+            .filter(entities::flow::Column::TenantId.eq(query.tenant_id.0));
+
+        if let Some(node_types) = query.node_types.as_ref().filter(|types| !types.is_empty()) {
+            let names: Vec<&str> = node_types.iter().map(|t| t.as_str()).collect();
+            select = select.filter(flows_with_node(NodeColumn::NodeType.is_in(names)));
+        }
+        if let Some(llm_config_id) = &query.llm_config_id {
+            select = select.filter(flows_with_node(NodeColumn::LlmConfigId.eq(llm_config_id.0)));
+        }
+        if let Some(mcp_tool_id) = &query.mcp_tool_id {
+            select = select.filter(flows_with_node(NodeColumn::McpToolId.eq(mcp_tool_id.0)));
+        }
+
+        let total = select.clone().count(self.db.as_ref()).await?;
+
+        let flows = select
+            .order_by_desc(entities::flow::Column::UpdatedAt)
+            .offset(offset)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        let mut result = Vec::with_capacity(flows.len());
+        for entity in flows {
+            result.push(Self::entity_to_domain(entity)?);
+        }
+        Ok((result, total))
+    }
 }
 
 pub struct FlowVersionRepositoryImpl {
@@ -234,6 +318,53 @@ impl FlowVersionRepositoryImpl {
             created_at: Set(version.created_at),
         })
     }
+
+    /// Replace the `flow_node_configs` rows of the flow with the nodes of
+    /// `version`, unless a later version of the flow exists
+    async fn sync_node_configs(&self, version: &FlowVersion, tenant_id: &TenantId) -> Result<()> {
+        use sea_orm::ActiveValue::Set;
+
+        let later_exists = entities::FlowVersion::find()
+            .filter(entities::flow_version::Column::FlowId.eq(version.flow_id.0))
+            .filter(entities::flow_version::Column::Version.gt(version.version.0))
+            .count(self.db.as_ref())
+            .await? > 0;
+        if later_exists {
+            return Ok(());
+        }
+
+        let mut node_ids = std::collections::HashSet::new();
+        let models: Vec<entities::flow_node_config::ActiveModel> = version
+            .definition
+            .workflow
+            .graph
+            .nodes
+            .iter()
+            .filter(|node| node_ids.insert(node.id.as_str()))
+            .map(|node| entities::flow_node_config::ActiveModel {
+                flow_id: Set(version.flow_id.0),
+                node_id: Set(node.id.clone()),
+                tenant_id: Set(tenant_id.0),
+                node_type: Set(node.node_type.as_str().to_string()),
+                llm_config_id: Set(node.llm_config_id()),
+                mcp_tool_id: Set(node.mcp_tool_id()),
+            })
+            .collect();
+
+        let txn = self.db.begin().await?;
+        entities::FlowNodeConfig::delete_many()
+            .filter(entities::flow_node_config::Column::FlowId.eq(version.flow_id.0))
+            .exec(&txn)
+            .await?;
+        if !models.is_empty() {
+            entities::FlowNodeConfig::insert_many(models)
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -360,7 +491,7 @@ impl FlowVersionRepository for FlowVersionRepositoryImpl {
                 .await?;
         }
 
-        Ok(())
+        self.sync_node_configs(&version, tenant_id).await
     }
 
     async fn delete(&self, id: &FlowId) -> Result<()> {
@@ -375,6 +506,10 @@ impl FlowVersionRepository for FlowVersionRepositoryImpl {
             .filter(entities::flow_version::Column::FlowId.eq(flow_id.0))
             .exec(self.db.as_ref())
             .await?;
+        entities::FlowNodeConfig::delete_many()
+            .filter(entities::flow_node_config::Column::FlowId.eq(flow_id.0))
+            .exec(self.db.as_ref())
+            .await?;
         Ok(())
     }
 
@@ -441,6 +576,7 @@ impl FlowExecutionRepositoryImpl {
             started_at: entity.started_at,
             completed_at: entity.completed_at,
             execution_time_ms: entity.execution_time_ms,
+            correlation_id: entity.correlation_id,
         })
     }
 
@@ -469,6 +605,7 @@ impl FlowExecutionRepositoryImpl {
             started_at: Set(execution.started_at),
             completed_at: Set(execution.completed_at),
             execution_time_ms: Set(execution.execution_time_ms),
+            correlation_id: Set(execution.correlation_id),
         }
     }
 }
@@ -693,4 +830,23 @@ impl FlowExecutionRepository for FlowExecutionRepositoryImpl {
         }
         Ok(result)
     }
+
+    async fn fail_unfinished(
+        &self,
+        started_before: DateTime<Utc>,
+        error_message: &str,
+    ) -> Result<u64> {
+        use entities::flow_execution::{Column, ExecutionStatus};
+
+        let result = entities::FlowExecution::update_many()
+            .filter(Column::Status.is_in([ExecutionStatus::Pending, ExecutionStatus::Running]))
+            .filter(Column::StartedAt.lt(started_before))
+            .col_expr(Column::Status, sea_orm::sea_query::Expr::value(ExecutionStatus::Failed))
+            .col_expr(Column::ErrorMessage, sea_orm::sea_query::Expr::value(error_message))
+            .col_expr(Column::CompletedAt, sea_orm::sea_query::Expr::value(Utc::now()))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
 }
@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, ColumnTrait, QueryOrder, Set};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use crate::domain::entities::{DailyTokenUsage, LlmUsageLog};
+use crate::domain::repositories::LlmUsageLogRepository;
+use crate::domain::value_objects::{AgentId, TenantId};
+use crate::error::Result;
+use crate::infrastructure::database::entities;
+
+pub struct LlmUsageLogRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl LlmUsageLogRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn domain_to_active_model(log: &LlmUsageLog) -> entities::llm_usage_log::ActiveModel {
+        entities::llm_usage_log::ActiveModel {
+            id: Set(log.id),
+            tenant_id: Set(log.tenant_id.0),
+            agent_id: Set(log.agent_id.0),
+            user_id: Set(log.user_id.0),
+            session_id: Set(log.session_id.map(|id| id.0)),
+            model: Set(log.model.clone()),
+            prompt_tokens: Set(log.prompt_tokens),
+            completion_tokens: Set(log.completion_tokens),
+            total_tokens: Set(log.total_tokens),
+            estimated: Set(log.estimated),
+            cost: Set(log.cost),
+            usage_date: Set(log.usage_date),
+            created_at: Set(log.created_at),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmUsageLogRepository for LlmUsageLogRepositoryImpl {
+    async fn create(&self, log: &LlmUsageLog) -> Result<()> {
+        entities::llm_usage_log::Entity::insert(Self::domain_to_active_model(log))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_daily_usage_by_agent(
+        &self,
+        agent_id: &AgentId,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<DailyTokenUsage>> {
+        let logs = entities::llm_usage_log::Entity::find()
+            .filter(entities::llm_usage_log::Column::AgentId.eq(agent_id.0))
+            .filter(entities::llm_usage_log::Column::UsageDate.gte(start_date))
+            .filter(entities::llm_usage_log::Column::UsageDate.lte(end_date))
+            .order_by_asc(entities::llm_usage_log::Column::UsageDate)
+            .all(self.db.as_ref())
+            .await?;
+
+        let mut days: BTreeMap<NaiveDate, DailyTokenUsage> = BTreeMap::new();
+        for log in logs {
+            let day = days.entry(log.usage_date).or_insert_with(|| DailyTokenUsage {
+                usage_date: log.usage_date,
+                request_count: 0,
+                prompt_tokens: 0,
+                completion_tokens: 0,
+                total_tokens: 0,
+                cost: Decimal::ZERO,
+            });
+            day.request_count += 1;
+            day.prompt_tokens += log.prompt_tokens;
+            day.completion_tokens += log.completion_tokens;
+            day.total_tokens += log.total_tokens;
+            day.cost += log.cost;
+        }
+
+        Ok(days.into_values().collect())
+    }
+
+    async fn sum_tokens_by_tenant(
+        &self,
+        tenant_id: &TenantId,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<u64> {
+        let totals: Vec<i64> = entities::llm_usage_log::Entity::find()
+            .select_only()
+            .column(entities::llm_usage_log::Column::TotalTokens)
+            .filter(entities::llm_usage_log::Column::TenantId.eq(tenant_id.0))
+            .filter(entities::llm_usage_log::Column::UsageDate.gte(start_date))
+            .filter(entities::llm_usage_log::Column::UsageDate.lte(end_date))
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(totals.into_iter().map(|tokens| tokens.max(0) as u64).sum())
+    }
+}
@@ -261,6 +261,34 @@ impl MCPToolVersionRepository for MCPToolVersionRepositoryImpl {
         Ok(count)
     }
 
+    async fn prune_by_tool_id(&self, tool_id: MCPToolId, keep: u64) -> Result<u64, PlatformError> {
+        if keep == 0 {
+            return Ok(0);
+        }
+
+        // 保留的版本中最旧的一个
+        let oldest_kept = mcp_tool_version::Entity::find()
+            .filter(mcp_tool_version::Column::ToolId.eq(tool_id.0))
+            .order_by_desc(mcp_tool_version::Column::Version)
+            .offset(keep - 1)
+            .one(&*self.db)
+            .await
+            .map_err(PlatformError::DatabaseError)?;
+
+        let Some(oldest_kept) = oldest_kept else {
+            return Ok(0);
+        };
+
+        let result = mcp_tool_version::Entity::delete_many()
+            .filter(mcp_tool_version::Column::ToolId.eq(tool_id.0))
+            .filter(mcp_tool_version::Column::Version.lt(oldest_kept.version))
+            .exec(&*self.db)
+            .await
+            .map_err(PlatformError::DatabaseError)?;
+
+        Ok(result.rows_affected)
+    }
+
     async fn compare_versions(
         &self,
         tool_id: MCPToolId,
@@ -1,5 +1,6 @@
 pub mod user_repository_impl;
 pub mod tenant_repository_impl;
+pub mod tenant_settings_repository_impl;
 pub mod flow_repository_impl;
 pub mod session_repository_impl;
 pub mod mcp_tool_repository_impl;
@@ -11,15 +12,22 @@ pub mod execution_history_repository_impl;
 pub mod agent_repository_impl;
 pub mod agent_daily_stats_repository_impl;
 pub mod interview_record_repository_impl;
+pub mod flow_node_annotation_repository_impl;
 pub mod file_repository_impl;
 pub mod oss_file_repository_impl;
 pub mod api_key_repository_impl;
+pub mod event_store_impl;
+pub mod llm_usage_log_repository_impl;
+pub mod refresh_token_repository_impl;
+pub mod secret_store_impl;
+pub mod vector_record_metadata_repository_impl;
 
 #[cfg(test)]
 mod user_repository_test;
 
 pub use user_repository_impl::*;
 pub use tenant_repository_impl::*;
+pub use tenant_settings_repository_impl::*;
 pub use flow_repository_impl::*;
 pub use session_repository_impl::*;
 pub use mcp_tool_repository_impl::*;
@@ -31,6 +39,12 @@ pub use execution_history_repository_impl::*;
 pub use agent_repository_impl::*;
 pub use agent_daily_stats_repository_impl::*;
 pub use interview_record_repository_impl::*;
+pub use flow_node_annotation_repository_impl::*;
 pub use file_repository_impl::*;
 pub use oss_file_repository_impl::*;
-pub use api_key_repository_impl::*;
\ No newline at end of file
+pub use api_key_repository_impl::*;
+pub use event_store_impl::*;
+pub use llm_usage_log_repository_impl::*;
+pub use refresh_token_repository_impl::*;
+pub use secret_store_impl::*;
+pub use vector_record_metadata_repository_impl::*;
\ No newline at end of file
@@ -1,14 +1,22 @@
 use async_trait::async_trait;
 use aliyun_oss_client::Client;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::HashMap;
 
 use crate::{
     config::OssConfig,
-    domain::repositories::FileRepository,
+    domain::repositories::{FileRepository, PresignedPost},
     error::{PlatformError, Result},
 };
 
 pub struct OssFileRepositoryImpl {
     client: Client,
+    access_key_id: String,
+    access_key_secret: String,
+    bucket: String,
+    /// Bucket endpoint that accepts PostObject uploads
+    post_url: String,
     upload_path: String,
     download_domain: String,
 }
@@ -28,10 +36,43 @@ impl OssFileRepositoryImpl {
         
         Ok(Self {
             client,
+            post_url: format!("https://{}.{}", config.bucket, config.endpoint),
+            access_key_id: config.access_key_id,
+            access_key_secret: config.access_key_secret,
+            bucket: config.bucket,
             upload_path: config.upload_path,
             download_domain: config.download_domain,
         })
     }
+
+    /// PostObject policy restricting the upload to one key, content type and size
+    fn upload_policy(
+        bucket: &str,
+        key: &str,
+        content_type: &str,
+        max_size_bytes: u64,
+        expires_at: DateTime<Utc>,
+    ) -> String {
+        serde_json::json!({
+            "expiration": expires_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "conditions": [
+                { "bucket": bucket },
+                ["eq", "$key", key],
+                ["eq", "$Content-Type", content_type],
+                ["content-length-range", 1, max_size_bytes],
+            ],
+        })
+        .to_string()
+    }
+
+    /// OSS V1 form signature: base64 HMAC-SHA1 of the encoded policy
+    fn sign_policy(access_key_secret: &str, encoded_policy: &str) -> String {
+        let key = ring::hmac::Key::new(
+            ring::hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            access_key_secret.as_bytes(),
+        );
+        STANDARD.encode(ring::hmac::sign(&key, encoded_policy.as_bytes()).as_ref())
+    }
     
     fn build_object_path(&self, tenant_id: &str, file_id: &str, filename: &str) -> String {
         format!("{}/{}/{}/{}", self.upload_path, tenant_id, file_id, filename)
@@ -122,4 +163,97 @@ impl FileRepository for OssFileRepositoryImpl {
             Err(PlatformError::NotFound("File not found".to_string()))
         }
     }
+
+    async fn presign_upload(
+        &self,
+        tenant_id: &str,
+        file_id: &str,
+        filename: &str,
+        content_type: &str,
+        max_size_bytes: u64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<PresignedPost> {
+        let key = self.build_object_path(tenant_id, file_id, filename);
+        let policy = Self::upload_policy(&self.bucket, &key, content_type, max_size_bytes, expires_at);
+        let encoded_policy = STANDARD.encode(policy);
+        let signature = Self::sign_policy(&self.access_key_secret, &encoded_policy);
+
+        let fields = HashMap::from([
+            ("key".to_string(), key.clone()),
+            ("OSSAccessKeyId".to_string(), self.access_key_id.clone()),
+            ("policy".to_string(), encoded_policy),
+            ("Signature".to_string(), signature),
+            ("Content-Type".to_string(), content_type.to_string()),
+            ("success_action_status".to_string(), "200".to_string()),
+        ]);
+
+        Ok(PresignedPost {
+            url: self.post_url.clone(),
+            key,
+            fields,
+            expires_at,
+        })
+    }
+
+    async fn confirm_upload(&self, tenant_id: &str, key: &str) -> Result<String> {
+        use aliyun_oss_client::{QueryKey, QueryValue, ObjectPath};
+
+        let tenant_prefix = format!("{}/{}/", self.upload_path, tenant_id);
+        if !key.starts_with(&tenant_prefix) || key.split('/').any(|part| part == "..") {
+            return Err(PlatformError::AuthorizationFailed(
+                "Upload key does not belong to this tenant".to_string(),
+            ));
+        }
+
+        let prefix_key: QueryKey = "prefix".into();
+        let prefix_value: QueryValue = key.to_string().into();
+        let objects = self.client.clone()
+            .get_object_list(vec![(prefix_key, prefix_value)])
+            .await
+            .map_err(|e| PlatformError::InternalError(format!("Failed to list objects: {}", e)))?;
+
+        #[allow(deprecated)]
+        let uploaded = objects.object_list.iter().any(|object| {
+            let path: &ObjectPath = object.as_ref();
+            let path_str: &str = path.as_ref();
+            path_str == key
+        });
+        if !uploaded {
+            return Err(PlatformError::NotFound("Uploaded file not found".to_string()));
+        }
+
+        Ok(self.build_download_url(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_upload_policy_pins_key_type_and_size() {
+        let expires_at = Utc.with_ymd_and_hms(2024, 12, 1, 12, 0, 0).unwrap();
+        let policy: serde_json::Value = serde_json::from_str(&OssFileRepositoryImpl::upload_policy(
+            "assets",
+            "uploads/t1/f1/report.pdf",
+            "application/pdf",
+            1024,
+            expires_at,
+        ))
+        .unwrap();
+
+        assert_eq!(policy["expiration"], "2024-12-01T12:00:00.000Z");
+        assert_eq!(policy["conditions"][1], serde_json::json!(["eq", "$key", "uploads/t1/f1/report.pdf"]));
+        assert_eq!(policy["conditions"][3], serde_json::json!(["content-length-range", 1, 1024]));
+    }
+
+    #[test]
+    fn test_sign_policy_matches_oss_signature() {
+        let encoded_policy = STANDARD.encode(r#"{"expiration":"2024-12-01T12:00:00.000Z"}"#);
+        assert_eq!(
+            OssFileRepositoryImpl::sign_policy("secret", &encoded_policy),
+            "1NKJUzP1+HFpPyuirUUXp/ShSyY="
+        );
+    }
 }
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    prelude::Expr, sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::domain::entities::RefreshToken;
+use crate::domain::repositories::RefreshTokenRepository;
+use crate::domain::value_objects::{TenantId, UserId};
+use crate::infrastructure::database::entities;
+use crate::error::Result;
+
+pub struct RefreshTokenRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl RefreshTokenRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: entities::refresh_token::Model) -> RefreshToken {
+        RefreshToken {
+            id: entity.id,
+            user_id: UserId::from_uuid(entity.user_id),
+            tenant_id: TenantId::from_uuid(entity.tenant_id),
+            token_hash: entity.token_hash,
+            expires_at: entity.expires_at,
+            revoked_at: entity.revoked_at,
+            created_at: entity.created_at,
+        }
+    }
+
+    fn domain_to_active_model(token: &RefreshToken) -> entities::refresh_token::ActiveModel {
+        use sea_orm::ActiveValue::Set;
+
+        entities::refresh_token::ActiveModel {
+            id: Set(token.id),
+            user_id: Set(token.user_id.0),
+            tenant_id: Set(token.tenant_id.0),
+            token_hash: Set(token.token_hash.clone()),
+            expires_at: Set(token.expires_at),
+            revoked_at: Set(token.revoked_at),
+            created_at: Set(token.created_at),
+        }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+    async fn save(&self, token: &RefreshToken) -> Result<()> {
+        use entities::refresh_token::Column;
+
+        entities::refresh_token::Entity::insert(Self::domain_to_active_model(token))
+            .on_conflict(
+                OnConflict::column(Column::Id)
+                    .update_columns([Column::RevokedAt])
+                    .to_owned(),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>> {
+        let token = entities::refresh_token::Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(token.map(Self::entity_to_domain))
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>> {
+        let token = entities::refresh_token::Entity::find()
+            .filter(entities::refresh_token::Column::TokenHash.eq(token_hash))
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(token.map(Self::entity_to_domain))
+    }
+
+    async fn revoke(&self, id: Uuid) -> Result<bool> {
+        use entities::refresh_token::Column;
+
+        let result = entities::refresh_token::Entity::update_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::RevokedAt.is_null())
+            .col_expr(Column::RevokedAt, Expr::value(Utc::now()))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn revoke_all_by_user(&self, user_id: &UserId) -> Result<u64> {
+        use entities::refresh_token::Column;
+
+        let result = entities::refresh_token::Entity::update_many()
+            .filter(Column::UserId.eq(user_id.0))
+            .filter(Column::RevokedAt.is_null())
+            .filter(Column::ExpiresAt.gt(Utc::now()))
+            .col_expr(Column::RevokedAt, Expr::value(Utc::now()))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,282 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use sea_orm::{
+    sea_query::{Alias, Expr, OnConflict, Query},
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::services::{validate_secret, SecretStore};
+use crate::domain::value_objects::TenantId;
+use crate::error::{PlatformError, Result};
+use crate::infrastructure::database::entities;
+
+/// HKDF salt and info for the secret encryption key; changing either makes
+/// stored secrets unreadable
+const KEY_DERIVATION_SALT: &[u8] = b"avalon.tenant-secrets";
+const KEY_DERIVATION_INFO: &[u8] = b"aes-256-gcm";
+
+/// AES-256-GCM with a key derived from `JWT_SECRET` via HKDF-SHA256.
+/// Each value is bound to its tenant and key through the associated data,
+/// so ciphertexts cannot be moved between rows.
+pub struct SecretCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl SecretCipher {
+    pub fn new(jwt_secret: &str) -> Result<Self> {
+        let prk = Salt::new(HKDF_SHA256, KEY_DERIVATION_SALT).extract(jwt_secret.as_bytes());
+        let okm = prk
+            .expand(&[KEY_DERIVATION_INFO], &AES_256_GCM)
+            .map_err(|_| PlatformError::InternalError("Failed to derive secret key".to_string()))?;
+
+        Ok(Self {
+            key: LessSafeKey::new(UnboundKey::from(okm)),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    fn associated_data(tenant_id: TenantId, key: &str) -> String {
+        format!("{}:{}", tenant_id.0, key)
+    }
+
+    /// Base64 of a random nonce followed by the sealed value
+    pub fn encrypt(&self, tenant_id: TenantId, key: &str, value: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| PlatformError::InternalError("Failed to generate nonce".to_string()))?;
+
+        let mut sealed = value.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(Self::associated_data(tenant_id, key).as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| PlatformError::InternalError("Failed to encrypt secret".to_string()))?;
+
+        let mut ciphertext = nonce.to_vec();
+        ciphertext.extend_from_slice(&sealed);
+        Ok(STANDARD.encode(ciphertext))
+    }
+
+    pub fn decrypt(&self, tenant_id: TenantId, key: &str, ciphertext: &str) -> Result<String> {
+        let unreadable =
+            || PlatformError::InternalError(format!("Secret {} cannot be decrypted", key));
+
+        let ciphertext = STANDARD.decode(ciphertext).map_err(|_| unreadable())?;
+        if ciphertext.len() < NONCE_LEN + AES_256_GCM.tag_len() {
+            return Err(unreadable());
+        }
+
+        let (nonce, sealed) = ciphertext.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| unreadable())?;
+        let mut sealed = sealed.to_vec();
+        let value = self
+            .key
+            .open_in_place(
+                nonce,
+                Aad::from(Self::associated_data(tenant_id, key).as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| unreadable())?;
+
+        String::from_utf8(value.to_vec()).map_err(|_| unreadable())
+    }
+}
+
+impl std::fmt::Debug for SecretCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretCipher")
+            .field("algorithm", &AES_256_GCM)
+            .field("key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Secrets encrypted into the `tenant_secrets` table
+pub struct SecretStoreImpl {
+    db: Arc<DatabaseConnection>,
+    cipher: SecretCipher,
+}
+
+impl SecretStoreImpl {
+    pub fn new(db: Arc<DatabaseConnection>, jwt_secret: &str) -> Result<Self> {
+        Ok(Self {
+            db,
+            cipher: SecretCipher::new(jwt_secret)?,
+        })
+    }
+
+    async fn find(&self, tenant_id: TenantId, key: &str) -> Result<entities::tenant_secret::Model> {
+        entities::TenantSecret::find_by_id((tenant_id.0, key.to_string()))
+            .one(self.db.as_ref())
+            .await?
+            .ok_or_else(|| PlatformError::NotFound(format!("Secret {} not found", key)))
+    }
+
+    /// Encrypt the plain-text secrets left in `tenant_settings.legacy_secrets`
+    /// by the tenant_secrets migration and clear the column. Secrets set
+    /// since then are kept. A tenant whose values cannot all be imported
+    /// keeps its column so nothing is lost; returns the number imported.
+    pub async fn import_legacy_secrets(&self) -> Result<usize> {
+        let backend = self.db.get_database_backend();
+        let select = Query::select()
+            .columns([Alias::new("tenant_id"), Alias::new("legacy_secrets")])
+            .from(Alias::new("tenant_settings"))
+            .and_where(Expr::col(Alias::new("legacy_secrets")).is_not_null())
+            .to_owned();
+
+        let mut imported = 0;
+        for row in self.db.query_all(backend.build(&select)).await? {
+            let tenant_id = TenantId(row.try_get("", "tenant_id")?);
+            let secrets: serde_json::Value = row.try_get("", "legacy_secrets")?;
+            let secrets: HashMap<String, String> = serde_json::from_value(secrets)?;
+
+            let mut complete = true;
+            for (key, value) in secrets {
+                if self.find(tenant_id, &key).await.is_ok() {
+                    continue;
+                }
+                match self.set(tenant_id, &key, value).await {
+                    Ok(()) => imported += 1,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Legacy secret {} of tenant {} not imported: {}",
+                            key,
+                            tenant_id.0,
+                            e
+                        );
+                        complete = false;
+                    }
+                }
+            }
+
+            if complete {
+                let clear = Query::update()
+                    .table(Alias::new("tenant_settings"))
+                    .value(
+                        Alias::new("legacy_secrets"),
+                        Expr::value(Option::<serde_json::Value>::None),
+                    )
+                    .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id.0))
+                    .to_owned();
+                self.db.execute(backend.build(&clear)).await?;
+            }
+        }
+
+        Ok(imported)
+    }
+}
+
+impl std::fmt::Debug for SecretStoreImpl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretStoreImpl")
+            .field("cipher", &self.cipher)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl SecretStore for SecretStoreImpl {
+    async fn set(&self, tenant_id: TenantId, key: &str, value: String) -> Result<()> {
+        use entities::tenant_secret::Column;
+
+        validate_secret(key, &value)?;
+
+        let now = chrono::Utc::now();
+        let secret = entities::tenant_secret::ActiveModel {
+            tenant_id: Set(tenant_id.0),
+            secret_key: Set(key.to_string()),
+            ciphertext: Set(self.cipher.encrypt(tenant_id, key, &value)?),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        entities::TenantSecret::insert(secret)
+            .on_conflict(
+                OnConflict::columns([Column::TenantId, Column::SecretKey])
+                    .update_columns([Column::Ciphertext, Column::UpdatedAt])
+                    .to_owned(),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, tenant_id: TenantId, key: &str) -> Result<String> {
+        let secret = self.find(tenant_id, key).await?;
+        self.cipher.decrypt(tenant_id, key, &secret.ciphertext)
+    }
+
+    async fn delete(&self, tenant_id: TenantId, key: &str) -> Result<()> {
+        let result = entities::TenantSecret::delete_by_id((tenant_id.0, key.to_string()))
+            .exec(self.db.as_ref())
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(PlatformError::NotFound(format!("Secret {} not found", key)));
+        }
+        Ok(())
+    }
+
+    async fn list_keys(&self, tenant_id: TenantId) -> Result<Vec<String>> {
+        use entities::tenant_secret::Column;
+
+        let secrets = entities::TenantSecret::find()
+            .filter(Column::TenantId.eq(tenant_id.0))
+            .order_by_asc(Column::SecretKey)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(secrets.into_iter().map(|secret| secret.secret_key).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let cipher = SecretCipher::new("jwt-secret").unwrap();
+        let tenant_id = TenantId::new();
+
+        let first = cipher.encrypt(tenant_id, "API_KEY", "sk-123").unwrap();
+        let second = cipher.encrypt(tenant_id, "API_KEY", "sk-123").unwrap();
+        assert_ne!(first, second);
+        assert!(!first.contains("sk-123"));
+
+        assert_eq!(cipher.decrypt(tenant_id, "API_KEY", &first).unwrap(), "sk-123");
+        assert_eq!(cipher.decrypt(tenant_id, "API_KEY", &second).unwrap(), "sk-123");
+    }
+
+    #[test]
+    fn test_decrypt_rejects_other_rows_and_keys() {
+        let cipher = SecretCipher::new("jwt-secret").unwrap();
+        let tenant_id = TenantId::new();
+        let ciphertext = cipher.encrypt(tenant_id, "API_KEY", "sk-123").unwrap();
+
+        assert!(cipher.decrypt(TenantId::new(), "API_KEY", &ciphertext).is_err());
+        assert!(cipher.decrypt(tenant_id, "OTHER_KEY", &ciphertext).is_err());
+        assert!(cipher.decrypt(tenant_id, "API_KEY", "not base64!").is_err());
+
+        let other_cipher = SecretCipher::new("rotated-secret").unwrap();
+        assert!(other_cipher.decrypt(tenant_id, "API_KEY", &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_key() {
+        let cipher = SecretCipher::new("jwt-secret").unwrap();
+        let debug = format!("{:?}", cipher);
+        assert!(debug.contains("[REDACTED]"));
+        assert!(!debug.contains("jwt-secret"));
+    }
+}
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
 use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect, PaginatorTrait, QueryOrder};
+use sea_orm::sea_query::Expr;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
-use crate::domain::entities::{ChatSession, Message};
-use crate::domain::repositories::{ChatSessionRepository, MessageRepository};
+use crate::domain::entities::{ChatSession, Message, MessageBranch};
+use crate::domain::repositories::{ChatSessionRepository, MessageBranchRepository, MessageRepository};
 use crate::domain::value_objects::{SessionId, TenantId, UserId, MessageId, SessionContext, ChatMessage, MessageRole};
 use crate::infrastructure::database::entities;
 use crate::error::{Result, PlatformError};
@@ -31,6 +32,7 @@ impl ChatSessionRepositoryImpl {
             user_id: UserId::from_uuid(entity.user_id),
             title: entity.title,
             context,
+            summary: entity.summary,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
         })
@@ -48,6 +50,7 @@ impl ChatSessionRepositoryImpl {
             user_id: Set(session.user_id.0),
             title: Set(session.title.clone()),
             context: Set(Some(context_json)),
+            summary: Set(session.summary.clone()),
             created_at: Set(session.created_at),
             updated_at: Set(session.updated_at),
         })
@@ -95,6 +98,27 @@ impl ChatSessionRepository for ChatSessionRepositoryImpl {
         Ok(result)
     }
 
+    async fn find_by_tenant_updated_before(
+        &self,
+        tenant_id: &TenantId,
+        before: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<ChatSession>> {
+        let sessions = entities::ChatSession::find()
+            .filter(entities::chat_session::Column::TenantId.eq(tenant_id.0))
+            .filter(entities::chat_session::Column::UpdatedAt.lt(before))
+            .order_by_asc(entities::chat_session::Column::UpdatedAt)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        let mut result = Vec::new();
+        for entity in sessions {
+            result.push(Self::entity_to_domain(entity)?);
+        }
+        Ok(result)
+    }
+
     async fn find_by_tenant_and_user(&self, tenant_id: &TenantId, user_id: &UserId) -> Result<Vec<ChatSession>> {
         let sessions = entities::ChatSession::find()
             .filter(entities::chat_session::Column::TenantId.eq(tenant_id.0))
@@ -230,6 +254,7 @@ impl MessageRepositoryImpl {
             id: MessageId::from_uuid(entity.id),
             session_id: SessionId::from_uuid(entity.session_id),
             message: chat_message,
+            superseded_at: entity.superseded_at,
         })
     }
 
@@ -256,6 +281,7 @@ impl MessageRepositoryImpl {
             content: Set(message.message.get_text_content()),
             metadata: Set(metadata_json),
             created_at: Set(message.message.timestamp),
+            superseded_at: Set(message.superseded_at),
         })
     }
 }
@@ -290,6 +316,7 @@ impl MessageRepository for MessageRepositoryImpl {
     async fn find_recent_by_session(&self, session_id: &SessionId, limit: u64) -> Result<Vec<Message>> {
         let messages = entities::ChatMessage::find()
             .filter(entities::chat_message::Column::SessionId.eq(session_id.0))
+            .filter(entities::chat_message::Column::SupersededAt.is_null())
             .order_by_desc(entities::chat_message::Column::CreatedAt)
             .limit(limit)
             .all(self.db.as_ref())
@@ -392,4 +419,80 @@ impl MessageRepository for MessageRepositoryImpl {
         }
         Ok(result)
     }
-}
\ No newline at end of file
+
+    async fn mark_superseded(&self, ids: &[MessageId], superseded_at: DateTime<Utc>) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        entities::ChatMessage::update_many()
+            .col_expr(entities::chat_message::Column::SupersededAt, Expr::value(superseded_at))
+            .filter(entities::chat_message::Column::Id.is_in(ids.iter().map(|id| id.0)))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct MessageBranchRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl MessageBranchRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: entities::message_branch::Model) -> MessageBranch {
+        MessageBranch {
+            id: entity.id,
+            session_id: SessionId::from_uuid(entity.session_id),
+            original_message_id: MessageId::from_uuid(entity.original_message_id),
+            edited_message_id: MessageId::from_uuid(entity.edited_message_id),
+            response_message_id: entity.response_message_id.map(MessageId::from_uuid),
+            created_by: UserId::from_uuid(entity.created_by),
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[async_trait]
+impl MessageBranchRepository for MessageBranchRepositoryImpl {
+    async fn save(&self, branch: &MessageBranch) -> Result<()> {
+        use sea_orm::ActiveValue::Set;
+
+        let active_model = entities::message_branch::ActiveModel {
+            id: Set(branch.id),
+            session_id: Set(branch.session_id.0),
+            original_message_id: Set(branch.original_message_id.0),
+            edited_message_id: Set(branch.edited_message_id.0),
+            response_message_id: Set(branch.response_message_id.map(|id| id.0)),
+            created_by: Set(branch.created_by.0),
+            created_at: Set(branch.created_at),
+        };
+
+        entities::MessageBranch::insert(active_model)
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn find_by_original(&self, original_message_id: &MessageId) -> Result<Vec<MessageBranch>> {
+        let branches = entities::MessageBranch::find()
+            .filter(entities::message_branch::Column::OriginalMessageId.eq(original_message_id.0))
+            .order_by_asc(entities::message_branch::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(branches.into_iter().map(Self::entity_to_domain).collect())
+    }
+
+    async fn find_by_edited(&self, edited_message_id: &MessageId) -> Result<Option<MessageBranch>> {
+        let branch = entities::MessageBranch::find()
+            .filter(entities::message_branch::Column::EditedMessageId.eq(edited_message_id.0))
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(branch.map(Self::entity_to_domain))
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use sea_orm::{sea_query::OnConflict, DatabaseConnection, EntityTrait};
+use std::sync::Arc;
+use crate::domain::entities::{QuotaConfig, TenantSettings};
+use crate::domain::repositories::TenantSettingsRepository;
+use crate::domain::value_objects::TenantId;
+use crate::infrastructure::database::entities;
+use crate::error::Result;
+
+pub struct TenantSettingsRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl TenantSettingsRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: entities::tenant_settings::Model) -> Result<TenantSettings> {
+        let quota = match entity.quota {
+            Some(quota) => serde_json::from_value(quota)?,
+            None => QuotaConfig::default(),
+        };
+
+        Ok(TenantSettings {
+            tenant_id: TenantId::from_uuid(entity.tenant_id),
+            max_system_prompt_length: entity
+                .max_system_prompt_length
+                .and_then(|len| usize::try_from(len).ok()),
+            quota,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        })
+    }
+
+    fn domain_to_active_model(settings: &TenantSettings) -> Result<entities::tenant_settings::ActiveModel> {
+        use sea_orm::ActiveValue::Set;
+
+        Ok(entities::tenant_settings::ActiveModel {
+            tenant_id: Set(settings.tenant_id.0),
+            max_system_prompt_length: Set(settings
+                .max_system_prompt_length
+                .map(|len| i32::try_from(len).unwrap_or(i32::MAX))),
+            quota: Set(Some(serde_json::to_value(&settings.quota)?)),
+            created_at: Set(settings.created_at),
+            updated_at: Set(settings.updated_at),
+        })
+    }
+}
+
+#[async_trait]
+impl TenantSettingsRepository for TenantSettingsRepositoryImpl {
+    async fn find_by_tenant(&self, tenant_id: &TenantId) -> Result<Option<TenantSettings>> {
+        let settings = entities::tenant_settings::Entity::find_by_id(tenant_id.0)
+            .one(self.db.as_ref())
+            .await?;
+
+        settings.map(Self::entity_to_domain).transpose()
+    }
+
+    async fn save(&self, settings: &TenantSettings) -> Result<()> {
+        use entities::tenant_settings::Column;
+
+        entities::tenant_settings::Entity::insert(Self::domain_to_active_model(settings)?)
+            .on_conflict(
+                OnConflict::column(Column::TenantId)
+                    .update_columns([Column::MaxSystemPromptLength, Column::Quota, Column::UpdatedAt])
+                    .to_owned(),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect, PaginatorTrait};
+use std::collections::HashMap;
 use std::sync::Arc;
 use crate::domain::entities::User;
 use crate::domain::repositories::UserRepository;
@@ -20,13 +21,18 @@ impl UserRepositoryImpl {
         let username = Username::new(entity.username)
             .map_err(|e| PlatformError::ValidationError(e))?;
         
-        User::new(
+        let mut user = User::new(
             UserId::from_uuid(entity.id),
             TenantId::from_uuid(entity.tenant_id),
             username,
             entity.password_hash,
             entity.nickname,
-        ).map_err(|e| PlatformError::ValidationError(e))
+        ).map_err(|e| PlatformError::ValidationError(e))?;
+        user.email = entity.email;
+        user.language = entity.language;
+        user.avatar_url = entity.avatar_url;
+
+        Ok(user)
     }
 
     pub fn domain_to_active_model(user: &User) -> entities::user::ActiveModel {
@@ -37,6 +43,9 @@ impl UserRepositoryImpl {
             tenant_id: Set(user.tenant_id.0),
             username: Set(user.username.0.clone()),
             nickname: Set(user.nickname.clone()),
+            email: Set(user.email.clone()),
+            language: Set(user.language.clone()),
+            avatar_url: Set(user.avatar_url.clone()),
             password_hash: Set(user.password_hash.clone()),
             created_at: Set(user.created_at),
             updated_at: Set(user.updated_at),
@@ -57,6 +66,24 @@ impl UserRepository for UserRepositoryImpl {
         }
     }
 
+    async fn find_by_ids(&self, ids: &[UserId]) -> Result<HashMap<UserId, User>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let users = entities::user::Entity::find()
+            .filter(entities::user::Column::Id.is_in(ids.iter().map(|id| id.0)))
+            .all(self.db.as_ref())
+            .await?;
+
+        let mut result = HashMap::with_capacity(users.len());
+        for entity in users {
+            let user = Self::entity_to_domain(entity)?;
+            result.insert(user.id, user);
+        }
+        Ok(result)
+    }
+
     async fn find_by_tenant_and_username(&self, tenant_id: TenantId, username: &str) -> Result<Option<User>> {
         let user = entities::user::Entity::find()
             .filter(entities::user::Column::TenantId.eq(tenant_id.0))
@@ -27,6 +27,9 @@ mod tests {
             tenant_id: Uuid::new_v4(),
             username: "testuser".to_string(),
             nickname: Some("Test User".to_string()),
+            email: None,
+            language: None,
+            avatar_url: None,
             password_hash: "hashed_password".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
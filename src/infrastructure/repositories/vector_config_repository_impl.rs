@@ -27,8 +27,9 @@ impl VectorConfigRepositoryImpl {
     fn entity_to_domain(entity: vector_config::Model) -> Result<VectorConfigEntity, PlatformError> {
         let provider = VectorProvider::from_str(&entity.provider)?;
         
-        let connection_params: HashMap<String, String> = serde_json::from_value(entity.config)
+        let mut connection_params: HashMap<String, String> = serde_json::from_value(entity.config)
             .map_err(|e| PlatformError::SerializationError(e))?;
+        provider.normalize_connection_params(&mut connection_params);
         
         Ok(VectorConfigEntity {
             id: ConfigId::from_uuid(entity.id),
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use std::sync::Arc;
+
+use crate::domain::repositories::VectorRecordMetadataRepository;
+use crate::domain::value_objects::{ConfigId, TenantId, VectorRecordExpiry};
+use crate::error::Result;
+use crate::infrastructure::database::entities::{self, vector_record_metadata::Column};
+
+pub struct VectorRecordMetadataRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl VectorRecordMetadataRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: entities::vector_record_metadata::Model) -> VectorRecordExpiry {
+        VectorRecordExpiry {
+            config_id: ConfigId::from_uuid(entity.config_id),
+            tenant_id: TenantId::from_uuid(entity.tenant_id),
+            namespace: (!entity.namespace.is_empty()).then_some(entity.namespace),
+            record_id: entity.record_id,
+            expires_at: entity.expires_at,
+        }
+    }
+}
+
+#[async_trait]
+impl VectorRecordMetadataRepository for VectorRecordMetadataRepositoryImpl {
+    async fn save_all(&self, expiries: &[VectorRecordExpiry]) -> Result<()> {
+        if expiries.is_empty() {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let models = expiries.iter().map(|expiry| entities::vector_record_metadata::ActiveModel {
+            config_id: Set(expiry.config_id.0),
+            namespace: Set(expiry.namespace.clone().unwrap_or_default()),
+            record_id: Set(expiry.record_id.clone()),
+            tenant_id: Set(expiry.tenant_id.0),
+            expires_at: Set(expiry.expires_at),
+            created_at: Set(now),
+        });
+
+        entities::VectorRecordMetadata::insert_many(models)
+            .on_conflict(
+                OnConflict::columns([Column::ConfigId, Column::Namespace, Column::RecordId])
+                    .update_column(Column::ExpiresAt)
+                    .to_owned(),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        config_id: ConfigId,
+        namespace: Option<String>,
+        record_ids: Vec<String>,
+    ) -> Result<()> {
+        if record_ids.is_empty() {
+            return Ok(());
+        }
+
+        entities::VectorRecordMetadata::delete_many()
+            .filter(Column::ConfigId.eq(config_id.0))
+            .filter(Column::Namespace.eq(namespace.unwrap_or_default()))
+            .filter(Column::RecordId.is_in(record_ids))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_expired(&self, now: DateTime<Utc>, limit: u64) -> Result<Vec<VectorRecordExpiry>> {
+        let entities = entities::VectorRecordMetadata::find()
+            .filter(Column::ExpiresAt.lte(now))
+            .order_by_asc(Column::ExpiresAt)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(entities.into_iter().map(Self::entity_to_domain).collect())
+    }
+}
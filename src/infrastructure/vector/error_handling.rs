@@ -56,7 +56,7 @@ impl VectorRetryWrapper {
                         return Err(error);
                     }
                     
-                    log::warn!(
+                    tracing::warn!(
                         "Vector operation attempt {} failed: {}, retrying in {:?}",
                         attempt, error_msg, delay
                     );
@@ -88,7 +88,10 @@ impl VectorRetryWrapper {
                 msg.contains("502") ||
                 msg.contains("504")
             },
-            PlatformError::InternalError(_) => true,
+            PlatformError::InternalError(_)
+            | PlatformError::RateLimit(_)
+            | PlatformError::ServiceUnavailable(_)
+            | PlatformError::Timeout(_) => true,
             _ => false,
         }
     }
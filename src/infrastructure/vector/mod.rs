@@ -2,10 +2,13 @@ pub mod providers;
 pub mod error_handling;
 
 use async_trait::async_trait;
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use std::collections::HashMap;
+use std::future::Future;
 
 use crate::domain::value_objects::{
-    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation
+    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation, TenantId
 };
 use crate::error::PlatformError;
 
@@ -18,7 +21,9 @@ pub trait VectorStore: Send + Sync {
     /// Store multiple vector records in batch
     async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError>;
     
-    /// Search for similar vectors
+    /// Search for similar vectors. Queries with a sparse vector run as hybrid
+    /// searches on providers whose `supports_hybrid_search` is set; other
+    /// providers search with the dense vector only.
     async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError>;
     
     /// Delete vectors by IDs
@@ -53,11 +58,329 @@ pub struct VectorProviderInfo {
     pub version: String,
     pub supports_namespaces: bool,
     pub supports_metadata_filtering: bool,
+    /// Dense + sparse search through `SearchQuery::sparse_vector`. Supported
+    /// by Qdrant, by Pinecone indexes using the dotproduct metric, and by
+    /// Milvus 2.4+ collections created with `sparse_vectors = true`;
+    /// ChromaDB and Weaviate search dense only.
     pub supports_hybrid_search: bool,
+    /// Expired records (`VectorRecord::expires_at`) are hidden from queries
+    /// and purged by the store itself, using an indexed payload field.
+    /// Expiry on other providers is tracked in `vector_record_metadata` and
+    /// enforced by `VectorTTLReaper`.
+    pub supports_native_ttl: bool,
     pub max_vector_dimension: usize,
     pub max_batch_size: usize,
 }
 
+/// Connection parameter setting how many upsert chunks are sent at once
+pub const UPSERT_PARALLELISM_PARAM: &str = "upsert_parallelism";
+
+/// Connection parameter (`"true"`) making a tenant's store also read and
+/// delete records in the unprefixed namespaces used before tenant isolation
+pub const LEGACY_NAMESPACE_FALLBACK_PARAM: &str = "legacy_namespace_fallback";
+
+/// Splits items into chunks of at most `chunk_size` and runs an operation on
+/// each, at most `parallelism` at a time
+#[derive(Debug, Clone, Copy)]
+pub struct BatchChunker<T> {
+    chunk_size: usize,
+    parallelism: usize,
+    _items: std::marker::PhantomData<fn(T)>,
+}
+
+impl<T> BatchChunker<T> {
+    pub fn new(chunk_size: usize) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            parallelism: 1,
+            _items: std::marker::PhantomData,
+        }
+    }
+    
+    /// Run up to `parallelism` chunks concurrently; 1 (the default) runs them in order
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+    
+    pub fn chunk(&self, mut items: Vec<T>) -> Vec<Vec<T>> {
+        let mut chunks = Vec::with_capacity(items.len().div_ceil(self.chunk_size));
+        while items.len() > self.chunk_size {
+            let rest = items.split_off(self.chunk_size);
+            chunks.push(std::mem::replace(&mut items, rest));
+        }
+        if !items.is_empty() {
+            chunks.push(items);
+        }
+        chunks
+    }
+    
+    /// Run `operation` on every chunk. Chunks not yet started when one fails
+    /// are skipped and the first error is returned; chunks already written
+    /// stay written.
+    pub async fn run<F, Fut>(&self, items: Vec<T>, operation: F) -> Result<(), PlatformError>
+    where
+        F: Fn(Vec<T>) -> Fut,
+        Fut: Future<Output = Result<(), PlatformError>>,
+    {
+        let mut results = stream::iter(self.chunk(items))
+            .map(operation)
+            .buffered(self.parallelism);
+        
+        while let Some(result) = results.next().await {
+            result?;
+        }
+        Ok(())
+    }
+}
+
+/// Adapter splitting batch writes into chunks the wrapped store accepts,
+/// per its `VectorProviderInfo::max_batch_size`. Every other call is
+/// delegated unchanged.
+pub struct BatchChunkingStore {
+    inner: Box<dyn VectorStore>,
+    parallelism: usize,
+}
+
+impl BatchChunkingStore {
+    pub fn new(inner: Box<dyn VectorStore>) -> Self {
+        Self { inner, parallelism: 1 }
+    }
+    
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+    
+    fn chunker<T>(&self) -> BatchChunker<T> {
+        BatchChunker::new(self.inner.provider_info().max_batch_size).with_parallelism(self.parallelism)
+    }
+}
+
+#[async_trait]
+impl VectorStore for BatchChunkingStore {
+    async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
+        self.inner.upsert(record).await
+    }
+    
+    async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
+        self.chunker()
+            .run(records, |chunk| self.inner.upsert_batch(chunk))
+            .await
+    }
+    
+    async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        self.inner.query(query).await
+    }
+    
+    async fn delete(&self, ids: Vec<String>, namespace: Option<String>) -> Result<(), PlatformError> {
+        self.inner.delete(ids, namespace).await
+    }
+    
+    /// Oversized operations are sent as several, upserts first, one after another
+    async fn execute_batch(&self, operation: BatchOperation) -> Result<(), PlatformError> {
+        let max_batch_size = self.inner.provider_info().max_batch_size.max(1);
+        if operation.upsert.len() + operation.delete.len() <= max_batch_size {
+            return self.inner.execute_batch(operation).await;
+        }
+        
+        let BatchOperation { upsert, delete, expires_at } = operation;
+        let chunker = BatchChunker::new(max_batch_size);
+        let delete_chunker = BatchChunker::<String>::new(max_batch_size);
+        
+        for records in chunker.chunk(upsert) {
+            let mut chunk = BatchOperation::new();
+            chunk.upsert = records;
+            chunk.expires_at = expires_at;
+            self.inner.execute_batch(chunk).await?;
+        }
+        for ids in delete_chunker.chunk(delete) {
+            let mut chunk = BatchOperation::new();
+            chunk.delete = ids;
+            self.inner.execute_batch(chunk).await?;
+        }
+        Ok(())
+    }
+    
+    async fn create_index(&self, config: IndexConfig) -> Result<(), PlatformError> {
+        self.inner.create_index(config).await
+    }
+    
+    async fn delete_index(&self, index_name: String) -> Result<(), PlatformError> {
+        self.inner.delete_index(index_name).await
+    }
+    
+    async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
+        self.inner.list_indexes().await
+    }
+    
+    async fn get_stats(&self, namespace: Option<String>) -> Result<VectorStats, PlatformError> {
+        self.inner.get_stats(namespace).await
+    }
+    
+    async fn test_connection(&self) -> Result<(), PlatformError> {
+        self.inner.test_connection().await
+    }
+    
+    fn provider_info(&self) -> VectorProviderInfo {
+        self.inner.provider_info()
+    }
+}
+
+/// Adapter confining a store to one tenant's namespaces. Every namespace
+/// sent to the wrapped store is prefixed with `{tenant_id}/` (the default
+/// namespace becomes `{tenant_id}`) and the prefix is stripped from the
+/// namespaces it reports, so a flow naming another tenant's namespace only
+/// reaches its own. Index-level operations span tenants and are refused.
+///
+/// With the legacy fallback, queries and deletes also cover the unprefixed
+/// namespace records were written to before isolation. Those records carry
+/// no tenant, so the fallback is only safe on collections no other tenant
+/// shares.
+pub struct TenantIsolatedVectorStore {
+    inner: Box<dyn VectorStore>,
+    tenant_id: TenantId,
+    legacy_fallback: bool,
+}
+
+impl TenantIsolatedVectorStore {
+    pub fn new(inner: Box<dyn VectorStore>, tenant_id: TenantId) -> Self {
+        Self {
+            inner,
+            tenant_id,
+            legacy_fallback: false,
+        }
+    }
+    
+    /// Also read and delete in the pre-isolation, unprefixed namespaces
+    pub fn with_legacy_fallback(mut self, legacy_fallback: bool) -> Self {
+        self.legacy_fallback = legacy_fallback;
+        self
+    }
+    
+    /// Namespace of the wrapped store holding the tenant's `namespace`
+    pub fn tenant_namespace(&self, namespace: Option<&str>) -> String {
+        match namespace {
+            Some(ns) if !ns.is_empty() => format!("{}/{}", self.tenant_id, ns),
+            _ => self.tenant_id.to_string(),
+        }
+    }
+    
+    /// Namespace as the tenant knows it, `""` for the default namespace, or
+    /// `None` if the namespace belongs to another tenant
+    pub fn local_namespace(&self, namespace: &str) -> Option<String> {
+        let tenant = self.tenant_id.to_string();
+        if namespace == tenant {
+            return Some(String::new());
+        }
+        namespace
+            .strip_prefix(&tenant)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map(str::to_string)
+    }
+    
+    fn isolate_record(&self, mut record: VectorRecord) -> Result<VectorRecord, PlatformError> {
+        if record.tenant_id != self.tenant_id {
+            return Err(PlatformError::AuthorizationFailed(
+                "Vector record tenant ID does not match the store's tenant".to_string()
+            ));
+        }
+        record.namespace = Some(self.tenant_namespace(record.namespace.as_deref()));
+        Ok(record)
+    }
+    
+    fn index_operations_refused() -> PlatformError {
+        PlatformError::AuthorizationFailed(
+            "Index operations are not available on a tenant's vector store".to_string()
+        )
+    }
+}
+
+#[async_trait]
+impl VectorStore for TenantIsolatedVectorStore {
+    async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
+        self.inner.upsert(self.isolate_record(record)?).await
+    }
+    
+    async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
+        let records = records
+            .into_iter()
+            .map(|record| self.isolate_record(record))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.inner.upsert_batch(records).await
+    }
+    
+    async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        let mut scoped = query.clone();
+        scoped.namespace = Some(self.tenant_namespace(query.namespace.as_deref()));
+        let mut results = self.inner.query(scoped).await?;
+        if !self.legacy_fallback {
+            return Ok(results);
+        }
+        
+        // Records re-written since isolation shadow their legacy copies
+        let top_k = query.top_k;
+        let legacy = self.inner.query(query).await?;
+        let seen: std::collections::HashSet<String> =
+            results.iter().map(|result| result.id.clone()).collect();
+        results.extend(legacy.into_iter().filter(|result| !seen.contains(&result.id)));
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(top_k);
+        Ok(results)
+    }
+    
+    async fn delete(&self, ids: Vec<String>, namespace: Option<String>) -> Result<(), PlatformError> {
+        let scoped = self.tenant_namespace(namespace.as_deref());
+        if self.legacy_fallback {
+            self.inner.delete(ids.clone(), namespace).await?;
+        }
+        self.inner.delete(ids, Some(scoped)).await
+    }
+    
+    async fn execute_batch(&self, mut operation: BatchOperation) -> Result<(), PlatformError> {
+        operation.upsert = operation
+            .upsert
+            .into_iter()
+            .map(|record| self.isolate_record(record))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.inner.execute_batch(operation).await
+    }
+    
+    async fn create_index(&self, _config: IndexConfig) -> Result<(), PlatformError> {
+        Err(Self::index_operations_refused())
+    }
+    
+    async fn delete_index(&self, _index_name: String) -> Result<(), PlatformError> {
+        Err(Self::index_operations_refused())
+    }
+    
+    async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
+        Err(Self::index_operations_refused())
+    }
+    
+    /// Stats of the tenant's namespace, with other tenants' namespaces left out
+    async fn get_stats(&self, namespace: Option<String>) -> Result<VectorStats, PlatformError> {
+        let namespace = self.tenant_namespace(namespace.as_deref());
+        let mut stats = self.inner.get_stats(Some(namespace)).await?;
+        stats.namespace_stats = stats
+            .namespace_stats
+            .into_iter()
+            .filter_map(|(namespace, ns_stats)| {
+                self.local_namespace(&namespace).map(|local| (local, ns_stats))
+            })
+            .collect();
+        Ok(stats)
+    }
+    
+    async fn test_connection(&self) -> Result<(), PlatformError> {
+        self.inner.test_connection().await
+    }
+    
+    fn provider_info(&self) -> VectorProviderInfo {
+        self.inner.provider_info()
+    }
+}
+
 /// Vector store configuration
 #[derive(Debug, Clone)]
 pub struct VectorStoreConfig {
@@ -66,6 +389,9 @@ pub struct VectorStoreConfig {
     pub default_namespace: Option<String>,
     pub timeout_seconds: u64,
     pub max_retries: u32,
+    /// Tenant owning the configuration. Stores created for a tenant only
+    /// see its namespaces; Pinecone requires one.
+    pub tenant_id: Option<TenantId>,
 }
 
 /// Supported vector store providers
@@ -101,34 +427,93 @@ impl VectorProvider {
             )),
         }
     }
+
+    /// Connection parameter names used by earlier versions of a provider,
+    /// as `(old, current)` pairs
+    pub fn legacy_param_aliases(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            VectorProvider::Qdrant => &[("base_url", "url"), ("collection_name", "collection")],
+            VectorProvider::Weaviate => &[("base_url", "url")],
+            _ => &[],
+        }
+    }
+
+    /// Rename legacy connection parameters to their current names, so
+    /// configurations saved before a rename keep working. A parameter that
+    /// is already set under its current name wins.
+    pub fn normalize_connection_params(&self, params: &mut HashMap<String, String>) {
+        for (old, current) in self.legacy_param_aliases() {
+            if params.contains_key(*current) {
+                continue;
+            }
+            if let Some(value) = params.remove(*old) {
+                params.insert(current.to_string(), value);
+            }
+        }
+    }
 }
 
 /// Vector store factory for creating provider instances
 pub struct VectorStoreFactory;
 
 impl VectorStoreFactory {
-    /// Create a vector store instance based on configuration
-    pub async fn create_store(config: VectorStoreConfig) -> Result<Box<dyn VectorStore>, PlatformError> {
-        match config.provider {
+    /// Create a vector store instance based on configuration. Batch writes
+    /// are chunked to the provider's `max_batch_size`, and stores of a
+    /// tenant's configuration are wrapped in `TenantIsolatedVectorStore`.
+    pub async fn create_store(mut config: VectorStoreConfig) -> Result<Box<dyn VectorStore>, PlatformError> {
+        let provider = config.provider.clone();
+        provider.normalize_connection_params(&mut config.connection_params);
+        let parallelism = Self::parse_upsert_parallelism(
+            config.connection_params.get(UPSERT_PARALLELISM_PARAM).map(String::as_str),
+        )?;
+        let legacy_fallback = config
+            .connection_params
+            .get(LEGACY_NAMESPACE_FALLBACK_PARAM)
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"));
+        // Pinecone prefixes namespaces with the tenant itself, in a format
+        // its existing indexes already use
+        let isolate_tenant = match config.provider {
+            VectorProvider::Pinecone => None,
+            _ => config.tenant_id,
+        };
+        
+        let store: Box<dyn VectorStore> = match config.provider {
             VectorProvider::Pinecone => {
-                let provider = providers::pinecone::PineconeStore::new(config).await?;
-                Ok(Box::new(provider))
+                Box::new(providers::pinecone::PineconeStore::new(config).await?)
             },
             VectorProvider::ChromaDB => {
-                let provider = providers::chromadb::ChromaDBStore::new(config).await?;
-                Ok(Box::new(provider))
+                Box::new(providers::chromadb::ChromaDBStore::new(config).await?)
             },
             VectorProvider::Weaviate => {
-                let provider = providers::weaviate::WeaviateStore::new(config).await?;
-                Ok(Box::new(provider))
+                Box::new(providers::weaviate::WeaviateStore::new(config).await?)
             },
             VectorProvider::Qdrant => {
-                let provider = providers::qdrant::QdrantStore::new(config).await?;
-                Ok(Box::new(provider))
+                Box::new(providers::qdrant::QdrantStore::new(config).await?)
             },
             VectorProvider::Milvus => {
-                let provider = providers::milvus::MilvusStore::new(config).await?;
-                Ok(Box::new(provider))
+                Box::new(providers::milvus::MilvusStore::new(config).await?)
+            },
+        };
+        
+        let store: Box<dyn VectorStore> =
+            Box::new(BatchChunkingStore::new(store).with_parallelism(parallelism));
+        Ok(match isolate_tenant {
+            Some(tenant_id) => Box::new(
+                TenantIsolatedVectorStore::new(store, tenant_id).with_legacy_fallback(legacy_fallback),
+            ),
+            None => store,
+        })
+    }
+    
+    fn parse_upsert_parallelism(value: Option<&str>) -> Result<usize, PlatformError> {
+        match value {
+            None => Ok(1),
+            Some(value) => match value.trim().parse::<usize>() {
+                Ok(parallelism) if parallelism > 0 => Ok(parallelism),
+                _ => Err(PlatformError::ValidationError(format!(
+                    "Invalid {}: {}",
+                    UPSERT_PARALLELISM_PARAM, value
+                ))),
             },
         }
     }
@@ -201,10 +586,298 @@ impl VectorStoreRegistry {
         
         results
     }
+    
+    /// Write the same record to every store in `target_stores` concurrently,
+    /// e.g. to both providers during a migration cutover. Each store reports
+    /// its own outcome; unknown store names report `NotFound`.
+    pub async fn broadcast_upsert(
+        &self,
+        record: VectorRecord,
+        target_stores: Vec<String>,
+    ) -> HashMap<String, Result<(), PlatformError>> {
+        let mut targets = target_stores;
+        targets.sort();
+        targets.dedup();
+        
+        let writes = targets.into_iter().map(|name| {
+            let record = record.clone();
+            async move {
+                let result = match self.get_store(&name) {
+                    Ok(store) => store.upsert(record).await,
+                    Err(e) => Err(e),
+                };
+                (name, result)
+            }
+        });
+        
+        join_all(writes).await.into_iter().collect()
+    }
+    
+    /// Query every registered store concurrently and union the results,
+    /// keeping the best score for ids found in more than one store. Stores
+    /// that fail are skipped unless all of them fail.
+    pub async fn broadcast_query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        if self.stores.is_empty() {
+            return Err(PlatformError::ValidationError("No vector stores registered".to_string()));
+        }
+        
+        let searches = self.stores.iter().map(|(name, store)| {
+            let query = query.clone();
+            async move { (name, store.query(query).await) }
+        });
+        
+        let mut merged: HashMap<String, SearchResult> = HashMap::new();
+        let mut last_error = None;
+        let mut succeeded = false;
+        for (name, result) in join_all(searches).await {
+            match result {
+                Ok(results) => {
+                    succeeded = true;
+                    for result in results {
+                        match merged.get(&result.id) {
+                            Some(existing) if existing.score >= result.score => {},
+                            _ => {
+                                merged.insert(result.id.clone(), result);
+                            },
+                        }
+                    }
+                },
+                Err(e) => {
+                    tracing::warn!("Broadcast query to vector store '{}' failed: {}", name, e);
+                    last_error = Some(e);
+                },
+            }
+        }
+        
+        if !succeeded {
+            return Err(last_error.unwrap_or_else(|| {
+                PlatformError::VectorStoreError("Broadcast query failed".to_string())
+            }));
+        }
+        
+        let mut results: Vec<SearchResult> = merged.into_values().collect();
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(query.top_k);
+        Ok(results)
+    }
 }
 
 impl Default for VectorStoreRegistry {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::NamespaceStats;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_chunk_sizes() {
+        let chunker = BatchChunker::new(3);
+        let chunks = chunker.chunk((0..8).collect::<Vec<_>>());
+        assert_eq!(chunks, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]]);
+        assert!(chunker.chunk(Vec::<i32>::new()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_keeps_order_and_stops_on_error() {
+        let seen = Mutex::new(Vec::new());
+        let chunker = BatchChunker::new(2).with_parallelism(2);
+
+        let result = chunker
+            .run((0..7).collect::<Vec<_>>(), |chunk| {
+                let seen = &seen;
+                async move {
+                    if chunk.contains(&4) {
+                        return Err(PlatformError::VectorStoreError("batch rejected".to_string()));
+                    }
+                    seen.lock().unwrap().push(chunk);
+                    Ok(())
+                }
+            })
+            .await;
+
+        assert!(result.is_err());
+        let seen = seen.into_inner().unwrap();
+        assert_eq!(&seen[..2], &[vec![0, 1], vec![2, 3]]);
+        assert!(!seen.contains(&vec![4, 5]));
+    }
+
+    /// Store remembering the namespaces it was called with
+    #[derive(Default)]
+    struct RecordingStore {
+        namespaces: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl VectorStore for Arc<RecordingStore> {
+        async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
+            self.namespaces.lock().unwrap().push(record.namespace);
+            Ok(())
+        }
+
+        async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
+            for record in records {
+                self.upsert(record).await?;
+            }
+            Ok(())
+        }
+
+        async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+            // A record re-written since isolation, plus one only in the
+            // namespace the query names
+            let results = match query.namespace.as_deref() {
+                Some(ns) if ns.contains('/') => vec![SearchResult::new("a".to_string(), 0.5)],
+                Some("docs") => vec![
+                    SearchResult::new("a".to_string(), 0.4),
+                    SearchResult::new("legacy".to_string(), 0.9),
+                ],
+                _ => Vec::new(),
+            };
+            self.namespaces.lock().unwrap().push(query.namespace);
+            Ok(results)
+        }
+
+        async fn delete(&self, _ids: Vec<String>, namespace: Option<String>) -> Result<(), PlatformError> {
+            self.namespaces.lock().unwrap().push(namespace);
+            Ok(())
+        }
+
+        async fn execute_batch(&self, operation: BatchOperation) -> Result<(), PlatformError> {
+            self.upsert_batch(operation.upsert).await
+        }
+
+        async fn create_index(&self, _config: IndexConfig) -> Result<(), PlatformError> {
+            Ok(())
+        }
+
+        async fn delete_index(&self, _index_name: String) -> Result<(), PlatformError> {
+            Ok(())
+        }
+
+        async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_stats(&self, namespace: Option<String>) -> Result<VectorStats, PlatformError> {
+            let namespace = namespace.unwrap_or_default();
+            let tenant = namespace.split('/').next().unwrap_or_default().to_string();
+            Ok(VectorStats {
+                total_vectors: 3,
+                dimension: 2,
+                index_fullness: 0.0,
+                namespace_stats: HashMap::from([
+                    (namespace, NamespaceStats { vector_count: 2 }),
+                    (format!("{}/docs", tenant), NamespaceStats { vector_count: 1 }),
+                    ("other-tenant/docs".to_string(), NamespaceStats { vector_count: 5 }),
+                ]),
+                index_state: None,
+            })
+        }
+
+        async fn test_connection(&self) -> Result<(), PlatformError> {
+            Ok(())
+        }
+
+        fn provider_info(&self) -> VectorProviderInfo {
+            VectorProviderInfo {
+                name: "recording".to_string(),
+                version: "1".to_string(),
+                supports_namespaces: true,
+                supports_metadata_filtering: false,
+                supports_hybrid_search: false,
+                supports_native_ttl: false,
+                max_vector_dimension: 8,
+                max_batch_size: 10,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolated_store_prefixes_namespaces() {
+        let tenant_id = TenantId::new();
+        let inner = Arc::new(RecordingStore::default());
+        let store = TenantIsolatedVectorStore::new(Box::new(inner.clone()), tenant_id);
+
+        let record = VectorRecord::new("a".to_string(), vec![0.1, 0.2], tenant_id)
+            .unwrap()
+            .with_namespace("docs".to_string());
+        store.upsert(record).await.unwrap();
+        store
+            .query(SearchQuery::new(vec![0.1, 0.2], 5).unwrap())
+            .await
+            .unwrap();
+        store.delete(vec!["a".to_string()], Some("docs".to_string())).await.unwrap();
+
+        let namespaces = inner.namespaces.lock().unwrap().clone();
+        assert_eq!(
+            namespaces,
+            vec![
+                Some(format!("{}/docs", tenant_id)),
+                Some(tenant_id.to_string()),
+                Some(format!("{}/docs", tenant_id)),
+            ]
+        );
+
+        let stats = store.get_stats(None).await.unwrap();
+        let mut local: Vec<&str> = stats.namespace_stats.keys().map(String::as_str).collect();
+        local.sort();
+        assert_eq!(local, vec!["", "docs"]);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolated_store_rejects_other_tenants_records() {
+        let inner = Arc::new(RecordingStore::default());
+        let store = TenantIsolatedVectorStore::new(Box::new(inner.clone()), TenantId::new());
+
+        let record = VectorRecord::new("a".to_string(), vec![0.1], TenantId::new()).unwrap();
+        let result = store.upsert_batch(vec![record]).await;
+
+        assert!(matches!(result, Err(PlatformError::AuthorizationFailed(_))));
+        assert!(inner.namespaces.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolated_store_legacy_fallback() {
+        let tenant_id = TenantId::new();
+        let inner = Arc::new(RecordingStore::default());
+        let query = SearchQuery::new(vec![0.1, 0.2], 5).unwrap().with_namespace("docs".to_string());
+
+        let isolated = TenantIsolatedVectorStore::new(Box::new(inner.clone()), tenant_id);
+        let ids: Vec<String> = isolated.query(query.clone()).await.unwrap().into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["a"]);
+
+        let store = TenantIsolatedVectorStore::new(Box::new(inner.clone()), tenant_id)
+            .with_legacy_fallback(true);
+        let results = store.query(query).await.unwrap();
+        let found: Vec<(&str, f32)> = results.iter().map(|r| (r.id.as_str(), r.score)).collect();
+        assert_eq!(found, vec![("legacy", 0.9), ("a", 0.5)]);
+
+        inner.namespaces.lock().unwrap().clear();
+        store.delete(vec!["legacy".to_string()], Some("docs".to_string())).await.unwrap();
+        assert_eq!(
+            inner.namespaces.lock().unwrap().clone(),
+            vec![Some("docs".to_string()), Some(format!("{}/docs", tenant_id))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolated_store_refuses_index_operations() {
+        let store = TenantIsolatedVectorStore::new(Box::new(Arc::new(RecordingStore::default())), TenantId::new());
+
+        assert!(matches!(store.list_indexes().await, Err(PlatformError::AuthorizationFailed(_))));
+        assert!(matches!(
+            store.delete_index("shared".to_string()).await,
+            Err(PlatformError::AuthorizationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_upsert_parallelism() {
+        assert_eq!(VectorStoreFactory::parse_upsert_parallelism(None).unwrap(), 1);
+        assert_eq!(VectorStoreFactory::parse_upsert_parallelism(Some("4")).unwrap(), 4);
+        assert!(VectorStoreFactory::parse_upsert_parallelism(Some("0")).is_err());
+    }
+}
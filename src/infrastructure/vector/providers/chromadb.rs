@@ -1,95 +1,131 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use std::collections::HashMap;
 
 use crate::domain::value_objects::{
     VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation,
-    DistanceMetric, NamespaceStats
+    DistanceMetric, SearchFilter, FilterOperator, ComparisonOperator
 };
 use crate::error::PlatformError;
 use crate::infrastructure::vector::{VectorStore, VectorStoreConfig, VectorProviderInfo};
 use super::{ProviderUtils, VectorHttpClient};
 
-/// ChromaDB vector store implementation
+/// Records sent per upsert request unless `max_batch_size` is configured.
+/// Larger requests run into Chroma's payload limit.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 1000;
+
+/// Collection metadata key Chroma reads the distance function from
+const SPACE_METADATA_KEY: &str = "hnsw:space";
+
+/// Collection metadata key holding the vector dimension
+const DIMENSION_METADATA_KEY: &str = "dimension";
+
+/// ChromaDB vector store implementation, using the v1 REST API.
+/// Record operations address the collection by its ID, which is resolved
+/// from the configured collection name on construction.
 pub struct ChromaDBStore {
     client: VectorHttpClient,
     base_url: String,
     collection_name: String,
+    collection_id: String,
+    metric: DistanceMetric,
     api_key: Option<String>,
+    max_batch_size: usize,
 }
 
 impl ChromaDBStore {
     pub async fn new(config: VectorStoreConfig) -> Result<Self, PlatformError> {
         // Validate required parameters
         ProviderUtils::validate_required_params(&config, &["base_url", "collection_name"])?;
-        
+
         let base_url = ProviderUtils::get_connection_param(&config, "base_url")?;
         let collection_name = ProviderUtils::get_connection_param(&config, "collection_name")?;
         let api_key = ProviderUtils::get_optional_connection_param(&config, "api_key");
-        
+        let max_batch_size = Self::parse_max_batch_size(
+            ProviderUtils::get_optional_connection_param(&config, "max_batch_size"),
+        )?;
+
         // Create HTTP client with ChromaDB-specific headers
         let mut headers = HashMap::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
-        
+
         if let Some(ref key) = api_key {
             headers.insert("Authorization".to_string(), format!("Bearer {}", key));
         }
-        
+
         let client = ProviderUtils::create_http_client(&config, headers)?;
-        
-        let store = Self {
+
+        let mut store = Self {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
             collection_name,
+            collection_id: String::new(),
+            metric: DistanceMetric::Euclidean,
             api_key,
+            max_batch_size,
         };
-        
+
         // Test connection and ensure collection exists
         store.test_connection().await?;
-        store.ensure_collection_exists().await?;
-        
+        let collection = store.get_or_create_collection().await?;
+        store.metric = Self::metric_from_metadata(collection.metadata.as_ref());
+        store.collection_id = collection.id;
+
         Ok(store)
     }
-    
+
+    fn parse_max_batch_size(value: Option<String>) -> Result<usize, PlatformError> {
+        match value {
+            None => Ok(DEFAULT_MAX_BATCH_SIZE),
+            Some(value) => match value.trim().parse::<usize>() {
+                Ok(size) if size > 0 => Ok(size),
+                _ => Err(PlatformError::ValidationError(format!(
+                    "Invalid ChromaDB max_batch_size: {}",
+                    value
+                ))),
+            },
+        }
+    }
+
     fn build_headers(&self) -> HashMap<String, String> {
         let mut headers = HashMap::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
-        
+
         if let Some(ref api_key) = self.api_key {
             headers.insert("Authorization".to_string(), format!("Bearer {}", api_key));
         }
-        
+
         headers
     }
-    
-    async fn ensure_collection_exists(&self) -> Result<(), PlatformError> {
-        // Check if collection exists
+
+    /// URL of a record operation (`add`, `upsert`, `query`, ...) on the collection
+    fn collection_url(&self, operation: &str) -> String {
+        format!("{}/api/v1/collections/{}/{}", self.base_url, self.collection_id, operation)
+    }
+
+    async fn get_or_create_collection(&self) -> Result<ChromaCollection, PlatformError> {
         let url = format!("{}/api/v1/collections/{}", self.base_url, self.collection_name);
-        
-        match self.client.get::<ChromaCollection>(&url, Some(self.build_headers())).await {
-            Ok(_) => Ok(()), // Collection exists
-            Err(_) => {
-                // Collection doesn't exist, create it
-                self.create_collection().await
-            }
+
+        if let Some(collection) = self.client
+            .get_optional::<ChromaCollection>(&url, Some(self.build_headers()))
+            .await?
+        {
+            return Ok(collection);
         }
-    }
-    
-    async fn create_collection(&self) -> Result<(), PlatformError> {
+
         let request = ChromaCreateCollectionRequest {
             name: self.collection_name.clone(),
-            metadata: Some(HashMap::new()),
+            metadata: None,
             get_or_create: Some(true),
         };
-        
+
         let url = format!("{}/api/v1/collections", self.base_url);
-        let _response: ChromaCollection = self.client
+        self.client
             .post_json(&url, &request, Some(self.build_headers()))
-            .await?;
-        
-        Ok(())
+            .await
     }
-    
+
     fn convert_distance_metric(metric: &DistanceMetric) -> String {
         match metric {
             DistanceMetric::Cosine => "cosine".to_string(),
@@ -97,265 +133,294 @@ impl ChromaDBStore {
             DistanceMetric::DotProduct => "ip".to_string(),
         }
     }
-    
-    fn convert_search_results(response: ChromaQueryResponse) -> Vec<SearchResult> {
-        let mut results = Vec::new();
-        
-        if let (Some(ids), Some(distances)) = (response.ids, response.distances) {
-            for (i, id) in ids.into_iter().enumerate() {
-                if let Some(distance) = distances.get(i) {
-                    let score = 1.0 - distance; // Convert distance to similarity score
-                    let mut result = SearchResult::new(id, score);
-                    
-                    // Add embeddings if available
-                    if let Some(ref embeddings) = response.embeddings {
-                        if let Some(embedding) = embeddings.get(i) {
-                            result = result.with_vector(embedding.clone());
-                        }
-                    }
-                    
-                    // Add metadata if available
-                    if let Some(ref metadatas) = response.metadatas {
-                        if let Some(metadata) = metadatas.get(i) {
-                            if let Some(metadata) = metadata {
-                                result = result.with_metadata(metadata.clone());
-                            }
-                        }
+
+    /// Chroma defaults to squared L2 when the collection does not set a space
+    fn metric_from_metadata(metadata: Option<&HashMap<String, Value>>) -> DistanceMetric {
+        match metadata
+            .and_then(|m| m.get(SPACE_METADATA_KEY))
+            .and_then(Value::as_str)
+        {
+            Some("cosine") => DistanceMetric::Cosine,
+            Some("ip") => DistanceMetric::DotProduct,
+            _ => DistanceMetric::Euclidean,
+        }
+    }
+
+    /// Turn a Chroma distance into a score where higher is more similar
+    fn distance_to_score(metric: &DistanceMetric, distance: f32) -> f32 {
+        match metric {
+            // Cosine distance is 1 - cosine similarity; ip distance is 1 - dot product
+            DistanceMetric::Cosine | DistanceMetric::DotProduct => 1.0 - distance,
+            DistanceMetric::Euclidean => 1.0 / (1.0 + distance),
+        }
+    }
+
+    /// Results come back as one list per query embedding; only one is sent
+    fn convert_search_results(
+        response: ChromaQueryResponse,
+        metric: &DistanceMetric,
+    ) -> Vec<SearchResult> {
+        let ids = response.ids.into_iter().next().unwrap_or_default();
+        let distances = response.distances.and_then(|d| d.into_iter().next()).unwrap_or_default();
+        let mut embeddings = response.embeddings.and_then(|e| e.into_iter().next());
+        let mut metadatas = response.metadatas.and_then(|m| m.into_iter().next());
+
+        ids.into_iter()
+            .zip(distances)
+            .enumerate()
+            .map(|(i, (id, distance))| {
+                let mut result = SearchResult::new(id, Self::distance_to_score(metric, distance));
+
+                if let Some(embedding) = embeddings.as_mut().and_then(|e| e.get_mut(i)) {
+                    result = result.with_vector(std::mem::take(embedding));
+                }
+
+                if let Some(metadata) = metadatas.as_mut().and_then(|m| m.get_mut(i)).and_then(Option::take) {
+                    result = result.with_metadata(metadata);
+                }
+
+                result
+            })
+            .collect()
+    }
+
+    /// Translate a filter into Chroma's where clause. Several conditions are
+    /// combined with `$and` or `$or`; a single condition stands on its own.
+    fn convert_filter(filter: &SearchFilter) -> Result<Option<Value>, PlatformError> {
+        let mut clauses = filter
+            .conditions
+            .iter()
+            .map(|condition| {
+                let operator = match condition.operator {
+                    ComparisonOperator::Equal => "$eq",
+                    ComparisonOperator::NotEqual => "$ne",
+                    ComparisonOperator::GreaterThan => "$gt",
+                    ComparisonOperator::GreaterThanOrEqual => "$gte",
+                    ComparisonOperator::LessThan => "$lt",
+                    ComparisonOperator::LessThanOrEqual => "$lte",
+                    ComparisonOperator::In => "$in",
+                    ComparisonOperator::NotIn => "$nin",
+                    ComparisonOperator::Contains => {
+                        return Err(PlatformError::VectorStoreError(format!(
+                            "ChromaDB does not support contains filters on metadata field '{}'",
+                            condition.field
+                        )));
                     }
-                    
-                    results.push(result);
+                };
+
+                if matches!(operator, "$in" | "$nin") && !condition.value.is_array() {
+                    return Err(PlatformError::VectorStoreError(format!(
+                        "Filter on '{}' needs a list of values for {}",
+                        condition.field, operator
+                    )));
                 }
+
+                Ok(json!({ condition.field.clone(): { operator: condition.value.clone() } }))
+            })
+            .collect::<Result<Vec<Value>, PlatformError>>()?;
+
+        let where_clause = match clauses.len() {
+            0 => None,
+            1 => clauses.pop(),
+            _ => {
+                let combinator = match filter.operator {
+                    FilterOperator::And => "$and",
+                    FilterOperator::Or => "$or",
+                };
+                Some(json!({ combinator: clauses }))
             }
+        };
+
+        Ok(where_clause)
+    }
+
+    fn to_upsert_request(records: &[VectorRecord]) -> ChromaAddRequest {
+        ChromaAddRequest {
+            ids: records.iter().map(|r| r.id.clone()).collect(),
+            embeddings: records.iter().map(|r| r.vector.clone()).collect(),
+            metadatas: records.iter().map(|r| Some(r.metadata.clone())).collect(),
+            documents: None,
         }
-        
-        results
     }
 }
 
 #[async_trait]
 impl VectorStore for ChromaDBStore {
     async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
-        let request = ChromaAddRequest {
-            ids: vec![record.id],
-            embeddings: vec![record.vector],
-            metadatas: vec![Some(record.metadata)],
-            documents: None,
-        };
-        
-        let url = format!("{}/api/v1/collections/{}/add", self.base_url, self.collection_name);
-        let _response: ChromaAddResponse = self.client
-            .post_json(&url, &request, Some(self.build_headers()))
-            .await?;
-        
-        Ok(())
+        self.upsert_batch(vec![record]).await
     }
-    
+
     async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
-        if records.is_empty() {
-            return Ok(());
+        let url = self.collection_url("upsert");
+
+        for chunk in records.chunks(self.max_batch_size) {
+            let request = Self::to_upsert_request(chunk);
+            let _response: Value = self.client
+                .post_json(&url, &request, Some(self.build_headers()))
+                .await?;
         }
-        
-        let ids: Vec<String> = records.iter().map(|r| r.id.clone()).collect();
-        let embeddings: Vec<Vec<f32>> = records.iter().map(|r| r.vector.clone()).collect();
-        let metadatas: Vec<Option<HashMap<String, serde_json::Value>>> = records
-            .iter()
-            .map(|r| Some(r.metadata.clone()))
-            .collect();
-        
-        let request = ChromaAddRequest {
-            ids,
-            embeddings,
-            metadatas,
-            documents: None,
-        };
-        
-        let url = format!("{}/api/v1/collections/{}/upsert", self.base_url, self.collection_name);
-        let _response: ChromaAddResponse = self.client
-            .post_json(&url, &request, Some(self.build_headers()))
-            .await?;
-        
+
         Ok(())
     }
-    
+
     async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        let query = ProviderUtils::dense_only(query, "ChromaDB");
+
+        let mut include = vec!["distances".to_string()];
+        if query.include_metadata {
+            include.push("metadatas".to_string());
+        }
+        if query.include_values {
+            include.push("embeddings".to_string());
+        }
+
+        let where_clause = match query.filter {
+            Some(ref filter) => Self::convert_filter(filter)?,
+            None => None,
+        };
+
         let request = ChromaQueryRequest {
             query_embeddings: vec![query.vector],
             n_results: Some(query.top_k as u32),
-            where_clause: query.filter.map(|f| self.convert_filter(f)),
+            where_clause,
             where_document: None,
-            include: Some(vec![
-                "embeddings".to_string(),
-                "metadatas".to_string(),
-                "distances".to_string(),
-            ]),
+            include: Some(include),
         };
-        
-        let url = format!("{}/api/v1/collections/{}/query", self.base_url, self.collection_name);
+
         let response: ChromaQueryResponse = self.client
-            .post_json(&url, &request, Some(self.build_headers()))
+            .post_json(&self.collection_url("query"), &request, Some(self.build_headers()))
             .await?;
-        
-        Ok(Self::convert_search_results(response))
+
+        Ok(Self::convert_search_results(response, &self.metric))
     }
-    
+
     async fn delete(&self, ids: Vec<String>, _namespace: Option<String>) -> Result<(), PlatformError> {
-        let request = ChromaDeleteRequest {
-            ids: Some(ids),
-            where_clause: None,
-        };
-        
-        let url = format!("{}/api/v1/collections/{}/delete", self.base_url, self.collection_name);
-        let _response: ChromaDeleteResponse = self.client
-            .post_json(&url, &request, Some(self.build_headers()))
-            .await?;
-        
+        let url = self.collection_url("delete");
+
+        for chunk in ids.chunks(self.max_batch_size) {
+            let request = ChromaDeleteRequest {
+                ids: Some(chunk.to_vec()),
+                where_clause: None,
+            };
+            let _response: Value = self.client
+                .post_json(&url, &request, Some(self.build_headers()))
+                .await?;
+        }
+
         Ok(())
     }
-    
+
     async fn execute_batch(&self, operation: BatchOperation) -> Result<(), PlatformError> {
         // Execute upserts first
         if !operation.upsert.is_empty() {
             self.upsert_batch(operation.upsert).await?;
         }
-        
+
         // Then execute deletes
         if !operation.delete.is_empty() {
             self.delete(operation.delete, None).await?;
         }
-        
+
         Ok(())
     }
-    
+
     async fn create_index(&self, config: IndexConfig) -> Result<(), PlatformError> {
         // ChromaDB doesn't have explicit index creation - collections serve as indexes
+        let mut metadata = HashMap::new();
+        metadata.insert(DIMENSION_METADATA_KEY.to_string(), json!(config.dimension));
+        metadata.insert(
+            SPACE_METADATA_KEY.to_string(),
+            json!(Self::convert_distance_metric(&config.metric)),
+        );
+
         let request = ChromaCreateCollectionRequest {
             name: config.name,
-            metadata: Some({
-                let mut metadata = HashMap::new();
-                metadata.insert("dimension".to_string(), serde_json::Value::Number(
-                    serde_json::Number::from(config.dimension)
-                ));
-                metadata.insert("metric".to_string(), serde_json::Value::String(
-                    Self::convert_distance_metric(&config.metric)
-                ));
-                metadata
-            }),
+            metadata: Some(metadata),
             get_or_create: Some(false),
         };
-        
+
         let url = format!("{}/api/v1/collections", self.base_url);
         let _response: ChromaCollection = self.client
             .post_json(&url, &request, Some(self.build_headers()))
             .await?;
-        
+
         Ok(())
     }
-    
+
     async fn delete_index(&self, index_name: String) -> Result<(), PlatformError> {
         let url = format!("{}/api/v1/collections/{}", self.base_url, index_name);
         self.client.delete(&url, Some(self.build_headers())).await?;
         Ok(())
     }
-    
+
     async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
         let url = format!("{}/api/v1/collections", self.base_url);
         let collections: Vec<ChromaCollection> = self.client
             .get(&url, Some(self.build_headers()))
             .await?;
-        
+
         Ok(collections.into_iter().map(|c| c.name).collect())
     }
-    
+
     async fn get_stats(&self, _namespace: Option<String>) -> Result<VectorStats, PlatformError> {
         let url = format!("{}/api/v1/collections/{}", self.base_url, self.collection_name);
         let collection: ChromaCollection = self.client
             .get(&url, Some(self.build_headers()))
             .await?;
-        
-        // ChromaDB doesn't provide detailed stats like Pinecone
-        // We'll return basic information
+
+        let total_vectors: u64 = self.client
+            .get(&self.collection_url("count"), Some(self.build_headers()))
+            .await?;
+
+        // Only collections created through `create_index` record their dimension
+        let dimension = collection
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(DIMENSION_METADATA_KEY))
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as usize;
+
         Ok(VectorStats {
-            total_vectors: 0, // ChromaDB doesn't expose this easily
-            dimension: 0,     // Would need to infer from data
+            total_vectors,
+            dimension,
             index_fullness: 0.0,
             namespace_stats: HashMap::new(),
+            index_state: None,
         })
     }
-    
+
     async fn test_connection(&self) -> Result<(), PlatformError> {
         let url = format!("{}/api/v1/heartbeat", self.base_url);
-        
-        match self.client.get::<serde_json::Value>(&url, Some(self.build_headers())).await {
+
+        match self.client.get::<Value>(&url, Some(self.build_headers())).await {
             Ok(_) => Ok(()),
             Err(e) => Err(PlatformError::VectorStoreError(
                 format!("ChromaDB connection test failed: {}", e)
             )),
         }
     }
-    
+
     fn provider_info(&self) -> VectorProviderInfo {
         VectorProviderInfo {
             name: "ChromaDB".to_string(),
             version: "0.4".to_string(),
             supports_namespaces: false, // ChromaDB uses collections instead
             supports_metadata_filtering: true,
-            supports_hybrid_search: true,
+            supports_hybrid_search: false,
+            supports_native_ttl: false,
             max_vector_dimension: 2048, // Typical limit, may vary
-            max_batch_size: 5000,
+            max_batch_size: self.max_batch_size,
         }
     }
 }
 
-impl ChromaDBStore {
-    fn convert_filter(&self, filter: crate::domain::value_objects::SearchFilter) -> serde_json::Value {
-        // Convert our generic filter format to ChromaDB's where clause format
-        let mut chroma_filter = serde_json::Map::new();
-        
-        for condition in filter.conditions {
-            let value = match condition.operator {
-                crate::domain::value_objects::ComparisonOperator::Equal => {
-                    serde_json::json!({ "$eq": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::NotEqual => {
-                    serde_json::json!({ "$ne": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::GreaterThan => {
-                    serde_json::json!({ "$gt": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::GreaterThanOrEqual => {
-                    serde_json::json!({ "$gte": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::LessThan => {
-                    serde_json::json!({ "$lt": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::LessThanOrEqual => {
-                    serde_json::json!({ "$lte": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::In => {
-                    serde_json::json!({ "$in": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::NotIn => {
-                    serde_json::json!({ "$nin": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::Contains => {
-                    serde_json::json!({ "$contains": condition.value })
-                },
-            };
-            
-            chroma_filter.insert(condition.field, value);
-        }
-        
-        serde_json::Value::Object(chroma_filter)
-    }
-}
-
 // ChromaDB API request/response structures
 
 #[derive(Debug, Serialize)]
 struct ChromaCreateCollectionRequest {
     name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    metadata: Option<HashMap<String, serde_json::Value>>,
+    metadata: Option<HashMap<String, Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     get_or_create: Option<bool>,
 }
@@ -364,43 +429,38 @@ struct ChromaCreateCollectionRequest {
 struct ChromaCollection {
     name: String,
     id: String,
-    metadata: Option<HashMap<String, serde_json::Value>>,
+    metadata: Option<HashMap<String, Value>>,
 }
 
 #[derive(Debug, Serialize)]
 struct ChromaAddRequest {
     ids: Vec<String>,
     embeddings: Vec<Vec<f32>>,
-    metadatas: Vec<Option<HashMap<String, serde_json::Value>>>,
+    metadatas: Vec<Option<HashMap<String, Value>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     documents: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ChromaAddResponse {
-    // ChromaDB add response is typically empty on success
-}
-
 #[derive(Debug, Serialize)]
 struct ChromaQueryRequest {
     query_embeddings: Vec<Vec<f32>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     n_results: Option<u32>,
     #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
-    where_clause: Option<serde_json::Value>,
+    where_clause: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    where_document: Option<serde_json::Value>,
+    where_document: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     include: Option<Vec<String>>,
 }
 
+/// Every field holds one list per query embedding
 #[derive(Debug, Deserialize)]
 struct ChromaQueryResponse {
-    ids: Option<Vec<String>>,
-    distances: Option<Vec<f32>>,
-    metadatas: Option<Vec<Option<HashMap<String, serde_json::Value>>>>,
-    embeddings: Option<Vec<Vec<f32>>>,
-    documents: Option<Vec<Option<String>>>,
+    ids: Vec<Vec<String>>,
+    distances: Option<Vec<Vec<f32>>>,
+    metadatas: Option<Vec<Vec<Option<HashMap<String, Value>>>>>,
+    embeddings: Option<Vec<Vec<Vec<f32>>>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -408,10 +468,101 @@ struct ChromaDeleteRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     ids: Option<Vec<String>>,
     #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
-    where_clause: Option<serde_json::Value>,
+    where_clause: Option<Value>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ChromaDeleteResponse {
-    // ChromaDB delete response is typically empty on success
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::FilterCondition;
+
+    fn condition(field: &str, operator: ComparisonOperator, value: Value) -> FilterCondition {
+        FilterCondition {
+            field: field.to_string(),
+            operator,
+            value,
+        }
+    }
+
+    #[test]
+    fn test_convert_single_condition_filter() {
+        let filter = SearchFilter {
+            conditions: vec![condition("category", ComparisonOperator::Equal, json!("news"))],
+            operator: FilterOperator::And,
+        };
+
+        assert_eq!(
+            ChromaDBStore::convert_filter(&filter).unwrap(),
+            Some(json!({ "category": { "$eq": "news" } }))
+        );
+    }
+
+    #[test]
+    fn test_convert_combined_filter() {
+        let filter = SearchFilter {
+            conditions: vec![
+                condition("category", ComparisonOperator::NotEqual, json!("spam")),
+                condition("lang", ComparisonOperator::In, json!(["en", "zh"])),
+            ],
+            operator: FilterOperator::Or,
+        };
+
+        assert_eq!(
+            ChromaDBStore::convert_filter(&filter).unwrap(),
+            Some(json!({
+                "$or": [
+                    { "category": { "$ne": "spam" } },
+                    { "lang": { "$in": ["en", "zh"] } }
+                ]
+            }))
+        );
+    }
+
+    #[test]
+    fn test_convert_filter_rejects_unsupported_conditions() {
+        let contains = SearchFilter {
+            conditions: vec![condition("title", ComparisonOperator::Contains, json!("rust"))],
+            operator: FilterOperator::And,
+        };
+        assert!(ChromaDBStore::convert_filter(&contains).is_err());
+
+        let scalar_in = SearchFilter {
+            conditions: vec![condition("lang", ComparisonOperator::In, json!("en"))],
+            operator: FilterOperator::And,
+        };
+        assert!(ChromaDBStore::convert_filter(&scalar_in).is_err());
+    }
+
+    #[test]
+    fn test_convert_search_results_reads_first_query() {
+        let response: ChromaQueryResponse = serde_json::from_value(json!({
+            "ids": [["a", "b"]],
+            "distances": [[0.1, 0.4]],
+            "metadatas": [[{ "category": "news" }, null]],
+            "embeddings": null
+        }))
+        .unwrap();
+
+        let results = ChromaDBStore::convert_search_results(response, &DistanceMetric::Cosine);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "a");
+        assert!((results[0].score - 0.9).abs() < 1e-6);
+        assert_eq!(results[0].metadata.as_ref().unwrap()["category"], json!("news"));
+        assert!(results[1].metadata.is_none());
+        assert!(results[1].vector.is_none());
+    }
+
+    #[test]
+    fn test_metric_from_metadata_defaults_to_l2() {
+        let metadata = HashMap::from([(SPACE_METADATA_KEY.to_string(), json!("cosine"))]);
+        assert_eq!(ChromaDBStore::metric_from_metadata(Some(&metadata)), DistanceMetric::Cosine);
+        assert_eq!(ChromaDBStore::metric_from_metadata(None), DistanceMetric::Euclidean);
+    }
+
+    #[test]
+    fn test_parse_max_batch_size() {
+        assert_eq!(ChromaDBStore::parse_max_batch_size(None).unwrap(), DEFAULT_MAX_BATCH_SIZE);
+        assert_eq!(ChromaDBStore::parse_max_batch_size(Some("250".to_string())).unwrap(), 250);
+        assert!(ChromaDBStore::parse_max_batch_size(Some("0".to_string())).is_err());
+    }
+}
@@ -1,107 +1,877 @@
 use async_trait::async_trait;
+use milvus::client::{Client, ClientBuilder};
+use milvus::collection::{Collection, SearchOption};
+use milvus::data::FieldColumn;
+use milvus::index::{IndexParams, IndexType, MetricType};
+use milvus::schema::{CollectionSchemaBuilder, FieldSchema};
+use milvus::value::{Value as MilvusValue, ValueVec};
+use serde::Deserialize;
+use serde_json::json;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
 
 use crate::domain::value_objects::{
-    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation
+    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation,
+    DistanceMetric, NamespaceStats
 };
 use crate::error::PlatformError;
 use crate::infrastructure::vector::{VectorStore, VectorStoreConfig, VectorProviderInfo};
-use super::ProviderUtils;
+use super::{ProviderUtils, VectorHttpClient};
 
-/// Milvus vector store implementation (placeholder)
-pub struct MilvusStore {
+const ID_FIELD: &str = "id";
+const VECTOR_FIELD: &str = "vector";
+/// Only present in collections created with `sparse_vectors = true`
+const SPARSE_VECTOR_FIELD: &str = "sparse_vector";
+/// Emulates namespaces; records without one are stored with an empty string
+const NAMESPACE_FIELD: &str = "namespace";
+/// Record metadata, stored as a JSON string
+const METADATA_FIELD: &str = "metadata";
+
+const ID_MAX_LENGTH: i32 = 512;
+const NAMESPACE_MAX_LENGTH: i32 = 256;
+const METADATA_MAX_LENGTH: i32 = 65535;
+
+/// Row count after which the exact FLAT index is replaced by HNSW
+const DEFAULT_INDEX_THRESHOLD: u64 = 10_000;
+/// Lower bound of the HNSW search list size; raised to `top_k` when larger
+const HNSW_SEARCH_EF: usize = 64;
+
+const FLAT_INDEX_NAME: &str = "vector_flat";
+const HNSW_INDEX_NAME: &str = "vector_hnsw";
+const SPARSE_INDEX_NAME: &str = "sparse_inverted";
+
+/// Envelope of Milvus RESTful API responses; failures are reported with a
+/// non-zero `code` and HTTP 200.
+#[derive(Debug, Deserialize)]
+struct RestResponse {
+    code: i64,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+}
+
+/// Similarity metric of a collection. The gRPC SDK only knows L2 and IP,
+/// so cosine collections use IP over vectors scaled to unit length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Cosine,
+    InnerProduct,
+    L2,
+}
+
+impl Metric {
+    fn milvus_type(self) -> MetricType {
+        match self {
+            Metric::Cosine | Metric::InnerProduct => MetricType::IP,
+            Metric::L2 => MetricType::L2,
+        }
+    }
+
+    /// Normalize vectors written to or searched in a cosine collection
+    fn prepare(self, mut vector: Vec<f32>) -> Vec<f32> {
+        if self == Metric::Cosine {
+            let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                vector.iter_mut().for_each(|value| *value /= norm);
+            }
+        }
+        vector
+    }
+}
+
+/// Milvus 2.4 RESTful API (v2), used for row counts and the sparse vector
+/// features the gRPC SDK does not expose
+struct MilvusRestClient {
+    client: VectorHttpClient,
     base_url: String,
-    api_key: Option<String>,
+    headers: HashMap<String, String>,
+}
+
+impl MilvusRestClient {
+    async fn post(&self, path: &str, body: serde_json::Value) -> Result<Option<serde_json::Value>, PlatformError> {
+        let url = format!("{}/v2/vectordb/{}", self.base_url, path);
+        let response: RestResponse = self.client.post_json(&url, &body, Some(self.headers.clone())).await?;
+
+        if response.code != 0 {
+            return Err(PlatformError::VectorStoreError(format!(
+                "Milvus {} failed ({}): {}",
+                path, response.code, response.message.unwrap_or_default()
+            )));
+        }
+        Ok(response.data)
+    }
+}
+
+/// Milvus vector store implementation over the gRPC SDK.
+///
+/// Collections start with a FLAT index so they can be loaded and searched
+/// right away; once they grow past `index_threshold` rows the index is
+/// rebuilt as HNSW.
+///
+/// Row counts come from the RESTful API at `rest_url` (defaults to
+/// `base_url`). With the `sparse_vectors` connection param, collections
+/// also get a sparse vector field, written and searched through that API,
+/// and hybrid queries use Milvus 2.4's `hybrid_search`.
+pub struct MilvusStore {
+    client: Client,
     collection_name: String,
+    /// Handle of `collection_name`, fetched on first use. Shared so that
+    /// searches see the store's own writes under session consistency.
+    collection: RwLock<Option<Arc<Collection>>>,
+    /// `metric_type` connection param; overrides `IndexConfig::metric`
+    metric_type: Option<Metric>,
+    index_threshold: u64,
+    hnsw_built: AtomicBool,
+    rest: MilvusRestClient,
+    sparse_vectors: bool,
 }
 
 impl MilvusStore {
     pub async fn new(config: VectorStoreConfig) -> Result<Self, PlatformError> {
         ProviderUtils::validate_required_params(&config, &["base_url", "collection_name"])?;
-        
+
         let base_url = ProviderUtils::get_connection_param(&config, "base_url")?;
         let collection_name = ProviderUtils::get_connection_param(&config, "collection_name")?;
-        let api_key = ProviderUtils::get_optional_connection_param(&config, "api_key");
-        
-        Ok(Self {
-            base_url: base_url.trim_end_matches('/').to_string(),
-            api_key,
+        let username = ProviderUtils::get_optional_connection_param(&config, "username");
+        let password = ProviderUtils::get_optional_connection_param(&config, "password");
+
+        let metric_type = ProviderUtils::get_optional_connection_param(&config, "metric_type")
+            .map(|metric| Self::parse_metric_type(&metric))
+            .transpose()?;
+
+        let index_threshold = match ProviderUtils::get_optional_connection_param(&config, "index_threshold") {
+            Some(threshold) => threshold.trim().parse::<u64>().map_err(|e| {
+                PlatformError::ValidationError(format!("Invalid index_threshold '{}': {}", threshold, e))
+            })?,
+            None => DEFAULT_INDEX_THRESHOLD,
+        };
+
+        let sparse_vectors = match ProviderUtils::get_optional_connection_param(&config, "sparse_vectors") {
+            Some(flag) => flag.trim().parse::<bool>().map_err(|e| {
+                PlatformError::ValidationError(format!("Invalid sparse_vectors '{}': {}", flag, e))
+            })?,
+            None => false,
+        };
+
+        let rest_url = ProviderUtils::get_optional_connection_param(&config, "rest_url")
+            .unwrap_or_else(|| base_url.clone());
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        if let (Some(username), Some(password)) = (username.as_deref(), password.as_deref()) {
+            headers.insert("Authorization".to_string(), format!("Bearer {}:{}", username, password));
+        }
+        let rest = MilvusRestClient {
+            client: ProviderUtils::create_http_client(&config, headers.clone())?,
+            base_url: rest_url.trim_end_matches('/').to_string(),
+            headers,
+        };
+
+        let mut builder = ClientBuilder::new(base_url.trim_end_matches('/').to_string());
+        if let Some(username) = username.as_deref() {
+            builder = builder.username(username);
+        }
+        if let Some(password) = password.as_deref() {
+            builder = builder.password(password);
+        }
+
+        let client = builder.build().await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to create Milvus client: {}", e)
+            ))?;
+
+        let store = Self {
+            client,
             collection_name,
+            collection: RwLock::new(None),
+            metric_type,
+            index_threshold,
+            hnsw_built: AtomicBool::new(false),
+            rest,
+            sparse_vectors,
+        };
+
+        // Test connection
+        store.test_connection().await?;
+
+        Ok(store)
+    }
+
+    fn parse_metric_type(metric: &str) -> Result<Metric, PlatformError> {
+        match metric.trim().to_uppercase().as_str() {
+            "COSINE" => Ok(Metric::Cosine),
+            "IP" => Ok(Metric::InnerProduct),
+            "L2" => Ok(Metric::L2),
+            other => Err(PlatformError::ValidationError(format!(
+                "Unsupported Milvus metric_type '{}', expected COSINE, IP or L2", other
+            ))),
+        }
+    }
+
+    fn convert_distance_metric(metric: &DistanceMetric) -> Metric {
+        match metric {
+            DistanceMetric::Cosine => Metric::Cosine,
+            DistanceMetric::Euclidean => Metric::L2,
+            DistanceMetric::DotProduct => Metric::InnerProduct,
+        }
+    }
+
+    fn metric_for(&self, config: &IndexConfig) -> Metric {
+        self.metric_type.unwrap_or_else(|| Self::convert_distance_metric(&config.metric))
+    }
+
+    /// Metric used for searches when no index config is at hand
+    fn search_metric(&self) -> Metric {
+        self.metric_type.unwrap_or(Metric::Cosine)
+    }
+
+    async fn collection(&self) -> Result<Arc<Collection>, PlatformError> {
+        if let Some(collection) = self.collection.read().await.as_ref() {
+            return Ok(collection.clone());
+        }
+
+        let collection = Arc::new(self.client.get_collection(&self.collection_name).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to describe Milvus collection '{}': {}", self.collection_name, e)
+            ))?);
+        *self.collection.write().await = Some(collection.clone());
+        Ok(collection)
+    }
+
+    /// Milvus reports L2 as a distance and IP/COSINE as similarities; map all
+    /// of them to "higher is better".
+    fn to_score(metric: MetricType, raw: f32) -> f32 {
+        match metric {
+            MetricType::L2 => 1.0 / (1.0 + raw),
+            _ => raw,
+        }
+    }
+
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    fn namespace_expr(namespace: Option<&str>) -> String {
+        format!("{} == {}", NAMESPACE_FIELD, Self::quote(namespace.unwrap_or_default()))
+    }
+
+    fn ids_expr(ids: &[String], namespace: Option<&str>) -> String {
+        let ids: Vec<String> = ids.iter().map(|id| Self::quote(id)).collect();
+        let mut expr = format!("{} in [{}]", ID_FIELD, ids.join(", "));
+        if let Some(namespace) = namespace {
+            expr = format!("{} and {}", expr, Self::namespace_expr(Some(namespace)));
+        }
+        expr
+    }
+
+    fn flat_index(metric: MetricType) -> IndexParams {
+        IndexParams::new(FLAT_INDEX_NAME.to_string(), IndexType::Flat, metric, HashMap::new())
+    }
+
+    fn hnsw_index(metric: MetricType) -> IndexParams {
+        IndexParams::new(
+            HNSW_INDEX_NAME.to_string(),
+            IndexType::HNSW,
+            metric,
+            HashMap::from([
+                ("M".to_string(), "16".to_string()),
+                ("efConstruction".to_string(), "200".to_string()),
+            ]),
+        )
+    }
+
+    async fn create_collection(&self, name: &str, dimension: usize, metric: Metric) -> Result<(), PlatformError> {
+        if dimension == 0 {
+            return Err(PlatformError::ValidationError(
+                "Milvus collections need a vector dimension greater than 0".to_string()
+            ));
+        }
+
+        if self.sparse_vectors {
+            // Providing index params makes Milvus load the collection as well
+            self.rest.post("collections/create", Self::sparse_collection_body(name, dimension, metric)).await?;
+            return Ok(());
+        }
+
+        let schema = CollectionSchemaBuilder::new(name, "Created by agent-platform")
+            .add_field(FieldSchema::new_primary_varchar(ID_FIELD, "record id", false, ID_MAX_LENGTH))
+            .add_field(FieldSchema::new_float_vector(VECTOR_FIELD, "embedding", dimension as i64))
+            .add_field(FieldSchema::new_varchar(NAMESPACE_FIELD, "namespace", NAMESPACE_MAX_LENGTH))
+            .add_field(FieldSchema::new_varchar(METADATA_FIELD, "metadata as JSON", METADATA_MAX_LENGTH))
+            .build()
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Invalid Milvus schema for '{}': {}", name, e)
+            ))?;
+
+        let collection = self.client.create_collection(schema, None).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to create Milvus collection '{}': {}", name, e)
+            ))?;
+
+        collection.create_index(VECTOR_FIELD, Self::flat_index(metric.milvus_type())).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to create FLAT index on '{}': {}", name, e)
+            ))?;
+
+        collection.load(1).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to load Milvus collection '{}': {}", name, e)
+            ))?;
+
+        Ok(())
+    }
+
+    fn sparse_collection_body(name: &str, dimension: usize, metric: Metric) -> serde_json::Value {
+        json!({
+            "collectionName": name,
+            "schema": {
+                "autoId": false,
+                "enableDynamicField": false,
+                "fields": [
+                    {
+                        "fieldName": ID_FIELD,
+                        "dataType": "VarChar",
+                        "isPrimary": true,
+                        "elementTypeParams": { "max_length": ID_MAX_LENGTH },
+                    },
+                    {
+                        "fieldName": VECTOR_FIELD,
+                        "dataType": "FloatVector",
+                        "elementTypeParams": { "dim": dimension },
+                    },
+                    { "fieldName": SPARSE_VECTOR_FIELD, "dataType": "SparseFloatVector" },
+                    {
+                        "fieldName": NAMESPACE_FIELD,
+                        "dataType": "VarChar",
+                        "elementTypeParams": { "max_length": NAMESPACE_MAX_LENGTH },
+                    },
+                    {
+                        "fieldName": METADATA_FIELD,
+                        "dataType": "VarChar",
+                        "elementTypeParams": { "max_length": METADATA_MAX_LENGTH },
+                    },
+                ],
+            },
+            "indexParams": [
+                {
+                    "fieldName": VECTOR_FIELD,
+                    "indexName": FLAT_INDEX_NAME,
+                    "metricType": metric.milvus_type().to_string(),
+                    "indexType": "FLAT",
+                },
+                {
+                    "fieldName": SPARSE_VECTOR_FIELD,
+                    "indexName": SPARSE_INDEX_NAME,
+                    "metricType": "IP",
+                    "indexType": "SPARSE_INVERTED_INDEX",
+                },
+            ],
+        })
+    }
+
+    /// Both sub-searches are scoped to the namespace; Milvus fuses them with
+    /// the weighted reranker.
+    fn hybrid_search_body(collection_name: &str, query: &SearchQuery, metric: Metric) -> serde_json::Value {
+        let filter = Self::namespace_expr(query.namespace.as_deref());
+        let dense_weight = query.dense_weight();
+
+        let mut output_fields = vec![ID_FIELD, METADATA_FIELD];
+        if query.include_values {
+            output_fields.push(VECTOR_FIELD);
+        }
+
+        json!({
+            "collectionName": collection_name,
+            "search": [
+                {
+                    "data": [metric.prepare(query.vector.clone())],
+                    "annsField": VECTOR_FIELD,
+                    "limit": query.top_k,
+                    "filter": filter,
+                    "metricType": metric.milvus_type().to_string(),
+                },
+                {
+                    "data": [query.sparse_vector.clone().unwrap_or_default()],
+                    "annsField": SPARSE_VECTOR_FIELD,
+                    "limit": query.top_k,
+                    "filter": filter,
+                    "metricType": "IP",
+                },
+            ],
+            "rerank": {
+                "strategy": "weighted",
+                "params": { "weights": [dense_weight, 1.0 - dense_weight] },
+            },
+            "limit": query.top_k,
+            "outputFields": output_fields,
         })
     }
+
+    /// Convert a `hybrid_search` hit; `distance` is already the fused score
+    fn convert_hybrid_hit(hit: &serde_json::Value, query: &SearchQuery) -> Option<SearchResult> {
+        let id = match hit.get(ID_FIELD)? {
+            serde_json::Value::String(id) => id.clone(),
+            serde_json::Value::Number(id) => id.to_string(),
+            _ => return None,
+        };
+        let score = hit.get("distance")?.as_f64()? as f32;
+
+        let mut result = SearchResult::new(id, score);
+        if query.include_values {
+            if let Some(vector) = hit.get(VECTOR_FIELD)
+                .and_then(|vector| serde_json::from_value::<Vec<f32>>(vector.clone()).ok())
+            {
+                result = result.with_vector(vector);
+            }
+        }
+        if query.include_metadata {
+            if let Some(metadata) = hit.get(METADATA_FIELD)
+                .and_then(|metadata| metadata.as_str())
+                .and_then(|metadata| serde_json::from_str::<HashMap<String, serde_json::Value>>(metadata).ok())
+            {
+                if !metadata.is_empty() {
+                    result = result.with_metadata(metadata);
+                }
+            }
+        }
+        Some(result)
+    }
+
+    async fn hybrid_query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        let body = Self::hybrid_search_body(&self.collection_name, &query, self.search_metric());
+        let data = self.rest.post("entities/hybrid_search", body).await?;
+
+        Ok(data.as_ref()
+            .and_then(|data| data.as_array())
+            .map(|hits| hits.iter().filter_map(|hit| Self::convert_hybrid_hit(hit, &query)).collect())
+            .unwrap_or_default())
+    }
+
+    /// Insert through the RESTful API, which accepts sparse vectors
+    async fn insert_with_sparse(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
+        let metric = self.search_metric();
+        let mut rows = Vec::with_capacity(records.len());
+        for record in records {
+            let metadata = serde_json::to_string(&record.metadata).map_err(|e| {
+                PlatformError::VectorStoreError(format!("Invalid metadata for record '{}': {}", record.id, e))
+            })?;
+            rows.push(json!({
+                ID_FIELD: record.id,
+                VECTOR_FIELD: metric.prepare(record.vector),
+                // The field is required, records without sparse terms get an empty vector
+                SPARSE_VECTOR_FIELD: record.sparse_vector.unwrap_or_default(),
+                NAMESPACE_FIELD: record.namespace.unwrap_or_default(),
+                METADATA_FIELD: metadata,
+            }));
+        }
+
+        self.rest.post("entities/insert", json!({
+            "collectionName": self.collection_name,
+            "data": rows,
+        })).await?;
+
+        Ok(())
+    }
+
+    async fn ensure_collection(&self, dimension: usize) -> Result<(), PlatformError> {
+        let exists = self.client.has_collection(&self.collection_name).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to check Milvus collection: {}", e)
+            ))?;
+
+        if !exists {
+            self.create_collection(&self.collection_name, dimension, self.search_metric()).await?;
+        }
+        Ok(())
+    }
+
+    async fn row_count(&self) -> Result<u64, PlatformError> {
+        let stats = self.rest.post("collections/get_stats", json!({
+            "collectionName": self.collection_name,
+        })).await?;
+
+        Ok(stats.as_ref()
+            .and_then(|stats| stats.get("rowCount"))
+            .and_then(|count| count.as_u64())
+            .unwrap_or(0))
+    }
+
+    async fn namespace_count(&self, namespace: &str) -> Result<u64, PlatformError> {
+        let rows = self.rest.post("entities/query", json!({
+            "collectionName": self.collection_name,
+            "filter": Self::namespace_expr(Some(namespace)),
+            "outputFields": ["count(*)"],
+        })).await?;
+
+        Ok(rows.as_ref()
+            .and_then(|rows| rows.get(0))
+            .and_then(|row| row.get("count(*)"))
+            .and_then(|count| count.as_u64())
+            .unwrap_or(0))
+    }
+
+    /// Swap the FLAT index for HNSW once the collection is large enough
+    async fn maybe_build_hnsw_index(&self) -> Result<(), PlatformError> {
+        if self.hnsw_built.load(Ordering::Relaxed) || self.row_count().await? < self.index_threshold {
+            return Ok(());
+        }
+
+        let collection = self.collection().await?;
+        let indexes = collection.describe_index(VECTOR_FIELD).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to describe Milvus index: {}", e)
+            ))?;
+        if indexes.iter().any(|index| index.params().name() == HNSW_INDEX_NAME) {
+            self.hnsw_built.store(true, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        let map_err = |e: milvus::error::Error| PlatformError::VectorStoreError(
+            format!("Failed to rebuild Milvus index as HNSW: {}", e)
+        );
+
+        // Indexes can only be replaced while the collection is released
+        collection.release().await.map_err(map_err)?;
+        collection.drop_index(VECTOR_FIELD).await.map_err(map_err)?;
+        collection
+            .create_index(VECTOR_FIELD, Self::hnsw_index(self.search_metric().milvus_type()))
+            .await
+            .map_err(map_err)?;
+        collection.load(1).await.map_err(map_err)?;
+
+        self.hnsw_built.store(true, Ordering::Relaxed);
+        tracing::info!("Built HNSW index for Milvus collection '{}'", self.collection_name);
+        Ok(())
+    }
+
+    fn convert_search_result(
+        result: &milvus::collection::SearchResult<'_>,
+        index: usize,
+        metric: MetricType,
+        query: &SearchQuery,
+    ) -> Option<SearchResult> {
+        let id = match result.id.get(index)? {
+            MilvusValue::String(id) => id.to_string(),
+            MilvusValue::Long(id) => id.to_string(),
+            _ => return None,
+        };
+        let score = Self::to_score(metric, *result.score.get(index)?);
+
+        let field = |name: &str| result.field.iter()
+            .find(|column| column.name == name)
+            .and_then(|column| column.get(index));
+
+        let mut search_result = SearchResult::new(id, score);
+        if query.include_values {
+            if let Some(MilvusValue::FloatArray(vector)) = field(VECTOR_FIELD) {
+                search_result = search_result.with_vector(vector.to_vec());
+            }
+        }
+        if query.include_metadata {
+            if let Some(MilvusValue::String(metadata)) = field(METADATA_FIELD) {
+                if let Ok(metadata) = serde_json::from_str::<HashMap<String, serde_json::Value>>(&metadata) {
+                    if !metadata.is_empty() {
+                        search_result = search_result.with_metadata(metadata);
+                    }
+                }
+            }
+        }
+        Some(search_result)
+    }
 }
 
 #[async_trait]
 impl VectorStore for MilvusStore {
-    async fn upsert(&self, _record: VectorRecord) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn upsert_batch(&self, _records: Vec<VectorRecord>) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn query(&self, _query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn delete(&self, _ids: Vec<String>, _namespace: Option<String>) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn execute_batch(&self, _operation: BatchOperation) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn create_index(&self, _config: IndexConfig) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn delete_index(&self, _index_name: String) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
-    }
-    
+    async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
+        self.upsert_batch(vec![record]).await
+    }
+
+    async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
+        let Some(first) = records.first() else {
+            return Ok(());
+        };
+        let dimension = first.vector.len();
+        if let Some(record) = records.iter().find(|record| record.vector.len() != dimension) {
+            return Err(PlatformError::ValidationError(format!(
+                "Record '{}' has dimension {}, expected {}", record.id, record.vector.len(), dimension
+            )));
+        }
+
+        self.ensure_collection(dimension).await?;
+
+        // Milvus has no upsert-by-primary-key in insert, so replace existing rows
+        let collection = self.collection().await?;
+        let ids: Vec<String> = records.iter().map(|record| record.id.clone()).collect();
+        collection.delete(Self::ids_expr(&ids, None), None).await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Milvus delete failed: {}", e)))?;
+
+        if self.sparse_vectors {
+            self.insert_with_sparse(records).await?;
+            return self.maybe_build_hnsw_index().await;
+        }
+
+        let metric = self.search_metric();
+        let mut vectors = Vec::with_capacity(records.len() * dimension);
+        let mut namespaces = Vec::with_capacity(records.len());
+        let mut metadata = Vec::with_capacity(records.len());
+        for record in records {
+            vectors.extend(metric.prepare(record.vector));
+            namespaces.push(record.namespace.unwrap_or_default());
+            metadata.push(serde_json::to_string(&record.metadata).map_err(|e| {
+                PlatformError::VectorStoreError(format!("Invalid metadata for record '{}': {}", record.id, e))
+            })?);
+        }
+
+        let schema_field = |name: &str| collection.schema().get_field(name)
+            .ok_or_else(|| PlatformError::VectorStoreError(format!(
+                "Milvus collection '{}' has no '{}' field", self.collection_name, name
+            )));
+
+        let columns = vec![
+            FieldColumn::new(schema_field(ID_FIELD)?, ValueVec::String(ids)),
+            FieldColumn::new(schema_field(VECTOR_FIELD)?, ValueVec::Float(vectors)),
+            FieldColumn::new(schema_field(NAMESPACE_FIELD)?, ValueVec::String(namespaces)),
+            FieldColumn::new(schema_field(METADATA_FIELD)?, ValueVec::String(metadata)),
+        ];
+
+        collection.insert(columns, None).await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Milvus insert failed: {}", e)))?;
+
+        self.maybe_build_hnsw_index().await
+    }
+
+    async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        if query.filter.is_some() {
+            return Err(PlatformError::ValidationError(
+                "Milvus store does not support metadata filters".to_string()
+            ));
+        }
+
+        if query.is_hybrid() {
+            if !self.sparse_vectors {
+                return Err(PlatformError::ValidationError(
+                    "Hybrid search requires the Milvus store to be configured with sparse_vectors = true".to_string()
+                ));
+            }
+            return self.hybrid_query(query).await;
+        }
+
+        let collection = self.collection().await?;
+        let metric = self.search_metric().milvus_type();
+        let mut output_fields = vec![METADATA_FIELD];
+        if query.include_values {
+            output_fields.push(VECTOR_FIELD);
+        }
+
+        let mut options = SearchOption::new();
+        options
+            .set_expr(Self::namespace_expr(query.namespace.as_deref()))
+            // Only read by HNSW indexes
+            .add_param("ef", json!(query.top_k.max(HNSW_SEARCH_EF)));
+
+        let results = collection
+            .search(
+                vec![self.search_metric().prepare(query.vector.clone()).into()],
+                VECTOR_FIELD,
+                query.top_k as i32,
+                metric,
+                output_fields,
+                &options,
+            )
+            .await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Milvus search failed: {}", e)))?;
+
+        // One result set per query vector
+        Ok(results.first()
+            .map(|result| {
+                (0..result.size as usize)
+                    .filter_map(|index| Self::convert_search_result(result, index, metric, &query))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn delete(&self, ids: Vec<String>, namespace: Option<String>) -> Result<(), PlatformError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        self.collection().await?
+            .delete(Self::ids_expr(&ids, namespace.as_deref()), None)
+            .await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Milvus delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn execute_batch(&self, operation: BatchOperation) -> Result<(), PlatformError> {
+        // Execute upserts first
+        if !operation.upsert.is_empty() {
+            self.upsert_batch(operation.upsert).await?;
+        }
+
+        // Then execute deletes
+        if !operation.delete.is_empty() {
+            self.delete(operation.delete, None).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_index(&self, config: IndexConfig) -> Result<(), PlatformError> {
+        self.create_collection(&config.name, config.dimension, self.metric_for(&config)).await
+    }
+
+    async fn delete_index(&self, index_name: String) -> Result<(), PlatformError> {
+        self.client.drop_collection(&index_name).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to drop Milvus collection '{}': {}", index_name, e)
+            ))?;
+
+        if index_name == self.collection_name {
+            *self.collection.write().await = None;
+            self.hnsw_built.store(false, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
     async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
+        self.client.list_collections().await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to list Milvus collections: {}", e)
+            ))
     }
-    
-    async fn get_stats(&self, _namespace: Option<String>) -> Result<VectorStats, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
+
+    async fn get_stats(&self, namespace: Option<String>) -> Result<VectorStats, PlatformError> {
+        let collection = self.collection().await?;
+
+        let dimension = collection.schema().get_field(VECTOR_FIELD)
+            .map(|field| field.dim as usize)
+            .unwrap_or(0);
+
+        let row_count = self.row_count().await?;
+
+        // A collection without an index reports an error here rather than an empty list
+        let index_state = collection.describe_index(VECTOR_FIELD).await
+            .ok()
+            .and_then(|indexes| indexes.first().map(|index| format!("{:?}", index.state())));
+
+        let mut namespace_stats = HashMap::new();
+        if let Some(namespace) = namespace {
+            let vector_count = self.namespace_count(&namespace).await?;
+            namespace_stats.insert(namespace, NamespaceStats { vector_count });
+        }
+
+        Ok(VectorStats {
+            total_vectors: row_count,
+            dimension,
+            // Milvus collections have no fixed capacity
+            index_fullness: 0.0,
+            namespace_stats,
+            index_state,
+        })
     }
-    
+
     async fn test_connection(&self) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Milvus implementation not yet available".to_string()
-        ))
+        self.client.list_collections().await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Milvus connection test failed: {}", e)
+            ))?;
+
+        Ok(())
     }
-    
+
     fn provider_info(&self) -> VectorProviderInfo {
         VectorProviderInfo {
             name: "Milvus".to_string(),
             version: "2.0".to_string(),
             supports_namespaces: true,
-            supports_metadata_filtering: true,
-            supports_hybrid_search: true,
+            supports_metadata_filtering: false,
+            supports_hybrid_search: self.sparse_vectors,
+            supports_native_ttl: false,
             max_vector_dimension: 32768,
             max_batch_size: 1000,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metric_type() {
+        assert_eq!(MilvusStore::parse_metric_type("cosine").unwrap(), Metric::Cosine);
+        assert_eq!(MilvusStore::parse_metric_type(" IP ").unwrap(), Metric::InnerProduct);
+        assert_eq!(MilvusStore::parse_metric_type("L2").unwrap(), Metric::L2);
+        assert!(MilvusStore::parse_metric_type("HAMMING").is_err());
+    }
+
+    #[test]
+    fn test_cosine_is_served_as_ip_over_unit_vectors() {
+        assert!(matches!(Metric::Cosine.milvus_type(), MetricType::IP));
+        assert_eq!(Metric::Cosine.prepare(vec![3.0, 4.0]), vec![0.6, 0.8]);
+        assert_eq!(Metric::Cosine.prepare(vec![0.0, 0.0]), vec![0.0, 0.0]);
+        assert_eq!(Metric::L2.prepare(vec![3.0, 4.0]), vec![3.0, 4.0]);
+        assert_eq!(Metric::InnerProduct.prepare(vec![3.0, 4.0]), vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_ids_expr_escapes_and_scopes_namespace() {
+        let ids = vec!["doc-1".to_string(), "say \"hi\"".to_string()];
+
+        assert_eq!(
+            MilvusStore::ids_expr(&ids, None),
+            r#"id in ["doc-1", "say \"hi\""]"#
+        );
+        assert_eq!(
+            MilvusStore::ids_expr(&ids[..1], Some("tenant-a")),
+            r#"id in ["doc-1"] and namespace == "tenant-a""#
+        );
+    }
+
+    #[test]
+    fn test_hybrid_search_body_weights_and_scopes_both_searches() {
+        let query = SearchQuery::new(vec![0.1, 0.2], 5)
+            .unwrap()
+            .with_namespace("tenant-a".to_string())
+            .with_sparse_vector(HashMap::from([(7, 0.5)]))
+            .with_hybrid_weight(0.75)
+            .unwrap();
+
+        let body = MilvusStore::hybrid_search_body("docs", &query, Metric::Cosine);
+
+        assert_eq!(body["collectionName"], "docs");
+        assert_eq!(body["rerank"]["params"]["weights"], json!([0.75, 0.25]));
+        assert_eq!(body["search"][0]["annsField"], VECTOR_FIELD);
+        assert_eq!(body["search"][0]["metricType"], "IP");
+        assert_eq!(body["search"][1]["annsField"], SPARSE_VECTOR_FIELD);
+        assert_eq!(body["search"][1]["data"], json!([{ "7": 0.5 }]));
+        for search in body["search"].as_array().unwrap() {
+            assert_eq!(search["filter"], r#"namespace == "tenant-a""#);
+            assert_eq!(search["limit"], 5);
+        }
+        assert_eq!(body["outputFields"], json!([ID_FIELD, METADATA_FIELD]));
+    }
+
+    #[test]
+    fn test_convert_hybrid_hit_parses_metadata() {
+        let query = SearchQuery::new(vec![0.1], 1).unwrap();
+        let hit = json!({ "id": "doc-1", "distance": 0.42, "metadata": r#"{"title":"Intro"}"# });
+
+        let result = MilvusStore::convert_hybrid_hit(&hit, &query).unwrap();
+
+        assert_eq!(result.id, "doc-1");
+        assert_eq!(result.score, 0.42);
+        assert_eq!(result.metadata.unwrap()["title"], "Intro");
+    }
+
+    #[test]
+    fn test_l2_distance_maps_to_similarity() {
+        assert_eq!(MilvusStore::to_score(MetricType::L2, 0.0), 1.0);
+        assert!(MilvusStore::to_score(MetricType::L2, 3.0) < MilvusStore::to_score(MetricType::L2, 1.0));
+        assert_eq!(MilvusStore::to_score(MetricType::IP, 0.8), 0.8);
+    }
+}
@@ -4,11 +4,14 @@ pub mod weaviate;
 pub mod qdrant;
 pub mod milvus;
 
+use flate2::{write::GzEncoder, Compression};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::Write;
 use std::time::Duration;
 
+use crate::domain::value_objects::{SearchQuery, SearchResult};
 use crate::error::PlatformError;
 use crate::infrastructure::vector::VectorStoreConfig;
 
@@ -93,6 +96,58 @@ impl VectorHttpClient {
         }
     }
     
+    /// Send a POST request with JSON body, gzip-compressed when the
+    /// serialized body is at least `min_compress_bytes` long
+    pub async fn post_json_compressed<T: Serialize, R: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        body: &T,
+        headers: Option<HashMap<String, String>>,
+        min_compress_bytes: usize,
+    ) -> Result<R, PlatformError> {
+        let payload = serde_json::to_vec(body)
+            .map_err(|e| PlatformError::VectorStoreError(format!("Failed to serialize request: {}", e)))?;
+        
+        let mut request = self.client.post(url).header("Content-Type", "application/json");
+        
+        request = if payload.len() >= min_compress_bytes {
+            request
+                .header("Content-Encoding", "gzip")
+                .body(Self::gzip(&payload)?)
+        } else {
+            request.body(payload)
+        };
+        
+        if let Some(headers) = headers {
+            for (key, value) in headers {
+                request = request.header(&key, &value);
+            }
+        }
+        
+        let response = request.send().await
+            .map_err(|e| PlatformError::VectorStoreError(format!("HTTP request failed: {}", e)))?;
+        
+        let status = response.status();
+        let response_text = response.text().await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Failed to read response: {}", e)))?;
+        
+        if status.is_success() {
+            serde_json::from_str(&response_text)
+                .map_err(|e| PlatformError::VectorStoreError(format!("Failed to parse response: {}", e)))
+        } else {
+            Err(PlatformError::VectorStoreError(
+                format!("HTTP error {}: {}", status, response_text)
+            ))
+        }
+    }
+    
+    fn gzip(data: &[u8]) -> Result<Vec<u8>, PlatformError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| PlatformError::VectorStoreError(format!("Failed to compress request: {}", e)))
+    }
+    
     /// Send a GET request
     pub async fn get<R: for<'de> Deserialize<'de>>(
         &self,
@@ -124,6 +179,42 @@ impl VectorHttpClient {
         }
     }
     
+    /// Send a GET request, returning `None` when the resource does not exist
+    pub async fn get_optional<R: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<Option<R>, PlatformError> {
+        let mut request = self.client.get(url);
+        
+        if let Some(headers) = headers {
+            for (key, value) in headers {
+                request = request.header(&key, &value);
+            }
+        }
+        
+        let response = request.send().await
+            .map_err(|e| PlatformError::VectorStoreError(format!("HTTP request failed: {}", e)))?;
+        
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        
+        let response_text = response.text().await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Failed to read response: {}", e)))?;
+        
+        if status.is_success() {
+            serde_json::from_str(&response_text)
+                .map(Some)
+                .map_err(|e| PlatformError::VectorStoreError(format!("Failed to parse response: {}", e)))
+        } else {
+            Err(PlatformError::VectorStoreError(
+                format!("HTTP error {}: {}", status, response_text)
+            ))
+        }
+    }
+    
     /// Send a DELETE request
     pub async fn delete(
         &self,
@@ -153,6 +244,71 @@ impl VectorHttpClient {
             ))
         }
     }
+    
+    /// Send a DELETE request with a JSON body
+    pub async fn delete_json<T: Serialize>(
+        &self,
+        url: &str,
+        body: &T,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<(), PlatformError> {
+        let mut request = self.client.delete(url).json(body);
+        
+        if let Some(headers) = headers {
+            for (key, value) in headers {
+                request = request.header(&key, &value);
+            }
+        }
+        
+        let response = request.send().await
+            .map_err(|e| PlatformError::VectorStoreError(format!("HTTP request failed: {}", e)))?;
+        
+        let status = response.status();
+        
+        if status.is_success() {
+            Ok(())
+        } else {
+            let response_text = response.text().await
+                .map_err(|e| PlatformError::VectorStoreError(format!("Failed to read response: {}", e)))?;
+            Err(PlatformError::VectorStoreError(
+                format!("HTTP error {}: {}", status, response_text)
+            ))
+        }
+    }
+    
+    /// Send a DELETE request, treating a missing resource as already deleted.
+    /// Returns whether anything was removed.
+    pub async fn delete_if_exists(
+        &self,
+        url: &str,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<bool, PlatformError> {
+        let mut request = self.client.delete(url);
+        
+        if let Some(headers) = headers {
+            for (key, value) in headers {
+                request = request.header(&key, &value);
+            }
+        }
+        
+        let response = request.send().await
+            .map_err(|e| PlatformError::VectorStoreError(format!("HTTP request failed: {}", e)))?;
+        
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        
+        if status.is_success() {
+            Ok(true)
+        } else {
+            let response_text = response.text().await
+                .map_err(|e| PlatformError::VectorStoreError(format!("Failed to read response: {}", e)))?;
+            Err(PlatformError::VectorStoreError(
+                format!("HTTP error {}: {}", status, response_text)
+            ))
+        }
+    }
 }
 
 /// Common utilities for vector store providers
@@ -196,6 +352,52 @@ impl ProviderUtils {
         
         VectorHttpClient::new(http_config)
     }
+    
+    /// Strip the sparse part of a query for providers without hybrid search
+    pub fn dense_only(query: SearchQuery, provider: &str) -> SearchQuery {
+        if query.is_hybrid() {
+            tracing::debug!("{} does not support hybrid search, ignoring sparse vector", provider);
+        }
+        query.into_dense()
+    }
+    
+    /// Merge dense and sparse result lists by weighted sum of min-max
+    /// normalized scores. A result missing from one list scores 0 there.
+    pub fn fuse_hybrid_results(
+        dense: Vec<SearchResult>,
+        sparse: Vec<SearchResult>,
+        dense_weight: f32,
+        top_k: usize,
+    ) -> Vec<SearchResult> {
+        fn normalized(results: &[SearchResult]) -> Vec<f32> {
+            let min = results.iter().map(|r| r.score).fold(f32::INFINITY, f32::min);
+            let max = results.iter().map(|r| r.score).fold(f32::NEG_INFINITY, f32::max);
+            results.iter()
+                .map(|r| if max > min { (r.score - min) / (max - min) } else { 1.0 })
+                .collect()
+        }
+        
+        let dense_scores = normalized(&dense);
+        let sparse_scores = normalized(&sparse);
+        
+        let mut fused: Vec<SearchResult> = Vec::with_capacity(dense.len() + sparse.len());
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let weighted = dense.into_iter().zip(dense_scores.into_iter().map(|s| s * dense_weight))
+            .chain(sparse.into_iter().zip(sparse_scores.into_iter().map(|s| s * (1.0 - dense_weight))));
+        for (result, score) in weighted {
+            match positions.get(&result.id) {
+                Some(&position) => fused[position].score += score,
+                None => {
+                    positions.insert(result.id.clone(), fused.len());
+                    fused.push(SearchResult { score, ..result });
+                }
+            }
+        }
+        
+        fused.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        fused.truncate(top_k);
+        fused
+    }
 }
 
 /// Standard response format for vector operations
@@ -212,4 +414,59 @@ pub struct StandardErrorResponse {
     pub error: String,
     pub code: Option<String>,
     pub details: Option<serde_json::Value>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gzip_round_trip() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let payload = br#"{"vectors":[]}"#.repeat(100);
+        let compressed = VectorHttpClient::gzip(&payload).unwrap();
+        assert!(compressed.len() < payload.len());
+
+        let mut decompressed = Vec::new();
+        GzDecoder::new(compressed.as_slice()).read_to_end(&mut decompressed).unwrap();
+        assert_eq!(decompressed, payload);
+    }
+
+    #[test]
+    fn test_fuse_hybrid_results_merges_by_id() {
+        let dense = vec![
+            SearchResult::new("a".to_string(), 0.9),
+            SearchResult::new("b".to_string(), 0.5),
+        ];
+        let sparse = vec![
+            SearchResult::new("b".to_string(), 12.0),
+            SearchResult::new("c".to_string(), 4.0),
+        ];
+
+        let fused = ProviderUtils::fuse_hybrid_results(dense, sparse, 0.5, 2);
+
+        let ids: Vec<&str> = fused.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(fused[0].score, 0.5);
+        assert_eq!(fused[1].score, 0.5);
+    }
+
+    #[test]
+    fn test_fuse_hybrid_results_follows_weight() {
+        let dense = vec![
+            SearchResult::new("a".to_string(), 0.9),
+            SearchResult::new("b".to_string(), 0.1),
+        ];
+        let sparse = vec![
+            SearchResult::new("b".to_string(), 3.0),
+            SearchResult::new("a".to_string(), 1.0),
+        ];
+
+        let fused = ProviderUtils::fuse_hybrid_results(dense, sparse, 0.2, 10);
+
+        assert_eq!(fused[0].id, "b");
+        assert_eq!(fused.len(), 2);
+    }
+}
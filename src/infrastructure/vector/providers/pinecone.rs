@@ -1,67 +1,141 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
 
 use crate::domain::value_objects::{
     VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation,
-    DistanceMetric, NamespaceStats
+    DistanceMetric, NamespaceStats, SearchFilter, FilterOperator, ComparisonOperator, TenantId
 };
 use crate::error::PlatformError;
 use crate::infrastructure::vector::{VectorStore, VectorStoreConfig, VectorProviderInfo};
-use super::{ProviderUtils, VectorHttpClient, HttpClientConfig};
+use super::{ProviderUtils, VectorHttpClient};
 
-/// Pinecone vector store implementation
+/// Records sent per upsert request unless `max_batch_size` is configured.
+/// Pinecone rejects requests above 1000 records or 2 MB.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+/// Largest batch size Pinecone accepts
+const MAX_UPSERT_BATCH_SIZE: usize = 1000;
+
+/// Request bodies of at least this many bytes are sent gzip-compressed
+const GZIP_MIN_BYTES: usize = 64 * 1024;
+
+/// Pinecone vector store implementation, using the v1 data plane REST API.
+///
+/// Tenants share an index and are kept apart by namespace: every namespace
+/// sent to Pinecone is prefixed with the tenant ID (`{tenant_id}` for the
+/// default namespace, `{tenant_id}_{namespace}` otherwise), so a tenant can
+/// never read, write or count another tenant's vectors.
 pub struct PineconeStore {
     client: VectorHttpClient,
     api_key: String,
-    environment: String,
     index_name: String,
     base_url: String,
+    tenant_id: TenantId,
+    max_batch_size: usize,
 }
 
 impl PineconeStore {
     pub async fn new(config: VectorStoreConfig) -> Result<Self, PlatformError> {
-        // Validate required parameters
-        ProviderUtils::validate_required_params(&config, &["api_key", "environment", "index_name"])?;
-        
+        // Validate required parameters; `environment` may be replaced by the index host
+        ProviderUtils::validate_required_params(&config, &["api_key", "index_name"])?;
+
         let api_key = ProviderUtils::get_connection_param(&config, "api_key")?;
-        let environment = ProviderUtils::get_connection_param(&config, "environment")?;
         let index_name = ProviderUtils::get_connection_param(&config, "index_name")?;
-        
-        let base_url = format!("https://{}-{}.svc.{}.pinecone.io", 
-            index_name, 
-            environment.split('-').next().unwrap_or(&environment),
-            environment
-        );
-        
+        let tenant_id = config.tenant_id.ok_or_else(|| PlatformError::ValidationError(
+            "Pinecone store requires a tenant to isolate namespaces".to_string()
+        ))?;
+        let max_batch_size = Self::parse_max_batch_size(
+            ProviderUtils::get_optional_connection_param(&config, "max_batch_size"),
+        )?;
+
+        // Serverless indexes are addressed by the host shown in the Pinecone console
+        let base_url = match ProviderUtils::get_optional_connection_param(&config, "host") {
+            Some(host) => Self::normalize_host(&host),
+            None => {
+                let environment = ProviderUtils::get_connection_param(&config, "environment")?;
+                format!("https://{}-{}.svc.{}.pinecone.io",
+                    index_name,
+                    environment.split('-').next().unwrap_or(&environment),
+                    environment
+                )
+            }
+        };
+
         // Create HTTP client with Pinecone-specific headers
         let mut headers = HashMap::new();
         headers.insert("Api-Key".to_string(), api_key.clone());
         headers.insert("Content-Type".to_string(), "application/json".to_string());
-        
+
         let client = ProviderUtils::create_http_client(&config, headers)?;
-        
+
         let store = Self {
             client,
             api_key,
-            environment,
             index_name,
             base_url,
+            tenant_id,
+            max_batch_size,
         };
-        
+
         // Test connection
         store.test_connection().await?;
-        
+
         Ok(store)
     }
-    
+
+    fn parse_max_batch_size(value: Option<String>) -> Result<usize, PlatformError> {
+        match value {
+            None => Ok(DEFAULT_MAX_BATCH_SIZE),
+            Some(value) => match value.trim().parse::<usize>() {
+                Ok(size) if size > 0 && size <= MAX_UPSERT_BATCH_SIZE => Ok(size),
+                _ => Err(PlatformError::ValidationError(format!(
+                    "Invalid Pinecone max_batch_size: {} (must be 1-{})",
+                    value, MAX_UPSERT_BATCH_SIZE
+                ))),
+            },
+        }
+    }
+
+    fn normalize_host(host: &str) -> String {
+        let host = host.trim().trim_end_matches('/');
+        if host.starts_with("http://") || host.starts_with("https://") {
+            host.to_string()
+        } else {
+            format!("https://{}", host)
+        }
+    }
+
     fn build_headers(&self) -> HashMap<String, String> {
         let mut headers = HashMap::new();
         headers.insert("Api-Key".to_string(), self.api_key.clone());
         headers.insert("Content-Type".to_string(), "application/json".to_string());
         headers
     }
-    
+
+    /// Pinecone namespace holding a tenant's vectors for `namespace`
+    fn tenant_namespace(tenant_id: TenantId, namespace: Option<&str>) -> String {
+        match namespace {
+            Some(ns) if !ns.is_empty() => format!("{}_{}", tenant_id, ns),
+            _ => tenant_id.to_string(),
+        }
+    }
+
+    /// Namespace as the tenant knows it, or `None` if the Pinecone namespace
+    /// belongs to someone else. The default namespace is returned as `""`.
+    fn local_namespace(tenant_id: TenantId, pinecone_namespace: &str) -> Option<String> {
+        let tenant = tenant_id.to_string();
+        if pinecone_namespace == tenant {
+            return Some(String::new());
+        }
+        pinecone_namespace
+            .strip_prefix(&tenant)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .filter(|ns| !ns.is_empty())
+            .map(str::to_string)
+    }
+
     fn convert_distance_metric(metric: &DistanceMetric) -> String {
         match metric {
             DistanceMetric::Cosine => "cosine".to_string(),
@@ -69,11 +143,39 @@ impl PineconeStore {
             DistanceMetric::DotProduct => "dotproduct".to_string(),
         }
     }
-    
+
+    fn to_pinecone_vector(record: VectorRecord) -> PineconeVector {
+        PineconeVector {
+            id: record.id,
+            values: record.vector,
+            sparse_values: record.sparse_vector.as_ref().map(PineconeSparseValues::from_map),
+            metadata: Some(record.metadata),
+        }
+    }
+
+    /// Dense and sparse query vectors. Pinecone scores a hybrid query by the
+    /// dot product of both parts, so the hybrid weight is applied by scaling
+    /// the dense part by `alpha` and the sparse part by `1 - alpha`.
+    fn query_vectors(query: &SearchQuery) -> (Vec<f32>, Option<PineconeSparseValues>) {
+        let Some(ref sparse) = query.sparse_vector else {
+            return (query.vector.clone(), None);
+        };
+
+        let alpha = query.dense_weight();
+        let dense = query.vector.iter().map(|v| v * alpha).collect();
+        let mut sparse = PineconeSparseValues::from_map(sparse);
+        for value in &mut sparse.values {
+            *value *= 1.0 - alpha;
+        }
+
+        (dense, Some(sparse))
+    }
+
     fn convert_search_results(response: PineconeQueryResponse) -> Vec<SearchResult> {
         response.matches.into_iter().map(|m| {
             let mut result = SearchResult::new(m.id, m.score);
-            if let Some(values) = m.values {
+            // Values come back empty when they were not requested
+            if let Some(values) = m.values.filter(|v| !v.is_empty()) {
                 result = result.with_vector(values);
             }
             if let Some(metadata) = m.metadata {
@@ -82,247 +184,304 @@ impl PineconeStore {
             result
         }).collect()
     }
+
+    /// Translate a filter into Pinecone's metadata filter. Several conditions
+    /// are combined with `$and` or `$or`; a single condition stands on its own.
+    fn convert_filter(filter: &SearchFilter) -> Result<Option<Value>, PlatformError> {
+        let mut clauses = filter
+            .conditions
+            .iter()
+            .map(|condition| {
+                let operator = match condition.operator {
+                    ComparisonOperator::Equal => "$eq",
+                    ComparisonOperator::NotEqual => "$ne",
+                    ComparisonOperator::GreaterThan => "$gt",
+                    ComparisonOperator::GreaterThanOrEqual => "$gte",
+                    ComparisonOperator::LessThan => "$lt",
+                    ComparisonOperator::LessThanOrEqual => "$lte",
+                    ComparisonOperator::In => "$in",
+                    ComparisonOperator::NotIn => "$nin",
+                    ComparisonOperator::Contains => {
+                        return Err(PlatformError::VectorStoreError(format!(
+                            "Pinecone does not support contains filters on metadata field '{}'",
+                            condition.field
+                        )));
+                    }
+                };
+
+                if matches!(operator, "$in" | "$nin") && !condition.value.is_array() {
+                    return Err(PlatformError::VectorStoreError(format!(
+                        "Filter on '{}' needs a list of values for {}",
+                        condition.field, operator
+                    )));
+                }
+
+                Ok(json!({ condition.field.clone(): { operator: condition.value.clone() } }))
+            })
+            .collect::<Result<Vec<Value>, PlatformError>>()?;
+
+        let pinecone_filter = match clauses.len() {
+            0 => None,
+            1 => clauses.pop(),
+            _ => {
+                let combinator = match filter.operator {
+                    FilterOperator::And => "$and",
+                    FilterOperator::Or => "$or",
+                };
+                Some(json!({ combinator: clauses }))
+            }
+        };
+
+        Ok(pinecone_filter)
+    }
+
+    /// Stats of the tenant's namespaces only, optionally narrowed to one
+    fn tenant_stats(
+        tenant_id: TenantId,
+        response: PineconeStatsResponse,
+        namespace: Option<&str>,
+    ) -> VectorStats {
+        let mut namespace_stats = HashMap::new();
+        let mut total_vectors = 0;
+
+        for (pinecone_namespace, ns_data) in response.namespaces.unwrap_or_default() {
+            let Some(local) = Self::local_namespace(tenant_id, &pinecone_namespace) else {
+                continue;
+            };
+            if namespace.is_some_and(|ns| ns != local) {
+                continue;
+            }
+
+            total_vectors += ns_data.vector_count;
+            namespace_stats.insert(local, NamespaceStats {
+                vector_count: ns_data.vector_count,
+            });
+        }
+
+        VectorStats {
+            total_vectors,
+            dimension: response.dimension,
+            index_fullness: response.index_fullness,
+            namespace_stats,
+            index_state: None,
+        }
+    }
+
+    async fn describe_index_stats(&self) -> Result<PineconeStatsResponse, PlatformError> {
+        let url = format!("{}/describe_index_stats", self.base_url);
+        self.client
+            .post_json(&url, &PineconeStatsRequest { filter: None }, Some(self.build_headers()))
+            .await
+    }
 }
 
 #[async_trait]
 impl VectorStore for PineconeStore {
     async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
-        let request = PineconeUpsertRequest {
-            vectors: vec![PineconeVector {
-                id: record.id,
-                values: record.vector,
-                metadata: Some(record.metadata),
-            }],
-            namespace: record.namespace,
-        };
-        
-        let url = format!("{}/vectors/upsert", self.base_url);
-        let _response: PineconeUpsertResponse = self.client
-            .post_json(&url, &request, Some(self.build_headers()))
-            .await?;
-        
-        Ok(())
+        self.upsert_batch(vec![record]).await
     }
-    
+
     async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
         if records.is_empty() {
             return Ok(());
         }
-        
-        // Group records by namespace
-        let mut namespace_groups: HashMap<Option<String>, Vec<VectorRecord>> = HashMap::new();
+
+        // Group records by tenant namespace
+        let mut namespace_groups: BTreeMap<String, Vec<PineconeVector>> = BTreeMap::new();
         for record in records {
-            namespace_groups.entry(record.namespace.clone()).or_default().push(record);
+            if record.tenant_id != self.tenant_id {
+                return Err(PlatformError::ValidationError(
+                    "Vector record tenant ID does not match the store's tenant".to_string()
+                ));
+            }
+
+            let namespace = Self::tenant_namespace(self.tenant_id, record.namespace.as_deref());
+            namespace_groups.entry(namespace).or_default().push(Self::to_pinecone_vector(record));
         }
-        
-        // Process each namespace group separately
-        for (namespace, group_records) in namespace_groups {
-            let vectors: Vec<PineconeVector> = group_records.into_iter().map(|record| {
-                PineconeVector {
-                    id: record.id,
-                    values: record.vector,
-                    metadata: Some(record.metadata),
-                }
-            }).collect();
-            
-            let request = PineconeUpsertRequest {
-                vectors,
-                namespace,
-            };
-            
-            let url = format!("{}/vectors/upsert", self.base_url);
-            let _response: PineconeUpsertResponse = self.client
-                .post_json(&url, &request, Some(self.build_headers()))
-                .await?;
+
+        let url = format!("{}/vectors/upsert", self.base_url);
+        for (namespace, mut vectors) in namespace_groups {
+            while !vectors.is_empty() {
+                let rest = vectors.split_off(vectors.len().min(self.max_batch_size));
+                let request = PineconeUpsertRequest {
+                    vectors,
+                    namespace: namespace.clone(),
+                };
+
+                let _response: PineconeUpsertResponse = self.client
+                    .post_json_compressed(&url, &request, Some(self.build_headers()), GZIP_MIN_BYTES)
+                    .await?;
+
+                vectors = rest;
+            }
         }
-        
+
         Ok(())
     }
-    
+
     async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        let (vector, sparse_vector) = Self::query_vectors(&query);
+        let filter = match query.filter {
+            Some(ref filter) => Self::convert_filter(filter)?,
+            None => None,
+        };
+
         let request = PineconeQueryRequest {
-            vector: Some(query.vector),
+            vector,
+            sparse_vector,
             top_k: query.top_k as u32,
-            namespace: query.namespace,
-            filter: query.filter.map(|f| self.convert_filter(f)),
+            namespace: Self::tenant_namespace(self.tenant_id, query.namespace.as_deref()),
+            filter,
             include_values: query.include_values,
-            include_metadata: query.include_metadata,
+            // Callers rely on metadata for tenant checks and display
+            include_metadata: true,
         };
-        
+
         let url = format!("{}/query", self.base_url);
         let response: PineconeQueryResponse = self.client
             .post_json(&url, &request, Some(self.build_headers()))
             .await?;
-        
+
         Ok(Self::convert_search_results(response))
     }
-    
+
     async fn delete(&self, ids: Vec<String>, namespace: Option<String>) -> Result<(), PlatformError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
         let request = PineconeDeleteRequest {
             ids: Some(ids),
             delete_all: None,
-            namespace,
+            namespace: Self::tenant_namespace(self.tenant_id, namespace.as_deref()),
             filter: None,
         };
-        
+
         let url = format!("{}/vectors/delete", self.base_url);
         let _response: PineconeDeleteResponse = self.client
             .post_json(&url, &request, Some(self.build_headers()))
             .await?;
-        
+
         Ok(())
     }
-    
+
     async fn execute_batch(&self, operation: BatchOperation) -> Result<(), PlatformError> {
         // Execute upserts first
         if !operation.upsert.is_empty() {
             self.upsert_batch(operation.upsert).await?;
         }
-        
+
         // Then execute deletes
         if !operation.delete.is_empty() {
             self.delete(operation.delete, None).await?;
         }
-        
+
         Ok(())
     }
-    
+
     async fn create_index(&self, config: IndexConfig) -> Result<(), PlatformError> {
         // Note: Pinecone index creation is typically done through their control plane API
         // This would require a different endpoint and potentially different authentication
-        Err(PlatformError::VectorStoreError(
-            "Index creation not supported through data plane API. Use Pinecone console or control plane API.".to_string()
-        ))
+        Err(PlatformError::VectorStoreError(format!(
+            "Index creation not supported through data plane API. Create a {} index named '{}' in the Pinecone console or control plane API.",
+            Self::convert_distance_metric(&config.metric),
+            config.name
+        )))
     }
-    
+
     async fn delete_index(&self, _index_name: String) -> Result<(), PlatformError> {
         // Note: Pinecone index deletion is typically done through their control plane API
         Err(PlatformError::VectorStoreError(
             "Index deletion not supported through data plane API. Use Pinecone console or control plane API.".to_string()
         ))
     }
-    
+
     async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
-        // Note: Pinecone index listing is typically done through their control plane API
-        Err(PlatformError::VectorStoreError(
-            "Index listing not supported through data plane API. Use Pinecone console or control plane API.".to_string()
-        ))
+        // The data plane only knows the index it is connected to
+        Ok(vec![self.index_name.clone()])
     }
-    
+
     async fn get_stats(&self, namespace: Option<String>) -> Result<VectorStats, PlatformError> {
-        let request = PineconeStatsRequest { namespace };
-        
-        let url = format!("{}/describe_index_stats", self.base_url);
-        let response: PineconeStatsResponse = self.client
-            .post_json(&url, &request, Some(self.build_headers()))
-            .await?;
-        
-        let mut namespace_stats = HashMap::new();
-        if let Some(namespaces) = response.namespaces {
-            for (ns_name, ns_data) in namespaces {
-                namespace_stats.insert(ns_name, NamespaceStats {
-                    vector_count: ns_data.vector_count,
-                });
-            }
-        }
-        
-        Ok(VectorStats {
-            total_vectors: response.total_vector_count,
-            dimension: response.dimension,
-            index_fullness: response.index_fullness,
-            namespace_stats,
-        })
+        let response = self.describe_index_stats().await?;
+        Ok(Self::tenant_stats(self.tenant_id, response, namespace.as_deref()))
     }
-    
+
     async fn test_connection(&self) -> Result<(), PlatformError> {
-        let request = PineconeStatsRequest { namespace: None };
-        let url = format!("{}/describe_index_stats", self.base_url);
-        
-        let _response: PineconeStatsResponse = self.client
-            .post_json(&url, &request, Some(self.build_headers()))
+        self.describe_index_stats()
             .await
             .map_err(|e| PlatformError::VectorStoreError(
                 format!("Pinecone connection test failed: {}", e)
             ))?;
-        
+
         Ok(())
     }
-    
+
     fn provider_info(&self) -> VectorProviderInfo {
         VectorProviderInfo {
             name: "Pinecone".to_string(),
             version: "1.0".to_string(),
             supports_namespaces: true,
             supports_metadata_filtering: true,
-            supports_hybrid_search: false,
+            // Sparse values are only accepted by dotproduct indexes
+            supports_hybrid_search: true,
+            supports_native_ttl: false,
             max_vector_dimension: 20000,
-            max_batch_size: 1000,
+            max_batch_size: self.max_batch_size,
         }
     }
 }
 
-impl PineconeStore {
-    fn convert_filter(&self, filter: crate::domain::value_objects::SearchFilter) -> serde_json::Value {
-        // Convert our generic filter format to Pinecone's filter format
-        // This is a simplified implementation - Pinecone has complex filter syntax
-        let mut pinecone_filter = serde_json::Map::new();
-        
-        for condition in filter.conditions {
-            let value = match condition.operator {
-                crate::domain::value_objects::ComparisonOperator::Equal => {
-                    serde_json::json!({ "$eq": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::NotEqual => {
-                    serde_json::json!({ "$ne": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::In => {
-                    serde_json::json!({ "$in": condition.value })
-                },
-                crate::domain::value_objects::ComparisonOperator::NotIn => {
-                    serde_json::json!({ "$nin": condition.value })
-                },
-                _ => condition.value, // For other operators, use value directly
-            };
-            
-            pinecone_filter.insert(condition.field, value);
-        }
-        
-        serde_json::Value::Object(pinecone_filter)
-    }
-}
-
 // Pinecone API request/response structures
 
 #[derive(Debug, Serialize)]
 struct PineconeUpsertRequest {
     vectors: Vec<PineconeVector>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    namespace: Option<String>,
+    namespace: String,
 }
 
 #[derive(Debug, Serialize)]
 struct PineconeVector {
     id: String,
     values: Vec<f32>,
+    #[serde(rename = "sparseValues", skip_serializing_if = "Option::is_none")]
+    sparse_values: Option<PineconeSparseValues>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    metadata: Option<HashMap<String, serde_json::Value>>,
+    metadata: Option<HashMap<String, Value>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PineconeSparseValues {
+    indices: Vec<u32>,
+    values: Vec<f32>,
+}
+
+impl PineconeSparseValues {
+    /// Index and value lists, ordered by index
+    fn from_map(sparse: &HashMap<u32, f32>) -> Self {
+        let mut entries: Vec<(u32, f32)> = sparse.iter().map(|(index, value)| (*index, *value)).collect();
+        entries.sort_by_key(|(index, _)| *index);
+        let (indices, values) = entries.into_iter().unzip();
+        Self { indices, values }
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct PineconeUpsertResponse {
+    #[serde(rename = "upsertedCount", default)]
+    #[allow(dead_code)]
     upserted_count: u32,
 }
 
 #[derive(Debug, Serialize)]
 struct PineconeQueryRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    vector: Option<Vec<f32>>,
+    vector: Vec<f32>,
+    #[serde(rename = "sparseVector", skip_serializing_if = "Option::is_none")]
+    sparse_vector: Option<PineconeSparseValues>,
     #[serde(rename = "topK")]
     top_k: u32,
+    namespace: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    namespace: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    filter: Option<serde_json::Value>,
+    filter: Option<Value>,
     #[serde(rename = "includeValues")]
     include_values: bool,
     #[serde(rename = "includeMetadata")]
@@ -331,6 +490,7 @@ struct PineconeQueryRequest {
 
 #[derive(Debug, Deserialize)]
 struct PineconeQueryResponse {
+    #[serde(default)]
     matches: Vec<PineconeMatch>,
 }
 
@@ -339,7 +499,7 @@ struct PineconeMatch {
     id: String,
     score: f32,
     values: Option<Vec<f32>>,
-    metadata: Option<HashMap<String, serde_json::Value>>,
+    metadata: Option<HashMap<String, Value>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -348,10 +508,9 @@ struct PineconeDeleteRequest {
     ids: Option<Vec<String>>,
     #[serde(rename = "deleteAll", skip_serializing_if = "Option::is_none")]
     delete_all: Option<bool>,
+    namespace: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    namespace: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    filter: Option<serde_json::Value>,
+    filter: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -362,15 +521,16 @@ struct PineconeDeleteResponse {
 #[derive(Debug, Serialize)]
 struct PineconeStatsRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    namespace: Option<String>,
+    filter: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
 struct PineconeStatsResponse {
-    #[serde(rename = "totalVectorCount")]
+    #[serde(rename = "totalVectorCount", default)]
+    #[allow(dead_code)]
     total_vector_count: u64,
     dimension: usize,
-    #[serde(rename = "indexFullness")]
+    #[serde(rename = "indexFullness", default)]
     index_fullness: f32,
     namespaces: Option<HashMap<String, PineconeNamespaceStats>>,
 }
@@ -379,4 +539,166 @@ struct PineconeStatsResponse {
 struct PineconeNamespaceStats {
     #[serde(rename = "vectorCount")]
     vector_count: u64,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::FilterCondition;
+
+    #[test]
+    fn test_tenant_namespace_round_trip() {
+        let tenant_id = TenantId::new();
+
+        let default_ns = PineconeStore::tenant_namespace(tenant_id, None);
+        assert_eq!(default_ns, tenant_id.to_string());
+        assert_eq!(PineconeStore::tenant_namespace(tenant_id, Some("")), default_ns);
+        assert_eq!(PineconeStore::local_namespace(tenant_id, &default_ns), Some(String::new()));
+
+        let docs_ns = PineconeStore::tenant_namespace(tenant_id, Some("docs"));
+        assert_eq!(docs_ns, format!("{}_docs", tenant_id));
+        assert_eq!(PineconeStore::local_namespace(tenant_id, &docs_ns), Some("docs".to_string()));
+    }
+
+    #[test]
+    fn test_local_namespace_rejects_other_tenants() {
+        let tenant_id = TenantId::new();
+        let other = PineconeStore::tenant_namespace(TenantId::new(), Some("docs"));
+
+        assert_eq!(PineconeStore::local_namespace(tenant_id, &other), None);
+        assert_eq!(PineconeStore::local_namespace(tenant_id, "docs"), None);
+        assert_eq!(PineconeStore::local_namespace(tenant_id, ""), None);
+    }
+
+    #[test]
+    fn test_tenant_stats_only_counts_own_namespaces() {
+        let tenant_id = TenantId::new();
+        let response: PineconeStatsResponse = serde_json::from_value(json!({
+            "namespaces": {
+                (tenant_id.to_string()): { "vectorCount": 10 },
+                (format!("{}_docs", tenant_id)): { "vectorCount": 5 },
+                (format!("{}_docs", TenantId::new())): { "vectorCount": 100 },
+                "": { "vectorCount": 1000 }
+            },
+            "dimension": 1536,
+            "indexFullness": 0.0,
+            "totalVectorCount": 1115
+        }))
+        .unwrap();
+
+        let stats = PineconeStore::tenant_stats(tenant_id, response, None);
+        assert_eq!(stats.total_vectors, 15);
+        assert_eq!(stats.dimension, 1536);
+        assert_eq!(stats.namespace_stats.len(), 2);
+        assert_eq!(stats.namespace_stats["docs"].vector_count, 5);
+    }
+
+    #[test]
+    fn test_tenant_stats_for_one_namespace() {
+        let tenant_id = TenantId::new();
+        let response: PineconeStatsResponse = serde_json::from_value(json!({
+            "namespaces": {
+                (tenant_id.to_string()): { "vectorCount": 10 },
+                (format!("{}_docs", tenant_id)): { "vectorCount": 5 }
+            },
+            "dimension": 8
+        }))
+        .unwrap();
+
+        let stats = PineconeStore::tenant_stats(tenant_id, response, Some("docs"));
+        assert_eq!(stats.total_vectors, 5);
+        assert_eq!(stats.namespace_stats.len(), 1);
+    }
+
+    #[test]
+    fn test_upsert_request_passes_sparse_values() {
+        let record = VectorRecord::new("doc-1".to_string(), vec![0.1, 0.2], TenantId::new())
+            .unwrap()
+            .with_sparse_vector(HashMap::from([(42, 0.5), (7, 1.5)]));
+
+        let request = PineconeUpsertRequest {
+            vectors: vec![PineconeStore::to_pinecone_vector(record)],
+            namespace: "ns".to_string(),
+        };
+        let body = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(
+            body["vectors"][0]["sparseValues"],
+            json!({ "indices": [7, 42], "values": [1.5, 0.5] })
+        );
+    }
+
+    #[test]
+    fn test_query_vectors_apply_hybrid_weight() {
+        let query = SearchQuery::new(vec![1.0, 2.0], 5)
+            .unwrap()
+            .with_sparse_vector(HashMap::from([(3, 2.0)]))
+            .with_hybrid_weight(0.25)
+            .unwrap();
+
+        let (dense, sparse) = PineconeStore::query_vectors(&query);
+        assert_eq!(dense, vec![0.25, 0.5]);
+        assert_eq!(sparse, Some(PineconeSparseValues { indices: vec![3], values: vec![1.5] }));
+
+        let dense_query = SearchQuery::new(vec![1.0, 2.0], 5).unwrap();
+        assert_eq!(PineconeStore::query_vectors(&dense_query), (vec![1.0, 2.0], None));
+    }
+
+    #[test]
+    fn test_convert_filter() {
+        let filter = SearchFilter {
+            conditions: vec![
+                FilterCondition {
+                    field: "year".to_string(),
+                    operator: ComparisonOperator::GreaterThanOrEqual,
+                    value: json!(2020),
+                },
+                FilterCondition {
+                    field: "lang".to_string(),
+                    operator: ComparisonOperator::In,
+                    value: json!(["en", "zh"]),
+                },
+            ],
+            operator: FilterOperator::And,
+        };
+
+        assert_eq!(
+            PineconeStore::convert_filter(&filter).unwrap(),
+            Some(json!({ "$and": [
+                { "year": { "$gte": 2020 } },
+                { "lang": { "$in": ["en", "zh"] } }
+            ] }))
+        );
+    }
+
+    #[test]
+    fn test_convert_filter_rejects_contains() {
+        let filter = SearchFilter {
+            conditions: vec![FilterCondition {
+                field: "title".to_string(),
+                operator: ComparisonOperator::Contains,
+                value: json!("rust"),
+            }],
+            operator: FilterOperator::And,
+        };
+
+        assert!(PineconeStore::convert_filter(&filter).is_err());
+    }
+
+    #[test]
+    fn test_parse_max_batch_size() {
+        assert_eq!(PineconeStore::parse_max_batch_size(None).unwrap(), DEFAULT_MAX_BATCH_SIZE);
+        assert_eq!(PineconeStore::parse_max_batch_size(Some("500".to_string())).unwrap(), 500);
+        assert!(PineconeStore::parse_max_batch_size(Some("0".to_string())).is_err());
+        assert!(PineconeStore::parse_max_batch_size(Some("5000".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_normalize_host() {
+        assert_eq!(
+            PineconeStore::normalize_host("docs-abc123.svc.aped-4627-b74a.pinecone.io/"),
+            "https://docs-abc123.svc.aped-4627-b74a.pinecone.io"
+        );
+        assert_eq!(PineconeStore::normalize_host("http://localhost:5080"), "http://localhost:5080");
+    }
+}
@@ -1,107 +1,687 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use qdrant_client::qdrant::{
+    vectors_config, vectors_output, point_id, Condition, CountPointsBuilder,
+    CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, DeletePointsBuilder, Distance,
+    FieldType, Filter, NamedVectors, PointId, PointStruct, PointsIdsList, Range, ScoredPoint,
+    SearchPointsBuilder, SparseIndices, SparseVectorParamsBuilder, SparseVectorsConfigBuilder,
+    UpsertPointsBuilder, Vector, VectorParamsBuilder,
+};
+use qdrant_client::{Payload, Qdrant};
 use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
 
 use crate::domain::value_objects::{
-    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation
+    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation,
+    DistanceMetric, NamespaceStats, SearchFilter, FilterOperator, FilterCondition,
+    ComparisonOperator
 };
 use crate::error::PlatformError;
 use crate::infrastructure::vector::{VectorStore, VectorStoreConfig, VectorProviderInfo};
 use super::ProviderUtils;
 
-/// Qdrant vector store implementation (placeholder)
+/// Payload field holding the caller's original record id. Qdrant only accepts
+/// unsigned integers or UUIDs as point ids, so other ids are mapped to a
+/// deterministic UUID and the original is kept here.
+const ID_FIELD: &str = "_id";
+
+/// Payload field used to emulate namespaces, which Qdrant has no notion of.
+const NAMESPACE_FIELD: &str = "_namespace";
+
+/// Payload field holding the record's expiry as a Unix timestamp. It is
+/// indexed so expired points can be filtered out and purged cheaply.
+const EXPIRES_AT_FIELD: &str = "_expires_at";
+
+/// Maximum number of points sent in a single upsert request.
+const UPSERT_CHUNK_SIZE: usize = 256;
+
+/// Name of the default (dense) vector when a point also has a sparse vector
+const DENSE_VECTOR_NAME: &str = "";
+
+/// Named sparse vector used for hybrid search
+const SPARSE_VECTOR_NAME: &str = "sparse";
+
+/// Qdrant vector store implementation over the gRPC transport
 pub struct QdrantStore {
-    base_url: String,
-    api_key: Option<String>,
+    client: Qdrant,
     collection_name: String,
 }
 
 impl QdrantStore {
     pub async fn new(config: VectorStoreConfig) -> Result<Self, PlatformError> {
-        ProviderUtils::validate_required_params(&config, &["base_url", "collection_name"])?;
-        
-        let base_url = ProviderUtils::get_connection_param(&config, "base_url")?;
-        let collection_name = ProviderUtils::get_connection_param(&config, "collection_name")?;
+        ProviderUtils::validate_required_params(&config, &["url", "collection"])?;
+
+        let url = ProviderUtils::get_connection_param(&config, "url")?;
+        let collection_name = ProviderUtils::get_connection_param(&config, "collection")?;
         let api_key = ProviderUtils::get_optional_connection_param(&config, "api_key");
-        
-        Ok(Self {
-            base_url: base_url.trim_end_matches('/').to_string(),
-            api_key,
+
+        let mut builder = Qdrant::from_url(url.trim_end_matches('/'))
+            .timeout(Duration::from_secs(config.timeout_seconds));
+        if let Some(api_key) = api_key {
+            builder = builder.api_key(api_key);
+        }
+
+        let client = builder.build()
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to create Qdrant client: {}", e)
+            ))?;
+
+        let store = Self {
+            client,
             collection_name,
+        };
+
+        // Test connection
+        store.test_connection().await?;
+
+        Ok(store)
+    }
+
+    fn convert_distance_metric(metric: &DistanceMetric) -> Distance {
+        match metric {
+            DistanceMetric::Cosine => Distance::Cosine,
+            DistanceMetric::Euclidean => Distance::Euclid,
+            DistanceMetric::DotProduct => Distance::Dot,
+        }
+    }
+
+    /// Map an arbitrary record id onto a valid Qdrant point id.
+    fn point_id(id: &str) -> PointId {
+        if let Ok(num) = id.parse::<u64>() {
+            return PointId::from(num);
+        }
+        if let Ok(uuid) = Uuid::parse_str(id) {
+            return PointId::from(uuid.to_string());
+        }
+        PointId::from(Uuid::new_v5(&Uuid::NAMESPACE_OID, id.as_bytes()).to_string())
+    }
+
+    fn to_point(record: VectorRecord) -> Result<PointStruct, PlatformError> {
+        let mut payload: serde_json::Map<String, serde_json::Value> = record.metadata.into_iter().collect();
+        payload.insert(ID_FIELD.to_string(), serde_json::Value::String(record.id.clone()));
+        if let Some(namespace) = record.namespace {
+            payload.insert(NAMESPACE_FIELD.to_string(), serde_json::Value::String(namespace));
+        }
+        if let Some(expires_at) = record.expires_at {
+            payload.insert(EXPIRES_AT_FIELD.to_string(), serde_json::Value::from(expires_at.timestamp()));
+        }
+
+        let payload = Payload::try_from(serde_json::Value::Object(payload))
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Invalid Qdrant payload for record '{}': {}", record.id, e)
+            ))?;
+
+        let point = match record.sparse_vector {
+            Some(sparse) => {
+                let (indices, values) = Self::sparse_parts(&sparse);
+                let vectors = NamedVectors::default()
+                    .add_vector(DENSE_VECTOR_NAME, Vector::from(record.vector))
+                    .add_vector(SPARSE_VECTOR_NAME, Vector::new_sparse(indices, values));
+                PointStruct::new(Self::point_id(&record.id), vectors, payload)
+            },
+            None => PointStruct::new(Self::point_id(&record.id), record.vector, payload),
+        };
+
+        Ok(point)
+    }
+
+    /// Split a sparse vector into index and value lists, ordered by index
+    fn sparse_parts(sparse: &HashMap<u32, f32>) -> (Vec<u32>, Vec<f32>) {
+        let mut entries: Vec<(u32, f32)> = sparse.iter().map(|(index, value)| (*index, *value)).collect();
+        entries.sort_by_key(|(index, _)| *index);
+        entries.into_iter().unzip()
+    }
+
+    fn namespace_condition(namespace: &str) -> Condition {
+        Condition::matches(NAMESPACE_FIELD, namespace.to_string())
+    }
+
+    /// Matches points whose expiry has passed; points without one never match
+    fn expired_condition(now: DateTime<Utc>) -> Condition {
+        Condition::range(EXPIRES_AT_FIELD, Range {
+            lte: Some(now.timestamp() as f64),
+            ..Default::default()
         })
     }
+
+    fn exclude_expired(filter: Option<Filter>, now: DateTime<Utc>) -> Filter {
+        let mut filter = filter.unwrap_or_default();
+        filter.must_not.push(Self::expired_condition(now));
+        filter
+    }
+
+    /// Delete expired points without waiting for the deletion to be applied
+    async fn purge_expired(&self) -> Result<(), PlatformError> {
+        let request = DeletePointsBuilder::new(&self.collection_name)
+            .points(Filter::must([Self::expired_condition(Utc::now())]))
+            .wait(false);
+
+        self.client.delete_points(request).await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Qdrant expiry purge failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn convert_condition(condition: FilterCondition) -> Result<Condition, PlatformError> {
+        let field = condition.field;
+        let value = condition.value;
+
+        let range_bound = |value: &serde_json::Value| value.as_f64().ok_or_else(|| {
+            PlatformError::ValidationError(format!(
+                "Range filter on '{}' requires a numeric value", field
+            ))
+        });
+
+        let condition = match condition.operator {
+            ComparisonOperator::Equal => Self::match_condition(&field, &value)?,
+            // Full-text match: substring match on fields without a text
+            // index, all-tokens match on fields with one
+            ComparisonOperator::Contains => {
+                let text = value.as_str().ok_or_else(|| PlatformError::ValidationError(format!(
+                    "Contains filter on '{}' requires a string value", field
+                )))?;
+                Condition::matches_text(&field, text)
+            },
+            ComparisonOperator::NotEqual => {
+                Filter::must_not([Self::match_condition(&field, &value)?]).into()
+            },
+            ComparisonOperator::In => Self::match_any_condition(&field, &value)?,
+            ComparisonOperator::NotIn => {
+                Filter::must_not([Self::match_any_condition(&field, &value)?]).into()
+            },
+            ComparisonOperator::GreaterThan => Condition::range(&field, Range {
+                gt: Some(range_bound(&value)?),
+                ..Default::default()
+            }),
+            ComparisonOperator::GreaterThanOrEqual => Condition::range(&field, Range {
+                gte: Some(range_bound(&value)?),
+                ..Default::default()
+            }),
+            ComparisonOperator::LessThan => Condition::range(&field, Range {
+                lt: Some(range_bound(&value)?),
+                ..Default::default()
+            }),
+            ComparisonOperator::LessThanOrEqual => Condition::range(&field, Range {
+                lte: Some(range_bound(&value)?),
+                ..Default::default()
+            }),
+        };
+
+        Ok(condition)
+    }
+
+    fn match_condition(field: &str, value: &serde_json::Value) -> Result<Condition, PlatformError> {
+        match value {
+            serde_json::Value::String(s) => Ok(Condition::matches(field, s.clone())),
+            serde_json::Value::Bool(b) => Ok(Condition::matches(field, *b)),
+            serde_json::Value::Number(n) if n.is_i64() => {
+                Ok(Condition::matches(field, n.as_i64().unwrap_or_default()))
+            },
+            _ => Err(PlatformError::ValidationError(format!(
+                "Unsupported match value for '{}': {}", field, value
+            ))),
+        }
+    }
+
+    fn match_any_condition(field: &str, value: &serde_json::Value) -> Result<Condition, PlatformError> {
+        let values = value.as_array().ok_or_else(|| PlatformError::ValidationError(
+            format!("In filter on '{}' requires an array value", field)
+        ))?;
+
+        if values.iter().all(|v| v.is_string()) {
+            let keywords: Vec<String> = values.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect();
+            Ok(Condition::matches(field, keywords))
+        } else if values.iter().all(|v| v.is_i64()) {
+            let integers: Vec<i64> = values.iter().filter_map(|v| v.as_i64()).collect();
+            Ok(Condition::matches(field, integers))
+        } else {
+            Err(PlatformError::ValidationError(format!(
+                "In filter on '{}' requires all strings or all integers", field
+            )))
+        }
+    }
+
+    fn build_filter(
+        filter: Option<SearchFilter>,
+        namespace: Option<&str>,
+    ) -> Result<Option<Filter>, PlatformError> {
+        let mut must = Vec::new();
+
+        if let Some(namespace) = namespace {
+            must.push(Self::namespace_condition(namespace));
+        }
+
+        if let Some(filter) = filter {
+            let conditions = filter.conditions.into_iter()
+                .map(Self::convert_condition)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if !conditions.is_empty() {
+                match filter.operator {
+                    FilterOperator::And => must.extend(conditions),
+                    FilterOperator::Or => must.push(Filter::should(conditions).into()),
+                }
+            }
+        }
+
+        if must.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Filter::must(must)))
+        }
+    }
+
+    fn convert_search_result(point: ScoredPoint) -> SearchResult {
+        let mut metadata: HashMap<String, serde_json::Value> = point.payload.into_iter()
+            .map(|(key, value)| (key, value.into_json()))
+            .collect();
+
+        let id = match metadata.remove(ID_FIELD) {
+            Some(serde_json::Value::String(id)) => id,
+            _ => match point.id.and_then(|id| id.point_id_options) {
+                Some(point_id::PointIdOptions::Num(num)) => num.to_string(),
+                Some(point_id::PointIdOptions::Uuid(uuid)) => uuid,
+                None => String::new(),
+            },
+        };
+        metadata.remove(NAMESPACE_FIELD);
+        metadata.remove(EXPIRES_AT_FIELD);
+
+        let mut result = SearchResult::new(id, point.score);
+        match point.vectors.and_then(|v| v.vectors_options) {
+            Some(vectors_output::VectorsOptions::Vector(vector)) => {
+                result = result.with_vector(vector.data);
+            },
+            // Points with a sparse vector store the dense one under its default name
+            Some(vectors_output::VectorsOptions::Vectors(mut named)) => {
+                if let Some(vector) = named.vectors.remove(DENSE_VECTOR_NAME) {
+                    result = result.with_vector(vector.data);
+                }
+            },
+            None => {},
+        }
+        if !metadata.is_empty() {
+            result = result.with_metadata(metadata);
+        }
+        result
+    }
+
+    async fn search(&self, request: SearchPointsBuilder, include_metadata: bool) -> Result<Vec<SearchResult>, PlatformError> {
+        let response = self.client.search_points(request).await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Qdrant search failed: {}", e)))?;
+
+        Ok(response.result.into_iter()
+            .map(Self::convert_search_result)
+            .map(|mut result| {
+                if !include_metadata {
+                    result.metadata = None;
+                }
+                result
+            })
+            .collect())
+    }
+
+    async fn count_points(&self, filter: Option<Filter>) -> Result<u64, PlatformError> {
+        let mut request = CountPointsBuilder::new(&self.collection_name).exact(true);
+        if let Some(filter) = filter {
+            request = request.filter(filter);
+        }
+
+        let response = self.client.count(request).await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Qdrant count failed: {}", e)))?;
+
+        Ok(response.result.map(|r| r.count).unwrap_or(0))
+    }
 }
 
 #[async_trait]
 impl VectorStore for QdrantStore {
-    async fn upsert(&self, _record: VectorRecord) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn upsert_batch(&self, _records: Vec<VectorRecord>) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn query(&self, _query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn delete(&self, _ids: Vec<String>, _namespace: Option<String>) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn execute_batch(&self, _operation: BatchOperation) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn create_index(&self, _config: IndexConfig) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn delete_index(&self, _index_name: String) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
-    }
-    
+    async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
+        self.upsert_batch(vec![record]).await
+    }
+
+    async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let points = records.into_iter()
+            .map(Self::to_point)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.client
+            .upsert_points_chunked(
+                UpsertPointsBuilder::new(&self.collection_name, points).wait(true),
+                UPSERT_CHUNK_SIZE,
+            )
+            .await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Qdrant upsert failed: {}", e)))?;
+
+        // Expired points are already hidden from queries; purging them on
+        // writes keeps the collection from growing without a reaper
+        if let Err(e) = self.purge_expired().await {
+            tracing::warn!("Failed to purge expired points from '{}': {}", self.collection_name, e);
+        }
+
+        Ok(())
+    }
+
+    async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        let filter = Some(Self::exclude_expired(
+            Self::build_filter(query.filter.clone(), query.namespace.as_deref())?,
+            Utc::now(),
+        ));
+        let top_k = query.top_k as u64;
+        let dense_weight = query.dense_weight();
+
+        let dense_request = || {
+            let mut request = SearchPointsBuilder::new(&self.collection_name, query.vector.clone(), top_k)
+                // The original id lives in the payload, so it is always requested
+                .with_payload(true)
+                .with_vectors(query.include_values);
+            if let Some(filter) = filter.clone() {
+                request = request.filter(filter);
+            }
+            request
+        };
+
+        let Some(sparse) = query.sparse_vector.as_ref() else {
+            return self.search(dense_request(), query.include_metadata).await;
+        };
+
+        let (indices, values) = Self::sparse_parts(sparse);
+        let mut sparse_request = SearchPointsBuilder::new(&self.collection_name, values, top_k)
+            .vector_name(SPARSE_VECTOR_NAME)
+            .sparse_indices(SparseIndices { data: indices })
+            .with_payload(true)
+            .with_vectors(query.include_values);
+        if let Some(filter) = filter.clone() {
+            sparse_request = sparse_request.filter(filter);
+        }
+
+        // Qdrant only fuses by rank, so weighted fusion is done here
+        let dense = if dense_weight > 0.0 {
+            self.search(dense_request(), query.include_metadata).await?
+        } else {
+            Vec::new()
+        };
+        let sparse = if dense_weight < 1.0 {
+            self.search(sparse_request, query.include_metadata).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(ProviderUtils::fuse_hybrid_results(dense, sparse, dense_weight, query.top_k))
+    }
+
+    async fn delete(&self, ids: Vec<String>, namespace: Option<String>) -> Result<(), PlatformError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let request = match namespace {
+            // Restrict the deletion to points that also belong to the namespace
+            Some(namespace) => {
+                let filter = Filter::must([
+                    Condition::has_id(ids.iter().map(|id| Self::point_id(id))),
+                    Self::namespace_condition(&namespace),
+                ]);
+                DeletePointsBuilder::new(&self.collection_name).points(filter)
+            },
+            None => DeletePointsBuilder::new(&self.collection_name).points(PointsIdsList {
+                ids: ids.iter().map(|id| Self::point_id(id)).collect(),
+            }),
+        };
+
+        self.client.delete_points(request.wait(true)).await
+            .map_err(|e| PlatformError::VectorStoreError(format!("Qdrant delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn execute_batch(&self, operation: BatchOperation) -> Result<(), PlatformError> {
+        // Execute upserts first
+        if !operation.upsert.is_empty() {
+            self.upsert_batch(operation.upsert).await?;
+        }
+
+        // Then execute deletes
+        if !operation.delete.is_empty() {
+            self.delete(operation.delete, None).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_index(&self, config: IndexConfig) -> Result<(), PlatformError> {
+        let mut sparse_vectors = SparseVectorsConfigBuilder::default();
+        sparse_vectors.add_named_vector_params(SPARSE_VECTOR_NAME, SparseVectorParamsBuilder::default());
+
+        let mut request = CreateCollectionBuilder::new(&config.name)
+            .vectors_config(VectorParamsBuilder::new(
+                config.dimension as u64,
+                Self::convert_distance_metric(&config.metric),
+            ))
+            .sparse_vectors_config(sparse_vectors);
+        if let Some(shards) = config.shards {
+            request = request.shard_number(shards as u32);
+        }
+        if let Some(replicas) = config.replicas {
+            request = request.replication_factor(replicas as u32);
+        }
+
+        self.client.create_collection(request).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to create Qdrant collection '{}': {}", config.name, e)
+            ))?;
+
+        self.client
+            .create_field_index(CreateFieldIndexCollectionBuilder::new(
+                &config.name,
+                EXPIRES_AT_FIELD,
+                FieldType::Integer,
+            ))
+            .await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to index expiry of Qdrant collection '{}': {}", config.name, e)
+            ))?;
+
+        Ok(())
+    }
+
+    async fn delete_index(&self, index_name: String) -> Result<(), PlatformError> {
+        self.client.delete_collection(&index_name).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to delete Qdrant collection '{}': {}", index_name, e)
+            ))?;
+
+        Ok(())
+    }
+
     async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
+        let response = self.client.list_collections().await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to list Qdrant collections: {}", e)
+            ))?;
+
+        Ok(response.collections.into_iter().map(|c| c.name).collect())
     }
-    
-    async fn get_stats(&self, _namespace: Option<String>) -> Result<VectorStats, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
+
+    async fn get_stats(&self, namespace: Option<String>) -> Result<VectorStats, PlatformError> {
+        let response = self.client.collection_info(&self.collection_name).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to fetch Qdrant collection info: {}", e)
+            ))?;
+
+        let info = response.result.ok_or_else(|| PlatformError::VectorStoreError(
+            format!("Qdrant collection '{}' not found", self.collection_name)
+        ))?;
+
+        let dimension = info.config
+            .and_then(|c| c.params)
+            .and_then(|p| p.vectors_config)
+            .and_then(|v| v.config)
+            .map(|config| match config {
+                vectors_config::Config::Params(params) => params.size as usize,
+                vectors_config::Config::ParamsMap(_) => 0,
+            })
+            .unwrap_or(0);
+
+        let mut namespace_stats = HashMap::new();
+        if let Some(namespace) = namespace {
+            let vector_count = self
+                .count_points(Some(Filter::must([Self::namespace_condition(&namespace)])))
+                .await?;
+            namespace_stats.insert(namespace, NamespaceStats { vector_count });
+        }
+
+        Ok(VectorStats {
+            total_vectors: info.points_count.unwrap_or(0),
+            dimension,
+            // Qdrant collections have no fixed capacity
+            index_fullness: 0.0,
+            namespace_stats,
+            index_state: None,
+        })
     }
-    
+
     async fn test_connection(&self) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Qdrant implementation not yet available".to_string()
-        ))
+        self.client.health_check().await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Qdrant connection test failed: {}", e)
+            ))?;
+
+        Ok(())
     }
-    
+
     fn provider_info(&self) -> VectorProviderInfo {
         VectorProviderInfo {
             name: "Qdrant".to_string(),
             version: "1.0".to_string(),
             supports_namespaces: true,
             supports_metadata_filtering: true,
-            supports_hybrid_search: false,
+            supports_hybrid_search: true,
+            supports_native_ttl: true,
             max_vector_dimension: 65536,
             max_batch_size: 1000,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qdrant_client::qdrant::condition::ConditionOneOf;
+
+    #[test]
+    fn test_point_id_mapping() {
+        assert_eq!(
+            QdrantStore::point_id("42").point_id_options,
+            Some(point_id::PointIdOptions::Num(42))
+        );
+
+        let uuid = Uuid::new_v4().to_string();
+        assert_eq!(
+            QdrantStore::point_id(&uuid).point_id_options,
+            Some(point_id::PointIdOptions::Uuid(uuid))
+        );
+
+        // Arbitrary ids map to a stable UUID
+        assert_eq!(QdrantStore::point_id("doc-1"), QdrantStore::point_id("doc-1"));
+        assert_ne!(QdrantStore::point_id("doc-1"), QdrantStore::point_id("doc-2"));
+    }
+
+    #[test]
+    fn test_build_filter_with_namespace_and_or_conditions() {
+        let filter = SearchFilter {
+            conditions: vec![
+                FilterCondition {
+                    field: "category".to_string(),
+                    operator: ComparisonOperator::Equal,
+                    value: serde_json::json!("news"),
+                },
+                FilterCondition {
+                    field: "year".to_string(),
+                    operator: ComparisonOperator::GreaterThan,
+                    value: serde_json::json!(2020),
+                },
+            ],
+            operator: FilterOperator::Or,
+        };
+
+        let built = QdrantStore::build_filter(Some(filter), Some("tenant-a"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(built.must.len(), 2);
+        match &built.must[1].condition_one_of {
+            Some(ConditionOneOf::Filter(inner)) => assert_eq!(inner.should.len(), 2),
+            other => panic!("expected nested filter, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_expiry_is_stored_and_filtered() {
+        let expires_at = Utc::now();
+        let tenant_id = crate::domain::value_objects::TenantId::new();
+        let record = VectorRecord::new("doc-1".to_string(), vec![1.0], tenant_id)
+            .unwrap()
+            .with_expires_at(expires_at);
+
+        let point = QdrantStore::to_point(record).unwrap();
+        assert_eq!(
+            point.payload.get(EXPIRES_AT_FIELD).cloned().map(|value| value.into_json()),
+            Some(serde_json::json!(expires_at.timestamp()))
+        );
+
+        let filter = QdrantStore::exclude_expired(None, expires_at);
+        assert!(filter.must.is_empty());
+        assert_eq!(filter.must_not.len(), 1);
+    }
+
+    #[test]
+    fn test_sparse_parts_are_ordered_by_index() {
+        let sparse = HashMap::from([(42, 0.5), (3, 1.5), (17, 0.25)]);
+
+        let (indices, values) = QdrantStore::sparse_parts(&sparse);
+
+        assert_eq!(indices, vec![3, 17, 42]);
+        assert_eq!(values, vec![1.5, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_contains_filter_is_a_text_match() {
+        let contains = |value: serde_json::Value| QdrantStore::convert_condition(FilterCondition {
+            field: "title".to_string(),
+            operator: ComparisonOperator::Contains,
+            value,
+        });
+
+        match contains(serde_json::json!("rust")).unwrap().condition_one_of {
+            Some(ConditionOneOf::Field(field)) => assert_eq!(
+                field.r#match.and_then(|m| m.match_value),
+                Some(qdrant_client::qdrant::r#match::MatchValue::Text("rust".to_string()))
+            ),
+            other => panic!("expected field condition, got {:?}", other),
+        }
+        assert!(contains(serde_json::json!(42)).is_err());
+    }
+
+    #[test]
+    fn test_build_filter_rejects_non_numeric_range() {
+        let filter = SearchFilter {
+            conditions: vec![FilterCondition {
+                field: "year".to_string(),
+                operator: ComparisonOperator::LessThan,
+                value: serde_json::json!("recent"),
+            }],
+            operator: FilterOperator::And,
+        };
+
+        assert!(QdrantStore::build_filter(Some(filter), None).is_err());
+        assert!(QdrantStore::build_filter(None, None).unwrap().is_none());
+    }
+}
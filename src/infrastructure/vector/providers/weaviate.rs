@@ -1,107 +1,820 @@
 use async_trait::async_trait;
-use std::collections::HashMap;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
 
 use crate::domain::value_objects::{
-    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation
+    VectorRecord, SearchQuery, SearchResult, IndexConfig, VectorStats, BatchOperation,
+    DistanceMetric, NamespaceStats, SearchFilter, FilterOperator, FilterCondition,
+    ComparisonOperator
 };
 use crate::error::PlatformError;
 use crate::infrastructure::vector::{VectorStore, VectorStoreConfig, VectorProviderInfo};
-use super::ProviderUtils;
+use super::{ProviderUtils, VectorHttpClient};
 
-/// Weaviate vector store implementation (placeholder)
+/// Property holding the caller's original record id. Weaviate only accepts
+/// UUIDs as object ids, so other ids are mapped to a deterministic UUID and
+/// the original is kept here.
+const RECORD_ID_PROPERTY: &str = "recordId";
+
+/// Date property holding the record's expiry. Weaviate indexes it for
+/// filtering, which is what the batch delete of expired objects relies on.
+const EXPIRES_AT_PROPERTY: &str = "expiresAt";
+
+/// Maximum number of objects sent in a single batch request.
+const BATCH_CHUNK_SIZE: usize = 100;
+
+/// Creates Weaviate classes on demand, remembering which ones exist
+pub struct WeaviateSchemaManager {
+    client: Arc<VectorHttpClient>,
+    base_url: String,
+    headers: HashMap<String, String>,
+    known_classes: RwLock<HashSet<String>>,
+}
+
+impl WeaviateSchemaManager {
+    pub fn new(client: Arc<VectorHttpClient>, base_url: String, headers: HashMap<String, String>) -> Self {
+        Self {
+            client,
+            base_url,
+            headers,
+            known_classes: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Create the class if it does not exist yet. Vectors are always supplied
+    /// by the caller, so the class is created without a vectorizer.
+    pub async fn ensure_class(&self, class_name: &str, metric: &DistanceMetric) -> Result<(), PlatformError> {
+        if self.is_known(class_name) {
+            return Ok(());
+        }
+
+        if self.fetch_class(class_name).await?.is_some() {
+            return Ok(());
+        }
+
+        self.create_class(class_name, metric).await
+    }
+
+    /// Create a class, failing if it already exists
+    pub async fn create_class(&self, class_name: &str, metric: &DistanceMetric) -> Result<(), PlatformError> {
+        let request = WeaviateClass {
+            class: class_name.to_string(),
+            vectorizer: Some("none".to_string()),
+            vector_index_config: Some(json!({ "distance": Self::convert_distance_metric(metric) })),
+            properties: vec![
+                WeaviateProperty {
+                    name: RECORD_ID_PROPERTY.to_string(),
+                    data_type: vec!["text".to_string()],
+                },
+                WeaviateProperty {
+                    name: EXPIRES_AT_PROPERTY.to_string(),
+                    data_type: vec!["date".to_string()],
+                },
+            ],
+        };
+
+        let url = format!("{}/v1/schema", self.base_url);
+        let _created: Value = self.client
+            .post_json(&url, &request, Some(self.headers.clone()))
+            .await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Failed to create Weaviate class '{}': {}", class_name, e)
+            ))?;
+
+        self.remember(class_name);
+        Ok(())
+    }
+
+    /// Property names of a class, or `None` if the class does not exist.
+    /// Always re-reads the schema, since auto-schema adds properties on insert.
+    pub async fn properties(&self, class_name: &str) -> Result<Option<HashSet<String>>, PlatformError> {
+        Ok(self.fetch_class(class_name).await?.map(|class| {
+            class.properties.into_iter().map(|p| p.name).collect()
+        }))
+    }
+
+    /// Drop a class and all of its objects
+    pub async fn delete_class(&self, class_name: &str) -> Result<(), PlatformError> {
+        let url = format!("{}/v1/schema/{}", self.base_url, class_name);
+        self.client.delete_if_exists(&url, Some(self.headers.clone())).await?;
+
+        self.known_classes.write().unwrap().remove(class_name);
+        Ok(())
+    }
+
+    pub async fn list_classes(&self) -> Result<Vec<String>, PlatformError> {
+        let url = format!("{}/v1/schema", self.base_url);
+        let schema: WeaviateSchema = self.client.get(&url, Some(self.headers.clone())).await?;
+
+        Ok(schema.classes.into_iter().map(|c| c.class).collect())
+    }
+
+    async fn fetch_class(&self, class_name: &str) -> Result<Option<WeaviateClass>, PlatformError> {
+        let url = format!("{}/v1/schema/{}", self.base_url, class_name);
+        let class: Option<WeaviateClass> = self.client
+            .get_optional(&url, Some(self.headers.clone()))
+            .await?;
+
+        if class.is_some() {
+            self.remember(class_name);
+        }
+
+        Ok(class)
+    }
+
+    fn is_known(&self, class_name: &str) -> bool {
+        self.known_classes.read().unwrap().contains(class_name)
+    }
+
+    fn remember(&self, class_name: &str) {
+        self.known_classes.write().unwrap().insert(class_name.to_string());
+    }
+
+    fn convert_distance_metric(metric: &DistanceMetric) -> &'static str {
+        match metric {
+            DistanceMetric::Cosine => "cosine",
+            DistanceMetric::Euclidean => "l2-squared",
+            DistanceMetric::DotProduct => "dot",
+        }
+    }
+}
+
+/// Weaviate vector store implementation over the REST v1 and GraphQL APIs.
+/// Each namespace is stored in its own class; records without a namespace go
+/// to the configured `class_name`.
 pub struct WeaviateStore {
+    client: Arc<VectorHttpClient>,
     base_url: String,
     api_key: Option<String>,
     class_name: String,
+    schema: WeaviateSchemaManager,
 }
 
 impl WeaviateStore {
     pub async fn new(config: VectorStoreConfig) -> Result<Self, PlatformError> {
-        ProviderUtils::validate_required_params(&config, &["base_url", "class_name"])?;
-        
-        let base_url = ProviderUtils::get_connection_param(&config, "base_url")?;
-        let class_name = ProviderUtils::get_connection_param(&config, "class_name")?;
+        ProviderUtils::validate_required_params(&config, &["url", "class_name"])?;
+
+        let url = ProviderUtils::get_connection_param(&config, "url")?;
+        let class_name = Self::class_for_namespace(
+            &ProviderUtils::get_connection_param(&config, "class_name")?
+        );
         let api_key = ProviderUtils::get_optional_connection_param(&config, "api_key");
-        
-        Ok(Self {
-            base_url: base_url.trim_end_matches('/').to_string(),
+        let scheme = ProviderUtils::get_optional_connection_param(&config, "scheme")
+            .unwrap_or_else(|| "http".to_string());
+
+        let base_url = Self::build_base_url(&url, &scheme)?;
+
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        if let Some(ref key) = api_key {
+            headers.insert("Authorization".to_string(), format!("Bearer {}", key));
+        }
+
+        let client = Arc::new(ProviderUtils::create_http_client(&config, headers.clone())?);
+        let schema = WeaviateSchemaManager::new(client.clone(), base_url.clone(), headers);
+
+        let store = Self {
+            client,
+            base_url,
             api_key,
             class_name,
+            schema,
+        };
+
+        // Test connection
+        store.test_connection().await?;
+
+        Ok(store)
+    }
+
+    /// Combine `url` and `scheme` into a base URL. A scheme already present
+    /// in `url` wins.
+    fn build_base_url(url: &str, scheme: &str) -> Result<String, PlatformError> {
+        let url = url.trim().trim_end_matches('/');
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Ok(url.to_string());
+        }
+
+        match scheme.to_lowercase().as_str() {
+            "http" | "https" => Ok(format!("{}://{}", scheme.to_lowercase(), url)),
+            other => Err(PlatformError::ValidationError(format!(
+                "Unsupported Weaviate scheme '{}', expected http or https", other
+            ))),
+        }
+    }
+
+    fn build_headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+
+        if let Some(ref api_key) = self.api_key {
+            headers.insert("Authorization".to_string(), format!("Bearer {}", api_key));
+        }
+
+        headers
+    }
+
+    /// Weaviate class names must be PascalCase identifiers, so namespaces such
+    /// as `tenant-a_docs` become `TenantADocs`.
+    fn class_for_namespace(namespace: &str) -> String {
+        let mut class_name: String = namespace
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+
+        if !class_name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            class_name.insert(0, 'C');
+        }
+        class_name
+    }
+
+    fn class_for(&self, namespace: Option<&str>) -> String {
+        match namespace {
+            Some(namespace) => Self::class_for_namespace(namespace),
+            None => self.class_name.clone(),
+        }
+    }
+
+    /// Map an arbitrary record id onto a valid Weaviate object id.
+    fn object_id(id: &str) -> Uuid {
+        Uuid::parse_str(id).unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_OID, id.as_bytes()))
+    }
+
+    fn to_object(&self, record: VectorRecord) -> WeaviateObject {
+        let mut properties: serde_json::Map<String, Value> = record.metadata.into_iter().collect();
+        properties.insert(RECORD_ID_PROPERTY.to_string(), Value::String(record.id.clone()));
+        if let Some(expires_at) = record.expires_at {
+            properties.insert(EXPIRES_AT_PROPERTY.to_string(), Value::String(Self::date_literal(expires_at)));
+        }
+
+        WeaviateObject {
+            class: self.class_for(record.namespace.as_deref()),
+            id: Self::object_id(&record.id),
+            vector: record.vector,
+            properties: Value::Object(properties),
+        }
+    }
+
+    /// RFC 3339 date as Weaviate accepts it
+    fn date_literal(date: DateTime<Utc>) -> String {
+        date.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    /// Whether a GraphQL result object carries an expiry that has passed
+    fn is_expired(object: &Value, now: DateTime<Utc>) -> bool {
+        object
+            .get(EXPIRES_AT_PROPERTY)
+            .and_then(|value| value.as_str())
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Batch delete the expired objects of a class
+    async fn purge_expired(&self, class_name: &str) -> Result<(), PlatformError> {
+        let request = json!({
+            "match": {
+                "class": class_name,
+                "where": {
+                    "path": [EXPIRES_AT_PROPERTY],
+                    "operator": "LessThanEqual",
+                    "valueDate": Self::date_literal(Utc::now()),
+                },
+            },
+            "output": "minimal",
+        });
+
+        let url = format!("{}/v1/batch/objects", self.base_url);
+        self.client.delete_json(&url, &request, Some(self.build_headers())).await
+    }
+
+    /// Build a GraphQL `where` argument as JSON; see `graphql_literal`
+    fn build_where(filter: SearchFilter) -> Result<Option<Value>, PlatformError> {
+        let operands = filter.conditions.into_iter()
+            .map(Self::convert_condition)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(match operands.len() {
+            0 => None,
+            1 => operands.into_iter().next(),
+            _ => Some(json!({
+                "operator": match filter.operator {
+                    FilterOperator::And => "And",
+                    FilterOperator::Or => "Or",
+                },
+                "operands": operands,
+            })),
         })
     }
+
+    fn convert_condition(condition: FilterCondition) -> Result<Value, PlatformError> {
+        let field = condition.field;
+        let value = condition.value;
+
+        let any_of = |operator: &str, combine: &str| -> Result<Value, PlatformError> {
+            let values = value.as_array().ok_or_else(|| PlatformError::ValidationError(
+                format!("In filter on '{}' requires an array value", field)
+            ))?;
+            let operands = values.iter()
+                .map(|v| Self::operand(&field, operator, v))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(json!({ "operator": combine, "operands": operands }))
+        };
+
+        match condition.operator {
+            ComparisonOperator::Equal => Self::operand(&field, "Equal", &value),
+            ComparisonOperator::NotEqual => Self::operand(&field, "NotEqual", &value),
+            ComparisonOperator::GreaterThan => Self::operand(&field, "GreaterThan", &value),
+            ComparisonOperator::GreaterThanOrEqual => Self::operand(&field, "GreaterThanEqual", &value),
+            ComparisonOperator::LessThan => Self::operand(&field, "LessThan", &value),
+            ComparisonOperator::LessThanOrEqual => Self::operand(&field, "LessThanEqual", &value),
+            ComparisonOperator::Contains => {
+                let text = value.as_str().ok_or_else(|| PlatformError::ValidationError(
+                    format!("Contains filter on '{}' requires a string value", field)
+                ))?;
+                Self::operand(&field, "Like", &Value::String(format!("*{}*", text)))
+            },
+            ComparisonOperator::In => any_of("Equal", "Or"),
+            ComparisonOperator::NotIn => any_of("NotEqual", "And"),
+        }
+    }
+
+    fn operand(field: &str, operator: &str, value: &Value) -> Result<Value, PlatformError> {
+        let value_key = match value {
+            Value::String(_) => "valueText",
+            Value::Bool(_) => "valueBoolean",
+            Value::Number(n) if n.is_i64() || n.is_u64() => "valueInt",
+            Value::Number(_) => "valueNumber",
+            _ => return Err(PlatformError::ValidationError(format!(
+                "Unsupported filter value for '{}': {}", field, value
+            ))),
+        };
+
+        let mut operand = serde_json::Map::new();
+        operand.insert("path".to_string(), json!([field]));
+        operand.insert("operator".to_string(), json!(operator));
+        operand.insert(value_key.to_string(), value.clone());
+        Ok(Value::Object(operand))
+    }
+
+    /// Render JSON as a GraphQL input literal: object keys are unquoted and
+    /// `operator` values are emitted as enum names.
+    fn graphql_literal(value: &Value) -> String {
+        match value {
+            Value::Object(map) => {
+                let fields: Vec<String> = map.iter()
+                    .map(|(key, value)| match (key.as_str(), value) {
+                        ("operator", Value::String(op)) => format!("{}: {}", key, op),
+                        _ => format!("{}: {}", key, Self::graphql_literal(value)),
+                    })
+                    .collect();
+                format!("{{{}}}", fields.join(", "))
+            },
+            Value::Array(items) => {
+                let items: Vec<String> = items.iter().map(Self::graphql_literal).collect();
+                format!("[{}]", items.join(", "))
+            },
+            other => other.to_string(),
+        }
+    }
+
+    fn build_get_query(
+        class_name: &str,
+        vector: &[f32],
+        limit: usize,
+        where_filter: Option<&Value>,
+        properties: &[String],
+        include_values: bool,
+    ) -> String {
+        let mut arguments = vec![
+            format!("nearVector: {{vector: {}}}", Self::graphql_literal(&json!(vector))),
+            format!("limit: {}", limit),
+        ];
+        if let Some(where_filter) = where_filter {
+            arguments.push(format!("where: {}", Self::graphql_literal(where_filter)));
+        }
+
+        let additional = if include_values { "id distance vector" } else { "id distance" };
+
+        format!(
+            "{{ Get {{ {}({}) {{ {} _additional {{ {} }} }} }} }}",
+            class_name,
+            arguments.join(", "),
+            properties.join(" "),
+            additional,
+        )
+    }
+
+    fn convert_search_result(object: Value, include_metadata: bool) -> SearchResult {
+        let mut properties: HashMap<String, Value> = match object {
+            Value::Object(map) => map.into_iter().collect(),
+            _ => HashMap::new(),
+        };
+        let additional = properties.remove("_additional").unwrap_or(Value::Null);
+
+        let id = match properties.remove(RECORD_ID_PROPERTY) {
+            Some(Value::String(id)) => id,
+            _ => additional.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        };
+        properties.remove(EXPIRES_AT_PROPERTY);
+        let distance = additional.get("distance").and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+        // Convert distance to similarity score
+        let mut result = SearchResult::new(id, 1.0 - distance as f32);
+        if let Some(vector) = additional.get("vector").and_then(|v| v.as_array()) {
+            result = result.with_vector(
+                vector.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect()
+            );
+        }
+
+        properties.retain(|_, value| !value.is_null());
+        if include_metadata && !properties.is_empty() {
+            result = result.with_metadata(properties);
+        }
+        result
+    }
+
+    async fn object_count(&self, class_name: &str) -> Result<u64, PlatformError> {
+        let url = format!("{}/v1/nodes?output=verbose", self.base_url);
+        let status: WeaviateNodesStatus = self.client.get(&url, Some(self.build_headers())).await?;
+
+        Ok(status.nodes.iter()
+            .flat_map(|node| node.shards.iter().flatten())
+            .filter(|shard| shard.class == class_name)
+            .map(|shard| shard.object_count)
+            .sum())
+    }
 }
 
 #[async_trait]
 impl VectorStore for WeaviateStore {
-    async fn upsert(&self, _record: VectorRecord) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn upsert_batch(&self, _records: Vec<VectorRecord>) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn query(&self, _query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn delete(&self, _ids: Vec<String>, _namespace: Option<String>) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn execute_batch(&self, _operation: BatchOperation) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn create_index(&self, _config: IndexConfig) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
-    }
-    
-    async fn delete_index(&self, _index_name: String) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
-    }
-    
+    async fn upsert(&self, record: VectorRecord) -> Result<(), PlatformError> {
+        self.upsert_batch(vec![record]).await
+    }
+
+    async fn upsert_batch(&self, records: Vec<VectorRecord>) -> Result<(), PlatformError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        // Classes receiving expiring records have the expiry property, so
+        // their expired objects can be purged after the write
+        let expiring_classes: HashSet<String> = records.iter()
+            .filter(|record| record.expires_at.is_some())
+            .map(|record| self.class_for(record.namespace.as_deref()))
+            .collect();
+
+        let objects: Vec<WeaviateObject> = records.into_iter()
+            .map(|record| self.to_object(record))
+            .collect();
+
+        let classes: HashSet<&str> = objects.iter().map(|o| o.class.as_str()).collect();
+        for class_name in classes {
+            self.schema.ensure_class(class_name, &DistanceMetric::Cosine).await?;
+        }
+
+        let url = format!("{}/v1/batch/objects", self.base_url);
+        for chunk in objects.chunks(BATCH_CHUNK_SIZE) {
+            let request = WeaviateBatchRequest { objects: chunk };
+            let response: Vec<Value> = self.client
+                .post_json(&url, &request, Some(self.build_headers()))
+                .await?;
+
+            // The batch endpoint reports per-object failures with a 200 status
+            let errors: Vec<String> = response.iter()
+                .filter_map(|object| object.pointer("/result/errors/error"))
+                .filter_map(|errors| errors.as_array())
+                .flatten()
+                .filter_map(|error| error.get("message").and_then(|m| m.as_str()))
+                .map(|message| message.to_string())
+                .collect();
+            if !errors.is_empty() {
+                return Err(PlatformError::VectorStoreError(
+                    format!("Weaviate batch upsert failed: {}", errors.join("; "))
+                ));
+            }
+        }
+
+        for class_name in expiring_classes {
+            if let Err(e) = self.purge_expired(&class_name).await {
+                tracing::warn!("Failed to purge expired objects from '{}': {}", class_name, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn query(&self, query: SearchQuery) -> Result<Vec<SearchResult>, PlatformError> {
+        let query = ProviderUtils::dense_only(query, "Weaviate");
+        let class_name = self.class_for(query.namespace.as_deref());
+
+        // Nothing has been stored in this namespace yet
+        let Some(properties) = self.schema.properties(&class_name).await? else {
+            return Ok(Vec::new());
+        };
+
+        let has_expiry = properties.contains(EXPIRES_AT_PROPERTY);
+        let mut selected: Vec<String> = if query.include_metadata {
+            properties.into_iter().collect()
+        } else if has_expiry {
+            vec![RECORD_ID_PROPERTY.to_string(), EXPIRES_AT_PROPERTY.to_string()]
+        } else {
+            vec![RECORD_ID_PROPERTY.to_string()]
+        };
+        selected.sort();
+
+        let where_filter = match query.filter {
+            Some(filter) => Self::build_where(filter)?,
+            None => None,
+        };
+
+        let graphql = Self::build_get_query(
+            &class_name,
+            &query.vector,
+            query.top_k,
+            where_filter.as_ref(),
+            &selected,
+            query.include_values,
+        );
+
+        let url = format!("{}/v1/graphql", self.base_url);
+        let response: WeaviateGraphQLResponse = self.client
+            .post_json(&url, &json!({ "query": graphql }), Some(self.build_headers()))
+            .await?;
+
+        if let Some(errors) = response.errors.filter(|errors| !errors.is_empty()) {
+            let messages: Vec<String> = errors.iter()
+                .map(|e| e.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error").to_string())
+                .collect();
+            return Err(PlatformError::VectorStoreError(
+                format!("Weaviate query failed: {}", messages.join("; "))
+            ));
+        }
+
+        let objects = response.data
+            .and_then(|mut data| data.pointer_mut(&format!("/Get/{}", class_name)).map(Value::take))
+            .and_then(|objects| match objects {
+                Value::Array(objects) => Some(objects),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        // Objects without the property cannot be matched by a `where` filter,
+        // so expired objects not yet purged are dropped here instead
+        let now = Utc::now();
+        Ok(objects.into_iter()
+            .filter(|object| !Self::is_expired(object, now))
+            .map(|object| Self::convert_search_result(object, query.include_metadata))
+            .collect())
+    }
+
+    async fn delete(&self, ids: Vec<String>, namespace: Option<String>) -> Result<(), PlatformError> {
+        let class_name = self.class_for(namespace.as_deref());
+
+        for id in ids {
+            let url = format!("{}/v1/objects/{}/{}", self.base_url, class_name, Self::object_id(&id));
+            self.client.delete_if_exists(&url, Some(self.build_headers())).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn execute_batch(&self, operation: BatchOperation) -> Result<(), PlatformError> {
+        // Execute upserts first
+        if !operation.upsert.is_empty() {
+            self.upsert_batch(operation.upsert).await?;
+        }
+
+        // Then execute deletes
+        if !operation.delete.is_empty() {
+            self.delete(operation.delete, None).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_index(&self, config: IndexConfig) -> Result<(), PlatformError> {
+        self.schema
+            .create_class(&Self::class_for_namespace(&config.name), &config.metric)
+            .await
+    }
+
+    async fn delete_index(&self, index_name: String) -> Result<(), PlatformError> {
+        self.schema.delete_class(&Self::class_for_namespace(&index_name)).await
+    }
+
     async fn list_indexes(&self) -> Result<Vec<String>, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
+        self.schema.list_classes().await
     }
-    
-    async fn get_stats(&self, _namespace: Option<String>) -> Result<VectorStats, PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
+
+    async fn get_stats(&self, namespace: Option<String>) -> Result<VectorStats, PlatformError> {
+        let total_vectors = self.object_count(&self.class_name).await?;
+
+        let mut namespace_stats = HashMap::new();
+        if let Some(namespace) = namespace {
+            let vector_count = self.object_count(&Self::class_for_namespace(&namespace)).await?;
+            namespace_stats.insert(namespace, NamespaceStats { vector_count });
+        }
+
+        Ok(VectorStats {
+            total_vectors,
+            // Weaviate does not report vector dimensions in its node status
+            dimension: 0,
+            // Weaviate classes have no fixed capacity
+            index_fullness: 0.0,
+            namespace_stats,
+            index_state: None,
+        })
     }
-    
+
     async fn test_connection(&self) -> Result<(), PlatformError> {
-        Err(PlatformError::VectorStoreError(
-            "Weaviate implementation not yet available".to_string()
-        ))
+        let url = format!("{}/v1/meta", self.base_url);
+        let _meta: Value = self.client.get(&url, Some(self.build_headers())).await
+            .map_err(|e| PlatformError::VectorStoreError(
+                format!("Weaviate connection test failed: {}", e)
+            ))?;
+
+        Ok(())
     }
-    
+
     fn provider_info(&self) -> VectorProviderInfo {
         VectorProviderInfo {
             name: "Weaviate".to_string(),
             version: "1.0".to_string(),
-            supports_namespaces: false,
+            supports_namespaces: true,
             supports_metadata_filtering: true,
-            supports_hybrid_search: true,
+            supports_hybrid_search: false,
+            supports_native_ttl: true,
             max_vector_dimension: 65536,
             max_batch_size: 1000,
         }
     }
-}
\ No newline at end of file
+}
+
+// Weaviate API types
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WeaviateClass {
+    class: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vectorizer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vector_index_config: Option<Value>,
+    #[serde(default)]
+    properties: Vec<WeaviateProperty>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WeaviateProperty {
+    name: String,
+    #[serde(default)]
+    data_type: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeaviateSchema {
+    #[serde(default)]
+    classes: Vec<WeaviateClass>,
+}
+
+#[derive(Debug, Serialize)]
+struct WeaviateObject {
+    class: String,
+    id: Uuid,
+    vector: Vec<f32>,
+    properties: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct WeaviateBatchRequest<'a> {
+    objects: &'a [WeaviateObject],
+}
+
+#[derive(Debug, Deserialize)]
+struct WeaviateGraphQLResponse {
+    data: Option<Value>,
+    errors: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeaviateNodesStatus {
+    #[serde(default)]
+    nodes: Vec<WeaviateNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeaviateNode {
+    shards: Option<Vec<WeaviateShard>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WeaviateShard {
+    class: String,
+    #[serde(default)]
+    object_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_objects_are_detected() {
+        let now = Utc::now();
+        let expired = json!({ "recordId": "a", EXPIRES_AT_PROPERTY: WeaviateStore::date_literal(now) });
+        let live = json!({
+            "recordId": "b",
+            EXPIRES_AT_PROPERTY: WeaviateStore::date_literal(now + chrono::Duration::hours(1)),
+        });
+
+        assert!(WeaviateStore::is_expired(&expired, now));
+        assert!(!WeaviateStore::is_expired(&live, now));
+        assert!(!WeaviateStore::is_expired(&json!({ "recordId": "c" }), now));
+    }
+
+    #[test]
+    fn test_class_for_namespace() {
+        assert_eq!(WeaviateStore::class_for_namespace("documents"), "Documents");
+        assert_eq!(WeaviateStore::class_for_namespace("tenant-a_docs"), "TenantADocs");
+        assert_eq!(WeaviateStore::class_for_namespace("2024 reports"), "C2024Reports");
+    }
+
+    #[test]
+    fn test_build_base_url() {
+        assert_eq!(
+            WeaviateStore::build_base_url("localhost:8080/", "http").unwrap(),
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            WeaviateStore::build_base_url("https://cluster.weaviate.network", "http").unwrap(),
+            "https://cluster.weaviate.network"
+        );
+        assert!(WeaviateStore::build_base_url("localhost:8080", "grpc").is_err());
+    }
+
+    #[test]
+    fn test_build_get_query_with_filter() {
+        let filter = SearchFilter {
+            conditions: vec![
+                FilterCondition {
+                    field: "category".to_string(),
+                    operator: ComparisonOperator::Equal,
+                    value: json!("news"),
+                },
+                FilterCondition {
+                    field: "year".to_string(),
+                    operator: ComparisonOperator::GreaterThan,
+                    value: json!(2020),
+                },
+            ],
+            operator: FilterOperator::And,
+        };
+        let where_filter = WeaviateStore::build_where(filter).unwrap();
+
+        let query = WeaviateStore::build_get_query(
+            "Documents",
+            &[0.5, 1.0],
+            3,
+            where_filter.as_ref(),
+            &["category".to_string(), RECORD_ID_PROPERTY.to_string()],
+            false,
+        );
+
+        assert!(query.starts_with(
+            "{ Get { Documents(nearVector: {vector: [0.5, 1.0]}, limit: 3, where: {"
+        ));
+        assert!(query.contains("operator: And"));
+        assert!(query.contains("path: [\"category\"]"));
+        assert!(query.contains("operator: Equal"));
+        assert!(query.contains("valueText: \"news\""));
+        assert!(query.contains("operator: GreaterThan"));
+        assert!(query.contains("valueInt: 2020"));
+        assert!(query.ends_with("{ category recordId _additional { id distance } } } }"));
+    }
+
+    #[test]
+    fn test_convert_search_result_restores_record_id() {
+        let object = json!({
+            "recordId": "doc-1",
+            "category": "news",
+            "_additional": { "id": WeaviateStore::object_id("doc-1"), "distance": 0.25 }
+        });
+
+        let result = WeaviateStore::convert_search_result(object, true);
+        assert_eq!(result.id, "doc-1");
+        assert_eq!(result.score, 0.75);
+        assert_eq!(result.metadata.unwrap()["category"], "news");
+    }
+}
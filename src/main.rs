@@ -5,8 +5,8 @@ mod application;
 mod infrastructure;
 mod presentation;
 
-use tklog::LOG;
 use std::sync::Arc;
+use tracing_subscriber::EnvFilter;
 use crate::config::AppConfig;
 use crate::infrastructure::database::Database;
 use crate::infrastructure::cache::RedisCache;
@@ -14,8 +14,8 @@ use crate::presentation::server::Server;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    LOG.uselog();
-    // tracing_subscriber::fmt::init();
+    init_tracing();
+    let tracer_provider = infrastructure::llm::telemetry::init_tracer_from_env()?;
 
     // Load configuration
     let config = AppConfig::load()?;
@@ -24,11 +24,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let database = Database::new(&config.database_url).await?;
     
     // Initialize Redis cache
-    let cache = RedisCache::new(&config.redis_url).await?;
+    let cache = RedisCache::with_pool_size(&config.redis_url, config.redis_pool_size).await?;
     
     // Start server
-    let server = Server::new(config, Arc::new(database), Arc::new(cache));
-    server.start().await?;
+    let server = Server::new(config, Arc::new(database), Arc::new(cache))?;
+    let result = server.start().await;
 
+    if let Some(tracer_provider) = tracer_provider {
+        if let Err(e) = tracer_provider.shutdown() {
+            tracing::warn!("Failed to flush LLM traces: {}", e);
+        }
+    }
+
+    result?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Human-readable logs by default; `APP_LOG_FORMAT=json` emits one JSON
+/// object per event for log shippers. `RUST_LOG` overrides `APP_LOGGING_LEVEL`.
+fn init_tracing() {
+    dotenvy::dotenv().ok();
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(std::env::var("APP_LOGGING_LEVEL").unwrap_or_else(|_| "info".to_string()))
+    });
+
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match std::env::var("APP_LOG_FORMAT").as_deref() {
+        Ok("json") => builder.json().with_current_span(true).with_span_list(false).init(),
+        _ => builder.init(),
+    }
+}
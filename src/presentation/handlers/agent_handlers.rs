@@ -1,23 +1,29 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
     application::{
-        dto::agent_dto::*,
-        services::AgentApplicationService,
+        dto::{agent_dto::*, APIKeyAuthContext},
+        services::{AgentApplicationService, AgentImportExportService, ExportFormat},
+    },
+    domain::{
+        services::ChatStreamEvent,
+        value_objects::{APIKeyId, AgentId, ConfigId, MCPToolId, FlowId, UserId},
+    },
+    error::{PlatformError, Result},
+    presentation::{
+        extractors::AuthenticatedUser,
+        handlers::sse_formatter::{last_event_id, StreamingSSEFormatter},
     },
-    domain::value_objects::{AgentId, ConfigId, MCPToolId, FlowId},
-    error::Result,
-    presentation::extractors::AuthenticatedUser,
 };
 
-use crate::application::dto::agent_dto::{AgentChatRequest, CompleteInterviewRequest};
+use crate::application::dto::agent_dto::{AgentChatRequest, AgentPreviewRequest, CompleteInterviewRequest};
 
 // ============================================================================
 // CRUD Handlers
@@ -43,6 +49,20 @@ pub async fn get_agent(
     Ok(Json(agent))
 }
 
+/// Get the agent of the calling API key, which must be scoped to exactly one agent
+pub async fn get_api_key_agent(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    Extension(auth_context): Extension<APIKeyAuthContext>,
+) -> Result<impl IntoResponse> {
+    let agent = service
+        .get_agent_by_api_key(
+            APIKeyId(auth_context.api_key_id),
+            UserId::from_uuid(auth_context.user_id),
+        )
+        .await?;
+    Ok(Json(agent))
+}
+
 /// Update agent
 pub async fn update_agent(
     State(service): State<Arc<dyn AgentApplicationService>>,
@@ -76,7 +96,9 @@ pub async fn list_agents(
     };
 
     let include_fired = query.include_fired.unwrap_or(false);
-    let response = service.list_agents(user.tenant_id, user.user_id, params, include_fired).await?;
+    let response = service
+        .list_agents(user.tenant_id, user.user_id, params, include_fired, query.keyword)
+        .await?;
     Ok(Json(response))
 }
 
@@ -137,6 +159,18 @@ pub async fn fire_agent(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// List the copied and employed instances of an agent
+pub async fn list_agent_copies(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let copies = service
+        .list_agent_copies(AgentId::from_uuid(agent_id), user.user_id)
+        .await?;
+    Ok(Json(copies))
+}
+
 /// List employed agents
 pub async fn list_employed_agents(
     State(service): State<Arc<dyn AgentApplicationService>>,
@@ -157,13 +191,17 @@ pub async fn list_employed_agents(
 // Allocation Management Handlers
 // ============================================================================
 
-/// Allocate an agent
+/// Allocate an agent; the body may set `expires_at`
 pub async fn allocate_agent(
     State(service): State<Arc<dyn AgentApplicationService>>,
     user: AuthenticatedUser,
     Path(agent_id): Path<Uuid>,
+    request: Option<Json<AllocateAgentDto>>,
 ) -> Result<impl IntoResponse> {
-    service.allocate_agent(AgentId::from_uuid(agent_id), user.user_id).await?;
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    service
+        .allocate_agent(AgentId::from_uuid(agent_id), user.user_id, request)
+        .await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -312,50 +350,98 @@ pub async fn chat_with_agent(
     Ok((StatusCode::OK, Json(response)))
 }
 
-/// Chat with an agent (SSE streaming)
-pub async fn chat_with_agent_stream(
+/// Try out an agent without creating a session or recording usage
+pub async fn preview_agent(
     State(service): State<Arc<dyn AgentApplicationService>>,
     user: AuthenticatedUser,
     Path(agent_id): Path<Uuid>,
-    Json(req): Json<AgentChatRequest>,
+    Json(req): Json<AgentPreviewRequest>,
 ) -> Result<impl IntoResponse> {
-    use axum::response::sse::{Event, Sse};
-    use futures::stream::StreamExt;
-
-    let session_id = req.session_id.map(crate::domain::value_objects::SessionId);
-    
-    let stream = service.chat_stream(
+    let response = service.preview_chat(
         AgentId::from_uuid(agent_id),
         req.message,
-        session_id,
         user.user_id,
         user.tenant_id,
     ).await?;
 
-    // Transform the stream into SSE events
-    let sse_stream = stream.map(|chunk_result| {
-        let event = match chunk_result {
-            Ok(chunk) => {
-                let json = serde_json::to_string(&chunk).unwrap_or_else(|_| "{}".to_string());
-                Event::default().data(json)
-            }
-            Err(e) => {
-                let error_chunk = crate::application::dto::agent_dto::AgentChatStreamChunk {
-                    chunk_type: "error".to_string(),
-                    content: None,
-                    reasoning_content: None,
-                    session_id: None,
-                    message_id: None,
-                    reply_id: None,
-                    metadata: None,
-                    finish_reason: None,
-                    error: Some(format!("{}", e)),
-                };
-                let json = serde_json::to_string(&error_chunk).unwrap_or_else(|_| "{}".to_string());
-                Event::default().data(json)
-            }
-        };
-        Ok::<_, std::convert::Infallible>(event)
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// How often a resumed stream checks for events still being generated
+const STREAM_RESUME_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// A resumed stream ends after this long without new events
+const STREAM_RESUME_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Chat with an agent (SSE streaming)
+///
+/// Each event is sent with an id. A client that reconnects with a
+/// `Last-Event-ID` header and the same `session_id` gets the events it
+/// missed instead of a new reply.
+pub async fn chat_with_agent_stream(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<AgentChatRequest>,
+) -> Result<impl IntoResponse> {
+    use axum::response::sse::Sse;
+    use futures::stream::{BoxStream, StreamExt};
+
+    let session_id = req.session_id.map(crate::domain::value_objects::SessionId);
+
+    let events: BoxStream<'static, ChatStreamEvent> = match last_event_id(&headers) {
+        Some(last_event_id) => {
+            let session_id = session_id.ok_or_else(|| {
+                PlatformError::ValidationError("session_id is required to resume a stream".to_string())
+            })?;
+            resume_chat_stream(service, session_id, last_event_id, user.user_id, user.tenant_id)
+                .await?
+                .boxed()
+        }
+        None => {
+            let stream = service.chat_stream(
+                AgentId::from_uuid(agent_id),
+                req.message,
+                session_id,
+                user.user_id,
+                user.tenant_id,
+            ).await?;
+
+            // Drive the stream in its own task so the reply is still generated,
+            // saved and buffered when the client disconnects
+            let (tx, rx) = futures::channel::mpsc::unbounded();
+            tokio::spawn(async move {
+                let mut stream = stream;
+                let mut formatter = StreamingSSEFormatter::new();
+                let mut stream_session_id = session_id;
+
+                while let Some(chunk_result) = stream.next().await {
+                    let event = match chunk_result {
+                        Ok(chunk) => {
+                            if let Some(id) = chunk.session_id {
+                                stream_session_id = Some(crate::domain::value_objects::SessionId(id));
+                            }
+                            formatter.format(&chunk)
+                        }
+                        Err(e) => formatter.format_error(&e),
+                    };
+
+                    if let Some(session_id) = stream_session_id {
+                        if let Err(e) = service.buffer_chat_stream_event(session_id, event.clone()).await {
+                            tracing::warn!("Failed to buffer chat stream event: {}", e);
+                        }
+                    }
+                    let _ = tx.unbounded_send(event);
+                }
+            });
+
+            rx.boxed()
+        }
+    };
+
+    let sse_stream = events.map(|event| {
+        Ok::<_, std::convert::Infallible>(StreamingSSEFormatter::to_sse_event(&event))
     });
 
     Ok(Sse::new(sse_stream).keep_alive(
@@ -365,6 +451,110 @@ pub async fn chat_with_agent_stream(
     ))
 }
 
+/// Replay the events after `last_event_id`, then keep following the stream
+/// until it ends in case the reply is still being generated
+async fn resume_chat_stream(
+    service: Arc<dyn AgentApplicationService>,
+    session_id: crate::domain::value_objects::SessionId,
+    last_event_id: u64,
+    user_id: UserId,
+    tenant_id: crate::domain::value_objects::TenantId,
+) -> Result<impl futures::Stream<Item = ChatStreamEvent>> {
+    use futures::stream::{self, StreamExt};
+
+    // Checked up front so an unknown session is an HTTP error, not an empty stream
+    let replayed = service
+        .replay_chat_stream(session_id, last_event_id, user_id, tenant_id)
+        .await?;
+
+    let last_id = replayed.last().map_or(last_event_id, |event| event.id);
+    let finished = replayed.last().is_some_and(ChatStreamEvent::is_terminal);
+
+    let follow = stream::unfold(
+        (last_id, finished, std::time::Duration::ZERO),
+        move |(last_id, finished, idle)| {
+            let service = service.clone();
+            async move {
+                if finished || idle >= STREAM_RESUME_IDLE_TIMEOUT {
+                    return None;
+                }
+                tokio::time::sleep(STREAM_RESUME_POLL_INTERVAL).await;
+
+                let events = match service
+                    .replay_chat_stream(session_id, last_id, user_id, tenant_id)
+                    .await
+                {
+                    Ok(events) => events,
+                    Err(e) => {
+                        tracing::warn!("Failed to replay chat stream {}: {}", session_id.0, e);
+                        return None;
+                    }
+                };
+
+                let state = match events.last() {
+                    Some(event) => (event.id, event.is_terminal(), std::time::Duration::ZERO),
+                    None => (last_id, false, idle + STREAM_RESUME_POLL_INTERVAL),
+                };
+                Some((stream::iter(events), state))
+            }
+        },
+    )
+    .flatten();
+
+    Ok(stream::iter(replayed).chain(follow))
+}
+
+// ============================================================================
+// Preset Question Handlers
+// ============================================================================
+
+/// Get the preset questions of an agent
+pub async fn get_preset_questions(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let questions = service.get_preset_questions(
+        AgentId::from_uuid(agent_id),
+        user.user_id,
+        user.tenant_id,
+    ).await?;
+
+    Ok(Json(questions))
+}
+
+/// Regenerate the preset questions of an agent from its system prompt
+pub async fn regenerate_preset_questions(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let questions = service.regenerate_preset_questions(
+        AgentId::from_uuid(agent_id),
+        user.user_id,
+        user.tenant_id,
+    ).await?;
+
+    Ok(Json(questions))
+}
+
+/// Suggest follow-up questions for a chat session
+pub async fn suggest_questions(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+    Json(req): Json<SuggestQuestionsRequest>,
+) -> Result<impl IntoResponse> {
+    let suggestions = service.suggest_questions(
+        AgentId::from_uuid(agent_id),
+        crate::domain::value_objects::SessionId(req.session_id),
+        user.user_id,
+        user.tenant_id,
+    ).await?;
+
+    Ok(Json(suggestions))
+}
+
 // ============================================================================
 // Statistics Handler
 // ============================================================================
@@ -453,12 +643,109 @@ pub async fn publish_agent(
     Ok(StatusCode::NO_CONTENT)
 }
 
-/// Unpublish an agent
+/// Unpublish an agent; `?fire_copies=true` also fires its employed copies
 pub async fn unpublish_agent(
     State(service): State<Arc<dyn AgentApplicationService>>,
     user: AuthenticatedUser,
     Path(agent_id): Path<Uuid>,
+    Query(query): Query<UnpublishAgentQuery>,
 ) -> Result<impl IntoResponse> {
-    service.unpublish_agent(AgentId::from_uuid(agent_id), user.user_id).await?;
+    service
+        .unpublish_agent(AgentId::from_uuid(agent_id), user.user_id, query.fire_copies)
+        .await?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+// ============================================================================
+// Import/Export Handlers
+// ============================================================================
+
+/// Export an agent; `?format=zip` bundles its avatar with the document
+pub async fn export_agent(
+    State(service): State<Arc<dyn AgentImportExportService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+    Query(query): Query<ExportAgentQuery>,
+) -> Result<impl IntoResponse> {
+    let format = match query.format {
+        Some(format) => format.parse::<ExportFormat>()?,
+        None => ExportFormat::OpenAgents,
+    };
+
+    let data = service
+        .export(AgentId::from_uuid(agent_id), format, user.tenant_id, user.user_id)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"agent-{}.{}\"", agent_id, format.file_extension()),
+            ),
+        ],
+        data,
+    ))
+}
+
+/// Import an agent from the `file` field of a multipart form. The format is
+/// taken from the `format` field, or from the file name when absent.
+pub async fn import_agent(
+    State(service): State<Arc<dyn AgentImportExportService>>,
+    user: AuthenticatedUser,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse> {
+    let mut file = None;
+    let mut format = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        PlatformError::ValidationError(format!("Failed to read multipart field: {}", e))
+    })? {
+        match field.name().unwrap_or("") {
+            "file" => {
+                let is_zip = field
+                    .file_name()
+                    .map(|name| name.to_ascii_lowercase().ends_with(".zip"))
+                    .unwrap_or(false)
+                    || field.content_type() == Some(ExportFormat::Zip.content_type());
+                let data = field.bytes().await.map_err(|e| {
+                    PlatformError::ValidationError(format!("Failed to read file data: {}", e))
+                })?;
+                file = Some((data.to_vec(), is_zip));
+            }
+            "format" => {
+                let value = field.text().await.map_err(|e| {
+                    PlatformError::ValidationError(format!("Failed to read format: {}", e))
+                })?;
+                format = Some(value.trim().parse::<ExportFormat>()?);
+            }
+            _ => {}
+        }
+    }
+
+    let (data, is_zip) = file.ok_or_else(|| {
+        PlatformError::ValidationError("No file field found in multipart form".to_string())
+    })?;
+    let format = format.unwrap_or(if is_zip { ExportFormat::Zip } else { ExportFormat::OpenAgents });
+
+    let agent = service.import(data, format, user.tenant_id, user.user_id).await?;
+    Ok((StatusCode::CREATED, Json(agent)))
+}
+
+// ============================================================================
+// Flow Generation Handlers
+// ============================================================================
+
+/// Generate a starter flow from a natural language description
+pub async fn generate_flow(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    user: AuthenticatedUser,
+    Json(req): Json<GenerateFlowRequest>,
+) -> Result<impl IntoResponse> {
+    let flow = service.generate_flow_from_description(
+        req.description,
+        user.tenant_id,
+        user.user_id,
+    ).await?;
+    Ok((StatusCode::CREATED, Json(flow)))
+}
@@ -0,0 +1,201 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, State,
+    },
+    response::IntoResponse,
+};
+use futures::{SinkExt, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dto::agent_dto::{AgentChatRequest, AgentChatStreamChunk},
+        services::AgentApplicationService,
+    },
+    domain::value_objects::{AgentId, SessionId},
+    presentation::extractors::AuthenticatedUser,
+};
+
+/// Interval between server heartbeat pings
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Chat with an agent over a WebSocket.
+///
+/// The client sends one `AgentChatRequest` JSON frame, then receives
+/// `AgentChatStreamChunk` JSON frames until the reply finishes. Closing the
+/// socket cancels the stream.
+pub async fn chat_with_agent_ws(
+    State(service): State<Arc<dyn AgentApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_chat_socket(socket, service, user, AgentId::from_uuid(agent_id)))
+}
+
+async fn handle_chat_socket(
+    socket: WebSocket,
+    service: Arc<dyn AgentApplicationService>,
+    user: AuthenticatedUser,
+    agent_id: AgentId,
+) {
+    let (mut sender, mut receiver) = socket.split();
+
+    let request = match read_chat_request(&mut receiver, &mut sender).await {
+        Some(request) => request,
+        None => return,
+    };
+
+    let mut stream = match service
+        .chat_stream(
+            agent_id,
+            request.message,
+            request.session_id.map(SessionId),
+            user.user_id,
+            user.tenant_id,
+        )
+        .await
+    {
+        Ok(stream) => stream,
+        Err(e) => {
+            let _ = send_chunk(&mut sender, &error_chunk(e.to_string())).await;
+            let _ = sender.send(Message::Close(None)).await;
+            return;
+        }
+    };
+
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    // The first tick completes immediately
+    heartbeat.tick().await;
+    let mut awaiting_pong = false;
+
+    loop {
+        tokio::select! {
+            chunk = stream.next() => {
+                let chunk = match chunk {
+                    Some(Ok(chunk)) => chunk,
+                    Some(Err(e)) => error_chunk(e.to_string()),
+                    None => break,
+                };
+                if send_chunk(&mut sender, &chunk).await.is_err() {
+                    tracing::debug!("WebSocket client went away, cancelling chat stream");
+                    return;
+                }
+            }
+            frame = receiver.next() => {
+                match frame {
+                    Some(Ok(Message::Pong(_))) => awaiting_pong = false,
+                    Some(Ok(Message::Text(text))) if is_ping(&text) => {
+                        if sender.send(Message::Text(r#"{"type":"pong"}"#.into())).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                        tracing::debug!("WebSocket closed by client, cancelling chat stream");
+                        return;
+                    }
+                    // Pings are answered by the protocol layer; other frames are ignored
+                    Some(Ok(_)) => {}
+                }
+            }
+            _ = heartbeat.tick() => {
+                if awaiting_pong {
+                    tracing::debug!("WebSocket heartbeat timed out, cancelling chat stream");
+                    return;
+                }
+                if sender.send(Message::Ping(Vec::new().into())).await.is_err() {
+                    return;
+                }
+                awaiting_pong = true;
+            }
+        }
+    }
+
+    let _ = sender.send(Message::Close(None)).await;
+}
+
+/// Wait for the initial chat request frame. Sends an error chunk and returns
+/// `None` if the client disconnects or the frame is not a valid request.
+async fn read_chat_request(
+    receiver: &mut futures::stream::SplitStream<WebSocket>,
+    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+) -> Option<AgentChatRequest> {
+    while let Some(frame) = receiver.next().await {
+        let text = match frame {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => return None,
+            Ok(_) => continue,
+        };
+
+        if is_ping(&text) {
+            if sender.send(Message::Text(r#"{"type":"pong"}"#.into())).await.is_err() {
+                return None;
+            }
+            continue;
+        }
+
+        return match serde_json::from_str::<AgentChatRequest>(&text) {
+            Ok(request) => Some(request),
+            Err(e) => {
+                let _ = send_chunk(sender, &error_chunk(format!("Invalid chat request: {}", e))).await;
+                let _ = sender.send(Message::Close(None)).await;
+                None
+            }
+        };
+    }
+
+    None
+}
+
+/// Application-level heartbeat for clients that cannot send ping frames
+fn is_ping(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|value| value.get("type").and_then(|t| t.as_str()).map(|t| t == "ping"))
+        .unwrap_or(false)
+}
+
+async fn send_chunk(
+    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    chunk: &AgentChatStreamChunk,
+) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(chunk).unwrap_or_else(|_| "{}".to_string());
+    sender.send(Message::Text(json.into())).await
+}
+
+fn error_chunk(error: String) -> AgentChatStreamChunk {
+    AgentChatStreamChunk {
+        chunk_type: "error".to_string(),
+        content: None,
+        reasoning_content: None,
+        session_id: None,
+        message_id: None,
+        reply_id: None,
+        metadata: None,
+        finish_reason: None,
+        error: Some(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ping() {
+        assert!(is_ping(r#"{"type":"ping"}"#));
+        assert!(!is_ping(r#"{"type":"pong"}"#));
+        assert!(!is_ping(r#"{"message":"ping"}"#));
+        assert!(!is_ping("ping"));
+    }
+
+    #[test]
+    fn test_error_chunk_serializes_as_error_type() {
+        let json = serde_json::to_value(error_chunk("boom".to_string())).unwrap();
+        assert_eq!(json["type"], "error");
+        assert_eq!(json["error"], "boom");
+    }
+}
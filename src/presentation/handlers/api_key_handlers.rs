@@ -134,6 +134,43 @@ pub async fn update_api_key(
     Ok(Json(api_key))
 }
 
+/// Rotate an API key
+///
+/// POST /api/v1/api-keys/:id/rotate
+///
+/// Generates a new token for the API key and returns it. The permission
+/// scope is kept and the old token stops working immediately.
+/// The new token is only shown once.
+pub async fn rotate_api_key(
+    State(service): State<Arc<APIKeyApplicationService>>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let rotated = service
+        .rotate_api_key(APIKeyId::from_uuid(id), user.user_id, None)
+        .await?;
+
+    Ok(Json(rotated))
+}
+
+/// Get the daily usage of an API key
+///
+/// GET /api/v1/api-keys/:id/usage-history
+///
+/// Returns requests and tokens per UTC day for the last 30 days, oldest
+/// first. Usage is written to the database about once a minute.
+pub async fn get_api_key_usage_history(
+    State(service): State<Arc<APIKeyApplicationService>>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let history = service
+        .get_usage_history(APIKeyId::from_uuid(id), user.user_id)
+        .await?;
+
+    Ok(Json(history))
+}
+
 /// Delete an API key
 ///
 /// DELETE /api/v1/api-keys/:id
@@ -217,6 +254,12 @@ fn validate_create_request(request: &CreateAPIKeyRequest) -> Result<()> {
         }
     }
 
+    if request.rate_limit_rpm == Some(0) {
+        return Err(PlatformError::ValidationError(
+            "Rate limit must be at least 1 request per minute".to_string(),
+        ));
+    }
+
     // Validate permission scope (at least one resource must be specified)
     let scope = &request.permission_scope;
     if scope.agent_ids.is_empty()
@@ -259,7 +302,11 @@ fn validate_update_request(request: &UpdateAPIKeyRequest) -> Result<()> {
     }
 
     // At least one field must be provided
-    if request.name.is_none() && request.enabled.is_none() && request.expires_at.is_none() {
+    if request.name.is_none()
+        && request.enabled.is_none()
+        && request.expires_at.is_none()
+        && request.rate_limit_rpm.is_none()
+    {
         return Err(PlatformError::ValidationError(
             "At least one field must be provided for update".to_string(),
         ));
@@ -286,6 +333,7 @@ mod tests {
                 vector_store_ids: vec![],
             },
             expires_at: Some(Utc::now() + Duration::days(30)),
+            rate_limit_rpm: None,
         };
 
         assert!(validate_create_request(&request).is_ok());
@@ -302,6 +350,7 @@ mod tests {
                 vector_store_ids: vec![],
             },
             expires_at: None,
+            rate_limit_rpm: None,
         };
 
         assert!(validate_create_request(&request).is_err());
@@ -318,6 +367,7 @@ mod tests {
                 vector_store_ids: vec![],
             },
             expires_at: None,
+            rate_limit_rpm: None,
         };
 
         assert!(validate_create_request(&request).is_err());
@@ -334,6 +384,7 @@ mod tests {
                 vector_store_ids: vec![],
             },
             expires_at: Some(Utc::now() - Duration::days(1)),
+            rate_limit_rpm: None,
         };
 
         assert!(validate_create_request(&request).is_err());
@@ -350,6 +401,7 @@ mod tests {
                 vector_store_ids: vec![],
             },
             expires_at: None,
+            rate_limit_rpm: None,
         };
 
         assert!(validate_create_request(&request).is_err());
@@ -361,6 +413,7 @@ mod tests {
             name: Some("Updated Name".to_string()),
             enabled: Some(false),
             expires_at: Some(Utc::now() + Duration::days(30)),
+            rate_limit_rpm: None,
         };
 
         assert!(validate_update_request(&request).is_ok());
@@ -372,6 +425,7 @@ mod tests {
             name: Some("".to_string()),
             enabled: None,
             expires_at: None,
+            rate_limit_rpm: None,
         };
 
         assert!(validate_update_request(&request).is_err());
@@ -383,17 +437,44 @@ mod tests {
             name: None,
             enabled: None,
             expires_at: None,
+            rate_limit_rpm: None,
         };
 
         assert!(validate_update_request(&request).is_err());
     }
 
+    #[test]
+    fn test_validate_rate_limit() {
+        let request = CreateAPIKeyRequest {
+            name: "Test API Key".to_string(),
+            permission_scope: PermissionScopeDTO {
+                agent_ids: vec![Uuid::new_v4()],
+                flow_ids: vec![],
+                mcp_tool_ids: vec![],
+                vector_store_ids: vec![],
+            },
+            expires_at: None,
+            rate_limit_rpm: Some(0),
+        };
+        assert!(validate_create_request(&request).is_err());
+
+        // 0 removes the limit of an existing key
+        let request = UpdateAPIKeyRequest {
+            name: None,
+            enabled: None,
+            expires_at: None,
+            rate_limit_rpm: Some(0),
+        };
+        assert!(validate_update_request(&request).is_ok());
+    }
+
     #[test]
     fn test_validate_update_request_expired_date() {
         let request = UpdateAPIKeyRequest {
             name: None,
             enabled: None,
             expires_at: Some(Utc::now() - Duration::days(1)),
+            rate_limit_rpm: None,
         };
 
         assert!(validate_update_request(&request).is_err());
@@ -11,8 +11,8 @@ use crate::{
     application::{
         services::AuthApplicationService,
         dto::{
-            LoginRequest, RefreshTokenRequest, LogoutRequest, 
-            ChangePasswordRequest, AuthContext
+            LoginRequest, RefreshRequestBody, LogoutRequest,
+            ChangePasswordRequest, AuthContext, RevokeTokenRequest, RevokeTokenResponse
         },
     },
     error::PlatformError,
@@ -47,7 +47,8 @@ pub async fn login_handler(
     Ok((StatusCode::OK, Json(login_response)).into_response())
 }
 
-/// Refresh token handler
+/// Refresh token handler. Exchanges a refresh token for a new access token,
+/// or renews a still valid access token for clients without refresh tokens.
 pub async fn refresh_token_handler(
     State(auth_service): State<Arc<dyn AuthApplicationService>>,
     request: Request,
@@ -59,17 +60,48 @@ pub async fn refresh_token_handler(
     let body_bytes = axum::body::to_bytes(request.into_body(), usize::MAX).await
         .map_err(|e| PlatformError::ValidationError(format!("Failed to read request body: {}", e)))?;
     
-    let refresh_request: RefreshTokenRequest = serde_json::from_slice(&body_bytes)
+    let refresh_request: RefreshRequestBody = serde_json::from_slice(&body_bytes)
         .map_err(|e| PlatformError::ValidationError(format!("Invalid JSON: {}", e)))?;
 
-    // Refresh token
-    let (refresh_response, _refresh_event) = auth_service
-        .refresh_token(refresh_request, ip_address)
-        .await?;
+    match refresh_request {
+        RefreshRequestBody::RefreshToken(request) => {
+            let token_pair = auth_service
+                .refresh_access_token(&request.refresh_token)
+                .await?;
+
+            Ok((StatusCode::OK, Json(token_pair)).into_response())
+        }
+        RefreshRequestBody::AccessToken(request) => {
+            // Refresh token
+            let (refresh_response, _refresh_event) = auth_service
+                .refresh_token(request, ip_address)
+                .await?;
 
-    // TODO: Publish refresh_event to event bus
+            // TODO: Publish refresh_event to event bus
+
+            Ok((StatusCode::OK, Json(refresh_response)).into_response())
+        }
+    }
+}
 
-    Ok((StatusCode::OK, Json(refresh_response)).into_response())
+/// Revoke one refresh token of the current user, or all of them when no
+/// token ID is given
+pub async fn revoke_token_handler(
+    State(auth_service): State<Arc<dyn AuthApplicationService>>,
+    axum::Extension(auth_context): axum::Extension<AuthContext>,
+    Json(revoke_request): Json<RevokeTokenRequest>,
+) -> Result<Response, PlatformError> {
+    let revoked = match revoke_request.token_id {
+        Some(token_id) => {
+            auth_service
+                .revoke_refresh_token(token_id, auth_context.user_id)
+                .await?;
+            1
+        }
+        None => auth_service.revoke_all_tokens(auth_context.user_id).await?,
+    };
+
+    Ok((StatusCode::OK, Json(RevokeTokenResponse { revoked })).into_response())
 }
 
 /// Logout handler
@@ -16,7 +16,7 @@ mod tests {
             dto::{
                 LoginRequest, LoginResponse, UserInfo, RefreshTokenRequest, 
                 RefreshTokenResponse, LogoutRequest, LogoutResponse,
-                ChangePasswordRequest, ChangePasswordResponse, AuthContext
+                ChangePasswordRequest, ChangePasswordResponse, AuthContext, TokenPairDto
             },
         },
         domain::events::{UserAuthenticatedEvent, TokenRefreshedEvent, UserLoggedOutEvent, PasswordChangedEvent},
@@ -72,6 +72,7 @@ mod tests {
                 created_at: chrono::Utc::now(),
             },
             expires_at: chrono::Utc::now() + chrono::Duration::hours(24),
+            refresh_token: None,
         };
 
         let auth_event = UserAuthenticatedEvent::new(
@@ -178,6 +179,40 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_refresh_token_handler_with_refresh_token() {
+        let mut auth_service = MockAuthApplicationService::new();
+
+        let token_pair = TokenPairDto {
+            access_token: "new_access_token".to_string(),
+            expires_at: chrono::Utc::now() + chrono::Duration::minutes(15),
+            refresh_token: None,
+        };
+
+        auth_service
+            .expect_refresh_access_token()
+            .with(eq("rt_refresh_token"))
+            .times(1)
+            .returning(move |_| Ok(token_pair.clone()));
+        auth_service.expect_refresh_token().never();
+
+        let app = create_auth_routes(Arc::new(auth_service));
+
+        let request_body = json!({
+            "refresh_token": "rt_refresh_token"
+        });
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/auth/refresh")
+            .header("content-type", "application/json")
+            .body(Body::from(request_body.to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_logout_handler_success() {
         let mut auth_service = MockAuthApplicationService::new();
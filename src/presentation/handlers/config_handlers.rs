@@ -11,12 +11,13 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
+    application::dto::TestConnectionResultDto,
     application::services::{LLMApplicationService, VectorApplicationService},
     domain::value_objects::{
         ConfigId, ModelConfig, ModelCredentials, ModelParameters, ModelProvider,
     },
     domain::LLMConfig,
-    error::Result,
+    error::{PlatformError, Result},
     infrastructure::vector::VectorProvider,
     presentation::extractors::AuthenticatedUser,
 };
@@ -30,6 +31,9 @@ pub struct CreateLLMConfigRequest {
     pub parameters: Option<Value>,
     pub credentials: Option<Value>,
     pub description: Option<String>,
+    /// Call the provider before saving and reject the config if it fails
+    #[serde(default)]
+    pub test_connection: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,6 +43,9 @@ pub struct UpdateLLMConfigRequest {
     pub parameters: Option<Value>,
     pub credentials: Option<Value>,
     pub description: Option<String>,
+    /// Call the provider with the changed model settings before saving
+    #[serde(default)]
+    pub test_connection: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -163,7 +170,13 @@ pub async fn create_llm_config(
     };
 
     let config = service
-        .create_config(user.tenant_id, req.name, model_config, req.description)
+        .create_config(
+            user.tenant_id,
+            req.name,
+            model_config,
+            req.description,
+            req.test_connection,
+        )
         .await?;
 
     Ok((StatusCode::CREATED, Json(llm_config_to_response(&config))))
@@ -267,6 +280,7 @@ pub async fn update_llm_config(
             req.name,
             model_config,
             req.description,
+            req.test_connection,
         )
         .await?;
 
@@ -358,6 +372,30 @@ pub async fn test_llm_connection(
     }
 }
 
+/// Check that a saved config still reaches its provider. Provider failures
+/// are reported in the result rather than as an error status.
+pub async fn retest_llm_config(
+    State(service): State<Arc<dyn LLMApplicationService>>,
+    user: AuthenticatedUser,
+    Path(config_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = match service
+        .test_connection(ConfigId(config_id), user.tenant_id)
+        .await
+    {
+        Ok(result) => TestConnectionResultDto::from(result),
+        Err(e @ (PlatformError::NotFound(_) | PlatformError::Forbidden(_))) => return Err(e),
+        Err(e) => TestConnectionResultDto {
+            success: false,
+            response_time_ms: 0,
+            model_info: None,
+            error_message: Some(e.to_string()),
+        },
+    };
+
+    Ok(Json(result))
+}
+
 pub async fn get_available_models(
     State(service): State<Arc<dyn LLMApplicationService>>,
     _user: AuthenticatedUser,
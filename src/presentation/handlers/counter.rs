@@ -237,7 +237,7 @@ impl ServerHandler for Counter {
         if let Some(http_request_part) = context.extensions.get::<axum::http::request::Parts>() {
             let initialize_headers = &http_request_part.headers;
             let initialize_uri = &http_request_part.uri;
-            log::info!("initialize from http server: {}", initialize_uri);
+            tracing::info!("initialize from http server: {}", initialize_uri);
         }
         Ok(self.get_info())
     }
@@ -8,13 +8,36 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::dto::{
-    ExecutionDetailsResponse, ExecutionDto, ExecutionMetricsDto, ExecutionStepDto,
-    QueryExecutionsRequest, QueryExecutionsResponse,
+    CursorPaginatedResponse, ExecutionDetailDto, ExecutionDto, ExecutionListFilter,
+    ExecutionMetricsDto, ExecutionStepDto, ExecutionSummaryDto, QueryExecutionsRequest,
+    QueryExecutionsResponse,
 };
 use crate::application::services::ExecutionHistoryApplicationService;
 use crate::error::{PlatformError, Result};
 use crate::presentation::extractors::AuthenticatedUser;
 
+/// List executions newest first, paginated by cursor
+pub async fn list_executions(
+    State(service): State<Arc<ExecutionHistoryApplicationService>>,
+    user: AuthenticatedUser,
+    Query(filter): Query<ExecutionListFilter>,
+) -> Result<Json<CursorPaginatedResponse<ExecutionSummaryDto>>> {
+    let page = service.list_executions(user.tenant_id.0, filter).await?;
+    Ok(Json(page))
+}
+
+/// Get an execution with its full node trace
+pub async fn get_execution_detail(
+    State(service): State<Arc<ExecutionHistoryApplicationService>>,
+    user: AuthenticatedUser,
+    Path(execution_id): Path<Uuid>,
+) -> Result<Json<ExecutionDetailDto>> {
+    let detail = service
+        .get_execution_detail(user.tenant_id.0, execution_id)
+        .await?;
+    Ok(Json(detail))
+}
+
 /// Query execution history
 pub async fn query_executions(
     State(service): State<Arc<ExecutionHistoryApplicationService>>,
@@ -141,7 +164,7 @@ pub async fn get_execution_details(
         slowest_step_time_ms: metrics.slowest_step_time_ms,
     };
 
-    let response = ExecutionDetailsResponse {
+    let response = ExecutionDetailDto {
         execution: execution_dto,
         steps: step_dtos,
         metrics: metrics_dto,
@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::{
-    application::services::FileApplicationService,
+    application::{
+        dto::{ConfirmUploadRequest, ConfirmUploadResponse, PresignedUploadRequest, PresignedUploadResponse},
+        services::FileApplicationService,
+    },
     error::{PlatformError, Result},
     presentation::extractors::AuthenticatedUser,
 };
@@ -64,3 +67,39 @@ pub async fn upload_file(
     
     Err(PlatformError::ValidationError("No file field found in multipart form".to_string()))
 }
+
+/// Create a presigned form for uploading a file straight to storage
+pub async fn presign_upload(
+    user: AuthenticatedUser,
+    State(service): State<Arc<dyn FileApplicationService>>,
+    Json(request): Json<PresignedUploadRequest>,
+) -> Result<Json<PresignedUploadResponse>> {
+    let content_type = request
+        .content_type
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+
+    let response = service
+        .generate_presigned_upload_url(
+            &user.tenant_id.to_string(),
+            &user.user_id.to_string(),
+            request.filename,
+            content_type,
+            request.max_size_bytes,
+        )
+        .await?;
+
+    Ok(Json(response))
+}
+
+/// Confirm a presigned upload and get the file's download URL
+pub async fn confirm_upload(
+    user: AuthenticatedUser,
+    State(service): State<Arc<dyn FileApplicationService>>,
+    Json(request): Json<ConfirmUploadRequest>,
+) -> Result<Json<ConfirmUploadResponse>> {
+    let response = service
+        .confirm_upload(&user.tenant_id.to_string(), &user.user_id.to_string(), request.key)
+        .await?;
+
+    Ok(Json(response))
+}
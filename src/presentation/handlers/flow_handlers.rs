@@ -6,12 +6,19 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    application::services::FlowApplicationService,
-    domain::value_objects::{FlowId, SessionId, FlowExecutionId, FlowDefinition},
+    application::dto::PaginationParams,
+    application::services::{
+        AsyncFlowExecutor, BatchFlowExecutor, FlowApplicationService, FlowExecutionResult,
+        FlowImportExportService,
+    },
+    domain::repositories::FlowQuery,
+    domain::services::DryRunConfig,
+    domain::value_objects::{ConfigId, FlowId, MCPToolId, NodeType, SessionId, FlowExecutionId, FlowDefinition, FlowNodeAnnotationId},
     error::{PlatformError, Result},
     presentation::extractors::AuthenticatedUser,
 };
@@ -27,6 +34,15 @@ pub struct CreateFlowRequest {
 pub struct UpdateFlowRequest {
     pub name: Option<String>,
     pub description: Option<String>,
+    pub definition: Option<Value>,
+    /// Required to change a published flow
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloneFlowRequest {
+    pub name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,12 +51,48 @@ pub struct ImportDslRequest {
     pub dsl: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ImportLangChainRequest {
+    pub name: String,
+    pub json: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ExecuteFlowRequest {
     pub session_id: Option<Uuid>,
     pub input_data: Option<Value>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExecuteFlowAsyncRequest {
+    pub input_data: Option<Value>,
+    /// Receives the `FlowExecutionResult` once the execution ends
+    pub callback_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExecuteFlowAsyncResponse {
+    pub execution_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchExecuteFlowRequest {
+    pub inputs: Vec<HashMap<String, Value>>,
+    #[serde(default = "default_batch_concurrency")]
+    pub concurrency: usize,
+}
+
+fn default_batch_concurrency() -> usize {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DryRunFlowRequest {
+    pub input_data: Option<Value>,
+    #[serde(default)]
+    pub config: DryRunConfig,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateVersionRequest {
     pub definition: Value,
@@ -60,6 +112,16 @@ pub struct ListFlowsQuery {
     pub limit: u64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SearchFlowsQuery {
+    /// Comma-separated node types, e.g. `llm,mcp_tool`
+    pub node_types: Option<String>,
+    pub llm_config_id: Option<Uuid>,
+    pub mcp_tool_id: Option<Uuid>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
+
 fn default_page() -> u64 {
     1
 }
@@ -85,9 +147,13 @@ pub struct FlowResponse {
     pub description: Option<String>,
     pub current_version: i32,
     pub status: String,
+    pub is_published: bool,
+    pub published_at: Option<String>,
+    pub is_enabled: bool,
     pub created_by: String,
     pub created_at: String,
     pub updated_at: String,
+    pub annotation_count: u32,
 }
 
 #[derive(Debug, Serialize)]
@@ -136,12 +202,42 @@ pub struct ExecutionListResponse {
     pub total_pages: u64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct BatchExecuteFlowResponse {
+    pub results: Vec<FlowExecutionResult>,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ImportDslResponse {
     pub flow: FlowResponse,
     pub validation: ValidationResultResponse,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CreateAnnotationRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListAnnotationsQuery {
+    pub node_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlowNodeAnnotationResponse {
+    pub id: String,
+    pub flow_id: String,
+    pub node_id: String,
+    pub version: i32,
+    pub author_id: String,
+    pub content: String,
+    pub resolved: bool,
+    pub created_at: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ValidationResultResponse {
     pub is_valid: bool,
@@ -171,7 +267,9 @@ pub async fn get_flow(
     Path(flow_id): Path<Uuid>,
 ) -> Result<impl IntoResponse> {
     let flow = service.get_flow(FlowId(flow_id), user.tenant_id).await?;
-    Ok(Json(flow_to_response(&flow)))
+    let mut response = flow_to_response(&flow);
+    response.annotation_count = service.count_annotations(flow.id, user.tenant_id).await?;
+    Ok(Json(response))
 }
 
 pub async fn list_flows(
@@ -183,7 +281,7 @@ pub async fn list_flows(
     let page = query.page.saturating_sub(1);
     let limit = query.limit;
     
-    let (flows, total) = service.list_flows(user.tenant_id, page, limit).await?;
+    let (flows, total) = service.list_tenant_flows(user.tenant_id, page, limit).await?;
     
     // Calculate total_pages
     let total_pages = if limit > 0 {
@@ -192,8 +290,19 @@ pub async fn list_flows(
         0
     };
     
+    let flow_ids: Vec<FlowId> = flows.iter().map(|flow| flow.id).collect();
+    let annotation_counts = service.count_annotations_by_flow(&flow_ids, user.tenant_id).await?;
+    let flow_responses: Vec<FlowResponse> = flows
+        .iter()
+        .map(|flow| {
+            let mut response = flow_to_response(flow);
+            response.annotation_count = annotation_counts.get(&flow.id).copied().unwrap_or(0);
+            response
+        })
+        .collect();
+    
     let response = FlowListResponse {
-        flows: flows.iter().map(flow_to_response).collect(),
+        flows: flow_responses,
         total,
         page: page + 1,  // Convert back to 1-based for API response
         limit,
@@ -203,17 +312,61 @@ pub async fn list_flows(
     Ok(Json(response))
 }
 
+pub async fn search_flows(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Query(query): Query<SearchFlowsQuery>,
+) -> Result<impl IntoResponse> {
+    let node_types = query
+        .node_types
+        .as_deref()
+        .map(|types| {
+            types
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(|t| {
+                    serde_json::from_value::<NodeType>(Value::String(t.to_string()))
+                        .map_err(|_| PlatformError::ValidationError(format!("Unknown node type: {}", t)))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?;
+
+    let flow_query = FlowQuery {
+        tenant_id: user.tenant_id,
+        node_types,
+        llm_config_id: query.llm_config_id.map(ConfigId),
+        mcp_tool_id: query.mcp_tool_id.map(MCPToolId),
+    };
+    let pagination = PaginationParams {
+        page: query.page,
+        limit: query.limit,
+    };
+
+    let response = service.list_flows(flow_query, pagination).await?;
+    Ok(Json(response))
+}
+
 pub async fn update_flow(
     State(service): State<Arc<dyn FlowApplicationService>>,
     user: AuthenticatedUser,
     Path(flow_id): Path<Uuid>,
     Json(req): Json<UpdateFlowRequest>,
 ) -> Result<impl IntoResponse> {
+    let definition = req.definition
+        .map(|definition| FlowDefinition::from_json(&definition))
+        .transpose()
+        .map_err(|e| PlatformError::ValidationError(e))?;
+
     let flow = service.update_flow(
         FlowId(flow_id),
         user.tenant_id,
         req.name,
         req.description,
+        definition,
+        user.user_id,
+        req.force,
     ).await?;
 
     Ok(Json(flow_to_response(&flow)))
@@ -246,6 +399,55 @@ pub async fn archive_flow(
     Ok(Json(flow_to_response(&flow)))
 }
 
+pub async fn publish_flow(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let flow = service.publish_flow(FlowId(flow_id), user.tenant_id, user.user_id).await?;
+    Ok(Json(flow_to_response(&flow)))
+}
+
+pub async fn unpublish_flow(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let flow = service.unpublish_flow(FlowId(flow_id), user.tenant_id, user.user_id).await?;
+    Ok(Json(flow_to_response(&flow)))
+}
+
+pub async fn enable_flow(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let flow = service.enable_flow(FlowId(flow_id), user.tenant_id, user.user_id).await?;
+    Ok(Json(flow_to_response(&flow)))
+}
+
+pub async fn disable_flow(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let flow = service.disable_flow(FlowId(flow_id), user.tenant_id, user.user_id).await?;
+    Ok(Json(flow_to_response(&flow)))
+}
+
+/// Copy a flow's current definition into a new flow
+pub async fn clone_flow(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+    Json(req): Json<CloneFlowRequest>,
+) -> Result<impl IntoResponse> {
+    let flow = service
+        .clone_flow(FlowId(flow_id), req.name, user.tenant_id, user.user_id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(flow_to_response(&flow))))
+}
+
 pub async fn import_from_dsl(
     State(service): State<Arc<dyn FlowApplicationService>>,
     user: AuthenticatedUser,
@@ -270,6 +472,50 @@ pub async fn import_from_dsl(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+/// Import a flow from a Dify DSL document
+pub async fn import_flow(
+    State(service): State<Arc<dyn FlowImportExportService>>,
+    user: AuthenticatedUser,
+    Json(dsl): Json<Value>,
+) -> Result<impl IntoResponse> {
+    let flow = service.import_from_dify_dsl(dsl, user.tenant_id, user.user_id).await?;
+    Ok((StatusCode::CREATED, Json(flow_to_response(&flow))))
+}
+
+/// Export a flow's current version as a Dify DSL document
+pub async fn export_flow(
+    State(service): State<Arc<dyn FlowImportExportService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let dsl = service.export_to_dify_dsl(FlowId(flow_id), user.tenant_id).await?;
+    Ok(Json(dsl))
+}
+
+pub async fn import_from_langchain(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Json(req): Json<ImportLangChainRequest>,
+) -> Result<impl IntoResponse> {
+    let (flow, validation) = service.import_from_langchain(
+        user.tenant_id,
+        req.name,
+        req.json,
+        user.user_id,
+    ).await?;
+
+    let response = ImportDslResponse {
+        flow: flow_to_response(&flow),
+        validation: ValidationResultResponse {
+            is_valid: validation.is_valid,
+            errors: validation.errors,
+            warnings: validation.warnings,
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
 pub async fn validate_definition(
     State(service): State<Arc<dyn FlowApplicationService>>,
     _user: AuthenticatedUser,
@@ -308,6 +554,69 @@ pub async fn execute_flow(
     Ok((StatusCode::CREATED, Json(execution_to_response(&execution))))
 }
 
+pub async fn execute_flow_async(
+    State(executor): State<Arc<AsyncFlowExecutor>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+    Json(req): Json<ExecuteFlowAsyncRequest>,
+) -> Result<impl IntoResponse> {
+    let execution_id = executor.execute_async(
+        FlowId(flow_id),
+        user.tenant_id,
+        user.user_id,
+        req.input_data,
+        req.callback_url,
+    ).await?;
+
+    let response = ExecuteFlowAsyncResponse {
+        execution_id: execution_id.0.to_string(),
+    };
+
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
+
+pub async fn batch_execute_flow(
+    State(executor): State<Arc<BatchFlowExecutor>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+    Json(req): Json<BatchExecuteFlowRequest>,
+) -> Result<impl IntoResponse> {
+    let results = executor.execute_batch(
+        FlowId(flow_id),
+        user.tenant_id,
+        user.user_id,
+        req.inputs,
+        req.concurrency,
+    ).await?;
+
+    let succeeded = results.iter().filter(|r| r.is_completed()).count();
+    let response = BatchExecuteFlowResponse {
+        total: results.len(),
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    };
+
+    Ok(Json(response))
+}
+
+pub async fn dry_run_flow(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+    Json(req): Json<DryRunFlowRequest>,
+) -> Result<impl IntoResponse> {
+    let result = service.dry_run(
+        FlowId(flow_id),
+        req.input_data,
+        user.tenant_id,
+        user.user_id,
+        req.config,
+    ).await?;
+
+    Ok(Json(result))
+}
+
 pub async fn get_execution_status(
     State(service): State<Arc<dyn FlowApplicationService>>,
     user: AuthenticatedUser,
@@ -383,6 +692,15 @@ pub async fn get_versions(
     Ok(Json(response))
 }
 
+pub async fn get_flow_at_version(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path((flow_id, version)): Path<(Uuid, i32)>,
+) -> Result<impl IntoResponse> {
+    let version = service.get_flow_at_version(FlowId(flow_id), user.tenant_id, version).await?;
+    Ok(Json(version_to_response(&version)))
+}
+
 pub async fn rollback_to_version(
     State(service): State<Arc<dyn FlowApplicationService>>,
     user: AuthenticatedUser,
@@ -399,6 +717,48 @@ pub async fn rollback_to_version(
     Ok(Json(flow_to_response(&flow)))
 }
 
+pub async fn add_annotation(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path((flow_id, node_id)): Path<(Uuid, String)>,
+    Json(req): Json<CreateAnnotationRequest>,
+) -> Result<impl IntoResponse> {
+    let annotation = service.add_annotation(
+        FlowId(flow_id),
+        user.tenant_id,
+        node_id,
+        req.content,
+        user.user_id,
+    ).await?;
+
+    Ok((StatusCode::CREATED, Json(annotation_to_response(&annotation))))
+}
+
+pub async fn list_annotations(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path(flow_id): Path<Uuid>,
+    Query(query): Query<ListAnnotationsQuery>,
+) -> Result<impl IntoResponse> {
+    let annotations = service.list_annotations(FlowId(flow_id), user.tenant_id, query.node_id).await?;
+    let response: Vec<FlowNodeAnnotationResponse> = annotations.iter().map(annotation_to_response).collect();
+    Ok(Json(response))
+}
+
+pub async fn resolve_annotation(
+    State(service): State<Arc<dyn FlowApplicationService>>,
+    user: AuthenticatedUser,
+    Path((flow_id, annotation_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse> {
+    service.resolve_annotation(
+        FlowId(flow_id),
+        FlowNodeAnnotationId(annotation_id),
+        user.tenant_id,
+        user.user_id,
+    ).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 // Helper functions
 fn flow_to_response(flow: &crate::domain::entities::Flow) -> FlowResponse {
     FlowResponse {
@@ -408,9 +768,26 @@ fn flow_to_response(flow: &crate::domain::entities::Flow) -> FlowResponse {
         description: flow.description.clone(),
         current_version: flow.current_version.0,
         status: format!("{:?}", flow.status),
+        is_published: flow.is_published,
+        published_at: flow.published_at.map(|at| at.to_rfc3339()),
+        is_enabled: flow.is_enabled,
         created_by: flow.created_by.0.to_string(),
         created_at: flow.created_at.to_rfc3339(),
         updated_at: flow.updated_at.to_rfc3339(),
+        annotation_count: 0,
+    }
+}
+
+fn annotation_to_response(annotation: &crate::domain::entities::FlowNodeAnnotation) -> FlowNodeAnnotationResponse {
+    FlowNodeAnnotationResponse {
+        id: annotation.id.0.to_string(),
+        flow_id: annotation.flow_id.0.to_string(),
+        node_id: annotation.node_id.clone(),
+        version: annotation.version.0,
+        author_id: annotation.author_id.0.to_string(),
+        content: annotation.content.clone(),
+        resolved: annotation.resolved,
+        created_at: annotation.created_at.to_rfc3339(),
     }
 }
 
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        extract::{Path, Query, State},
+        response::IntoResponse,
+    };
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use crate::{
+        application::services::{FlowApplicationService, FlowApplicationServiceImpl},
+        domain::{
+            entities::{Flow, FlowNodeAnnotation},
+            repositories::{
+                MockFlowExecutionRepository, MockFlowNodeAnnotationRepository, MockFlowRepository,
+                MockFlowVersionRepository,
+            },
+            services::FlowDomainServiceImpl,
+            value_objects::{FlowId, FlowName, TenantId, UserId, Version},
+        },
+        error::PlatformError,
+        presentation::{
+            extractors::AuthenticatedUser,
+            handlers::{list_flows, resolve_annotation, ListFlowsQuery},
+        },
+    };
+
+    fn create_user() -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: UserId::new(),
+            tenant_id: TenantId::new(),
+            username: "reviewer".to_string(),
+            nickname: None,
+        }
+    }
+
+    fn create_service(
+        flow_repo: MockFlowRepository,
+        annotation_repo: MockFlowNodeAnnotationRepository,
+    ) -> Arc<dyn FlowApplicationService> {
+        Arc::new(
+            FlowApplicationServiceImpl::new(
+                Arc::new(flow_repo),
+                Arc::new(MockFlowVersionRepository::new()),
+                Arc::new(MockFlowExecutionRepository::new()),
+                Arc::new(FlowDomainServiceImpl::new()),
+                None,
+            )
+            .with_annotation_repository(Arc::new(annotation_repo)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_list_flows_counts_annotations_in_one_query() {
+        let user = create_user();
+        let flows: Vec<Flow> = (0..3)
+            .map(|i| {
+                Flow::new(
+                    user.tenant_id,
+                    FlowName::new(format!("Flow {}", i)).unwrap(),
+                    None,
+                    user.user_id,
+                )
+            })
+            .collect();
+        let annotated = flows[2].id;
+
+        let mut flow_repo = MockFlowRepository::new();
+        let listed = flows.clone();
+        flow_repo
+            .expect_find_by_tenant_paginated()
+            .returning(move |_, _, _| Ok(listed.clone()));
+        flow_repo.expect_count_by_tenant().returning(|_| Ok(3));
+
+        let mut annotation_repo = MockFlowNodeAnnotationRepository::new();
+        annotation_repo.expect_count_by_flow().never();
+        annotation_repo
+            .expect_count_by_flows()
+            .withf(|_, ids| ids.len() == 3)
+            .times(1)
+            .returning(move |_, _| Ok(HashMap::from([(annotated, 2)])));
+
+        let response = list_flows(
+            State(create_service(flow_repo, annotation_repo)),
+            user,
+            Query(ListFlowsQuery { page: 1, limit: 10 }),
+        )
+        .await
+        .unwrap()
+        .into_response();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let counts: Vec<u64> = body["flows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|flow| flow["annotation_count"].as_u64().unwrap())
+            .collect();
+        assert_eq!(counts, vec![0, 0, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_annotation_through_another_flow_is_not_found() {
+        let user = create_user();
+        let annotation = FlowNodeAnnotation::new(
+            FlowId::new(),
+            "answer".to_string(),
+            Version::new(),
+            user.user_id,
+            "Typo in the prompt".to_string(),
+        )
+        .unwrap();
+        let annotation_id = annotation.id;
+
+        let mut annotation_repo = MockFlowNodeAnnotationRepository::new();
+        annotation_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(annotation.clone())));
+        annotation_repo.expect_save().never();
+
+        let result = resolve_annotation(
+            State(create_service(MockFlowRepository::new(), annotation_repo)),
+            user,
+            Path((FlowId::new().0, annotation_id.0)),
+        )
+        .await;
+
+        assert!(matches!(result, Err(PlatformError::NotFound(_))));
+    }
+}
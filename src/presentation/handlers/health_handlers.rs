@@ -8,21 +8,24 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use sea_orm::DatabaseConnection;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Instant;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct HealthResponse {
-    pub status: String,
-    pub version: String,
-    pub uptime_seconds: u64,
-    pub checks: HealthChecks,
-}
+use crate::infrastructure::database::QueryOptimizer;
+use crate::infrastructure::llm::LLMProviderRegistry;
+use crate::infrastructure::mcp::{MCPProxyServiceImpl, ToolCircuitStatus};
+use crate::infrastructure::RedisCache;
+use crate::presentation::middleware::RequestMetrics;
+use crate::presentation::shutdown::GracefulShutdown;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct HealthChecks {
-    pub database: ComponentHealth,
-    pub redis: ComponentHealth,
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub checks: BTreeMap<String, ComponentHealth>,
+    /// Circuit breakers of MCP tools called so far. Informational only: an
+    /// open circuit does not make the instance unready.
+    pub mcp_circuits: Vec<ToolCircuitStatus>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,37 +35,36 @@ pub struct ComponentHealth {
     pub response_time_ms: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MetricsResponse {
-    pub uptime_seconds: u64,
-    pub database_connections: DatabaseMetrics,
-    pub cache_stats: CacheMetrics,
-    pub request_stats: RequestMetrics,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DatabaseMetrics {
-    pub active_connections: u32,
-    pub idle_connections: u32,
-    pub max_connections: u32,
-}
+impl ComponentHealth {
+    fn ok(response_time_ms: u64) -> Self {
+        Self {
+            status: "ok".to_string(),
+            message: None,
+            response_time_ms: Some(response_time_ms),
+        }
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CacheMetrics {
-    pub hit_rate: f64,
-    pub total_keys: u64,
-    pub memory_usage_mb: f64,
-}
+    fn error(message: String) -> Self {
+        Self {
+            status: "error".to_string(),
+            message: Some(message),
+            response_time_ms: None,
+        }
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct RequestMetrics {
-    pub total_requests: u64,
-    pub requests_per_second: f64,
-    pub average_response_time_ms: f64,
+    fn is_ok(&self) -> bool {
+        self.status == "ok"
+    }
 }
 
-pub struct AppState {
+/// Dependencies probed by the readiness check
+pub struct HealthState {
     pub db: Arc<DatabaseConnection>,
+    pub cache: Arc<RedisCache>,
+    pub llm_providers: Arc<LLMProviderRegistry>,
+    pub mcp_proxy: Arc<MCPProxyServiceImpl>,
+    pub request_metrics: Arc<RequestMetrics>,
+    pub shutdown: Arc<GracefulShutdown>,
     pub start_time: Instant,
 }
 
@@ -75,66 +77,63 @@ pub async fn health_check() -> impl IntoResponse {
     })))
 }
 
-/// Detailed health check with component status
-/// GET /health/detailed
-pub async fn detailed_health_check(
-    State(state): State<Arc<AppState>>,
+/// Readiness probe for Kubernetes. Fails with 503 while shutting down, or if
+/// the database, Redis or any registered LLM provider is unreachable. Also
+/// reports the circuit breaker state of each MCP tool.
+/// GET /health/ready
+pub async fn readiness_check(
+    State(state): State<Arc<HealthState>>,
 ) -> impl IntoResponse {
-    let uptime = state.start_time.elapsed().as_secs();
-    
-    // Check database health
-    let db_health = check_database_health(&state.db).await;
-    
-    // Check Redis health (simplified - would need Redis client)
-    let redis_health = ComponentHealth {
-        status: "ok".to_string(),
-        message: None,
-        response_time_ms: Some(1),
-    };
-    
-    let overall_status = if db_health.status == "ok" && redis_health.status == "ok" {
-        "healthy"
-    } else {
-        "degraded"
-    };
-    
-    let response = HealthResponse {
-        status: overall_status.to_string(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        uptime_seconds: uptime,
-        checks: HealthChecks {
-            database: db_health,
-            redis: redis_health,
-        },
-    };
-    
-    let status_code = if overall_status == "healthy" {
+    // Load balancers should stop routing here before the listener closes
+    if state.shutdown.is_draining() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadinessResponse {
+                status: "draining".to_string(),
+                checks: BTreeMap::new(),
+                mcp_circuits: Vec::new(),
+            }),
+        );
+    }
+
+    let (db_health, redis_health, llm_results, mcp_circuits) = tokio::join!(
+        check_database_health(&state.db),
+        check_redis_health(&state.cache),
+        state.llm_providers.test_all_connections(),
+        state.mcp_proxy.circuit_statuses(),
+    );
+
+    let mut checks = BTreeMap::new();
+    checks.insert("database".to_string(), db_health);
+    checks.insert("redis".to_string(), redis_health);
+    for (name, result) in llm_results {
+        let health = if result.success {
+            ComponentHealth::ok(result.response_time_ms)
+        } else {
+            ComponentHealth::error(
+                result
+                    .error_message
+                    .unwrap_or_else(|| "Connection test failed".to_string()),
+            )
+        };
+        checks.insert(format!("llm:{}", name), health);
+    }
+
+    let healthy = checks.values().all(ComponentHealth::is_ok);
+    let status_code = if healthy {
         StatusCode::OK
     } else {
         StatusCode::SERVICE_UNAVAILABLE
     };
-    
-    (status_code, Json(response))
-}
 
-/// Readiness probe for Kubernetes
-/// GET /health/ready
-pub async fn readiness_check(
-    State(state): State<Arc<AppState>>,
-) -> impl IntoResponse {
-    // Check if application is ready to serve traffic
-    let db_health = check_database_health(&state.db).await;
-    
-    if db_health.status == "ok" {
-        (StatusCode::OK, Json(serde_json::json!({
-            "ready": true
-        })))
-    } else {
-        (StatusCode::SERVICE_UNAVAILABLE, Json(serde_json::json!({
-            "ready": false,
-            "reason": db_health.message
-        })))
-    }
+    (
+        status_code,
+        Json(ReadinessResponse {
+            status: if healthy { "ok" } else { "error" }.to_string(),
+            checks,
+            mcp_circuits,
+        }),
+    )
 }
 
 /// Liveness probe for Kubernetes
@@ -142,67 +141,26 @@ pub async fn readiness_check(
 pub async fn liveness_check() -> impl IntoResponse {
     // Simple check that the application is running
     (StatusCode::OK, Json(serde_json::json!({
-        "alive": true
+        "status": "ok"
     })))
 }
 
-/// Metrics endpoint for monitoring
-/// GET /metrics
-pub async fn metrics(
-    State(state): State<Arc<AppState>>,
-) -> impl IntoResponse {
-    let uptime = state.start_time.elapsed().as_secs();
-    
-    // In production, these would be collected from actual metrics
-    let metrics = MetricsResponse {
-        uptime_seconds: uptime,
-        database_connections: DatabaseMetrics {
-            active_connections: 5,
-            idle_connections: 10,
-            max_connections: 100,
-        },
-        cache_stats: CacheMetrics {
-            hit_rate: 0.85,
-            total_keys: 1234,
-            memory_usage_mb: 45.6,
-        },
-        request_stats: RequestMetrics {
-            total_requests: 10000,
-            requests_per_second: 50.0,
-            average_response_time_ms: 25.5,
-        },
-    };
-    
-    (StatusCode::OK, Json(metrics))
-}
-
-/// Prometheus-compatible metrics endpoint
-/// GET /metrics/prometheus
+/// Prometheus-compatible request counters and latencies
+/// GET /health/metrics
 pub async fn prometheus_metrics(
-    State(state): State<Arc<AppState>>,
+    State(state): State<Arc<HealthState>>,
 ) -> impl IntoResponse {
     let uptime = state.start_time.elapsed().as_secs();
-    
-    // Format metrics in Prometheus format
+
     let metrics = format!(
         "# HELP agent_platform_uptime_seconds Application uptime in seconds\n\
          # TYPE agent_platform_uptime_seconds counter\n\
          agent_platform_uptime_seconds {}\n\
-         \n\
-         # HELP agent_platform_db_connections_active Active database connections\n\
-         # TYPE agent_platform_db_connections_active gauge\n\
-         agent_platform_db_connections_active 5\n\
-         \n\
-         # HELP agent_platform_cache_hit_rate Cache hit rate\n\
-         # TYPE agent_platform_cache_hit_rate gauge\n\
-         agent_platform_cache_hit_rate 0.85\n\
-         \n\
-         # HELP agent_platform_requests_total Total number of requests\n\
-         # TYPE agent_platform_requests_total counter\n\
-         agent_platform_requests_total 10000\n",
-        uptime
+         {}",
+        uptime,
+        state.request_metrics.render_prometheus()
     );
-    
+
     (
         StatusCode::OK,
         [("Content-Type", "text/plain; version=0.0.4")],
@@ -210,25 +168,31 @@ pub async fn prometheus_metrics(
     )
 }
 
+/// Per query type counts and latencies collected by the query optimizer
+/// GET /internal/db-stats
+pub async fn db_stats(
+    State(query_optimizer): State<Arc<QueryOptimizer>>,
+) -> impl IntoResponse {
+    (StatusCode::OK, Json(query_optimizer.report()))
+}
+
 // Helper functions
 
 async fn check_database_health(db: &DatabaseConnection) -> ComponentHealth {
     let start = Instant::now();
     
     match db.ping().await {
-        Ok(_) => {
-            let response_time = start.elapsed().as_millis() as u64;
-            ComponentHealth {
-                status: "ok".to_string(),
-                message: None,
-                response_time_ms: Some(response_time),
-            }
-        }
-        Err(e) => ComponentHealth {
-            status: "error".to_string(),
-            message: Some(format!("Database connection failed: {}", e)),
-            response_time_ms: None,
-        },
+        Ok(_) => ComponentHealth::ok(start.elapsed().as_millis() as u64),
+        Err(e) => ComponentHealth::error(format!("Database connection failed: {}", e)),
+    }
+}
+
+async fn check_redis_health(cache: &RedisCache) -> ComponentHealth {
+    let start = Instant::now();
+
+    match cache.ping().await {
+        Ok(_) => ComponentHealth::ok(start.elapsed().as_millis() as u64),
+        Err(e) => ComponentHealth::error(format!("Redis PING failed: {}", e)),
     }
 }
 
@@ -0,0 +1,43 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dto::agent_dto::{InterviewQuestionsDto, InterviewTurnRequest},
+        services::InterviewApplicationService,
+    },
+    domain::value_objects::AgentId,
+    error::Result,
+    presentation::extractors::AuthenticatedUser,
+};
+
+/// Generate interview questions from an agent's system prompt
+pub async fn generate_interview_questions(
+    State(service): State<Arc<dyn InterviewApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let questions = service
+        .generate_questions(AgentId::from_uuid(agent_id), user.tenant_id)
+        .await?;
+    Ok(Json(InterviewQuestionsDto { agent_id, questions }))
+}
+
+/// Answer the current interview question and get the next one
+pub async fn conduct_interview_turn(
+    State(service): State<Arc<dyn InterviewApplicationService>>,
+    user: AuthenticatedUser,
+    Path(record_id): Path<Uuid>,
+    request: Option<Json<InterviewTurnRequest>>,
+) -> Result<impl IntoResponse> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let turn = service
+        .conduct_interview_turn(record_id, user.tenant_id, user.user_id, request.answer)
+        .await?;
+    Ok(Json(turn))
+}
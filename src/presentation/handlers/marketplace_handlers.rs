@@ -0,0 +1,64 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dto::marketplace_dto::{MarketplaceAgentQuery, MarketplaceSearchQuery, PurchaseAgentRequest},
+        services::MarketplaceApplicationService,
+    },
+    domain::value_objects::AgentId,
+    error::Result,
+    presentation::extractors::AuthenticatedUser,
+};
+
+/// List published agents
+pub async fn list_marketplace_agents(
+    State(service): State<Arc<dyn MarketplaceApplicationService>>,
+    user: AuthenticatedUser,
+    Query(query): Query<MarketplaceAgentQuery>,
+) -> Result<impl IntoResponse> {
+    let response = service.list_agents(user.tenant_id, query).await?;
+    Ok(Json(response))
+}
+
+/// Search published agents across all tenants
+pub async fn search_marketplace_agents(
+    State(service): State<Arc<dyn MarketplaceApplicationService>>,
+    _user: AuthenticatedUser,
+    Query(query): Query<MarketplaceSearchQuery>,
+) -> Result<impl IntoResponse> {
+    let response = service.search_agents(query).await?;
+    Ok(Json(response))
+}
+
+/// Get the public view of a published agent
+pub async fn get_marketplace_agent(
+    State(service): State<Arc<dyn MarketplaceApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let agent = service
+        .get_agent(AgentId::from_uuid(agent_id), user.tenant_id)
+        .await?;
+    Ok(Json(agent))
+}
+
+/// Purchase and employ a published agent
+pub async fn purchase_marketplace_agent(
+    State(service): State<Arc<dyn MarketplaceApplicationService>>,
+    user: AuthenticatedUser,
+    Path(agent_id): Path<Uuid>,
+    request: Option<Json<PurchaseAgentRequest>>,
+) -> Result<impl IntoResponse> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let response = service
+        .purchase_agent(AgentId::from_uuid(agent_id), user.tenant_id, user.user_id, request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
@@ -209,6 +209,19 @@ pub async fn rollback_tool_version(
     Ok(Json(response))
 }
 
+/// 回退到指定版本
+pub async fn rollback_to_version(
+    State(service): State<Arc<dyn MCPApplicationService>>,
+    user: AuthenticatedUser,
+    Path((tool_id, version)): Path<(Uuid, i32)>,
+) -> Result<StatusCode, PlatformError> {
+    service
+        .rollback_to_version(MCPToolId(tool_id), version, user.user_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// 获取工具统计信息
 pub async fn get_tool_stats(
     State(service): State<Arc<dyn MCPApplicationService>>,
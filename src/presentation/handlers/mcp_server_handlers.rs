@@ -1,16 +1,26 @@
 use axum::{
-    extract::{Query, State},
-    response::Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json,
+    },
+    Extension,
 };
+use futures::StreamExt;
+use rmcp::model::ClientJsonRpcMessage;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
 
 use crate::{
+    application::dto::APIKeyAuthContext,
+    domain::{repositories::TenantRepository, value_objects::TenantId},
     error::PlatformError,
     infrastructure::mcp::{
         mcp_protocol::{MCPToolCallResponse, MCPToolListResponse},
         mcp_server_handler::MCPServerHandler,
+        TenantMCPSessions,
     },
     presentation::extractors::AuthenticatedUser,
 };
@@ -62,6 +72,112 @@ pub async fn call_mcp_tool(
     Ok(Json(response))
 }
 
+/// State of the tenant-hosted MCP endpoints
+#[derive(Clone)]
+pub struct TenantMCPState {
+    pub sessions: Arc<TenantMCPSessions>,
+    pub tenant_repository: Arc<dyn TenantRepository>,
+}
+
+/// Query of `POST /mcp/{tenant_slug}/messages`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantMCPMessageQuery {
+    pub session_id: String,
+}
+
+/// Ends the session once the SSE stream holding it is dropped
+struct SessionGuard {
+    sessions: Arc<TenantMCPSessions>,
+    session_id: String,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        let sessions = self.sessions.clone();
+        let session_id = std::mem::take(&mut self.session_id);
+        tokio::spawn(async move { sessions.close(&session_id).await });
+    }
+}
+
+/// The slug in the path must name the tenant that owns the API key
+async fn verify_tenant_slug(
+    state: &TenantMCPState,
+    tenant_slug: &str,
+    auth_context: &APIKeyAuthContext,
+) -> Result<(), PlatformError> {
+    let tenant = state
+        .tenant_repository
+        .find_by_id(TenantId::from_uuid(auth_context.tenant_id))
+        .await?
+        .ok_or_else(|| PlatformError::NotFound("Tenant not found".to_string()))?;
+
+    if tenant.name.slug() != tenant_slug {
+        return Err(PlatformError::AuthorizationFailed(
+            "API key does not belong to this tenant".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Open an MCP session over SSE (MCP Server接口)
+/// GET /mcp/{tenant_slug}/sse
+///
+/// The first event is `endpoint`, carrying the URL to POST messages to;
+/// every server message follows as a `message` event.
+pub async fn tenant_mcp_sse(
+    State(state): State<TenantMCPState>,
+    Path(tenant_slug): Path<String>,
+    Extension(auth_context): Extension<APIKeyAuthContext>,
+) -> Result<impl IntoResponse, PlatformError> {
+    verify_tenant_slug(&state, &tenant_slug, &auth_context).await?;
+
+    let (session_id, messages) = state.sessions.open(auth_context).await?;
+    let endpoint = Event::default()
+        .event("endpoint")
+        .data(format!("/mcp/{}/messages?sessionId={}", tenant_slug, session_id));
+
+    let guard = SessionGuard {
+        sessions: state.sessions.clone(),
+        session_id,
+    };
+    let events = messages.map(move |message| {
+        let _guard = &guard;
+        let data = serde_json::to_string(&message).unwrap_or_else(|_| "{}".to_string());
+        Ok::<_, std::convert::Infallible>(Event::default().event("message").data(data))
+    });
+
+    let stream = futures::stream::once(async move { Ok(endpoint) }).chain(events);
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(std::time::Duration::from_secs(15))
+            .text("keep-alive"),
+    ))
+}
+
+/// Post a JSON-RPC message to an MCP session (MCP Server接口)
+/// POST /mcp/{tenant_slug}/messages?sessionId=...
+///
+/// Replies arrive on the session's SSE stream, so this only acknowledges.
+pub async fn tenant_mcp_message(
+    State(state): State<TenantMCPState>,
+    Path(tenant_slug): Path<String>,
+    Query(query): Query<TenantMCPMessageQuery>,
+    Extension(auth_context): Extension<APIKeyAuthContext>,
+    Json(message): Json<ClientJsonRpcMessage>,
+) -> Result<StatusCode, PlatformError> {
+    verify_tenant_slug(&state, &tenant_slug, &auth_context).await?;
+
+    state
+        .sessions
+        .send(&query.session_id, &auth_context, message)
+        .await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,6 +362,18 @@ mod tests {
             ))
         }
 
+        async fn forward_tool_call(
+            &self,
+            _tool: &MCPTool,
+            _parameters: Value,
+            _context: ToolCallContext,
+        ) -> Result<ToolCallResult, PlatformError> {
+            Ok(ToolCallResult::success(
+                serde_json::json!({"result": "success"}),
+                100,
+            ))
+        }
+
         async fn handle_mcp_request(
             &self,
             _request: crate::infrastructure::mcp::protocol_handler::MCPRequest,
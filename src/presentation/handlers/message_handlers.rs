@@ -0,0 +1,58 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    application::services::MessageApplicationService,
+    domain::value_objects::{MessageId, SessionId},
+    error::{PlatformError, Result},
+    presentation::extractors::AuthenticatedUser,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct EditMessageRequest {
+    pub content: String,
+}
+
+/// Edit a user message and regenerate the response to it
+pub async fn edit_message(
+    State(service): State<Arc<MessageApplicationService>>,
+    user: AuthenticatedUser,
+    Path((session_id, message_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<EditMessageRequest>,
+) -> Result<impl IntoResponse> {
+    let message_id = MessageId(message_id);
+    let message = service
+        .get_message(&message_id, &user.tenant_id, &user.user_id)
+        .await?;
+    if message.session_id != SessionId(session_id) {
+        return Err(PlatformError::NotFound("Message not found".to_string()));
+    }
+
+    let result = service
+        .edit_message(&message_id, request.content, &user.tenant_id, &user.user_id)
+        .await?;
+    Ok(Json(result))
+}
+
+/// List the edits of a message with their responses
+pub async fn list_message_branches(
+    State(service): State<Arc<MessageApplicationService>>,
+    user: AuthenticatedUser,
+    Path((session_id, message_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse> {
+    let branches = service
+        .list_branches(
+            &SessionId(session_id),
+            &MessageId(message_id),
+            &user.tenant_id,
+            &user.user_id,
+        )
+        .await?;
+    Ok(Json(branches))
+}
@@ -10,13 +10,22 @@ pub mod config_handlers;
 pub mod session_audit_handlers;
 pub mod health_handlers;
 pub mod agent_handlers;
+pub mod agent_ws_handlers;
+pub mod sse_formatter;
 pub mod file_handlers;
 pub mod api_key_handlers;
 pub mod counter;
 pub mod dashboard_handlers;
+pub mod marketplace_handlers;
+pub mod interview_handlers;
+pub mod message_handlers;
+pub mod tenant_handlers;
+pub mod user_handlers;
 
 #[cfg(test)]
 mod auth_handlers_test;
+#[cfg(test)]
+mod flow_handlers_test;
 
 pub use auth_handlers::*;
 pub use vector_config_handlers::*;
@@ -1,21 +1,29 @@
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    application::services::{SessionApplicationService, AuditApplicationService, ExecutionHistoryApplicationService},
+    application::{
+        dto::{AuditQuery, SearchAuditLogsRequest},
+        services::{ChatExportFormat, SessionApplicationService, AuditApplicationService},
+    },
     domain::{
         entities::{AuditAction, ResourceType},
-        value_objects::{SessionId, ChatMessage, MessageRole},
+        value_objects::{
+            chat_message::{MessageContent, MessageContentType},
+            ChatMessage, MessageRole, SessionId, TenantId,
+        },
     },
-    error::Result,
+    error::{PlatformError, Result},
     presentation::extractors::AuthenticatedUser,
 };
 
@@ -45,7 +53,10 @@ fn default_page() -> u64 {
 #[derive(Debug, Deserialize)]
 pub struct AddMessageRequest {
     pub role: String,
-    pub content: String,
+    /// A string, or an array of content parts when `content_type` is `multimodal`
+    pub content: Value,
+    #[serde(default)]
+    pub content_type: Option<MessageContentType>,
     pub metadata: Option<Value>,
 }
 
@@ -61,10 +72,17 @@ pub struct SessionResponse {
     pub tenant_id: String,
     pub user_id: String,
     pub title: Option<String>,
+    pub summary: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SessionSummaryResponse {
+    pub session_id: String,
+    pub summary: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct MessageResponse {
     pub id: String,
@@ -73,6 +91,8 @@ pub struct MessageResponse {
     pub content: String,
     pub metadata: Option<Value>,
     pub created_at: String,
+    /// Set when an edit replaced the message; see its branches
+    pub superseded_at: Option<String>,
 }
 
 // Audit DTOs
@@ -115,76 +135,6 @@ pub struct AuditLogsListResponse {
     pub page_size: u64,
 }
 
-// Execution History DTOs
-#[derive(Debug, Deserialize)]
-pub struct QueryExecutionsRequest {
-    pub flow_id: Option<Uuid>,
-    pub user_id: Option<Uuid>,
-    pub status: Option<String>,
-    pub start_date: Option<String>,
-    pub end_date: Option<String>,
-    #[serde(default)]
-    pub page: u64,
-    #[serde(default = "default_page_size")]
-    pub page_size: u64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ExecutionHistoryResponse {
-    pub id: String,
-    pub flow_id: String,
-    pub flow_version: i32,
-    pub tenant_id: String,
-    pub user_id: String,
-    pub session_id: Option<String>,
-    pub status: String,
-    pub input_data: Option<Value>,
-    pub output_data: Option<Value>,
-    pub error_message: Option<String>,
-    pub started_at: String,
-    pub completed_at: Option<String>,
-    pub execution_time_ms: Option<i64>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ExecutionStepResponse {
-    pub id: String,
-    pub execution_id: String,
-    pub step_name: String,
-    pub step_type: String,
-    pub status: String,
-    pub input_data: Option<Value>,
-    pub output_data: Option<Value>,
-    pub error_message: Option<String>,
-    pub started_at: String,
-    pub completed_at: Option<String>,
-    pub execution_time_ms: Option<i64>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ExecutionDetailsResponse {
-    pub execution: ExecutionHistoryResponse,
-    pub steps: Vec<ExecutionStepResponse>,
-    pub metrics: ExecutionMetricsResponse,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ExecutionMetricsResponse {
-    pub total_steps: u32,
-    pub successful_steps: u32,
-    pub failed_steps: u32,
-    pub total_execution_time_ms: i64,
-    pub average_step_time_ms: i64,
-}
-
-#[derive(Debug, Serialize)]
-pub struct ExecutionsListResponse {
-    pub executions: Vec<ExecutionHistoryResponse>,
-    pub total: u64,
-    pub page: u64,
-    pub page_size: u64,
-}
-
 // Session Handlers
 pub async fn create_session(
     State(service): State<Arc<SessionApplicationService>>,
@@ -254,6 +204,43 @@ pub async fn delete_session(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PurgeSessionsQuery {
+    pub older_than_days: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PurgeSessionsResponse {
+    pub deleted: u64,
+}
+
+/// Delete sessions of a tenant that have been idle for `older_than_days` days
+pub async fn purge_old_sessions(
+    State(service): State<Arc<SessionApplicationService>>,
+    Path(tenant_id): Path<Uuid>,
+    Query(query): Query<PurgeSessionsQuery>,
+) -> Result<impl IntoResponse> {
+    let deleted = service
+        .purge_old_sessions(&TenantId::from_uuid(tenant_id), query.older_than_days)
+        .await?;
+    Ok(Json(PurgeSessionsResponse { deleted }))
+}
+
+pub async fn summarize_session(
+    State(service): State<Arc<SessionApplicationService>>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let summary = service
+        .summarize_session(&SessionId(session_id), &user.tenant_id, &user.user_id)
+        .await?;
+
+    Ok(Json(SessionSummaryResponse {
+        session_id: session_id.to_string(),
+        summary,
+    }))
+}
+
 pub async fn add_message(
     State(service): State<Arc<SessionApplicationService>>,
     user: AuthenticatedUser,
@@ -261,6 +248,8 @@ pub async fn add_message(
     Json(req): Json<AddMessageRequest>,
 ) -> Result<impl IntoResponse> {
     let role = parse_message_role(&req.role)?;
+    let content = MessageContent::from_json(req.content_type.unwrap_or_default(), req.content)
+        .map_err(PlatformError::ValidationError)?;
     
     // Parse metadata if provided
     let metadata = req.metadata.map(|m| {
@@ -269,7 +258,7 @@ pub async fn add_message(
     
     let chat_message = ChatMessage {
         role,
-        content: crate::domain::value_objects::chat_message::MessageContent::Text(req.content),
+        content,
         metadata,
         timestamp: chrono::Utc::now(),
     };
@@ -311,6 +300,57 @@ pub async fn get_session_messages(
     Ok(Json(response))
 }
 
+/// Exports larger than this are streamed with chunked transfer encoding
+const EXPORT_STREAM_THRESHOLD: usize = 1024 * 1024;
+
+/// Size of each chunk of a streamed export
+const EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportSessionQuery {
+    /// `markdown` (default), `json` or `pdf`
+    pub format: Option<String>,
+}
+
+/// Download the conversation of a session as Markdown, JSON or PDF
+pub async fn export_session(
+    State(service): State<Arc<SessionApplicationService>>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+    Query(query): Query<ExportSessionQuery>,
+) -> Result<impl IntoResponse> {
+    let format = match query.format {
+        Some(format) => format.parse::<ChatExportFormat>()?,
+        None => ChatExportFormat::Markdown,
+    };
+
+    let data = service
+        .export_chat_history(&SessionId(session_id), &user.tenant_id, &user.user_id, format)
+        .await?;
+
+    let body = if data.len() > EXPORT_STREAM_THRESHOLD {
+        let data = Bytes::from(data);
+        let chunks: Vec<std::result::Result<Bytes, std::io::Error>> = (0..data.len())
+            .step_by(EXPORT_CHUNK_SIZE)
+            .map(|start| Ok(data.slice(start..(start + EXPORT_CHUNK_SIZE).min(data.len()))))
+            .collect();
+        Body::from_stream(futures::stream::iter(chunks))
+    } else {
+        Body::from(data)
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"session-{}.{}\"", session_id, format.file_extension()),
+            ),
+        ],
+        body,
+    ))
+}
+
 // Audit Handlers
 pub async fn query_audit_logs(
     State(service): State<Arc<AuditApplicationService>>,
@@ -343,6 +383,27 @@ pub async fn query_audit_logs(
     Ok(Json(response))
 }
 
+/// Admin search over audit events. Action and resource type take the stored
+/// names (e.g. `execute`, `agent`, `api_key`); `page` is 1-based.
+pub async fn search_audit_logs(
+    State(service): State<Arc<AuditApplicationService>>,
+    user: AuthenticatedUser,
+    Query(request): Query<SearchAuditLogsRequest>,
+) -> Result<impl IntoResponse> {
+    let mut query = AuditQuery::new(request.tenant_id.unwrap_or(user.tenant_id.0));
+    query.user_id = request.user_id;
+    query.action = request.action.map(AuditAction::from);
+    query.resource_type = request.resource_type.map(ResourceType::from);
+    query.start_time = request.start_time;
+    query.end_time = request.end_time;
+    query.page = request.page.unwrap_or(query.page);
+    query.limit = request.limit.unwrap_or(query.limit).min(200);
+
+    let events = service.search_events(query).await?;
+
+    Ok(Json(events))
+}
+
 pub async fn get_audit_statistics(
     State(service): State<Arc<AuditApplicationService>>,
     user: AuthenticatedUser,
@@ -364,53 +425,6 @@ pub async fn get_audit_statistics(
     Ok(Json(response))
 }
 
-// Execution History Handlers
-pub async fn query_executions(
-    State(service): State<Arc<ExecutionHistoryApplicationService>>,
-    user: AuthenticatedUser,
-    Query(query): Query<QueryExecutionsRequest>,
-) -> Result<impl IntoResponse> {
-    let start_date = query.start_date.as_ref().and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok().map(|dt| dt.with_timezone(&chrono::Utc)));
-    let end_date = query.end_date.as_ref().and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok().map(|dt| dt.with_timezone(&chrono::Utc)));
-
-    let (executions, total) = service.query_executions_paginated(
-        user.tenant_id.0,
-        query.page + 1, // Service expects 1-based page
-        query.page_size,
-        query.flow_id,
-        query.user_id,
-        query.status,
-        start_date,
-        end_date,
-    ).await?;
-
-    let response = ExecutionsListResponse {
-        executions: executions.iter().map(execution_to_response).collect(),
-        total,
-        page: query.page,
-        page_size: query.page_size,
-    };
-
-    Ok(Json(response))
-}
-
-pub async fn get_execution_details(
-    State(service): State<Arc<ExecutionHistoryApplicationService>>,
-    _user: AuthenticatedUser,
-    Path(execution_id): Path<Uuid>,
-) -> Result<impl IntoResponse> {
-    let details = service.get_execution_details(execution_id).await?
-        .ok_or_else(|| crate::error::PlatformError::NotFound("Execution not found".to_string()))?;
-
-    let response = ExecutionDetailsResponse {
-        execution: execution_to_response(&details.0),
-        steps: details.1.iter().map(step_to_response).collect(),
-        metrics: metrics_to_response(&details.2),
-    };
-
-    Ok(Json(response))
-}
-
 // Helper functions
 fn session_to_response(session: &crate::domain::entities::ChatSession) -> SessionResponse {
     SessionResponse {
@@ -418,6 +432,7 @@ fn session_to_response(session: &crate::domain::entities::ChatSession) -> Sessio
         tenant_id: session.tenant_id.0.to_string(),
         user_id: session.user_id.0.to_string(),
         title: session.title.clone(),
+        summary: session.summary.clone(),
         created_at: session.created_at.to_rfc3339(),
         updated_at: session.updated_at.to_rfc3339(),
     }
@@ -431,6 +446,7 @@ fn message_to_response(message: &crate::domain::entities::Message) -> MessageRes
         content: message.message.get_text_content(),
         metadata: message.message.metadata.as_ref().map(|m| serde_json::to_value(m).unwrap_or(Value::Null)),
         created_at: message.message.timestamp.to_rfc3339(),
+        superseded_at: message.superseded_at.map(|at| at.to_rfc3339()),
     }
 }
 
@@ -449,50 +465,6 @@ fn audit_log_to_response(log: &crate::domain::entities::AuditLog) -> AuditLogRes
     }
 }
 
-fn execution_to_response(exec: &crate::domain::entities::FlowExecutionHistory) -> ExecutionHistoryResponse {
-    ExecutionHistoryResponse {
-        id: exec.id.to_string(),
-        flow_id: exec.flow_id.to_string(),
-        flow_version: exec.flow_version,
-        tenant_id: exec.tenant_id.to_string(),
-        user_id: exec.user_id.to_string(),
-        session_id: exec.session_id.map(|id| id.to_string()),
-        status: format!("{:?}", exec.status),
-        input_data: exec.input_data.clone(),
-        output_data: exec.output_data.clone(),
-        error_message: exec.error_message.clone(),
-        started_at: exec.started_at.to_rfc3339(),
-        completed_at: exec.completed_at.map(|dt| dt.to_rfc3339()),
-        execution_time_ms: exec.execution_time_ms.map(|t| t as i64),
-    }
-}
-
-fn step_to_response(step: &crate::domain::entities::ExecutionStep) -> ExecutionStepResponse {
-    ExecutionStepResponse {
-        id: step.id.to_string(),
-        execution_id: step.execution_id.to_string(),
-        step_name: step.step_name.clone(),
-        step_type: step.step_type.clone(),
-        status: format!("{:?}", step.status),
-        input_data: step.input_data.clone(),
-        output_data: step.output_data.clone(),
-        error_message: step.error_message.clone(),
-        started_at: step.started_at.to_rfc3339(),
-        completed_at: step.completed_at.map(|dt| dt.to_rfc3339()),
-        execution_time_ms: step.execution_time_ms.map(|t| t as i64),
-    }
-}
-
-fn metrics_to_response(metrics: &crate::domain::entities::ExecutionMetrics) -> ExecutionMetricsResponse {
-    ExecutionMetricsResponse {
-        total_steps: metrics.total_steps,
-        successful_steps: metrics.completed_steps,
-        failed_steps: metrics.failed_steps,
-        total_execution_time_ms: metrics.total_execution_time_ms as i64,
-        average_step_time_ms: metrics.average_step_time_ms as i64,
-    }
-}
-
 fn parse_message_role(role: &str) -> Result<MessageRole> {
     match role.to_lowercase().as_str() {
         "user" => Ok(MessageRole::User),
@@ -0,0 +1,141 @@
+use axum::http::HeaderMap;
+use axum::response::sse::Event;
+
+use crate::application::dto::agent_dto::AgentChatStreamChunk;
+use crate::domain::services::ChatStreamEvent;
+use crate::error::PlatformError;
+
+/// Header an EventSource client sends when reconnecting
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// SSE event types of a chat stream
+const EVENT_TYPES: [&str; 4] = ["content", "reasoning", "done", "error"];
+
+/// Turns the chunks of a chat stream into SSE events. Every event carries
+/// its chunk type as the event name and an id that increases by one per
+/// event, so a reconnecting client can say where it left off.
+#[derive(Debug)]
+pub struct StreamingSSEFormatter {
+    next_id: u64,
+}
+
+impl StreamingSSEFormatter {
+    pub fn new() -> Self {
+        Self { next_id: 1 }
+    }
+
+    /// Format a chunk as the next event of the stream
+    pub fn format(&mut self, chunk: &AgentChatStreamChunk) -> ChatStreamEvent {
+        let event = if EVENT_TYPES.contains(&chunk.chunk_type.as_str()) {
+            chunk.chunk_type.clone()
+        } else {
+            "content".to_string()
+        };
+        let data = serde_json::to_string(chunk).unwrap_or_else(|_| "{}".to_string());
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        ChatStreamEvent { id, event, data }
+    }
+
+    /// Format a failure of the stream itself as an `error` event
+    pub fn format_error(&mut self, error: &PlatformError) -> ChatStreamEvent {
+        self.format(&AgentChatStreamChunk {
+            chunk_type: "error".to_string(),
+            content: None,
+            reasoning_content: None,
+            session_id: None,
+            message_id: None,
+            reply_id: None,
+            metadata: None,
+            finish_reason: None,
+            error: Some(error.to_string()),
+        })
+    }
+
+    /// Render an event in the SSE wire format
+    pub fn to_sse_event(event: &ChatStreamEvent) -> Event {
+        Event::default()
+            .id(event.id.to_string())
+            .event(&event.event)
+            .data(&event.data)
+    }
+}
+
+impl Default for StreamingSSEFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The id of the last event a reconnecting client received
+pub fn last_event_id(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn chunk(chunk_type: &str) -> AgentChatStreamChunk {
+        AgentChatStreamChunk {
+            chunk_type: chunk_type.to_string(),
+            content: Some("Hi".to_string()),
+            reasoning_content: None,
+            session_id: Some(Uuid::new_v4()),
+            message_id: None,
+            reply_id: None,
+            metadata: None,
+            finish_reason: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_format_assigns_increasing_ids() {
+        let mut formatter = StreamingSSEFormatter::new();
+
+        let first = formatter.format(&chunk("reasoning"));
+        let second = formatter.format(&chunk("content"));
+        let last = formatter.format(&chunk("done"));
+
+        assert_eq!((first.id, first.event.as_str()), (1, "reasoning"));
+        assert_eq!((second.id, second.event.as_str()), (2, "content"));
+        assert_eq!((last.id, last.event.as_str()), (3, "done"));
+        assert!(last.is_terminal());
+
+        let data: serde_json::Value = serde_json::from_str(&second.data).unwrap();
+        assert_eq!(data["type"], "content");
+        assert_eq!(data["content"], "Hi");
+    }
+
+    #[test]
+    fn test_format_error_and_unknown_types() {
+        let mut formatter = StreamingSSEFormatter::new();
+
+        assert_eq!(formatter.format(&chunk("delta")).event, "content");
+
+        let error = formatter.format_error(&PlatformError::InternalError("boom".to_string()));
+        assert_eq!((error.id, error.event.as_str()), (2, "error"));
+        assert!(error.is_terminal());
+        let data: serde_json::Value = serde_json::from_str(&error.data).unwrap();
+        assert!(data["error"].as_str().unwrap().contains("boom"));
+    }
+
+    #[test]
+    fn test_last_event_id() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(last_event_id(&headers), None);
+
+        headers.insert(LAST_EVENT_ID_HEADER, "42".parse().unwrap());
+        assert_eq!(last_event_id(&headers), Some(42));
+
+        headers.insert(LAST_EVENT_ID_HEADER, "abc".parse().unwrap());
+        assert_eq!(last_event_id(&headers), None);
+    }
+}
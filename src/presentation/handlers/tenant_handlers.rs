@@ -0,0 +1,97 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    application::services::TenantApplicationService,
+    domain::{entities::QuotaConfig, value_objects::TenantId},
+    error::Result,
+    presentation::extractors::AuthenticatedUser,
+};
+
+/// Replace a tenant's quota. Omitted or null limits mean unlimited.
+pub async fn set_tenant_quota(
+    State(service): State<Arc<dyn TenantApplicationService>>,
+    Path(tenant_id): Path<Uuid>,
+    Json(quota): Json<QuotaConfig>,
+) -> Result<impl IntoResponse> {
+    let quota = service.set_quota(TenantId::from_uuid(tenant_id), quota).await?;
+    Ok(Json(quota))
+}
+
+/// Current usage of a tenant next to its quota
+pub async fn get_tenant_usage(
+    State(service): State<Arc<dyn TenantApplicationService>>,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let usage = service.get_usage(TenantId::from_uuid(tenant_id)).await?;
+    Ok(Json(usage))
+}
+
+#[derive(Deserialize)]
+pub struct SetSecretRequest {
+    pub value: String,
+}
+
+impl std::fmt::Debug for SetSecretRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetSecretRequest")
+            .field("value", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Set a secret used by `secret` flow environment variables
+pub async fn set_tenant_secret(
+    State(service): State<Arc<dyn TenantApplicationService>>,
+    Path((tenant_id, name)): Path<(Uuid, String)>,
+    Json(request): Json<SetSecretRequest>,
+) -> Result<impl IntoResponse> {
+    service
+        .set_secret(TenantId::from_uuid(tenant_id), name, request.value)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_tenant_secret(
+    State(service): State<Arc<dyn TenantApplicationService>>,
+    Path((tenant_id, name)): Path<(Uuid, String)>,
+) -> Result<impl IntoResponse> {
+    service.delete_secret(TenantId::from_uuid(tenant_id), name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Secret names only; values cannot be read back
+pub async fn list_tenant_secrets(
+    State(service): State<Arc<dyn TenantApplicationService>>,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let names = service.list_secret_names(TenantId::from_uuid(tenant_id)).await?;
+    Ok(Json(serde_json::json!({ "names": names })))
+}
+
+/// Set a secret of the caller's tenant
+pub async fn set_secret(
+    State(service): State<Arc<dyn TenantApplicationService>>,
+    user: AuthenticatedUser,
+    Path(key): Path<String>,
+    Json(request): Json<SetSecretRequest>,
+) -> Result<impl IntoResponse> {
+    service.set_secret(user.tenant_id, key, request.value).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_secret(
+    State(service): State<Arc<dyn TenantApplicationService>>,
+    user: AuthenticatedUser,
+    Path(key): Path<String>,
+) -> Result<impl IntoResponse> {
+    service.delete_secret(user.tenant_id, key).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Multipart, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        dto::{FileUpload, UpdateProfileDto, UserProfileDto},
+        services::UserApplicationService,
+    },
+    error::{PlatformError, Result},
+    presentation::extractors::AuthenticatedUser,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AvatarUploadResponse {
+    pub avatar_url: String,
+}
+
+/// Profile of the signed-in user
+pub async fn get_me(
+    user: AuthenticatedUser,
+    State(service): State<Arc<dyn UserApplicationService>>,
+) -> Result<Json<UserProfileDto>> {
+    let profile = service.get_profile(user.user_id).await?;
+    Ok(Json(profile))
+}
+
+/// Update nickname, email or language of the signed-in user
+pub async fn update_me(
+    user: AuthenticatedUser,
+    State(service): State<Arc<dyn UserApplicationService>>,
+    Json(request): Json<UpdateProfileDto>,
+) -> Result<Json<UserProfileDto>> {
+    let profile = service.update_profile(user.user_id, request).await?;
+    Ok(Json(profile))
+}
+
+/// Replace the signed-in user's avatar with the image in the `file` field
+pub async fn upload_avatar(
+    user: AuthenticatedUser,
+    State(service): State<Arc<dyn UserApplicationService>>,
+    mut multipart: Multipart,
+) -> Result<Json<AvatarUploadResponse>> {
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        PlatformError::ValidationError(format!("Failed to read multipart field: {}", e))
+    })? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field.file_name().unwrap_or("avatar").to_string();
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let data = field.bytes().await.map_err(|e| {
+            PlatformError::ValidationError(format!("Failed to read file data: {}", e))
+        })?;
+
+        let avatar_url = service
+            .upload_avatar(
+                user.user_id,
+                FileUpload {
+                    filename,
+                    content_type,
+                    data: data.to_vec(),
+                },
+            )
+            .await?;
+
+        return Ok(Json(AvatarUploadResponse { avatar_url }));
+    }
+
+    Err(PlatformError::ValidationError("No file field found in multipart form".to_string()))
+}
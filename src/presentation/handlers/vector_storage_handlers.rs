@@ -2,6 +2,7 @@ use axum::{
     extract::{Path, Query, State},
     response::Json,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -21,6 +22,10 @@ pub struct UpsertVectorRequest {
     pub vector: Vec<f32>,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     pub namespace: Option<String>,
+    /// Sparse term weights keyed by dimension index, for hybrid search
+    pub sparse_vector: Option<HashMap<u32, f32>>,
+    /// The vector is deleted after this time
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 /// Request to upsert multiple vectors
@@ -38,6 +43,10 @@ pub struct SearchVectorsRequest {
     pub namespace: Option<String>,
     pub include_metadata: Option<bool>,
     pub include_values: Option<bool>,
+    /// Turns the search into a hybrid dense + sparse search
+    pub sparse_vector: Option<HashMap<u32, f32>>,
+    /// Weight of the dense scores in hybrid search, 0.0 to 1.0
+    pub hybrid_weight: Option<f32>,
 }
 
 /// Search filter request
@@ -67,6 +76,8 @@ pub struct DeleteVectorsRequest {
 pub struct BatchOperationRequest {
     pub upsert: Option<Vec<UpsertVectorRequest>>,
     pub delete: Option<Vec<String>>,
+    /// Expiry for upserted vectors that do not set their own
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 /// Response for vector operations
@@ -218,6 +229,14 @@ pub async fn upsert_vector(
         record = record.with_namespace(namespace);
     }
     
+    if let Some(sparse_vector) = request.sparse_vector {
+        record = record.with_sparse_vector(sparse_vector);
+    }
+    
+    if let Some(expires_at) = request.expires_at {
+        record = record.with_expires_at(expires_at);
+    }
+    
     service.upsert_vector(user.tenant_id, record).await?;
     
     Ok(Json(VectorOperationResponse {
@@ -247,6 +266,14 @@ pub async fn upsert_vectors_batch(
             record = record.with_namespace(namespace);
         }
         
+        if let Some(sparse_vector) = vector_req.sparse_vector {
+            record = record.with_sparse_vector(sparse_vector);
+        }
+        
+        if let Some(expires_at) = vector_req.expires_at {
+            record = record.with_expires_at(expires_at);
+        }
+        
         records.push(record);
     }
     
@@ -287,6 +314,15 @@ pub async fn search_vectors(
         search_query = search_query.include_values(include_values);
     }
     
+    if let Some(sparse_vector) = request.sparse_vector {
+        search_query = search_query.with_sparse_vector(sparse_vector);
+    }
+    
+    if let Some(hybrid_weight) = request.hybrid_weight {
+        search_query = search_query.with_hybrid_weight(hybrid_weight)
+            .map_err(|e| PlatformError::ValidationError(e))?;
+    }
+    
     let results = if let Some(config_id_str) = query_params.config_id {
         let config_id = crate::domain::value_objects::ConfigId::from_string(&config_id_str)
             .map_err(|_| PlatformError::ValidationError("Invalid config ID format".to_string()))?;
@@ -333,6 +369,10 @@ pub async fn execute_batch_operation(
     let mut batch = BatchOperation::new();
     let mut total_operations = 0;
     
+    if let Some(expires_at) = request.expires_at {
+        batch = batch.with_expires_at(expires_at);
+    }
+    
     if let Some(upsert_requests) = request.upsert {
         for vector_req in upsert_requests {
             let mut record = VectorRecord::new(vector_req.id, vector_req.vector, user.tenant_id)
@@ -346,6 +386,14 @@ pub async fn execute_batch_operation(
                 record = record.with_namespace(namespace);
             }
             
+            if let Some(sparse_vector) = vector_req.sparse_vector {
+                record = record.with_sparse_vector(sparse_vector);
+            }
+            
+            if let Some(expires_at) = vector_req.expires_at {
+                record = record.with_expires_at(expires_at);
+            }
+            
             batch = batch.add_upsert(record);
             total_operations += 1;
         }
@@ -425,6 +473,15 @@ pub async fn multi_store_search(
         search_query = search_query.include_values(include_values);
     }
     
+    if let Some(sparse_vector) = request.sparse_vector {
+        search_query = search_query.with_sparse_vector(sparse_vector);
+    }
+    
+    if let Some(hybrid_weight) = request.hybrid_weight {
+        search_query = search_query.with_hybrid_weight(hybrid_weight)
+            .map_err(|e| PlatformError::ValidationError(e))?;
+    }
+    
     let max_results_per_store = query_params.max_results_per_store.unwrap_or(10);
     let aggregate = query_params.aggregate.unwrap_or(false);
     
@@ -489,6 +546,8 @@ mod tests {
             vector: vec![1.0, 2.0, 3.0],
             metadata: None,
             namespace: None,
+            sparse_vector: None,
+            expires_at: None,
         };
         
         assert_eq!(request.id, "test_vector");
@@ -10,10 +10,10 @@ use uuid::Uuid;
 use crate::{
     application::{
         services::{AuthApplicationService, APIKeyApplicationService},
-        dto::{AuthContext, APIKeyAuthContext},
+        dto::{AuthContext, APIKeyAuthContext, APIKeyTokenUsage},
     },
     domain::entities::AuditContext,
-    error::{PlatformError, Result},
+    error::{rate_limit_response, PlatformError, Result},
 };
 
 /// Authentication middleware that validates JWT tokens
@@ -105,6 +105,40 @@ pub fn tenant_permission_middleware(
     }
 }
 
+/// Users allowed to call admin-only endpoints. Admins are matched by user
+/// ID: usernames are only unique within a tenant.
+#[derive(Debug, Clone, Default)]
+pub struct AdminPolicy {
+    user_ids: std::collections::HashSet<uuid::Uuid>,
+}
+
+impl AdminPolicy {
+    pub fn new(user_ids: impl IntoIterator<Item = uuid::Uuid>) -> Self {
+        Self {
+            user_ids: user_ids.into_iter().collect(),
+        }
+    }
+
+    pub fn is_admin(&self, auth_context: &AuthContext) -> bool {
+        self.user_ids.contains(&auth_context.user_id)
+    }
+}
+
+/// Admin middleware; must run after `auth_middleware`
+pub async fn require_admin(
+    State(policy): State<Arc<AdminPolicy>>,
+    request: Request,
+    next: Next,
+) -> std::result::Result<Response, PlatformError> {
+    let auth_context = extract_auth_context(&request)?;
+
+    if !policy.is_admin(auth_context) {
+        return Err(PlatformError::Forbidden("Admin access required".to_string()));
+    }
+
+    Ok(next.run(request).await)
+}
+
 /// Extract authentication context from request
 pub fn extract_auth_context(request: &Request) -> std::result::Result<&AuthContext, PlatformError> {
     request
@@ -125,8 +159,10 @@ pub fn extract_optional_auth_context(request: &Request) -> Option<&AuthContext>
 /// - Validates the API key format (must start with "pk_")
 /// - Calls the APIKeyApplicationService to validate the token
 /// - Logs authentication attempts (success and failure) via audit service
+/// - Enforces the key's `rate_limit_rpm`, answering 429 with Retry-After when exceeded
 /// - Injects APIKeyAuthContext into request extensions for downstream handlers
-/// - Updates the last_used timestamp for the API key (async, fire-and-forget)
+/// - Counts the request, and any `APIKeyTokenUsage` the handler sets on the
+///   response, against the key (async, fire-and-forget)
 ///
 /// # Errors
 ///
@@ -179,24 +215,42 @@ pub async fn api_key_auth_middleware(
         .await
         .map_err(|e| {
             // Log authentication failure and return generic error
-            log::warn!("API key authentication failed: {}", e);
+            tracing::warn!("API key authentication failed: {}", e);
             PlatformError::AuthenticationFailed("Invalid or expired API key".to_string())
         })?;
 
-    // Update last used timestamp (fire and forget)
-    let api_key_id = crate::domain::value_objects::APIKeyId(api_key_context.api_key_id);
-    let service = api_key_service.clone();
-    tokio::spawn(async move {
-        if let Err(e) = service.update_last_used(api_key_id).await {
-            log::warn!("Failed to update API key last_used timestamp: {}", e);
+    // Enforce the per-key request limit; fail open if the counter is unavailable
+    match api_key_service.acquire_request(&api_key_context).await {
+        Ok(Some(retry_after)) => {
+            return Ok(rate_limit_response(
+                "API key rate limit exceeded, please retry later".to_string(),
+                retry_after.as_millis().div_ceil(1000) as u64,
+            ));
         }
-    });
+        Ok(None) => {}
+        Err(e) => tracing::warn!("API key rate limit check failed, allowing request: {}", e),
+    }
+
+    let api_key_id = crate::domain::value_objects::APIKeyId(api_key_context.api_key_id);
 
     // Add API key auth context to request extensions
     request.extensions_mut().insert(api_key_context);
 
     // Continue to next middleware/handler
-    Ok(next.run(request).await)
+    let response = next.run(request).await;
+
+    // Record usage (fire and forget)
+    let tokens_used = response
+        .extensions()
+        .get::<APIKeyTokenUsage>()
+        .map_or(0, |usage| usage.0);
+    tokio::spawn(async move {
+        if let Err(e) = api_key_service.record_usage(api_key_id, tokens_used).await {
+            tracing::warn!("Failed to record API key usage: {}", e);
+        }
+    });
+
+    Ok(response)
 }
 
 /// Extract API key authentication context from request
@@ -287,12 +341,12 @@ pub fn require_resource_permission(
                 .check_permission(api_key_id, resource_type, resource_id, Some(audit_context))
                 .await
                 .map_err(|e| {
-                    log::error!("Error checking API key permission: {}", e);
+                    tracing::error!("Error checking API key permission: {}", e);
                     PlatformError::InternalError("Failed to check permissions".to_string())
                 })?;
 
             if !has_permission {
-                log::warn!(
+                tracing::warn!(
                     "API key {} denied access to {} resource {}",
                     api_key_context.api_key_id,
                     resource_type.as_str(),
@@ -418,6 +472,7 @@ mod tests {
                 mcp_tool_ids: vec![],
                 vector_store_ids: vec![],
             },
+            rate_limit_rpm: None,
         };
 
         let mut request = Request::new(Body::empty());
@@ -15,7 +15,7 @@ mod tests {
         error::PlatformError,
         presentation::middleware::{
             auth_middleware, optional_auth_middleware,
-            extract_auth_context, extract_optional_auth_context
+            extract_auth_context, extract_optional_auth_context, AdminPolicy
         },
     };
     use mockall::predicate::*;
@@ -43,6 +43,35 @@ mod tests {
         assert_eq!(extracted.username, "testuser");
     }
 
+    #[test]
+    fn test_admin_policy_matches_user_id() {
+        let auth_context = create_test_auth_context();
+
+        assert!(AdminPolicy::new(vec![auth_context.user_id]).is_admin(&auth_context));
+        assert!(!AdminPolicy::new(vec![Uuid::new_v4()]).is_admin(&auth_context));
+        assert!(!AdminPolicy::default().is_admin(&auth_context));
+    }
+
+    #[test]
+    fn test_admin_policy_rejects_same_username_in_other_tenant() {
+        let admin = create_test_auth_context();
+        let policy = AdminPolicy::new(vec![admin.user_id]);
+
+        // Another tenant registers a user with the admin's username
+        let impostor = AuthContext::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            admin.username.clone(),
+            None,
+            Uuid::new_v4(),
+            None,
+            None,
+        );
+
+        assert!(policy.is_admin(&admin));
+        assert!(!policy.is_admin(&impostor));
+    }
+
     #[test]
     fn test_extract_auth_context_missing() {
         let request = Request::new(Body::empty());
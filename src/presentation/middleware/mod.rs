@@ -1,8 +1,9 @@
 pub mod auth_middleware;
 pub mod rate_limit_middleware;
+pub mod request_metrics_middleware;
 
 #[cfg(test)]
 mod auth_middleware_test;
 
 pub use auth_middleware::*;
-pub use rate_limit_middleware::*;
\ No newline at end of file
+pub use rate_limit_middleware::*;pub use request_metrics_middleware::*;
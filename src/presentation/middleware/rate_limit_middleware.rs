@@ -2,155 +2,187 @@
 // Requirement 1.4: Implement API rate limiting
 
 use axum::{
-    body::Body,
-    extract::Request,
-    http::StatusCode,
+    extract::{Request, State},
     middleware::Next,
-    response::{IntoResponse, Response},
+    response::Response,
 };
-use redis::Client;
 use std::sync::Arc;
-use std::time::Duration;
+
+use crate::application::dto::{APIKeyAuthContext, AuthContext};
+use crate::config::RateLimitConfig;
+use crate::error::{rate_limit_response, Result};
+use crate::infrastructure::{RedisCache, TOKEN_BUCKET_SCRIPT};
+
+/// Outcome of taking a token from a bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    Allowed,
+    Limited { retry_after_secs: u64 },
+}
 
 pub struct RateLimiter {
-    redis_client: Arc<Client>,
-    max_requests: u32,
-    window: Duration,
+    cache: Arc<RedisCache>,
+    script: redis::Script,
+    config: RateLimitConfig,
 }
 
 impl RateLimiter {
-    pub fn new(redis_client: Arc<Client>, max_requests: u32, window: Duration) -> Self {
+    pub fn new(cache: Arc<RedisCache>, config: RateLimitConfig) -> Self {
         Self {
-            redis_client,
-            max_requests,
-            window,
+            cache,
+            script: redis::Script::new(TOKEN_BUCKET_SCRIPT),
+            config,
         }
     }
 
-    async fn check_rate_limit(&self, key: &str) -> Result<bool, redis::RedisError> {
-        let mut conn = self.redis_client.get_async_connection().await?;
-        
-        // Increment counter
-        let count: u32 = redis::cmd("INCR")
-            .arg(key)
-            .query_async(&mut conn)
+    async fn check_rate_limit(&self, key: &str) -> Result<RateLimitDecision> {
+        let refill_per_ms = self.config.requests_per_minute.max(1) as f64 / 60_000.0;
+        let capacity = self.config.burst_size.max(1);
+
+        let (allowed, retry_after_ms): (i64, i64) = self
+            .cache
+            .invoke_script(
+                self.script
+                    .key(key)
+                    .arg(capacity)
+                    .arg(refill_per_ms.to_string()),
+            )
             .await?;
-        
-        // Set expiry on first request
-        if count == 1 {
-            redis::cmd("EXPIRE")
-                .arg(key)
-                .arg(self.window.as_secs())
-                .query_async::<_, ()>(&mut conn)
-                .await?;
+
+        if allowed == 1 {
+            Ok(RateLimitDecision::Allowed)
+        } else {
+            Ok(RateLimitDecision::Limited {
+                retry_after_secs: Self::retry_after_secs(retry_after_ms),
+            })
         }
-        
-        Ok(count <= self.max_requests)
+    }
+
+    /// Round up to whole seconds, as `Retry-After` has no finer resolution
+    fn retry_after_secs(retry_after_ms: i64) -> u64 {
+        ((retry_after_ms.max(0) as u64) + 999) / 1000
+    }
+
+    fn rate_limit_key(req: &Request) -> String {
+        format!(
+            "rate_limit:{}:{}",
+            Self::extract_client_id(req),
+            Self::endpoint_prefix(req.uri().path())
+        )
     }
 
     fn extract_client_id(req: &Request) -> String {
         // Extract client identifier from request
-        // Priority: User ID from auth > IP address
-        
-        // Try to get user ID from headers (set by auth middleware)
-        if let Some(user_id) = req.headers().get("x-user-id") {
-            if let Ok(id) = user_id.to_str() {
-                return format!("user:{}", id);
-            }
+        // Priority: Tenant from auth > IP address
+
+        if let Some(auth_context) = req.extensions().get::<AuthContext>() {
+            return format!("tenant:{}", auth_context.tenant_id);
+        }
+
+        if let Some(api_key_context) = req.extensions().get::<APIKeyAuthContext>() {
+            return format!("tenant:{}", api_key_context.tenant_id);
         }
-        
+
         // Fall back to IP address
         if let Some(forwarded) = req.headers().get("x-forwarded-for") {
             if let Ok(ip) = forwarded.to_str() {
-                return format!("ip:{}", ip.split(',').next().unwrap_or("unknown"));
+                return format!("ip:{}", ip.split(',').next().unwrap_or("unknown").trim());
             }
         }
-        
+
         if let Some(real_ip) = req.headers().get("x-real-ip") {
             if let Ok(ip) = real_ip.to_str() {
                 return format!("ip:{}", ip);
             }
         }
-        
+
         // Last resort
         "ip:unknown".to_string()
     }
+
+    /// First two path segments, e.g. `/api/agents` for `/api/agents/{id}/chat`
+    fn endpoint_prefix(path: &str) -> String {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).take(2).collect();
+        format!("/{}", segments.join("/"))
+    }
 }
 
+/// Must run after the auth middleware so requests are keyed by tenant
 pub async fn rate_limit_middleware(
-    rate_limiter: Arc<RateLimiter>,
+    State(rate_limiter): State<Arc<RateLimiter>>,
     req: Request,
     next: Next,
 ) -> Response {
-    let client_id = RateLimiter::extract_client_id(&req);
-    let rate_limit_key = format!("rate_limit:{}", client_id);
-    
+    if !rate_limiter.config.enabled {
+        return next.run(req).await;
+    }
+
+    let rate_limit_key = RateLimiter::rate_limit_key(&req);
+
     match rate_limiter.check_rate_limit(&rate_limit_key).await {
-        Ok(allowed) => {
-            if allowed {
-                next.run(req).await
-            } else {
-                (
-                    StatusCode::TOO_MANY_REQUESTS,
-                    "Rate limit exceeded. Please try again later.",
-                )
-                    .into_response()
-            }
-        }
+        Ok(RateLimitDecision::Allowed) => next.run(req).await,
+        Ok(RateLimitDecision::Limited { retry_after_secs }) => rate_limit_response(
+            "Rate limit exceeded. Please try again later.".to_string(),
+            retry_after_secs,
+        ),
         Err(e) => {
-            log::error!("Rate limit check failed: {}", e);
+            tracing::error!("Rate limit check failed: {}", e);
             // On error, allow the request (fail open)
             next.run(req).await
         }
     }
 }
 
-// Different rate limit tiers
-pub struct RateLimitConfig {
-    pub default_max_requests: u32,
-    pub default_window: Duration,
-    pub authenticated_max_requests: u32,
-    pub authenticated_window: Duration,
-}
-
-impl Default for RateLimitConfig {
-    fn default() -> Self {
-        Self {
-            default_max_requests: 100,
-            default_window: Duration::from_secs(60),
-            authenticated_max_requests: 1000,
-            authenticated_window: Duration::from_secs(60),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::body::Body;
+    use uuid::Uuid;
 
     #[test]
-    fn test_extract_client_id() {
-        use axum::http::Request;
-        
-        let req = Request::builder()
-            .header("x-user-id", "user-123")
+    fn test_extract_client_id_from_auth_context() {
+        let tenant_id = Uuid::new_v4();
+        let mut req = axum::http::Request::builder()
+            .uri("/api/agents")
             .body(Body::empty())
             .unwrap();
-        
+        req.extensions_mut().insert(AuthContext {
+            user_id: Uuid::new_v4(),
+            tenant_id,
+            username: "testuser".to_string(),
+            nickname: None,
+            token_id: Uuid::new_v4(),
+            ip_address: None,
+            user_agent: None,
+        });
+
         let client_id = RateLimiter::extract_client_id(&req);
-        assert_eq!(client_id, "user:user-123");
+        assert_eq!(client_id, format!("tenant:{}", tenant_id));
     }
 
     #[test]
     fn test_extract_client_id_from_ip() {
-        use axum::http::Request;
-        
-        let req = Request::builder()
+        let req = axum::http::Request::builder()
             .header("x-forwarded-for", "192.168.1.1, 10.0.0.1")
             .body(Body::empty())
             .unwrap();
-        
+
         let client_id = RateLimiter::extract_client_id(&req);
         assert_eq!(client_id, "ip:192.168.1.1");
     }
+
+    #[test]
+    fn test_endpoint_prefix() {
+        assert_eq!(RateLimiter::endpoint_prefix("/api/agents/123/chat"), "/api/agents");
+        assert_eq!(RateLimiter::endpoint_prefix("/api/flows"), "/api/flows");
+        assert_eq!(RateLimiter::endpoint_prefix("/"), "/");
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up() {
+        assert_eq!(RateLimiter::retry_after_secs(1), 1);
+        assert_eq!(RateLimiter::retry_after_secs(1000), 1);
+        assert_eq!(RateLimiter::retry_after_secs(1001), 2);
+        assert_eq!(RateLimiter::retry_after_secs(-5), 0);
+    }
 }
@@ -0,0 +1,162 @@
+// Request count and latency metrics for the Prometheus endpoint
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Upper bounds of the latency histogram buckets, in seconds
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+#[derive(Debug, Clone, Default)]
+struct LatencyHistogram {
+    /// Non-cumulative counts per bucket; the extra slot holds `+Inf`
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    sum_seconds: f64,
+    count: u64,
+}
+
+impl LatencyHistogram {
+    fn observe(&mut self, seconds: f64) {
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.sum_seconds += seconds;
+        self.count += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+struct MetricsInner {
+    /// Keyed by (method, status)
+    requests: BTreeMap<(String, u16), u64>,
+    /// Keyed by method
+    latencies: BTreeMap<String, LatencyHistogram>,
+}
+
+/// In-process request counters. Labels are limited to method and status so
+/// that arbitrary paths cannot blow up the series count.
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    inner: Mutex<MetricsInner>,
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, method: &str, status: u16, elapsed: Duration) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        *inner
+            .requests
+            .entry((method.to_string(), status))
+            .or_insert(0) += 1;
+        inner
+            .latencies
+            .entry(method.to_string())
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Render the counters in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        out.push_str("# HELP http_requests_total Total number of HTTP requests\n");
+        out.push_str("# TYPE http_requests_total counter\n");
+        for ((method, status), count) in &inner.requests {
+            let _ = writeln!(
+                out,
+                "http_requests_total{{method=\"{}\",status=\"{}\"}} {}",
+                method, status, count
+            );
+        }
+
+        out.push_str("# HELP http_request_duration_seconds HTTP request latency in seconds\n");
+        out.push_str("# TYPE http_request_duration_seconds histogram\n");
+        for (method, histogram) in &inner.latencies {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets.iter()) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "http_request_duration_seconds_bucket{{method=\"{}\",le=\"{}\"}} {}",
+                    method, bound, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "http_request_duration_seconds_bucket{{method=\"{}\",le=\"+Inf\"}} {}",
+                method, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "http_request_duration_seconds_sum{{method=\"{}\"}} {}",
+                method, histogram.sum_seconds
+            );
+            let _ = writeln!(
+                out,
+                "http_request_duration_seconds_count{{method=\"{}\"}} {}",
+                method, histogram.count
+            );
+        }
+
+        out
+    }
+}
+
+/// Outermost layer, so the timing covers auth, rate limiting and handlers
+pub async fn request_metrics_middleware(
+    State(metrics): State<Arc<RequestMetrics>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().as_str().to_string();
+    let start = Instant::now();
+
+    let response = next.run(req).await;
+
+    metrics.record(&method, response.status().as_u16(), start.elapsed());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_by_method_and_status() {
+        let metrics = RequestMetrics::new();
+        metrics.record("GET", 200, Duration::from_millis(3));
+        metrics.record("GET", 200, Duration::from_millis(30));
+        metrics.record("POST", 500, Duration::from_secs(20));
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("http_requests_total{method=\"GET\",status=\"200\"} 2"));
+        assert!(text.contains("http_requests_total{method=\"POST\",status=\"500\"} 1"));
+    }
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let metrics = RequestMetrics::new();
+        metrics.record("GET", 200, Duration::from_millis(3));
+        metrics.record("GET", 200, Duration::from_millis(30));
+        metrics.record("GET", 200, Duration::from_secs(20));
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("http_request_duration_seconds_bucket{method=\"GET\",le=\"0.005\"} 1"));
+        assert!(text.contains("http_request_duration_seconds_bucket{method=\"GET\",le=\"0.05\"} 2"));
+        assert!(text.contains("http_request_duration_seconds_bucket{method=\"GET\",le=\"10\"} 2"));
+        assert!(text.contains("http_request_duration_seconds_bucket{method=\"GET\",le=\"+Inf\"} 3"));
+        assert!(text.contains("http_request_duration_seconds_count{method=\"GET\"} 3"));
+    }
+}
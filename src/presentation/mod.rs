@@ -3,5 +3,6 @@ pub mod handlers;
 pub mod middleware;
 pub mod routes;
 pub mod extractors;
+pub mod shutdown;
 
 pub use server::*;
\ No newline at end of file
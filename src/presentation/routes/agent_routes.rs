@@ -1,14 +1,32 @@
 use axum::{
+    middleware,
     routing::{delete, get, post, put},
     Router,
 };
 use std::sync::Arc;
 
 use crate::{
-    application::services::AgentApplicationService,
-    presentation::handlers::agent_handlers,
+    application::services::{AgentApplicationService, AgentImportExportService, APIKeyApplicationService},
+    presentation::{
+        handlers::{agent_handlers, agent_ws_handlers},
+        middleware::api_key_auth_middleware,
+    },
 };
 
+/// Agent routes for clients authenticated with a `pk_` API key
+pub fn api_key_agent_routes(
+    service: Arc<dyn AgentApplicationService>,
+    api_key_service: Arc<APIKeyApplicationService>,
+) -> Router {
+    Router::new()
+        .route("/v1/api-keys/me/agent", get(agent_handlers::get_api_key_agent))
+        .route_layer(middleware::from_fn_with_state(
+            api_key_service,
+            api_key_auth_middleware,
+        ))
+        .with_state(service)
+}
+
 /// Create agent management routes
 pub fn agent_routes(service: Arc<dyn AgentApplicationService>) -> Router {
     Router::new()
@@ -21,6 +39,7 @@ pub fn agent_routes(service: Arc<dyn AgentApplicationService>) -> Router {
         
         // Copy operation
         .route("/agents/{agent_id}/copy", post(agent_handlers::copy_agent))
+        .route("/v1/agents/{agent_id}/copies", get(agent_handlers::list_agent_copies))
         
         // Employment management
         .route("/agents/{agent_id}/employ", post(agent_handlers::employ_agent))
@@ -38,6 +57,13 @@ pub fn agent_routes(service: Arc<dyn AgentApplicationService>) -> Router {
         // Chat
         .route("/agents/{agent_id}/chat", post(agent_handlers::chat_with_agent))
         .route("/agents/{agent_id}/chat/stream", post(agent_handlers::chat_with_agent_stream))
+        .route("/agents/{agent_id}/chat/ws", get(agent_ws_handlers::chat_with_agent_ws))
+        .route("/v1/agents/{agent_id}/preview", post(agent_handlers::preview_agent))
+        
+        // Preset questions
+        .route("/agents/{agent_id}/preset-questions", get(agent_handlers::get_preset_questions))
+        .route("/agents/{agent_id}/preset-questions/suggest", post(agent_handlers::suggest_questions))
+        .route("/v1/agents/{agent_id}/regenerate-preset-questions", post(agent_handlers::regenerate_preset_questions))
         
         // Statistics
         .route("/agents/{agent_id}/stats", get(agent_handlers::get_agent_usage_stats))
@@ -80,6 +106,17 @@ pub fn agent_routes(service: Arc<dyn AgentApplicationService>) -> Router {
             "/agents/{agent_id}/flows/{flow_id}",
             delete(agent_handlers::remove_flow),
         )
+
+        // Flow generation
+        .route("/flows/generate", post(agent_handlers::generate_flow))
         
         .with_state(service)
 }
+
+/// OpenAgents import and export
+pub fn agent_import_export_routes(service: Arc<dyn AgentImportExportService>) -> Router {
+    Router::new()
+        .route("/v1/agents/import", post(agent_handlers::import_agent))
+        .route("/v1/agents/{agent_id}/export", get(agent_handlers::export_agent))
+        .with_state(service)
+}
@@ -18,5 +18,7 @@ pub fn api_key_routes(service: Arc<APIKeyApplicationService>) -> Router {
         .route("/api-keys/{id}", get(api_key_handlers::get_api_key))
         .route("/api-keys/{id}", patch(api_key_handlers::update_api_key))
         .route("/api-keys/{id}", delete(api_key_handlers::delete_api_key))
+        .route("/api-keys/{id}/rotate", post(api_key_handlers::rotate_api_key))
+        .route("/api-keys/{id}/usage-history", get(api_key_handlers::get_api_key_usage_history))
         .with_state(service)
 }
@@ -10,7 +10,7 @@ use crate::{
     presentation::{
         handlers::{
             login_handler, refresh_token_handler, logout_handler,
-            change_password_handler, me_handler, health_handler,
+            change_password_handler, me_handler, health_handler, revoke_token_handler,
         },
         middleware::auth_middleware,
     },
@@ -22,6 +22,7 @@ pub fn create_auth_routes(auth_service: Arc<dyn AuthApplicationService>) -> Rout
     let protected_routes = Router::new()
         .route("/auth/me", get(me_handler))
         .route("/auth/change-password", post(change_password_handler))
+        .route("/auth/revoke", post(revoke_token_handler))
         .route_layer(middleware::from_fn_with_state(
             auth_service.clone(),
             auth_middleware,
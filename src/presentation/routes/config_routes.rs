@@ -18,6 +18,7 @@ pub fn llm_config_routes(service: Arc<dyn LLMApplicationService>) -> Router {
         .route("/config/llm/{config_id}", delete(config_handlers::delete_llm_config))
         .route("/config/llm/{config_id}/set-default", post(config_handlers::set_default_llm_config))
         .route("/config/llm/{config_id}/test", post(config_handlers::test_llm_connection))
+        .route("/v1/llm-configs/{config_id}/test", post(config_handlers::retest_llm_config))
         .route("/llm-providers/{provider}/models", get(config_handlers::get_available_models))
         .with_state(service)
 }
@@ -9,5 +9,7 @@ use crate::{
 pub fn file_routes(service: Arc<dyn FileApplicationService>) -> Router {
     Router::new()
         .route("/files/upload", post(file_handlers::upload_file))
+        .route("/files/presigned-upload", post(file_handlers::presign_upload))
+        .route("/files/confirm-upload", post(file_handlers::confirm_upload))
         .with_state(service)
 }
@@ -1,11 +1,11 @@
 use axum::{
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
 
 use crate::{
-    application::services::FlowApplicationService,
+    application::services::{AsyncFlowExecutor, BatchFlowExecutor, FlowApplicationService, FlowImportExportService},
     presentation::handlers::flow_handlers,
 };
 
@@ -14,29 +14,66 @@ pub fn flow_routes(service: Arc<dyn FlowApplicationService>) -> Router {
         // Flow CRUD
         .route("/flows", post(flow_handlers::create_flow))
         .route("/flows", get(flow_handlers::list_flows))
+        .route("/v1/flows/search", get(flow_handlers::search_flows))
         .route("/flows/{flow_id}", get(flow_handlers::get_flow))
         .route("/flows/{flow_id}", put(flow_handlers::update_flow))
         .route("/flows/{flow_id}", delete(flow_handlers::delete_flow))
+        .route("/v1/flows/{flow_id}/clone", post(flow_handlers::clone_flow))
         
         // Flow status management
         .route("/flows/{flow_id}/activate", post(flow_handlers::activate_flow))
         .route("/flows/{flow_id}/archive", post(flow_handlers::archive_flow))
+        .route("/v1/flows/{flow_id}/publish", post(flow_handlers::publish_flow))
+        .route("/v1/flows/{flow_id}/unpublish", post(flow_handlers::unpublish_flow))
+        .route("/v1/flows/{flow_id}/enable", patch(flow_handlers::enable_flow))
+        .route("/v1/flows/{flow_id}/disable", patch(flow_handlers::disable_flow))
         
         // DSL import and validation
         .route("/flows/import-dsl", post(flow_handlers::import_from_dsl))
+        .route("/flows/import-langchain", post(flow_handlers::import_from_langchain))
         .route("/flows/validate-definition", post(flow_handlers::validate_definition))
         
         // Flow execution
         .route("/flows/{flow_id}/execute", post(flow_handlers::execute_flow))
+        .route("/flows/{flow_id}/dry-run", post(flow_handlers::dry_run_flow))
         .route("/flows/{flow_id}/executions/{execution_id}", get(flow_handlers::get_execution_status))
         .route("/flows/{flow_id}/executions", get(flow_handlers::list_executions))
         
         // Version management
         .route("/flows/{flow_id}/versions", post(flow_handlers::create_version))
         .route("/flows/{flow_id}/versions", get(flow_handlers::get_versions))
+        .route("/flows/{flow_id}/versions/{version}", get(flow_handlers::get_flow_at_version))
         .route("/flows/{flow_id}/rollback", post(flow_handlers::rollback_to_version))
+        
+        // Review annotations
+        .route("/flows/{flow_id}/nodes/{node_id}/annotations", post(flow_handlers::add_annotation))
+        .route("/flows/{flow_id}/annotations", get(flow_handlers::list_annotations))
+        .route("/flows/{flow_id}/annotations/{annotation_id}/resolve", post(flow_handlers::resolve_annotation))
 
         .route("/flow-executions/{execution_id}", get(flow_handlers::get_execution_status))
+        .route("/v1/flow-executions/{execution_id}", get(flow_handlers::get_execution_status))
         
         .with_state(service)
 }
+
+/// Dify DSL import and export
+pub fn flow_import_export_routes(service: Arc<dyn FlowImportExportService>) -> Router {
+    Router::new()
+        .route("/flows/import", post(flow_handlers::import_flow))
+        .route("/flows/{flow_id}/export", get(flow_handlers::export_flow))
+        .with_state(service)
+}
+
+/// Batch execution of one flow over many input sets
+pub fn batch_execution_routes(executor: Arc<BatchFlowExecutor>) -> Router {
+    Router::new()
+        .route("/flows/{flow_id}/batch-execute", post(flow_handlers::batch_execute_flow))
+        .with_state(executor)
+}
+
+/// Background execution reported to a callback URL
+pub fn async_execution_routes(executor: Arc<AsyncFlowExecutor>) -> Router {
+    Router::new()
+        .route("/v1/flows/{flow_id}/execute-async", post(flow_handlers::execute_flow_async))
+        .with_state(executor)
+}
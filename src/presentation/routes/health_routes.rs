@@ -0,0 +1,15 @@
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+use crate::presentation::handlers::health_handlers::{
+    liveness_check, prometheus_metrics, readiness_check, HealthState,
+};
+
+/// Create health probe and metrics routes (no auth, served at the root)
+pub fn health_routes(state: Arc<HealthState>) -> Router {
+    Router::new()
+        .route("/health/live", get(liveness_check))
+        .route("/health/ready", get(readiness_check))
+        .route("/health/metrics", get(prometheus_metrics))
+        .with_state(state)
+}
@@ -0,0 +1,21 @@
+use axum::{routing::post, Router};
+use std::sync::Arc;
+
+use crate::{
+    application::services::InterviewApplicationService,
+    presentation::handlers::interview_handlers,
+};
+
+/// Create automated interview routes
+pub fn interview_routes(service: Arc<dyn InterviewApplicationService>) -> Router {
+    Router::new()
+        .route(
+            "/agents/{agent_id}/interview/questions",
+            post(interview_handlers::generate_interview_questions),
+        )
+        .route(
+            "/interviews/{record_id}/turns",
+            post(interview_handlers::conduct_interview_turn),
+        )
+        .with_state(service)
+}
@@ -0,0 +1,26 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::{
+    application::services::MarketplaceApplicationService,
+    presentation::handlers::marketplace_handlers,
+};
+
+/// Create agent marketplace routes
+pub fn marketplace_routes(service: Arc<dyn MarketplaceApplicationService>) -> Router {
+    Router::new()
+        .route("/v1/marketplace/agents", get(marketplace_handlers::list_marketplace_agents))
+        .route(
+            "/v1/marketplace/agents/search",
+            get(marketplace_handlers::search_marketplace_agents),
+        )
+        .route("/v1/marketplace/agents/{agent_id}", get(marketplace_handlers::get_marketplace_agent))
+        .route(
+            "/v1/marketplace/agents/{agent_id}/purchase",
+            post(marketplace_handlers::purchase_marketplace_agent),
+        )
+        .with_state(service)
+}
@@ -45,6 +45,11 @@ pub fn create_mcp_api_routes(
 ) -> Router {
     Router::new()
         .nest("/mcp", create_mcp_routes())
+        .route("/v1/mcp-tools/{tool_id}/versions", get(list_tool_versions))
+        .route(
+            "/v1/mcp-tools/{tool_id}/versions/{version}/rollback",
+            post(rollback_to_version),
+        )
         .with_state(mcp_service)
 }
 
@@ -1,4 +1,5 @@
 use axum::{
+    middleware,
     routing::{get, post},
     Router,
 };
@@ -7,8 +8,12 @@ use std::sync::Arc;
 use rmcp::transport::StreamableHttpService;
 
 use crate::{
+    application::services::APIKeyApplicationService,
     infrastructure::mcp::mcp_server_handler::MCPServerHandler,
-    presentation::handlers::{mcp_server_handlers, Counter},
+    presentation::{
+        handlers::{mcp_server_handlers, mcp_server_handlers::TenantMCPState, Counter},
+        middleware::api_key_auth_middleware,
+    },
 };
 
 /// 创建MCP Server协议路由
@@ -28,6 +33,25 @@ pub fn create_mcp_server_api_routes(
         .nest_service("/mcp", streamable_http_service)
 }
 
+/// 创建租户MCP Server路由 (SSE transport)
+/// 使用 `pk_` 前缀的API Key认证
+pub fn create_tenant_mcp_routes(
+    state: TenantMCPState,
+    api_key_service: Arc<APIKeyApplicationService>,
+) -> Router {
+    Router::new()
+        .route("/mcp/{tenant_slug}/sse", get(mcp_server_handlers::tenant_mcp_sse))
+        .route(
+            "/mcp/{tenant_slug}/messages",
+            post(mcp_server_handlers::tenant_mcp_message),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            api_key_service,
+            api_key_auth_middleware,
+        ))
+        .with_state(state)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,6 +194,18 @@ mod tests {
             ))
         }
 
+        async fn forward_tool_call(
+            &self,
+            _tool: &MCPTool,
+            _parameters: Value,
+            _context: ToolCallContext,
+        ) -> Result<ToolCallResult, PlatformError> {
+            Ok(ToolCallResult::success(
+                serde_json::json!({"result": "success"}),
+                100,
+            ))
+        }
+
         async fn handle_mcp_request(
             &self,
             _request: crate::infrastructure::mcp::protocol_handler::MCPRequest,
@@ -0,0 +1,24 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::{
+    application::services::MessageApplicationService,
+    presentation::handlers::message_handlers,
+};
+
+/// Create message editing and branch routes
+pub fn message_routes(service: Arc<MessageApplicationService>) -> Router {
+    Router::new()
+        .route(
+            "/v1/sessions/{session_id}/messages/{message_id}",
+            put(message_handlers::edit_message),
+        )
+        .route(
+            "/v1/sessions/{session_id}/messages/{message_id}/branches",
+            get(message_handlers::list_message_branches),
+        )
+        .with_state(service)
+}
@@ -10,18 +10,32 @@ pub mod vector_storage_routes;
 pub mod file_routes;
 pub mod api_key_routes;
 pub mod dashboard_routes;
+pub mod marketplace_routes;
+pub mod interview_routes;
+pub mod message_routes;
+pub mod health_routes;
+pub mod tenant_routes;
+pub mod user_routes;
 
 pub use auth_routes::*;
 
 // Re-export route creation functions
-pub use agent_routes::agent_routes;
+pub use agent_routes::{agent_import_export_routes, agent_routes, api_key_agent_routes};
 pub use config_routes::{llm_config_routes, vector_config_routes};
-pub use flow_routes::flow_routes;
+pub use flow_routes::{async_execution_routes, batch_execution_routes, flow_import_export_routes, flow_routes};
 pub use mcp_routes::create_mcp_api_routes;
-pub use mcp_server_routes::create_mcp_server_api_routes;
-pub use session_audit_routes::{audit_routes, execution_history_routes, session_routes};
+pub use mcp_server_routes::{create_mcp_server_api_routes, create_tenant_mcp_routes};
+pub use session_audit_routes::{
+    admin_audit_routes, admin_session_routes, audit_routes, execution_history_routes, session_routes,
+};
 pub use vector_config_routes::create_vector_config_routes;
 pub use vector_storage_routes::create_vector_storage_routes;
 pub use file_routes::file_routes;
 pub use api_key_routes::api_key_routes;
 pub use dashboard_routes::dashboard_routes;
+pub use marketplace_routes::marketplace_routes;
+pub use interview_routes::interview_routes;
+pub use message_routes::message_routes;
+pub use health_routes::health_routes;
+pub use tenant_routes::{admin_tenant_routes, secret_routes};
+pub use user_routes::user_routes;
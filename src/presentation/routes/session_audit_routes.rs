@@ -1,4 +1,5 @@
 use axum::{
+    middleware,
     routing::{delete, get, post, put},
     Router,
 };
@@ -6,7 +7,10 @@ use std::sync::Arc;
 
 use crate::{
     application::services::{SessionApplicationService, AuditApplicationService, ExecutionHistoryApplicationService},
-    presentation::handlers::session_audit_handlers,
+    presentation::{
+        handlers::{execution_history_handlers, session_audit_handlers},
+        middleware::{require_admin, AdminPolicy},
+    },
 };
 
 pub fn session_routes(service: Arc<SessionApplicationService>) -> Router {
@@ -16,10 +20,22 @@ pub fn session_routes(service: Arc<SessionApplicationService>) -> Router {
         .route("/sessions/{session_id}", get(session_audit_handlers::get_session))
         .route("/sessions/{session_id}", put(session_audit_handlers::update_session))
         .route("/sessions/{session_id}", delete(session_audit_handlers::delete_session))
+        .route("/v1/sessions/{session_id}", delete(session_audit_handlers::delete_session))
         .route("/sessions/{session_id}/messages", get(session_audit_handlers::get_session_messages))
         .route("/sessions/{session_id}/messages", post(session_audit_handlers::add_message))
         .route("/sessions/{session_id}/context", post(session_audit_handlers::set_context))
         .route("/sessions/{session_id}/context/{key}", get(session_audit_handlers::get_context))
+        .route("/sessions/{session_id}/summarize", post(session_audit_handlers::summarize_session))
+        .route("/sessions/{session_id}/export", get(session_audit_handlers::export_session))
+        .route("/v1/sessions/{session_id}/export", get(session_audit_handlers::export_session))
+        .with_state(service)
+}
+
+/// Admin-only session purge; merge inside the authenticated router
+pub fn admin_session_routes(service: Arc<SessionApplicationService>, admin_policy: Arc<AdminPolicy>) -> Router {
+    Router::new()
+        .route("/admin/tenants/{tenant_id}/sessions", delete(session_audit_handlers::purge_old_sessions))
+        .route_layer(middleware::from_fn_with_state(admin_policy, require_admin))
         .with_state(service)
 }
 
@@ -30,9 +46,17 @@ pub fn audit_routes(service: Arc<AuditApplicationService>) -> Router {
         .with_state(service)
 }
 
+/// Admin-only audit search; merge inside the authenticated router
+pub fn admin_audit_routes(service: Arc<AuditApplicationService>, admin_policy: Arc<AdminPolicy>) -> Router {
+    Router::new()
+        .route("/audit-logs", get(session_audit_handlers::search_audit_logs))
+        .route_layer(middleware::from_fn_with_state(admin_policy, require_admin))
+        .with_state(service)
+}
+
 pub fn execution_history_routes(service: Arc<ExecutionHistoryApplicationService>) -> Router {
     Router::new()
-        .route("/execution-history", get(session_audit_handlers::query_executions))
-        .route("/execution-history/{execution_id}", get(session_audit_handlers::get_execution_details))
+        .route("/execution-history", get(execution_history_handlers::list_executions))
+        .route("/execution-history/{execution_id}", get(execution_history_handlers::get_execution_detail))
         .with_state(service)
 }
@@ -0,0 +1,41 @@
+use axum::{
+    middleware,
+    routing::{get, put},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::{
+    application::services::TenantApplicationService,
+    presentation::{
+        handlers::tenant_handlers,
+        middleware::{require_admin, AdminPolicy},
+    },
+};
+
+/// Admin-only tenant quota and secret management; merge inside the authenticated router
+pub fn admin_tenant_routes(
+    service: Arc<dyn TenantApplicationService>,
+    admin_policy: Arc<AdminPolicy>,
+) -> Router {
+    Router::new()
+        .route("/admin/tenants/{tenant_id}/quota", put(tenant_handlers::set_tenant_quota))
+        .route("/admin/tenants/{tenant_id}/usage", get(tenant_handlers::get_tenant_usage))
+        .route("/admin/tenants/{tenant_id}/secrets", get(tenant_handlers::list_tenant_secrets))
+        .route(
+            "/admin/tenants/{tenant_id}/secrets/{name}",
+            put(tenant_handlers::set_tenant_secret).delete(tenant_handlers::delete_tenant_secret),
+        )
+        .route_layer(middleware::from_fn_with_state(admin_policy, require_admin))
+        .with_state(service)
+}
+
+/// Secrets of the caller's tenant; merge inside the authenticated router
+pub fn secret_routes(service: Arc<dyn TenantApplicationService>) -> Router {
+    Router::new()
+        .route(
+            "/v1/secrets/{key}",
+            put(tenant_handlers::set_secret).delete(tenant_handlers::delete_secret),
+        )
+        .with_state(service)
+}
@@ -0,0 +1,21 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use std::sync::Arc;
+
+use crate::{
+    application::services::UserApplicationService,
+    presentation::handlers::user_handlers,
+};
+
+/// Profile of the signed-in user; merge inside the authenticated router
+pub fn user_routes(service: Arc<dyn UserApplicationService>) -> Router {
+    Router::new()
+        .route(
+            "/v1/users/me",
+            get(user_handlers::get_me).patch(user_handlers::update_me),
+        )
+        .route("/v1/users/me/avatar", post(user_handlers::upload_avatar))
+        .with_state(service)
+}
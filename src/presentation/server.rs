@@ -1,25 +1,40 @@
 use crate::{
     application::services::*,
     config::AppConfig,
-    domain::{repositories::FileRepository, services::*},
+    domain::{
+        events::{BroadcastEventBus, DomainEvent, EventStore},
+        repositories::{AgentAllocationRepository, APIKeyRepository, FileRepository},
+        services::*,
+    },
     error::Result,
     infrastructure::{
-        llm::LLMProviderRegistry, mcp::MCPProxyServiceImpl, repositories::*,
-        vector::VectorStoreRegistry, Database, RedisCache,
+        llm::{EmbeddingProviderFactory, LLMProviderRegistry}, mcp::{MCPProxyServiceImpl, RMCPServerConfig, TenantMCPSessions}, repositories::*,
+        database::QueryOptimizer, vector::VectorStoreRegistry, Database, RedisCache,
     },
     presentation::{
-        middleware::auth_middleware,
+        middleware::{
+            auth_middleware, rate_limit_middleware, request_metrics_middleware, require_admin,
+            AdminPolicy, RateLimiter, RequestMetrics,
+        },
         routes::{
-            agent_routes, api_key_routes, audit_routes, create_app_router, create_mcp_api_routes,
-            create_mcp_server_api_routes, dashboard_routes,
-            execution_history_routes, file_routes, flow_routes, llm_config_routes, session_routes,
+            admin_audit_routes, admin_session_routes, admin_tenant_routes, agent_import_export_routes, agent_routes,
+            api_key_agent_routes, api_key_routes, async_execution_routes, audit_routes,
+            batch_execution_routes,
+            create_app_router, create_mcp_api_routes,
+            create_mcp_server_api_routes, create_tenant_mcp_routes, dashboard_routes,
+            execution_history_routes, file_routes, flow_import_export_routes, flow_routes, health_routes,
+            interview_routes,
+            llm_config_routes,
+            marketplace_routes, message_routes, secret_routes, session_routes, user_routes,
             vector_config_routes,
         },
-        handlers::Counter,
+        handlers::{db_stats, mcp_server_handlers::TenantMCPState, Counter, HealthState},
+        shutdown::{in_flight_middleware, GracefulShutdown},
     },
 };
-use axum::{middleware, Router};
+use axum::{middleware, routing::get, Router};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tower_http::{
     cors::{Any, CorsLayer},
     services::fs::ServeDir,
@@ -27,48 +42,315 @@ use tower_http::{
 use rmcp::transport::StreamableHttpService;
 use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 
+/// How often expired agent allocations are removed
+const ALLOCATION_CLEANUP_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How often API key usage counted in Redis is written to the database
+const API_KEY_USAGE_FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often audit logs past the retention period are deleted
+const AUDIT_LOG_RETENTION_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+/// How often expired records are deleted from vector stores without native TTL
+const VECTOR_TTL_REAPER_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Longest shutdown waits for queued audit logs to be written
+const AUDIT_LOG_FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct Server {
     config: AppConfig,
     database: Arc<Database>,
-    #[allow(dead_code)]
     cache: Arc<RedisCache>,
+    shutdown: Arc<GracefulShutdown>,
+    secret_store: Arc<SecretStoreImpl>,
+    /// Bus the app publishes flow execution events on
+    event_bus: Arc<BroadcastEventBus>,
+    /// Writer of the audit repository the app uses, set by `create_app`
+    audit_writer: std::sync::OnceLock<Arc<AuditLogWriter>>,
 }
 
 impl Server {
-    pub fn new(config: AppConfig, database: Arc<Database>, cache: Arc<RedisCache>) -> Self {
-        Self {
+    pub fn new(
+        config: AppConfig,
+        database: Arc<Database>,
+        cache: Arc<RedisCache>,
+    ) -> Result<Self> {
+        let secret_store =
+            Arc::new(SecretStoreImpl::new(database.connection(), &config.jwt_secret)?);
+
+        Ok(Self {
             config,
             database,
             cache,
-        }
+            shutdown: Arc::new(GracefulShutdown::new()),
+            secret_store,
+            event_bus: Arc::new(BroadcastEventBus::new()),
+            audit_writer: std::sync::OnceLock::new(),
+        })
     }
 
     pub async fn start(self) -> Result<()> {
         let app = self.create_app();
 
+        match self.secret_store.import_legacy_secrets().await {
+            Ok(0) => {}
+            Ok(imported) => tracing::info!("Encrypted {} legacy tenant secrets", imported),
+            Err(e) => tracing::error!("Failed to import legacy tenant secrets: {}", e),
+        }
+
+        let execution_repository = FlowExecutionRepositoryImpl::new(self.database.connection());
+        match AsyncFlowExecutor::fail_orphaned_executions(&execution_repository).await {
+            Ok(0) => {}
+            Ok(failed) => tracing::warn!("Marked {} orphaned flow executions as failed", failed),
+            Err(e) => tracing::error!("Failed to clean up orphaned flow executions: {}", e),
+        }
+
+        Self::spawn_domain_event_recorder(
+            self.event_bus.subscribe(),
+            Arc::new(EventStoreImpl::new(self.database.connection())),
+        );
+        Self::spawn_allocation_cleanup(Arc::new(AgentAllocationRepositoryImpl::new(
+            self.database.connection(),
+        )));
+        Self::spawn_api_key_usage_flush(
+            self.cache.clone(),
+            Arc::new(APIKeyRepositoryImpl::new(self.database.connection())),
+        );
+        if let Some(days) = self.config.audit_log_retention_days {
+            tracing::info!("Audit logs are kept for {} days", days);
+            Self::spawn_audit_log_retention(Arc::new(
+                AuditLogRepositoryImpl::new(self.database.connection())
+                    .with_retention_policy(Some(chrono::Days::new(days))),
+            ));
+        }
+        Self::spawn_vector_ttl_reaper(Arc::new(VectorTTLReaper::new(
+            Arc::new(VectorRecordMetadataRepositoryImpl::new(self.database.connection())),
+            Arc::new(VectorApplicationService::new(Arc::new(
+                VectorConfigRepositoryImpl::new(self.database.connection()),
+            ))),
+        )));
+
+        if self.config.rate_limit.enabled {
+            tracing::info!(
+                "API rate limit: {} requests/minute per tenant and endpoint, burst {}",
+                self.config.rate_limit.requests_per_minute,
+                self.config.rate_limit.burst_size
+            );
+        }
+
         let addr = format!("{}:{}", self.config.server.host, self.config.server.port);
-        log::info!("Starting server on {}", addr);
+        tracing::info!("Starting server on {}", addr);
 
         let listener = tokio::net::TcpListener::bind(&addr).await.map_err(|e| {
             crate::error::PlatformError::InternalError(format!("Failed to bind to {}: {}", addr, e))
         })?;
 
-        axum::serve(listener, app).await.map_err(|e| {
-            crate::error::PlatformError::InternalError(format!("Server error: {}", e))
-        })?;
+        // On SIGTERM or Ctrl-C the listener closes and open connections
+        // finish their requests, up to the drain timeout
+        let drain_timeout = Duration::from_secs(self.config.server.shutdown_drain_timeout_secs);
+        let server = axum::serve(listener, app).with_graceful_shutdown(self.shutdown.clone().signal());
+        tokio::select! {
+            result = server => {
+                result.map_err(|e| {
+                    crate::error::PlatformError::InternalError(format!("Server error: {}", e))
+                })?;
+            }
+            drained = self.shutdown.drain(drain_timeout) => {
+                if !drained {
+                    tracing::warn!(
+                        "Drain timeout of {}s elapsed with {} requests still in flight",
+                        drain_timeout.as_secs(),
+                        self.shutdown.in_flight()
+                    );
+                }
+            }
+        }
+
+        self.shutdown_resources().await;
+        tracing::info!("Server stopped");
 
         Ok(())
     }
 
+    /// Flush buffered state and release connections once requests have
+    /// drained: audit logs still queued for the background writer and API
+    /// key usage counted in Redis.
+    async fn shutdown_resources(&self) {
+        if let Some(writer) = self.audit_writer.get() {
+            if !writer.close(AUDIT_LOG_FLUSH_TIMEOUT).await {
+                tracing::error!(
+                    "Queued audit logs were not written within {}s",
+                    AUDIT_LOG_FLUSH_TIMEOUT.as_secs()
+                );
+            }
+        }
+
+        let api_key_repository = APIKeyRepositoryImpl::new(self.database.connection());
+        match APIKeyUsageCounter::drain(self.cache.as_ref()).await {
+            Ok(usage) if !usage.is_empty() => {
+                if let Err(e) = api_key_repository.record_usage(&usage).await {
+                    tracing::error!("Failed to store usage of {} API key days: {}", usage.len(), e);
+                    // Left in Redis for the next start
+                    if let Err(e) = APIKeyUsageCounter::restore(self.cache.as_ref(), &usage).await {
+                        tracing::error!("Failed to restore usage of {} API key days: {}", usage.len(), e);
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to read API key usage: {}", e),
+        }
+
+        if let Err(e) = self.database.close().await {
+            tracing::error!("Failed to close database connections: {}", e);
+        }
+    }
+
+    /// Append events published on the bus to the event store, where they keep
+    /// their correlation ID for lookups from the audit log
+    fn spawn_domain_event_recorder(
+        mut receiver: tokio::sync::broadcast::Receiver<Arc<dyn DomainEvent>>,
+        event_store: Arc<dyn EventStore>,
+    ) {
+        tokio::spawn(async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::error!(
+                            "Domain event recorder fell behind, {} events not stored",
+                            skipped
+                        );
+                        continue;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+
+                if let Err(e) = event_store.append(event.as_ref()).await {
+                    tracing::error!(
+                        "Failed to store {} event {}: {}",
+                        event.event_type(),
+                        event.event_id(),
+                        e
+                    );
+                }
+            }
+        });
+    }
+
+    /// Periodically delete agent allocations past their expiry. Expired
+    /// allocations are already ignored by lookups; this only keeps the table small.
+    fn spawn_allocation_cleanup(allocation_repository: Arc<dyn AgentAllocationRepository>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(ALLOCATION_CLEANUP_INTERVAL);
+
+            loop {
+                ticker.tick().await;
+
+                match allocation_repository.delete_expired(chrono::Utc::now()).await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Removed {} expired agent allocations", count);
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!("Failed to remove expired agent allocations: {}", e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Periodically move API key usage counted in Redis into the database
+    fn spawn_api_key_usage_flush(
+        usage_counter: Arc<dyn APIKeyUsageCounter>,
+        api_key_repository: Arc<dyn APIKeyRepository>,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(API_KEY_USAGE_FLUSH_INTERVAL);
+
+            loop {
+                ticker.tick().await;
+
+                let usage = match usage_counter.drain().await {
+                    Ok(usage) => usage,
+                    Err(e) => {
+                        tracing::error!("Failed to read API key usage: {}", e);
+                        continue;
+                    }
+                };
+                if usage.is_empty() {
+                    continue;
+                }
+
+                if let Err(e) = api_key_repository.record_usage(&usage).await {
+                    tracing::error!("Failed to store usage of {} API key days: {}", usage.len(), e);
+                    // Counted again on the next tick instead of being lost
+                    if let Err(e) = usage_counter.restore(&usage).await {
+                        tracing::error!("Failed to restore usage of {} API key days: {}", usage.len(), e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Delete audit logs past the retention period once a day
+    fn spawn_audit_log_retention(audit_repository: Arc<AuditLogRepositoryImpl>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(AUDIT_LOG_RETENTION_INTERVAL);
+
+            loop {
+                ticker.tick().await;
+
+                match audit_repository.apply_retention_policy().await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Removed {} audit logs past the retention period", count);
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!("Failed to remove old audit logs: {}", e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Periodically delete expired records from vector stores that cannot
+    /// expire them themselves
+    fn spawn_vector_ttl_reaper(reaper: Arc<VectorTTLReaper>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(VECTOR_TTL_REAPER_INTERVAL);
+
+            loop {
+                ticker.tick().await;
+
+                match reaper.reap(chrono::Utc::now()).await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Removed {} expired vector records", count);
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!("Failed to remove expired vector records: {}", e);
+                    }
+                }
+            }
+        });
+    }
+
     pub fn create_app(&self) -> Router {
+        let query_optimizer = Arc::new(QueryOptimizer::new(self.database.connection()));
+
         // Create repositories
         let user_repository = Arc::new(UserRepositoryImpl::new(self.database.connection()));
         let tenant_repository = Arc::new(TenantRepositoryImpl::new(self.database.connection()));
-        let flow_repository = Arc::new(FlowRepositoryImpl::new(self.database.connection()));
+        let flow_repository = Arc::new(
+            FlowRepositoryImpl::new(self.database.connection())
+                .with_query_optimizer(query_optimizer.clone()),
+        );
         let flow_version_repository =
             Arc::new(FlowVersionRepositoryImpl::new(self.database.connection()));
         let flow_execution_repository =
             Arc::new(FlowExecutionRepositoryImpl::new(self.database.connection()));
+        let flow_node_annotation_repository =
+            Arc::new(FlowNodeAnnotationRepositoryImpl::new(self.database.connection()));
         let llm_config_repository =
             Arc::new(LLMConfigRepositoryImpl::new(self.database.connection()));
         let vector_config_repository =
@@ -81,18 +363,34 @@ impl Server {
             Arc::new(ChatSessionRepositoryImpl::new(self.database.connection()));
         let message_repository = Arc::new(MessageRepositoryImpl::new(self.database.connection()));
         let audit_repository = Arc::new(AuditLogRepositoryImpl::new(self.database.connection()));
+        let _ = self.audit_writer.set(audit_repository.writer());
         let execution_history_repository = Arc::new(ExecutionHistoryRepositoryImpl::new(
             self.database.connection(),
         ));
-        let agent_repository = Arc::new(AgentRepositoryImpl::new(self.database.connection()));
+        let agent_repository = Arc::new(
+            AgentRepositoryImpl::new(self.database.connection())
+                .with_query_optimizer(query_optimizer.clone()),
+        );
         let agent_allocation_repository = Arc::new(AgentAllocationRepositoryImpl::new(
             self.database.connection(),
         ));
         let api_key_repository = Arc::new(APIKeyRepositoryImpl::new(self.database.connection()));
+        let refresh_token_repository =
+            Arc::new(RefreshTokenRepositoryImpl::new(self.database.connection()));
+        let tenant_settings_repository =
+            Arc::new(TenantSettingsRepositoryImpl::new(self.database.connection()));
+        let llm_usage_log_repository =
+            Arc::new(LlmUsageLogRepositoryImpl::new(self.database.connection()));
+        let event_store: Arc<dyn EventStore> =
+            Arc::new(EventStoreImpl::new(self.database.connection()));
+        let secret_store: Arc<dyn SecretStore> = self.secret_store.clone();
 
         let vector_store_registry = Arc::new(VectorStoreRegistry::new());
         let llm_provider_registry = Arc::new(LLMProviderRegistry::new());
-        let mcp_proxy_service = Arc::new(MCPProxyServiceImpl::new());
+        let mcp_proxy_service = Arc::new(
+            MCPProxyServiceImpl::new()
+                .with_circuit_breaker_config(self.config.mcp_circuit_breaker.clone()),
+        );
 
         // Create domain services
         let auth_domain_service = Arc::new(AuthenticationDomainServiceImpl::new(
@@ -118,37 +416,71 @@ impl Server {
         let execution_engine = ExecutionEngineFactory::create_with_services(
             llm_domain_service.clone(),
             llm_config_repository.clone(),
+            vector_config_repository.clone(),
             vector_store_domain_service.clone(),
             mcp_domain_service.clone(),
             mcp_tool_repository.clone(),
+            mcp_proxy_service.clone(),
+            EmbeddingProviderFactory::create_from_env(),
+            Some(secret_store.clone()),
+            Some(self.cache.clone()),
         );
 
         // Create application services
+        let audit_service = Arc::new(
+            AuditApplicationService::new(audit_domain_service).with_event_store(event_store.clone()),
+        );
+
         let auth_service: Arc<dyn AuthApplicationService> =
             Arc::new(AuthApplicationServiceImpl::new(
                 user_repository.clone(),
-                tenant_repository,
+                tenant_repository.clone(),
                 auth_domain_service,
                 None, // Use default token expiry
-            ));
+            )
+            .with_refresh_tokens(refresh_token_repository, self.config.refresh_tokens.clone()));
+
+        let vector_service = Arc::new(VectorApplicationService::new(
+            vector_config_repository.clone(),
+        ));
+
+        let tenant_service: Arc<dyn TenantApplicationService> =
+            Arc::new(TenantApplicationServiceImpl::new(
+                tenant_repository.clone(),
+                tenant_settings_repository.clone(),
+                agent_repository.clone(),
+                flow_repository.clone(),
+                mcp_tool_repository.clone(),
+                llm_usage_log_repository.clone(),
+            )
+            .with_vector_service(vector_service.clone())
+            .with_secret_store(secret_store));
 
         let flow_service: Arc<dyn FlowApplicationService> =
             Arc::new(FlowApplicationServiceImpl::new(
                 flow_repository.clone(),
-                flow_version_repository,
+                flow_version_repository.clone(),
                 flow_execution_repository,
-                flow_domain_service,
+                flow_domain_service.clone(),
                 Some(execution_engine),
+            )
+            .with_event_bus(self.event_bus.clone())
+            .with_event_store(event_store.clone())
+            .with_annotation_repository(flow_node_annotation_repository)
+            .with_audit_service(audit_service.clone())
+            .with_quota_service(tenant_service.clone()));
+
+        let flow_import_export_service: Arc<dyn FlowImportExportService> =
+            Arc::new(FlowImportExportServiceImpl::new(
+                flow_service.clone(),
+                flow_version_repository,
+                flow_domain_service,
             ));
 
         let llm_service: Arc<dyn LLMApplicationService> = Arc::new(LLMApplicationServiceImpl::new(
             llm_config_repository.clone(),
             llm_domain_service.clone(),
-            llm_provider_registry,
-        ));
-
-        let vector_service = Arc::new(VectorApplicationService::new(
-            vector_config_repository.clone(),
+            llm_provider_registry.clone(),
         ));
 
         // let vector_storage_service = Arc::new(VectorStorageApplicationService::new(
@@ -156,19 +488,29 @@ impl Server {
         //     vector_store_registry,
         // ));
 
-        let mcp_service: Arc<dyn MCPApplicationService> = Arc::new(MCPApplicationServiceImpl::new(
+        // Shared with the MCP server service so each tool endpoint has one circuit breaker
+        let mcp_server_service: Arc<dyn MCPServerApplicationService> = Arc::new(MCPServerApplicationServiceImpl::new(
             mcp_tool_repository.clone(),
-            mcp_version_repository,
-            mcp_domain_service,
-            mcp_proxy_service,
+            mcp_proxy_service.clone(),
         ));
 
-        let mcp_proxy_service = Arc::new(MCPProxyServiceImpl::new());
+        let tenant_mcp_state = TenantMCPState {
+            sessions: Arc::new(TenantMCPSessions::new(
+                mcp_server_service.clone(),
+                RMCPServerConfig::default(),
+            )),
+            tenant_repository,
+        };
 
-        let mcp_server_service: Arc<dyn MCPServerApplicationService> = Arc::new(MCPServerApplicationServiceImpl::new(
+        let mcp_service: Arc<dyn MCPApplicationService> = Arc::new(MCPApplicationServiceImpl::new(
             mcp_tool_repository.clone(),
-            mcp_proxy_service,
-        ));
+            mcp_version_repository,
+            mcp_domain_service,
+            mcp_proxy_service.clone(),
+        )
+        .with_mcp_server_service(mcp_server_service.clone())
+        .with_quota_service(tenant_service.clone())
+        .with_version_retention(self.config.mcp_tool_version_retention));
 
         let streamable_http_service = StreamableHttpService::new(
             || Ok(Counter::new()),
@@ -176,26 +518,78 @@ impl Server {
             Default::default(),
         );
 
-        let session_service = Arc::new(SessionApplicationService::new(
-            session_repository.clone(),
-            message_repository.clone(),
-            session_domain_service.clone(),
-        ));
+        // File repository (using OSS), shared by sessions, files and avatars
+        let file_repository: Arc<dyn FileRepository> = Arc::new(
+            OssFileRepositoryImpl::new(self.config.oss.clone())
+                .expect("Failed to initialize OSS client")
+        );
 
-        let audit_service = Arc::new(AuditApplicationService::new(audit_domain_service));
+        let session_service = Arc::new(
+            SessionApplicationService::new(
+                session_repository.clone(),
+                message_repository.clone(),
+                session_domain_service.clone(),
+            )
+            .with_llm_service(llm_domain_service.clone())
+            .with_llm_config_repo(llm_config_repository.clone())
+            .with_summary_threshold(self.config.session_summary_threshold)
+            .with_event_store(event_store.clone())
+            .with_audit_service(audit_service.clone())
+            .with_agent_repository(agent_repository.clone())
+            .with_file_repository(file_repository.clone()),
+        );
 
-        let api_key_service = Arc::new(APIKeyApplicationService::new(
-            api_key_domain_service,
-            api_key_repository,
-            audit_service.clone(),
+        let context_service = Arc::new(
+            ContextManagementService::new(
+                session_repository.clone(),
+                message_repository.clone(),
+                session_domain_service.clone(),
+            )
+            .with_llm_service(llm_domain_service.clone()),
+        );
+
+        let message_service = Arc::new(
+            MessageApplicationService::new(
+                session_repository.clone(),
+                message_repository.clone(),
+                session_domain_service.clone(),
+            )
+            .with_branch_repository(Arc::new(MessageBranchRepositoryImpl::new(
+                self.database.connection(),
+            )))
+            .with_agent_repository(agent_repository.clone())
+            .with_llm(
+                llm_domain_service.clone(),
+                Arc::new(LLMConfigSelector::new(llm_config_repository.clone())),
+            ),
+        );
+
+        let api_key_service = Arc::new(
+            APIKeyApplicationService::new(
+                api_key_domain_service,
+                api_key_repository.clone(),
+                audit_service.clone(),
+            )
+            .with_usage_counter(self.cache.clone()),
+        );
+
+        let batch_flow_executor = Arc::new(BatchFlowExecutor::new(
+            flow_service.clone(),
+            execution_history_repository.clone(),
         ));
 
+        let async_flow_executor = Arc::new(
+            AsyncFlowExecutor::new(flow_service.clone())
+                .with_callback_secret(&self.config.flow_callback_secret),
+        );
+
         let execution_history_service = Arc::new(ExecutionHistoryServiceImpl::new(
             execution_history_repository,
         ));
 
         let execution_history_application_service = Arc::new(
-            ExecutionHistoryApplicationService::new(execution_history_service),
+            ExecutionHistoryApplicationService::new(execution_history_service)
+                .with_agent_repository(agent_repository.clone()),
         );
 
         // Create agent daily stats repository and service
@@ -218,18 +612,42 @@ impl Server {
                 interview_record_repository.clone(),
             )
             .with_session_service(session_service.clone())
+            .with_context_service(context_service)
             .with_llm_service(llm_domain_service.clone())
             .with_llm_config_repo(llm_config_repository.clone())
             .with_db(self.database.connection())
+            .with_stats_service(agent_stats_service.clone())
+            .with_flow_service(flow_service.clone())
+            .with_event_store(event_store)
+            .with_usage_log_repository(llm_usage_log_repository)
+            .with_audit_service(audit_service.clone())
+            .with_agent_limits(self.config.agent_limits)
+            .with_tenant_settings_repository(tenant_settings_repository)
+            .with_quota_service(tenant_service.clone())
+            .with_api_key_repository(api_key_repository)
+            .with_stream_buffer(self.cache.clone()));
+
+        let interview_service: Arc<dyn InterviewApplicationService> =
+            Arc::new(InterviewApplicationServiceImpl::new(
+                agent_repository.clone(),
+                interview_record_repository.clone(),
+                llm_domain_service.clone(),
+                Arc::new(LLMConfigSelector::new(llm_config_repository.clone())),
+            )
             .with_stats_service(agent_stats_service));
 
-        // Create file repository and service (using OSS)
-        let file_repository: Arc<dyn FileRepository> = Arc::new(
-            OssFileRepositoryImpl::new(self.config.oss.clone())
-                .expect("Failed to initialize OSS client")
-        );
+        // Create file service
         let file_service: Arc<dyn FileApplicationService> =
-            Arc::new(FileApplicationServiceImpl::new(file_repository));
+            Arc::new(FileApplicationServiceImpl::new(file_repository.clone()));
+
+        let user_service: Arc<dyn UserApplicationService> = Arc::new(
+            UserApplicationServiceImpl::new(user_repository.clone(), file_repository),
+        );
+
+        let agent_import_export_service: Arc<dyn AgentImportExportService> = Arc::new(
+            AgentImportExportServiceImpl::new(agent_repository.clone(), agent_service.clone())
+                .with_file_service(file_service.clone()),
+        );
 
         // Create dashboard service
         let dashboard_service: Arc<dyn DashboardApplicationService> =
@@ -241,9 +659,46 @@ impl Server {
                 session_repository.clone(),
             ));
 
+        let marketplace_service: Arc<dyn MarketplaceApplicationService> =
+            Arc::new(MarketplaceApplicationServiceImpl::new(
+                agent_repository.clone(),
+                user_repository.clone(),
+                agent_service.clone(),
+                audit_service.clone(),
+            ));
+
         // Configure CORS
         let cors = self.create_cors_layer();
 
+        let rate_limiter = Arc::new(RateLimiter::new(
+            self.cache.clone(),
+            self.config.rate_limit.clone(),
+        ));
+
+        let admin_policy = Arc::new(AdminPolicy::new(self.config.admin_user_ids.clone()));
+
+        let request_metrics = Arc::new(RequestMetrics::new());
+
+        let health_state = Arc::new(HealthState {
+            db: self.database.connection(),
+            cache: self.cache.clone(),
+            llm_providers: llm_provider_registry,
+            mcp_proxy: mcp_proxy_service,
+            request_metrics: request_metrics.clone(),
+            shutdown: self.shutdown.clone(),
+            start_time: Instant::now(),
+        });
+
+        // Operational endpoints outside the public API, for admins only
+        let internal_routes = Router::new()
+            .route("/internal/db-stats", get(db_stats))
+            .route_layer(middleware::from_fn_with_state(admin_policy.clone(), require_admin))
+            .route_layer(middleware::from_fn_with_state(
+                auth_service.clone(),
+                auth_middleware,
+            ))
+            .with_state(query_optimizer);
+
         // Create application router with all routes
         let app = Router::new()
             // Auth routes (includes /api/health and /api/auth/*)
@@ -253,15 +708,27 @@ impl Server {
                 "/api",
                 Router::new()
                     // Agent management routes
-                    .merge(agent_routes(agent_service))
+                    .merge(agent_routes(agent_service.clone()))
+                    .merge(agent_import_export_routes(agent_import_export_service))
+                    .merge(interview_routes(interview_service))
+                    // Agent marketplace routes
+                    .merge(marketplace_routes(marketplace_service))
                     // Flow management routes
                     .merge(flow_routes(flow_service))
+                    .merge(flow_import_export_routes(flow_import_export_service))
+                    .merge(batch_execution_routes(batch_flow_executor))
+                    .merge(async_execution_routes(async_flow_executor))
                     // Configuration routes
                     .merge(llm_config_routes(llm_service))
                     .merge(vector_config_routes(vector_service))
                     // Session and audit routes
-                    .merge(session_routes(session_service))
-                    .merge(audit_routes(audit_service))
+                    .merge(session_routes(session_service.clone()))
+                    .merge(admin_session_routes(session_service, admin_policy.clone()))
+                    .merge(message_routes(message_service))
+                    .merge(audit_routes(audit_service.clone()))
+                    .merge(admin_audit_routes(audit_service, admin_policy.clone()))
+                    .merge(secret_routes(tenant_service.clone()))
+                    .merge(admin_tenant_routes(tenant_service, admin_policy))
                     .merge(execution_history_routes(
                         execution_history_application_service,
                     ))
@@ -269,22 +736,44 @@ impl Server {
                     .merge(create_mcp_api_routes(mcp_service))
                     // File upload routes
                     .merge(file_routes(file_service))
+                    // Profile of the signed-in user
+                    .merge(user_routes(user_service))
                     // API key management routes
-                    .merge(api_key_routes(api_key_service))
+                    .merge(api_key_routes(api_key_service.clone()))
                     // Dashboard statistics routes
                     .merge(dashboard_routes(dashboard_service))
+                    // Layered inside auth so the limiter sees the tenant
+                    .route_layer(middleware::from_fn_with_state(
+                        rate_limiter,
+                        rate_limit_middleware,
+                    ))
                     .route_layer(middleware::from_fn_with_state(
                         auth_service.clone(),
                         auth_middleware,
                     ))
+                    // Agent of the calling API key, authenticated with the key itself
+                    .merge(api_key_agent_routes(agent_service, api_key_service.clone()))
                     // MCP server routes
                     .merge(create_mcp_server_api_routes(streamable_http_service)),
             )
+            .merge(internal_routes)
+            // Liveness, readiness and Prometheus metrics
+            .merge(health_routes(health_state))
+            // Tenant-hosted MCP servers, authenticated with API keys
+            .merge(create_tenant_mcp_routes(tenant_mcp_state, api_key_service))
             // Serve uploaded files (no auth required for downloads)
             .nest_service("/files", ServeDir::new("/tmp/uploads"));
 
         // Apply CORS
-        app.layer(cors)
+        app.layer(middleware::from_fn_with_state(
+            request_metrics,
+            request_metrics_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            self.shutdown.clone(),
+            in_flight_middleware,
+        ))
+        .layer(cors)
     }
 
     fn create_cors_layer(&self) -> CorsLayer {
@@ -0,0 +1,203 @@
+// Graceful shutdown: on SIGTERM or Ctrl-C, fail readiness, stop accepting
+// connections and give in-flight requests time to finish
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+
+/// How often the number of remaining requests is logged while draining
+const DRAIN_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shutdown state shared by the server, the readiness probe and the
+/// in-flight request counter
+#[derive(Debug)]
+pub struct GracefulShutdown {
+    draining: watch::Sender<bool>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl GracefulShutdown {
+    pub fn new() -> Self {
+        Self {
+            draining: watch::Sender::new(false),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+        }
+    }
+
+    /// Whether a shutdown signal has been received
+    pub fn is_draining(&self) -> bool {
+        *self.draining.borrow()
+    }
+
+    /// Requests currently being handled, including streaming responses
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Start draining. Readiness fails from here on.
+    pub fn begin_drain(&self) {
+        self.draining.send_replace(true);
+    }
+
+    /// Resolve on the first SIGTERM or Ctrl-C and start draining. Meant for
+    /// `axum::serve(..).with_graceful_shutdown`.
+    pub async fn signal(self: Arc<Self>) {
+        let ctrl_c = async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                tracing::error!("Failed to listen for Ctrl-C: {}", e);
+                std::future::pending::<()>().await;
+            }
+        };
+
+        #[cfg(unix)]
+        let terminate = async {
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(mut signal) => {
+                    signal.recv().await;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to listen for SIGTERM: {}", e);
+                    std::future::pending::<()>().await;
+                }
+            }
+        };
+
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        tokio::select! {
+            _ = ctrl_c => {}
+            _ = terminate => {}
+        }
+
+        tracing::info!("Shutdown signal received, draining {} in-flight requests", self.in_flight());
+        self.begin_drain();
+    }
+
+    /// Once draining starts, wait for in-flight requests to finish. Returns
+    /// false if some were still running when `timeout` elapsed.
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        let mut draining = self.draining.subscribe();
+        if draining.wait_for(|draining| *draining).await.is_err() {
+            return false;
+        }
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let idle = self.idle.notified();
+            tokio::pin!(idle);
+            idle.as_mut().enable();
+
+            let remaining = self.in_flight();
+            if remaining == 0 {
+                return true;
+            }
+
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            tracing::info!("Waiting for {} in-flight requests to finish", remaining);
+
+            tokio::select! {
+                _ = idle => {}
+                _ = tokio::time::sleep((deadline - now).min(DRAIN_LOG_INTERVAL)) => {}
+            }
+        }
+    }
+
+    fn track(self: &Arc<Self>) -> InFlightGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard(self.clone())
+    }
+}
+
+impl Default for GracefulShutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counts a request as in flight until dropped
+struct InFlightGuard(Arc<GracefulShutdown>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+/// Count requests in flight. Server-sent event streams stay counted until
+/// their body ends, since they outlive the handler.
+pub async fn in_flight_middleware(
+    State(shutdown): State<Arc<GracefulShutdown>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let guard = shutdown.track();
+    let response = next.run(req).await;
+
+    let is_event_stream = response
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if !is_event_stream {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = Body::from_stream(body.into_data_stream().map(move |frame| {
+        let _ = &guard;
+        frame
+    }));
+    Response::from_parts(parts, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight_requests() {
+        let shutdown = Arc::new(GracefulShutdown::new());
+        let guard = shutdown.track();
+        assert_eq!(shutdown.in_flight(), 1);
+        assert!(!shutdown.is_draining());
+
+        shutdown.begin_drain();
+        assert!(shutdown.is_draining());
+
+        let drain = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.drain(Duration::from_secs(5)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(guard);
+
+        assert!(drain.await.unwrap());
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_gives_up_after_timeout() {
+        let shutdown = Arc::new(GracefulShutdown::new());
+        let _guard = shutdown.track();
+        shutdown.begin_drain();
+
+        assert!(!shutdown.drain(Duration::from_millis(50)).await);
+        assert_eq!(shutdown.in_flight(), 1);
+    }
+}
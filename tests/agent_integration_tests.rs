@@ -178,7 +178,7 @@ mod tests {
             .await
             .expect("Failed to connect to Redis"));
         
-        let server = Server::new(config, db, cache);
+        let server = Server::new(config, db, cache).expect("Failed to create server");
         server.create_app()
     }
 
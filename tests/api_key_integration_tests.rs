@@ -66,7 +66,8 @@ mod test_helpers {
             let cache = RedisCache::new(&config.redis_url)
                 .await
                 .expect("Failed to load redis");
-            let server = Server::new(config, Arc::new(database), Arc::new(cache));
+            let server = Server::new(config, Arc::new(database), Arc::new(cache))
+                .expect("Failed to create server");
 
             // Create test tenant 1
             let tenant_id = Uuid::new_v4();
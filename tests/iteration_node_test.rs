@@ -136,6 +136,7 @@ async fn test_iteration_node_basic() {
                     },
                 ],
             },
+            environment_variables: Vec::new(),
         },
     };
 